@@ -0,0 +1,140 @@
+//! Watches the imported blocks for Aura slot equivocations and reports them to the runtime.
+//!
+//! GRANDPA double votes are detected by the GRANDPA voter itself, which reports them through the
+//! `GrandpaApi` runtime API. Aura has no such mechanism on the client side, so every imported
+//! header is tracked by its slot and a report is submitted as soon as an authority is found to
+//! have authored two different blocks in the same slot.
+
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
+
+use futures::StreamExt;
+use pallet_bfc_offences_runtime_api::{AuraEquivocationApi, EquivocationProof};
+use parity_scale_codec::{Codec, Decode};
+use sc_client_api::BlockchainEvents;
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_consensus_aura::{AuraApi, Slot, AURA_ENGINE_ID};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	DigestItem,
+};
+
+const LOG_TARGET: &str = "aura-equivocation";
+
+/// The number of slots that the imported headers are kept for.
+const MAX_SLOT_CAPACITY: u64 = 1000;
+
+/// Finds the slot of the given header from its Aura pre-runtime digest.
+fn find_slot<B: BlockT>(header: &B::Header) -> Option<Slot> {
+	header.digest().logs().iter().find_map(|log| match log {
+		DigestItem::PreRuntime(id, data) if *id == AURA_ENGINE_ID => {
+			Slot::decode(&mut &data[..]).ok()
+		},
+		_ => None,
+	})
+}
+
+/// Generates a key ownership proof of the offender and submits the equivocation report as an
+/// unsigned extrinsic on top of the current best block.
+fn report_equivocation<B, C, AuthorityId>(
+	client: &C,
+	offchain_tx_pool_factory: &OffchainTransactionPoolFactory<B>,
+	equivocation_proof: EquivocationProof<B::Header, AuthorityId>,
+) -> Result<(), String>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+	C::Api: AuraEquivocationApi<B, AuthorityId>,
+	AuthorityId: Codec + Clone,
+{
+	let best_hash = client.info().best_hash;
+	let mut runtime_api = client.runtime_api();
+	runtime_api.register_extension(offchain_tx_pool_factory.offchain_transaction_pool(best_hash));
+
+	let key_owner_proof = runtime_api
+		.generate_key_ownership_proof(
+			best_hash,
+			equivocation_proof.slot,
+			equivocation_proof.offender.clone(),
+		)
+		.map_err(|e| e.to_string())?
+		.ok_or("the offender is not a member of the current validator set")?;
+
+	runtime_api
+		.submit_report_equivocation_unsigned_extrinsic(
+			best_hash,
+			equivocation_proof,
+			key_owner_proof,
+		)
+		.map_err(|e| e.to_string())?
+		.ok_or("failed to submit the equivocation report")?;
+
+	Ok(())
+}
+
+/// Runs the Aura equivocation watcher until the block import notification stream ends.
+pub async fn run_aura_equivocation_watcher<B, C, AuthorityId>(
+	client: Arc<C>,
+	offchain_tx_pool_factory: OffchainTransactionPoolFactory<B>,
+) where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + BlockchainEvents<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: AuraApi<B, AuthorityId> + AuraEquivocationApi<B, AuthorityId>,
+	AuthorityId: Codec + Clone + PartialEq + Debug + Send + Sync,
+{
+	let mut imported: BTreeMap<Slot, Vec<(AuthorityId, B::Header)>> = BTreeMap::new();
+	let mut import_notifications = client.import_notification_stream();
+
+	while let Some(notification) = import_notifications.next().await {
+		// blocks of the initial sync are too old to be reported
+		if notification.origin == BlockOrigin::NetworkInitialSync {
+			continue;
+		}
+		let header = notification.header;
+		let Some(slot) = find_slot::<B>(&header) else {
+			continue;
+		};
+
+		// the author of each slot is selected in a round robin manner
+		let author = match client.runtime_api().authorities(*header.parent_hash()) {
+			Ok(authorities) if !authorities.is_empty() => {
+				authorities[(*slot % authorities.len() as u64) as usize].clone()
+			},
+			_ => continue,
+		};
+
+		// prune the headers of the outdated slots
+		imported =
+			imported.split_off(&Slot::from(u64::from(slot).saturating_sub(MAX_SLOT_CAPACITY)));
+
+		let headers = imported.entry(slot).or_default();
+		let first_header = match headers.iter().find(|(signer, _)| *signer == author) {
+			Some((_, first_header)) if first_header.hash() != header.hash() => first_header.clone(),
+			Some(_) => continue,
+			None => {
+				headers.push((author, header));
+				continue;
+			},
+		};
+
+		log::warn!(
+			target: LOG_TARGET,
+			"🚨 Detected an Aura equivocation of {:?} at slot {}: {:?} and {:?}",
+			author,
+			*slot,
+			first_header.hash(),
+			header.hash(),
+		);
+
+		let equivocation_proof =
+			EquivocationProof { offender: author, slot, first_header, second_header: header };
+		match report_equivocation(&*client, &offchain_tx_pool_factory, equivocation_proof) {
+			Ok(()) => log::info!(target: LOG_TARGET, "Submitted an Aura equivocation report"),
+			Err(e) => {
+				log::error!(target: LOG_TARGET, "Failed to report an Aura equivocation: {}", e)
+			},
+		}
+	}
+}
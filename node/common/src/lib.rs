@@ -1,6 +1,7 @@
 #![warn(unused_crate_dependencies)]
 
 pub mod cli_opt;
+pub mod equivocation;
 pub mod rpc;
 pub mod service;
 pub mod tracing;
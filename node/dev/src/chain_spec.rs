@@ -34,10 +34,11 @@ fn session_keys(aura: AuraId, grandpa: GrandpaId, im_online: ImOnlineId) -> Sess
 
 pub fn inflation_config() -> InflationInfo<Balance> {
 	fn to_round_inflation(annual: Range<Perbill>) -> Range<Perbill> {
-		use pallet_bfc_staking::inflation::{perbill_annual_to_perbill_round, BLOCKS_PER_YEAR};
+		use pallet_bfc_staking::inflation::{blocks_per_year, perbill_annual_to_perbill_round};
 		perbill_annual_to_perbill_round(
 			annual,
-			BLOCKS_PER_YEAR / bifrost_dev_runtime::DefaultBlocksPerRound::get(),
+			blocks_per_year(bifrost_dev_constants::time::SLOT_DURATION)
+				/ bifrost_dev_runtime::DefaultBlocksPerRound::get(),
 		)
 	}
 	let annual = Range {
@@ -55,6 +56,9 @@ pub fn inflation_config() -> InflationInfo<Balance> {
 		// annual inflation
 		annual,
 		round: to_round_inflation(annual),
+		// issuance shares
+		treasury: Perbill::zero(),
+		burn: Perbill::zero(),
 	}
 }
 
@@ -8,6 +8,7 @@ use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
 use bifrost_common_node::{
 	cli_opt::{EthApi as EthApiCmd, RpcConfig},
+	equivocation::run_aura_equivocation_watcher,
 	rpc::{FullDevDeps, GrandpaDeps, SpawnTasksParams, TracingConfig},
 	service::{open_frontier_backend, HostFunctions},
 	tracing::{spawn_tracing_tasks, RpcRequesters},
@@ -30,7 +31,7 @@ use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 
 use bp_core::*;
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_consensus_aura::sr25519::{AuthorityId as AuraId, AuthorityPair as AuraPair};
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 
 /// The minimum period of blocks on which justifications will be
@@ -404,6 +405,15 @@ where
 		task_manager
 			.spawn_essential_handle()
 			.spawn_blocking("aura", Some("block-authoring"), aura);
+
+		task_manager.spawn_handle().spawn(
+			"aura-equivocation-watcher",
+			Some("block-authoring"),
+			run_aura_equivocation_watcher::<Block, _, AuraId>(
+				client.clone(),
+				OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+			),
+		);
 	}
 
 	let keystore = if role.is_authority() { Some(keystore_container.keystore()) } else { None };
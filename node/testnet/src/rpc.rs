@@ -54,6 +54,7 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_bfc_staking_rpc::BfcStakingRuntimeApi<Block, AccountId, Balance>,
 	C::Api: BlockBuilder<Block>,
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
@@ -70,6 +71,7 @@ where
 	};
 	use fc_rpc_debug::{Debug, DebugServer};
 	use fc_rpc_trace::{Trace, TraceServer};
+	use pallet_bfc_staking_rpc::{BfcStaking, BfcStakingApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -111,6 +113,7 @@ where
 
 	io.merge(System::new(Arc::clone(&client), Arc::clone(&pool)).into_rpc()).ok();
 	io.merge(TransactionPayment::new(Arc::clone(&client)).into_rpc()).ok();
+	io.merge(BfcStaking::new(Arc::clone(&client)).into_rpc()).ok();
 
 	io.merge(
 		Grandpa::new(
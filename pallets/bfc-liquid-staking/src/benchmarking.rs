@@ -0,0 +1,157 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use bp_staking::traits::NominationManager;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::{vec, vec::Vec};
+
+/// The amount of a single deposit that satisfies every minimum of the pool and the staking pallet
+fn deposit_amount<T: Config>() -> BalanceOf<T> {
+	T::MinDeposit::get()
+		.max(T::MinWithdrawal::get())
+		.max(T::NominationManager::min_nominator_stake())
+		.saturating_mul(2u32.into())
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account::<T::AccountId>(name, index, 0);
+	T::Currency::make_free_balance_be(&who, deposit_amount::<T>().saturating_mul(1_000u32.into()));
+	who
+}
+
+/// Funds the given account and deposits to the pool through the extrinsic
+fn deposited<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = funded_account::<T>(name, index);
+	Pallet::<T>::deposit(RawOrigin::Signed(who.clone()).into(), deposit_amount::<T>())
+		.expect("deposit succeeds");
+	who
+}
+
+/// Puts a pending request on every pool nomination and queues `n` withdrawals behind them
+fn queue_withdrawals<T: Config>(n: u32) {
+	let count = n.saturating_add(T::NominationManager::max_nominations_per_nominator()) + 1;
+	let depositors: Vec<_> = (0..count).map(|index| deposited::<T>("depositor", index)).collect();
+	for who in depositors {
+		if WithdrawalQueue::<T>::decode_len().unwrap_or_default() as u32 >= n {
+			break;
+		}
+		let shares = Shares::<T>::get(&who);
+		Pallet::<T>::withdraw(RawOrigin::Signed(who).into(), shares).expect("withdrawal succeeds");
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn deposit() {
+		T::NominationManager::set_benchmark(0).expect("candidate joins");
+		deposited::<T>("depositor", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), deposit_amount::<T>());
+
+		assert!(!Shares::<T>::get(&caller).is_zero());
+	}
+
+	#[benchmark]
+	fn withdraw(n: Linear<0, { T::MaxQueuedWithdrawals::get() - 1 }>) {
+		T::NominationManager::set_benchmark(0).expect("candidate joins");
+		let caller = deposited::<T>("caller", 0);
+		queue_withdrawals::<T>(n);
+		let shares = Shares::<T>::get(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), shares);
+
+		assert!(Shares::<T>::get(&caller).is_zero());
+	}
+
+	#[benchmark]
+	fn claim(n: Linear<0, { T::MaxQueuedWithdrawals::get() }>) {
+		T::NominationManager::set_benchmark(0).expect("candidate joins");
+		let caller = deposited::<T>("caller", 0);
+		queue_withdrawals::<T>(n);
+
+		// a matured withdrawal whose nomination has been unbonded
+		let amount = deposit_amount::<T>();
+		let points = Pallet::<T>::unbonding_balance_to_points(amount);
+		Unbonding::<T>::insert(
+			&caller,
+			BoundedVec::truncate_from(vec![UnbondingChunk {
+				points,
+				unlock_round: T::NominationManager::current_round(),
+			}]),
+		);
+		TotalUnbonding::<T>::mutate(|total| *total = total.saturating_add(amount));
+		TotalUnbondingPoints::<T>::mutate(|total| *total = total.saturating_add(points));
+		TotalUnlocked::<T>::mutate(|total| *total = total.saturating_add(amount));
+		let _ = T::Currency::deposit_creating(&Pallet::<T>::account_id(), amount);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(Unbonding::<T>::get(&caller).is_empty());
+	}
+
+	#[benchmark]
+	fn transfer() {
+		let caller = account::<T::AccountId>("caller", 0, 0);
+		let to = account::<T::AccountId>("to", 0, 0);
+		let shares = deposit_amount::<T>();
+		Shares::<T>::insert(&caller, shares);
+		Shares::<T>::insert(&to, shares);
+		TotalShares::<T>::put(shares.saturating_mul(2u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), to.clone(), shares);
+
+		assert_eq!(Shares::<T>::get(&to), shares.saturating_mul(2u32.into()));
+	}
+
+	#[benchmark]
+	fn approve() {
+		let caller = account::<T::AccountId>("caller", 0, 0);
+		let spender = account::<T::AccountId>("spender", 0, 0);
+		let shares = deposit_amount::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), spender.clone(), shares);
+
+		assert_eq!(Allowances::<T>::get(&caller, &spender), shares);
+	}
+
+	#[benchmark]
+	fn transfer_from() {
+		let owner = account::<T::AccountId>("owner", 0, 0);
+		let caller = account::<T::AccountId>("caller", 0, 0);
+		let to = account::<T::AccountId>("to", 0, 0);
+		let shares = deposit_amount::<T>();
+		Shares::<T>::insert(&owner, shares);
+		Shares::<T>::insert(&to, shares);
+		TotalShares::<T>::put(shares.saturating_mul(2u32.into()));
+		Allowances::<T>::insert(&owner, &caller, shares.saturating_mul(2u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), owner.clone(), to.clone(), shares);
+
+		assert_eq!(Allowances::<T>::get(&owner, &caller), shares);
+	}
+
+	#[benchmark]
+	fn process_withdrawals(n: Linear<0, { T::MaxQueuedWithdrawals::get() }>) {
+		T::NominationManager::set_benchmark(0).expect("candidate joins");
+		let caller = funded_account::<T>("caller", 0);
+		queue_withdrawals::<T>(n);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller));
+	}
+
+	impl_benchmark_test_suite!(Pallet, mock::new_test_ext(), mock::Test);
+}
@@ -0,0 +1,49 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod pallet;
+pub mod strategy;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use pallet::pallet::*;
+use weights::WeightInfo;
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+
+use frame_support::{pallet_prelude::MaxEncodedLen, traits::Currency};
+
+use bp_staking::RoundIndex;
+use sp_runtime::RuntimeDebug;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The amount of shares that is used to represent the share price (18 decimals)
+pub const SHARE_UNIT: u128 = 1_000_000_000_000_000_000;
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// A scheduled withdrawal of a pool member
+pub struct UnbondingChunk<Balance> {
+	/// The unbonding points of the withdrawal. Their value shares the slashes of the pool until
+	/// the withdrawal is claimed.
+	pub points: Balance,
+	/// The round index when the chunk becomes claimable
+	pub unlock_round: RoundIndex,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// A withdrawal that waits for a pool nomination to be unbonded from
+pub struct QueuedWithdrawal<AccountId, Balance> {
+	/// The pool member that requested the withdrawal
+	pub who: AccountId,
+	/// The unbonding points of the withdrawal that are not scheduled yet
+	pub points: Balance,
+}
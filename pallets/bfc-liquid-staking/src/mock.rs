@@ -0,0 +1,360 @@
+use crate as pallet_bfc_liquid_staking;
+use bp_core::{AccountId, Balance, BlockNumber};
+use bp_staking::{traits::NominationManager, RoundIndex};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Currency, Everything, ReservableCurrency},
+	PalletId,
+};
+use sp_core::{H160, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, DispatchError, DispatchResult,
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		LiquidStaking: pallet_bfc_liquid_staking,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: BlockNumber = 256;
+	pub const SS58Prefix: u8 = 42;
+	pub const ExistentialDeposit: u128 = 0;
+	pub const LiquidStakingPalletId: PalletId = PalletId(*b"bfc/lqst");
+	pub const MinDeposit: Balance = 10;
+	pub const MinWithdrawal: Balance = 10;
+	pub const MaxUnbondingChunks: u32 = 2;
+	pub const MaxQueuedWithdrawals: u32 = 4;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeTask = RuntimeTask;
+	type Nonce = u64;
+	type Block = Block;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type SingleBlockMigrations = ();
+	type MultiBlockMigrator = ();
+	type PreInherents = ();
+	type PostInherents = ();
+	type PostTransactions = ();
+	type ExtensionsWeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type DoneSlashHandler = ();
+}
+
+/// The number of rounds that a nomination decrease is delayed by
+pub const NOMINATION_DELAY: RoundIndex = 2;
+/// The minimum amount of a single nomination
+pub const MIN_NOMINATION: Balance = 5;
+/// The minimum total stake of a nominator
+pub const MIN_NOMINATOR_STAKE: Balance = 10;
+
+#[derive(Default)]
+pub struct StakingState {
+	pub round: RoundIndex,
+	pub candidates: BTreeMap<AccountId, Balance>,
+	pub nominations: BTreeMap<AccountId, BTreeMap<AccountId, Balance>>,
+	pub requests: BTreeMap<AccountId, BTreeMap<AccountId, (Balance, RoundIndex)>>,
+}
+
+thread_local! {
+	pub static STAKING: RefCell<StakingState> = RefCell::new(StakingState::default());
+}
+
+/// A simplified `pallet_bfc_staking` that reserves the nominated funds
+pub struct MockStaking;
+
+impl MockStaking {
+	pub fn set_round(round: RoundIndex) {
+		STAKING.with(|s| s.borrow_mut().round = round);
+	}
+
+	pub fn nomination(nominator: &AccountId, candidate: &AccountId) -> Balance {
+		STAKING.with(|s| {
+			s.borrow()
+				.nominations
+				.get(nominator)
+				.and_then(|n| n.get(candidate).copied())
+				.unwrap_or_default()
+		})
+	}
+
+	/// Compounds the given reward into the nomination of the candidate
+	pub fn reward(nominator: &AccountId, candidate: &AccountId, amount: Balance) {
+		let _ = Balances::deposit_creating(nominator, amount);
+		Balances::reserve(nominator, amount).unwrap();
+		STAKING.with(|s| {
+			*s.borrow_mut()
+				.nominations
+				.entry(*nominator)
+				.or_default()
+				.entry(*candidate)
+				.or_default() += amount;
+		});
+		STAKING.with(|s| *s.borrow_mut().candidates.entry(*candidate).or_default() += amount);
+	}
+
+	/// Slashes the given amount from the nomination of the candidate
+	pub fn slash(nominator: &AccountId, candidate: &AccountId, amount: Balance) {
+		let _ = Balances::slash_reserved(nominator, amount);
+		STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			*s.nominations.get_mut(nominator).unwrap().get_mut(candidate).unwrap() -= amount;
+			*s.candidates.get_mut(candidate).unwrap() -= amount;
+		});
+	}
+
+	/// Removes the nomination of the candidate and returns its stake, as a bottom nomination
+	/// kick does
+	pub fn kick(nominator: &AccountId, candidate: &AccountId) {
+		let amount = STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			if let Some(requests) = s.requests.get_mut(nominator) {
+				requests.remove(candidate);
+			}
+			let amount = s
+				.nominations
+				.get_mut(nominator)
+				.and_then(|n| n.remove(candidate))
+				.unwrap_or_default();
+			*s.candidates.get_mut(candidate).unwrap() -= amount;
+			amount
+		});
+		Balances::unreserve(nominator, amount);
+	}
+
+	/// Removes the candidate and returns the stake of all its nominations, as
+	/// `execute_leave_candidates` does
+	pub fn leave_candidate(candidate: &AccountId) {
+		let nominators: Vec<AccountId> = STAKING.with(|s| {
+			s.borrow()
+				.nominations
+				.iter()
+				.filter(|(_, n)| n.contains_key(candidate))
+				.map(|(nominator, _)| *nominator)
+				.collect()
+		});
+		nominators.iter().for_each(|nominator| Self::kick(nominator, candidate));
+		STAKING.with(|s| s.borrow_mut().candidates.remove(candidate));
+	}
+
+	pub fn pending_request(nominator: &AccountId, candidate: &AccountId) -> Option<Balance> {
+		STAKING.with(|s| {
+			s.borrow()
+				.requests
+				.get(nominator)
+				.and_then(|r| r.get(candidate))
+				.map(|(a, _)| *a)
+		})
+	}
+}
+
+impl NominationManager<AccountId, Balance> for MockStaking {
+	fn current_round() -> RoundIndex {
+		STAKING.with(|s| s.borrow().round)
+	}
+
+	fn min_nomination() -> Balance {
+		MIN_NOMINATION
+	}
+
+	fn min_nominator_stake() -> Balance {
+		MIN_NOMINATOR_STAKE
+	}
+
+	fn max_nominations_per_nominator() -> u32 {
+		2
+	}
+
+	fn nomination_bond_less_delay() -> RoundIndex {
+		NOMINATION_DELAY
+	}
+
+	fn selected_candidates() -> Vec<(AccountId, Balance)> {
+		STAKING.with(|s| s.borrow().candidates.iter().map(|(c, v)| (*c, *v)).collect())
+	}
+
+	fn nominations(nominator: &AccountId) -> Vec<(AccountId, Balance)> {
+		STAKING.with(|s| {
+			s.borrow()
+				.nominations
+				.get(nominator)
+				.map(|n| n.iter().map(|(c, v)| (*c, *v)).collect())
+				.unwrap_or_default()
+		})
+	}
+
+	fn total_nominated(nominator: &AccountId) -> Balance {
+		Self::nominations(nominator).iter().map(|(_, v)| v).sum()
+	}
+
+	fn pending_requests(nominator: &AccountId) -> Vec<(AccountId, Balance, RoundIndex)> {
+		STAKING.with(|s| {
+			s.borrow()
+				.requests
+				.get(nominator)
+				.map(|r| r.iter().map(|(c, (a, w))| (*c, *a, *w)).collect())
+				.unwrap_or_default()
+		})
+	}
+
+	fn nominate(nominator: &AccountId, candidate: &AccountId, amount: Balance) -> DispatchResult {
+		Balances::reserve(nominator, amount)?;
+		STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			*s.nominations.entry(*nominator).or_default().entry(*candidate).or_default() += amount;
+			*s.candidates.entry(*candidate).or_default() += amount;
+		});
+		Ok(())
+	}
+
+	fn schedule_nomination_decrease(
+		nominator: &AccountId,
+		candidate: &AccountId,
+		amount: Balance,
+	) -> Result<RoundIndex, DispatchError> {
+		STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			let when = s.round + NOMINATION_DELAY;
+			let requests = s.requests.entry(*nominator).or_default();
+			if requests.contains_key(candidate) {
+				return Err(DispatchError::Other("PendingNominationRequestAlreadyExists"));
+			}
+			requests.insert(*candidate, (amount, when));
+			Ok(when)
+		})
+	}
+
+	fn execute_nomination_request(nominator: &AccountId, candidate: &AccountId) -> DispatchResult {
+		let amount = STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			let (amount, when) = s
+				.requests
+				.get(nominator)
+				.and_then(|r| r.get(candidate).copied())
+				.ok_or(DispatchError::Other("PendingNominationRequestDNE"))?;
+			if when > s.round {
+				return Err(DispatchError::Other("PendingNominationRequestNotDueYet"));
+			}
+			s.requests.get_mut(nominator).unwrap().remove(candidate);
+			let nominations = s.nominations.get_mut(nominator).unwrap();
+			let bonded = nominations.get_mut(candidate).unwrap();
+			*bonded -= amount;
+			if *bonded == 0 {
+				nominations.remove(candidate);
+			}
+			*s.candidates.get_mut(candidate).unwrap() -= amount;
+			Ok(amount)
+		})?;
+		Balances::unreserve(nominator, amount);
+		Ok(())
+	}
+
+	fn set_reward_payee(_nominator: &AccountId, _payee: AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_benchmark(index: u32) -> Result<AccountId, DispatchError> {
+		let candidate = account(100 + index as u64);
+		STAKING.with(|s| s.borrow_mut().candidates.insert(candidate, 1_000));
+		Ok(candidate)
+	}
+}
+
+impl pallet_bfc_liquid_staking::Config for Test {
+	type Currency = Balances;
+	type NominationManager = MockStaking;
+	type Strategy = crate::strategy::EvenSpread<AccountId, Balance>;
+	type PalletId = LiquidStakingPalletId;
+	type MinDeposit = MinDeposit;
+	type MinWithdrawal = MinWithdrawal;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type MaxQueuedWithdrawals = MaxQueuedWithdrawals;
+	type WeightInfo = ();
+}
+
+pub fn account(seed: u64) -> AccountId {
+	AccountId::from(H160::from_low_u64_be(seed))
+}
+
+pub fn alice() -> AccountId {
+	account(1)
+}
+
+pub fn bob() -> AccountId {
+	account(2)
+}
+
+pub fn validator_1() -> AccountId {
+	account(11)
+}
+
+pub fn validator_2() -> AccountId {
+	account(12)
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(alice(), 1_000), (bob(), 1_000)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		STAKING.with(|s| {
+			let mut s = s.borrow_mut();
+			*s = StakingState::default();
+			s.round = 1;
+			s.candidates.insert(validator_1(), 1_000);
+			s.candidates.insert(validator_2(), 2_000);
+		});
+	});
+	ext
+}
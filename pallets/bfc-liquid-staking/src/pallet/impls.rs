@@ -0,0 +1,465 @@
+use super::pallet::*;
+
+use crate::{
+	strategy::{NominationStrategy, NominationTarget},
+	BalanceOf, QueuedWithdrawal, UnbondingChunk, SHARE_UNIT,
+};
+
+use bp_staking::{traits::NominationManager, RoundIndex};
+use frame_support::{ensure, storage::with_storage_layer, traits::Currency, BoundedVec};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AccountIdConversion, CheckedSub, Get, Saturating, UniqueSaturatedInto, Zero},
+	DispatchResult, Rounding,
+};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec::Vec,
+};
+
+impl<T: Config> Pallet<T> {
+	/// The account that holds and nominates the pooled funds
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// The total amount of BFC that backs the issued shares. This includes the compounded
+	/// rewards and excludes the BFC owed to pending withdrawals.
+	pub fn total_pooled() -> BalanceOf<T> {
+		Self::synced_balances().0
+	}
+
+	/// Returns the pooled stake, the amount owed to the pending withdrawals and the amount
+	/// nominated by the pool. The compounded rewards and the slashes of the pool's nominations
+	/// that are not accounted yet are applied. Rewards are added to the pooled stake and slashes
+	/// are shared pro rata between the pooled stake and the withdrawals that are not claimed yet.
+	/// A drop of the nominated amount that has been returned into the pool account is not a
+	/// slash.
+	fn synced_balances() -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
+		let pooled = TotalPooled::<T>::get();
+		let unbonding = TotalUnbonding::<T>::get();
+		let expected = TotalBonded::<T>::get();
+		let bonded = T::NominationManager::total_nominated(&Self::account_id());
+
+		if bonded >= expected {
+			(pooled.saturating_add(bonded - expected), unbonding, bonded)
+		} else {
+			let dropped = expected - bonded;
+			let slash = dropped.saturating_sub(Self::returned_stake(dropped));
+			if slash.is_zero() {
+				return (pooled, unbonding, bonded);
+			}
+			let pooled_slash =
+				Self::mul_div(slash, pooled, pooled.saturating_add(unbonding)).min(pooled);
+			let unbonding_slash = slash.saturating_sub(pooled_slash).min(unbonding);
+			(pooled - pooled_slash, unbonding - unbonding_slash, bonded)
+		}
+	}
+
+	/// The part of the dropped nominated amount that the staking pallet has returned into the
+	/// free balance of the pool account, because a candidate left or kicked a pool nomination
+	fn returned_stake(dropped: BalanceOf<T>) -> BalanceOf<T> {
+		let held = TotalUnlocked::<T>::get().saturating_add(TotalIdle::<T>::get());
+		T::Currency::free_balance(&Self::account_id()).saturating_sub(held).min(dropped)
+	}
+
+	/// Accounts the compounded rewards, the slashes and the returned stake of the pool's
+	/// nominations. The idle stake is nominated again afterwards.
+	pub(crate) fn sync_pool() {
+		let expected = TotalBonded::<T>::get();
+		let (pooled, unbonding, bonded) = Self::synced_balances();
+		if bonded != expected {
+			if bonded < expected {
+				let returned = Self::returned_stake(expected - bonded);
+				if !returned.is_zero() {
+					Self::book_returned_stake(returned);
+				}
+			}
+
+			let old_pooled = TotalPooled::<T>::get();
+			let old_unbonding = TotalUnbonding::<T>::get();
+			if pooled > old_pooled {
+				Self::deposit_event(Event::Rewarded { amount: pooled - old_pooled });
+			} else if pooled < old_pooled || unbonding < old_unbonding {
+				Self::deposit_event(Event::Slashed {
+					pooled: old_pooled - pooled,
+					unbonding: old_unbonding.saturating_sub(unbonding),
+				});
+			}
+			TotalPooled::<T>::put(pooled);
+			TotalUnbonding::<T>::put(unbonding);
+			TotalBonded::<T>::put(bonded);
+		}
+		Self::nominate_idle();
+	}
+
+	/// Books the stake that the staking pallet has returned into the pool account. The pending
+	/// requests of the returned nominations have been removed with them, so their amount is held
+	/// for the scheduled withdrawals. The rest becomes idle until it is nominated again.
+	fn book_returned_stake(amount: BalanceOf<T>) {
+		let pending = Self::pending_decreases(&Self::account_id());
+		let dropped_requests = TotalScheduled::<T>::get().saturating_sub(pending).min(amount);
+		TotalScheduled::<T>::put(pending);
+		TotalUnlocked::<T>::mutate(|total| *total = total.saturating_add(dropped_requests));
+		TotalIdle::<T>::mutate(|total| *total = total.saturating_add(amount - dropped_requests));
+		Self::deposit_event(Event::StakeReturned { amount });
+	}
+
+	/// Nominates the idle stake to the candidate selected by the strategy. It stays idle and is
+	/// retried on the next sync if the nomination fails.
+	fn nominate_idle() {
+		let idle = TotalIdle::<T>::get();
+		if idle.is_zero() {
+			return;
+		}
+		let Some(candidate) = Self::deposit_target(idle) else {
+			return;
+		};
+		let pool = Self::account_id();
+		if with_storage_layer(|| T::NominationManager::nominate(&pool, &candidate, idle)).is_ok() {
+			TotalIdle::<T>::kill();
+			TotalBonded::<T>::mutate(|total| *total = total.saturating_add(idle));
+			Self::deposit_event(Event::Renominated { candidate, amount: idle });
+		}
+	}
+
+	/// The total amount of the pending nomination decreases of the pool
+	fn pending_decreases(pool: &T::AccountId) -> BalanceOf<T> {
+		T::NominationManager::pending_requests(pool)
+			.into_iter()
+			.fold(Zero::zero(), |acc, (_, amount, _)| acc.saturating_add(amount))
+	}
+
+	/// The amount of shares minted for the given deposit at the current share price
+	pub fn balance_to_shares(amount: BalanceOf<T>) -> BalanceOf<T> {
+		let total_shares = TotalShares::<T>::get();
+		let total_pooled = Self::total_pooled();
+		if total_shares.is_zero() || total_pooled.is_zero() {
+			return amount;
+		}
+		Self::mul_div(amount, total_shares, total_pooled)
+	}
+
+	/// The amount of BFC that the given shares are worth at the current share price
+	pub fn shares_to_balance(shares: BalanceOf<T>) -> BalanceOf<T> {
+		let total_shares = TotalShares::<T>::get();
+		if total_shares.is_zero() {
+			return Zero::zero();
+		}
+		Self::mul_div(shares, Self::total_pooled(), total_shares)
+	}
+
+	/// The amount of BFC that a single share unit is worth
+	pub fn share_price() -> BalanceOf<T> {
+		let unit: BalanceOf<T> = SHARE_UNIT.unique_saturated_into();
+		if TotalShares::<T>::get().is_zero() {
+			return unit;
+		}
+		Self::shares_to_balance(unit)
+	}
+
+	/// The amount of BFC that the given unbonding points are worth
+	pub fn unbonding_points_to_balance(points: BalanceOf<T>) -> BalanceOf<T> {
+		let total_points = TotalUnbondingPoints::<T>::get();
+		if total_points.is_zero() {
+			return Zero::zero();
+		}
+		Self::mul_div(points, Self::synced_balances().1, total_points)
+	}
+
+	/// The amount of unbonding points minted for a withdrawal of the given amount
+	pub(crate) fn unbonding_balance_to_points(amount: BalanceOf<T>) -> BalanceOf<T> {
+		let total_points = TotalUnbondingPoints::<T>::get();
+		let total_unbonding = TotalUnbonding::<T>::get();
+		if total_points.is_zero() || total_unbonding.is_zero() {
+			return amount;
+		}
+		Self::mul_div(amount, total_points, total_unbonding)
+	}
+
+	/// Returns the pending withdrawals of the given account and the round that each becomes
+	/// claimable. Queued withdrawals that are not scheduled yet are returned with round zero.
+	pub fn unbonding_of(who: &T::AccountId) -> Vec<(BalanceOf<T>, RoundIndex)> {
+		let scheduled = Unbonding::<T>::get(who)
+			.into_iter()
+			.map(|chunk| (Self::unbonding_points_to_balance(chunk.points), chunk.unlock_round));
+		let queued = WithdrawalQueue::<T>::get()
+			.into_iter()
+			.filter(|entry| entry.who == *who)
+			.map(|entry| (Self::unbonding_points_to_balance(entry.points), 0));
+		scheduled.chain(queued).collect()
+	}
+
+	fn mul_div(a: BalanceOf<T>, b: BalanceOf<T>, c: BalanceOf<T>) -> BalanceOf<T> {
+		multiply_by_rational_with_rounding(
+			a.unique_saturated_into(),
+			b.unique_saturated_into(),
+			c.unique_saturated_into(),
+			Rounding::Down,
+		)
+		.map(|result| result.unique_saturated_into())
+		.unwrap_or_else(Zero::zero)
+	}
+
+	/// Selects the candidate that the given deposit will be nominated to. Candidates that are
+	/// not nominated by the pool yet are only eligible if the deposit satisfies the minimum
+	/// nomination.
+	pub(crate) fn deposit_target(amount: BalanceOf<T>) -> Option<T::AccountId> {
+		let pool = Self::account_id();
+		let nominations: BTreeMap<_, _> =
+			T::NominationManager::nominations(&pool).into_iter().collect();
+		let min = if nominations.is_empty() {
+			T::NominationManager::min_nominator_stake()
+		} else {
+			T::NominationManager::min_nomination()
+		};
+		let can_add = amount >= min
+			&& (nominations.len() as u32) < T::NominationManager::max_nominations_per_nominator();
+
+		let targets: Vec<_> = T::NominationManager::selected_candidates()
+			.into_iter()
+			.filter_map(|(candidate, voting_power)| match nominations.get(&candidate) {
+				Some(bonded) => Some(NominationTarget { candidate, bonded: *bonded, voting_power }),
+				None if can_add => {
+					Some(NominationTarget { candidate, bonded: Zero::zero(), voting_power })
+				},
+				None => None,
+			})
+			.collect();
+		T::Strategy::deposit_target(&targets, amount)
+	}
+
+	/// Returns the pool nominations that a withdrawal can be unbonded from. A nomination is
+	/// eligible if it has no pending request.
+	fn withdraw_targets() -> Vec<NominationTarget<T::AccountId, BalanceOf<T>>> {
+		let pool = Self::account_id();
+		let pending: BTreeSet<_> = T::NominationManager::pending_requests(&pool)
+			.into_iter()
+			.map(|(candidate, _, _)| candidate)
+			.collect();
+		let voting_powers: BTreeMap<_, _> =
+			T::NominationManager::selected_candidates().into_iter().collect();
+
+		T::NominationManager::nominations(&pool)
+			.into_iter()
+			.filter(|(candidate, _)| !pending.contains(candidate))
+			.map(|(candidate, bonded)| {
+				let voting_power = voting_powers.get(&candidate).copied().unwrap_or_default();
+				NominationTarget { candidate, bonded, voting_power }
+			})
+			.collect()
+	}
+
+	/// Splits the given withdrawal across the eligible nominations in the order of the strategy.
+	/// A nomination is either fully revoked or remains above the minimum nomination. Returns the
+	/// amount taken from each candidate.
+	fn plan_withdrawal(
+		targets: &mut Vec<NominationTarget<T::AccountId, BalanceOf<T>>>,
+		amount: BalanceOf<T>,
+	) -> BTreeMap<T::AccountId, BalanceOf<T>> {
+		let min_nomination = T::NominationManager::min_nomination();
+		let mut plan = BTreeMap::new();
+		let mut remaining = amount;
+		while !remaining.is_zero() {
+			let Some(candidate) = T::Strategy::withdraw_target(targets, remaining) else {
+				break;
+			};
+			let Some(target) = targets.iter_mut().find(|t| t.candidate == candidate) else {
+				break;
+			};
+			let take = if remaining >= target.bonded {
+				target.bonded
+			} else {
+				remaining.min(target.bonded.saturating_sub(min_nomination))
+			};
+			target.bonded = target.bonded.saturating_sub(take);
+			if take.is_zero() || target.bonded.is_zero() {
+				targets.retain(|t| t.candidate != candidate);
+			}
+			if !take.is_zero() {
+				remaining = remaining.saturating_sub(take);
+				let planned: &mut BalanceOf<T> = plan.entry(candidate).or_default();
+				*planned = planned.saturating_add(take);
+			}
+		}
+		plan
+	}
+
+	/// Schedules the queued withdrawals, oldest first, on the pool nominations without a pending
+	/// request. A withdrawal that exceeds the available nominations is scheduled in part and its
+	/// remainder stays queued. Withdrawals of accounts without a free unbonding chunk are skipped
+	/// until the account claims.
+	pub(crate) fn process_withdrawal_queue() {
+		let queue = WithdrawalQueue::<T>::get();
+		if queue.is_empty() {
+			return;
+		}
+		let pool = Self::account_id();
+		let unlock_round = T::NominationManager::current_round()
+			.saturating_add(T::NominationManager::nomination_bond_less_delay());
+
+		let mut targets = Self::withdraw_targets();
+		let mut totals: BTreeMap<T::AccountId, BalanceOf<T>> = BTreeMap::new();
+		let plans: Vec<_> = queue
+			.iter()
+			.map(|entry| {
+				if targets.is_empty() || !Self::has_free_chunk(&entry.who, unlock_round) {
+					return BTreeMap::new();
+				}
+				let amount = Self::unbonding_points_to_balance(entry.points);
+				let plan = Self::plan_withdrawal(&mut targets, amount);
+				for (candidate, take) in plan.iter() {
+					let total = totals.entry(candidate.clone()).or_default();
+					*total = total.saturating_add(*take);
+				}
+				plan
+			})
+			.collect();
+
+		// a single request is scheduled per nomination. the withdrawals planned on a nomination
+		// that fails to be scheduled stay queued.
+		let scheduled: BTreeMap<_, _> = totals
+			.into_iter()
+			.filter(|(candidate, total)| {
+				with_storage_layer(|| {
+					T::NominationManager::schedule_nomination_decrease(&pool, candidate, *total)
+				})
+				.is_ok()
+			})
+			.collect();
+		let scheduled_total = scheduled
+			.values()
+			.fold(BalanceOf::<T>::zero(), |acc, total| acc.saturating_add(*total));
+		TotalScheduled::<T>::mutate(|total| *total = total.saturating_add(scheduled_total));
+
+		let mut remaining = Vec::new();
+		for (entry, plan) in queue.into_iter().zip(plans) {
+			let amount = plan
+				.iter()
+				.filter(|(candidate, _)| scheduled.contains_key(*candidate))
+				.fold(BalanceOf::<T>::zero(), |acc, (_, take)| acc.saturating_add(*take));
+			if amount.is_zero() {
+				remaining.push(entry);
+				continue;
+			}
+
+			let value = Self::unbonding_points_to_balance(entry.points);
+			let points = if amount >= value {
+				entry.points
+			} else {
+				Self::mul_div(entry.points, amount, value)
+			};
+			Self::add_chunk(&entry.who, points, unlock_round);
+			Self::deposit_event(Event::WithdrawalScheduled {
+				who: entry.who.clone(),
+				amount,
+				unlock_round,
+			});
+			if points < entry.points {
+				remaining.push(QueuedWithdrawal { who: entry.who, points: entry.points - points });
+			}
+		}
+		// the remaining withdrawals never exceed the bound
+		WithdrawalQueue::<T>::put(BoundedVec::truncate_from(remaining));
+	}
+
+	/// Verifies whether the account can hold a withdrawal that unlocks in the given round
+	fn has_free_chunk(who: &T::AccountId, unlock_round: RoundIndex) -> bool {
+		let chunks = Unbonding::<T>::get(who);
+		chunks.iter().any(|chunk| chunk.unlock_round == unlock_round)
+			|| (chunks.len() as u32) < T::MaxUnbondingChunks::get()
+	}
+
+	/// Adds the scheduled withdrawal to the chunk of the account that unlocks in the same round
+	fn add_chunk(who: &T::AccountId, points: BalanceOf<T>, unlock_round: RoundIndex) {
+		Unbonding::<T>::mutate(who, |chunks| {
+			if let Some(chunk) = chunks.iter_mut().find(|chunk| chunk.unlock_round == unlock_round)
+			{
+				chunk.points = chunk.points.saturating_add(points);
+			} else {
+				// a free chunk is verified by `has_free_chunk()` beforehand
+				let _ = chunks.try_push(UnbondingChunk { points, unlock_round });
+			}
+		});
+	}
+
+	/// Executes the pool's nomination requests that are due. A failed execution is reverted,
+	/// leaves the request pending and it will be retried later. The unbonded amount is moved from
+	/// the pool's nominated stake to the amount held for the withdrawals.
+	pub(crate) fn execute_due_requests(pool: &T::AccountId, now: RoundIndex) {
+		let before = T::NominationManager::total_nominated(pool);
+		for (candidate, _, when) in T::NominationManager::pending_requests(pool) {
+			if when <= now {
+				let _ = with_storage_layer(|| {
+					T::NominationManager::execute_nomination_request(pool, &candidate)
+				});
+			}
+		}
+		let unbonded = before.saturating_sub(T::NominationManager::total_nominated(pool));
+		TotalBonded::<T>::mutate(|total| *total = total.saturating_sub(unbonded));
+		TotalUnlocked::<T>::mutate(|total| *total = total.saturating_add(unbonded));
+		TotalScheduled::<T>::put(Self::pending_decreases(pool));
+	}
+
+	pub(crate) fn burn_shares(who: &T::AccountId, shares: BalanceOf<T>) {
+		Shares::<T>::mutate_exists(who, |balance| {
+			let remaining = balance.unwrap_or_default().saturating_sub(shares);
+			*balance = if remaining.is_zero() { None } else { Some(remaining) };
+		});
+		TotalShares::<T>::mutate(|total| *total = total.saturating_sub(shares));
+	}
+
+	/// Transfers shares between accounts
+	pub fn transfer_shares(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		shares: BalanceOf<T>,
+	) -> DispatchResult {
+		let balance = Shares::<T>::get(from);
+		ensure!(balance >= shares, Error::<T>::InsufficientShares);
+		if from != to && !shares.is_zero() {
+			let remaining = balance.saturating_sub(shares);
+			if remaining.is_zero() {
+				Shares::<T>::remove(from);
+			} else {
+				Shares::<T>::insert(from, remaining);
+			}
+			Shares::<T>::mutate(to, |balance| *balance = balance.saturating_add(shares));
+		}
+		Self::deposit_event(Event::Transfer { from: from.clone(), to: to.clone(), shares });
+		Ok(())
+	}
+
+	/// Sets the amount of shares that the spender is allowed to transfer on behalf of the owner
+	pub fn approve_shares(owner: &T::AccountId, spender: &T::AccountId, shares: BalanceOf<T>) {
+		if shares.is_zero() {
+			Allowances::<T>::remove(owner, spender);
+		} else {
+			Allowances::<T>::insert(owner, spender, shares);
+		}
+		Self::deposit_event(Event::Approval {
+			owner: owner.clone(),
+			spender: spender.clone(),
+			shares,
+		});
+	}
+
+	/// Decreases the allowance of the spender by the given shares
+	pub fn spend_allowance(
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		shares: BalanceOf<T>,
+	) -> DispatchResult {
+		if owner == spender {
+			return Ok(());
+		}
+		Allowances::<T>::try_mutate_exists(owner, spender, |allowance| -> DispatchResult {
+			let remaining = allowance
+				.unwrap_or_default()
+				.checked_sub(&shares)
+				.ok_or(Error::<T>::InsufficientAllowance)?;
+			*allowance = if remaining.is_zero() { None } else { Some(remaining) };
+			Ok(())
+		})
+	}
+}
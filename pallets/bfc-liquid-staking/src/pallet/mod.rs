@@ -0,0 +1,336 @@
+mod impls;
+
+use crate::{
+	strategy::NominationStrategy, BalanceOf, QueuedWithdrawal, UnbondingChunk, WeightInfo,
+};
+
+use bp_staking::{traits::NominationManager, RoundIndex};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement},
+	PalletId,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::prelude::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Pallet for bfc liquid staking
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configuration trait of this pallet
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The currency type
+		type Currency: Currency<Self::AccountId>;
+		/// The handler of the pool's nominations
+		type NominationManager: NominationManager<Self::AccountId, BalanceOf<Self>>;
+		/// The strategy that selects the candidates that the pooled funds are nominated to
+		type Strategy: NominationStrategy<Self::AccountId, BalanceOf<Self>>;
+		/// The pallet id used to derive the pool account
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The minimum amount of a single deposit
+		#[pallet::constant]
+		type MinDeposit: Get<BalanceOf<Self>>;
+		/// The minimum amount of a single withdrawal, unless it withdraws every share of the
+		/// account. It keeps the withdrawal queue from being filled with dust.
+		#[pallet::constant]
+		type MinWithdrawal: Get<BalanceOf<Self>>;
+		/// The maximum number of pending withdrawals per account
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+		/// The maximum number of withdrawals that wait for a pool nomination to be unbonded from
+		#[pallet::constant]
+		type MaxQueuedWithdrawals: Get<u32>;
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The amount must be greater than zero
+		ZeroAmount,
+		/// The deposit is below the minimum amount
+		DepositBelowMin,
+		/// The withdrawal is below the minimum amount
+		WithdrawalBelowMin,
+		/// There is no candidate that can receive the deposit
+		NoAvailableCandidate,
+		/// The withdrawal queue is full
+		WithdrawalQueueFull,
+		/// The account does not hold enough shares
+		InsufficientShares,
+		/// The spender is not allowed to transfer the requested shares
+		InsufficientAllowance,
+		/// The account has reached the maximum number of pending withdrawals
+		TooManyUnbondingChunks,
+		/// There is no matured withdrawal to claim
+		NothingToClaim,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// BFC has been deposited to the pool and shares have been minted
+		Deposited {
+			who: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+			shares: BalanceOf<T>,
+		},
+		/// Shares have been burned and the withdrawal of the backing BFC has been queued
+		WithdrawalQueued { who: T::AccountId, shares: BalanceOf<T>, amount: BalanceOf<T> },
+		/// (A part of) a queued withdrawal has been scheduled on the pool's nominations
+		WithdrawalScheduled { who: T::AccountId, amount: BalanceOf<T>, unlock_round: RoundIndex },
+		/// Compounded rewards of the pool's nominations have been added to the pooled stake
+		Rewarded { amount: BalanceOf<T> },
+		/// A slash of the pool's nominations has been shared between the pooled stake and the
+		/// pending withdrawals
+		Slashed { pooled: BalanceOf<T>, unbonding: BalanceOf<T> },
+		/// The staking pallet has returned the stake of pool nominations into the pool account
+		/// because their candidate left or kicked them
+		StakeReturned { amount: BalanceOf<T> },
+		/// The idle stake of the pool has been nominated again
+		Renominated { candidate: T::AccountId, amount: BalanceOf<T> },
+		/// Matured withdrawals have been paid out
+		Claimed { who: T::AccountId, amount: BalanceOf<T> },
+		/// Shares have been transferred
+		Transfer { from: T::AccountId, to: T::AccountId, shares: BalanceOf<T> },
+		/// An allowance has been set
+		Approval { owner: T::AccountId, spender: T::AccountId, shares: BalanceOf<T> },
+	}
+
+	#[pallet::storage]
+	/// The amount of BFC that backs the issued shares. BFC sent directly to the pool account is
+	/// not part of it.
+	pub type TotalPooled<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The amount of BFC that the pool has nominated according to its own accounting. A difference
+	/// to the actual nominated amount is a compounded reward, a slash or a returned stake that is
+	/// not accounted yet.
+	pub type TotalBonded<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The amount of the pool's nomination decreases that are scheduled and not executed yet
+	pub type TotalScheduled<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The amount of BFC held in the pool account for the scheduled withdrawals whose nomination
+	/// has been unbonded or returned
+	pub type TotalUnlocked<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The pooled stake that has been returned into the pool account and waits to be nominated
+	/// again
+	pub type TotalIdle<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The total amount of issued shares
+	pub type TotalShares<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The shares held by each account
+	pub type Shares<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The amount of shares that a spender is allowed to transfer on behalf of an owner
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// The scheduled withdrawals of each account
+	pub type Unbonding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<UnbondingChunk<BalanceOf<T>>, T::MaxUnbondingChunks>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// The withdrawals that wait for a pool nomination to be unbonded from, oldest first
+	pub type WithdrawalQueue<T: Config> = StorageValue<
+		_,
+		BoundedVec<QueuedWithdrawal<T::AccountId, BalanceOf<T>>, T::MaxQueuedWithdrawals>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// The total amount of BFC that is owed to the queued and scheduled withdrawals
+	pub type TotalUnbonding<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The total unbonding points of the queued and scheduled withdrawals
+	pub type TotalUnbondingPoints<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::deposit())]
+		/// Deposit BFC to the pool and receive shares at the current share price. The deposit is
+		/// nominated to the candidate selected by the configured strategy.
+		pub fn deposit(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(amount >= T::MinDeposit::get(), Error::<T>::DepositBelowMin);
+			Self::sync_pool();
+			let shares = Self::balance_to_shares(amount);
+			ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+			let candidate = Self::deposit_target(amount).ok_or(Error::<T>::NoAvailableCandidate)?;
+
+			let pool = Self::account_id();
+			T::Currency::transfer(&who, &pool, amount, ExistenceRequirement::KeepAlive)?;
+			T::NominationManager::nominate(&pool, &candidate, amount)?;
+			TotalPooled::<T>::mutate(|total| *total = total.saturating_add(amount));
+			TotalBonded::<T>::mutate(|total| *total = total.saturating_add(amount));
+
+			Shares::<T>::mutate(&who, |balance| *balance = balance.saturating_add(shares));
+			TotalShares::<T>::mutate(|total| *total = total.saturating_add(shares));
+			Self::deposit_event(Event::Deposited { who, candidate, amount, shares });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw(T::MaxQueuedWithdrawals::get()))]
+		/// Burn the given shares and queue the withdrawal of the backing BFC. Queued withdrawals
+		/// are scheduled on the pool nominations without a pending request, oldest first, and
+		/// become claimable once the nomination decrease delay has passed.
+		pub fn withdraw(origin: OriginFor<T>, shares: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(Shares::<T>::get(&who) >= shares, Error::<T>::InsufficientShares);
+			Self::sync_pool();
+			let amount = Self::shares_to_balance(shares);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				amount >= T::MinWithdrawal::get() || Shares::<T>::get(&who) == shares,
+				Error::<T>::WithdrawalBelowMin
+			);
+
+			let mut queue = WithdrawalQueue::<T>::get();
+			let queued = queue.iter().filter(|entry| entry.who == who).count();
+			ensure!(
+				Unbonding::<T>::decode_len(&who).unwrap_or_default().saturating_add(queued)
+					< T::MaxUnbondingChunks::get() as usize,
+				Error::<T>::TooManyUnbondingChunks
+			);
+			let points = Self::unbonding_balance_to_points(amount);
+			queue
+				.try_push(QueuedWithdrawal { who: who.clone(), points })
+				.map_err(|_| Error::<T>::WithdrawalQueueFull)?;
+			WithdrawalQueue::<T>::put(queue);
+			TotalUnbonding::<T>::mutate(|total| *total = total.saturating_add(amount));
+			TotalUnbondingPoints::<T>::mutate(|total| *total = total.saturating_add(points));
+			TotalPooled::<T>::mutate(|total| *total = total.saturating_sub(amount));
+
+			Self::burn_shares(&who, shares);
+			Self::deposit_event(Event::WithdrawalQueued { who, shares, amount });
+			Self::process_withdrawal_queue();
+			Ok(().into())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim(T::MaxQueuedWithdrawals::get()))]
+		/// Claim every matured withdrawal of the caller. The pool's due nomination requests are
+		/// executed and the queued withdrawals are scheduled beforehand.
+		pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let now = T::NominationManager::current_round();
+			Self::sync_pool();
+			let pool = Self::account_id();
+			Self::execute_due_requests(&pool, now);
+			Self::process_withdrawal_queue();
+
+			let (matured, pending): (Vec<_>, Vec<_>) =
+				Unbonding::<T>::get(&who).into_iter().partition(|c| c.unlock_round <= now);
+			ensure!(!matured.is_empty(), Error::<T>::NothingToClaim);
+
+			let points = matured
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |acc, chunk| acc.saturating_add(chunk.points));
+			let amount = Self::unbonding_points_to_balance(points);
+			T::Currency::transfer(&pool, &who, amount, ExistenceRequirement::AllowDeath)?;
+			TotalUnlocked::<T>::mutate(|total| *total = total.saturating_sub(amount));
+
+			if pending.is_empty() {
+				Unbonding::<T>::remove(&who);
+			} else {
+				// the remaining chunks never exceed the bound
+				Unbonding::<T>::insert(&who, BoundedVec::truncate_from(pending));
+			}
+			TotalUnbonding::<T>::mutate(|total| *total = total.saturating_sub(amount));
+			TotalUnbondingPoints::<T>::mutate(|total| *total = total.saturating_sub(points));
+			Self::deposit_event(Event::Claimed { who, amount });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer())]
+		/// Transfer shares to the given account.
+		pub fn transfer(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			shares: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			Self::transfer_shares(&from, &to, shares)?;
+			Ok(().into())
+		}
+
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		/// Allow the spender to transfer the given amount of the caller's shares.
+		pub fn approve(
+			origin: OriginFor<T>,
+			spender: T::AccountId,
+			shares: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::approve_shares(&owner, &spender, shares);
+			Ok(().into())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_from())]
+		/// Transfer shares of the given owner within the caller's allowance.
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			shares: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let spender = ensure_signed(origin)?;
+			Self::spend_allowance(&from, &spender, shares)?;
+			Self::transfer_shares(&from, &to, shares)?;
+			Ok(().into())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::process_withdrawals(T::MaxQueuedWithdrawals::get())
+		)]
+		/// Execute the pool's due nomination requests and schedule the queued withdrawals on the
+		/// nominations that became available. Callable by anyone.
+		pub fn process_withdrawals(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let now = T::NominationManager::current_round();
+			Self::sync_pool();
+			Self::execute_due_requests(&Self::account_id(), now);
+			Self::process_withdrawal_queue();
+			Ok(().into())
+		}
+	}
+}
@@ -0,0 +1,71 @@
+use sp_std::marker::PhantomData;
+
+/// A candidate that the pooled funds can be nominated to or unbonded from
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NominationTarget<AccountId, Balance> {
+	/// The candidate account
+	pub candidate: AccountId,
+	/// The amount that the pool has bonded to the candidate
+	pub bonded: Balance,
+	/// The current voting power of the candidate. Zero if it is not selected for this round.
+	pub voting_power: Balance,
+}
+
+/// The strategy that spreads the pooled funds across candidates
+pub trait NominationStrategy<AccountId, Balance> {
+	/// Selects the candidate that the given deposit will be nominated to.
+	fn deposit_target(
+		targets: &[NominationTarget<AccountId, Balance>],
+		amount: Balance,
+	) -> Option<AccountId>;
+
+	/// Selects the candidate that the given withdrawal will be unbonded from.
+	fn withdraw_target(
+		targets: &[NominationTarget<AccountId, Balance>],
+		amount: Balance,
+	) -> Option<AccountId>;
+}
+
+/// Keeps the pool's nominations even. Deposits go to the candidate with the smallest pool
+/// nomination and withdrawals are taken from the largest one.
+pub struct EvenSpread<AccountId, Balance>(PhantomData<(AccountId, Balance)>);
+
+impl<AccountId: Clone, Balance: Ord + Copy> NominationStrategy<AccountId, Balance>
+	for EvenSpread<AccountId, Balance>
+{
+	fn deposit_target(
+		targets: &[NominationTarget<AccountId, Balance>],
+		_amount: Balance,
+	) -> Option<AccountId> {
+		targets.iter().min_by_key(|t| t.bonded).map(|t| t.candidate.clone())
+	}
+
+	fn withdraw_target(
+		targets: &[NominationTarget<AccountId, Balance>],
+		_amount: Balance,
+	) -> Option<AccountId> {
+		targets.iter().max_by_key(|t| t.bonded).map(|t| t.candidate.clone())
+	}
+}
+
+/// Supports the decentralization of the validator set. Deposits go to the candidate with the
+/// lowest voting power and withdrawals are taken from the candidate with the highest one.
+pub struct LeastBacked<AccountId, Balance>(PhantomData<(AccountId, Balance)>);
+
+impl<AccountId: Clone, Balance: Ord + Copy> NominationStrategy<AccountId, Balance>
+	for LeastBacked<AccountId, Balance>
+{
+	fn deposit_target(
+		targets: &[NominationTarget<AccountId, Balance>],
+		_amount: Balance,
+	) -> Option<AccountId> {
+		targets.iter().min_by_key(|t| t.voting_power).map(|t| t.candidate.clone())
+	}
+
+	fn withdraw_target(
+		targets: &[NominationTarget<AccountId, Balance>],
+		_amount: Balance,
+	) -> Option<AccountId> {
+		targets.iter().max_by_key(|t| t.voting_power).map(|t| t.candidate.clone())
+	}
+}
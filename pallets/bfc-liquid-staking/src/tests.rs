@@ -0,0 +1,340 @@
+use crate::{
+	mock::*,
+	strategy::{LeastBacked, NominationStrategy, NominationTarget},
+	Error, Event, Shares, TotalBonded, TotalIdle, TotalShares, TotalUnbonding, TotalUnlocked,
+	Unbonding, WithdrawalQueue, SHARE_UNIT,
+};
+use frame_support::{assert_noop, assert_ok};
+
+fn pool() -> bp_core::AccountId {
+	LiquidStaking::account_id()
+}
+
+#[test]
+fn deposit_mints_shares_and_nominates() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+
+		assert_eq!(Shares::<Test>::get(alice()), 100);
+		assert_eq!(TotalShares::<Test>::get(), 100);
+		assert_eq!(MockStaking::nomination(&pool(), &validator_1()), 100);
+		assert_eq!(Balances::reserved_balance(pool()), 100);
+		assert_eq!(Balances::free_balance(alice()), 900);
+		System::assert_last_event(
+			Event::Deposited { who: alice(), candidate: validator_1(), amount: 100, shares: 100 }
+				.into(),
+		);
+
+		// the next deposit is spread to the candidate with the smallest pool nomination
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+		assert_eq!(MockStaking::nomination(&pool(), &validator_2()), 100);
+		assert_eq!(LiquidStaking::share_price(), SHARE_UNIT);
+	});
+}
+
+#[test]
+fn deposit_below_min_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 9),
+			Error::<Test>::DepositBelowMin
+		);
+	});
+}
+
+#[test]
+fn rewards_compound_into_share_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		MockStaking::reward(&pool(), &validator_1(), 50);
+
+		assert_eq!(LiquidStaking::total_pooled(), 150);
+		assert_eq!(LiquidStaking::share_price(), SHARE_UNIT * 3 / 2);
+
+		// new members receive shares at the increased price
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 150));
+		assert_eq!(Shares::<Test>::get(bob()), 100);
+		assert_eq!(LiquidStaking::shares_to_balance(100), 150);
+	});
+}
+
+#[test]
+fn donations_do_not_change_share_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		let _ = Balances::deposit_creating(&pool(), 50);
+
+		assert_eq!(LiquidStaking::total_pooled(), 100);
+		assert_eq!(LiquidStaking::share_price(), SHARE_UNIT);
+
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+		assert_eq!(Shares::<Test>::get(bob()), 100);
+	});
+}
+
+#[test]
+fn withdraw_and_claim_after_delay() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 50));
+		let unlock_round = 1 + NOMINATION_DELAY;
+		System::assert_has_event(
+			Event::WithdrawalQueued { who: alice(), shares: 50, amount: 50 }.into(),
+		);
+		System::assert_last_event(
+			Event::WithdrawalScheduled { who: alice(), amount: 50, unlock_round }.into(),
+		);
+		assert_eq!(MockStaking::pending_request(&pool(), &validator_2()), Some(50));
+		assert_eq!(Shares::<Test>::get(alice()), 50);
+		assert_eq!(TotalUnbonding::<Test>::get(), 50);
+		assert_eq!(LiquidStaking::total_pooled(), 150);
+		assert_eq!(LiquidStaking::share_price(), SHARE_UNIT);
+
+		assert_noop!(
+			LiquidStaking::claim(RuntimeOrigin::signed(alice())),
+			Error::<Test>::NothingToClaim
+		);
+
+		MockStaking::set_round(unlock_round);
+		assert_ok!(LiquidStaking::claim(RuntimeOrigin::signed(alice())));
+		System::assert_last_event(Event::Claimed { who: alice(), amount: 50 }.into());
+		assert_eq!(Balances::free_balance(alice()), 950);
+		assert_eq!(MockStaking::nomination(&pool(), &validator_2()), 50);
+		assert_eq!(TotalUnbonding::<Test>::get(), 0);
+		assert!(Unbonding::<Test>::get(alice()).is_empty());
+		assert_eq!(LiquidStaking::total_pooled(), 150);
+	});
+}
+
+#[test]
+fn withdraw_is_split_across_nominations() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+		assert_ok!(LiquidStaking::transfer(RuntimeOrigin::signed(bob()), alice(), 100));
+
+		// the largest nomination is revoked and the remainder is taken from the next one
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 150));
+		let unlock_round = 1 + NOMINATION_DELAY;
+		System::assert_last_event(
+			Event::WithdrawalScheduled { who: alice(), amount: 150, unlock_round }.into(),
+		);
+		assert_eq!(MockStaking::pending_request(&pool(), &validator_2()), Some(100));
+		assert_eq!(MockStaking::pending_request(&pool(), &validator_1()), Some(50));
+		assert_eq!(LiquidStaking::unbonding_of(&alice()), vec![(150, unlock_round)]);
+		assert!(WithdrawalQueue::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn withdraw_keeps_remaining_nomination_above_minimum() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+
+		// the nomination can only be decreased down to the minimum nomination
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 98));
+		let unlock_round = 1 + NOMINATION_DELAY;
+		assert_eq!(
+			MockStaking::pending_request(&pool(), &validator_1()),
+			Some(100 - MIN_NOMINATION)
+		);
+		assert_eq!(
+			LiquidStaking::unbonding_of(&alice()),
+			vec![(100 - MIN_NOMINATION, unlock_round), (3, 0)]
+		);
+		assert_eq!(WithdrawalQueue::<Test>::get().len(), 1);
+	});
+}
+
+#[test]
+fn queued_withdrawal_is_scheduled_once_a_nomination_is_available() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+		assert_noop!(
+			LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 101),
+			Error::<Test>::InsufficientShares
+		);
+
+		// each nomination serves a single pending request
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 10));
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 10));
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 10));
+		System::assert_last_event(
+			Event::WithdrawalQueued { who: alice(), shares: 10, amount: 10 }.into(),
+		);
+		let unlock_round = 1 + NOMINATION_DELAY;
+		assert_eq!(LiquidStaking::unbonding_of(&alice()), vec![(20, unlock_round), (10, 0)]);
+
+		// the queued withdrawal occupies the last unbonding chunk
+		assert_noop!(
+			LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 10),
+			Error::<Test>::TooManyUnbondingChunks
+		);
+
+		MockStaking::set_round(unlock_round);
+		assert_ok!(LiquidStaking::process_withdrawals(RuntimeOrigin::signed(bob())));
+		let next_unlock_round = unlock_round + NOMINATION_DELAY;
+		System::assert_last_event(
+			Event::WithdrawalScheduled {
+				who: alice(),
+				amount: 10,
+				unlock_round: next_unlock_round,
+			}
+			.into(),
+		);
+		assert!(WithdrawalQueue::<Test>::get().is_empty());
+
+		assert_ok!(LiquidStaking::claim(RuntimeOrigin::signed(alice())));
+		System::assert_last_event(Event::Claimed { who: alice(), amount: 20 }.into());
+		assert_eq!(Balances::free_balance(alice()), 920);
+		assert_eq!(LiquidStaking::unbonding_of(&alice()), vec![(10, next_unlock_round)]);
+	});
+}
+
+#[test]
+fn slashes_are_shared_with_pending_withdrawals() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 50));
+		let unlock_round = 1 + NOMINATION_DELAY;
+
+		// 150 pooled and 50 unbonding share the slash pro rata
+		MockStaking::slash(&pool(), &validator_1(), 40);
+		assert_eq!(LiquidStaking::total_pooled(), 120);
+		assert_eq!(LiquidStaking::share_price(), SHARE_UNIT * 4 / 5);
+		assert_eq!(LiquidStaking::unbonding_of(&alice()), vec![(40, unlock_round)]);
+
+		assert_ok!(LiquidStaking::process_withdrawals(RuntimeOrigin::signed(bob())));
+		System::assert_last_event(Event::Slashed { pooled: 30, unbonding: 10 }.into());
+		assert_eq!(TotalUnbonding::<Test>::get(), 40);
+
+		MockStaking::set_round(unlock_round);
+		assert_ok!(LiquidStaking::claim(RuntimeOrigin::signed(alice())));
+		System::assert_last_event(Event::Claimed { who: alice(), amount: 40 }.into());
+		assert_eq!(LiquidStaking::total_pooled(), 120);
+	});
+}
+
+#[test]
+fn stake_returned_by_a_leaving_candidate_is_not_slashed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 50));
+		let unlock_round = 1 + NOMINATION_DELAY;
+		assert_eq!(MockStaking::pending_request(&pool(), &validator_2()), Some(50));
+
+		// the candidate leaves and the nomination is unreserved with its pending request
+		MockStaking::leave_candidate(&validator_2());
+		assert_eq!(Balances::free_balance(pool()), 100);
+		assert_eq!(LiquidStaking::total_pooled(), 150);
+
+		assert_ok!(LiquidStaking::process_withdrawals(RuntimeOrigin::signed(bob())));
+		System::assert_has_event(Event::StakeReturned { amount: 100 }.into());
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::LiquidStaking(Event::Slashed { .. })
+		)));
+
+		// the amount of the dropped request is held for the withdrawal and the rest is
+		// nominated again
+		System::assert_last_event(
+			Event::Renominated { candidate: validator_1(), amount: 50 }.into(),
+		);
+		assert_eq!(MockStaking::nomination(&pool(), &validator_1()), 150);
+		assert_eq!(TotalBonded::<Test>::get(), 150);
+		assert_eq!(TotalUnlocked::<Test>::get(), 50);
+		assert_eq!(TotalIdle::<Test>::get(), 0);
+		assert_eq!(LiquidStaking::total_pooled(), 150);
+		assert_eq!(LiquidStaking::share_price(), SHARE_UNIT);
+
+		MockStaking::set_round(unlock_round);
+		assert_ok!(LiquidStaking::claim(RuntimeOrigin::signed(alice())));
+		System::assert_last_event(Event::Claimed { who: alice(), amount: 50 }.into());
+		assert_eq!(Balances::free_balance(alice()), 950);
+		assert_eq!(Balances::free_balance(pool()), 0);
+		assert_eq!(TotalUnlocked::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn kicked_nomination_only_books_the_missing_stake_as_a_slash() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(bob()), 100));
+
+		// a slash is followed by a bottom nomination kick before the pool is synced
+		MockStaking::slash(&pool(), &validator_2(), 20);
+		MockStaking::kick(&pool(), &validator_2());
+		assert_eq!(LiquidStaking::total_pooled(), 180);
+
+		assert_ok!(LiquidStaking::process_withdrawals(RuntimeOrigin::signed(bob())));
+		System::assert_has_event(Event::StakeReturned { amount: 80 }.into());
+		System::assert_has_event(Event::Slashed { pooled: 20, unbonding: 0 }.into());
+		System::assert_last_event(
+			Event::Renominated { candidate: validator_2(), amount: 80 }.into(),
+		);
+		assert_eq!(MockStaking::nomination(&pool(), &validator_2()), 80);
+		assert_eq!(TotalBonded::<Test>::get(), 180);
+		assert_eq!(LiquidStaking::total_pooled(), 180);
+		assert_eq!(LiquidStaking::share_price(), SHARE_UNIT * 9 / 10);
+	});
+}
+
+#[test]
+fn withdrawals_below_the_minimum_must_withdraw_every_share() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+		assert_noop!(
+			LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 9),
+			Error::<Test>::WithdrawalBelowMin
+		);
+
+		assert_ok!(LiquidStaking::transfer(RuntimeOrigin::signed(alice()), bob(), 95));
+		assert_ok!(LiquidStaking::withdraw(RuntimeOrigin::signed(alice()), 5));
+		assert_eq!(Shares::<Test>::get(alice()), 0);
+	});
+}
+
+#[test]
+fn transfer_and_allowance_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::deposit(RuntimeOrigin::signed(alice()), 100));
+
+		assert_ok!(LiquidStaking::transfer(RuntimeOrigin::signed(alice()), bob(), 30));
+		assert_eq!(Shares::<Test>::get(alice()), 70);
+		assert_eq!(Shares::<Test>::get(bob()), 30);
+		assert_eq!(TotalShares::<Test>::get(), 100);
+
+		assert_ok!(LiquidStaking::approve(RuntimeOrigin::signed(alice()), bob(), 20));
+		assert_noop!(
+			LiquidStaking::transfer_from(RuntimeOrigin::signed(bob()), alice(), bob(), 21),
+			Error::<Test>::InsufficientAllowance
+		);
+		assert_ok!(LiquidStaking::transfer_from(RuntimeOrigin::signed(bob()), alice(), bob(), 20));
+		assert_eq!(Shares::<Test>::get(alice()), 50);
+		assert_eq!(Shares::<Test>::get(bob()), 50);
+		assert_eq!(crate::Allowances::<Test>::get(alice(), bob()), 0);
+
+		assert_noop!(
+			LiquidStaking::transfer(RuntimeOrigin::signed(bob()), alice(), 51),
+			Error::<Test>::InsufficientShares
+		);
+	});
+}
+
+#[test]
+fn least_backed_strategy_prefers_lowest_voting_power() {
+	let targets = vec![
+		NominationTarget { candidate: 1u64, bonded: 10u128, voting_power: 500u128 },
+		NominationTarget { candidate: 2u64, bonded: 50u128, voting_power: 100u128 },
+		NominationTarget { candidate: 3u64, bonded: 30u128, voting_power: 300u128 },
+	];
+	assert_eq!(LeastBacked::<u64, u128>::deposit_target(&targets, 10), Some(2));
+	assert_eq!(LeastBacked::<u64, u128>::withdraw_target(&targets, 10), Some(1));
+	assert_eq!(LeastBacked::<u64, u128>::deposit_target(&[], 10), None);
+}
@@ -0,0 +1,128 @@
+//! Hand-estimated weights for `pallet_bfc_liquid_staking`, not generated by the benchmark CLI
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_bfc_liquid_staking`.
+pub trait WeightInfo {
+	fn deposit() -> Weight;
+	fn withdraw(n: u32) -> Weight;
+	fn claim(n: u32) -> Weight;
+	fn transfer() -> Weight;
+	fn approve() -> Weight;
+	fn transfer_from() -> Weight;
+	fn process_withdrawals(n: u32) -> Weight;
+}
+
+/// Weights for `pallet_bfc_liquid_staking` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn deposit() -> Weight {
+		Weight::from_parts(98_430_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn withdraw(n: u32) -> Weight {
+		Weight::from_parts(112_760_000, 0)
+			.saturating_add(Weight::from_parts(21_350_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+			.saturating_add(T::DbWeight::get().reads((n as u64).saturating_mul(1 as u64)))
+			.saturating_add(T::DbWeight::get().writes((n as u64).saturating_mul(1 as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn claim(n: u32) -> Weight {
+		Weight::from_parts(131_920_000, 0)
+			.saturating_add(Weight::from_parts(21_870_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+			.saturating_add(T::DbWeight::get().reads((n as u64).saturating_mul(1 as u64)))
+			.saturating_add(T::DbWeight::get().writes((n as u64).saturating_mul(1 as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn transfer() -> Weight {
+		Weight::from_parts(24_180_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn approve() -> Weight {
+		Weight::from_parts(13_050_000, 0).saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn transfer_from() -> Weight {
+		Weight::from_parts(31_640_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn process_withdrawals(n: u32) -> Weight {
+		Weight::from_parts(84_210_000, 0)
+			.saturating_add(Weight::from_parts(21_410_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().reads((n as u64).saturating_mul(1 as u64)))
+			.saturating_add(T::DbWeight::get().writes((n as u64).saturating_mul(1 as u64)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn deposit() -> Weight {
+		Weight::from_parts(98_430_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn withdraw(n: u32) -> Weight {
+		Weight::from_parts(112_760_000, 0)
+			.saturating_add(Weight::from_parts(21_350_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+			.saturating_add(RocksDbWeight::get().reads((n as u64).saturating_mul(1 as u64)))
+			.saturating_add(RocksDbWeight::get().writes((n as u64).saturating_mul(1 as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn claim(n: u32) -> Weight {
+		Weight::from_parts(131_920_000, 0)
+			.saturating_add(Weight::from_parts(21_870_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(16 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+			.saturating_add(RocksDbWeight::get().reads((n as u64).saturating_mul(1 as u64)))
+			.saturating_add(RocksDbWeight::get().writes((n as u64).saturating_mul(1 as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn transfer() -> Weight {
+		Weight::from_parts(24_180_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn approve() -> Weight {
+		Weight::from_parts(13_050_000, 0).saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn transfer_from() -> Weight {
+		Weight::from_parts(31_640_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn process_withdrawals(n: u32) -> Weight {
+		Weight::from_parts(84_210_000, 0)
+			.saturating_add(Weight::from_parts(21_410_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads((n as u64).saturating_mul(1 as u64)))
+			.saturating_add(RocksDbWeight::get().writes((n as u64).saturating_mul(1 as u64)))
+	}
+}
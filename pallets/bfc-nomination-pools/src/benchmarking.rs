@@ -0,0 +1,187 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use bp_staking::traits::NominationManager;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::vec::Vec;
+
+/// The amount of a single bond that satisfies every minimum of the pool and the staking pallet
+fn bond_amount<T: Config>() -> BalanceOf<T> {
+	T::MinJoinBond::get()
+		.max(T::NominationManager::min_nominator_stake())
+		.saturating_mul(2u32.into())
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account::<T::AccountId>(name, index, 0);
+	T::Currency::make_free_balance_be(&who, bond_amount::<T>().saturating_mul(1_000u32.into()));
+	who
+}
+
+/// Creates a pool through the extrinsic and returns its operator and identifier
+fn create_pool<T: Config>() -> (T::AccountId, PoolId) {
+	let candidate = T::NominationManager::set_benchmark(0).expect("candidate joins");
+	let operator = funded_account::<T>("operator", 0);
+	let pool_id = NextPoolId::<T>::get();
+	Pallet::<T>::create(
+		RawOrigin::Signed(operator.clone()).into(),
+		candidate,
+		bond_amount::<T>(),
+		T::MaxPoolCommission::get(),
+	)
+	.expect("pool is created");
+	(operator, pool_id)
+}
+
+/// Funds the given account and joins the pool through the extrinsic
+fn joined<T: Config>(name: &'static str, index: u32, pool_id: PoolId) -> T::AccountId {
+	let who = funded_account::<T>(name, index);
+	Pallet::<T>::join(RawOrigin::Signed(who.clone()).into(), pool_id, bond_amount::<T>())
+		.expect("member joins");
+	who
+}
+
+/// Pays a round reward into the reward account of the pool
+fn reward<T: Config>(pool_id: PoolId) {
+	let _ =
+		T::Currency::deposit_creating(&Pallet::<T>::reward_account(pool_id), bond_amount::<T>());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create() {
+		let candidate = T::NominationManager::set_benchmark(0).expect("candidate joins");
+		let caller = funded_account::<T>("operator", 0);
+		let pool_id = NextPoolId::<T>::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), candidate, bond_amount::<T>(), T::MaxPoolCommission::get());
+
+		assert!(Pools::<T>::contains_key(pool_id));
+	}
+
+	#[benchmark]
+	fn join() {
+		let (_, pool_id) = create_pool::<T>();
+		// a member that bonds more is paid out its pending rewards beforehand
+		let caller = joined::<T>("member", 0, pool_id);
+		reward::<T>(pool_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), pool_id, bond_amount::<T>());
+
+		assert_eq!(
+			Pallet::<T>::member_balance(&caller),
+			bond_amount::<T>().saturating_mul(2u32.into())
+		);
+	}
+
+	#[benchmark]
+	fn unbond() {
+		let (_, pool_id) = create_pool::<T>();
+		let caller = joined::<T>("member", 0, pool_id);
+		reward::<T>(pool_id);
+		let amount = Pallet::<T>::member_balance(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), amount);
+
+		assert!(Pallet::<T>::member_balance(&caller).is_zero());
+	}
+
+	#[benchmark]
+	fn withdraw_unbonded(n: Linear<1, { T::MaxUnbondingChunks::get() }>) {
+		let (_, pool_id) = create_pool::<T>();
+		let caller = joined::<T>("member", 0, pool_id);
+
+		// `n` executed batches that the caller has unbonded into
+		let amount = bond_amount::<T>();
+		let chunks: Vec<_> = (0..n)
+			.map(|batch| {
+				UnbondingBatches::<T>::insert(
+					pool_id,
+					batch,
+					UnbondingBatch {
+						points: amount,
+						balance: amount,
+						execute_round: Some(T::NominationManager::current_round()),
+						unlocked: true,
+					},
+				);
+				UnbondingChunk { batch, points: amount }
+			})
+			.collect();
+		Unbonding::<T>::insert(&caller, BoundedVec::truncate_from(chunks));
+		let total = amount.saturating_mul(n.into());
+		Pools::<T>::mutate(pool_id, |pool| {
+			if let Some(pool) = pool {
+				pool.withdrawable = pool.withdrawable.saturating_add(total);
+				pool.current_batch = n;
+			}
+		});
+		let _ = T::Currency::deposit_creating(&Pallet::<T>::bonded_account(pool_id), total);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(Unbonding::<T>::get(&caller).is_empty());
+	}
+
+	#[benchmark]
+	fn claim_rewards() {
+		let (_, pool_id) = create_pool::<T>();
+		let caller = joined::<T>("member", 0, pool_id);
+		reward::<T>(pool_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(Pallet::<T>::pending_rewards(&caller).is_zero());
+	}
+
+	#[benchmark]
+	fn set_commission() {
+		let (operator, pool_id) = create_pool::<T>();
+		reward::<T>(pool_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(operator), pool_id, Zero::zero());
+
+		assert!(Pools::<T>::get(pool_id).is_some_and(|pool| pool.commission.is_zero()));
+	}
+
+	#[benchmark]
+	fn claim_commission() {
+		let (operator, pool_id) = create_pool::<T>();
+		reward::<T>(pool_id);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(operator), pool_id);
+
+		assert!(Pools::<T>::get(pool_id).is_some_and(|pool| pool.pending_commission.is_zero()));
+	}
+
+	#[benchmark]
+	fn poke() {
+		let (_, pool_id) = create_pool::<T>();
+		// the first unbonding is scheduled and the second one waits in the current batch
+		let first = joined::<T>("member", 0, pool_id);
+		let second = joined::<T>("member", 1, pool_id);
+		for who in [first, second] {
+			let amount = Pallet::<T>::member_balance(&who);
+			Pallet::<T>::unbond(RawOrigin::Signed(who).into(), amount).expect("member unbonds");
+		}
+		let caller = funded_account::<T>("caller", 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), pool_id);
+	}
+
+	impl_benchmark_test_suite!(Pallet, mock::new_test_ext(), mock::Test);
+}
@@ -0,0 +1,113 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod pallet;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use pallet::pallet::*;
+use weights::WeightInfo;
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+
+use frame_support::{pallet_prelude::MaxEncodedLen, traits::Currency};
+
+use bp_staking::RoundIndex;
+use sp_runtime::{FixedU128, Perbill, RuntimeDebug};
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The type that indicates the identifier of a nomination pool
+pub type PoolId = u32;
+
+/// The type that indicates the identifier of an unbonding batch of a pool
+pub type BatchId = u32;
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The sub accounts owned by each nomination pool
+pub enum PoolAccount {
+	/// The account that nominates the pooled funds
+	Bonded,
+	/// The account that receives the round rewards of the pool
+	Reward,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The state of a nomination pool
+pub struct PoolInfo<AccountId, Balance> {
+	/// The account that created the pool and receives its commission
+	pub operator: AccountId,
+	/// The candidate that the pool nominates
+	pub candidate: AccountId,
+	/// The commission taken from the pool rewards
+	pub commission: Perbill,
+	/// The total bonded points of the pool members
+	pub points: Balance,
+	/// The bonded amount that backs the points of the pool members. Slashes of the pool
+	/// nomination decrease it, which lowers the value of every point.
+	pub active: Balance,
+	/// The amount that the pool has nominated according to its own accounting. A drop of the
+	/// actual nominated amount is either a slash or a stake returned into the bonded account that
+	/// is not accounted yet.
+	pub total_bonded: Balance,
+	/// The balance of the unlocked batches that is held in the bonded account until withdrawn
+	pub withdrawable: Balance,
+	/// The stake that the staking pallet has returned into the bonded account because the
+	/// candidate left or kicked the pool nomination. It still backs the points of the pool
+	/// members and unlocks the next unbonding batches without a nomination decrease.
+	pub returned: Balance,
+	/// The number of pool members, including members that are still unbonding
+	pub member_count: u32,
+	/// The unbonding batch that collects the unbondings until it can be scheduled
+	pub current_batch: BatchId,
+	/// The unbonding batch whose nomination decrease is pending in the staking pallet
+	pub scheduled_batch: Option<BatchId>,
+	/// The accumulated member rewards per point
+	pub reward_counter: FixedU128,
+	/// The reward account balance that has already been accounted into the reward counter
+	pub last_recorded_reward_balance: Balance,
+	/// The commission that has not been claimed by the operator yet
+	pub pending_commission: Balance,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The state of a pool member
+pub struct PoolMember<Balance> {
+	/// The pool that the member joined
+	pub pool_id: PoolId,
+	/// The bonded points of the member
+	pub points: Balance,
+	/// The reward counter of the pool at the last reward payout of the member
+	pub last_reward_counter: FixedU128,
+}
+
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The unbondings of a pool that are unbonded by a single nomination decrease
+pub struct UnbondingBatch<Balance> {
+	/// The total unbonding points of the batch
+	pub points: Balance,
+	/// The amount of BFC that is owed to the batch. It shares the slashes of the pool nomination
+	/// until the batch is unlocked.
+	pub balance: Balance,
+	/// The round that the nomination decrease becomes executable. `None` until it is scheduled.
+	pub execute_round: Option<RoundIndex>,
+	/// Whether the nomination decrease has been executed and the balance is withdrawable
+	pub unlocked: bool,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// A pending withdrawal of a pool member
+pub struct UnbondingChunk<Balance> {
+	/// The unbonding batch of the pool that the withdrawal belongs to
+	pub batch: BatchId,
+	/// The unbonding points of the member in the batch
+	pub points: Balance,
+}
@@ -0,0 +1,367 @@
+use super::pallet::*;
+
+use crate::{
+	BalanceOf, BatchId, PoolAccount, PoolId, PoolInfo, PoolMember, UnbondingBatch, UnbondingChunk,
+};
+
+use bp_staking::{traits::NominationManager, RoundIndex};
+use frame_support::traits::{Currency, ExistenceRequirement};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AccountIdConversion, Get, Saturating, UniqueSaturatedInto, Zero},
+	DispatchError, FixedPointNumber, FixedU128, Rounding,
+};
+use sp_std::vec::Vec;
+
+impl<T: Config> Pallet<T> {
+	/// The account that nominates the funds of the given pool
+	pub fn bonded_account(pool_id: PoolId) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating((PoolAccount::Bonded, pool_id))
+	}
+
+	/// The account that receives the round rewards of the given pool
+	pub fn reward_account(pool_id: PoolId) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating((PoolAccount::Reward, pool_id))
+	}
+
+	/// Accounts the rewards that have been paid into the reward account since the last update.
+	/// The commission is set aside for the operator and the rest is distributed per point.
+	pub(crate) fn update_rewards(pool_id: PoolId, pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>) {
+		let balance = T::Currency::free_balance(&Self::reward_account(pool_id));
+		let earned = balance.saturating_sub(pool.last_recorded_reward_balance);
+		if earned.is_zero() {
+			return;
+		}
+		let commission = pool.commission * earned;
+		let members_share = earned.saturating_sub(commission);
+		if pool.points.is_zero() {
+			// no member can be rewarded, the whole amount goes to the operator
+			pool.pending_commission = pool.pending_commission.saturating_add(earned);
+		} else {
+			pool.pending_commission = pool.pending_commission.saturating_add(commission);
+			pool.reward_counter =
+				pool.reward_counter.saturating_add(FixedU128::saturating_from_rational(
+					UniqueSaturatedInto::<u128>::unique_saturated_into(members_share),
+					UniqueSaturatedInto::<u128>::unique_saturated_into(pool.points),
+				));
+		}
+		pool.last_recorded_reward_balance = balance;
+	}
+
+	/// The rewards that the member has earned since its last payout
+	fn earned_rewards(
+		pool: &PoolInfo<T::AccountId, BalanceOf<T>>,
+		member: &PoolMember<BalanceOf<T>>,
+	) -> BalanceOf<T> {
+		let points: u128 = member.points.unique_saturated_into();
+		pool.reward_counter
+			.saturating_sub(member.last_reward_counter)
+			.saturating_mul_int(points)
+			.unique_saturated_into()
+	}
+
+	/// Pays out the earned rewards of the member. The pool rewards must be updated beforehand.
+	/// Returns the paid amount.
+	pub(crate) fn payout_rewards(
+		who: &T::AccountId,
+		member: &mut PoolMember<BalanceOf<T>>,
+		pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let amount = Self::earned_rewards(pool, member);
+		member.last_reward_counter = pool.reward_counter;
+		if !amount.is_zero() {
+			T::Currency::transfer(
+				&Self::reward_account(member.pool_id),
+				who,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			pool.last_recorded_reward_balance =
+				pool.last_recorded_reward_balance.saturating_sub(amount);
+			Self::deposit_event(Event::RewardsPaid {
+				member: who.clone(),
+				pool_id: member.pool_id,
+				amount,
+			});
+		}
+		Ok(amount)
+	}
+
+	fn mul_div(a: BalanceOf<T>, b: BalanceOf<T>, c: BalanceOf<T>) -> BalanceOf<T> {
+		multiply_by_rational_with_rounding(
+			a.unique_saturated_into(),
+			b.unique_saturated_into(),
+			c.unique_saturated_into(),
+			Rounding::Down,
+		)
+		.map(|result| result.unique_saturated_into())
+		.unwrap_or_else(Zero::zero)
+	}
+
+	/// The bonded amount that the given points of the pool are worth
+	pub(crate) fn points_to_balance(
+		pool: &PoolInfo<T::AccountId, BalanceOf<T>>,
+		points: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		Self::mul_div(points, pool.active, pool.points)
+	}
+
+	/// The points that the given bonded amount is worth. A point is worth one unit while the
+	/// pool has no points or no active stake.
+	pub(crate) fn balance_to_points(
+		pool: &PoolInfo<T::AccountId, BalanceOf<T>>,
+		amount: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		if pool.points.is_zero() || pool.active.is_zero() {
+			return amount;
+		}
+		Self::mul_div(amount, pool.points, pool.active)
+	}
+
+	/// Accounts the drop of the pool nomination. The part that has been returned into the free
+	/// balance of the bonded account, because the candidate left or kicked the nomination, is
+	/// booked as a returned stake. The rest is a slash that is shared pro rata between the
+	/// nominated stake of the members and the unbonding batches that have not been executed yet.
+	pub(crate) fn sync_slashes(pool_id: PoolId, pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>) {
+		let bonded = Self::bonded_account(pool_id);
+		let nominated = T::NominationManager::total_nominated(&bonded);
+		if nominated >= pool.total_bonded {
+			return;
+		}
+		let dropped = pool.total_bonded.saturating_sub(nominated);
+		let held = pool.withdrawable.saturating_add(pool.returned);
+		let returned = T::Currency::free_balance(&bonded).saturating_sub(held).min(dropped);
+		pool.total_bonded = nominated;
+		if !returned.is_zero() {
+			Self::book_returned_stake(pool_id, pool, returned);
+		}
+
+		let slash = dropped.saturating_sub(returned);
+		if slash.is_zero() {
+			return;
+		}
+
+		let mut batches: Vec<(BatchId, UnbondingBatch<BalanceOf<T>>)> =
+			[pool.scheduled_batch, Some(pool.current_batch)]
+				.into_iter()
+				.flatten()
+				.filter_map(|id| UnbondingBatches::<T>::get(pool_id, id).map(|batch| (id, batch)))
+				.filter(|(_, batch)| !batch.unlocked)
+				.collect();
+		// the returned stake is no longer nominated and does not share the slash
+		let nominated_active = pool.active.saturating_sub(pool.returned);
+		let total = batches
+			.iter()
+			.fold(nominated_active, |acc, (_, batch)| acc.saturating_add(batch.balance));
+
+		let mut remainder = slash;
+		for (id, batch) in batches.iter_mut() {
+			let share = Self::mul_div(slash, batch.balance, total);
+			batch.balance = batch.balance.saturating_sub(share);
+			remainder = remainder.saturating_sub(share);
+			UnbondingBatches::<T>::insert(pool_id, *id, batch.clone());
+		}
+		pool.active = pool.active.saturating_sub(remainder);
+		Self::deposit_event(Event::Slashed { pool_id, amount: slash });
+	}
+
+	/// Books the stake that the staking pallet has returned into the bonded account. The pending
+	/// nomination requests have been removed with the nomination, so the returned stake unlocks
+	/// the scheduled and the current batch first. The rest is kept as the returned stake of the
+	/// members.
+	fn book_returned_stake(
+		pool_id: PoolId,
+		pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>,
+		amount: BalanceOf<T>,
+	) {
+		let mut remaining = amount;
+		for id in [pool.scheduled_batch, Some(pool.current_batch)].into_iter().flatten() {
+			let Some(mut batch) = UnbondingBatches::<T>::get(pool_id, id) else {
+				continue;
+			};
+			if batch.unlocked || batch.balance > remaining {
+				continue;
+			}
+			remaining = remaining.saturating_sub(batch.balance);
+			pool.withdrawable = pool.withdrawable.saturating_add(batch.balance);
+			batch.unlocked = true;
+			UnbondingBatches::<T>::insert(pool_id, id, batch);
+			if pool.scheduled_batch == Some(id) {
+				pool.scheduled_batch = None;
+			} else {
+				pool.current_batch = id.saturating_add(1);
+			}
+		}
+		pool.returned = pool.returned.saturating_add(remaining);
+		Self::deposit_event(Event::StakeReturned { pool_id, amount });
+	}
+
+	/// Adds the given amount to an unbonding batch of the pool and returns the batch points that
+	/// it is worth
+	pub(crate) fn add_to_batch(
+		pool_id: PoolId,
+		batch_id: BatchId,
+		amount: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		let mut batch = UnbondingBatches::<T>::get(pool_id, batch_id).unwrap_or_default();
+		let points = if batch.points.is_zero() || batch.balance.is_zero() {
+			amount
+		} else {
+			Self::mul_div(amount, batch.points, batch.balance)
+		};
+		batch.points = batch.points.saturating_add(points);
+		batch.balance = batch.balance.saturating_add(amount);
+		UnbondingBatches::<T>::insert(pool_id, batch_id, batch);
+		points
+	}
+
+	/// Removes the share of the given chunk from its batch and returns the withdrawable amount.
+	/// Returns `None` if the batch has not been executed yet.
+	pub(crate) fn withdraw_from_batch(
+		pool_id: PoolId,
+		chunk: &UnbondingChunk<BalanceOf<T>>,
+	) -> Option<BalanceOf<T>> {
+		let mut batch = UnbondingBatches::<T>::get(pool_id, chunk.batch)?;
+		if !batch.unlocked {
+			return None;
+		}
+		let amount = if chunk.points >= batch.points {
+			batch.balance
+		} else {
+			Self::mul_div(chunk.points, batch.balance, batch.points)
+		};
+		batch.points = batch.points.saturating_sub(chunk.points);
+		batch.balance = batch.balance.saturating_sub(amount);
+		if batch.points.is_zero() {
+			UnbondingBatches::<T>::remove(pool_id, chunk.batch);
+		} else {
+			UnbondingBatches::<T>::insert(pool_id, chunk.batch, batch);
+		}
+		Some(amount)
+	}
+
+	/// Accounts the slashes of the pool, executes the due nomination request of the scheduled
+	/// batch and schedules the current batch once the pool has no pending request left. The
+	/// current batch is unlocked at once if the returned stake covers it. A failed execution or
+	/// schedule is retried on the next pool interaction.
+	pub(crate) fn process_unbonding(
+		pool_id: PoolId,
+		pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>,
+	) {
+		Self::sync_slashes(pool_id, pool);
+		let bonded = Self::bonded_account(pool_id);
+		let now = T::NominationManager::current_round();
+
+		if let Some(batch_id) = pool.scheduled_batch {
+			let due = T::NominationManager::pending_requests(&bonded)
+				.iter()
+				.any(|(_, _, when)| *when <= now);
+			let before = T::NominationManager::total_nominated(&bonded);
+			if due
+				&& T::NominationManager::execute_nomination_request(&bonded, &pool.candidate)
+					.is_ok()
+			{
+				let unbonded =
+					before.saturating_sub(T::NominationManager::total_nominated(&bonded));
+				pool.total_bonded = pool.total_bonded.saturating_sub(unbonded);
+				if let Some(mut batch) = UnbondingBatches::<T>::get(pool_id, batch_id) {
+					// the decrease was scheduled before the batch shared a slash, so the surplus
+					// belongs to the active stake and is nominated again
+					let surplus = unbonded.saturating_sub(batch.balance);
+					if !surplus.is_zero()
+						&& T::NominationManager::nominate(&bonded, &pool.candidate, surplus).is_ok()
+					{
+						pool.total_bonded = pool.total_bonded.saturating_add(surplus);
+					} else {
+						batch.balance = unbonded;
+					}
+					pool.withdrawable = pool.withdrawable.saturating_add(batch.balance);
+					batch.unlocked = true;
+					UnbondingBatches::<T>::insert(pool_id, batch_id, batch);
+				}
+				pool.scheduled_batch = None;
+			}
+		}
+
+		if pool.scheduled_batch.is_some()
+			|| !T::NominationManager::pending_requests(&bonded).is_empty()
+		{
+			return;
+		}
+		let batch_id = pool.current_batch;
+		let Some(mut batch) = UnbondingBatches::<T>::get(pool_id, batch_id) else {
+			return;
+		};
+		if batch.balance.is_zero() {
+			return;
+		}
+		if batch.balance <= pool.returned {
+			// the returned stake is already held in the bonded account
+			pool.returned = pool.returned.saturating_sub(batch.balance);
+			pool.withdrawable = pool.withdrawable.saturating_add(batch.balance);
+			batch.execute_round = Some(now);
+			batch.unlocked = true;
+			UnbondingBatches::<T>::insert(pool_id, batch_id, batch);
+			pool.current_batch = batch_id.saturating_add(1);
+			return;
+		}
+		if let Ok(execute_round) = T::NominationManager::schedule_nomination_decrease(
+			&bonded,
+			&pool.candidate,
+			batch.balance,
+		) {
+			Self::deposit_event(Event::UnbondingScheduled {
+				pool_id,
+				batch: batch_id,
+				amount: batch.balance,
+				execute_round,
+			});
+			batch.execute_round = Some(execute_round);
+			UnbondingBatches::<T>::insert(pool_id, batch_id, batch);
+			pool.scheduled_batch = Some(batch_id);
+			pool.current_batch = batch_id.saturating_add(1);
+		}
+	}
+
+	/// Returns the withdrawable amount and the execution round of each pending withdrawal of the
+	/// given account. The round is `None` while the batch waits to be scheduled.
+	pub fn unbonding_of(who: &T::AccountId) -> Vec<(BalanceOf<T>, Option<RoundIndex>)> {
+		let Some(member) = Members::<T>::get(who) else {
+			return Vec::new();
+		};
+		Unbonding::<T>::get(who)
+			.into_iter()
+			.filter_map(|chunk| {
+				let batch = UnbondingBatches::<T>::get(member.pool_id, chunk.batch)?;
+				let amount = if chunk.points >= batch.points {
+					batch.balance
+				} else {
+					Self::mul_div(chunk.points, batch.balance, batch.points)
+				};
+				Some((amount, batch.execute_round))
+			})
+			.collect()
+	}
+
+	/// Returns the bonded amount that the points of the given account are worth
+	pub fn member_balance(who: &T::AccountId) -> BalanceOf<T> {
+		let Some(member) = Members::<T>::get(who) else {
+			return Zero::zero();
+		};
+		Pools::<T>::get(member.pool_id)
+			.map(|pool| Self::points_to_balance(&pool, member.points))
+			.unwrap_or_default()
+	}
+
+	/// Returns the rewards that the given account can claim from its pool
+	pub fn pending_rewards(who: &T::AccountId) -> BalanceOf<T> {
+		let Some(member) = Members::<T>::get(who) else {
+			return Zero::zero();
+		};
+		let Some(mut pool) = Pools::<T>::get(member.pool_id) else {
+			return Zero::zero();
+		};
+		Self::update_rewards(member.pool_id, &mut pool);
+		Self::earned_rewards(&pool, &member)
+	}
+}
@@ -0,0 +1,460 @@
+mod impls;
+
+use crate::{
+	BalanceOf, BatchId, PoolId, PoolInfo, PoolMember, UnbondingBatch, UnbondingChunk, WeightInfo,
+};
+
+use bp_staking::{traits::NominationManager, RoundIndex};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement},
+	PalletId,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Perbill,
+};
+use sp_std::prelude::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	/// Pallet for bfc nomination pools
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configuration trait of this pallet
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The currency type
+		type Currency: Currency<Self::AccountId>;
+		/// The handler of the pool nominations
+		type NominationManager: NominationManager<Self::AccountId, BalanceOf<Self>>;
+		/// The pallet id used to derive the pool accounts
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The minimum amount that a member has to bond to join a pool
+		#[pallet::constant]
+		type MinJoinBond: Get<BalanceOf<Self>>;
+		/// The maximum commission that a pool operator can set
+		#[pallet::constant]
+		type MaxPoolCommission: Get<Perbill>;
+		/// The maximum number of pending withdrawals per member
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pool does not exist
+		PoolDNE,
+		/// The account is not a pool member
+		NotMember,
+		/// The account is already a member of another pool
+		AlreadyMember,
+		/// The bond is below the minimum amount
+		BondBelowMin,
+		/// The operator must keep the minimum nominator stake while the pool has other members
+		OperatorBondBelowMin,
+		/// The caller is not the operator of the pool
+		NotOperator,
+		/// The commission exceeds the maximum pool commission
+		CommissionTooHigh,
+		/// The member does not have enough bonded points
+		InsufficientPoints,
+		/// The member has reached the maximum number of pending withdrawals
+		TooManyUnbondingChunks,
+		/// There is no unlocked withdrawal
+		NothingToWithdraw,
+		/// There is nothing to claim
+		NothingToClaim,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new pool has been created
+		Created {
+			pool_id: PoolId,
+			operator: T::AccountId,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A member has bonded to a pool
+		Bonded { member: T::AccountId, pool_id: PoolId, amount: BalanceOf<T> },
+		/// A member has unbonded from a pool
+		Unbonded { member: T::AccountId, pool_id: PoolId, amount: BalanceOf<T>, batch: BatchId },
+		/// A member has withdrawn its unbonded funds
+		Withdrawn { member: T::AccountId, pool_id: PoolId, amount: BalanceOf<T> },
+		/// The rewards of a member have been paid out
+		RewardsPaid { member: T::AccountId, pool_id: PoolId, amount: BalanceOf<T> },
+		/// An unbonding batch of a pool has been scheduled in the staking pallet
+		UnbondingScheduled {
+			pool_id: PoolId,
+			batch: BatchId,
+			amount: BalanceOf<T>,
+			execute_round: RoundIndex,
+		},
+		/// A slash of the pool nomination has been shared between its members
+		Slashed { pool_id: PoolId, amount: BalanceOf<T> },
+		/// The staking pallet has returned the stake of the pool nomination into the bonded
+		/// account
+		StakeReturned { pool_id: PoolId, amount: BalanceOf<T> },
+		/// The commission of a pool has been set
+		CommissionSet { pool_id: PoolId, old: Perbill, new: Perbill },
+		/// The operator has claimed the commission of a pool
+		CommissionClaimed { pool_id: PoolId, operator: T::AccountId, amount: BalanceOf<T> },
+		/// The last member has left and the pool has been removed
+		Destroyed { pool_id: PoolId },
+	}
+
+	#[pallet::storage]
+	/// The identifier of the next created pool
+	pub type NextPoolId<T: Config> = StorageValue<_, PoolId, ValueQuery>;
+
+	#[pallet::storage]
+	/// The state of each pool
+	pub type Pools<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId, PoolInfo<T::AccountId, BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	/// The state of each pool member. An account can be a member of a single pool.
+	pub type Members<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, PoolMember<BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	/// The unbonding batches of each pool that have not been fully withdrawn
+	pub type UnbondingBatches<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId,
+		Twox64Concat,
+		BatchId,
+		UnbondingBatch<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// The pending withdrawals of each pool member
+	pub type Unbonding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<UnbondingChunk<BalanceOf<T>>, T::MaxUnbondingChunks>,
+		ValueQuery,
+	>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::create())]
+		/// Create a new pool that nominates the given candidate. The caller becomes the operator
+		/// and the first member of the pool. The initial bond must satisfy the minimum nominator
+		/// stake.
+		pub fn create(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+			commission: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let operator = ensure_signed(origin)?;
+			ensure!(!Members::<T>::contains_key(&operator), Error::<T>::AlreadyMember);
+			ensure!(
+				amount >= T::NominationManager::min_nominator_stake(),
+				Error::<T>::OperatorBondBelowMin
+			);
+			ensure!(commission <= T::MaxPoolCommission::get(), Error::<T>::CommissionTooHigh);
+
+			let pool_id = NextPoolId::<T>::get();
+			let bonded = Self::bonded_account(pool_id);
+			T::Currency::transfer(&operator, &bonded, amount, ExistenceRequirement::KeepAlive)?;
+			T::NominationManager::nominate(&bonded, &candidate, amount)?;
+			T::NominationManager::set_reward_payee(&bonded, Self::reward_account(pool_id))?;
+
+			Pools::<T>::insert(
+				pool_id,
+				PoolInfo {
+					operator: operator.clone(),
+					candidate: candidate.clone(),
+					commission,
+					points: amount,
+					active: amount,
+					total_bonded: amount,
+					withdrawable: Zero::zero(),
+					returned: Zero::zero(),
+					member_count: 1,
+					current_batch: 0,
+					scheduled_batch: None,
+					reward_counter: Default::default(),
+					last_recorded_reward_balance: Zero::zero(),
+					pending_commission: Zero::zero(),
+				},
+			);
+			Members::<T>::insert(
+				&operator,
+				PoolMember { pool_id, points: amount, last_reward_counter: Default::default() },
+			);
+			NextPoolId::<T>::put(pool_id.saturating_add(1));
+			Self::deposit_event(Event::Created { pool_id, operator, candidate, amount });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::join())]
+		/// Bond the given amount to a pool. Joins the pool if the caller is not a member yet.
+		/// Pending rewards are paid out before the bond is increased. The member receives points
+		/// at the current value of a point.
+		pub fn join(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolDNE)?;
+			let mut member = match Members::<T>::get(&who) {
+				Some(member) => {
+					ensure!(member.pool_id == pool_id, Error::<T>::AlreadyMember);
+					member
+				},
+				None => {
+					pool.member_count = pool.member_count.saturating_add(1);
+					PoolMember {
+						pool_id,
+						points: Zero::zero(),
+						last_reward_counter: pool.reward_counter,
+					}
+				},
+			};
+			Self::sync_slashes(pool_id, &mut pool);
+			let points = Self::balance_to_points(&pool, amount);
+			ensure!(
+				!points.is_zero()
+					&& Self::points_to_balance(&pool, member.points).saturating_add(amount)
+						>= T::MinJoinBond::get(),
+				Error::<T>::BondBelowMin
+			);
+
+			Self::update_rewards(pool_id, &mut pool);
+			Self::payout_rewards(&who, &mut member, &mut pool)?;
+
+			let bonded = Self::bonded_account(pool_id);
+			T::Currency::transfer(&who, &bonded, amount, ExistenceRequirement::KeepAlive)?;
+			T::NominationManager::nominate(&bonded, &pool.candidate, amount)?;
+
+			member.points = member.points.saturating_add(points);
+			pool.points = pool.points.saturating_add(points);
+			pool.active = pool.active.saturating_add(amount);
+			pool.total_bonded = pool.total_bonded.saturating_add(amount);
+			Members::<T>::insert(&who, member);
+			Pools::<T>::insert(pool_id, pool);
+			Self::deposit_event(Event::Bonded { member: who, pool_id, amount });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::unbond())]
+		/// Unbond the given amount from the caller's pool. The amount joins the current unbonding
+		/// batch of the pool, which is scheduled in the staking pallet once the pool has no pending
+		/// nomination request. It becomes withdrawable when the batch has been executed.
+		pub fn unbond(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut member = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			let pool_id = member.pool_id;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolDNE)?;
+			Self::sync_slashes(pool_id, &mut pool);
+
+			let value = Self::points_to_balance(&pool, member.points);
+			ensure!(!amount.is_zero(), Error::<T>::InsufficientPoints);
+			ensure!(value >= amount, Error::<T>::InsufficientPoints);
+			let points = if amount == value {
+				member.points
+			} else {
+				Self::balance_to_points(&pool, amount).min(member.points)
+			};
+			ensure!(!points.is_zero(), Error::<T>::InsufficientPoints);
+
+			let remaining = value.saturating_sub(amount);
+			if who == pool.operator {
+				ensure!(
+					remaining >= T::NominationManager::min_nominator_stake()
+						|| (remaining.is_zero() && pool.member_count == 1),
+					Error::<T>::OperatorBondBelowMin
+				);
+			} else {
+				ensure!(
+					remaining.is_zero() || remaining >= T::MinJoinBond::get(),
+					Error::<T>::BondBelowMin
+				);
+			}
+
+			Self::update_rewards(pool_id, &mut pool);
+			Self::payout_rewards(&who, &mut member, &mut pool)?;
+
+			let batch = pool.current_batch;
+			let batch_points = Self::add_to_batch(pool_id, batch, amount);
+			Unbonding::<T>::try_mutate(&who, |chunks| {
+				match chunks.iter_mut().find(|chunk| chunk.batch == batch) {
+					Some(chunk) => {
+						chunk.points = chunk.points.saturating_add(batch_points);
+						Ok(())
+					},
+					None => chunks
+						.try_push(UnbondingChunk { batch, points: batch_points })
+						.map_err(|_| Error::<T>::TooManyUnbondingChunks),
+				}
+			})?;
+
+			member.points = member.points.saturating_sub(points);
+			pool.points = pool.points.saturating_sub(points);
+			pool.active = pool.active.saturating_sub(amount);
+			Members::<T>::insert(&who, member);
+			Self::deposit_event(Event::Unbonded { member: who, pool_id, amount, batch });
+			Self::process_unbonding(pool_id, &mut pool);
+			Pools::<T>::insert(pool_id, pool);
+			Ok(().into())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_unbonded(T::MaxUnbondingChunks::get()))]
+		/// Withdraw the unbondings of the caller whose batch has been executed. Each unbonding is
+		/// paid its share of the batch. The member leaves the pool once it has no bond and no
+		/// pending withdrawal left.
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let member = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			let pool_id = member.pool_id;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolDNE)?;
+			Self::process_unbonding(pool_id, &mut pool);
+
+			let mut amount = BalanceOf::<T>::zero();
+			let mut withdrawn = false;
+			let mut pending = Vec::new();
+			for chunk in Unbonding::<T>::get(&who) {
+				match Self::withdraw_from_batch(pool_id, &chunk) {
+					Some(value) => {
+						amount = amount.saturating_add(value);
+						withdrawn = true;
+					},
+					None => pending.push(chunk),
+				}
+			}
+			ensure!(withdrawn, Error::<T>::NothingToWithdraw);
+
+			let bonded = Self::bonded_account(pool_id);
+			T::Currency::transfer(&bonded, &who, amount, ExistenceRequirement::AllowDeath)?;
+			pool.withdrawable = pool.withdrawable.saturating_sub(amount);
+			Self::deposit_event(Event::Withdrawn { member: who.clone(), pool_id, amount });
+
+			if !pending.is_empty() {
+				// the remaining chunks never exceed the bound
+				Unbonding::<T>::insert(&who, BoundedVec::truncate_from(pending));
+			} else {
+				Unbonding::<T>::remove(&who);
+				if member.points.is_zero() {
+					Members::<T>::remove(&who);
+					pool.member_count = pool.member_count.saturating_sub(1);
+				}
+			}
+
+			if pool.member_count.is_zero() {
+				// the last member is the operator, who receives the remaining commission
+				let reward_account = Self::reward_account(pool_id);
+				T::Currency::transfer(
+					&reward_account,
+					&pool.operator,
+					T::Currency::free_balance(&reward_account),
+					ExistenceRequirement::AllowDeath,
+				)?;
+				Pools::<T>::remove(pool_id);
+				Self::deposit_event(Event::Destroyed { pool_id });
+			} else {
+				Pools::<T>::insert(pool_id, pool);
+			}
+			Ok(().into())
+		}
+
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim_rewards())]
+		/// Claim the pending rewards of the caller.
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut member = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			let pool_id = member.pool_id;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolDNE)?;
+
+			Self::update_rewards(pool_id, &mut pool);
+			let amount = Self::payout_rewards(&who, &mut member, &mut pool)?;
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+			Members::<T>::insert(&who, member);
+			Pools::<T>::insert(pool_id, pool);
+			Ok(().into())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_commission())]
+		/// Set the commission of the pool. Only callable by the pool operator. Rewards earned
+		/// before the change are accounted with the previous commission.
+		pub fn set_commission(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			new: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolDNE)?;
+			ensure!(who == pool.operator, Error::<T>::NotOperator);
+			ensure!(new <= T::MaxPoolCommission::get(), Error::<T>::CommissionTooHigh);
+
+			Self::update_rewards(pool_id, &mut pool);
+			let old = pool.commission;
+			pool.commission = new;
+			Pools::<T>::insert(pool_id, pool);
+			Self::deposit_event(Event::CommissionSet { pool_id, old, new });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim_commission())]
+		/// Claim the pending commission of the pool. Only callable by the pool operator.
+		pub fn claim_commission(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolDNE)?;
+			ensure!(who == pool.operator, Error::<T>::NotOperator);
+
+			Self::update_rewards(pool_id, &mut pool);
+			let amount = pool.pending_commission;
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+			T::Currency::transfer(
+				&Self::reward_account(pool_id),
+				&who,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			pool.pending_commission = Zero::zero();
+			pool.last_recorded_reward_balance =
+				pool.last_recorded_reward_balance.saturating_sub(amount);
+			Pools::<T>::insert(pool_id, pool);
+			Self::deposit_event(Event::CommissionClaimed { pool_id, operator: who, amount });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::poke())]
+		/// Account the slashes of the pool, execute its due nomination request and schedule its
+		/// current unbonding batch. Callable by anyone.
+		pub fn poke(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let mut pool = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolDNE)?;
+			Self::process_unbonding(pool_id, &mut pool);
+			Pools::<T>::insert(pool_id, pool);
+			Ok(().into())
+		}
+	}
+}
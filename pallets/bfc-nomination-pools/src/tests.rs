@@ -0,0 +1,278 @@
+use crate::{mock::*, Error, Event, Members, Pools, UnbondingBatches};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Perbill;
+
+fn create_pool() {
+	assert_ok!(NominationPools::create(
+		RuntimeOrigin::signed(alice()),
+		validator_1(),
+		200,
+		Perbill::from_percent(10),
+	));
+}
+
+#[test]
+fn create_and_join_nominate_from_the_bonded_account() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let bonded = NominationPools::bonded_account(0);
+		assert_eq!(MockStaking::nomination(&bonded, &validator_1()), 200);
+		System::assert_last_event(
+			Event::Created { pool_id: 0, operator: alice(), candidate: validator_1(), amount: 200 }
+				.into(),
+		);
+
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(bob()), 0, 100));
+		assert_eq!(MockStaking::nomination(&bonded, &validator_1()), 300);
+		assert_eq!(Balances::free_balance(bob()), 900);
+
+		let pool = Pools::<Test>::get(0).unwrap();
+		assert_eq!(pool.points, 300);
+		assert_eq!(pool.member_count, 2);
+		assert_eq!(Members::<Test>::get(bob()).unwrap().points, 100);
+	});
+}
+
+#[test]
+fn create_checks_the_operator_bond_and_commission() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			NominationPools::create(
+				RuntimeOrigin::signed(alice()),
+				validator_1(),
+				MIN_NOMINATOR_STAKE - 1,
+				Perbill::zero(),
+			),
+			Error::<Test>::OperatorBondBelowMin
+		);
+		assert_noop!(
+			NominationPools::create(
+				RuntimeOrigin::signed(alice()),
+				validator_1(),
+				200,
+				Perbill::from_percent(60),
+			),
+			Error::<Test>::CommissionTooHigh
+		);
+	});
+}
+
+#[test]
+fn rewards_are_shared_per_point_after_commission() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(bob()), 0, 100));
+		MockStaking::reward(&NominationPools::bonded_account(0), 300);
+
+		// 10% commission is set aside and the remaining 270 is shared over 300 points
+		assert_eq!(NominationPools::pending_rewards(&alice()), 180);
+		assert_eq!(NominationPools::pending_rewards(&bob()), 90);
+
+		assert_ok!(NominationPools::claim_rewards(RuntimeOrigin::signed(bob())));
+		assert_eq!(Balances::free_balance(bob()), 990);
+		assert_noop!(
+			NominationPools::claim_rewards(RuntimeOrigin::signed(bob())),
+			Error::<Test>::NothingToClaim
+		);
+
+		assert_ok!(NominationPools::claim_commission(RuntimeOrigin::signed(alice()), 0));
+		assert_eq!(Balances::free_balance(alice()), 830);
+		assert_eq!(NominationPools::pending_rewards(&alice()), 180);
+	});
+}
+
+#[test]
+fn unbond_is_batched_behind_the_pending_request() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(bob()), 0, 100));
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(charlie()), 0, 100));
+
+		assert_ok!(NominationPools::unbond(RuntimeOrigin::signed(bob()), 50));
+		assert_eq!(NominationPools::unbonding_of(&bob()), vec![(50, Some(1 + NOMINATION_DELAY))]);
+
+		// the pool already has a pending request, so the next unbonding waits in a new batch
+		// without a promised round
+		assert_ok!(NominationPools::unbond(RuntimeOrigin::signed(charlie()), 50));
+		assert_eq!(NominationPools::unbonding_of(&charlie()), vec![(50, None)]);
+		assert_eq!(Pools::<Test>::get(0).unwrap().current_batch, 1);
+
+		// the batch is only scheduled once the pool is touched after the request is due
+		MockStaking::set_round(2 + NOMINATION_DELAY);
+		assert_ok!(NominationPools::poke(RuntimeOrigin::signed(alice()), 0));
+		System::assert_last_event(
+			Event::UnbondingScheduled {
+				pool_id: 0,
+				batch: 1,
+				amount: 50,
+				execute_round: 2 + 2 * NOMINATION_DELAY,
+			}
+			.into(),
+		);
+		assert_eq!(
+			NominationPools::unbonding_of(&charlie()),
+			vec![(50, Some(2 + 2 * NOMINATION_DELAY))]
+		);
+
+		assert_ok!(NominationPools::withdraw_unbonded(RuntimeOrigin::signed(bob())));
+		assert_eq!(Balances::free_balance(bob()), 950);
+		assert_noop!(
+			NominationPools::withdraw_unbonded(RuntimeOrigin::signed(charlie())),
+			Error::<Test>::NothingToWithdraw
+		);
+
+		MockStaking::set_round(2 + 2 * NOMINATION_DELAY);
+		assert_ok!(NominationPools::withdraw_unbonded(RuntimeOrigin::signed(charlie())));
+		assert_eq!(Balances::free_balance(charlie()), 950);
+		assert!(UnbondingBatches::<Test>::iter_prefix(0).next().is_none());
+		assert_eq!(
+			MockStaking::nomination(&NominationPools::bonded_account(0), &validator_1()),
+			300
+		);
+	});
+}
+
+#[test]
+fn slashes_are_shared_with_the_unbonding_batches() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let bonded = NominationPools::bonded_account(0);
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(bob()), 0, 100));
+		assert_ok!(NominationPools::unbond(RuntimeOrigin::signed(bob()), 50));
+
+		// 30 is shared over the active 250 and the unbonding 50
+		MockStaking::slash(&bonded, &validator_1(), 30);
+		assert_ok!(NominationPools::poke(RuntimeOrigin::signed(alice()), 0));
+		System::assert_last_event(Event::Slashed { pool_id: 0, amount: 30 }.into());
+		let pool = Pools::<Test>::get(0).unwrap();
+		assert_eq!(pool.points, 250);
+		assert_eq!(pool.active, 225);
+		assert_eq!(NominationPools::member_balance(&alice()), 180);
+		assert_eq!(NominationPools::member_balance(&bob()), 45);
+		assert_eq!(NominationPools::unbonding_of(&bob()), vec![(45, Some(1 + NOMINATION_DELAY))]);
+
+		// new members join at the slashed point value
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(charlie()), 0, 90));
+		assert_eq!(Members::<Test>::get(charlie()).unwrap().points, 100);
+
+		// the decrease was scheduled before the slash, so its surplus is nominated again
+		MockStaking::set_round(1 + NOMINATION_DELAY);
+		assert_ok!(NominationPools::withdraw_unbonded(RuntimeOrigin::signed(bob())));
+		assert_eq!(Balances::free_balance(bob()), 945);
+		assert_eq!(MockStaking::nomination(&bonded, &validator_1()), 315);
+		assert_eq!(Pools::<Test>::get(0).unwrap().total_bonded, 315);
+		assert_eq!(Balances::free_balance(bonded), 0);
+	});
+}
+
+#[test]
+fn stake_returned_by_a_leaving_candidate_is_not_slashed() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let bonded = NominationPools::bonded_account(0);
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(bob()), 0, 100));
+		assert_ok!(NominationPools::unbond(RuntimeOrigin::signed(bob()), 50));
+
+		// the candidate leaves and the whole nomination is unreserved with its pending request
+		MockStaking::leave_candidate(&validator_1());
+		assert_eq!(Balances::free_balance(bonded), 300);
+		assert_ok!(NominationPools::poke(RuntimeOrigin::signed(alice()), 0));
+		System::assert_last_event(Event::StakeReturned { pool_id: 0, amount: 300 }.into());
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::NominationPools(Event::Slashed { .. })
+		)));
+
+		let pool = Pools::<Test>::get(0).unwrap();
+		assert_eq!(pool.active, 250);
+		assert_eq!(pool.total_bonded, 0);
+		assert_eq!(pool.returned, 250);
+		assert_eq!(pool.withdrawable, 50);
+		assert_eq!(pool.scheduled_batch, None);
+		assert_eq!(NominationPools::member_balance(&alice()), 200);
+
+		// the scheduled batch is unlocked with the returned stake
+		assert_ok!(NominationPools::withdraw_unbonded(RuntimeOrigin::signed(bob())));
+		assert_eq!(Balances::free_balance(bob()), 950);
+
+		// later unbondings are unlocked at once from the returned stake
+		assert_ok!(NominationPools::unbond(RuntimeOrigin::signed(bob()), 50));
+		assert_eq!(NominationPools::unbonding_of(&bob()), vec![(50, Some(1))]);
+		assert_ok!(NominationPools::withdraw_unbonded(RuntimeOrigin::signed(bob())));
+		assert_eq!(Balances::free_balance(bob()), 1_000);
+		assert!(Members::<Test>::get(bob()).is_none());
+
+		let pool = Pools::<Test>::get(0).unwrap();
+		assert_eq!(pool.returned, 200);
+		assert_eq!(pool.withdrawable, 0);
+		assert_eq!(Balances::free_balance(bonded), 200);
+	});
+}
+
+#[test]
+fn kicked_nomination_only_books_the_missing_stake_as_a_slash() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let bonded = NominationPools::bonded_account(0);
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(bob()), 0, 100));
+
+		// a slash is followed by a bottom nomination kick before the pool is touched
+		MockStaking::slash(&bonded, &validator_1(), 30);
+		MockStaking::kick(&bonded, &validator_1());
+		assert_eq!(Balances::free_balance(bonded), 270);
+		assert_ok!(NominationPools::poke(RuntimeOrigin::signed(alice()), 0));
+		System::assert_has_event(Event::StakeReturned { pool_id: 0, amount: 270 }.into());
+		System::assert_last_event(Event::Slashed { pool_id: 0, amount: 30 }.into());
+
+		let pool = Pools::<Test>::get(0).unwrap();
+		assert_eq!(pool.active, 270);
+		assert_eq!(pool.returned, 270);
+		assert_eq!(NominationPools::member_balance(&alice()), 180);
+		assert_eq!(NominationPools::member_balance(&bob()), 90);
+
+		// a second sync does not book the returned stake again
+		assert_ok!(NominationPools::poke(RuntimeOrigin::signed(alice()), 0));
+		assert_eq!(Pools::<Test>::get(0).unwrap(), pool);
+	});
+}
+
+#[test]
+fn operator_keeps_the_minimum_stake_while_members_remain() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(NominationPools::join(RuntimeOrigin::signed(bob()), 0, 100));
+
+		assert_noop!(
+			NominationPools::unbond(RuntimeOrigin::signed(alice()), 150),
+			Error::<Test>::OperatorBondBelowMin
+		);
+		assert_noop!(
+			NominationPools::unbond(RuntimeOrigin::signed(alice()), 200),
+			Error::<Test>::OperatorBondBelowMin
+		);
+		assert_noop!(
+			NominationPools::set_commission(RuntimeOrigin::signed(bob()), 0, Perbill::zero()),
+			Error::<Test>::NotOperator
+		);
+		assert_noop!(
+			NominationPools::join(RuntimeOrigin::signed(alice()), 1, 100),
+			Error::<Test>::PoolDNE
+		);
+	});
+}
+
+#[test]
+fn pool_is_destroyed_when_the_last_member_leaves() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		MockStaking::reward(&NominationPools::bonded_account(0), 10);
+		assert_ok!(NominationPools::unbond(RuntimeOrigin::signed(alice()), 200));
+
+		MockStaking::set_round(1 + NOMINATION_DELAY);
+		assert_ok!(NominationPools::withdraw_unbonded(RuntimeOrigin::signed(alice())));
+		System::assert_last_event(Event::Destroyed { pool_id: 0 }.into());
+		assert!(Pools::<Test>::get(0).is_none());
+		assert!(Members::<Test>::get(alice()).is_none());
+		assert_eq!(Balances::free_balance(alice()), 1_010);
+	});
+}
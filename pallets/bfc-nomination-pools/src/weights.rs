@@ -0,0 +1,133 @@
+//! Hand-estimated weights for `pallet_bfc_nomination_pools`, not generated by the benchmark CLI
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_bfc_nomination_pools`.
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn join() -> Weight;
+	fn unbond() -> Weight;
+	fn withdraw_unbonded(n: u32) -> Weight;
+	fn claim_rewards() -> Weight;
+	fn set_commission() -> Weight;
+	fn claim_commission() -> Weight;
+	fn poke() -> Weight;
+}
+
+/// Weights for `pallet_bfc_nomination_pools` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn create() -> Weight {
+		Weight::from_parts(95_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn join() -> Weight {
+		Weight::from_parts(85_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn unbond() -> Weight {
+		Weight::from_parts(115_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn withdraw_unbonded(n: u32) -> Weight {
+		Weight::from_parts(70_000_000, 0)
+			.saturating_add(Weight::from_parts(18_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn claim_rewards() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_commission() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn claim_commission() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn poke() -> Weight {
+		Weight::from_parts(90_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn create() -> Weight {
+		Weight::from_parts(95_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn join() -> Weight {
+		Weight::from_parts(85_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn unbond() -> Weight {
+		Weight::from_parts(115_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn withdraw_unbonded(n: u32) -> Weight {
+		Weight::from_parts(70_000_000, 0)
+			.saturating_add(Weight::from_parts(18_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn claim_rewards() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_commission() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn claim_commission() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn poke() -> Weight {
+		Weight::from_parts(90_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+}
@@ -0,0 +1,53 @@
+//! Runtime API definition for the bfc offences pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+pub use sp_consensus_slots::{EquivocationProof, Slot};
+
+/// An opaque type used to represent the key ownership proof at the runtime API boundary. The
+/// inner value is an encoded representation of the actual key ownership proof which will be
+/// parameterized when defining the runtime.
+#[derive(Decode, Encode, PartialEq, TypeInfo)]
+pub struct OpaqueKeyOwnershipProof(Vec<u8>);
+
+impl OpaqueKeyOwnershipProof {
+	/// Create a new `OpaqueKeyOwnershipProof` using the given encoded representation.
+	pub fn new(inner: Vec<u8>) -> OpaqueKeyOwnershipProof {
+		OpaqueKeyOwnershipProof(inner)
+	}
+
+	/// Try to decode this `OpaqueKeyOwnershipProof` into the given concrete key ownership proof
+	/// type.
+	pub fn decode<T: Decode>(self) -> Option<T> {
+		Decode::decode(&mut &self.0[..]).ok()
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to report Aura slot equivocations of validators.
+	pub trait AuraEquivocationApi<AuthorityId> where
+		AuthorityId: Codec,
+	{
+		/// Generates a proof of key ownership for the given authority in the current session.
+		/// Only equivocations of the current session can be reported, so the proof does not
+		/// depend on the slot and reports of older slots are rejected by the pallet. Returns
+		/// `None` if the authority is not part of the current validator set.
+		fn generate_key_ownership_proof(
+			slot: Slot,
+			authority_id: AuthorityId,
+		) -> Option<OpaqueKeyOwnershipProof>;
+
+		/// Submits an unsigned extrinsic to report an Aura equivocation. The caller must provide
+		/// the equivocation proof and a key ownership proof (should be obtained using
+		/// `generate_key_ownership_proof`). Returns `None` when the extrinsic creation or the
+		/// submission failed.
+		fn submit_report_equivocation_unsigned_extrinsic(
+			equivocation_proof: EquivocationProof<Block::Header, AuthorityId>,
+			key_owner_proof: OpaqueKeyOwnershipProof,
+		) -> Option<()>;
+	}
+}
@@ -0,0 +1,308 @@
+//! An opt-in utility module for reporting Aura slot equivocations.
+//!
+//! This module defines an offence type for Aura equivocations and a report system that verifies
+//! equivocation proofs and key ownership proofs before reporting the offence. The offence is
+//! reported through the configured offence reporter, which should be wrapped with
+//! `OffenceKindReporter` in order for the offence to be handled as `OffenceKind::AuraEquivocation`.
+//!
+//! The equivocation proofs are expected to be submitted by the node through the
+//! `submit_report_equivocation_unsigned_extrinsic` runtime API as unsigned extrinsics.
+
+use crate::{
+	Call, Config, CurrentSessionStart, EquivocationSlashFraction, Error, Pallet, LOG_TARGET,
+};
+
+use frame_support::traits::{Get, KeyOwnerProofSystem};
+use frame_system::pallet_prelude::HeaderFor;
+use parity_scale_codec::Decode;
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_consensus_slots::{EquivocationProof, Slot};
+use sp_runtime::{
+	traits::Header as HeaderT,
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		TransactionValidityError, ValidTransaction,
+	},
+	DigestItem, DispatchError, KeyTypeId, Perbill, RuntimeAppPublic, RuntimeDebug,
+};
+use sp_session::{GetSessionNumber, GetValidatorCount};
+use sp_staking::{
+	offence::{Kind, Offence, OffenceReportSystem, ReportOffence},
+	SessionIndex,
+};
+use sp_std::{marker::PhantomData, prelude::*, vec};
+
+/// An Aura equivocation offence, reported if a validator authored two different blocks in the
+/// same slot.
+#[derive(RuntimeDebug)]
+pub struct EquivocationOffence<Offender, T> {
+	/// The slot in which this equivocation happened.
+	pub slot: Slot,
+	/// The session index in which the equivocation happened.
+	pub session_index: SessionIndex,
+	/// The size of the validator set at the time of the offence.
+	pub validator_set_count: u32,
+	/// The authority that produced the equivocation.
+	pub offender: Offender,
+	/// A zero-sized type used to mark things that "act like" they own a T.
+	phantom: PhantomData<T>,
+}
+
+impl<Offender: Clone, T: Config> Offence<Offender> for EquivocationOffence<Offender, T> {
+	const ID: Kind = *b"aura:equivocatio";
+	type TimeSlot = Slot;
+
+	fn offenders(&self) -> Vec<Offender> {
+		vec![self.offender.clone()]
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		self.slot
+	}
+
+	fn slash_fraction(&self, _offenders_count: u32) -> Perbill {
+		<EquivocationSlashFraction<T>>::get()
+	}
+}
+
+/// Verifies the given Aura equivocation proof by making sure that both headers have different
+/// hashes, are targeting the same slot, and have valid seals by the same authority.
+pub fn check_equivocation_proof<H, P>(proof: EquivocationProof<H, P>) -> bool
+where
+	H: HeaderT,
+	P: RuntimeAppPublic,
+{
+	let find_slot = |header: &H| -> Option<Slot> {
+		header.digest().logs().iter().find_map(|log| match log {
+			DigestItem::PreRuntime(id, data) if *id == AURA_ENGINE_ID => {
+				Slot::decode(&mut &data[..]).ok()
+			},
+			_ => None,
+		})
+	};
+
+	let verify_seal = |mut header: H| -> Option<()> {
+		let signature = match header.digest_mut().pop()? {
+			DigestItem::Seal(id, signature) if id == AURA_ENGINE_ID => {
+				P::Signature::decode(&mut &signature[..]).ok()?
+			},
+			_ => return None,
+		};
+		// the seal is signed over the hash of the header without the seal
+		let pre_hash = header.hash();
+		proof.offender.verify(&pre_hash.as_ref(), &signature).then_some(())
+	};
+
+	let verify_proof = || -> Option<()> {
+		// we must have different headers for the equivocation to be valid
+		if proof.first_header.hash() == proof.second_header.hash() {
+			return None;
+		}
+		// both headers must be targeting the same slot and it must be the same as the one in the
+		// proof.
+		if find_slot(&proof.first_header)? != proof.slot
+			|| find_slot(&proof.second_header)? != proof.slot
+		{
+			return None;
+		}
+		verify_seal(proof.first_header.clone())?;
+		verify_seal(proof.second_header.clone())?;
+		Some(())
+	};
+
+	verify_proof().is_some()
+}
+
+/// Checks that the equivocation happened in the session of the given key ownership proof and
+/// that it is the current session. Key ownership proofs are only generated for the current
+/// session, so an older equivocation could not be attributed to the session it happened in.
+fn is_in_current_session<T: Config>(
+	proof: &EquivocationProof<HeaderFor<T>, T::AuraId>,
+	session_index: SessionIndex,
+) -> bool {
+	let Some((current, start)) = CurrentSessionStart::<T>::get() else {
+		// no session has started since the upgrade yet
+		return true;
+	};
+	session_index == current
+		&& *proof.first_header.number() >= start
+		&& *proof.second_header.number() >= start
+}
+
+/// Aura equivocation offence report system.
+///
+/// This type implements `OffenceReportSystem` such that:
+/// - Equivocation reports are published on-chain as unsigned extrinsics via
+///   `offchain::CreateBare`.
+/// - On-chain validity checks and processing are mostly delegated to the user provided generic
+///   types implementing `KeyOwnerProofSystem` and `ReportOffence` traits.
+/// - Offence reporter for unsigned transactions is left empty.
+pub struct EquivocationReportSystem<T, R, P, L>(PhantomData<(T, R, P, L)>);
+
+impl<T, R, P, L>
+	OffenceReportSystem<
+		Option<T::AccountId>,
+		(EquivocationProof<HeaderFor<T>, T::AuraId>, T::KeyOwnerProof),
+	> for EquivocationReportSystem<T, R, P, L>
+where
+	T: Config + frame_system::offchain::CreateBare<Call<T>>,
+	R: ReportOffence<
+		T::AccountId,
+		P::IdentificationTuple,
+		EquivocationOffence<P::IdentificationTuple, T>,
+	>,
+	P: KeyOwnerProofSystem<(KeyTypeId, T::AuraId), Proof = T::KeyOwnerProof>,
+	P::IdentificationTuple: Clone,
+	L: Get<u64>,
+{
+	type Longevity = L;
+
+	fn publish_evidence(
+		evidence: (EquivocationProof<HeaderFor<T>, T::AuraId>, T::KeyOwnerProof),
+	) -> Result<(), ()> {
+		use frame_system::offchain::SubmitTransaction;
+		let (equivocation_proof, key_owner_proof) = evidence;
+
+		let call = Call::report_aura_equivocation_unsigned {
+			equivocation_proof: Box::new(equivocation_proof),
+			key_owner_proof,
+		};
+		let xt = T::create_bare(call.into());
+		let res = SubmitTransaction::<T, Call<T>>::submit_transaction(xt);
+		match res {
+			Ok(_) => log::info!(target: LOG_TARGET, "Submitted Aura equivocation report"),
+			Err(e) => {
+				log::error!(target: LOG_TARGET, "Error submitting Aura equivocation report: {:?}", e)
+			},
+		}
+		res
+	}
+
+	fn check_evidence(
+		evidence: (EquivocationProof<HeaderFor<T>, T::AuraId>, T::KeyOwnerProof),
+	) -> Result<(), TransactionValidityError> {
+		let (equivocation_proof, key_owner_proof) = evidence;
+		if !is_in_current_session::<T>(&equivocation_proof, key_owner_proof.session()) {
+			return Err(InvalidTransaction::Stale.into());
+		}
+
+		// check the membership proof to extract the offender's id
+		let key = (<T::AuraId as RuntimeAppPublic>::ID, equivocation_proof.offender.clone());
+		let offender = P::check_proof(key, key_owner_proof).ok_or(InvalidTransaction::BadProof)?;
+
+		// check if the offence has already been reported, and if so then we can discard the report.
+		if R::is_known_offence(&[offender], &equivocation_proof.slot) {
+			Err(InvalidTransaction::Stale.into())
+		} else {
+			Ok(())
+		}
+	}
+
+	fn process_evidence(
+		reporter: Option<T::AccountId>,
+		evidence: (EquivocationProof<HeaderFor<T>, T::AuraId>, T::KeyOwnerProof),
+	) -> Result<(), DispatchError> {
+		let (equivocation_proof, key_owner_proof) = evidence;
+		let offender = equivocation_proof.offender.clone();
+		let slot = equivocation_proof.slot;
+		if !is_in_current_session::<T>(&equivocation_proof, key_owner_proof.session()) {
+			return Err(Error::<T>::InvalidKeyOwnershipProof.into());
+		}
+
+		// validate the equivocation proof (check that both headers are sealed by the offender)
+		if !check_equivocation_proof(equivocation_proof) {
+			return Err(Error::<T>::InvalidEquivocationProof.into());
+		}
+
+		let validator_set_count = key_owner_proof.validator_count();
+		let session_index = key_owner_proof.session();
+
+		// check the membership proof and extract the offender's id
+		let key = (<T::AuraId as RuntimeAppPublic>::ID, offender);
+		let offender =
+			P::check_proof(key, key_owner_proof).ok_or(Error::<T>::InvalidKeyOwnershipProof)?;
+
+		let offence = EquivocationOffence {
+			slot,
+			session_index,
+			validator_set_count,
+			offender,
+			phantom: PhantomData,
+		};
+		R::report_offence(reporter.into_iter().collect(), offence)
+			.map_err(|_| Error::<T>::DuplicateOffenceReport)?;
+
+		Ok(())
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Submits an extrinsic to report an Aura equivocation. This method will create an unsigned
+	/// extrinsic with a call to `report_aura_equivocation_unsigned` and will push the transaction
+	/// to the pool. Only useful in an offchain context.
+	pub fn submit_unsigned_equivocation_report(
+		equivocation_proof: EquivocationProof<HeaderFor<T>, T::AuraId>,
+		key_owner_proof: T::KeyOwnerProof,
+	) -> Option<()> {
+		T::EquivocationReportSystem::publish_evidence((equivocation_proof, key_owner_proof)).ok()
+	}
+
+	/// Validates the unsigned Aura equivocation reports. Reports that are not coming from the
+	/// local node are discarded.
+	pub fn validate_unsigned(source: TransactionSource, call: &Call<T>) -> TransactionValidity {
+		if let Call::report_aura_equivocation_unsigned { equivocation_proof, key_owner_proof } =
+			call
+		{
+			// discard equivocation reports not coming from the local node
+			match source {
+				TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ },
+				_ => {
+					log::warn!(
+						target: LOG_TARGET,
+						"rejecting unsigned report equivocation transaction because it is not local/in-block."
+					);
+					return InvalidTransaction::Call.into();
+				},
+			}
+
+			// check report staleness
+			let evidence = (*equivocation_proof.clone(), key_owner_proof.clone());
+			T::EquivocationReportSystem::check_evidence(evidence)?;
+
+			let longevity =
+				<T::EquivocationReportSystem as OffenceReportSystem<_, _>>::Longevity::get();
+
+			ValidTransaction::with_tag_prefix("AuraEquivocation")
+				// we assign the maximum priority for any equivocation report.
+				.priority(TransactionPriority::MAX)
+				// only one equivocation report for the same offender at the same slot.
+				.and_provides((equivocation_proof.offender.clone(), *equivocation_proof.slot))
+				.longevity(longevity)
+				// we don't propagate this. This can never be included on a remote node.
+				.propagate(false)
+				.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
+	}
+
+	/// Checks the staleness of the unsigned Aura equivocation reports before dispatching them.
+	pub fn pre_dispatch(call: &Call<T>) -> Result<(), TransactionValidityError> {
+		if let Call::report_aura_equivocation_unsigned { equivocation_proof, key_owner_proof } =
+			call
+		{
+			let evidence = (*equivocation_proof.clone(), key_owner_proof.clone());
+			T::EquivocationReportSystem::check_evidence(evidence)
+		} else {
+			Err(InvalidTransaction::Call.into())
+		}
+	}
+}
@@ -1,22 +1,36 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(unused_crate_dependencies)]
 
+pub mod equivocation;
 pub mod migrations;
 mod pallet;
 pub mod weights;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 pub use pallet::pallet::*;
 use weights::WeightInfo;
 
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 
-use frame_support::{pallet_prelude::MaxEncodedLen, traits::Currency};
+use frame_support::{pallet_prelude::MaxEncodedLen, traits::Currency, weights::Weight};
+
+pub use bp_staking::{OffenceKind, OffenceKindConfig};
 
-use bp_staking::{Offence, RoundIndex};
-use sp_runtime::{traits::Zero, Perbill, RuntimeDebug};
-use sp_staking::SessionIndex;
-use sp_std::prelude::*;
+use bp_staking::{traits::OnOffenceKindHandler, Offence, RoundIndex};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Perbill, RuntimeDebug,
+};
+use sp_staking::{
+	offence::{self, OffenceError, ReportOffence},
+	SessionIndex,
+};
+use sp_std::{marker::PhantomData, prelude::*};
 
 /// The type that indicates the count of offences
 pub type OffenceCount = u32;
@@ -122,4 +136,116 @@ impl<
 		self.increase_slash_fraction(offence.slash_fraction);
 		self.offences.push(offence);
 	}
+
+	/// Returns the number of offences of the given kind
+	pub fn offence_count_of(&self, kind: OffenceKind) -> OffenceCount {
+		self.offences.iter().filter(|offence| offence.kind == kind).count() as OffenceCount
+	}
+
+	/// Returns the aggregated slash fraction of the offences of the given kind
+	pub fn slash_fraction_of(&self, kind: OffenceKind) -> Perbill {
+		self.offences
+			.iter()
+			.filter(|offence| offence.kind == kind)
+			.fold(Perbill::zero(), |acc, offence| acc.saturating_add(offence.slash_fraction))
+	}
+
+	/// Returns the latest session that an offence of the given kind has been earned
+	pub fn latest_session_of(&self, kind: OffenceKind) -> Option<SessionIndex> {
+		self.offences
+			.iter()
+			.filter(|offence| offence.kind == kind)
+			.map(|offence| offence.session_index)
+			.max()
+	}
+
+	/// Returns the kind of the latest offence
+	pub fn latest_offence_kind(&self) -> Option<OffenceKind> {
+		self.offences.last().map(|offence| offence.kind)
+	}
+
+	/// Remove every offence of the given kind and recompute the offence related fields
+	pub fn remove_offences_of(&mut self, kind: OffenceKind) {
+		self.offences.retain(|offence| offence.kind != kind);
+		self.offence_count = self.offences.len() as OffenceCount;
+		self.aggregated_slash_fraction = self
+			.offences
+			.iter()
+			.fold(Perbill::zero(), |acc, offence| acc.saturating_add(offence.slash_fraction));
+		if let Some(latest) = self.offences.last() {
+			self.set_latest_offence(latest.round_index, latest.session_index);
+		}
+	}
+}
+
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+/// A slash that has been scheduled to be applied in a future round
+pub struct UnappliedSlash<AccountId, Balance> {
+	/// The controller account of the offender
+	pub who: AccountId,
+	/// The stash account of the offender
+	pub stash: AccountId,
+	/// The self-bond of the offender at the moment of the offence
+	pub self_bond: Balance,
+	/// The slashable nomination of each nominator at the moment of the offence
+	pub nominators: Vec<(AccountId, Balance)>,
+	/// The slash fraction to be applied
+	pub slash_fraction: Perbill,
+	/// The round index this slash has been reported
+	pub reported_round: RoundIndex,
+}
+
+/// Keeps the number of offenders that have been reported for each offence kind and time slot
+pub trait ConcurrentOffenders {
+	/// Returns the number of offenders reported for the given offence kind and encoded time slot
+	fn concurrent_offenders(kind: &offence::Kind, time_slot: &[u8]) -> u32;
+
+	/// Sets the number of offenders reported for the given offence kind and encoded time slot
+	fn set_concurrent_offenders(kind: &offence::Kind, time_slot: &[u8], count: u32);
+}
+
+/// A wrapper of an offence reporter that hands the newly reported offenders to the given handler
+/// together with the kind of the offence. The wrapped reporter records the report and rejects
+/// duplicates, so its own offence handler should be `()`. Offences of an unknown kind are handled
+/// as unresponsiveness.
+///
+/// Like `pallet_offences`, the slash fraction is computed from every offender reported for the
+/// same offence kind and time slot so far, which are counted by `C`.
+pub struct OffenceKindReporter<R, H, C>(PhantomData<(R, H, C)>);
+
+impl<R, H, C, Reporter, Offender, O> ReportOffence<Reporter, Offender, O>
+	for OffenceKindReporter<R, H, C>
+where
+	R: ReportOffence<Reporter, Offender, O>,
+	H: OnOffenceKindHandler<Reporter, Offender, Weight>,
+	C: ConcurrentOffenders,
+	Reporter: Clone,
+	O: offence::Offence<Offender>,
+{
+	fn report_offence(reporters: Vec<Reporter>, offence: O) -> Result<(), OffenceError> {
+		let kind = OffenceKind::from_id(&O::ID).unwrap_or_default();
+		let time_slot = offence.time_slot();
+		let slash_session = offence.session_index();
+		let offenders: Vec<_> = offence
+			.offenders()
+			.into_iter()
+			.filter(|offender| !R::is_known_offence(sp_std::slice::from_ref(offender), &time_slot))
+			.map(|offender| offence::OffenceDetails { offender, reporters: reporters.clone() })
+			.collect();
+
+		// the offenders of the previous reports in the same time slot are counted as well
+		let encoded_time_slot = time_slot.encode();
+		let concurrent_offenders = C::concurrent_offenders(&O::ID, &encoded_time_slot)
+			.saturating_add(offenders.len() as u32);
+		let slash_fraction = offence.slash_fraction(concurrent_offenders);
+
+		R::report_offence(reporters, offence)?;
+		C::set_concurrent_offenders(&O::ID, &encoded_time_slot, concurrent_offenders);
+		H::on_offence(kind, &offenders, &vec![slash_fraction; offenders.len()], slash_session);
+		Ok(())
+	}
+
+	fn is_known_offence(offenders: &[Offender], time_slot: &O::TimeSlot) -> bool {
+		R::is_known_offence(offenders, time_slot)
+	}
 }
@@ -4,6 +4,187 @@ use frame_support::{pallet_prelude::*, storage_alias, traits::OnRuntimeUpgrade};
 #[storage_alias]
 pub type StorageVersion<T: Config> = StorageValue<Pallet<T>, Releases, ValueQuery>;
 
+#[storage_alias]
+pub type OffenceExpirationInSessions<T: Config> = StorageValue<Pallet<T>, SessionIndex, ValueQuery>;
+
+#[storage_alias]
+pub type FullMaximumOffenceCount<T: Config> = StorageValue<Pallet<T>, OffenceCount, ValueQuery>;
+
+#[storage_alias]
+pub type BasicMaximumOffenceCount<T: Config> = StorageValue<Pallet<T>, OffenceCount, ValueQuery>;
+
+pub mod v6 {
+	use super::*;
+
+	/// Builds the pending slash counts of the validators from the deferred slashes, so that a
+	/// pending slash can be checked without iterating every round.
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 5 {
+				for slashes in UnappliedSlashes::<T>::iter_values() {
+					weight = weight.saturating_add(T::DbWeight::get().reads(1));
+					for slash in slashes {
+						PendingSlashCount::<T>::mutate(&slash.who, |count| {
+							*count = Some(count.unwrap_or_default().saturating_add(1))
+						});
+						weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+					}
+				}
+
+				frame_support::traits::StorageVersion::new(6).put::<Pallet<T>>();
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				log!(info, "bfc-offences storage migration v6 completed successfully ✅");
+			} else {
+				log!(warn, "Skipping bfc-offences storage migration v6 💤");
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+
+			weight
+		}
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// Initializes the minimum slash fraction of consensus equivocations and resets the offence
+	/// policies of the equivocation kinds to their defaults, so that they are slashed immediately.
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			// chained with the later migrations, so it must not depend on the in-code version
+			if onchain == 4 {
+				EquivocationSlashFraction::<T>::put(T::DefaultEquivocationSlashFraction::get());
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+				for (kind, config) in T::DefaultOffenceKindConfigs::get() {
+					if kind.is_equivocation() {
+						OffenceKindConfigs::<T>::insert(kind, config);
+						weight = weight.saturating_add(T::DbWeight::get().writes(1));
+					}
+				}
+
+				frame_support::traits::StorageVersion::new(5).put::<Pallet<T>>();
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				log!(info, "bfc-offences storage migration v5 completed successfully ✅");
+			} else {
+				log!(warn, "Skipping bfc-offences storage migration v5 💤");
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+
+			weight
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+	use bp_staking::OffenceKindConfig;
+
+	#[derive(Decode)]
+	pub struct OldOffence<Balance> {
+		pub round_index: RoundIndex,
+		pub session_index: SessionIndex,
+		pub self_bond: Balance,
+		pub total_slash: Balance,
+		pub offender_slash: Balance,
+		pub nominators_slash: Balance,
+		pub slash_fraction: Perbill,
+	}
+
+	#[derive(Decode)]
+	pub struct OldValidatorOffenceInfo<Balance> {
+		pub latest_offence_round_index: RoundIndex,
+		pub latest_offence_session_index: SessionIndex,
+		pub offence_count: OffenceCount,
+		pub aggregated_slash_fraction: Perbill,
+		pub offences: Vec<OldOffence<Balance>>,
+	}
+
+	/// Replaces the single maximum offence counts and expiration with a policy per offence kind.
+	/// Every kind inherits the current on-chain values, and the recorded offences are migrated as
+	/// unresponsiveness since their kind is unknown.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			// chained with the later migrations, so it must not depend on the in-code version
+			if onchain == 3 {
+				let expiration_in_sessions = OffenceExpirationInSessions::<T>::take();
+				let full_maximum_offence_count = FullMaximumOffenceCount::<T>::take();
+				let basic_maximum_offence_count = BasicMaximumOffenceCount::<T>::take();
+				let defaults = T::DefaultOffenceKindConfigs::get();
+				for kind in OffenceKind::ALL {
+					let max_slash_fraction = defaults
+						.iter()
+						.find(|(k, _)| *k == kind)
+						.map_or(Perbill::one(), |(_, config)| config.max_slash_fraction);
+					OffenceKindConfigs::<T>::insert(
+						kind,
+						OffenceKindConfig {
+							full_maximum_offence_count,
+							basic_maximum_offence_count,
+							max_slash_fraction,
+							expiration_in_sessions,
+						},
+					);
+				}
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 6));
+
+				ValidatorOffences::<T>::translate::<OldValidatorOffenceInfo<BalanceOf<T>>, _>(
+					|_, old| {
+						weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+						Some(ValidatorOffenceInfo {
+							latest_offence_round_index: old.latest_offence_round_index,
+							latest_offence_session_index: old.latest_offence_session_index,
+							offence_count: old.offence_count,
+							aggregated_slash_fraction: old.aggregated_slash_fraction,
+							offences: old
+								.offences
+								.into_iter()
+								.map(|offence| Offence {
+									kind: OffenceKind::Unresponsive,
+									round_index: offence.round_index,
+									session_index: offence.session_index,
+									self_bond: offence.self_bond,
+									total_slash: offence.total_slash,
+									offender_slash: offence.offender_slash,
+									nominators_slash: offence.nominators_slash,
+									slash_fraction: offence.slash_fraction,
+								})
+								.collect(),
+						})
+					},
+				);
+
+				frame_support::traits::StorageVersion::new(4).put::<Pallet<T>>();
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				log!(info, "bfc-offences storage migration v4 completed successfully ✅");
+			} else {
+				log!(warn, "Skipping bfc-offences storage migration v4 💤");
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+
+			weight
+		}
+	}
+}
+
 /// Used to match mainnet pallet version
 pub mod v3_update {
 	use super::*;
@@ -0,0 +1,254 @@
+use crate as pallet_bfc_offences;
+use crate::equivocation::EquivocationReportSystem;
+use bp_staking::{traits::OnOffenceKindHandler, OffenceKind, OffenceKindConfig};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU64, Everything, KeyOwnerProofSystem},
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_consensus_aura::sr25519::AuthorityId;
+use sp_consensus_slots::Slot;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, KeyTypeId, Perbill,
+};
+use sp_session::MembershipProof;
+use sp_staking::{
+	offence::{self, OffenceDetails, OffenceError, ReportOffence},
+	SessionIndex,
+};
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+pub type Balance = u128;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		BfcOffences: pallet_bfc_offences,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 256;
+	pub const SS58Prefix: u8 = 42;
+	pub const ExistentialDeposit: u128 = 0;
+	pub DefaultOffenceKindConfigs: Vec<(OffenceKind, OffenceKindConfig)> = OffenceKind::ALL
+		.into_iter()
+		.map(|kind| (kind, OffenceKindConfig {
+			full_maximum_offence_count: 0,
+			basic_maximum_offence_count: 0,
+			max_slash_fraction: Perbill::one(),
+			expiration_in_sessions: 1,
+		}))
+		.collect();
+	pub const IsOffenceActive: bool = true;
+	pub const IsSlashActive: bool = true;
+	pub const IsNominatorSlashActive: bool = false;
+	pub const DefaultSlashDeferDuration: u32 = 0;
+	pub const MinUnbondingDelay: u32 = 3;
+	pub const DefaultEquivocationSlashFraction: Perbill = Perbill::from_percent(10);
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeTask = RuntimeTask;
+	type Nonce = u64;
+	type Block = Block;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type SingleBlockMigrations = ();
+	type MultiBlockMigrator = ();
+	type PreInherents = ();
+	type PostInherents = ();
+	type PostTransactions = ();
+	type ExtensionsWeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type DoneSlashHandler = ();
+}
+
+impl<C> frame_system::offchain::CreateTransactionBase<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type RuntimeCall = RuntimeCall;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
+impl<C> frame_system::offchain::CreateBare<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	fn create_bare(call: Self::RuntimeCall) -> Self::Extrinsic {
+		UncheckedExtrinsic::new_bare(call)
+	}
+}
+
+impl pallet_bfc_offences::Config for Test {
+	type Currency = Balances;
+	type Slash = ();
+	type DefaultOffenceKindConfigs = DefaultOffenceKindConfigs;
+	type IsOffenceActive = IsOffenceActive;
+	type IsSlashActive = IsSlashActive;
+	type IsNominatorSlashActive = IsNominatorSlashActive;
+	type DefaultSlashDeferDuration = DefaultSlashDeferDuration;
+	type MinUnbondingDelay = MinUnbondingDelay;
+	type DefaultEquivocationSlashFraction = DefaultEquivocationSlashFraction;
+	type AuraId = AuthorityId;
+	type KeyOwnerProof = MembershipProof;
+	type EquivocationReportSystem =
+		EquivocationReportSystem<Test, MockOffences, MockHistorical, ConstU64<100>>;
+	type CancelOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+/// The session that the key ownership proofs of `MockHistorical` are valid for
+pub const SESSION: SessionIndex = 3;
+
+thread_local! {
+	/// The Aura keys of the validators of the current session
+	pub static KEYS: RefCell<Vec<(AuthorityId, AccountId)>> = RefCell::new(Vec::new());
+	/// The reported offenders with the slot and the session of their offence
+	pub static REPORTED: RefCell<Vec<(AccountId, u64, SessionIndex)>> = RefCell::new(Vec::new());
+	/// The offenders handed to `MockOffenceKindHandler` with their slash fraction
+	pub static HANDLED: RefCell<Vec<(AccountId, Perbill)>> = RefCell::new(Vec::new());
+}
+
+/// A simplified `pallet_session::historical` that proves the keys of the current session
+pub struct MockHistorical;
+
+impl KeyOwnerProofSystem<(KeyTypeId, AuthorityId)> for MockHistorical {
+	type Proof = MembershipProof;
+	type IdentificationTuple = AccountId;
+
+	fn prove(key: (KeyTypeId, AuthorityId)) -> Option<Self::Proof> {
+		Self::owner(&key.1).map(|_| MembershipProof {
+			session: SESSION,
+			trie_nodes: Vec::new(),
+			validator_count: 3,
+		})
+	}
+
+	fn check_proof(key: (KeyTypeId, AuthorityId), proof: Self::Proof) -> Option<AccountId> {
+		if proof.session != SESSION {
+			return None;
+		}
+		Self::owner(&key.1)
+	}
+}
+
+impl MockHistorical {
+	fn owner(key: &AuthorityId) -> Option<AccountId> {
+		KEYS.with(|k| k.borrow().iter().find(|(k, _)| k == key).map(|(_, who)| *who))
+	}
+}
+
+/// A simplified `pallet_offences` that records the reported offenders
+pub struct MockOffences;
+
+impl<O> ReportOffence<AccountId, AccountId, O> for MockOffences
+where
+	O: offence::Offence<AccountId, TimeSlot = Slot>,
+{
+	fn report_offence(_reporters: Vec<AccountId>, offence: O) -> Result<(), OffenceError> {
+		let slot = offence.time_slot();
+		let offenders: Vec<_> = offence
+			.offenders()
+			.into_iter()
+			.filter(|offender| !Self::is_known_offence(&[*offender], &slot))
+			.collect();
+		if offenders.is_empty() {
+			return Err(OffenceError::DuplicateReport);
+		}
+		REPORTED.with(|r| {
+			let mut reported = r.borrow_mut();
+			for offender in offenders {
+				reported.push((offender, *slot, offence.session_index()));
+			}
+		});
+		Ok(())
+	}
+
+	fn is_known_offence(offenders: &[AccountId], time_slot: &Slot) -> bool {
+		REPORTED.with(|r| {
+			r.borrow()
+				.iter()
+				.any(|(who, slot, _)| offenders.contains(who) && *slot == **time_slot)
+		})
+	}
+}
+
+/// An offence handler that records the offenders and their slash fractions
+pub struct MockOffenceKindHandler;
+
+impl OnOffenceKindHandler<AccountId, AccountId, Weight> for MockOffenceKindHandler {
+	fn on_offence(
+		_kind: OffenceKind,
+		offenders: &[OffenceDetails<AccountId, AccountId>],
+		slash_fraction: &[Perbill],
+		_slash_session: SessionIndex,
+	) -> Weight {
+		HANDLED.with(|h| {
+			let mut handled = h.borrow_mut();
+			for (details, fraction) in offenders.iter().zip(slash_fraction) {
+				handled.push((details.offender, *fraction));
+			}
+		});
+		Weight::zero()
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_bfc_offences::GenesisConfig::<Test>::default()
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		KEYS.with(|k| k.borrow_mut().clear());
+		REPORTED.with(|r| r.borrow_mut().clear());
+		HANDLED.with(|h| h.borrow_mut().clear());
+	});
+	ext
+}
@@ -1,11 +1,15 @@
-use crate::{BalanceOf, Offence, ValidatorOffenceInfo};
+use crate::{BalanceOf, ConcurrentOffenders, Offence, UnappliedSlash, ValidatorOffenceInfo};
 
 use super::pallet::*;
 
-use bp_staking::{traits::OffenceHandler, TierType};
+use bp_staking::{traits::OffenceHandler, OffenceKind, RoundIndex, TierType};
 use frame_support::traits::{OnUnbalanced, ReservableCurrency};
-use sp_runtime::{traits::Zero, Perbill};
-use sp_staking::SessionIndex;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Perbill,
+};
+use sp_staking::{offence::Kind, SessionIndex};
+use sp_std::{vec, vec::Vec};
 
 impl<T: Config> OffenceHandler<T::AccountId, BalanceOf<T>> for Pallet<T> {
 	fn try_handle_offence(
@@ -13,12 +17,13 @@ impl<T: Config> OffenceHandler<T::AccountId, BalanceOf<T>> for Pallet<T> {
 		stash: &T::AccountId,
 		tier: TierType,
 		offence: Offence<BalanceOf<T>>,
-	) -> (bool, BalanceOf<T>) {
+		nominators: Vec<(T::AccountId, BalanceOf<T>)>,
+	) -> (bool, BalanceOf<T>, Vec<(T::AccountId, BalanceOf<T>)>) {
 		// offence check only if activated
 		if IsOffenceActive::<T>::get() {
-			return Self::handle_offence(who, stash, tier, offence);
+			return Self::handle_offence(who, stash, tier, offence, nominators);
 		}
-		return (false, BalanceOf::<T>::zero());
+		return (false, BalanceOf::<T>::zero(), vec![]);
 	}
 
 	fn handle_offence(
@@ -26,32 +31,47 @@ impl<T: Config> OffenceHandler<T::AccountId, BalanceOf<T>> for Pallet<T> {
 		stash: &T::AccountId,
 		tier: TierType,
 		offence: Offence<BalanceOf<T>>,
-	) -> (bool, BalanceOf<T>) {
-		let mut slash_amount = BalanceOf::<T>::zero();
-		// Check if the validator had offenced before.
-		if let Some(mut offences) = ValidatorOffences::<T>::get(who) {
-			if Self::is_offence_count_exceeds(offences.offence_count + 1, tier) {
-				// apply offence penalty to this validator
-				slash_amount = Self::try_slash(
-					who,
-					stash,
-					offences.aggregated_slash_fraction,
-					offence.self_bond,
-				);
-				ValidatorOffences::<T>::remove(who);
-				return (true, slash_amount);
-			}
+		nominators: Vec<(T::AccountId, BalanceOf<T>)>,
+	) -> (bool, BalanceOf<T>, Vec<(T::AccountId, BalanceOf<T>)>) {
+		// nominators are not exposed to the offence if nominator slashing is inactive
+		let nominators = if Self::is_nominator_slash_active() { nominators } else { vec![] };
+		let offence = Self::adjust_slash_fraction(offence, &nominators);
+		let kind = offence.kind;
+		let mut offences = ValidatorOffences::<T>::get(who);
+		let offence_count = offences.as_ref().map_or(0, |offences| offences.offence_count_of(kind));
 
-			// add a new offence and increase offence count to this validator
-			offences.add_offence(offence);
-			ValidatorOffences::<T>::insert(who, offences);
+		// Check if the offence count of this kind exceeds the limit. Kinds that are limited to
+		// zero offences (e.g. equivocations) will be slashed as soon as they are reported.
+		if Self::is_offence_count_exceeds(kind, offence_count + 1, tier) {
+			let slash_fraction = match offences.as_mut() {
+				Some(offences) if offence_count > 0 => {
+					let slash_fraction = offences.slash_fraction_of(kind);
+					offences.remove_offences_of(kind);
+					slash_fraction
+				},
+				_ => offence.slash_fraction,
+			};
+			match offences {
+				Some(offences) if !offences.offences.is_empty() => {
+					ValidatorOffences::<T>::insert(who, offences)
+				},
+				_ => ValidatorOffences::<T>::remove(who),
+			}
+			return Self::slash_offender(who, stash, &offence, slash_fraction, nominators);
+		}
 
-			(false, slash_amount)
-		} else {
-			// add the initial offence to this validator
-			ValidatorOffences::<T>::insert(who, ValidatorOffenceInfo::new(offence));
-			return (false, slash_amount);
+		match offences {
+			Some(mut offences) => {
+				// add a new offence and increase offence count to this validator
+				offences.add_offence(offence);
+				ValidatorOffences::<T>::insert(who, offences);
+			},
+			None => {
+				// add the initial offence to this validator
+				ValidatorOffences::<T>::insert(who, ValidatorOffenceInfo::new(offence));
+			},
 		}
+		(false, BalanceOf::<T>::zero(), vec![])
 	}
 
 	/// Slash validator account if IsSlashActive is active
@@ -63,31 +83,218 @@ impl<T: Config> OffenceHandler<T::AccountId, BalanceOf<T>> for Pallet<T> {
 	) -> BalanceOf<T> {
 		// slash bonds only if activated
 		if IsSlashActive::<T>::get() {
-			let slash_amount = slash_fraction * bond;
 			// slash the validator's reserved self bond
 			// the slashed imbalance will be reserved to the treasury
-			T::Slash::on_unbalanced(T::Currency::slash_reserved(stash, slash_amount).0);
-			Self::deposit_event(Event::Slashed { who: who.clone(), amount: slash_amount });
+			let slash_amount = Self::slash_reserved(stash, slash_fraction * bond);
+			if !slash_amount.is_zero() {
+				Self::deposit_event(Event::Slashed { who: who.clone(), amount: slash_amount });
+			}
 			return slash_amount;
 		}
 		BalanceOf::<T>::zero()
 	}
 
+	/// Slash nominator accounts if both IsSlashActive and IsNominatorSlashActive are active
+	fn try_slash_nominators(
+		slash_fraction: Perbill,
+		nominators: Vec<(T::AccountId, BalanceOf<T>)>,
+	) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		// slash nominations only if activated
+		if !Self::is_nominator_slash_active() {
+			return vec![];
+		}
+		nominators
+			.into_iter()
+			.filter_map(|(nominator, exposure)| {
+				// slash the nominator's reserved nomination
+				// the slashed imbalance will be reserved to the treasury
+				let slash_amount = Self::slash_reserved(&nominator, slash_fraction * exposure);
+				if slash_amount.is_zero() {
+					return None;
+				}
+				Self::deposit_event(Event::Slashed {
+					who: nominator.clone(),
+					amount: slash_amount,
+				});
+				Some((nominator, slash_amount))
+			})
+			.collect()
+	}
+
+	fn apply_unapplied_slashes<B, F>(
+		now: RoundIndex,
+		current_bond: B,
+		current_nomination: F,
+	) -> Vec<(T::AccountId, BalanceOf<T>, Vec<(T::AccountId, BalanceOf<T>)>)>
+	where
+		B: Fn(&T::AccountId) -> Option<BalanceOf<T>>,
+		F: Fn(&T::AccountId, &T::AccountId) -> BalanceOf<T>,
+	{
+		UnappliedSlashes::<T>::take(now)
+			.into_iter()
+			.filter_map(|slash| {
+				Self::note_settled_slash(&slash.who);
+				// the stake of an offender that already left has been returned with its nominations
+				let Some(bond) = current_bond(&slash.who) else {
+					Self::deposit_event(Event::DeferredSlashSkipped {
+						who: slash.who,
+						apply_round: now,
+					});
+					return None;
+				};
+				let slash_amount = Self::try_slash(
+					&slash.who,
+					&slash.stash,
+					slash.slash_fraction,
+					slash.self_bond.min(bond),
+				);
+				// the nominations may have been revoked or decreased since the offence
+				let nominators = slash
+					.nominators
+					.into_iter()
+					.map(|(nominator, exposure)| {
+						let nomination = current_nomination(&slash.who, &nominator);
+						(nominator, exposure.min(nomination))
+					})
+					.collect();
+				let nominators_slash = Self::try_slash_nominators(slash.slash_fraction, nominators);
+				Some((slash.who, slash_amount, nominators_slash))
+			})
+			.collect()
+	}
+
+	fn has_unapplied_slash(who: &T::AccountId) -> bool {
+		PendingSlashCount::<T>::contains_key(who)
+	}
+
 	fn refresh_offences(session_index: SessionIndex) {
-		<ValidatorOffences<T>>::iter().for_each(|offences| {
-			if (session_index - offences.1.latest_offence_session_index)
-				> OffenceExpirationInSessions::<T>::get()
-			{
-				<ValidatorOffences<T>>::remove(&offences.0);
+		// called on the first block of the given session
+		CurrentSessionStart::<T>::put((session_index, frame_system::Pallet::<T>::block_number()));
+
+		let expirations: Vec<(OffenceKind, SessionIndex)> = OffenceKind::ALL
+			.into_iter()
+			.map(|kind| (kind, OffenceKindConfigs::<T>::get(kind).expiration_in_sessions))
+			.collect();
+		<ValidatorOffences<T>>::iter().for_each(|(who, mut offences)| {
+			let mut is_refreshed = false;
+			for (kind, expiration) in &expirations {
+				if let Some(latest) = offences.latest_session_of(*kind) {
+					if session_index.saturating_sub(latest) > *expiration {
+						offences.remove_offences_of(*kind);
+						is_refreshed = true;
+					}
+				}
 			}
+			if offences.offences.is_empty() {
+				<ValidatorOffences<T>>::remove(&who);
+			} else if is_refreshed {
+				<ValidatorOffences<T>>::insert(&who, offences);
+			}
+		});
+	}
+
+	fn is_offence_count_exceeds(kind: OffenceKind, count: u32, tier: TierType) -> bool {
+		// if offence count exceeds the configured limit of this kind
+		count > OffenceKindConfigs::<T>::get(kind).maximum_offence_count(tier)
+	}
+}
+
+impl<T: Config> ConcurrentOffenders for Pallet<T> {
+	fn concurrent_offenders(kind: &Kind, time_slot: &[u8]) -> u32 {
+		<ConcurrentOffenderCount<T>>::get(kind, time_slot)
+	}
+
+	fn set_concurrent_offenders(kind: &Kind, time_slot: &[u8], count: u32) {
+		<ConcurrentOffenderCount<T>>::insert(kind, time_slot, count);
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns whether both the slash mechanism and the nominator slash mechanism are activated.
+	fn is_nominator_slash_active() -> bool {
+		IsSlashActive::<T>::get() && IsNominatorSlashActive::<T>::get()
+	}
+
+	/// Slash the given amount of the reserved balance of `who`. Returns the amount actually
+	/// slashed, which is lower than the given amount if the reserved balance falls short.
+	fn slash_reserved(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		let (imbalance, remaining) = T::Currency::slash_reserved(who, amount);
+		T::Slash::on_unbalanced(imbalance);
+		amount.saturating_sub(remaining)
+	}
+
+	/// Apply the penalty of the given offence to the validator and its nominators, or defer it to
+	/// a future round if a slash defer duration is set.
+	fn slash_offender(
+		who: &T::AccountId,
+		stash: &T::AccountId,
+		offence: &Offence<BalanceOf<T>>,
+		slash_fraction: Perbill,
+		nominators: Vec<(T::AccountId, BalanceOf<T>)>,
+	) -> (bool, BalanceOf<T>, Vec<(T::AccountId, BalanceOf<T>)>) {
+		let defer_duration = SlashDeferDuration::<T>::get();
+		if !defer_duration.is_zero() {
+			// defer the offence penalty to the future round
+			let apply_round = offence.round_index.saturating_add(defer_duration);
+			UnappliedSlashes::<T>::append(
+				apply_round,
+				UnappliedSlash {
+					who: who.clone(),
+					stash: stash.clone(),
+					self_bond: offence.self_bond,
+					nominators,
+					slash_fraction,
+					reported_round: offence.round_index,
+				},
+			);
+			PendingSlashCount::<T>::mutate(who, |count| {
+				*count = Some(count.unwrap_or_default().saturating_add(1))
+			});
+			Self::deposit_event(Event::SlashDeferred {
+				who: who.clone(),
+				apply_round,
+				slash_fraction,
+			});
+			return (true, BalanceOf::<T>::zero(), vec![]);
+		}
+
+		// apply offence penalty to this validator
+		let slash_amount = Self::try_slash(who, stash, slash_fraction, offence.self_bond);
+		// apply offence penalty to the nominators of this validator
+		let nominators_slash = Self::try_slash_nominators(slash_fraction, nominators);
+		(true, slash_amount, nominators_slash)
+	}
+
+	/// Decrease the number of pending slashes of the given validator once one of its deferred
+	/// slashes has been applied or cancelled.
+	pub(crate) fn note_settled_slash(who: &T::AccountId) {
+		PendingSlashCount::<T>::mutate_exists(who, |count| {
+			*count = count.map(|count| count.saturating_sub(1)).filter(|count| !count.is_zero());
 		});
 	}
 
-	fn is_offence_count_exceeds(count: u32, tier: TierType) -> bool {
-		// if offence count exceeds the configured limit
-		return match tier {
-			TierType::Full => count > FullMaximumOffenceCount::<T>::get(),
-			_ => count > BasicMaximumOffenceCount::<T>::get(),
-		};
+	/// Adjust the slash fraction of the given offence to the policy of its kind. Equivocations are
+	/// slashed by at least the configured equivocation slash fraction, and every offence is
+	/// limited to the maximum of its kind. The slash amounts of the offence are recomputed with
+	/// the adjusted slash fraction and the given nominator exposures.
+	fn adjust_slash_fraction(
+		mut offence: Offence<BalanceOf<T>>,
+		nominators: &[(T::AccountId, BalanceOf<T>)],
+	) -> Offence<BalanceOf<T>> {
+		let mut slash_fraction = offence.slash_fraction;
+		if offence.kind.is_equivocation() {
+			slash_fraction = slash_fraction.max(EquivocationSlashFraction::<T>::get());
+		}
+		slash_fraction =
+			slash_fraction.min(OffenceKindConfigs::<T>::get(offence.kind).max_slash_fraction);
+
+		offence.slash_fraction = slash_fraction;
+		offence.offender_slash = slash_fraction * offence.self_bond;
+		offence.nominators_slash =
+			nominators.iter().fold(BalanceOf::<T>::zero(), |acc, (_, exposure)| {
+				acc.saturating_add(slash_fraction * *exposure)
+			});
+		offence.total_slash = offence.offender_slash.saturating_add(offence.nominators_slash);
+		offence
 	}
 }
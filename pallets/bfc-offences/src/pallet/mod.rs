@@ -1,23 +1,31 @@
 mod impls;
 
 use crate::{
-	migrations, BalanceOf, NegativeImbalanceOf, OffenceCount, ValidatorOffenceInfo, WeightInfo,
+	migrations, BalanceOf, NegativeImbalanceOf, OffenceCount, UnappliedSlash, ValidatorOffenceInfo,
+	WeightInfo,
 };
 
-use bp_staking::TierType;
+use bp_staking::{OffenceKind, OffenceKindConfig, RoundIndex, TierType};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{Currency, OnRuntimeUpgrade, OnUnbalanced, ReservableCurrency, StorageVersion},
 };
 use frame_system::pallet_prelude::*;
-use sp_staking::SessionIndex;
+use sp_consensus_slots::EquivocationProof;
+use sp_runtime::{traits::MaybeSerializeDeserialize, Perbill, RuntimeAppPublic};
+use sp_session::{GetSessionNumber, GetValidatorCount};
+use sp_staking::{
+	offence::{Kind, OffenceReportSystem},
+	SessionIndex,
+};
+use sp_std::boxed::Box;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
 
 	/// Pallet for bfc offences
 	#[pallet::pallet]
@@ -31,21 +39,44 @@ pub mod pallet {
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 		/// Handler for the unbalanced reduction when slashing a preimage deposit.
 		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
-		/// The default offence expiration in sessions
-		#[pallet::constant]
-		type DefaultOffenceExpirationInSessions: Get<SessionIndex>;
-		/// The default maximum offence count for all full nodes
-		#[pallet::constant]
-		type DefaultFullMaximumOffenceCount: Get<OffenceCount>;
-		/// The default maximum offence count for all basic nodes
+		/// The default offence management policy of each offence kind
 		#[pallet::constant]
-		type DefaultBasicMaximumOffenceCount: Get<OffenceCount>;
+		type DefaultOffenceKindConfigs: Get<Vec<(OffenceKind, OffenceKindConfig)>>;
 		/// The activation of validator offence management
 		#[pallet::constant]
 		type IsOffenceActive: Get<bool>;
 		/// The activation of validator slashing
 		#[pallet::constant]
 		type IsSlashActive: Get<bool>;
+		/// The activation of nominator slashing
+		#[pallet::constant]
+		type IsNominatorSlashActive: Get<bool>;
+		/// The default number of rounds that slashes are deferred by
+		#[pallet::constant]
+		type DefaultSlashDeferDuration: Get<RoundIndex>;
+		/// The shortest number of rounds that a candidate or nominator has to wait to unbond its
+		/// stake. Slashes must be deferred by fewer rounds so that they are applied before the
+		/// stake of the offence can be withdrawn.
+		#[pallet::constant]
+		type MinUnbondingDelay: Get<RoundIndex>;
+		/// The default slash fraction of consensus equivocations
+		#[pallet::constant]
+		type DefaultEquivocationSlashFraction: Get<Perbill>;
+		/// The identifier type of an Aura authority
+		type AuraId: Member
+			+ Parameter
+			+ RuntimeAppPublic
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen;
+		/// The proof of key ownership, used for validating Aura equivocation reports
+		type KeyOwnerProof: Parameter + GetSessionNumber + GetValidatorCount;
+		/// The Aura equivocation proof processing system
+		type EquivocationReportSystem: OffenceReportSystem<
+			Option<Self::AccountId>,
+			(EquivocationProof<HeaderFor<Self>, Self::AuraId>, Self::KeyOwnerProof),
+		>;
+		/// The origin that is allowed to cancel deferred slashes
+		type CancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		/// Weight information for extrinsics in this pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -56,21 +87,45 @@ pub mod pallet {
 		CannotSetBelowMin,
 		/// Cannot set the value as identical to the previous value
 		NoWritingSameValue,
+		/// The deferred slash does not exist
+		UnappliedSlashDNE,
+		/// The equivocation proof is invalid
+		InvalidEquivocationProof,
+		/// The key ownership proof is invalid
+		InvalidKeyOwnershipProof,
+		/// The given equivocation has already been reported
+		DuplicateOffenceReport,
+		/// Cannot defer slashes by the unbonding delay or longer
+		CannotSetAboveUnbondingDelay,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Set the offence expiration
+		/// Set the offence expiration of unresponsiveness offences
 		OffenceExpirationSet { old: SessionIndex, new: SessionIndex },
-		/// Set the maximum offence count
+		/// Set the maximum offence count of unresponsiveness offences
 		MaximumOffenceCountSet { old: OffenceCount, new: OffenceCount, tier: TierType },
+		/// Set the offence management policy of an offence kind
+		OffenceKindConfigSet { kind: OffenceKind, old: OffenceKindConfig, new: OffenceKindConfig },
 		/// Set the activation of validator offence management
 		OffenceActivationSet { is_active: bool },
 		/// Set the activation of validator slashing
 		SlashActivationSet { is_active: bool },
+		/// Set the activation of nominator slashing
+		NominatorSlashActivationSet { is_active: bool },
 		/// A validator or nominator has been slashed due to misbehavior
 		Slashed { who: T::AccountId, amount: BalanceOf<T> },
+		/// Set the number of rounds that slashes are deferred by
+		SlashDeferDurationSet { old: RoundIndex, new: RoundIndex },
+		/// A slash of a validator and its nominators has been deferred to the given round
+		SlashDeferred { who: T::AccountId, apply_round: RoundIndex, slash_fraction: Perbill },
+		/// A deferred slash has been cancelled
+		DeferredSlashCancelled { who: T::AccountId, apply_round: RoundIndex },
+		/// Set the slash fraction of consensus equivocations
+		EquivocationSlashFractionSet { old: Perbill, new: Perbill },
+		/// A deferred slash has been skipped since the offender already left the candidate pool
+		DeferredSlashSkipped { who: T::AccountId, apply_round: RoundIndex },
 	}
 
 	#[pallet::storage]
@@ -80,16 +135,10 @@ pub mod pallet {
 		StorageMap<_, Twox64Concat, T::AccountId, ValidatorOffenceInfo<BalanceOf<T>>, OptionQuery>;
 
 	#[pallet::storage]
-	/// The current offence expiration in sessions
-	pub type OffenceExpirationInSessions<T: Config> = StorageValue<_, SessionIndex, ValueQuery>;
-
-	#[pallet::storage]
-	/// The current maximum offence count for all full nodes
-	pub type FullMaximumOffenceCount<T: Config> = StorageValue<_, OffenceCount, ValueQuery>;
-
-	#[pallet::storage]
-	/// The current maximum offence count for all basic nodes
-	pub type BasicMaximumOffenceCount<T: Config> = StorageValue<_, OffenceCount, ValueQuery>;
+	/// The current offence management policy (maximum counts, slash fraction and expiration) of
+	/// each offence kind
+	pub type OffenceKindConfigs<T: Config> =
+		StorageMap<_, Twox64Concat, OffenceKind, OffenceKindConfig, ValueQuery>;
 
 	#[pallet::storage]
 	/// The current activation of validator offence management
@@ -99,20 +148,68 @@ pub mod pallet {
 	/// The current activation of validator slashing
 	pub type IsSlashActive<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+	#[pallet::storage]
+	/// The current activation of nominator slashing. If activated, the slash fraction will also be
+	/// applied pro rata to the nominations of the offender.
+	pub type IsNominatorSlashActive<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	/// The current number of rounds that slashes are deferred by. Slashes are applied immediately
+	/// if it's set to zero.
+	pub type SlashDeferDuration<T: Config> = StorageValue<_, RoundIndex, ValueQuery>;
+
+	#[pallet::storage]
+	/// The current minimum slash fraction of consensus equivocations (Aura and GRANDPA). Reported
+	/// equivocations are slashed by at least this fraction.
+	pub type EquivocationSlashFraction<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	#[pallet::storage]
+	/// The index and the first block of the current session. Aura equivocations can only be
+	/// reported within the session they happened in, since key ownership proofs are only
+	/// generated for the current session.
+	pub type CurrentSessionStart<T: Config> =
+		StorageValue<_, (SessionIndex, BlockNumberFor<T>), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::unbounded]
+	/// The slashes that are scheduled to be applied at the start of each round
+	pub type UnappliedSlashes<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		RoundIndex,
+		Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// The number of deferred slashes of a specific validator that are not applied yet
+	pub type PendingSlashCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::unbounded]
+	/// The number of offenders reported for each offence kind and (encoded) time slot
+	pub type ConcurrentOffenderCount<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, Kind, Twox64Concat, Vec<u8>, u32, ValueQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_runtime_upgrade() -> Weight {
 			migrations::v3_update::MigrateToV3Update::<T>::on_runtime_upgrade()
+				.saturating_add(migrations::v4::MigrateToV4::<T>::on_runtime_upgrade())
+				.saturating_add(migrations::v5::MigrateToV5::<T>::on_runtime_upgrade())
+				.saturating_add(migrations::v6::MigrateToV6::<T>::on_runtime_upgrade())
 		}
 	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T> {
-		pub default_offence_expiration_in_sessions: SessionIndex,
-		pub default_full_maximum_offence_count: OffenceCount,
-		pub default_basic_maximum_offence_count: OffenceCount,
+		pub offence_kind_configs: Vec<(OffenceKind, OffenceKindConfig)>,
 		pub is_offence_active: bool,
 		pub is_slash_active: bool,
+		pub is_nominator_slash_active: bool,
+		pub slash_defer_duration: RoundIndex,
+		pub equivocation_slash_fraction: Perbill,
 		#[serde(skip)]
 		pub _config: PhantomData<T>,
 	}
@@ -120,12 +217,12 @@ pub mod pallet {
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
 			Self {
-				default_offence_expiration_in_sessions: T::DefaultOffenceExpirationInSessions::get(
-				),
-				default_full_maximum_offence_count: T::DefaultFullMaximumOffenceCount::get(),
-				default_basic_maximum_offence_count: T::DefaultBasicMaximumOffenceCount::get(),
+				offence_kind_configs: T::DefaultOffenceKindConfigs::get(),
 				is_offence_active: T::IsOffenceActive::get(),
 				is_slash_active: T::IsSlashActive::get(),
+				is_nominator_slash_active: T::IsNominatorSlashActive::get(),
+				slash_defer_duration: T::DefaultSlashDeferDuration::get(),
+				equivocation_slash_fraction: T::DefaultEquivocationSlashFraction::get(),
 				_config: Default::default(),
 			}
 		}
@@ -134,11 +231,24 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
-			OffenceExpirationInSessions::<T>::put(self.default_offence_expiration_in_sessions);
-			FullMaximumOffenceCount::<T>::put(self.default_full_maximum_offence_count);
-			BasicMaximumOffenceCount::<T>::put(self.default_basic_maximum_offence_count);
+			for kind in OffenceKind::ALL {
+				let config = self
+					.offence_kind_configs
+					.iter()
+					.find(|(k, _)| *k == kind)
+					.map(|(_, config)| *config)
+					.expect("Every offence kind must be configured");
+				OffenceKindConfigs::<T>::insert(kind, config);
+			}
 			IsOffenceActive::<T>::put(self.is_offence_active);
 			IsSlashActive::<T>::put(self.is_slash_active);
+			IsNominatorSlashActive::<T>::put(self.is_nominator_slash_active);
+			assert!(
+				self.slash_defer_duration < T::MinUnbondingDelay::get(),
+				"Slashes must be deferred by fewer rounds than the unbonding delay"
+			);
+			SlashDeferDuration::<T>::put(self.slash_defer_duration);
+			EquivocationSlashFraction::<T>::put(self.equivocation_slash_fraction);
 		}
 	}
 
@@ -148,17 +258,28 @@ pub mod pallet {
 		#[pallet::weight(
 			(<T as Config>::WeightInfo::set_offence_expiration(), DispatchClass::Operational,)
 		)]
-		/// Set a new offence expiration for all validators. It must be specified in sessions.
+		/// Set a new offence expiration of unresponsiveness offences. It must be specified in
+		/// sessions.
+		/// - deprecated: use `set_offence_kind_config` instead
 		pub fn set_offence_expiration(
 			origin: OriginFor<T>,
 			new: SessionIndex,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			ensure!(new > 0u32, Error::<T>::CannotSetBelowMin);
-			let old = <OffenceExpirationInSessions<T>>::get();
+			let kind = OffenceKind::Unresponsive;
+			let old_config = <OffenceKindConfigs<T>>::get(kind);
+			let old = old_config.expiration_in_sessions;
 			ensure!(old != new, Error::<T>::NoWritingSameValue);
-			<OffenceExpirationInSessions<T>>::put(new);
+
+			let new_config = OffenceKindConfig { expiration_in_sessions: new, ..old_config };
+			<OffenceKindConfigs<T>>::insert(kind, new_config);
 			Self::deposit_event(Event::OffenceExpirationSet { old, new });
+			Self::deposit_event(Event::OffenceKindConfigSet {
+				kind,
+				old: old_config,
+				new: new_config,
+			});
 			Ok(().into())
 		}
 
@@ -166,7 +287,8 @@ pub mod pallet {
 		#[pallet::weight(
 			(<T as Config>::WeightInfo::set_max_offence_count(), DispatchClass::Operational,)
 		)]
-		/// Set a new maximum offence count for all validators.
+		/// Set a new maximum offence count of unresponsiveness offences for the given tier.
+		/// - deprecated: use `set_offence_kind_config` instead
 		pub fn set_max_offence_count(
 			origin: OriginFor<T>,
 			new: OffenceCount,
@@ -174,27 +296,30 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			ensure!(new > 0u32, Error::<T>::CannotSetBelowMin);
+			let kind = OffenceKind::Unresponsive;
+			let old_config = <OffenceKindConfigs<T>>::get(kind);
+			let mut new_config = old_config;
 			match tier {
 				TierType::Full => {
-					let old = <FullMaximumOffenceCount<T>>::get();
+					let old = old_config.full_maximum_offence_count;
 					ensure!(old != new, Error::<T>::NoWritingSameValue);
-					<FullMaximumOffenceCount<T>>::put(new);
+					new_config.full_maximum_offence_count = new;
 					Self::deposit_event(Event::MaximumOffenceCountSet { old, new, tier });
 				},
 				TierType::Basic => {
-					let old = <BasicMaximumOffenceCount<T>>::get();
+					let old = old_config.basic_maximum_offence_count;
 					ensure!(old != new, Error::<T>::NoWritingSameValue);
-					<BasicMaximumOffenceCount<T>>::put(new);
+					new_config.basic_maximum_offence_count = new;
 					Self::deposit_event(Event::MaximumOffenceCountSet { old, new, tier });
 				},
 				TierType::All => {
-					let old_full = <FullMaximumOffenceCount<T>>::get();
+					let old_full = old_config.full_maximum_offence_count;
 					ensure!(old_full != new, Error::<T>::NoWritingSameValue);
-					let old_basic = <BasicMaximumOffenceCount<T>>::get();
+					let old_basic = old_config.basic_maximum_offence_count;
 					ensure!(old_basic != new, Error::<T>::NoWritingSameValue);
 
-					<FullMaximumOffenceCount<T>>::put(new);
-					<BasicMaximumOffenceCount<T>>::put(new);
+					new_config.full_maximum_offence_count = new;
+					new_config.basic_maximum_offence_count = new;
 
 					Self::deposit_event(Event::MaximumOffenceCountSet {
 						old: old_full,
@@ -208,6 +333,12 @@ pub mod pallet {
 					});
 				},
 			}
+			<OffenceKindConfigs<T>>::insert(kind, new_config);
+			Self::deposit_event(Event::OffenceKindConfigSet {
+				kind,
+				old: old_config,
+				new: new_config,
+			});
 			Ok(().into())
 		}
 
@@ -242,5 +373,143 @@ pub mod pallet {
 			Self::deposit_event(Event::SlashActivationSet { is_active });
 			Ok(().into())
 		}
+
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			(<T as Config>::WeightInfo::set_nominator_slash_activation(), DispatchClass::Operational,)
+		)]
+		/// Set the activation of nominator slashing.
+		pub fn set_nominator_slash_activation(
+			origin: OriginFor<T>,
+			is_active: bool,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				is_active != <IsNominatorSlashActive<T>>::get(),
+				Error::<T>::NoWritingSameValue
+			);
+			<IsNominatorSlashActive<T>>::put(is_active);
+			Self::deposit_event(Event::NominatorSlashActivationSet { is_active });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			(<T as Config>::WeightInfo::set_slash_defer_duration(), DispatchClass::Operational,)
+		)]
+		/// Set a new number of rounds that slashes are deferred by. Slashes will be applied
+		/// immediately if it's set to zero. It must be shorter than the unbonding delays, otherwise
+		/// the offenders could withdraw their stake before the slash is applied.
+		pub fn set_slash_defer_duration(
+			origin: OriginFor<T>,
+			new: RoundIndex,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(new < T::MinUnbondingDelay::get(), Error::<T>::CannotSetAboveUnbondingDelay);
+			let old = <SlashDeferDuration<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<SlashDeferDuration<T>>::put(new);
+			Self::deposit_event(Event::SlashDeferDurationSet { old, new });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			(<T as Config>::WeightInfo::cancel_deferred_slash(), DispatchClass::Operational,)
+		)]
+		/// Cancel the deferred slash of the given validator scheduled to be applied in the given
+		/// round. Only the penalty is cancelled: a validator that has been jailed for the offence
+		/// stays jailed until it unjails itself once the jail duration has passed.
+		pub fn cancel_deferred_slash(
+			origin: OriginFor<T>,
+			apply_round: RoundIndex,
+			who: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::ensure_origin(origin)?;
+			<UnappliedSlashes<T>>::try_mutate(apply_round, |slashes| -> DispatchResult {
+				let index = slashes
+					.iter()
+					.position(|slash| slash.who == who)
+					.ok_or(Error::<T>::UnappliedSlashDNE)?;
+				slashes.remove(index);
+				Ok(())
+			})?;
+			Self::note_settled_slash(&who);
+			Self::deposit_event(Event::DeferredSlashCancelled { who, apply_round });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(
+			(<T as Config>::WeightInfo::set_offence_kind_config(), DispatchClass::Operational,)
+		)]
+		/// Set a new offence management policy for the given offence kind. The expiration must be
+		/// specified as a non-zero value. Offences of the given kind will be slashed as soon as
+		/// they are reported if the maximum offence count is set to zero.
+		pub fn set_offence_kind_config(
+			origin: OriginFor<T>,
+			kind: OffenceKind,
+			new: OffenceKindConfig,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(new.expiration_in_sessions > 0u32, Error::<T>::CannotSetBelowMin);
+			let old = <OffenceKindConfigs<T>>::get(kind);
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<OffenceKindConfigs<T>>::insert(kind, new);
+			Self::deposit_event(Event::OffenceKindConfigSet { kind, old, new });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(
+			(<T as Config>::WeightInfo::set_equivocation_slash_fraction(), DispatchClass::Operational,)
+		)]
+		/// Set a new minimum slash fraction of consensus equivocations.
+		pub fn set_equivocation_slash_fraction(
+			origin: OriginFor<T>,
+			new: Perbill,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let old = <EquivocationSlashFraction<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<EquivocationSlashFraction<T>>::put(new);
+			Self::deposit_event(Event::EquivocationSlashFractionSet { old, new });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::report_aura_equivocation())]
+		/// Report an Aura slot equivocation of a validator. This method will verify the
+		/// equivocation proof and validate the given key ownership proof against the extracted
+		/// offender. If both are valid, the offence will be reported. This extrinsic must be called
+		/// unsigned and it is expected that only block authors will call it (validated in
+		/// `ValidateUnsigned`), as such if the block author is defined it will be defined as the
+		/// equivocation reporter.
+		pub fn report_aura_equivocation_unsigned(
+			origin: OriginFor<T>,
+			equivocation_proof: Box<EquivocationProof<HeaderFor<T>, T::AuraId>>,
+			key_owner_proof: T::KeyOwnerProof,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			T::EquivocationReportSystem::process_evidence(
+				None,
+				(*equivocation_proof, key_owner_proof),
+			)?;
+			// waive the fee since the report is valid and beneficial
+			Ok(Pays::No.into())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			Self::validate_unsigned(source, call)
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			Self::pre_dispatch(call)
+		}
 	}
 }
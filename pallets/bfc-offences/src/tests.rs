@@ -0,0 +1,380 @@
+use crate::{
+	equivocation::check_equivocation_proof, migrations, mock::*, ConcurrentOffenderCount,
+	CurrentSessionStart, Error, Event, OffenceKindReporter, PendingSlashCount, UnappliedSlashes,
+};
+use bp_staking::{traits::OffenceHandler, Offence, OffenceKind, TierType};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+};
+use frame_system::pallet_prelude::HeaderFor;
+use parity_scale_codec::Encode;
+use sp_consensus_aura::{sr25519::AuthorityId, AURA_ENGINE_ID};
+use sp_consensus_slots::{EquivocationProof, Slot};
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::{traits::Header as _, Digest, DigestItem, Perbill};
+use sp_session::MembershipProof;
+use sp_staking::{
+	offence::{self, Offence as _, OffenceError, ReportOffence},
+	SessionIndex,
+};
+
+fn pair(seed: u8) -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[seed; 32])
+}
+
+fn register(pair: &sr25519::Pair, who: AccountId) {
+	KEYS.with(|k| k.borrow_mut().push((AuthorityId::from(pair.public()), who)));
+}
+
+/// Builds a header of the given slot that is sealed by the given key
+fn sealed_header(pair: &sr25519::Pair, number: u64, slot: u64, parent: u8) -> HeaderFor<Test> {
+	let mut header = HeaderFor::<Test>::new(
+		number,
+		Default::default(),
+		Default::default(),
+		H256::repeat_byte(parent),
+		Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(slot).encode())] },
+	);
+	let signature = pair.sign(header.hash().as_ref());
+	header.digest_mut().push(DigestItem::Seal(AURA_ENGINE_ID, signature.encode()));
+	header
+}
+
+fn proof(
+	offender: &sr25519::Pair,
+	first_header: HeaderFor<Test>,
+	second_header: HeaderFor<Test>,
+) -> EquivocationProof<HeaderFor<Test>, AuthorityId> {
+	EquivocationProof {
+		offender: AuthorityId::from(offender.public()),
+		slot: Slot::from(10),
+		first_header,
+		second_header,
+	}
+}
+
+fn key_owner_proof(session: u32) -> MembershipProof {
+	MembershipProof { session, trie_nodes: Vec::new(), validator_count: 3 }
+}
+
+fn report(
+	proof: EquivocationProof<HeaderFor<Test>, AuthorityId>,
+	key_owner_proof: MembershipProof,
+) -> frame_support::dispatch::DispatchResultWithPostInfo {
+	BfcOffences::report_aura_equivocation_unsigned(
+		RuntimeOrigin::none(),
+		Box::new(proof),
+		key_owner_proof,
+	)
+}
+
+#[test]
+fn double_signed_slot_is_reported() {
+	new_test_ext().execute_with(|| {
+		let alice = pair(1);
+		register(&alice, 1);
+		let proof = proof(&alice, sealed_header(&alice, 5, 10, 1), sealed_header(&alice, 5, 10, 2));
+		assert!(check_equivocation_proof(proof.clone()));
+
+		assert_ok!(report(proof.clone(), key_owner_proof(SESSION)));
+		assert_eq!(REPORTED.with(|r| r.borrow().clone()), vec![(1, 10, SESSION)]);
+
+		assert_noop!(
+			report(proof, key_owner_proof(SESSION)),
+			Error::<Test>::DuplicateOffenceReport
+		);
+	});
+}
+
+#[test]
+fn same_header_twice_is_not_an_equivocation() {
+	new_test_ext().execute_with(|| {
+		let alice = pair(1);
+		register(&alice, 1);
+		let header = sealed_header(&alice, 5, 10, 1);
+		let proof = proof(&alice, header.clone(), header);
+		assert!(!check_equivocation_proof(proof.clone()));
+
+		assert_noop!(
+			report(proof, key_owner_proof(SESSION)),
+			Error::<Test>::InvalidEquivocationProof
+		);
+	});
+}
+
+#[test]
+fn headers_of_different_slots_are_not_an_equivocation() {
+	new_test_ext().execute_with(|| {
+		let alice = pair(1);
+		register(&alice, 1);
+		let proof = proof(&alice, sealed_header(&alice, 5, 10, 1), sealed_header(&alice, 6, 11, 2));
+		assert!(!check_equivocation_proof(proof.clone()));
+
+		assert_noop!(
+			report(proof, key_owner_proof(SESSION)),
+			Error::<Test>::InvalidEquivocationProof
+		);
+	});
+}
+
+#[test]
+fn headers_of_another_author_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (pair(1), pair(2));
+		register(&alice, 1);
+		register(&bob, 2);
+		// the headers are sealed by alice but bob is blamed
+		let proof = proof(&bob, sealed_header(&alice, 5, 10, 1), sealed_header(&alice, 5, 10, 2));
+		assert!(!check_equivocation_proof(proof.clone()));
+
+		assert_noop!(
+			report(proof, key_owner_proof(SESSION)),
+			Error::<Test>::InvalidEquivocationProof
+		);
+	});
+}
+
+#[test]
+fn bad_seal_signature_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let alice = pair(1);
+		register(&alice, 1);
+		let mut second_header = sealed_header(&alice, 5, 10, 2);
+		second_header.digest_mut().pop();
+		let signature = alice.sign(b"not the header hash");
+		second_header
+			.digest_mut()
+			.push(DigestItem::Seal(AURA_ENGINE_ID, signature.encode()));
+		let proof = proof(&alice, sealed_header(&alice, 5, 10, 1), second_header);
+		assert!(!check_equivocation_proof(proof.clone()));
+
+		assert_noop!(
+			report(proof, key_owner_proof(SESSION)),
+			Error::<Test>::InvalidEquivocationProof
+		);
+	});
+}
+
+#[test]
+fn key_ownership_must_be_proven_for_the_current_session() {
+	new_test_ext().execute_with(|| {
+		let alice = pair(1);
+		register(&alice, 1);
+		let proof = proof(&alice, sealed_header(&alice, 5, 10, 1), sealed_header(&alice, 5, 10, 2));
+
+		assert_noop!(
+			report(proof.clone(), key_owner_proof(SESSION - 1)),
+			Error::<Test>::InvalidKeyOwnershipProof
+		);
+
+		// the equivocation happened before the current session started
+		CurrentSessionStart::<Test>::put((SESSION, 6));
+		assert_noop!(
+			report(proof.clone(), key_owner_proof(SESSION)),
+			Error::<Test>::InvalidKeyOwnershipProof
+		);
+
+		CurrentSessionStart::<Test>::put((SESSION, 5));
+		assert_ok!(report(proof, key_owner_proof(SESSION)));
+	});
+}
+
+/// Reports an offence of validator `1` (stash `11`) with a reserved self-bond of 1000 that is
+/// slashed by 10%, and returns the round its slash is deferred to
+fn defer_offence() -> u32 {
+	Balances::make_free_balance_be(&11, 1_000);
+	assert_ok!(Balances::reserve(&11, 1_000));
+	assert_ok!(BfcOffences::set_slash_defer_duration(RuntimeOrigin::root(), 2));
+	let offence = Offence::new(
+		OffenceKind::Unresponsive,
+		1,
+		1,
+		1_000,
+		100,
+		100,
+		0,
+		Perbill::from_percent(10),
+	);
+	assert_eq!(
+		<BfcOffences as OffenceHandler<_, _>>::handle_offence(
+			&1,
+			&11,
+			TierType::Full,
+			offence,
+			vec![]
+		),
+		(true, 0, vec![])
+	);
+	assert_eq!(UnappliedSlashes::<Test>::get(3).len(), 1);
+	3
+}
+
+#[test]
+fn slash_defer_duration_must_be_shorter_than_the_unbonding_delay() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BfcOffences::set_slash_defer_duration(RuntimeOrigin::root(), MinUnbondingDelay::get()),
+			Error::<Test>::CannotSetAboveUnbondingDelay
+		);
+		assert_ok!(BfcOffences::set_slash_defer_duration(
+			RuntimeOrigin::root(),
+			MinUnbondingDelay::get() - 1
+		));
+	});
+}
+
+#[test]
+fn deferred_slash_of_a_departed_offender_is_skipped() {
+	new_test_ext().execute_with(|| {
+		let apply_round = defer_offence();
+		assert!(<BfcOffences as OffenceHandler<_, _>>::has_unapplied_slash(&1));
+		assert!(!<BfcOffences as OffenceHandler<_, _>>::has_unapplied_slash(&2));
+
+		let slashes = <BfcOffences as OffenceHandler<_, _>>::apply_unapplied_slashes(
+			apply_round,
+			|_| None,
+			|_, _| 0,
+		);
+		assert!(slashes.is_empty());
+		assert!(UnappliedSlashes::<Test>::get(apply_round).is_empty());
+		assert!(!<BfcOffences as OffenceHandler<_, _>>::has_unapplied_slash(&1));
+		assert_eq!(Balances::reserved_balance(11), 1_000);
+		System::assert_last_event(Event::DeferredSlashSkipped { who: 1, apply_round }.into());
+	});
+}
+
+#[test]
+fn deferred_slash_is_bounded_by_the_current_bond() {
+	new_test_ext().execute_with(|| {
+		let apply_round = defer_offence();
+
+		// the self-bond has been decreased to 500 since the offence
+		let slashes = <BfcOffences as OffenceHandler<_, _>>::apply_unapplied_slashes(
+			apply_round,
+			|_| Some(500),
+			|_, _| 0,
+		);
+		assert_eq!(slashes, vec![(1, 50, vec![])]);
+		assert_eq!(Balances::reserved_balance(11), 950);
+		System::assert_last_event(Event::Slashed { who: 1, amount: 50 }.into());
+	});
+}
+
+#[test]
+fn deferred_slash_emits_the_actually_slashed_amount() {
+	new_test_ext().execute_with(|| {
+		let apply_round = defer_offence();
+		// only 30 is still reserved when the slash is applied
+		Balances::unreserve(&11, 970);
+
+		let slashes = <BfcOffences as OffenceHandler<_, _>>::apply_unapplied_slashes(
+			apply_round,
+			|_| Some(1_000),
+			|_, _| 0,
+		);
+		assert_eq!(slashes, vec![(1, 30, vec![])]);
+		assert_eq!(Balances::reserved_balance(11), 0);
+		System::assert_last_event(Event::Slashed { who: 1, amount: 30 }.into());
+	});
+}
+
+#[test]
+fn cancelled_slash_is_no_longer_pending() {
+	new_test_ext().execute_with(|| {
+		let apply_round = defer_offence();
+		assert_eq!(PendingSlashCount::<Test>::get(1), Some(1));
+
+		assert_ok!(BfcOffences::cancel_deferred_slash(RuntimeOrigin::root(), apply_round, 1));
+		assert!(!<BfcOffences as OffenceHandler<_, _>>::has_unapplied_slash(&1));
+		assert_eq!(PendingSlashCount::<Test>::get(1), None);
+		assert_noop!(
+			BfcOffences::cancel_deferred_slash(RuntimeOrigin::root(), apply_round, 1),
+			Error::<Test>::UnappliedSlashDNE
+		);
+	});
+}
+
+#[test]
+fn migration_to_v6_counts_the_pending_slashes() {
+	new_test_ext().execute_with(|| {
+		let apply_round = defer_offence();
+		let slash = UnappliedSlashes::<Test>::get(apply_round)[0].clone();
+		UnappliedSlashes::<Test>::append(apply_round + 1, slash);
+		PendingSlashCount::<Test>::remove(1);
+		StorageVersion::new(5).put::<BfcOffences>();
+
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(PendingSlashCount::<Test>::get(1), Some(2));
+		assert_eq!(BfcOffences::on_chain_storage_version(), 6);
+
+		// applying one of the slashes leaves the other pending
+		<BfcOffences as OffenceHandler<_, _>>::apply_unapplied_slashes(
+			apply_round,
+			|_| Some(1_000),
+			|_, _| 0,
+		);
+		assert!(<BfcOffences as OffenceHandler<_, _>>::has_unapplied_slash(&1));
+		assert_eq!(PendingSlashCount::<Test>::get(1), Some(1));
+	});
+}
+
+/// An offence whose slash fraction grows by 10% with each concurrent offender
+struct ConcurrentOffence {
+	slot: u64,
+	offenders: Vec<AccountId>,
+}
+
+impl offence::Offence<AccountId> for ConcurrentOffence {
+	const ID: offence::Kind = *b"test:concurrent_";
+	type TimeSlot = Slot;
+
+	fn offenders(&self) -> Vec<AccountId> {
+		self.offenders.clone()
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		SESSION
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		10
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		Slot::from(self.slot)
+	}
+
+	fn slash_fraction(&self, offenders_count: u32) -> Perbill {
+		Perbill::from_percent(10 * offenders_count)
+	}
+}
+
+#[test]
+fn concurrent_reports_are_slashed_by_the_total_offenders_of_the_slot() {
+	new_test_ext().execute_with(|| {
+		type Reporter = OffenceKindReporter<MockOffences, MockOffenceKindHandler, BfcOffences>;
+		let report = |slot, offenders| {
+			Reporter::report_offence(vec![], ConcurrentOffence { slot, offenders })
+		};
+		let handled = || HANDLED.with(|h| h.take());
+
+		assert_ok!(report(5, vec![1, 2]));
+		assert_eq!(handled(), vec![(1, Perbill::from_percent(20)), (2, Perbill::from_percent(20))]);
+
+		// the second report of the slot counts the offenders of the first one
+		assert_ok!(report(5, vec![2, 3]));
+		assert_eq!(handled(), vec![(3, Perbill::from_percent(30))]);
+
+		// other slots are counted on their own
+		assert_ok!(report(6, vec![4]));
+		assert_eq!(handled(), vec![(4, Perbill::from_percent(10))]);
+
+		assert_eq!(report(5, vec![1, 3]), Err(OffenceError::DuplicateReport));
+		assert!(handled().is_empty());
+		assert_eq!(
+			ConcurrentOffenderCount::<Test>::get(ConcurrentOffence::ID, Slot::from(5).encode()),
+			3
+		);
+	});
+}
@@ -11,8 +11,14 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn set_offence_expiration() -> Weight;
 	fn set_max_offence_count() -> Weight;
+	fn set_offence_kind_config() -> Weight;
 	fn set_offence_activation() -> Weight;
 	fn set_slash_activation() -> Weight;
+	fn set_nominator_slash_activation() -> Weight;
+	fn set_slash_defer_duration() -> Weight;
+	fn cancel_deferred_slash() -> Weight;
+	fn set_equivocation_slash_fraction() -> Weight;
+	fn report_aura_equivocation() -> Weight;
 }
 
 /// Weights for `pallet_bfc_offences` using the Substrate node and recommended hardware.
@@ -24,12 +30,32 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	fn set_max_offence_count() -> Weight {
 		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(1))
 	}
+	fn set_offence_kind_config() -> Weight {
+		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(1))
+	}
 	fn set_offence_activation() -> Weight {
 		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(1))
 	}
 	fn set_slash_activation() -> Weight {
 		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(1))
 	}
+	fn set_nominator_slash_activation() -> Weight {
+		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_slash_defer_duration() -> Weight {
+		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn cancel_deferred_slash() -> Weight {
+		T::DbWeight::get().reads(2).saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn set_equivocation_slash_fraction() -> Weight {
+		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn report_aura_equivocation() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
 }
 
 // For backwards compatibility and tests
@@ -40,10 +66,30 @@ impl WeightInfo for () {
 	fn set_max_offence_count() -> Weight {
 		RocksDbWeight::get().reads(1).saturating_add(RocksDbWeight::get().writes(1))
 	}
+	fn set_offence_kind_config() -> Weight {
+		RocksDbWeight::get().reads(1).saturating_add(RocksDbWeight::get().writes(1))
+	}
 	fn set_offence_activation() -> Weight {
 		RocksDbWeight::get().reads(1).saturating_add(RocksDbWeight::get().writes(1))
 	}
 	fn set_slash_activation() -> Weight {
 		RocksDbWeight::get().reads(1).saturating_add(RocksDbWeight::get().writes(1))
 	}
+	fn set_nominator_slash_activation() -> Weight {
+		RocksDbWeight::get().reads(1).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_slash_defer_duration() -> Weight {
+		RocksDbWeight::get().reads(1).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn cancel_deferred_slash() -> Weight {
+		RocksDbWeight::get().reads(2).saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn set_equivocation_slash_fraction() -> Weight {
+		RocksDbWeight::get().reads(1).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn report_aura_equivocation() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(12))
+			.saturating_add(RocksDbWeight::get().writes(8))
+	}
 }
@@ -0,0 +1,43 @@
+//! Runtime API definition for the bfc staking pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_runtime::FixedU128;
+use sp_std::vec::Vec;
+
+pub use bp_staking::{RoundIndex, TierType};
+pub use pallet_bfc_staking::{CandidateState, NominatorInfo, RewardEntry};
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the staking state of validator candidates and nominators.
+	pub trait BfcStakingApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns the state of the given candidate including its top and bottom nominations.
+		fn candidate_state(candidate: AccountId) -> Option<CandidateState<AccountId, Balance>>;
+
+		/// Returns the state of the given nominator including its pending unbonding requests.
+		fn nominator_state(nominator: AccountId) -> Option<NominatorInfo<AccountId, Balance>>;
+
+		/// Returns the selected candidates of the current round that matches the given tier.
+		fn selected_candidates(tier: TierType) -> Vec<AccountId>;
+
+		/// Returns the estimated annual percentage rate of the staking rewards as a ratio, which
+		/// may exceed one. If a candidate is given, the rate of its nominators is returned.
+		fn estimated_apr(candidate: Option<AccountId>) -> FixedU128;
+
+		/// Returns the amount of rewards that are prepared but not paid out yet for the given
+		/// account.
+		fn pending_rewards(account: AccountId) -> Balance;
+
+		/// Returns the rewards paid to the given account that were earned between the given
+		/// rounds (inclusive), oldest first.
+		fn reward_history(
+			account: AccountId,
+			from: RoundIndex,
+			to: RoundIndex,
+		) -> Vec<RewardEntry<AccountId, Balance>>;
+	}
+}
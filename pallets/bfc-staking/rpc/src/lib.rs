@@ -0,0 +1,176 @@
+//! RPC interface for the bfc staking pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use parity_scale_codec::Codec;
+use serde::{de::DeserializeOwned, Serialize};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{traits::Block as BlockT, FixedU128};
+
+pub use pallet_bfc_staking_rpc_runtime_api::{
+	BfcStakingApi as BfcStakingRuntimeApi, CandidateState, NominatorInfo, RewardEntry, RoundIndex,
+	TierType,
+};
+
+/// The error code returned when a runtime API call has failed.
+const RUNTIME_ERROR: i32 = 1;
+
+#[rpc(client, server)]
+pub trait BfcStakingApi<BlockHash, AccountId, Balance> {
+	/// Returns the state of the given candidate including its top and bottom nominations.
+	#[method(name = "bfcStaking_candidateState")]
+	fn candidate_state(
+		&self,
+		candidate: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CandidateState<AccountId, Balance>>>;
+
+	/// Returns the state of the given nominator including its pending unbonding requests.
+	#[method(name = "bfcStaking_nominatorState")]
+	fn nominator_state(
+		&self,
+		nominator: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NominatorInfo<AccountId, Balance>>>;
+
+	/// Returns the selected candidates of the current round that matches the given tier.
+	#[method(name = "bfcStaking_selectedCandidates")]
+	fn selected_candidates(
+		&self,
+		tier: TierType,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<AccountId>>;
+
+	/// Returns the estimated annual percentage rate of the staking rewards as a ratio, which may
+	/// exceed one. If a candidate is given, the rate of its nominators is returned.
+	#[method(name = "bfcStaking_estimatedApr")]
+	fn estimated_apr(
+		&self,
+		candidate: Option<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<FixedU128>;
+
+	/// Returns the amount of rewards that are prepared but not paid out yet for the given account.
+	#[method(name = "bfcStaking_pendingRewards")]
+	fn pending_rewards(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Balance>;
+
+	/// Returns the rewards paid to the given account that were earned between the given rounds
+	/// (inclusive), oldest first.
+	#[method(name = "bfcStaking_rewardHistory")]
+	fn reward_history(
+		&self,
+		account: AccountId,
+		from: RoundIndex,
+		to: RoundIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<RewardEntry<AccountId, Balance>>>;
+}
+
+/// Provides RPC methods to query the bfc staking state.
+pub struct BfcStaking<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> BfcStaking<C, Block> {
+	/// Creates a new instance of the BfcStaking RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime API error into an RPC error.
+fn runtime_error(message: &'static str, err: impl std::fmt::Debug) -> ErrorObjectOwned {
+	ErrorObject::owned(RUNTIME_ERROR, message, Some(format!("{:?}", err)))
+}
+
+impl<C, Block, AccountId, Balance> BfcStakingApiServer<<Block as BlockT>::Hash, AccountId, Balance>
+	for BfcStaking<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BfcStakingRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+	Balance: Codec + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+	fn candidate_state(
+		&self,
+		candidate: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<CandidateState<AccountId, Balance>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.candidate_state(at, candidate)
+			.map_err(|e| runtime_error("Unable to query candidate state.", e))
+	}
+
+	fn nominator_state(
+		&self,
+		nominator: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<NominatorInfo<AccountId, Balance>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.nominator_state(at, nominator)
+			.map_err(|e| runtime_error("Unable to query nominator state.", e))
+	}
+
+	fn selected_candidates(
+		&self,
+		tier: TierType,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.selected_candidates(at, tier)
+			.map_err(|e| runtime_error("Unable to query selected candidates.", e))
+	}
+
+	fn estimated_apr(
+		&self,
+		candidate: Option<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<FixedU128> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.estimated_apr(at, candidate)
+			.map_err(|e| runtime_error("Unable to query estimated APR.", e))
+	}
+
+	fn pending_rewards(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.pending_rewards(at, account)
+			.map_err(|e| runtime_error("Unable to query pending rewards.", e))
+	}
+
+	fn reward_history(
+		&self,
+		account: AccountId,
+		from: RoundIndex,
+		to: RoundIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<RewardEntry<AccountId, Balance>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.reward_history(at, account, from, to)
+			.map_err(|e| runtime_error("Unable to query reward history.", e))
+	}
+}
@@ -0,0 +1,314 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::election::{SequentialPhragmen, StakeCapped, TopN};
+use bp_staking::MAX_AUTHORITIES;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::vec;
+
+/// Creates `count` candidates alternating between the full and basic tiers, each nominated by
+/// the same `nominations` nominators
+fn create_candidates<T: Config>(count: u32, nominations: u32) {
+	let mut pool = <CandidatePool<T>>::get();
+	for i in 0..count {
+		let controller = account::<T::AccountId>("controller", i, 0);
+		let stash = account::<T::AccountId>("stash", i, 0);
+
+		let (tier, bond) = if i % 2 == 0 {
+			(TierType::Full, T::MinFullCandidateStk::get())
+		} else {
+			(TierType::Basic, T::MinBasicCandidateStk::get())
+		};
+		let bond = bond.saturating_add(i.into());
+		let top_nominations = (0..nominations)
+			.map(|n| Bond {
+				owner: account::<T::AccountId>("nominator", n, 0),
+				amount: T::MinNominatorStk::get().saturating_add(n.into()),
+			})
+			.collect::<Vec<Bond<T::AccountId, BalanceOf<T>>>>();
+		let total = top_nominations
+			.iter()
+			.fold(BalanceOf::<T>::zero(), |acc, n| acc.saturating_add(n.amount));
+		let voting_power = bond.saturating_add(total);
+
+		let mut state = CandidateMetadata::new::<T>(stash, bond, tier);
+		state.voting_power = voting_power;
+		<CandidateInfo<T>>::insert(&controller, state);
+		<TopNominations<T>>::insert(
+			&controller,
+			Nominations { nominations: top_nominations, total },
+		);
+		pool.try_insert(controller, voting_power).expect("candidate pool is not full");
+	}
+	<CandidatePool<T>>::put(pool);
+	<MaxFullSelected<T>>::put(count / 2);
+	<MaxBasicSelected<T>>::put(count / 2);
+}
+
+/// Creates a funded account with the given name and index
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account::<T::AccountId>(name, index, 0);
+	let amount = T::MinFullCandidateStk::get().saturating_mul(1_000u32.into());
+	T::Currency::make_free_balance_be(&who, amount);
+	who
+}
+
+/// Joins a basic candidate through the extrinsic and returns its controller
+fn join_candidate<T: Config>(index: u32) -> T::AccountId {
+	let stash = funded_account::<T>("stash", index);
+	let controller = account::<T::AccountId>("controller", index, 0);
+	let candidate_count = <CandidatePool<T>>::get().len() as u32;
+	Pallet::<T>::join_candidates(
+		RawOrigin::Signed(stash).into(),
+		controller.clone(),
+		None,
+		T::MinBasicCandidateStk::get(),
+		candidate_count,
+	)
+	.expect("candidate joins");
+	controller
+}
+
+/// Nominates the given candidate through the extrinsic
+fn nominate<T: Config>(nominator: &T::AccountId, candidate: &T::AccountId, amount: BalanceOf<T>) {
+	Pallet::<T>::nominate(
+		RawOrigin::Signed(nominator.clone()).into(),
+		candidate.clone(),
+		amount,
+		u32::MAX,
+		u32::MAX,
+	)
+	.expect("nominator nominates");
+}
+
+/// An identity field of the given length
+fn identity_field(len: u32) -> IdentityField {
+	IdentityField::truncate_from(vec![b'a'; len as usize])
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn compute_top_candidates_top_n(
+		x: Linear<1, MAX_AUTHORITIES>,
+		y: Linear<0, { T::MaxTopNominationsPerCandidate::get() }>,
+	) {
+		create_candidates::<T>(x, y);
+
+		#[block]
+		{
+			Pallet::<T>::compute_top_candidates_with::<TopN>();
+		}
+	}
+
+	#[benchmark]
+	fn compute_top_candidates_sequential_phragmen(
+		x: Linear<1, MAX_AUTHORITIES>,
+		y: Linear<0, { T::MaxTopNominationsPerCandidate::get() }>,
+	) {
+		create_candidates::<T>(x, y);
+
+		#[block]
+		{
+			Pallet::<T>::compute_top_candidates_with::<SequentialPhragmen>();
+		}
+	}
+
+	#[benchmark]
+	fn compute_top_candidates_stake_capped(
+		x: Linear<1, MAX_AUTHORITIES>,
+		y: Linear<0, { T::MaxTopNominationsPerCandidate::get() }>,
+	) {
+		create_candidates::<T>(x, y);
+
+		#[block]
+		{
+			Pallet::<T>::compute_top_candidates_with::<StakeCapped<T::MinFullCandidateStk>>();
+		}
+	}
+
+	#[benchmark]
+	fn take_validator_snapshots(x: Linear<1, MAX_AUTHORITIES>) {
+		create_candidates::<T>(x, T::MaxTopNominationsPerCandidate::get());
+		let validators =
+			(0..x).map(|i| account::<T::AccountId>("controller", i, 0)).collect::<Vec<_>>();
+		<PendingValidatorSnapshots<T>>::put((1u32, BoundedVec::truncate_from(validators)));
+
+		#[block]
+		{
+			Pallet::<T>::handle_pending_validator_snapshots(x);
+		}
+
+		assert!(<PendingValidatorSnapshots<T>>::get().is_none());
+	}
+
+	#[benchmark]
+	fn redelegate(
+		x: Linear<1, { Pallet::<T>::max_candidate_nominations() }>,
+		y: Linear<0, { Pallet::<T>::max_candidate_nominations() }>,
+	) {
+		let from = join_candidate::<T>(0);
+		let to = join_candidate::<T>(1);
+		// the redelegated stake enters the top of `to`. once both of its lists are full, its lowest
+		// top nomination is bumped into the bottom and kicks the lowest bottom nomination
+		for i in 0..y {
+			let filler = funded_account::<T>("to_filler", i);
+			nominate::<T>(&filler, &to, T::MinNominatorStk::get().saturating_add(i.into()));
+		}
+		for i in 1..x {
+			let filler = funded_account::<T>("from_filler", i);
+			nominate::<T>(&filler, &from, T::MinNominatorStk::get().saturating_add(i.into()));
+		}
+		// the entire nomination leaves `from`, whose highest bottom nomination is promoted
+		let caller = funded_account::<T>("nominator", 0);
+		let amount = T::MinNominatorStk::get().saturating_mul(4u32.into());
+		nominate::<T>(&caller, &from, amount);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), from.clone(), to.clone(), amount);
+
+		let state = <NominatorState<T>>::get(&caller).unwrap();
+		assert_eq!(state.nominations.get(&to), Some(&amount));
+		assert!(state.nominations.get(&from).is_none());
+		// a full `to` keeps its nomination count after kicking its lowest bottom nomination
+		assert_eq!(
+			<CandidateInfo<T>>::get(&to).unwrap().nomination_count,
+			(y + 1).min(Pallet::<T>::max_candidate_nominations())
+		);
+	}
+
+	#[benchmark]
+	fn pay_one_validator_reward(y: Linear<0, { T::MaxTopNominationsPerCandidate::get() }>) {
+		let validator = join_candidate::<T>(0);
+		// the points of full validators are weighted by their relayer score
+		<CandidateInfo<T>>::mutate(&validator, |state| {
+			if let Some(state) = state {
+				state.tier = TierType::Full;
+			}
+		});
+		for i in 0..y {
+			let nominator = funded_account::<T>("nominator", i);
+			nominate::<T>(&nominator, &validator, T::MinNominatorStk::get());
+		}
+		let state = <CandidateInfo<T>>::get(&validator).unwrap();
+		let snapshot = ValidatorSnapshot {
+			bond: state.bond,
+			nominations: <TopNominations<T>>::get(&validator).unwrap().nominations,
+			total: state.voting_power,
+			commission: state.commission,
+		};
+		<AtStake<T>>::insert(1, &validator, snapshot);
+		<AwardedPts<T>>::insert(1, &validator, 20);
+		<Points<T>>::insert(1, 20);
+		<WeightedPoints<T>>::insert(1, 20);
+		let reward = T::MinFullCandidateStk::get();
+		let payout_info = DelayedPayout {
+			round_issuance: reward,
+			total_staking_reward: reward,
+			validator_commission: state.commission,
+		};
+
+		#[block]
+		{
+			Pallet::<T>::pay_one_validator_reward(1, payout_info);
+		}
+
+		assert!(!<AtStake<T>>::contains_key(1, &validator));
+	}
+
+	#[benchmark]
+	fn set_max_commission_increase_per_round() {
+		let new = Perbill::from_percent(1);
+		<MaxCommissionIncreasePerRound<T>>::put(Perbill::zero());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, new);
+
+		assert_eq!(<MaxCommissionIncreasePerRound<T>>::get(), new);
+	}
+
+	#[benchmark]
+	fn set_relayer_score_reward_activation() {
+		<IsRelayerScoreRewardActive<T>>::put(false);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, true);
+
+		assert!(<IsRelayerScoreRewardActive<T>>::get());
+	}
+
+	#[benchmark]
+	fn set_candidate_identity() {
+		let controller = join_candidate::<T>(0);
+		// only full candidates can register a relayer endpoint
+		<CandidateInfo<T>>::mutate(&controller, |state| {
+			if let Some(state) = state {
+				state.tier = TierType::Full;
+			}
+		});
+		let field = || identity_field(MAX_IDENTITY_FIELD_LENGTH);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(controller.clone()),
+			field(),
+			field(),
+			field(),
+			[1u8; 32],
+			Some(field()),
+		);
+
+		assert!(<CandidateIdentities<T>>::contains_key(&controller));
+	}
+
+	#[benchmark]
+	fn clear_candidate_identity() {
+		let controller = join_candidate::<T>(0);
+		let field = || identity_field(MAX_IDENTITY_FIELD_LENGTH);
+		Pallet::<T>::set_candidate_identity(
+			RawOrigin::Signed(controller.clone()).into(),
+			field(),
+			field(),
+			field(),
+			[1u8; 32],
+			None,
+		)
+		.expect("identity is set");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(controller.clone()));
+
+		assert!(!<CandidateIdentities<T>>::contains_key(&controller));
+	}
+
+	#[benchmark]
+	fn cancel_leave_candidates(x: Linear<1, { MAX_AUTHORITIES - 1 }>) {
+		create_candidates::<T>(x, 0);
+		let controller = join_candidate::<T>(x);
+		Pallet::<T>::schedule_leave_candidates(RawOrigin::Signed(controller.clone()).into(), x + 1)
+			.expect("candidate schedules to leave");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(controller.clone()), x);
+
+		assert!(<CandidatePool<T>>::get().contains_key(&controller));
+	}
+
+	#[benchmark]
+	fn unjail() {
+		let controller = join_candidate::<T>(0);
+		Pallet::<T>::jail_validator(&controller);
+		// the jail ends in the current round
+		let (_, jailed_until) = <JailCount<T>>::get(&controller);
+		<Round<T>>::mutate(|round| round.current_round_index = jailed_until);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(controller.clone()));
+
+		assert!(<CandidatePool<T>>::get().contains_key(&controller));
+	}
+}
@@ -0,0 +1,243 @@
+//! Validator election algorithms used to select the active validator set of each round.
+use crate::{Bond, WeightInfo};
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_npos_elections::{seq_phragmen, IdentifierT, VoteWeight};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, One, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+	Perbill,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
+
+/// A validator candidate participating in an election
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ElectionCandidate<AccountId, Balance> {
+	/// The candidate's controller account
+	pub who: AccountId,
+	/// The self-bonded amount of the candidate
+	pub bond: Balance,
+	/// The total voting power of the candidate (self-bond + top nominations)
+	pub voting_power: Balance,
+	/// The top nominations of the candidate. Only collected if the election provider requires it.
+	pub nominations: Vec<Bond<AccountId, Balance>>,
+}
+
+/// Something that can elect the validators of a single tier among its candidates.
+pub trait ElectionProvider<AccountId, Balance> {
+	/// Whether the nominations of each candidate must be collected before the election
+	const REQUIRES_NOMINATIONS: bool;
+
+	/// Elect at most `to_elect` validators among the given `candidates`. The candidates are
+	/// given in descending order of voting power and any candidate with a voting power lower
+	/// than `min_stake` must not be elected. The returned validators are sorted by account.
+	fn elect(
+		candidates: Vec<ElectionCandidate<AccountId, Balance>>,
+		to_elect: usize,
+		min_stake: Balance,
+	) -> Vec<AccountId>;
+
+	/// The weight of computing the top candidates with this provider, given the number of
+	/// candidates and the number of top nominations of each candidate
+	fn weight<W: WeightInfo>(candidates: u32, nominations: u32) -> Weight;
+}
+
+/// Elects the top `to_elect` candidates ordered by voting power.
+pub struct TopN;
+
+impl<AccountId: Ord, Balance: PartialOrd> ElectionProvider<AccountId, Balance> for TopN {
+	const REQUIRES_NOMINATIONS: bool = false;
+
+	fn elect(
+		candidates: Vec<ElectionCandidate<AccountId, Balance>>,
+		to_elect: usize,
+		min_stake: Balance,
+	) -> Vec<AccountId> {
+		let mut validators = candidates
+			.into_iter()
+			.filter(|x| x.voting_power >= min_stake)
+			.take(to_elect)
+			.map(|x| x.who)
+			.collect::<Vec<AccountId>>();
+		validators.sort();
+		validators
+	}
+
+	fn weight<W: WeightInfo>(candidates: u32, nominations: u32) -> Weight {
+		W::compute_top_candidates_top_n(candidates, nominations)
+	}
+}
+
+/// Elects the candidates with the sequential Phragmén method. Each self-bond is a vote for its
+/// own candidate and the top nominations of each nominator are aggregated into a single vote
+/// for every candidate it nominates, which balances the backing across the elected set.
+/// Falls back to `TopN` if the election fails.
+pub struct SequentialPhragmen;
+
+impl<AccountId: IdentifierT, Balance: AtLeast32BitUnsigned + Copy>
+	ElectionProvider<AccountId, Balance> for SequentialPhragmen
+{
+	const REQUIRES_NOMINATIONS: bool = true;
+
+	fn elect(
+		candidates: Vec<ElectionCandidate<AccountId, Balance>>,
+		to_elect: usize,
+		min_stake: Balance,
+	) -> Vec<AccountId> {
+		let candidates = candidates
+			.into_iter()
+			.filter(|x| x.voting_power >= min_stake)
+			.collect::<Vec<ElectionCandidate<AccountId, Balance>>>();
+		if candidates.len() <= to_elect {
+			return TopN::elect(candidates, to_elect, min_stake);
+		}
+
+		let mut stakes: BTreeMap<AccountId, (Balance, Vec<AccountId>)> = BTreeMap::new();
+		for candidate in &candidates {
+			let mut vote = |voter: &AccountId, amount: Balance| {
+				let (stake, targets) =
+					stakes.entry(voter.clone()).or_insert((Zero::zero(), vec![]));
+				*stake = stake.saturating_add(amount);
+				if !targets.contains(&candidate.who) {
+					targets.push(candidate.who.clone());
+				}
+			};
+			vote(&candidate.who, candidate.bond);
+			candidate.nominations.iter().for_each(|n| vote(&n.owner, n.amount));
+		}
+
+		// downscale the stakes so that the total fits into a `VoteWeight`
+		let total = stakes
+			.values()
+			.fold(Balance::zero(), |acc, (stake, _)| acc.saturating_add(*stake));
+		let factor =
+			(total / Balance::unique_saturated_from(VoteWeight::MAX)).saturating_add(One::one());
+		let voters = stakes
+			.into_iter()
+			.map(|(who, (stake, targets))| (who, (stake / factor).unique_saturated_into(), targets))
+			.collect::<Vec<(AccountId, VoteWeight, Vec<AccountId>)>>();
+		let targets = candidates.iter().map(|x| x.who.clone()).collect::<Vec<AccountId>>();
+
+		match seq_phragmen::<AccountId, Perbill>(to_elect, targets, voters, None) {
+			Ok(result) => {
+				let mut validators =
+					result.winners.into_iter().map(|(who, _)| who).collect::<Vec<AccountId>>();
+				validators.sort();
+				validators
+			},
+			Err(error) => {
+				log::error!("💥 sequential phragmen election failed: {:?}", error);
+				TopN::elect(candidates, to_elect, min_stake)
+			},
+		}
+	}
+
+	fn weight<W: WeightInfo>(candidates: u32, nominations: u32) -> Weight {
+		W::compute_top_candidates_sequential_phragmen(candidates, nominations)
+	}
+}
+
+/// Elects the top `to_elect` candidates ordered by their voting power counted up to `Cap`, so
+/// that stake beyond the cap does not improve a candidate's rank. Ties are broken by self-bond.
+pub struct StakeCapped<Cap>(PhantomData<Cap>);
+
+impl<AccountId: Ord, Balance: Ord + Copy, Cap: Get<Balance>> ElectionProvider<AccountId, Balance>
+	for StakeCapped<Cap>
+{
+	const REQUIRES_NOMINATIONS: bool = false;
+
+	fn elect(
+		candidates: Vec<ElectionCandidate<AccountId, Balance>>,
+		to_elect: usize,
+		min_stake: Balance,
+	) -> Vec<AccountId> {
+		let cap = Cap::get();
+		let mut candidates = candidates
+			.into_iter()
+			.filter(|x| x.voting_power >= min_stake)
+			.collect::<Vec<ElectionCandidate<AccountId, Balance>>>();
+		candidates.sort_by(|x, y| {
+			y.voting_power
+				.min(cap)
+				.cmp(&x.voting_power.min(cap))
+				.then_with(|| y.bond.cmp(&x.bond))
+		});
+		TopN::elect(candidates, to_elect, min_stake)
+	}
+
+	fn weight<W: WeightInfo>(candidates: u32, nominations: u32) -> Weight {
+		W::compute_top_candidates_stake_capped(candidates, nominations)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::traits::ConstU128;
+
+	fn candidate(
+		who: u64,
+		bond: u128,
+		nominations: Vec<(u64, u128)>,
+	) -> ElectionCandidate<u64, u128> {
+		let nominations = nominations
+			.into_iter()
+			.map(|(owner, amount)| Bond { owner, amount })
+			.collect::<Vec<Bond<u64, u128>>>();
+		let voting_power = bond + nominations.iter().map(|n| n.amount).sum::<u128>();
+		ElectionCandidate { who, bond, voting_power, nominations }
+	}
+
+	fn sorted(
+		mut candidates: Vec<ElectionCandidate<u64, u128>>,
+	) -> Vec<ElectionCandidate<u64, u128>> {
+		candidates.sort_by(|x, y| y.voting_power.cmp(&x.voting_power));
+		candidates
+	}
+
+	#[test]
+	fn top_n_elects_highest_voting_power() {
+		let candidates = sorted(vec![
+			candidate(1, 100, vec![(10, 50)]),
+			candidate(2, 300, vec![]),
+			candidate(3, 10, vec![]),
+			candidate(4, 120, vec![]),
+		]);
+		assert_eq!(TopN::elect(candidates.clone(), 2, 0), vec![1, 2]);
+		assert_eq!(TopN::elect(candidates, 4, 100), vec![1, 2, 4]);
+	}
+
+	#[test]
+	fn stake_capped_ignores_stake_above_cap() {
+		let candidates = sorted(vec![
+			candidate(1, 100, vec![(10, 1_000)]),
+			candidate(2, 300, vec![]),
+			candidate(3, 200, vec![(11, 100)]),
+		]);
+		assert_eq!(StakeCapped::<ConstU128<300>>::elect(candidates.clone(), 1, 0), vec![2]);
+		assert_eq!(StakeCapped::<ConstU128<300>>::elect(candidates, 2, 0), vec![2, 3]);
+	}
+
+	#[test]
+	fn sequential_phragmen_balances_shared_nominators() {
+		// nominator 10 backs both 1 and 2, which would otherwise be elected together by top-n
+		let candidates = sorted(vec![
+			candidate(1, 10, vec![(10, 291)]),
+			candidate(2, 10, vec![(10, 291)]),
+			candidate(3, 300, vec![]),
+		]);
+		assert_eq!(TopN::elect(candidates.clone(), 2, 0), vec![1, 2]);
+		let validators = SequentialPhragmen::elect(candidates, 2, 0);
+		assert_eq!(validators.len(), 2);
+		assert!(validators.contains(&3));
+	}
+
+	#[test]
+	fn sequential_phragmen_respects_min_stake() {
+		let candidates = sorted(vec![
+			candidate(1, 100, vec![]),
+			candidate(2, 50, vec![]),
+			candidate(3, 10, vec![]),
+		]);
+		assert_eq!(SequentialPhragmen::elect(candidates, 2, 60), vec![1]);
+	}
+}
@@ -7,19 +7,22 @@ use serde::{Deserialize, Serialize};
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Get};
 
 use sp_runtime::{PerThing, Perbill, RuntimeDebug};
 
 use substrate_fixed::{transcendental::pow as floatpow, types::I64F64};
 
-const SECONDS_PER_YEAR: u32 = 31557600;
-const SECONDS_PER_BLOCK: u32 = 3;
-pub const BLOCKS_PER_YEAR: u32 = SECONDS_PER_YEAR / SECONDS_PER_BLOCK;
+const MILLISECS_PER_YEAR: u64 = 31_557_600_000;
+
+/// Returns the number of blocks produced per year for the given slot duration (in milliseconds)
+pub fn blocks_per_year(slot_duration: u64) -> u32 {
+	(MILLISECS_PER_YEAR / slot_duration.max(1)).min(u32::MAX as u64) as u32
+}
 
 pub fn rounds_per_year<T: Config>() -> u32 {
 	let blocks_per_round = <Round<T>>::get().round_length;
-	BLOCKS_PER_YEAR / blocks_per_round
+	blocks_per_year(T::SlotDuration::get()) / blocks_per_round
 }
 
 #[derive(
@@ -122,6 +125,10 @@ pub struct InflationInfo<Balance> {
 	pub annual: Range<Perbill>,
 	/// Round inflation range
 	pub round: Range<Perbill>,
+	/// The fraction of the round issuance paid to the treasury
+	pub treasury: Perbill,
+	/// The fraction of the round issuance that is burned (never minted)
+	pub burn: Perbill,
 }
 
 impl<Balance> InflationInfo<Balance> {
@@ -129,7 +136,24 @@ impl<Balance> InflationInfo<Balance> {
 		annual: Range<Perbill>,
 		expect: Range<Balance>,
 	) -> InflationInfo<Balance> {
-		InflationInfo { expect, annual, round: annual_to_round::<T>(annual) }
+		InflationInfo {
+			expect,
+			annual,
+			round: annual_to_round::<T>(annual),
+			treasury: Perbill::zero(),
+			burn: Perbill::zero(),
+		}
+	}
+
+	/// Returns `true` if the treasury and burn shares do not exceed the round issuance
+	pub fn is_valid_shares(treasury: Perbill, burn: Perbill) -> bool {
+		treasury.deconstruct().saturating_add(burn.deconstruct()) <= Perbill::ACCURACY
+	}
+
+	/// Set the treasury and burn shares of the round issuance
+	pub fn set_shares(&mut self, treasury: Perbill, burn: Perbill) {
+		self.treasury = treasury;
+		self.burn = burn;
 	}
 
 	/// Set round inflation range according to input annual inflation range
@@ -138,8 +162,8 @@ impl<Balance> InflationInfo<Balance> {
 	}
 
 	/// Reset round inflation rate based on changes to round length
-	pub fn reset_round(&mut self, new_length: u32) {
-		let periods = BLOCKS_PER_YEAR / new_length;
+	pub fn reset_round<T: Config>(&mut self, new_length: u32) {
+		let periods = blocks_per_year(T::SlotDuration::get()) / new_length;
 		self.round = perbill_annual_to_perbill_round(self.annual, periods);
 	}
 
@@ -222,4 +246,11 @@ mod tests {
 			mock_round_issuance_range(10_000_000, mock_annual_to_round(schedule, 8766))
 		);
 	}
+
+	#[test]
+	fn blocks_per_year_follows_slot_duration() {
+		assert_eq!(blocks_per_year(3_000), 10_519_200);
+		assert_eq!(blocks_per_year(6_000), 5_259_600);
+		assert_eq!(blocks_per_year(0), u32::MAX);
+	}
 }
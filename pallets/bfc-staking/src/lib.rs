@@ -33,9 +33,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(unused_crate_dependencies)]
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod election;
 pub mod inflation;
 pub mod migrations;
+#[cfg(test)]
+mod mock;
 mod pallet;
+pub mod slashing;
+#[cfg(test)]
+mod tests;
 pub mod weights;
 
 pub use frame_system::pallet_prelude::BlockNumberFor;
@@ -45,6 +53,7 @@ use weights::WeightInfo;
 
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode};
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 
 use bp_staking::{RoundIndex, TierType};
 use frame_support::{
@@ -65,6 +74,10 @@ use sp_std::{
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
 /// The type that indicates the point of a reward
 pub type RewardPoint = u32;
 
@@ -112,6 +125,8 @@ impl Default for Releases {
 	RuntimeDebug,
 	TypeInfo,
 	MaxEncodedLen,
+	Serialize,
+	Deserialize,
 )]
 /// The candidates or the nominators bonded amount to the network
 pub struct Bond<AccountId, Balance> {
@@ -146,6 +161,8 @@ impl<A: Decode, B: Default> Default for Bond<A, B> {
 	RuntimeDebug,
 	TypeInfo,
 	MaxEncodedLen,
+	Serialize,
+	Deserialize,
 )]
 /// The activity status of the validator
 pub enum ValidatorStatus {
@@ -153,10 +170,13 @@ pub enum ValidatorStatus {
 	Active,
 	/// Temporarily inactive and excused for inactivity
 	Idle,
-	/// Kicked out until candidates rejoin
+	/// Kicked out until candidates rejoin. Only remains for the candidates that have been kicked
+	/// out before jailing was introduced.
 	KickedOut,
 	/// Bonded until the inner round
 	Leaving(RoundIndex),
+	/// Jailed for an offence. Cannot be selected until the given round and must `unjail` after.
+	Jailed { until_round: RoundIndex },
 }
 
 impl Default for ValidatorStatus {
@@ -185,21 +205,70 @@ pub struct ValidatorSnapshot<AccountId, Balance> {
 	pub nominations: Vec<Bond<AccountId, Balance>>,
 	/// The voting power of the active validator
 	pub total: Balance,
+	/// The commission rate of the active validator
+	pub commission: Perbill,
 }
 
 impl<A, B: Default> Default for ValidatorSnapshot<A, B> {
 	fn default() -> ValidatorSnapshot<A, B> {
-		ValidatorSnapshot { bond: B::default(), nominations: Vec::new(), total: B::default() }
+		ValidatorSnapshot {
+			bond: B::default(),
+			nominations: Vec::new(),
+			total: B::default(),
+			commission: Perbill::zero(),
+		}
+	}
+}
+
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	RuntimeDebug,
+	TypeInfo,
+)]
+/// The full identification of an active validator recorded by the historical session. It keeps
+/// the layout of the snapshot without the commission rate, so that the identifications and the
+/// offence reports recorded before the commission was added still decode.
+pub struct ValidatorIdentification<AccountId, Balance> {
+	/// The self-bond of the active validator
+	pub bond: Balance,
+	/// The top nominations of the active validator
+	pub nominations: Vec<Bond<AccountId, Balance>>,
+	/// The voting power of the active validator
+	pub total: Balance,
+}
+
+impl<A, B: Default> Default for ValidatorIdentification<A, B> {
+	fn default() -> ValidatorIdentification<A, B> {
+		ValidatorIdentification { bond: B::default(), nominations: Vec::new(), total: B::default() }
+	}
+}
+
+impl<A, B> From<ValidatorSnapshot<A, B>> for ValidatorIdentification<A, B> {
+	fn from(snapshot: ValidatorSnapshot<A, B>) -> ValidatorIdentification<A, B> {
+		ValidatorIdentification {
+			bond: snapshot.bond,
+			nominations: snapshot.nominations,
+			total: snapshot.total,
+		}
 	}
 }
 
 pub struct ValidatorSnapshotOf<T>(PhantomData<T>);
-impl<T: Config> Convert<T::AccountId, Option<ValidatorSnapshot<T::AccountId, BalanceOf<T>>>>
+impl<T: Config> Convert<T::AccountId, Option<ValidatorIdentification<T::AccountId, BalanceOf<T>>>>
 	for ValidatorSnapshotOf<T>
 {
-	fn convert(validator: T::AccountId) -> Option<ValidatorSnapshot<T::AccountId, BalanceOf<T>>> {
+	fn convert(
+		validator: T::AccountId,
+	) -> Option<ValidatorIdentification<T::AccountId, BalanceOf<T>>> {
 		let round = <Round<T>>::get();
-		Some(<AtStake<T>>::get(round.current_round_index, &validator))
+		Some(Pallet::<T>::validator_snapshot(round.current_round_index, &validator).into())
 	}
 }
 
@@ -360,7 +429,6 @@ impl<
 	PartialEq,
 	Ord,
 	PartialOrd,
-	Copy,
 	Clone,
 	Encode,
 	Decode,
@@ -368,15 +436,22 @@ impl<
 	RuntimeDebug,
 	TypeInfo,
 	MaxEncodedLen,
+	Serialize,
+	Deserialize,
 )]
-pub enum RewardDestination {
+pub enum RewardDestination<AccountId> {
 	/// Pay into the bonded account, increasing the amount at stake accordingly.
 	Staked,
 	/// Pay into the bonded account, not increasing the amount at stake.
 	Account,
+	/// Pay into the given payee account, not increasing the amount at stake.
+	Payee(AccountId),
+	/// Pay the `staked` portion into the bonded account, increasing the amount at stake
+	/// accordingly, and the remainder into the given payee account.
+	Split { staked: Perbill, payee: AccountId },
 }
 
-impl Default for RewardDestination {
+impl<AccountId> Default for RewardDestination<AccountId> {
 	fn default() -> Self {
 		RewardDestination::Staked
 	}
@@ -427,6 +502,31 @@ pub struct DelayedPayout<Balance> {
 	pub validator_commission: Perbill,
 }
 
+#[derive(
+	Clone,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+/// A round reward paid to a validator or a nominator
+pub struct RewardEntry<AccountId, Balance> {
+	/// The round in which the reward was earned
+	pub round: RoundIndex,
+	/// The validator (controller account) that earned the reward
+	pub validator: AccountId,
+	/// The total amount of the reward
+	pub amount: Balance,
+	/// The portion of the reward that was auto-compounded
+	pub compounded: Balance,
+}
+
 #[derive(
 	Eq,
 	PartialEq,
@@ -439,6 +539,8 @@ pub struct DelayedPayout<Balance> {
 	RuntimeDebug,
 	TypeInfo,
 	MaxEncodedLen,
+	Serialize,
+	Deserialize,
 )]
 /// Request scheduled to change the candidate self-bond
 pub struct CandidateBondLessRequest<Balance> {
@@ -621,7 +723,7 @@ pub struct CandidateMetadata<AccountId, Balance, BlockNumber> {
 	/// The block productivity status for this candidate in the current round
 	pub productivity_status: ProductivityStatus,
 	/// The destination for round rewards
-	pub reward_dst: RewardDestination,
+	pub reward_dst: RewardDestination<AccountId>,
 	/// The amount of awarded tokens to this candidate
 	pub awarded_tokens: Balance,
 	/// The tier type of this candidate
@@ -692,6 +794,10 @@ impl<
 		matches!(self.status, ValidatorStatus::Leaving(_))
 	}
 
+	pub fn is_jailed(&self) -> bool {
+		matches!(self.status, ValidatorStatus::Jailed { .. })
+	}
+
 	pub fn schedule_leave<T: Config>(&mut self) -> Result<(RoundIndex, RoundIndex), DispatchError> {
 		ensure!(!self.is_leaving(), Error::<T>::CandidateAlreadyLeaving);
 		ensure!(self.request.is_none(), Error::<T>::PendingCandidateRequestAlreadyExists);
@@ -722,11 +828,23 @@ impl<
 		self.status = ValidatorStatus::Active;
 	}
 
-	pub fn kick_out(&mut self) {
-		self.status = ValidatorStatus::KickedOut;
+	pub fn jail(&mut self, until_round: RoundIndex) {
+		self.status = ValidatorStatus::Jailed { until_round };
 		self.is_selected = false;
 	}
 
+	pub fn can_unjail<T: Config>(&self) -> DispatchResult {
+		if let ValidatorStatus::Jailed { until_round } = self.status {
+			ensure!(
+				<Round<T>>::get().current_round_index >= until_round,
+				Error::<T>::CandidateCannotUnjailYet
+			);
+			Ok(())
+		} else {
+			Err(Error::<T>::CandidateNotJailed.into())
+		}
+	}
+
 	pub fn slash_bond(&mut self, bond: Balance) {
 		self.bond = self.bond.saturating_sub(bond);
 	}
@@ -740,10 +858,10 @@ impl<
 	}
 
 	pub fn reset_commission<T: Config>(&mut self, who: &T::AccountId) {
-		let round = <Round<T>>::get();
-		let mut commission_sets = <DelayedCommissionSets<T>>::get(round.current_round_index);
+		let round = Pallet::<T>::delayed_request_round();
+		let mut commission_sets = <DelayedCommissionSets<T>>::get(round);
 		commission_sets.retain(|c| c.who != *who);
-		<DelayedCommissionSets<T>>::insert(round.current_round_index, commission_sets);
+		<DelayedCommissionSets<T>>::insert(round, commission_sets);
 
 		if self.tier == TierType::Full {
 			self.commission = <DefaultFullValidatorCommission<T>>::get();
@@ -752,7 +870,7 @@ impl<
 		}
 	}
 
-	pub fn set_reward_dst(&mut self, reward_dst: RewardDestination) {
+	pub fn set_reward_dst(&mut self, reward_dst: RewardDestination<AccountId>) {
 		self.reward_dst = reward_dst;
 	}
 
@@ -803,6 +921,7 @@ impl<
 	where
 		BalanceOf<T>: From<Balance>,
 	{
+		Pallet::<T>::freeze_validator_snapshot(&controller);
 		T::Currency::reserve(&stash, more.into())?;
 		let new_total = <Total<T>>::get().saturating_add(more.into());
 		self.bond = self.bond.saturating_add(more.into());
@@ -856,6 +975,7 @@ impl<
 	where
 		BalanceOf<T>: From<Balance>,
 	{
+		Pallet::<T>::freeze_validator_snapshot(&controller);
 		let request = self.request.ok_or(Error::<T>::PendingCandidateRequestsDNE)?;
 		ensure!(
 			request.when_executable <= <Round<T>>::get().current_round_index,
@@ -989,6 +1109,7 @@ impl<
 	where
 		BalanceOf<T>: Into<Balance> + From<Balance>,
 	{
+		Pallet::<T>::freeze_validator_snapshot(candidate);
 		let mut less_total_staked = None;
 		let mut top_nominations =
 			<TopNominations<T>>::get(candidate).ok_or(<Error<T>>::TopNominationDNE)?;
@@ -1127,6 +1248,7 @@ impl<
 	where
 		BalanceOf<T>: Into<Balance> + From<Balance>,
 	{
+		Pallet::<T>::freeze_validator_snapshot(candidate);
 		let old_voting_power = self.voting_power;
 		// remove top nomination
 		let mut top_nominations =
@@ -1246,6 +1368,7 @@ impl<
 	where
 		BalanceOf<T>: Into<Balance> + From<Balance>,
 	{
+		Pallet::<T>::freeze_validator_snapshot(candidate);
 		let mut top_nominations =
 			<TopNominations<T>>::get(candidate).ok_or(<Error<T>>::TopNominationDNE)?;
 		let mut in_top = false;
@@ -1280,6 +1403,7 @@ impl<
 	where
 		BalanceOf<T>: Into<Balance> + From<Balance>,
 	{
+		Pallet::<T>::freeze_validator_snapshot(candidate);
 		let mut bottom_nominations =
 			<BottomNominations<T>>::get(candidate).ok_or(Error::<T>::CandidateDNE)?;
 		let mut nomination_option: Option<Bond<T::AccountId, BalanceOf<T>>> = None;
@@ -1389,6 +1513,7 @@ impl<
 	where
 		BalanceOf<T>: Into<Balance> + From<Balance>,
 	{
+		Pallet::<T>::freeze_validator_snapshot(candidate);
 		// The nomination after the `decrease-nomination` will be strictly less than the
 		// highest bottom nomination
 		let bond_after_less_than_highest_bottom =
@@ -1508,7 +1633,9 @@ pub enum NominatorAdded<B> {
 	AddedToBottom,
 }
 
-#[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[derive(
+	Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Serialize, Deserialize,
+)]
 pub enum NominatorStatus {
 	/// Active with no scheduled exit
 	Active,
@@ -1532,7 +1659,7 @@ pub struct Nominator<AccountId, Balance> {
 	/// Status for this nominator
 	pub status: NominatorStatus,
 	/// The destination for round rewards
-	pub reward_dst: RewardDestination,
+	pub reward_dst: RewardDestination<AccountId>,
 	/// The total amount of awarded tokens to this nominator
 	pub awarded_tokens: Balance,
 	/// The amount of awarded tokens to this nominator per candidate
@@ -1657,7 +1784,7 @@ impl<
 		self.status = NominatorStatus::Leaving(when);
 	}
 
-	pub fn set_reward_dst(&mut self, reward_dst: RewardDestination) {
+	pub fn set_reward_dst(&mut self, reward_dst: RewardDestination<AccountId>) {
 		self.reward_dst = reward_dst;
 	}
 
@@ -1705,6 +1832,72 @@ impl<
 		}
 	}
 
+	/// Move `amount` of the nomination of `from` to the nomination of `to`. The nomination of
+	/// `from` is removed if the entire amount is moved and a new nomination is added if `to` is not
+	/// nominated yet. Returns the previous nomination amount of `to` if it existed
+	pub fn redelegate<T: Config>(
+		&mut self,
+		from: &AccountId,
+		to: AccountId,
+		amount: Balance,
+	) -> Result<Option<Balance>, DispatchError> {
+		let bond = *self.nominations.get(from).ok_or(Error::<T>::NominationDNE)?;
+		ensure!(amount <= bond, Error::<T>::RedelegationExceedsNomination);
+		if amount == bond {
+			self.rm_nomination(from);
+		} else if let Some(nomination) = self.nominations.get_mut(from) {
+			*nomination = nomination.saturating_sub(amount);
+			self.total = self.total.saturating_sub(amount);
+		}
+		match self.nominations.get_mut(&to) {
+			Some(nomination) => {
+				let before = *nomination;
+				*nomination = nomination.saturating_add(amount);
+				self.total = self.total.saturating_add(amount);
+				Ok(Some(before))
+			},
+			None => {
+				self.add_nomination::<T>(to, amount)?;
+				Ok(None)
+			},
+		}
+	}
+
+	/// Slash the nomination of the given validator by the given amount. Pending requests of the
+	/// nomination will be adjusted to the remaining nomination. Returns the remaining nomination
+	/// amount or None if nomination not found
+	pub fn slash_nomination(&mut self, validator: &AccountId, amount: Balance) -> Option<Balance> {
+		let nomination = self.nominations.get_mut(validator)?;
+		let amount = amount.min(*nomination);
+		*nomination = nomination.saturating_sub(amount);
+		let remaining = *nomination;
+		self.total = self.total.saturating_sub(amount);
+
+		let mut is_cancelled = false;
+		if let Some(request) = self.requests.requests.get_mut(validator) {
+			match request.action {
+				NominationChange::Revoke => {
+					// revoke the remaining nomination
+					let less = request.amount.saturating_sub(remaining);
+					request.amount = remaining;
+					self.requests.less_total = self.requests.less_total.saturating_sub(less);
+				},
+				NominationChange::Decrease => {
+					// the request can no longer be executed
+					if request.amount >= remaining {
+						self.requests.less_total =
+							self.requests.less_total.saturating_sub(request.amount);
+						is_cancelled = true;
+					}
+				},
+			}
+		}
+		if is_cancelled {
+			self.requests.requests.remove(validator);
+		}
+		Some(remaining)
+	}
+
 	pub fn increase_nomination<T: Config>(
 		&mut self,
 		candidate: AccountId,
@@ -1930,7 +2123,18 @@ impl<
 	}
 }
 
-#[derive(Clone, Eq, PartialEq, Encode, Decode, DecodeWithMemTracking, RuntimeDebug, TypeInfo)]
+#[derive(
+	Clone,
+	Eq,
+	PartialEq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	RuntimeDebug,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
 /// Changes requested by the nominator
 /// - limit of 1 ongoing change per nomination
 pub enum NominationChange {
@@ -1940,7 +2144,18 @@ pub enum NominationChange {
 	Decrease,
 }
 
-#[derive(Clone, Eq, PartialEq, Encode, Decode, DecodeWithMemTracking, RuntimeDebug, TypeInfo)]
+#[derive(
+	Clone,
+	Eq,
+	PartialEq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	RuntimeDebug,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
 /// The nomination unbonding request of a specific nominator
 pub struct NominationRequest<AccountId, Balance> {
 	/// The validator who owns this nomination
@@ -2150,3 +2365,150 @@ impl<
 		RoundInfo::new(1u32, 1u32, 1u32, 0u32.into(), 0u32.into(), 0u32.into(), 20u32, 200u32)
 	}
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The phases of a round transition, run one per block. The phases up to `Elected` run over the
+/// last blocks of the ending round and the remaining phases over the first blocks of the new round.
+pub enum RoundTransitionPhase {
+	/// Take the remaining `AtStake` snapshots of the ending round
+	ValidatorSnapshots,
+	/// Apply the delayed commission set requests of the ending round
+	CommissionSets,
+	/// Apply the delayed controller set requests of the ending round
+	ControllerSets,
+	/// Elect the validators of the next round
+	Election,
+	/// Apply the deferred slashes scheduled for the next round
+	UnappliedSlashes,
+	/// The validators of the next round are elected and wait for the round boundary
+	Elected,
+	/// Reset the `per round` states of the previous validators that were not reselected
+	CandidateStates,
+	/// Prepare the delayed staking payouts
+	StakingPayouts,
+}
+
+impl RoundTransitionPhase {
+	/// Whether the phase runs before the round boundary
+	pub fn is_before_boundary(&self) -> bool {
+		!matches!(self, Self::CandidateStates | Self::StakingPayouts)
+	}
+}
+
+/// The maximum length of each field of a candidate identity
+pub const MAX_IDENTITY_FIELD_LENGTH: u32 = 128;
+
+/// A bounded field of a candidate identity
+pub type IdentityField = BoundedVec<u8, ConstU32<MAX_IDENTITY_FIELD_LENGTH>>;
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The operator information registered by a validator candidate
+pub struct CandidateIdentity<Balance> {
+	/// The display name of the validator
+	pub name: IdentityField,
+	/// The website of the validator operator
+	pub website: IdentityField,
+	/// The contact (e.g. email) of the validator operator
+	pub contact: IdentityField,
+	/// The hash of the validator logo image
+	pub logo_hash: [u8; 32],
+	/// The public endpoint of the relayer operated by a full validator
+	pub relayer_endpoint: Option<IdentityField>,
+	/// The amount reserved from the stash account for the registration
+	pub deposit: Balance,
+}
+
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The identity of a validator candidate exposed through the `BfcStakingApi` runtime API
+pub struct CandidateIdentityInfo<Balance> {
+	/// The display name of the validator
+	pub name: Vec<u8>,
+	/// The website of the validator operator
+	pub website: Vec<u8>,
+	/// The contact (e.g. email) of the validator operator
+	pub contact: Vec<u8>,
+	/// The hash of the validator logo image
+	pub logo_hash: [u8; 32],
+	/// The public endpoint of the relayer operated by a full validator
+	pub relayer_endpoint: Option<Vec<u8>>,
+	/// The amount reserved from the stash account for the registration
+	pub deposit: Balance,
+}
+
+impl<Balance> From<CandidateIdentity<Balance>> for CandidateIdentityInfo<Balance> {
+	fn from(identity: CandidateIdentity<Balance>) -> Self {
+		CandidateIdentityInfo {
+			name: identity.name.into_inner(),
+			website: identity.website.into_inner(),
+			contact: identity.contact.into_inner(),
+			logo_hash: identity.logo_hash,
+			relayer_endpoint: identity.relayer_endpoint.map(|e| e.into_inner()),
+			deposit: identity.deposit,
+		}
+	}
+}
+
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The state of a validator candidate exposed through the `BfcStakingApi` runtime API
+pub struct CandidateState<AccountId, Balance> {
+	/// This candidate's controller account
+	pub controller: AccountId,
+	/// This candidate's stash account
+	pub stash: AccountId,
+	/// This candidate's current self-bond
+	pub bond: Balance,
+	/// This candidate's initial self-bond
+	pub initial_bond: Balance,
+	/// Self-bond + top nominations
+	pub voting_power: Balance,
+	/// Total number of nominations (top + bottom) to this candidate
+	pub nomination_count: u32,
+	/// The top nominations of this candidate
+	pub top_nominations: Vec<Bond<AccountId, Balance>>,
+	/// The bottom nominations of this candidate
+	pub bottom_nominations: Vec<Bond<AccountId, Balance>>,
+	/// The pending request to decrease the candidate self-bond
+	pub request: Option<CandidateBondLessRequest<Balance>>,
+	/// Current status of the validator
+	pub status: ValidatorStatus,
+	/// Selection state of the candidate in the current round
+	pub is_selected: bool,
+	/// The validator commission ratio
+	pub commission: Perbill,
+	/// The total blocks this candidate produced in the current round
+	pub blocks_produced: u32,
+	/// The block productivity for this candidate in the current round
+	pub productivity: Perbill,
+	/// The destination for round rewards
+	pub reward_dst: RewardDestination<AccountId>,
+	/// The amount of awarded tokens to this candidate
+	pub awarded_tokens: Balance,
+	/// The tier type of this candidate
+	pub tier: TierType,
+	/// The registered identity of this candidate
+	pub identity: Option<CandidateIdentityInfo<Balance>>,
+}
+
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The state of a nominator exposed through the `BfcStakingApi` runtime API
+pub struct NominatorInfo<AccountId, Balance> {
+	/// Nominator account
+	pub id: AccountId,
+	/// Current state of all nominations
+	pub nominations: Vec<Bond<AccountId, Balance>>,
+	/// Total balance locked for this nominator
+	pub total: Balance,
+	/// Pending requests to unbond nominations
+	pub requests: Vec<NominationRequest<AccountId, Balance>>,
+	/// Sum of pending revocation amounts + bond less amounts
+	pub less_total: Balance,
+	/// Status for this nominator
+	pub status: NominatorStatus,
+	/// The destination for round rewards
+	pub reward_dst: RewardDestination<AccountId>,
+	/// The total amount of awarded tokens to this nominator
+	pub awarded_tokens: Balance,
+}
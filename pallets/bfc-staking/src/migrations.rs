@@ -1,5 +1,193 @@
 use super::*;
 
+pub mod v9 {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	use super::*;
+
+	#[derive(Decode)]
+	pub struct OldValidatorSnapshot<AccountId, Balance> {
+		pub bond: Balance,
+		pub nominations: Vec<Bond<AccountId, Balance>>,
+		pub total: Balance,
+	}
+
+	/// Adds the commission rate to the `AtStake` snapshots. The snapshots of the rounds that are
+	/// not paid out yet are migrated with the current commission of their validators.
+	pub struct MigrateToV9<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV9<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+
+			let current = Pallet::<T>::in_code_storage_version();
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if current >= 9 && onchain == 8 {
+				let mut translated = 0u64;
+				<AtStake<T>>::translate::<OldValidatorSnapshot<T::AccountId, BalanceOf<T>>, _>(
+					|_, validator, old| {
+						translated += 1;
+						let commission = <CandidateInfo<T>>::get(&validator)
+							.map(|state| state.commission)
+							.unwrap_or_default();
+						Some(ValidatorSnapshot {
+							bond: old.bond,
+							nominations: old.nominations,
+							total: old.total,
+							commission,
+						})
+					},
+				);
+				StorageVersion::new(9).put::<Pallet<T>>();
+				weight = weight.saturating_add(
+					T::DbWeight::get().reads_writes(translated * 2 + 1, translated + 1),
+				);
+
+				log!(info, "bfc-staking storage migration v9 completed successfully ✅");
+			} else {
+				log!(warn, "Skipping bfc-staking storage migration v9 💤");
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+			weight
+		}
+	}
+}
+
+pub mod v8 {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	use super::*;
+
+	/// Initializes the maximum commission rate increase per round with its configured default.
+	pub struct MigrateToV8<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+
+			let current = Pallet::<T>::in_code_storage_version();
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if current >= 8 && onchain == 7 {
+				<MaxCommissionIncreasePerRound<T>>::put(
+					T::DefaultMaxCommissionIncreasePerRound::get(),
+				);
+				StorageVersion::new(8).put::<Pallet<T>>();
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+
+				log!(info, "bfc-staking storage migration v8 completed successfully ✅");
+			} else {
+				log!(warn, "Skipping bfc-staking storage migration v8 💤");
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+			weight
+		}
+	}
+}
+
+pub mod v7 {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	use super::*;
+
+	#[derive(Decode)]
+	pub struct OldInflationInfo<Balance> {
+		pub expect: Range<Balance>,
+		pub annual: Range<Perbill>,
+		pub round: Range<Perbill>,
+	}
+
+	/// Adds the treasury and burn shares of the round issuance to the inflation config. The
+	/// existing config is migrated with zero shares, so the whole issuance stays with stakers.
+	pub struct MigrateToV7<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+
+			let current = Pallet::<T>::in_code_storage_version();
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if current >= 7 && onchain == 6 {
+				let _ =
+					<InflationConfig<T>>::translate::<OldInflationInfo<BalanceOf<T>>, _>(|old| {
+						old.map(|old| InflationInfo {
+							expect: old.expect,
+							annual: old.annual,
+							round: old.round,
+							treasury: Perbill::zero(),
+							burn: Perbill::zero(),
+						})
+					});
+				StorageVersion::new(7).put::<Pallet<T>>();
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+
+				log!(info, "bfc-staking storage migration v7 completed successfully ✅");
+			} else {
+				log!(warn, "Skipping bfc-staking storage migration v7 💤");
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+			weight
+		}
+	}
+}
+
+pub mod v6 {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	use super::*;
+
+	#[cfg(feature = "try-runtime")]
+	use sp_runtime::TryRuntimeError;
+
+	/// Adds the `Payee` and `Split` reward destinations. The new variants are appended to
+	/// `RewardDestination`, so the existing `Staked` and `Account` entries decode unchanged under
+	/// the new layout. Entries are therefore migrated lazily, on their next write, and the upgrade
+	/// itself only bumps the storage version.
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+
+			let current = Pallet::<T>::in_code_storage_version();
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if current >= 6 && onchain == 5 {
+				StorageVersion::new(6).put::<Pallet<T>>();
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+
+				log!(info, "bfc-staking storage migration v6 completed successfully ✅");
+			} else {
+				log!(warn, "Skipping bfc-staking storage migration v6 💤");
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 6, "v6 not applied");
+
+			for candidate in <CandidateInfo<T>>::iter_keys() {
+				ensure!(
+					<CandidateInfo<T>>::try_get(&candidate).is_ok(),
+					"CandidateInfo entry does not decode under v6"
+				);
+			}
+			for nominator in <NominatorState<T>>::iter_keys() {
+				ensure!(
+					<NominatorState<T>>::try_get(&nominator).is_ok(),
+					"NominatorState entry does not decode under v6"
+				);
+			}
+
+			Ok(())
+		}
+	}
+}
+
 pub mod v5 {
 	use frame_support::traits::OnRuntimeUpgrade;
 
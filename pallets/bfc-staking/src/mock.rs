@@ -0,0 +1,454 @@
+use crate as pallet_bfc_staking;
+use crate::{InflationInfo, Range, Round, RoundTransition};
+use bp_core::{AccountId, Balance, BlockNumber};
+use bp_staking::{
+	traits::{OffenceHandler, RelayManager},
+	Offence, OffenceKind, RoundIndex, TierType, MAX_AUTHORITIES,
+};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU64, Everything, Hooks},
+	BoundedBTreeSet,
+};
+use frame_system::EnsureRoot;
+use sp_core::{ConstU32, H160, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, DispatchError, Perbill,
+};
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		BfcStaking: pallet_bfc_staking,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: BlockNumber = 256;
+	pub const SS58Prefix: u8 = 42;
+	pub const ExistentialDeposit: u128 = 0;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeTask = RuntimeTask;
+	type Nonce = u64;
+	type Block = Block;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type SingleBlockMigrations = ();
+	type MultiBlockMigrator = ();
+	type PreInherents = ();
+	type PostInherents = ();
+	type PostTransactions = ();
+	type ExtensionsWeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type DoneSlashHandler = ();
+}
+
+/// The number of blocks per round
+pub const ROUND_LENGTH: u32 = 10;
+/// The minimum self-bond of a full candidate
+pub const MIN_FULL_CANDIDATE_STK: Balance = 500;
+/// The minimum self-bond of a basic candidate
+pub const MIN_BASIC_CANDIDATE_STK: Balance = 100;
+/// The minimum amount of a nomination
+pub const MIN_NOMINATION: Balance = 10;
+
+parameter_types! {
+	pub const MinBlocksPerRound: u32 = 1;
+	pub const MaxBlocksPerRound: u32 = 100;
+	pub const DefaultBlocksPerSession: u32 = ROUND_LENGTH / 2;
+	pub const DefaultBlocksPerRound: u32 = ROUND_LENGTH;
+	pub const StorageCacheLifetimeInRounds: u32 = 8;
+	pub const LeaveCandidatesDelay: RoundIndex = 2;
+	pub const CandidateBondLessDelay: RoundIndex = 2;
+	pub const JailDuration: RoundIndex = 1;
+	pub const MaxJailDuration: RoundIndex = 8;
+	pub const JailCountDecay: RoundIndex = 8;
+	pub const LeaveNominatorsDelay: RoundIndex = 2;
+	pub const RevokeNominationDelay: RoundIndex = 2;
+	pub const RedelegationDelay: RoundIndex = 1;
+	pub const NominationBondLessDelay: RoundIndex = 2;
+	pub const RewardPaymentDelay: RoundIndex = 1;
+	pub const MaxRewardHistory: u32 = 4;
+	pub const RewardHistoryDepth: RoundIndex = 4;
+	pub const MaxRewardHistoryPrunesPerBlock: u32 = 2;
+	pub const MaxValidatorSnapshotsPerBlock: u32 = 2;
+	pub const RoundTransitionBlocks: u32 = 6;
+	pub const DefaultMaxSelectedFullCandidates: u32 = 5;
+	pub const DefaultMaxSelectedBasicCandidates: u32 = 5;
+	pub const MaxTopNominationsPerCandidate: u32 = 2;
+	pub const MaxBottomNominationsPerCandidate: u32 = 2;
+	pub const MaxNominationsPerNominator: u32 = 3;
+	pub const DefaultFullValidatorCommission: Perbill = Perbill::from_percent(10);
+	pub const DefaultBasicValidatorCommission: Perbill = Perbill::from_percent(10);
+	pub const MaxFullValidatorCommission: Perbill = Perbill::from_percent(50);
+	pub const MaxBasicValidatorCommission: Perbill = Perbill::from_percent(50);
+	pub const DefaultMaxCommissionIncreasePerRound: Perbill = Perbill::from_percent(5);
+	pub const MinFullValidatorStk: Balance = MIN_FULL_CANDIDATE_STK;
+	pub const MinBasicValidatorStk: Balance = MIN_BASIC_CANDIDATE_STK;
+	pub const MinFullCandidateStk: Balance = MIN_FULL_CANDIDATE_STK;
+	pub const MinBasicCandidateStk: Balance = MIN_BASIC_CANDIDATE_STK;
+	pub const MinNomination: Balance = MIN_NOMINATION;
+	pub const MinNominatorStk: Balance = MIN_NOMINATION;
+	pub const IdentityDeposit: Balance = 10;
+}
+
+impl pallet_bfc_staking::Config for Test {
+	type Currency = Balances;
+	type MonetaryGovernanceOrigin = EnsureRoot<AccountId>;
+	type RelayManager = MockRelayManager;
+	type OffenceHandler = MockOffenceHandler;
+	type Treasury = ();
+	type ElectionProvider = pallet_bfc_staking::election::TopN;
+	type DefaultBlocksPerSession = DefaultBlocksPerSession;
+	type DefaultBlocksPerRound = DefaultBlocksPerRound;
+	type SlotDuration = ConstU64<3_000>;
+	type MinBlocksPerRound = MinBlocksPerRound;
+	type MaxBlocksPerRound = MaxBlocksPerRound;
+	type StorageCacheLifetimeInRounds = StorageCacheLifetimeInRounds;
+	type LeaveCandidatesDelay = LeaveCandidatesDelay;
+	type CandidateBondLessDelay = CandidateBondLessDelay;
+	type JailDuration = JailDuration;
+	type MaxJailDuration = MaxJailDuration;
+	type JailCountDecay = JailCountDecay;
+	type LeaveNominatorsDelay = LeaveNominatorsDelay;
+	type RevokeNominationDelay = RevokeNominationDelay;
+	type RedelegationDelay = RedelegationDelay;
+	type NominationBondLessDelay = NominationBondLessDelay;
+	type RewardPaymentDelay = RewardPaymentDelay;
+	type MaxRewardHistory = MaxRewardHistory;
+	type RewardHistoryDepth = RewardHistoryDepth;
+	type MaxRewardHistoryPrunesPerBlock = MaxRewardHistoryPrunesPerBlock;
+	type MaxValidatorSnapshotsPerBlock = MaxValidatorSnapshotsPerBlock;
+	type RoundTransitionBlocks = RoundTransitionBlocks;
+	type DefaultMaxSelectedFullCandidates = DefaultMaxSelectedFullCandidates;
+	type DefaultMaxSelectedBasicCandidates = DefaultMaxSelectedBasicCandidates;
+	type MaxTopNominationsPerCandidate = MaxTopNominationsPerCandidate;
+	type MaxBottomNominationsPerCandidate = MaxBottomNominationsPerCandidate;
+	type MaxNominationsPerNominator = MaxNominationsPerNominator;
+	type DefaultFullValidatorCommission = DefaultFullValidatorCommission;
+	type DefaultBasicValidatorCommission = DefaultBasicValidatorCommission;
+	type MaxFullValidatorCommission = MaxFullValidatorCommission;
+	type MaxBasicValidatorCommission = MaxBasicValidatorCommission;
+	type DefaultMaxCommissionIncreasePerRound = DefaultMaxCommissionIncreasePerRound;
+	type MinFullValidatorStk = MinFullValidatorStk;
+	type MinBasicValidatorStk = MinBasicValidatorStk;
+	type MinFullCandidateStk = MinFullCandidateStk;
+	type MinBasicCandidateStk = MinBasicCandidateStk;
+	type MinNomination = MinNomination;
+	type MinNominatorStk = MinNominatorStk;
+	type IdentityDeposit = IdentityDeposit;
+	type WeightInfo = ();
+}
+
+/// A deferred slash of an offender and its nominators that is applied at the given round
+pub type UnappliedSlash = (RoundIndex, AccountId, Balance, Vec<(AccountId, Balance)>);
+
+thread_local! {
+	/// The deferred slashes that `MockOffenceHandler` applies at the start of their round
+	pub static UNAPPLIED_SLASHES: RefCell<Vec<UnappliedSlash>> = RefCell::new(Vec::new());
+	/// The relayer scores of the controllers that `MockRelayManager` reports for every round
+	pub static RELAYER_SCORES: RefCell<Vec<(AccountId, Perbill)>> = RefCell::new(Vec::new());
+}
+
+/// A relay manager without relayers. The relayer scores are set by the tests.
+pub struct MockRelayManager;
+
+impl RelayManager<AccountId> for MockRelayManager {
+	fn join_relayers(_: AccountId, _: AccountId) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn refresh_round(_: RoundIndex) {}
+
+	fn refresh_relayer_pool() {}
+
+	fn refresh_selected_relayers(_: RoundIndex, _: Vec<AccountId>) {}
+
+	fn refresh_cached_selected_relayers(
+		_: RoundIndex,
+		_: BoundedBTreeSet<AccountId, ConstU32<MAX_AUTHORITIES>>,
+	) {
+	}
+
+	fn refresh_majority(_: RoundIndex) {}
+
+	fn replace_bonded_controller(_: AccountId, _: AccountId) {}
+
+	fn leave_relayers(_: &AccountId) {}
+
+	fn kickout_relayer(_: &AccountId) {}
+
+	fn collect_heartbeats() {}
+
+	fn handle_delayed_relayer_sets(_: RoundIndex) {}
+
+	fn relayer_score(controller: &AccountId, _: RoundIndex) -> Option<Perbill> {
+		RELAYER_SCORES.with(|scores| {
+			scores
+				.borrow()
+				.iter()
+				.find(|(who, _)| who == controller)
+				.map(|(_, score)| *score)
+		})
+	}
+}
+
+/// A simplified `pallet_bfc_offences` that only applies the deferred slashes set by the tests
+pub struct MockOffenceHandler;
+
+impl MockOffenceHandler {
+	/// Defers the given slash to the given round
+	pub fn defer_slash(
+		round: RoundIndex,
+		offender: AccountId,
+		offender_slash: Balance,
+		nominators_slash: Vec<(AccountId, Balance)>,
+	) {
+		UNAPPLIED_SLASHES
+			.with(|s| s.borrow_mut().push((round, offender, offender_slash, nominators_slash)));
+	}
+}
+
+impl OffenceHandler<AccountId, Balance> for MockOffenceHandler {
+	fn try_handle_offence(
+		_: &AccountId,
+		_: &AccountId,
+		_: TierType,
+		_: Offence<Balance>,
+		_: Vec<(AccountId, Balance)>,
+	) -> (bool, Balance, Vec<(AccountId, Balance)>) {
+		(false, 0, vec![])
+	}
+
+	fn handle_offence(
+		_: &AccountId,
+		_: &AccountId,
+		_: TierType,
+		_: Offence<Balance>,
+		_: Vec<(AccountId, Balance)>,
+	) -> (bool, Balance, Vec<(AccountId, Balance)>) {
+		(false, 0, vec![])
+	}
+
+	fn try_slash(_: &AccountId, _: &AccountId, _: Perbill, _: Balance) -> Balance {
+		0
+	}
+
+	fn try_slash_nominators(_: Perbill, _: Vec<(AccountId, Balance)>) -> Vec<(AccountId, Balance)> {
+		vec![]
+	}
+
+	fn apply_unapplied_slashes<B, F>(
+		now: RoundIndex,
+		current_bond: B,
+		current_nomination: F,
+	) -> Vec<(AccountId, Balance, Vec<(AccountId, Balance)>)>
+	where
+		B: Fn(&AccountId) -> Option<Balance>,
+		F: Fn(&AccountId, &AccountId) -> Balance,
+	{
+		UNAPPLIED_SLASHES.with(|s| {
+			let mut slashes = s.borrow_mut();
+			let (due, pending) = slashes.drain(..).partition(|(round, ..)| *round <= now);
+			*slashes = pending;
+			due.into_iter()
+				.filter_map(|(_, offender, offender_slash, nominators_slash)| {
+					let offender_slash = offender_slash.min(current_bond(&offender)?);
+					let nominators_slash = nominators_slash
+						.into_iter()
+						.map(|(nominator, amount)| {
+							(nominator, amount.min(current_nomination(&offender, &nominator)))
+						})
+						.collect();
+					Some((offender, offender_slash, nominators_slash))
+				})
+				.collect()
+		})
+	}
+
+	fn has_unapplied_slash(who: &AccountId) -> bool {
+		UNAPPLIED_SLASHES.with(|s| s.borrow().iter().any(|(_, offender, ..)| offender == who))
+	}
+
+	fn refresh_offences(_: RoundIndex) {}
+
+	fn is_offence_count_exceeds(_: OffenceKind, _: u32, _: TierType) -> bool {
+		false
+	}
+}
+
+pub fn account(seed: u64) -> AccountId {
+	AccountId::from(H160::from_low_u64_be(seed))
+}
+
+/// The stash account of the `index`-th candidate
+pub fn stash(index: u64) -> AccountId {
+	account(100 + index)
+}
+
+/// The controller account of the `index`-th candidate
+pub fn controller(index: u64) -> AccountId {
+	account(200 + index)
+}
+
+/// The `index`-th nominator
+pub fn nominator(index: u64) -> AccountId {
+	account(300 + index)
+}
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, Balance)>,
+	candidates: Vec<(AccountId, AccountId, AccountId, Balance)>,
+	nominations: Vec<(AccountId, AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: (1..=5)
+				.map(|i| (stash(i), 10_000))
+				.chain((1..=5).map(|i| (nominator(i), 10_000)))
+				.collect(),
+			candidates: vec![],
+			nominations: vec![],
+		}
+	}
+}
+
+impl ExtBuilder {
+	/// Adds full candidates with the given indexes and self-bonds
+	pub fn with_candidates(mut self, candidates: Vec<(u64, Balance)>) -> Self {
+		self.candidates = candidates
+			.into_iter()
+			.map(|(i, bond)| (stash(i), controller(i), controller(i), bond))
+			.collect();
+		self
+	}
+
+	/// Adds nominations of the given nominator indexes to the given candidate indexes
+	pub fn with_nominations(mut self, nominations: Vec<(u64, u64, Balance)>) -> Self {
+		self.nominations = nominations
+			.into_iter()
+			.map(|(n, c, amount)| (nominator(n), controller(c), amount))
+			.collect();
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> { balances: self.balances, ..Default::default() }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		pallet_bfc_staking::GenesisConfig::<Test> {
+			candidates: self.candidates,
+			nominations: self.nominations,
+			inflation_config: InflationInfo {
+				expect: Range { min: 1_000, ideal: 2_000, max: 5_000 },
+				annual: Range {
+					min: Perbill::from_percent(50),
+					ideal: Perbill::from_percent(50),
+					max: Perbill::from_percent(50),
+				},
+				round: Range {
+					min: Perbill::from_percent(1),
+					ideal: Perbill::from_percent(1),
+					max: Perbill::from_percent(1),
+				},
+				treasury: Perbill::zero(),
+				burn: Perbill::zero(),
+			},
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			UNAPPLIED_SLASHES.with(|s| s.borrow_mut().clear());
+		});
+		ext
+	}
+}
+
+/// Rolls to the given block. A new round starts whenever the current round has ended, as the
+/// session manager does.
+pub fn roll_to(n: u64) {
+	let mut now = System::block_number();
+	while now < n {
+		now += 1;
+		System::set_block_number(now);
+		if Round::<Test>::get().should_update(now) {
+			let (full_validators, basic_validators) = BfcStaking::take_elected_candidates();
+			BfcStaking::new_round(now, full_validators, basic_validators);
+		}
+		BfcStaking::on_initialize(now);
+	}
+}
+
+/// Rolls to the first block of the given round
+pub fn roll_to_round_begin(round: RoundIndex) {
+	while Round::<Test>::get().current_round_index < round {
+		roll_to(System::block_number() + 1);
+	}
+}
+
+/// Rolls into the given round until its round transition prepared the payouts, without paying
+/// any of them
+pub fn roll_to_prepared_payouts(round: RoundIndex) {
+	roll_to_round_begin(round);
+	while RoundTransition::<Test>::get().is_some() {
+		roll_to(System::block_number() + 1);
+	}
+}
+
+/// Returns the events of this pallet
+pub fn staking_events() -> Vec<pallet_bfc_staking::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|e| match e.event {
+			RuntimeEvent::BfcStaking(inner) => Some(inner),
+			_ => None,
+		})
+		.collect()
+}
@@ -1,31 +1,35 @@
 use super::pallet::*;
 
 use crate::{
-	inflation::Range, weights::WeightInfo, BalanceOf, Bond, DelayedCommissionSet,
-	DelayedControllerSet, DelayedPayout, ProductivityStatus, RewardDestination, RewardPoint,
-	RoundIndex, TierType, TotalSnapshot, ValidatorSnapshot, ValidatorSnapshotOf,
+	election::{ElectionCandidate, ElectionProvider},
+	inflation::{rounds_per_year, Range},
+	slashing::nominator_exposures,
+	weights::WeightInfo,
+	BalanceOf, Bond, CandidateMetadata, CandidateState, DelayedCommissionSet, DelayedControllerSet,
+	DelayedPayout, NominatorInfo, ProductivityStatus, RewardDestination, RewardEntry, RewardPoint,
+	RoundIndex, RoundTransitionPhase, TierType, TotalSnapshot, ValidatorIdentification,
+	ValidatorSnapshot, ValidatorSnapshotOf,
 };
 
-use frame_system::pallet_prelude::BlockNumberFor;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::ShouldEndSession;
 
 use bp_staking::{
-	traits::{OffenceHandler, RelayManager},
-	Offence, MAX_AUTHORITIES,
+	traits::{NominationManager, OffenceHandler, OnOffenceKindHandler, RelayManager},
+	Offence, OffenceKind, MAX_AUTHORITIES,
 };
 use sp_runtime::{
 	traits::{Convert, Saturating, Zero},
-	Perbill, Permill,
-};
-use sp_staking::{
-	offence::{OffenceDetails, OnOffenceHandler},
-	SessionIndex,
+	FixedPointNumber, FixedU128, Perbill, Permill,
 };
+use sp_staking::{offence::OffenceDetails, SessionIndex};
 use sp_std::{collections::btree_set::BTreeSet, vec, vec::Vec};
 
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, EstimateNextSessionRotation, Get, Imbalance, ReservableCurrency},
+	traits::{
+		Currency, EstimateNextSessionRotation, Get, Imbalance, OnUnbalanced, ReservableCurrency,
+	},
 	weights::Weight,
 	BoundedBTreeSet,
 };
@@ -60,17 +64,32 @@ impl<T: Config> Pallet<T> {
 		is_selected_candidate
 	}
 
+	/// The maximum number of nominations (top and bottom) that a candidate can hold
+	pub fn max_candidate_nominations() -> u32 {
+		T::MaxTopNominationsPerCandidate::get()
+			.saturating_add(T::MaxBottomNominationsPerCandidate::get())
+	}
+
+	/// Returns the round whose delayed controller and commission set requests are applied at the
+	/// next round transition. Requests made once the round transition has started are applied at
+	/// the end of the next round.
+	pub fn delayed_request_round() -> RoundIndex {
+		let round = Round::<T>::get().current_round_index;
+		match RoundTransition::<T>::get() {
+			Some(phase) if phase.is_before_boundary() => round + 1,
+			_ => round,
+		}
+	}
+
 	/// Verifies if the given account has already requested for controller account update
 	pub fn is_controller_set_requested(controller: &T::AccountId) -> bool {
-		let round = Round::<T>::get();
-		let controller_sets = DelayedControllerSets::<T>::get(round.current_round_index);
+		let controller_sets = DelayedControllerSets::<T>::get(Self::delayed_request_round());
 		controller_sets.into_iter().any(|c| c.old == *controller)
 	}
 
 	/// Verifies if the given account has already requested for commission rate update
 	pub fn is_commission_set_requested(who: &T::AccountId) -> bool {
-		let round = Round::<T>::get();
-		let commission_sets = DelayedCommissionSets::<T>::get(round.current_round_index);
+		let commission_sets = DelayedCommissionSets::<T>::get(Self::delayed_request_round());
 		if commission_sets.is_empty() {
 			return false;
 		}
@@ -83,9 +102,8 @@ impl<T: Config> Pallet<T> {
 		old: T::AccountId,
 		new: T::AccountId,
 	) -> DispatchResult {
-		let round = Round::<T>::get();
 		<DelayedControllerSets<T>>::try_mutate(
-			round.current_round_index,
+			Self::delayed_request_round(),
 			|controller_sets| -> DispatchResult {
 				ensure!(
 					!controller_sets.into_iter().any(|c| c.old == old || c.new == new),
@@ -105,9 +123,8 @@ impl<T: Config> Pallet<T> {
 		old: Perbill,
 		new: Perbill,
 	) -> DispatchResult {
-		let round = Round::<T>::get();
 		<DelayedCommissionSets<T>>::try_mutate(
-			round.current_round_index,
+			Self::delayed_request_round(),
 			|commission_sets| -> DispatchResult {
 				Ok(commission_sets
 					.try_push(DelayedCommissionSet::new(who.clone(), old, new))
@@ -116,19 +133,17 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
-	/// Remove the given `who` from the `DelayedControllerSets` of the current round.
+	/// Remove the given `who` from the pending `DelayedControllerSets`.
 	pub fn remove_controller_set(who: &T::AccountId) -> DispatchResult {
-		let round = Round::<T>::get();
-		<DelayedControllerSets<T>>::mutate(round.current_round_index, |controller_set| {
+		<DelayedControllerSets<T>>::mutate(Self::delayed_request_round(), |controller_set| {
 			controller_set.retain(|c| c.old != *who);
 		});
 		Ok(())
 	}
 
-	/// Remove the given `who` from the `DelayedCommissionSets` of the current round.
+	/// Remove the given `who` from the pending `DelayedCommissionSets`.
 	pub fn remove_commission_set(who: &T::AccountId) -> DispatchResult {
-		let round = Round::<T>::get();
-		<DelayedCommissionSets<T>>::mutate(round.current_round_index, |commission_sets| {
+		<DelayedCommissionSets<T>>::mutate(Self::delayed_request_round(), |commission_sets| {
 			commission_sets.retain(|c| c.who != *who);
 		});
 		Ok(())
@@ -275,62 +290,167 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Generates a delayed payout for staking rewards
-	pub fn prepare_staking_payouts(now: RoundIndex) {
+	/// Generates a delayed payout for staking rewards. Returns the consumed weight.
+	pub fn prepare_staking_payouts(now: RoundIndex) -> Weight {
 		// payout is now - delay rounds ago => now - delay > 0 else return early
 		let delay = T::RewardPaymentDelay::get();
 		if now <= delay {
-			return;
+			return Weight::from_parts(0u64, 0u64);
 		}
 		let round_to_payout = now - delay;
 		let total_points = Points::<T>::get(round_to_payout);
 		if total_points.is_zero() {
-			return;
+			return T::DbWeight::get().reads(1);
 		}
 		// total staked amount for the given round
 		let total_staked = <Staked<T>>::take(round_to_payout);
 
 		// total issuance for the given round
 		let round_issuance = Self::compute_issuance(total_staked);
+		let (total_staking_reward, treasury_reward, burned) =
+			Self::split_round_issuance(round_issuance);
+
+		// mint the treasury share of the round issuance
+		if !treasury_reward.is_zero() {
+			T::Treasury::on_unbalanced(T::Currency::issue(treasury_reward));
+			Self::deposit_event(Event::TreasuryRewarded {
+				round: round_to_payout,
+				amount: treasury_reward,
+			});
+		}
+		// the burn share of the round issuance is never minted
+		if !burned.is_zero() {
+			Self::deposit_event(Event::IssuanceBurned { round: round_to_payout, amount: burned });
+		}
 
 		let payout = DelayedPayout {
 			round_issuance,
-			total_staking_reward: round_issuance,
+			total_staking_reward,
 			validator_commission: DefaultBasicValidatorCommission::<T>::get(),
 		};
 		<DelayedPayouts<T>>::insert(round_to_payout, payout);
+
+		// the weighting is fixed for the round once its payout has been prepared
+		if !IsRelayerScoreRewardActive::<T>::get() {
+			return T::DbWeight::get().reads_writes(7, 5);
+		}
+		let mut validator_count = 0u64;
+		let weighted_points = <AwardedPts<T>>::iter_prefix(round_to_payout)
+			.map(|(validator, pts)| {
+				validator_count += 1;
+				CandidateInfo::<T>::get(&validator).map_or(pts, |state| {
+					Self::relayer_weighted_points(round_to_payout, &validator, state.tier, pts)
+				})
+			})
+			.fold(RewardPoint::zero(), |acc, pts| acc.saturating_add(pts));
+		<WeightedPoints<T>>::insert(round_to_payout, weighted_points);
+		T::DbWeight::get().reads_writes(7 + validator_count * 4, 6)
+	}
+
+	/// Returns the given points of a validator weighted by the performance score of its relayer
+	/// for the given round. Only the points of full validators are weighted.
+	fn relayer_weighted_points(
+		round: RoundIndex,
+		validator: &T::AccountId,
+		tier: TierType,
+		pts: RewardPoint,
+	) -> RewardPoint {
+		if tier != TierType::Full {
+			return pts;
+		}
+		T::RelayManager::relayer_score(validator, round).map_or(pts, |score| score * pts)
+	}
+
+	/// Returns the reward points of the given validator and the total reward points of the given
+	/// round. If the payout of the round has been prepared with the relayer score reward
+	/// weighting active, both are weighted.
+	fn reward_points(
+		round: RoundIndex,
+		validator: &T::AccountId,
+		tier: TierType,
+		pts: RewardPoint,
+	) -> (RewardPoint, RewardPoint) {
+		match <WeightedPoints<T>>::get(round) {
+			Some(total_points) => {
+				(Self::relayer_weighted_points(round, validator, tier, pts), total_points)
+			},
+			None => (pts, Points::<T>::get(round)),
+		}
 	}
 
-	/// Handle validators auto-compoundable round rewards payout. If the reward destination is set
-	/// to `Staked`, it will be auto-compounded
+	/// Recompute the round inflation range with the current slot duration and round length, so
+	/// that a changed block time is reflected after a runtime upgrade. Returns the consumed weight.
+	pub fn refresh_round_inflation() -> Weight {
+		let round_length = <Round<T>>::get().round_length;
+		if round_length.is_zero() {
+			return T::DbWeight::get().reads(1);
+		}
+		let mut config = <InflationConfig<T>>::get();
+		let old = config.round;
+		config.reset_round::<T>(round_length);
+		if config.round == old {
+			return T::DbWeight::get().reads(2);
+		}
+		<InflationConfig<T>>::put(&config);
+		Self::deposit_event(Event::InflationSet {
+			annual_min: config.annual.min,
+			annual_ideal: config.annual.ideal,
+			annual_max: config.annual.max,
+			round_min: config.round.min,
+			round_ideal: config.round.ideal,
+			round_max: config.round.max,
+			treasury: config.treasury,
+			burn: config.burn,
+		});
+		T::DbWeight::get().reads_writes(2, 1)
+	}
+
+	/// Splits the given round issuance into the (staking, treasury, burn) shares according to the
+	/// current inflation config
+	pub fn split_round_issuance(
+		round_issuance: BalanceOf<T>,
+	) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
+		let config = <InflationConfig<T>>::get();
+		let treasury = config.treasury * round_issuance;
+		let burn = config.burn * round_issuance;
+		(round_issuance.saturating_sub(treasury).saturating_sub(burn), treasury, burn)
+	}
+
+	/// Handle validators auto-compoundable round rewards payout. The rewards are paid according to
+	/// the reward destination and the portion paid into the stash account is auto-compounded if
+	/// it is set to `Staked` or `Split`
 	pub fn handle_validator_reward_payout(
+		round: RoundIndex,
 		controller: T::AccountId,
 		stash: T::AccountId,
 		reward: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
 		if let Some(mut validator_state) = CandidateInfo::<T>::get(&controller) {
-			// mint rewards to the validators stash account
-			Self::mint_reward(reward, stash);
+			// mint rewards to the validators reward destination
+			let compound = Self::distribute_reward(&validator_state.reward_dst, stash, reward);
 			// increment the awarded tokens of this validator
 			validator_state.increment_awarded_tokens(reward);
 
-			// auto-compound round rewards if `reward_dst` is set to `Staked`
-			if validator_state.reward_dst == RewardDestination::Staked {
+			// auto-compound the rewards paid into the stash account
+			if !compound.is_zero() {
 				validator_state.bond_more::<T>(
 					validator_state.stash.clone(),
 					controller.clone(),
-					reward,
+					compound,
 				)?;
 				Self::update_active(&controller, validator_state.voting_power)?;
 			}
 			<CandidateInfo<T>>::insert(&controller, validator_state);
+			Self::record_reward(&controller, round, controller.clone(), reward, compound);
 		}
 		Ok(().into())
 	}
 
-	/// Handle nominators auto-compoundable round rewards payout. If the reward destination is set
-	/// to `Staked`, it will be auto-compounded
+	/// Handle nominators auto-compoundable round rewards payout. The rewards are paid according to
+	/// the reward destination and the portion paid into the nominator account is auto-compounded
+	/// if it is set to `Staked` or `Split`
 	pub fn handle_nominator_reward_payout(
+		round: RoundIndex,
 		controller: T::AccountId,
 		nominator: T::AccountId,
 		reward: BalanceOf<T>,
@@ -342,33 +462,141 @@ impl<T: Config> Pallet<T> {
 				&& !nominator_state.is_revoking(&controller)
 				&& !nominator_state.is_decreasing(&controller)
 			{
-				// mint rewards to the nominator account
-				Self::mint_reward(reward, nominator.clone());
-
-				// auto-compound round rewards if `reward_dst` is set to `Staked`
-				match nominator_state.reward_dst {
-					RewardDestination::Staked => {
-						// increment the awarded tokens of this nominator
-						nominator_state.increment_awarded_tokens(&controller, reward);
-						// auto-compound nomination
-						if nominator_state
-							.increase_nomination::<T>(controller.clone(), reward)
-							.is_ok()
-						{
-							<NominatorState<T>>::insert(&nominator, nominator_state);
-						}
-					},
-					RewardDestination::Account => {
-						// increment the awarded tokens of this nominator
-						nominator_state.increment_awarded_tokens(&controller, reward);
-						<NominatorState<T>>::insert(&nominator, nominator_state);
-					},
+				// mint rewards to the nominators reward destination
+				let compound =
+					Self::distribute_reward(&nominator_state.reward_dst, nominator.clone(), reward);
+				// increment the awarded tokens of this nominator
+				nominator_state.increment_awarded_tokens(&controller, reward);
+
+				// auto-compound the rewards paid into the nominator account
+				if compound.is_zero()
+					|| nominator_state
+						.increase_nomination::<T>(controller.clone(), compound)
+						.is_ok()
+				{
+					<NominatorState<T>>::insert(&nominator, nominator_state);
+					Self::record_reward(&nominator, round, controller, reward, compound);
+				} else {
+					Self::record_reward(&nominator, round, controller, reward, Zero::zero());
 				}
 			}
 		}
 		Ok(().into())
 	}
 
+	/// Mints the given `reward` according to the `reward_dst` and returns the amount paid into the
+	/// `bonded` account that should be auto-compounded. Payee accounts are created if they do not
+	/// exist yet, and rewards that still cannot be paid into them are paid into the `bonded`
+	/// account instead.
+	fn distribute_reward(
+		reward_dst: &RewardDestination<T::AccountId>,
+		bonded: T::AccountId,
+		reward: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		let pay_to_payee = |amount: BalanceOf<T>, payee: &T::AccountId| {
+			if !amount.is_zero() && !Self::mint_reward_creating(amount, payee.clone()) {
+				Self::mint_reward(amount, bonded.clone());
+			}
+		};
+		match reward_dst {
+			RewardDestination::Staked => {
+				Self::mint_reward(reward, bonded);
+				reward
+			},
+			RewardDestination::Account => {
+				Self::mint_reward(reward, bonded);
+				Zero::zero()
+			},
+			RewardDestination::Payee(payee) => {
+				pay_to_payee(reward, payee);
+				Zero::zero()
+			},
+			RewardDestination::Split { staked, payee } => {
+				let compound = *staked * reward;
+				pay_to_payee(reward.saturating_sub(compound), payee);
+				if !compound.is_zero() {
+					Self::mint_reward(compound, bonded);
+				}
+				compound
+			},
+		}
+	}
+
+	/// Appends a paid round reward to the reward history of the given account. Entries older than
+	/// `RewardHistoryDepth` rounds are pruned and the oldest entry is dropped if the history is
+	/// full.
+	fn record_reward(
+		who: &T::AccountId,
+		round: RoundIndex,
+		validator: T::AccountId,
+		amount: BalanceOf<T>,
+		compounded: BalanceOf<T>,
+	) {
+		let now = Round::<T>::get().current_round_index;
+		<RewardHistory<T>>::mutate(who, |history| {
+			history.retain(|entry| Self::is_recent_reward(entry.round, now));
+			if history.is_full() {
+				history.remove(0);
+			}
+			let _ = history.try_push(RewardEntry { round, validator, amount, compounded });
+		});
+	}
+
+	/// Starts a new pruning pass over every reward history, unless the pass of a previous round is
+	/// still in progress. Entries only become stale at round boundaries, so one pass per round is
+	/// enough.
+	fn queue_reward_history_pruning() {
+		if !<RewardHistoryPruneCursor<T>>::exists() {
+			<RewardHistoryPruneCursor<T>>::put(BoundedVec::default());
+		}
+	}
+
+	/// Prunes the stale entries from the reward history of up to `limit` accounts, resuming after
+	/// the account visited last. The history of an account that no longer earns rewards is removed
+	/// once all of its entries are older than `RewardHistoryDepth` rounds. Nothing is done unless a
+	/// pruning pass has been started at the beginning of the round.
+	pub fn handle_reward_history_pruning(now: RoundIndex, limit: u32) -> Weight {
+		let histories: Vec<_> = match <RewardHistoryPruneCursor<T>>::get() {
+			None => return T::DbWeight::get().reads(1),
+			Some(cursor) if cursor.is_empty() => {
+				<RewardHistory<T>>::iter().take(limit as usize).collect()
+			},
+			Some(cursor) => <RewardHistory<T>>::iter_from(cursor.into_inner())
+				.take(limit as usize)
+				.collect(),
+		};
+		let visited = histories.len() as u64;
+
+		let mut writes = 1u64;
+		let mut last_key = None;
+		for (who, mut history) in histories {
+			let len = history.len();
+			history.retain(|entry| Self::is_recent_reward(entry.round, now));
+			if history.is_empty() {
+				<RewardHistory<T>>::remove(&who);
+				writes += 1;
+			} else if history.len() < len {
+				<RewardHistory<T>>::insert(&who, history);
+				writes += 1;
+			}
+			last_key = Some(<RewardHistory<T>>::hashed_key_for(&who));
+		}
+
+		// finish the pass once every account has been visited
+		match last_key {
+			Some(key) if visited == limit as u64 => {
+				<RewardHistoryPruneCursor<T>>::put(BoundedVec::truncate_from(key))
+			},
+			_ => <RewardHistoryPruneCursor<T>>::kill(),
+		}
+		T::DbWeight::get().reads_writes(visited + 1, writes)
+	}
+
+	/// Verifies if a reward earned in the given `round` is still kept in the reward history
+	fn is_recent_reward(round: RoundIndex, now: RoundIndex) -> bool {
+		round.saturating_add(T::RewardHistoryDepth::get()) > now
+	}
+
 	/// Wrapper around pay_one_validator_reward which handles the following logic:
 	/// * whether or not a payout needs to be made
 	/// * cleaning up when payouts are done
@@ -391,6 +619,7 @@ impl<T: Config> Pallet<T> {
 				// clean up storage items that we no longer need
 				<DelayedPayouts<T>>::remove(round_to_payout);
 				<Points<T>>::remove(round_to_payout);
+				<WeightedPoints<T>>::remove(round_to_payout);
 			}
 			// weight consumed by pay_one_validator_reward
 			result.1
@@ -400,12 +629,12 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Replace each nominators nominated candidate's account from `old` to `new`. This method will
-	/// also replace the pending requests.
+	/// also replace the pending requests. Returns the consumed weight.
 	fn replace_nominator_nominations(
 		nominators: &Vec<T::AccountId>,
 		old: &T::AccountId,
 		new: &T::AccountId,
-	) {
+	) -> Weight {
 		nominators.into_iter().for_each(|n| {
 			if let Some(mut nominator) = NominatorState::<T>::get(n) {
 				nominator.replace_nominations(old, new);
@@ -413,32 +642,87 @@ impl<T: Config> Pallet<T> {
 				<NominatorState<T>>::insert(n, nominator);
 			}
 		});
+		T::DbWeight::get().reads_writes(nominators.len() as u64, nominators.len() as u64)
 	}
 
-	/// Apply the delayed candidate rate set requests.
-	pub fn handle_delayed_commission_sets(now: RoundIndex) {
+	/// Returns the number of rounds required to move the commission from `old` to `new` under
+	/// the `MaxCommissionIncreasePerRound` limit. Decreases are applied at once.
+	pub fn commission_increase_steps(old: Perbill, new: Perbill) -> RoundIndex {
+		let max_step = <MaxCommissionIncreasePerRound<T>>::get().deconstruct();
+		let increase = new.deconstruct().saturating_sub(old.deconstruct());
+		if max_step == 0 || increase == 0 {
+			return 1;
+		}
+		increase.div_ceil(max_step)
+	}
+
+	/// Returns the commission that is applied in the next round while moving from `old` to
+	/// `target`.
+	fn next_commission_step(old: Perbill, target: Perbill) -> Perbill {
+		let max_step = <MaxCommissionIncreasePerRound<T>>::get();
+		if max_step.is_zero() || target <= old {
+			return target;
+		}
+		target.min(old.saturating_add(max_step))
+	}
+
+	/// Apply the delayed candidate rate set requests. Increases above the per round limit are
+	/// applied partially and the remainder is requeued for the next round. Returns the consumed
+	/// weight.
+	pub fn handle_delayed_commission_sets(now: RoundIndex) -> Weight {
 		let delayed_round = now - 1;
 		let commission_sets = <DelayedCommissionSets<T>>::take(delayed_round);
+		let count = commission_sets.len() as u64;
 		commission_sets.into_iter().for_each(|c| {
 			if let Some(mut candidate) = CandidateInfo::<T>::get(&c.who) {
-				candidate.set_commission(c.new);
+				let commission = Self::next_commission_step(candidate.commission, c.new);
+				candidate.set_commission(commission);
 				<CandidateInfo<T>>::insert(&c.who, candidate);
+
+				if commission != c.new {
+					<DelayedCommissionSets<T>>::mutate(now, |commission_sets| {
+						// a candidate has a single request per round, so this never exceeds
+						// the bound
+						let _ = commission_sets.try_push(DelayedCommissionSet::new(
+							c.who.clone(),
+							commission,
+							c.new,
+						));
+					});
+					Self::deposit_event(Event::ValidatorCommissionStepApplied {
+						candidate: c.who,
+						commission,
+						target: c.new,
+					});
+				}
 			}
 		});
+		T::DbWeight::get().reads_writes(1 + 2 * count, 1 + 2 * count)
 	}
 
 	/// Apply the delayed controller set requests. Replaces the entire bonded storage values from
-	/// the old to new.
-	pub fn handle_delayed_controller_sets(now: RoundIndex) {
+	/// the old to new. Returns the consumed weight.
+	pub fn handle_delayed_controller_sets(now: RoundIndex) -> Weight {
 		let delayed_round = now - 1;
 		let controller_sets = <DelayedControllerSets<T>>::take(delayed_round);
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
 		controller_sets.into_iter().for_each(|c| {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 0));
 			if let Some(candidate) = CandidateInfo::<T>::get(&c.old) {
 				// replace `CandidateInfo`
 				<CandidateInfo<T>>::remove(&c.old);
 				<CandidateInfo<T>>::insert(&c.new, candidate.clone());
 				// replace `BondedStash`
 				<BondedStash<T>>::insert(&c.stash, c.new.clone());
+				// replace `CandidateIdentities`
+				if let Some(identity) = <CandidateIdentities<T>>::take(&c.old) {
+					<CandidateIdentities<T>>::insert(&c.new, identity);
+				}
+				// replace `JailCount`
+				let jail_count = <JailCount<T>>::take(&c.old);
+				if jail_count > 0 {
+					<JailCount<T>>::insert(&c.new, jail_count);
+				}
 				// replace `CandidatePool`
 				Self::replace_from_candidate_pool(&c.old, &c.new);
 				// replace `SelectedCandidates`
@@ -450,20 +734,20 @@ impl<T: Config> Pallet<T> {
 				}
 				// replace `TopNominations`
 				if let Some(top_nominations) = <TopNominations<T>>::take(&c.old) {
-					Self::replace_nominator_nominations(
+					weight.saturating_accrue(Self::replace_nominator_nominations(
 						&top_nominations.nominators(),
 						&c.old,
 						&c.new,
-					);
+					));
 					<TopNominations<T>>::insert(&c.new, top_nominations);
 				}
 				// replace `BottomNominations`
 				if let Some(bottom_nominations) = <BottomNominations<T>>::take(&c.old) {
-					Self::replace_nominator_nominations(
+					weight.saturating_accrue(Self::replace_nominator_nominations(
 						&bottom_nominations.nominators(),
 						&c.old,
 						&c.new,
-					);
+					));
 					<BottomNominations<T>>::insert(&c.new, bottom_nominations);
 				}
 				// replace `AwardedPts`
@@ -474,21 +758,83 @@ impl<T: Config> Pallet<T> {
 				// replace `AtStake`
 				let at_stake = <AtStake<T>>::take(now, &c.old);
 				<AtStake<T>>::insert(now, &c.new, at_stake);
+				let previous_stake = <AtStake<T>>::take(delayed_round, &c.old);
+				<AtStake<T>>::insert(delayed_round, &c.new, previous_stake);
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(14, 20));
 			}
 		});
+		weight
 	}
 
-	/// Mints exactly `amount` native tokens to the `to` account.
-	fn mint_reward(amount: BalanceOf<T>, to: T::AccountId) {
+	/// Mints exactly `amount` native tokens to the `to` account. Returns `true` if minted.
+	fn mint_reward(amount: BalanceOf<T>, to: T::AccountId) -> bool {
 		if let Ok(amount_transferred) = T::Currency::deposit_into_existing(&to, amount) {
-			<AwardedTokens<T>>::mutate(|awarded_tokens| {
-				*awarded_tokens += amount_transferred.peek();
-			});
-			Self::deposit_event(Event::Rewarded {
-				account: to.clone(),
-				rewards: amount_transferred.peek(),
-			});
+			Self::note_minted_reward(to, amount_transferred.peek());
+			return true;
+		}
+		false
+	}
+
+	/// Mints exactly `amount` native tokens to the `to` account and creates the account if it
+	/// does not exist. Returns `true` if minted.
+	fn mint_reward_creating(amount: BalanceOf<T>, to: T::AccountId) -> bool {
+		let amount_transferred = T::Currency::deposit_creating(&to, amount).peek();
+		if amount_transferred.is_zero() {
+			return false;
+		}
+		Self::note_minted_reward(to, amount_transferred);
+		true
+	}
+
+	/// Records the minted reward of the `to` account
+	fn note_minted_reward(to: T::AccountId, amount: BalanceOf<T>) {
+		<AwardedTokens<T>>::mutate(|awarded_tokens| {
+			*awarded_tokens += amount;
+		});
+		Self::deposit_event(Event::Rewarded { account: to, rewards: amount });
+	}
+
+	/// Computes the reward distribution of a single validator for the given round.
+	///
+	/// Returns a tuple of (total reward, validator reward, nominator rewards). Solo validators
+	/// receive the total reward and nominators with zero rewards are omitted.
+	pub fn compute_validator_rewards(
+		payout_info: &DelayedPayout<BalanceOf<T>>,
+		pts: RewardPoint,
+		total_points: RewardPoint,
+		snapshot: &ValidatorSnapshot<T::AccountId, BalanceOf<T>>,
+	) -> (BalanceOf<T>, BalanceOf<T>, Vec<(T::AccountId, BalanceOf<T>)>) {
+		if total_points.is_zero() {
+			// no points have been earned in the round
+			return (Zero::zero(), Zero::zero(), vec![]);
 		}
+		let validator_issuance = snapshot.commission * payout_info.total_staking_reward;
+
+		// compute contribution percentage from given round total points
+		let validator_contribution_pct = Perbill::from_rational(pts, total_points);
+		// total reward amount for this validator and nominators
+		let total_reward_amount = validator_contribution_pct * payout_info.total_staking_reward;
+
+		if snapshot.nominations.is_empty() {
+			// solo validator with no nominators
+			return (total_reward_amount, total_reward_amount, vec![]);
+		}
+
+		let validator_stake_pct = Perbill::from_rational(snapshot.bond, snapshot.total);
+		let commission = validator_contribution_pct * validator_issuance;
+		let amount_due = total_reward_amount - commission;
+		let validator_reward = (validator_stake_pct * amount_due) + commission;
+
+		let nominator_rewards = snapshot
+			.nominations
+			.iter()
+			.map(|Bond { owner, amount }| {
+				(owner.clone(), Perbill::from_rational(*amount, snapshot.total) * amount_due)
+			})
+			.filter(|(_, reward)| !reward.is_zero())
+			.collect();
+
+		(total_reward_amount, validator_reward, nominator_rewards)
 	}
 
 	/// Payout a single validator from the given round.
@@ -507,57 +853,43 @@ impl<T: Config> Pallet<T> {
 		if let Some((validator, pts)) = <AwardedPts<T>>::iter_prefix(round_to_payout).drain().next()
 		{
 			if let Some(state) = CandidateInfo::<T>::get(&validator) {
-				let validator_issuance = state.commission * payout_info.round_issuance;
-
-				// compute contribution percentage from given round total points
-				let validator_contribution_pct = Perbill::from_rational(pts, total_points);
-				// total reward amount for this validator and nominators
-				let total_reward_amount =
-					validator_contribution_pct * payout_info.total_staking_reward;
-
 				// Take the snapshot of block author and nominations
 				let snapshot = <AtStake<T>>::take(round_to_payout, &validator);
 				let num_nominators = snapshot.nominations.len();
 
-				if snapshot.nominations.is_empty() {
-					// solo validator with no nominators
-					Self::handle_validator_reward_payout(
-						validator.clone(),
-						state.stash.clone(),
-						total_reward_amount,
-					)
-					.expect("Graceful validator reward payout");
-				} else {
-					// pay validator first; commission + due_portion
-					let validator_stake_pct = Perbill::from_rational(snapshot.bond, snapshot.total);
-					let commission = validator_contribution_pct * validator_issuance;
-					let amount_due = total_reward_amount - commission;
-					let validator_reward = (validator_stake_pct * amount_due) + commission;
-
-					Self::handle_validator_reward_payout(
+				let (pts, total_points) =
+					Self::reward_points(round_to_payout, &validator, state.tier, pts);
+				let (total_reward_amount, validator_reward, nominator_rewards) =
+					Self::compute_validator_rewards(&payout_info, pts, total_points, &snapshot);
+
+				// pay validator first; commission + due_portion
+				Self::handle_validator_reward_payout(
+					round_to_payout,
+					validator.clone(),
+					state.stash.clone(),
+					validator_reward,
+				)
+				.expect("Graceful validator reward payout");
+				// pay nominators due portion
+				for (owner, nominator_reward) in nominator_rewards {
+					Self::handle_nominator_reward_payout(
+						round_to_payout,
 						validator.clone(),
-						state.stash.clone(),
-						validator_reward,
+						owner,
+						nominator_reward,
 					)
-					.expect("Graceful validator reward payout");
-					// pay nominators due portion
-					for Bond { owner, amount } in snapshot.nominations {
-						let nominator_stake_pct = Perbill::from_rational(amount, snapshot.total);
-						let nominator_reward = nominator_stake_pct * amount_due;
-						if !nominator_reward.is_zero() {
-							Self::handle_nominator_reward_payout(
-								validator.clone(),
-								owner.clone(),
-								nominator_reward,
-							)
-							.expect("Graceful nominator reward payout");
-						}
-					}
+					.expect("Graceful nominator reward payout");
 				}
 
 				(
 					Some((validator, total_reward_amount)),
-					T::WeightInfo::pay_one_validator_reward(num_nominators as u32),
+					T::WeightInfo::pay_one_validator_reward(num_nominators as u32)
+						// records the payout in the reward history of each recipient
+						.saturating_add(
+							T::DbWeight::get()
+								.reads_writes(1, 1)
+								.saturating_mul(num_nominators as u64 + 1),
+						),
 				)
 			} else {
 				(None, Weight::from_parts(0u64, 0u64))
@@ -572,33 +904,57 @@ impl<T: Config> Pallet<T> {
 	/// Compute the top full and basic candidates in the CandidatePool and return
 	/// a vector of their AccountIds (in the order of selection)
 	pub fn compute_top_candidates() -> (Vec<T::AccountId>, Vec<T::AccountId>) {
+		Self::compute_top_candidates_with::<T::ElectionProvider>()
+	}
+
+	/// Compute the top full and basic candidates in the CandidatePool with the given election
+	/// provider `E`
+	pub fn compute_top_candidates_with<E: ElectionProvider<T::AccountId, BalanceOf<T>>>(
+	) -> (Vec<T::AccountId>, Vec<T::AccountId>) {
 		let candidates = Self::get_sorted_candidates();
 		let mut full_candidates = vec![];
 		let mut basic_candidates = vec![];
 
 		candidates.into_iter().for_each(|candidate| {
 			if let Some(state) = CandidateInfo::<T>::get(&candidate.owner) {
+				// jailed validators cannot be selected until they unjail
+				if state.is_jailed() {
+					return;
+				}
+				let nominations = if E::REQUIRES_NOMINATIONS {
+					TopNominations::<T>::get(&candidate.owner)
+						.map(|top| top.nominations)
+						.unwrap_or_default()
+				} else {
+					vec![]
+				};
+				let election_candidate = ElectionCandidate {
+					who: candidate.owner,
+					bond: state.bond,
+					voting_power: candidate.amount,
+					nominations,
+				};
 				match state.tier {
 					TierType::Full => {
 						if state.bond >= T::MinFullCandidateStk::get() {
-							full_candidates.push(candidate);
+							full_candidates.push(election_candidate);
 						}
 					},
 					_ => {
 						if state.bond >= T::MinBasicCandidateStk::get() {
-							basic_candidates.push(candidate);
+							basic_candidates.push(election_candidate);
 						}
 					},
 				}
 			}
 		});
 
-		let full_validators = Self::get_top_n_candidates(
+		let full_validators = E::elect(
 			full_candidates,
 			MaxFullSelected::<T>::get() as usize,
 			T::MinFullValidatorStk::get(),
 		);
-		let basic_validators = Self::get_top_n_candidates(
+		let basic_validators = E::elect(
 			basic_candidates,
 			MaxBasicSelected::<T>::get() as usize,
 			T::MinBasicValidatorStk::get(),
@@ -607,48 +963,25 @@ impl<T: Config> Pallet<T> {
 		(full_validators, basic_validators)
 	}
 
-	/// Compute the top basic/full candidates based on their voting power
-	fn get_top_n_candidates(
-		candidates: Vec<Bond<T::AccountId, BalanceOf<T>>>,
-		top_n: usize,
-		min_stake: BalanceOf<T>,
-	) -> Vec<T::AccountId> {
-		// choose the top MaxBasicSelected qualified candidates, ordered by voting power
-		let mut validators = candidates
-			.into_iter()
-			.filter(|x| x.amount >= min_stake)
-			.take(top_n)
-			.map(|x| x.owner)
-			.collect::<Vec<T::AccountId>>();
-		validators.sort();
-		validators
-	}
-
-	/// Take the snapshot of the given validators
-	pub fn collect_validator_snapshot(
+	/// Mark the given validators as selected for the round. Their `AtStake` snapshots are queued
+	/// and taken over the next blocks by `handle_pending_validator_snapshots()`.
+	pub fn select_validators(
 		now: RoundIndex,
 		validators: Vec<T::AccountId>,
 	) -> (u32, u32, BalanceOf<T>) {
 		let (mut validator_count, mut nomination_count, mut total) =
 			(0u32, 0u32, BalanceOf::<T>::zero());
-		// snapshot exposure for round for weighting reward distribution
 		for validator in validators.iter() {
 			let mut state = CandidateInfo::<T>::get(validator)
 				.expect("all members of CandidateQ must be candidates");
-			let top_nominations = TopNominations::<T>::get(validator)
-				.expect("all members of CandidateQ must be candidates");
 
 			validator_count += 1u32;
 			nomination_count += state.nomination_count;
 			total += state.voting_power;
 
 			let snapshot_total = state.voting_power;
-			let snapshot = ValidatorSnapshot {
-				bond: state.bond,
-				nominations: top_nominations.nominations,
-				total: state.voting_power,
-			};
-			<AtStake<T>>::insert(now, validator, snapshot);
+			state.reset_blocks_produced();
+			state.reset_productivity();
 			state.set_is_selected(true);
 			<CandidateInfo<T>>::insert(&validator, state);
 
@@ -661,6 +994,90 @@ impl<T: Config> Pallet<T> {
 		(validator_count, nomination_count, total)
 	}
 
+	/// Queue the `AtStake` snapshots of the selected validators of the given round
+	pub fn queue_validator_snapshots(now: RoundIndex) {
+		<PendingValidatorSnapshots<T>>::put((
+			now,
+			BoundedVec::truncate_from(SelectedCandidates::<T>::get().into_iter().collect()),
+		));
+	}
+
+	/// Take the pending `AtStake` snapshots of the selected validators, at most `limit` of them.
+	/// Returns the consumed weight.
+	pub fn handle_pending_validator_snapshots(limit: u32) -> Weight {
+		let Some((round, pending)) = <PendingValidatorSnapshots<T>>::get() else {
+			return Weight::from_parts(0u64, 0u64);
+		};
+		let mut validators = pending.into_inner();
+		let count = validators.len().min(limit as usize);
+		let remaining = validators.split_off(count);
+
+		for validator in validators {
+			if let Some(snapshot) = Self::build_validator_snapshot(&validator) {
+				<AtStake<T>>::insert(round, &validator, snapshot);
+			}
+		}
+		if remaining.is_empty() {
+			<PendingValidatorSnapshots<T>>::kill();
+		} else {
+			<PendingValidatorSnapshots<T>>::put((round, BoundedVec::truncate_from(remaining)));
+		}
+		T::WeightInfo::take_validator_snapshots(count as u32)
+	}
+
+	/// Take the pending `AtStake` snapshot of the given validator before its stake changes. This
+	/// freezes the snapshot at the stake of the round boundary.
+	pub fn freeze_validator_snapshot(validator: &T::AccountId) {
+		let Some((round, mut pending)) = <PendingValidatorSnapshots<T>>::get() else {
+			return;
+		};
+		let Some(index) = pending.iter().position(|v| v == validator) else {
+			return;
+		};
+		if let Some(snapshot) = Self::build_validator_snapshot(validator) {
+			<AtStake<T>>::insert(round, validator, snapshot);
+		}
+		pending.remove(index);
+		if pending.is_empty() {
+			<PendingValidatorSnapshots<T>>::kill();
+		} else {
+			<PendingValidatorSnapshots<T>>::put((round, pending));
+		}
+	}
+
+	/// Build the snapshot of the given validator based on its current stake
+	fn build_validator_snapshot(
+		validator: &T::AccountId,
+	) -> Option<ValidatorSnapshot<T::AccountId, BalanceOf<T>>> {
+		let state = CandidateInfo::<T>::get(validator)?;
+		let top_nominations = TopNominations::<T>::get(validator)?;
+		Some(ValidatorSnapshot {
+			bond: state.bond,
+			nominations: top_nominations.nominations,
+			total: state.voting_power,
+			commission: state.commission,
+		})
+	}
+
+	/// Returns the `AtStake` snapshot of the given validator in the given round. If the snapshot
+	/// is still pending, it is built from the current stake of the validator, which has not
+	/// changed since the round boundary.
+	pub fn validator_snapshot(
+		round: RoundIndex,
+		validator: &T::AccountId,
+	) -> ValidatorSnapshot<T::AccountId, BalanceOf<T>> {
+		if !<AtStake<T>>::contains_key(round, validator) {
+			if let Some((pending_round, pending)) = <PendingValidatorSnapshots<T>>::get() {
+				if pending_round == round && pending.contains(validator) {
+					if let Some(snapshot) = Self::build_validator_snapshot(validator) {
+						return snapshot;
+					}
+				}
+			}
+		}
+		<AtStake<T>>::get(round, validator)
+	}
+
 	/// Best as in most cumulatively supported in terms of stake
 	/// Returns [validator_count, nomination_count, total staked]
 	pub fn update_top_candidates(
@@ -671,12 +1088,12 @@ impl<T: Config> Pallet<T> {
 		let (mut validator_count, mut nomination_count, mut total) =
 			(0u32, 0u32, BalanceOf::<T>::zero());
 		// choose the top qualified full and basic candidates, ordered by their voting power
-		let full_snapshot = Self::collect_validator_snapshot(now, full_validators.clone());
-		let basic_snapshot = Self::collect_validator_snapshot(now, basic_validators.clone());
+		let full_selection = Self::select_validators(now, full_validators.clone());
+		let basic_selection = Self::select_validators(now, basic_validators.clone());
 
-		validator_count += full_snapshot.0 + basic_snapshot.0;
-		nomination_count += full_snapshot.1 + basic_snapshot.1;
-		total += full_snapshot.2 + basic_snapshot.2;
+		validator_count += full_selection.0 + basic_selection.0;
+		nomination_count += full_selection.1 + basic_selection.1;
+		total += full_selection.2 + basic_selection.2;
 
 		let validators: BoundedBTreeSet<T::AccountId, ConstU32<MAX_AUTHORITIES>> =
 			[full_validators.clone(), basic_validators.clone()]
@@ -729,16 +1146,47 @@ impl<T: Config> Pallet<T> {
 		<Points<T>>::mutate(round_index, |x: &mut RewardPoint| *x += 5);
 	}
 
-	/// Reset every `per round` related parameters of every candidates
-	pub fn reset_candidate_states() {
-		for candidate in <CandidateInfo<T>>::iter() {
-			let owner = candidate.0;
-			let mut state = candidate.1;
-			state.reset_blocks_produced();
-			state.reset_productivity();
-			state.set_is_selected(false);
-			<CandidateInfo<T>>::insert(&owner, state);
+	/// Queue the candidates that participated in the given round to reset their `per round`
+	/// related parameters after the round boundary
+	pub fn queue_candidate_resets(round: RoundIndex) {
+		<PendingCandidateResets<T>>::put(BoundedVec::truncate_from(
+			Self::round_participants(round).into_iter().collect::<Vec<T::AccountId>>(),
+		));
+	}
+
+	/// Reset every `per round` related parameters of the queued candidates that were not selected
+	/// for the current round. The selected validators are reset when they are chosen. Returns the
+	/// consumed weight.
+	pub fn reset_candidate_states() -> Weight {
+		let selected = SelectedCandidates::<T>::get();
+		let participants = <PendingCandidateResets<T>>::take();
+		let count = participants.len() as u64;
+		for owner in participants {
+			if selected.contains(&owner) {
+				continue;
+			}
+			if let Some(mut state) = CandidateInfo::<T>::get(&owner) {
+				state.reset_blocks_produced();
+				state.reset_productivity();
+				state.set_is_selected(false);
+				<CandidateInfo<T>>::insert(&owner, state);
+			}
 		}
+		T::DbWeight::get().reads_writes(2 + count, 1 + count)
+	}
+
+	/// Returns the candidates whose `per round` related parameters may have been updated in the
+	/// given round. These are the validators selected for the round, the validators selected for
+	/// the previous round (which remain in the session until the first session rotation) and
+	/// every block author of the round.
+	fn round_participants(round: RoundIndex) -> BTreeSet<T::AccountId> {
+		let mut participants =
+			SelectedCandidates::<T>::get().into_iter().collect::<BTreeSet<T::AccountId>>();
+		if let Some(previous) = CachedSelectedCandidates::<T>::get().get(&round.saturating_sub(1)) {
+			participants.extend(previous.iter().cloned());
+		}
+		participants.extend(AwardedPts::<T>::iter_key_prefix(round));
+		participants
 	}
 
 	/// Refresh the `CachedSelectedCandidates` adding the new selected candidates
@@ -868,13 +1316,36 @@ impl<T: Config> Pallet<T> {
 		<TotalAtStake<T>>::insert(now, snapshot);
 	}
 
-	/// kick out validator from the active validator set
-	pub fn kickout_validator(who: &T::AccountId) {
+	/// Returns the number of rounds that a validator is jailed for, given the number of times it
+	/// has been jailed including this one. The base duration is doubled for every repeated jail.
+	pub fn compute_jail_duration(jail_count: u32) -> RoundIndex {
+		let multiplier = 1u32.checked_shl(jail_count.saturating_sub(1)).unwrap_or(u32::MAX);
+		T::JailDuration::get().saturating_mul(multiplier).min(T::MaxJailDuration::get())
+	}
+
+	/// Returns the jail count of a validator after its decay. The count decreases by one for
+	/// every `JailCountDecay` rounds since its latest jail ended.
+	pub fn decayed_jail_count(count: u32, jailed_until: RoundIndex, now: RoundIndex) -> u32 {
+		let decay = T::JailCountDecay::get();
+		if decay.is_zero() {
+			return count;
+		}
+		count.saturating_sub(now.saturating_sub(jailed_until) / decay)
+	}
+
+	/// Jail validator and kick it out from the active validator set
+	pub fn jail_validator(who: &T::AccountId) {
 		// remove from candidate pool
 		Self::remove_from_candidate_pool(who);
 		// update candidate info
 		let mut candidate_state = CandidateInfo::<T>::get(who).expect("CandidateInfo must exist");
-		candidate_state.kick_out();
+		let now = Round::<T>::get().current_round_index;
+		let (jail_count, until_round) = <JailCount<T>>::mutate(who, |(count, until)| {
+			*count = Self::decayed_jail_count(*count, *until, now).saturating_add(1);
+			*until = now.saturating_add(Self::compute_jail_duration(*count));
+			(*count, *until)
+		});
+		candidate_state.jail(until_round);
 		CandidateInfo::<T>::insert(who, &candidate_state);
 		// remove from selected candidates
 		Self::remove_from_selected_candidates(who, candidate_state.tier);
@@ -888,21 +1359,103 @@ impl<T: Config> Pallet<T> {
 			// kickout relayer
 			T::RelayManager::kickout_relayer(who);
 		}
-		Pallet::<T>::deposit_event(Event::<T>::KickedOut(who.clone()));
+		Pallet::<T>::deposit_event(Event::<T>::Jailed {
+			candidate: who.clone(),
+			until_round,
+			jail_count,
+			nominator_count: candidate_state.nomination_count,
+		});
+		// notify the top nominators so that they can move their stake
+		let top_nominations = TopNominations::<T>::get(who).unwrap_or_default().nominations;
+		top_nominations.into_iter().for_each(|bond| {
+			Pallet::<T>::deposit_event(Event::<T>::NominationJailed {
+				nominator: bond.owner,
+				candidate: who.clone(),
+				until_round,
+			});
+		});
+	}
+
+	/// Returns the slashable nominations of the given validator. Only the nominators captured by
+	/// the snapshot of the offence round are exposed, bounded by their current nomination.
+	pub fn compute_nominator_exposures(
+		snapshot: &ValidatorIdentification<T::AccountId, BalanceOf<T>>,
+		validator: &T::AccountId,
+	) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		let top_nominations =
+			TopNominations::<T>::get(validator).map(|n| n.nominations).unwrap_or_default();
+		let bottom_nominations = BottomNominations::<T>::get(validator)
+			.map(|n| n.nominations)
+			.unwrap_or_default();
+		let current = top_nominations.into_iter().chain(bottom_nominations).collect::<Vec<_>>();
+
+		nominator_exposures(&snapshot.nominations, &current)
+	}
+
+	/// Updates the nomination related storage of the given slashed nominator. This will update
+	/// its nomination, the offender's top or bottom nominations and the total locked stake. The
+	/// nomination will be removed if the entire nomination has been slashed.
+	fn slash_reserved_nomination(
+		offender: &T::AccountId,
+		candidate_state: &mut CandidateMetadata<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+		nominator: &T::AccountId,
+		amount: BalanceOf<T>,
+	) {
+		if let Some(mut nominator_state) = NominatorState::<T>::get(nominator) {
+			let bond = match nominator_state.nominations.get(offender) {
+				Some(bond) => *bond,
+				None => return,
+			};
+			let amount = amount.min(bond);
+			let remaining = nominator_state.slash_nomination(offender, amount).unwrap_or_default();
+
+			if remaining.is_zero() {
+				// the entire nomination has been slashed
+				let _ =
+					candidate_state.rm_nomination_if_exists::<T>(offender, nominator.clone(), bond);
+				nominator_state.rm_nomination(offender);
+				if nominator_state.requests.requests.remove(offender).is_some() {
+					nominator_state.requests.revocations_count =
+						nominator_state.requests.revocations_count.saturating_sub(1u32);
+				}
+			} else {
+				let _ = candidate_state.decrease_nomination::<T>(
+					offender,
+					nominator.clone(),
+					bond,
+					amount,
+				);
+			}
+
+			if nominator_state.nominations.is_empty() {
+				<NominatorState<T>>::remove(nominator);
+			} else {
+				<NominatorState<T>>::insert(nominator, nominator_state);
+			}
+			let new_total_locked = Total::<T>::get().saturating_sub(amount);
+			<Total<T>>::put(new_total_locked);
+		}
 	}
 
 	/// Updates the self-bond related storage of the given validator. This will update it's
-	/// self-bond, voting power, total locked stake. It will also cancel the pending request if the
-	/// result after the slash creates an integer underflow.
+	/// self-bond, voting power (also in the `CandidatePool`), total locked stake. It will also cancel the pending request if the
+	/// result after the slash creates an integer underflow. The nomination related storage of each
+	/// slashed nominator will be updated as well.
 	pub fn slash_reserved_bonds(
 		offender: &T::AccountId,
 		offender_slash: BalanceOf<T>,
-		_nominators_slash: &Vec<(T::AccountId, BalanceOf<T>)>,
+		nominators_slash: &Vec<(T::AccountId, BalanceOf<T>)>,
 	) {
+		Self::freeze_validator_snapshot(offender);
 		let mut candidate_state =
 			CandidateInfo::<T>::get(offender).expect("CandidateInfo must exist");
+		for (nominator, amount) in nominators_slash {
+			Self::slash_reserved_nomination(offender, &mut candidate_state, nominator, *amount);
+		}
 		candidate_state.slash_bond(offender_slash);
 		candidate_state.slash_voting_power(offender_slash);
+		// update candidate pool value because it must change if self bond changes
+		let _ = Self::update_active(offender, candidate_state.voting_power);
 
 		// remove validator bond less request amount to prevent integer underflow
 		if let Some(request) = &candidate_state.request {
@@ -923,8 +1476,35 @@ impl<T: Config> Pallet<T> {
 		CandidateInfo::<T>::insert(offender, candidate_state);
 	}
 
-	/// Update to the new round. This method will refresh the candidate states and some other
-	/// metadata, and will also apply the new top candidates selected for the new round.
+	/// Applies the deferred slashes scheduled for the given round. This will update the stake
+	/// related storage of each offender and its slashed nominators. Returns the consumed weight.
+	pub fn handle_unapplied_slashes(now: RoundIndex) -> Weight {
+		let current_bond =
+			|offender: &T::AccountId| CandidateInfo::<T>::get(offender).map(|state| state.bond);
+		let current_nomination = |offender: &T::AccountId, nominator: &T::AccountId| {
+			NominatorState::<T>::get(nominator)
+				.and_then(|state| state.nominations.get(offender).copied())
+				.unwrap_or_default()
+		};
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		for (offender, offender_slash, nominators_slash) in
+			T::OffenceHandler::apply_unapplied_slashes(now, current_bond, current_nomination)
+		{
+			let nominators = nominators_slash.len() as u64;
+			weight.saturating_accrue(
+				T::DbWeight::get().reads_writes(3 + 3 * nominators, 2 + 3 * nominators),
+			);
+			if CandidateInfo::<T>::contains_key(&offender) {
+				Self::slash_reserved_bonds(&offender, offender_slash, &nominators_slash);
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(6, 5));
+			}
+		}
+		weight
+	}
+
+	/// Update to the new round. This method will apply the new top candidates selected for the
+	/// new round and will queue the remaining phases of the round transition over the next
+	/// blocks.
 	pub fn new_round(
 		now: BlockNumberFor<T>,
 		full_validators: Vec<T::AccountId>,
@@ -938,10 +1518,13 @@ impl<T: Config> Pallet<T> {
 		// this must be executed in advance, bc initial and current state should be matched at this moment
 		T::RelayManager::refresh_round(now);
 		T::RelayManager::handle_delayed_relayer_sets(now);
-		// reset candidate states
-		Pallet::<T>::reset_candidate_states();
-		// pay all stakers for T::RewardPaymentDelay rounds ago
-		Self::prepare_staking_payouts(now);
+		// apply the deferred slashes reported after the round transition applied them
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			Self::handle_unapplied_slashes(now),
+			DispatchClass::Mandatory,
+		);
+		// reset the states of the previous validators over the next blocks
+		Self::queue_candidate_resets(now - 1);
 		// select top validator candidates for the next round
 		let (validator_count, _, total_staked) =
 			Self::update_top_candidates(now, full_validators, basic_validators);
@@ -955,10 +1538,12 @@ impl<T: Config> Pallet<T> {
 		// snapshot total stake and storage state
 		<Staked<T>>::insert(now, Total::<T>::get());
 		<TotalAtStake<T>>::remove(now - 1);
-		// handle delayed commission update requests
-		Self::handle_delayed_commission_sets(now);
-		// handle delayed controller update requests
-		Self::handle_delayed_controller_sets(now);
+		// queue the snapshots of the new validators to be taken over the next blocks
+		Self::queue_validator_snapshots(now);
+		// start pruning the reward history entries that became stale over the next blocks
+		Self::queue_reward_history_pruning();
+		// run the phases of the round transition after the round boundary over the next blocks
+		<RoundTransition<T>>::put(RoundTransitionPhase::CandidateStates);
 
 		Self::deposit_event(Event::NewRound {
 			starting_block: round.first_round_block,
@@ -967,6 +1552,251 @@ impl<T: Config> Pallet<T> {
 			total_balance: total_staked,
 		});
 	}
+
+	/// Run the next phase of the round transition. The transition starts `RoundTransitionBlocks`
+	/// before the round boundary. Returns the consumed weight.
+	pub fn handle_round_transition(now: BlockNumberFor<T>) -> Weight {
+		let round = Round::<T>::get();
+		let phase = match <RoundTransition<T>>::get() {
+			Some(phase) => phase,
+			None if !round.should_update(now)
+				&& round.should_update(now + T::RoundTransitionBlocks::get().into()) =>
+			{
+				RoundTransitionPhase::ValidatorSnapshots
+			},
+			None => return T::DbWeight::get().reads(2),
+		};
+		Self::run_round_transition_phase(
+			round.current_round_index,
+			phase,
+			T::MaxValidatorSnapshotsPerBlock::get(),
+		)
+	}
+
+	/// Run the given phase of the round transition of the given round and move to the next phase.
+	/// Returns the consumed weight.
+	fn run_round_transition_phase(
+		round: RoundIndex,
+		phase: RoundTransitionPhase,
+		snapshot_limit: u32,
+	) -> Weight {
+		use RoundTransitionPhase::*;
+		let next_round = round + 1;
+		let (weight, next) = match phase {
+			ValidatorSnapshots => {
+				let weight = Self::handle_pending_validator_snapshots(snapshot_limit);
+				if <PendingValidatorSnapshots<T>>::exists() {
+					(weight, Some(ValidatorSnapshots))
+				} else {
+					(weight, Some(CommissionSets))
+				}
+			},
+			CommissionSets => {
+				(Self::handle_delayed_commission_sets(next_round), Some(ControllerSets))
+			},
+			ControllerSets => (Self::handle_delayed_controller_sets(next_round), Some(Election)),
+			Election => (Self::elect_next_validators(), Some(UnappliedSlashes)),
+			UnappliedSlashes => (Self::handle_unapplied_slashes(next_round), Some(Elected)),
+			Elected => (Weight::from_parts(0u64, 0u64), Some(Elected)),
+			CandidateStates => (Self::reset_candidate_states(), Some(StakingPayouts)),
+			StakingPayouts => (Self::prepare_staking_payouts(round), None),
+		};
+		match next {
+			Some(next) => <RoundTransition<T>>::put(next),
+			None => <RoundTransition<T>>::kill(),
+		}
+		weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	/// Elect the validators of the next round and keep them until the round boundary. Returns the
+	/// consumed weight.
+	fn elect_next_validators() -> Weight {
+		let weight = T::ElectionProvider::weight::<T::WeightInfo>(
+			CandidatePool::<T>::decode_len().unwrap_or_default() as u32,
+			T::MaxTopNominationsPerCandidate::get(),
+		);
+		let (full_validators, basic_validators) = Self::compute_top_candidates();
+		<ElectedCandidates<T>>::put((
+			BoundedVec::truncate_from(full_validators),
+			BoundedVec::truncate_from(basic_validators),
+		));
+		weight.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	/// Returns the full and basic validators elected for the next round. The phases of the round
+	/// transition that did not get a block before the round boundary are run first.
+	pub fn take_elected_candidates() -> (Vec<T::AccountId>, Vec<T::AccountId>) {
+		let round = Round::<T>::get().current_round_index;
+		let mut weight = Weight::from_parts(0u64, 0u64);
+		loop {
+			match <RoundTransition<T>>::get() {
+				Some(RoundTransitionPhase::Elected) => break,
+				Some(phase) => {
+					weight.saturating_accrue(Self::run_round_transition_phase(
+						round,
+						phase,
+						MAX_AUTHORITIES,
+					));
+				},
+				None => <RoundTransition<T>>::put(RoundTransitionPhase::ValidatorSnapshots),
+			}
+		}
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			weight,
+			DispatchClass::Mandatory,
+		);
+
+		let (full_validators, basic_validators) =
+			<ElectedCandidates<T>>::take().unwrap_or_default();
+		// the candidates that left, went offline or were jailed since the election are not selected
+		let is_eligible =
+			|v: &T::AccountId| CandidateInfo::<T>::get(v).is_some_and(|state| state.is_active());
+		(
+			full_validators.into_iter().filter(is_eligible).collect(),
+			basic_validators.into_iter().filter(is_eligible).collect(),
+		)
+	}
+
+	/// Returns the state of the given candidate including its top and bottom nominations
+	pub fn candidate_state(
+		candidate: &T::AccountId,
+	) -> Option<CandidateState<T::AccountId, BalanceOf<T>>> {
+		let state = CandidateInfo::<T>::get(candidate)?;
+		let top_nominations =
+			TopNominations::<T>::get(candidate).map(|n| n.nominations).unwrap_or_default();
+		let bottom_nominations = BottomNominations::<T>::get(candidate)
+			.map(|n| n.nominations)
+			.unwrap_or_default();
+
+		Some(CandidateState {
+			controller: candidate.clone(),
+			stash: state.stash,
+			bond: state.bond,
+			initial_bond: state.initial_bond,
+			voting_power: state.voting_power,
+			nomination_count: state.nomination_count,
+			top_nominations,
+			bottom_nominations,
+			request: state.request,
+			status: state.status,
+			is_selected: state.is_selected,
+			commission: state.commission,
+			blocks_produced: state.blocks_produced,
+			productivity: state.productivity,
+			reward_dst: state.reward_dst,
+			awarded_tokens: state.awarded_tokens,
+			tier: state.tier,
+			identity: CandidateIdentities::<T>::get(candidate).map(Into::into),
+		})
+	}
+
+	/// Returns the state of the given nominator including its pending unbonding requests
+	pub fn nominator_info(
+		nominator: &T::AccountId,
+	) -> Option<NominatorInfo<T::AccountId, BalanceOf<T>>> {
+		let state = NominatorState::<T>::get(nominator)?;
+
+		Some(NominatorInfo {
+			id: state.id,
+			nominations: state
+				.nominations
+				.into_iter()
+				.map(|(owner, amount)| Bond { owner, amount })
+				.collect(),
+			total: state.total,
+			requests: state.requests.requests.into_values().collect(),
+			less_total: state.requests.less_total,
+			status: state.status,
+			reward_dst: state.reward_dst,
+			awarded_tokens: state.awarded_tokens,
+		})
+	}
+
+	/// Returns the selected candidates of the current round that matches the given `tier`
+	pub fn selected_candidates_by_tier(tier: TierType) -> Vec<T::AccountId> {
+		match tier {
+			TierType::Full => SelectedFullCandidates::<T>::get().into_iter().collect(),
+			TierType::Basic => SelectedBasicCandidates::<T>::get().into_iter().collect(),
+			TierType::All => SelectedCandidates::<T>::get().into_iter().collect(),
+		}
+	}
+
+	/// Returns the estimated annual percentage rate of the staking rewards as a ratio of the
+	/// annual rewards to the stake, which may exceed one. If a `candidate` is given, the rate of
+	/// its nominators is estimated under the assumption that every selected candidate produces an
+	/// equal share of blocks. Otherwise the network-wide rate is returned. The rate is zero if
+	/// nothing is staked.
+	pub fn estimated_apr(candidate: Option<T::AccountId>) -> FixedU128 {
+		let total_staked = Total::<T>::get();
+		let (round_staking_reward, _, _) =
+			Self::split_round_issuance(Self::compute_issuance(total_staked));
+		let annual_issuance = round_staking_reward.saturating_mul(rounds_per_year::<T>().into());
+
+		let (rewards, stake) = match candidate {
+			None => (annual_issuance, total_staked),
+			Some(candidate) => {
+				let selected = SelectedCandidates::<T>::get().len() as u32;
+				match CandidateInfo::<T>::get(&candidate) {
+					Some(state) if !selected.is_zero() => {
+						let validator_issuance = annual_issuance / selected.into();
+						let amount_due = state.commission.left_from_one() * validator_issuance;
+						(amount_due, state.voting_power)
+					},
+					_ => return FixedU128::zero(),
+				}
+			},
+		};
+		FixedU128::checked_from_rational(rewards, stake).unwrap_or_else(FixedU128::zero)
+	}
+
+	/// Returns the reward history of the given account (validator controller or nominator) earned
+	/// between the `from` and `to` rounds (inclusive), oldest first.
+	pub fn reward_history(
+		who: &T::AccountId,
+		from: RoundIndex,
+		to: RoundIndex,
+	) -> Vec<RewardEntry<T::AccountId, BalanceOf<T>>> {
+		let now = Round::<T>::get().current_round_index;
+		RewardHistory::<T>::get(who)
+			.into_iter()
+			.filter(|entry| {
+				entry.round >= from && entry.round <= to && Self::is_recent_reward(entry.round, now)
+			})
+			.collect()
+	}
+
+	/// Returns the amount of rewards that the given account (validator controller or nominator)
+	/// will receive from the rounds which are prepared for payouts but not paid out yet. The
+	/// rewards are computed with the commission of the validator snapshots.
+	pub fn pending_rewards(who: &T::AccountId) -> BalanceOf<T> {
+		let mut pending = BalanceOf::<T>::zero();
+
+		for (round, payout_info) in DelayedPayouts::<T>::iter() {
+			let total_points = Points::<T>::get(round);
+			if total_points.is_zero() {
+				continue;
+			}
+			for (validator, pts) in AwardedPts::<T>::iter_prefix(round) {
+				// validators that have left are not paid out
+				let Some(state) = CandidateInfo::<T>::get(&validator) else {
+					continue;
+				};
+				let (pts, total_points) = Self::reward_points(round, &validator, state.tier, pts);
+				let snapshot = Self::validator_snapshot(round, &validator);
+				let (_, validator_reward, nominator_rewards) =
+					Self::compute_validator_rewards(&payout_info, pts, total_points, &snapshot);
+				if validator == *who {
+					pending = pending.saturating_add(validator_reward);
+				}
+				for (nominator, reward) in nominator_rewards {
+					if nominator == *who {
+						pending = pending.saturating_add(reward);
+					}
+				}
+			}
+		}
+		pending
+	}
 }
 
 impl<T> pallet_authorship::EventHandler<T::AccountId, BlockNumberFor<T>> for Pallet<T>
@@ -1015,8 +1845,8 @@ where
 
 		// Check if the round should update
 		if round.should_update(now) {
-			// Compute the new validators (full, basic) for the new round
-			let (mut full_validators, mut basic_validators) = Self::compute_top_candidates();
+			// Take the new validators (full, basic) elected for the new round
+			let (mut full_validators, mut basic_validators) = Self::take_elected_candidates();
 			// Filter and verify if each validator has an on-chain session key registered
 			let session_key_verifier = |validators: Vec<T::AccountId>| {
 				validators
@@ -1092,12 +1922,12 @@ impl<T: Config> EstimateNextSessionRotation<BlockNumberFor<T>> for Pallet<T> {
 }
 
 impl<T: Config>
-	OnOffenceHandler<T::AccountId, pallet_session::historical::IdentificationTuple<T>, Weight>
+	OnOffenceKindHandler<T::AccountId, pallet_session::historical::IdentificationTuple<T>, Weight>
 	for Pallet<T>
 where
 	T: pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
 	T: pallet_session::historical::Config<
-		FullIdentification = ValidatorSnapshot<
+		FullIdentification = ValidatorIdentification<
 			<T as frame_system::Config>::AccountId,
 			BalanceOf<T>,
 		>,
@@ -1112,6 +1942,7 @@ where
 	T::AccountId: Copy,
 {
 	fn on_offence(
+		kind: OffenceKind,
 		offenders: &[OffenceDetails<
 			T::AccountId,
 			pallet_session::historical::IdentificationTuple<T>,
@@ -1121,34 +1952,43 @@ where
 	) -> Weight {
 		let round = Round::<T>::get();
 		for (details, slash_fraction) in offenders.iter().zip(slash_fraction) {
-			let (controller, _snapshot) = &details.offender;
+			// the snapshot has been recorded by the historical session at the offence session
+			let (controller, snapshot) = &details.offender;
 			if let Some(candidate_state) = CandidateInfo::<T>::get(controller) {
-				// prevent offence handling if the validator is already kicked out (due to session
-				// update delay)
-				if candidate_state.is_kicked_out() {
+				// prevent offence handling if the validator is already kicked out or jailed (due to
+				// session update delay)
+				if candidate_state.is_kicked_out() || candidate_state.is_jailed() {
 					continue;
 				}
+				let nominators = Pallet::<T>::compute_nominator_exposures(snapshot, controller);
 				let offender_slash = *slash_fraction * candidate_state.bond;
+				let nominators_slash =
+					nominators.iter().fold(BalanceOf::<T>::zero(), |acc, (_, exposure)| {
+						acc.saturating_add(*slash_fraction * *exposure)
+					});
 				let offence = Offence::new(
+					kind,
 					round.current_round_index,
 					slash_session,
 					candidate_state.bond,
+					offender_slash.saturating_add(nominators_slash),
 					offender_slash,
-					offender_slash,
-					BalanceOf::<T>::zero(), // disable slashing nominators for now
+					nominators_slash,
 					*slash_fraction,
 				);
-				let (is_slashed, slash_amount) = T::OffenceHandler::try_handle_offence(
-					&controller,
-					&candidate_state.stash,
-					candidate_state.tier,
-					offence,
-				);
+				let (is_slashed, slash_amount, nominators_slash) =
+					T::OffenceHandler::try_handle_offence(
+						&controller,
+						&candidate_state.stash,
+						candidate_state.tier,
+						offence,
+						nominators,
+					);
 				if is_slashed {
-					// kick out validator from active set
-					Pallet::<T>::kickout_validator(&controller);
+					// jail validator and kick out from active set
+					Pallet::<T>::jail_validator(&controller);
 					// update stake related storage
-					Pallet::<T>::slash_reserved_bonds(&controller, slash_amount, &vec![]);
+					Pallet::<T>::slash_reserved_bonds(&controller, slash_amount, &nominators_slash);
 				}
 			}
 		}
@@ -1156,3 +1996,145 @@ where
 		consumed_weight
 	}
 }
+
+impl<T: Config> NominationManager<T::AccountId, BalanceOf<T>> for Pallet<T> {
+	fn current_round() -> RoundIndex {
+		Round::<T>::get().current_round_index
+	}
+
+	fn min_nomination() -> BalanceOf<T> {
+		T::MinNomination::get()
+	}
+
+	fn min_nominator_stake() -> BalanceOf<T> {
+		T::MinNominatorStk::get()
+	}
+
+	fn max_nominations_per_nominator() -> u32 {
+		T::MaxNominationsPerNominator::get()
+	}
+
+	fn nomination_bond_less_delay() -> RoundIndex {
+		T::NominationBondLessDelay::get()
+	}
+
+	fn selected_candidates() -> Vec<(T::AccountId, BalanceOf<T>)> {
+		SelectedCandidates::<T>::get()
+			.into_iter()
+			.filter_map(|candidate| {
+				CandidateInfo::<T>::get(&candidate).map(|state| (candidate, state.voting_power))
+			})
+			.collect()
+	}
+
+	fn nominations(nominator: &T::AccountId) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		NominatorState::<T>::get(nominator)
+			.map(|state| state.nominations.into_iter().collect())
+			.unwrap_or_default()
+	}
+
+	fn total_nominated(nominator: &T::AccountId) -> BalanceOf<T> {
+		NominatorState::<T>::get(nominator).map(|state| state.total).unwrap_or_default()
+	}
+
+	fn pending_requests(nominator: &T::AccountId) -> Vec<(T::AccountId, BalanceOf<T>, RoundIndex)> {
+		NominatorState::<T>::get(nominator)
+			.map(|state| {
+				state
+					.requests
+					.requests
+					.into_values()
+					.map(|request| (request.validator, request.amount, request.when_executable))
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	fn nominate(
+		nominator: &T::AccountId,
+		candidate: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let origin = RawOrigin::Signed(nominator.clone()).into();
+		let result = match NominatorState::<T>::get(nominator) {
+			Some(state) if state.nominations.contains_key(candidate) => {
+				Self::nominator_bond_more(origin, candidate.clone(), amount)
+			},
+			state => {
+				let candidate_nomination_count = CandidateInfo::<T>::get(candidate)
+					.map(|c| c.nomination_count)
+					.ok_or(Error::<T>::CandidateDNE)?;
+				let nomination_count =
+					state.map(|s| s.nominations.len() as u32).unwrap_or_default();
+				Self::nominate(
+					origin,
+					candidate.clone(),
+					amount,
+					candidate_nomination_count,
+					nomination_count,
+				)
+			},
+		};
+		result.map(|_| ()).map_err(|e| e.error)
+	}
+
+	fn schedule_nomination_decrease(
+		nominator: &T::AccountId,
+		candidate: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Result<RoundIndex, DispatchError> {
+		let state = NominatorState::<T>::get(nominator).ok_or(Error::<T>::NominatorDNE)?;
+		let bonded = state.nominations.get(candidate).copied().ok_or(Error::<T>::NominationDNE)?;
+		let origin: T::RuntimeOrigin = RawOrigin::Signed(nominator.clone()).into();
+		let result = if amount == bonded {
+			Self::schedule_revoke_nomination(origin, candidate.clone())
+		} else {
+			Self::schedule_nominator_bond_less(origin, candidate.clone(), amount)
+		};
+		result.map_err(|e| e.error)?;
+
+		NominatorState::<T>::get(nominator)
+			.and_then(|state| state.requests.requests.get(candidate).map(|r| r.when_executable))
+			.ok_or(Error::<T>::PendingNominationRequestDNE.into())
+	}
+
+	fn execute_nomination_request(
+		nominator: &T::AccountId,
+		candidate: &T::AccountId,
+	) -> DispatchResult {
+		Self::execute_nomination_request(
+			RawOrigin::Signed(nominator.clone()).into(),
+			candidate.clone(),
+		)
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+
+	fn set_reward_payee(nominator: &T::AccountId, payee: T::AccountId) -> DispatchResult {
+		Self::set_nominator_reward_dst(
+			RawOrigin::Signed(nominator.clone()).into(),
+			RewardDestination::Payee(payee),
+		)
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_benchmark(index: u32) -> Result<T::AccountId, DispatchError> {
+		let stash = frame_benchmarking::account::<T::AccountId>("stash", index, 0);
+		let controller = frame_benchmarking::account::<T::AccountId>("controller", index, 0);
+		let bond = T::MinBasicCandidateStk::get();
+		T::Currency::make_free_balance_be(&stash, bond.saturating_mul(2u32.into()));
+		Self::join_candidates(
+			RawOrigin::Signed(stash).into(),
+			controller.clone(),
+			None,
+			bond,
+			CandidatePool::<T>::get().len() as u32,
+		)
+		.map_err(|e| e.error)?;
+		SelectedCandidates::<T>::mutate(|selected| selected.try_insert(controller.clone()))
+			.map_err(|_| Error::<T>::TooManyCandidates)?;
+		Ok(controller)
+	}
+}
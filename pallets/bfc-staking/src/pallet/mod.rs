@@ -1,10 +1,11 @@
 mod impls;
 
 use crate::{
-	migrations, BalanceOf, BlockNumberFor, Bond, CandidateMetadata, DelayedCommissionSet,
-	DelayedControllerSet, DelayedPayout, InflationInfo, NominationRequest, Nominations, Nominator,
-	NominatorAdded, Range, RewardDestination, RewardPoint, RoundIndex, RoundInfo, TierType,
-	TotalSnapshot, ValidatorSnapshot, WeightInfo,
+	election::ElectionProvider, migrations, BalanceOf, BlockNumberFor, Bond, CandidateIdentity,
+	CandidateMetadata, DelayedCommissionSet, DelayedControllerSet, DelayedPayout, IdentityField,
+	InflationInfo, NegativeImbalanceOf, NominationRequest, Nominations, Nominator, NominatorAdded,
+	Range, RewardDestination, RewardEntry, RewardPoint, RoundIndex, RoundInfo,
+	RoundTransitionPhase, TierType, TotalSnapshot, ValidatorSnapshot, WeightInfo,
 };
 
 use bp_staking::{
@@ -14,7 +15,7 @@ use bp_staking::{
 use frame_support::{
 	dispatch::DispatchResultWithPostInfo,
 	pallet_prelude::*,
-	traits::{Currency, Get, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	traits::{Currency, Get, OnRuntimeUpgrade, OnUnbalanced, ReservableCurrency, StorageVersion},
 	BoundedBTreeMap, BoundedBTreeSet, Twox64Concat,
 };
 use frame_system::pallet_prelude::*;
@@ -30,7 +31,7 @@ pub mod pallet {
 	use super::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
 
 	/// Pallet for bfc staking
 	#[pallet::pallet]
@@ -48,12 +49,20 @@ pub mod pallet {
 		type RelayManager: RelayManager<Self::AccountId>;
 		/// The offence handler type
 		type OffenceHandler: OffenceHandler<Self::AccountId, BalanceOf<Self>>;
+		/// Handler for the treasury share of the round issuance
+		type Treasury: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// The election algorithm used to select the validators of each tier
+		type ElectionProvider: ElectionProvider<Self::AccountId, BalanceOf<Self>>;
 		/// The default number of blocks per session at genesis
 		#[pallet::constant]
 		type DefaultBlocksPerSession: Get<u32>;
 		/// The default number of blocks per round at genesis
 		#[pallet::constant]
 		type DefaultBlocksPerRound: Get<u32>;
+		/// The expected block time (slot duration) in milliseconds used to derive the number of
+		/// rounds per year
+		#[pallet::constant]
+		type SlotDuration: Get<u64>;
 		/// Minimum round length that can be set by the system.
 		#[pallet::constant]
 		type MinBlocksPerRound: Get<u32>;
@@ -69,18 +78,47 @@ pub mod pallet {
 		/// Number of rounds candidate requests to decrease self-bond must wait to be executable
 		#[pallet::constant]
 		type CandidateBondLessDelay: Get<RoundIndex>;
+		/// Number of rounds that a validator is jailed for its first offence. The jail length is
+		/// doubled for every repeated offence
+		#[pallet::constant]
+		type JailDuration: Get<RoundIndex>;
+		/// Maximum number of rounds that a validator can be jailed for
+		#[pallet::constant]
+		type MaxJailDuration: Get<RoundIndex>;
+		/// Number of rounds without another jail after which the jail count of a validator
+		/// decreases by one, counted from the end of its latest jail
+		#[pallet::constant]
+		type JailCountDecay: Get<RoundIndex>;
 		/// Number of rounds that nominators remain bonded before exit request is executable
 		#[pallet::constant]
 		type LeaveNominatorsDelay: Get<RoundIndex>;
 		/// Number of rounds that nominations remain bonded before revocation request is executable
 		#[pallet::constant]
 		type RevokeNominationDelay: Get<RoundIndex>;
+		/// Minimum number of rounds between two redelegations of a nominator
+		#[pallet::constant]
+		type RedelegationDelay: Get<RoundIndex>;
 		/// Number of rounds that nomination less requests must wait before executable
 		#[pallet::constant]
 		type NominationBondLessDelay: Get<RoundIndex>;
 		/// Number of rounds after which block authors are rewarded
 		#[pallet::constant]
 		type RewardPaymentDelay: Get<RoundIndex>;
+		/// Maximum number of reward entries kept in the history of each account
+		#[pallet::constant]
+		type MaxRewardHistory: Get<u32>;
+		/// Number of rounds that reward entries are kept in the history of each account
+		#[pallet::constant]
+		type RewardHistoryDepth: Get<RoundIndex>;
+		/// Maximum number of accounts whose stale reward history is pruned per block
+		#[pallet::constant]
+		type MaxRewardHistoryPrunesPerBlock: Get<u32>;
+		/// Maximum number of validator snapshots taken per block after a round transition
+		#[pallet::constant]
+		type MaxValidatorSnapshotsPerBlock: Get<u32>;
+		/// Number of blocks before the round boundary at which the round transition starts
+		#[pallet::constant]
+		type RoundTransitionBlocks: Get<u32>;
 		/// Default maximum number of selected full node candidates every round
 		#[pallet::constant]
 		type DefaultMaxSelectedFullCandidates: Get<u32>;
@@ -108,6 +146,9 @@ pub mod pallet {
 		/// The maximum commission rate available for a basic validator
 		#[pallet::constant]
 		type MaxBasicValidatorCommission: Get<Perbill>;
+		/// The default maximum commission rate increase that is applied to a validator per round
+		#[pallet::constant]
+		type DefaultMaxCommissionIncreasePerRound: Get<Perbill>;
 		/// Minimum stake required for any full node candidate to be in `SelectedCandidates` for the
 		/// round
 		#[pallet::constant]
@@ -128,6 +169,9 @@ pub mod pallet {
 		/// Minimum stake for any registered on-chain account to be a nominator
 		#[pallet::constant]
 		type MinNominatorStk: Get<BalanceOf<Self>>;
+		/// The amount reserved from the stash account to register a candidate identity
+		#[pallet::constant]
+		type IdentityDeposit: Get<BalanceOf<Self>>;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -202,6 +246,8 @@ pub mod pallet {
 		AlreadyCommissionSetRequested,
 		/// The requested inflation options are invalid.
 		InvalidSchedule,
+		/// The requested treasury and burn shares exceed the round issuance.
+		InvalidIssuanceShares,
 		/// The requested tier type are invalid.
 		InvalidTierType,
 		/// The requested value are below the minimum value.
@@ -250,6 +296,26 @@ pub mod pallet {
 		PendingNominationRequestNotDueYet,
 		/// Cannot nominate if the given amount is less than the lowest bottom.
 		CannotNominateLessThanLowestBottomWhenBottomIsFull,
+		/// Cannot redelegate to the same candidate.
+		CannotRedelegateToSameCandidate,
+		/// Cannot redelegate more than the nominated amount.
+		RedelegationExceedsNomination,
+		/// Cannot redelegate before the redelegation delay has passed.
+		RedelegationTooFrequent,
+		/// Only full node candidates can register a relayer endpoint.
+		RelayerEndpointOnlyForFullTier,
+		/// A candidate identity does not exist with the target controller account.
+		CandidateIdentityDNE,
+		/// The given candidate is jailed.
+		CandidateJailed,
+		/// The given candidate is not jailed.
+		CandidateNotJailed,
+		/// Cannot unjail before the jail of the candidate ends.
+		CandidateCannotUnjailYet,
+		/// Cannot leave while a deferred slash of the candidate is waiting to be applied.
+		CannotLeaveWithUnappliedSlash,
+		/// Cannot redelegate from a candidate whose deferred slash is waiting to be applied.
+		CannotRedelegateWithUnappliedSlash,
 	}
 
 	#[pallet::event]
@@ -387,6 +453,10 @@ pub mod pallet {
 		},
 		/// Paid the account (nominator or validator) the round reward.
 		Rewarded { account: T::AccountId, rewards: BalanceOf<T> },
+		/// Paid the treasury share of the round issuance.
+		TreasuryRewarded { round: RoundIndex, amount: BalanceOf<T> },
+		/// Burned (never minted) the burn share of the round issuance.
+		IssuanceBurned { round: RoundIndex, amount: BalanceOf<T> },
 		/// Annual inflation input (first 3) was used to derive new per-round inflation (last 3)
 		InflationSet {
 			annual_min: Perbill,
@@ -395,6 +465,8 @@ pub mod pallet {
 			round_min: Perbill,
 			round_ideal: Perbill,
 			round_max: Perbill,
+			treasury: Perbill,
+			burn: Perbill,
 		},
 		/// Staking expectations set.
 		StakeExpectationsSet {
@@ -414,6 +486,24 @@ pub mod pallet {
 		ValidatorCommissionSet { candidate: T::AccountId, old: Perbill, new: Perbill },
 		/// Cancel the validator commission set.
 		ValidatorCommissionSetCancelled { candidate: T::AccountId },
+		/// Set the maximum commission rate increase per round to this value.
+		MaxCommissionIncreasePerRoundSet { old: Perbill, new: Perbill },
+		/// Set the activation of the relayer score based reward weighting for full validators.
+		RelayerScoreRewardActivationSet { is_active: bool },
+		/// The requested commission increase exceeds the per round limit and will be applied
+		/// stepwise. The target is reached at the start of `final_round`.
+		ValidatorCommissionIncreaseScheduled {
+			candidate: T::AccountId,
+			old: Perbill,
+			new: Perbill,
+			final_round: RoundIndex,
+		},
+		/// A step of a scheduled commission increase has been applied.
+		ValidatorCommissionStepApplied {
+			candidate: T::AccountId,
+			commission: Perbill,
+			target: Perbill,
+		},
 		/// Set blocks per round.
 		BlocksPerRoundSet {
 			current_round: RoundIndex,
@@ -433,17 +523,43 @@ pub mod pallet {
 		/// Set the validator reward destination
 		ValidatorRewardDstSet {
 			candidate: T::AccountId,
-			old: RewardDestination,
-			new: RewardDestination,
+			old: RewardDestination<T::AccountId>,
+			new: RewardDestination<T::AccountId>,
 		},
 		/// Set the nominator reward destination
 		NominatorRewardDstSet {
 			nominator: T::AccountId,
-			old: RewardDestination,
-			new: RewardDestination,
+			old: RewardDestination<T::AccountId>,
+			new: RewardDestination<T::AccountId>,
+		},
+		/// Validator has been jailed for an offence until the given round. Its top nominators are
+		/// notified by `NominationJailed` as well.
+		Jailed {
+			candidate: T::AccountId,
+			until_round: RoundIndex,
+			jail_count: u32,
+			nominator_count: u32,
+		},
+		/// Nomination moved from one candidate to another.
+		NominationRedelegated {
+			nominator: T::AccountId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// The identity of the candidate has been registered or updated.
+		CandidateIdentitySet { candidate: T::AccountId, deposit: BalanceOf<T> },
+		/// The identity of the candidate has been cleared and its deposit returned.
+		CandidateIdentityCleared { candidate: T::AccountId, deposit: BalanceOf<T> },
+		/// Jailed validator has been released and rejoined the set of validator candidates.
+		Unjailed { candidate: T::AccountId },
+		/// The candidate of the nomination has been jailed until the given round. The nominator
+		/// may want to move its stake to another candidate.
+		NominationJailed {
+			nominator: T::AccountId,
+			candidate: T::AccountId,
+			until_round: RoundIndex,
 		},
-		/// Kick out validator
-		KickedOut(T::AccountId),
 	}
 
 	#[pallet::storage]
@@ -474,6 +590,16 @@ pub mod pallet {
 	/// Maximum commission rate for basic validators
 	pub type MaxBasicValidatorCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
+	#[pallet::storage]
+	/// Maximum commission rate increase applied to a validator per round. Larger increases are
+	/// stepped across multiple rounds. A zero value disables the limit.
+	pub type MaxCommissionIncreasePerRound<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+	#[pallet::storage]
+	/// The activation of the relayer score based reward weighting. If active, the rewards of
+	/// full validators are weighted by the performance score of their relayer for the round.
+	pub type IsRelayerScoreRewardActive<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::storage]
 	/// The maximum node candidates selected every round
 	pub type MaxTotalSelected<T: Config> = StorageValue<_, u32, ValueQuery>;
@@ -637,6 +763,12 @@ pub mod pallet {
 	/// Total points awarded to validators for block production in the round
 	pub type Points<T: Config> = StorageMap<_, Twox64Concat, RoundIndex, RewardPoint, ValueQuery>;
 
+	#[pallet::storage]
+	/// Total relayer score weighted points of the round. Only set for the rounds whose payout has
+	/// been prepared with the relayer score reward weighting active.
+	pub type WeightedPoints<T: Config> =
+		StorageMap<_, Twox64Concat, RoundIndex, RewardPoint, OptionQuery>;
+
 	#[pallet::storage]
 	/// Points for each validator per round
 	pub type AwardedPts<T: Config> = StorageDoubleMap<
@@ -653,6 +785,69 @@ pub mod pallet {
 	/// The amount of awarded tokens to validators and nominators since genesis
 	pub type AwardedTokens<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	#[pallet::storage]
+	/// The round of the latest redelegation of each nominator
+	pub type LastRedelegation<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, RoundIndex, OptionQuery>;
+
+	#[pallet::storage]
+	/// The registered identity of each candidate (controller account)
+	pub type CandidateIdentities<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, CandidateIdentity<BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	/// The number of times each candidate (controller account) has been jailed and the round that
+	/// its latest jail ends. The count decays by one for every `JailCountDecay` rounds after that.
+	pub type JailCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (u32, RoundIndex), ValueQuery>;
+
+	#[pallet::storage]
+	/// The recent round rewards paid to each validator and nominator, oldest first
+	pub type RewardHistory<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<RewardEntry<T::AccountId, BalanceOf<T>>, T::MaxRewardHistory>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// The raw storage key of the last `RewardHistory` entry visited by the stale history pruning.
+	/// The pruning resumes after this key in the next block. It is empty when a pass has just been
+	/// started at the beginning of a round, and removed once the pass is over.
+	pub type RewardHistoryPruneCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<128>>, OptionQuery>;
+
+	#[pallet::storage]
+	/// The selected validators of the given round whose `AtStake` snapshot is not taken yet. The
+	/// snapshots are taken over the first blocks of the round.
+	pub type PendingValidatorSnapshots<T: Config> = StorageValue<
+		_,
+		(RoundIndex, BoundedVec<T::AccountId, ConstU32<MAX_AUTHORITIES>>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// The next phase of the round transition in progress
+	pub type RoundTransition<T: Config> = StorageValue<_, RoundTransitionPhase, OptionQuery>;
+
+	#[pallet::storage]
+	/// The full and basic validators elected for the next round ahead of the round boundary
+	pub type ElectedCandidates<T: Config> = StorageValue<
+		_,
+		(
+			BoundedVec<T::AccountId, ConstU32<MAX_AUTHORITIES>>,
+			BoundedVec<T::AccountId, ConstU32<MAX_AUTHORITIES>>,
+		),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// The validators of the previous round whose `per round` states are reset after the round
+	/// boundary
+	pub type PendingCandidateResets<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, ConstU32<{ 2 * MAX_AUTHORITIES }>>, ValueQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
@@ -667,13 +862,31 @@ pub mod pallet {
 
 				// Handle the delayed payouts for the previous round
 				weight += Self::handle_delayed_payouts(round.current_round_index);
+
+				// Take the pending validator snapshots of the current round
+				weight += Self::handle_pending_validator_snapshots(
+					T::MaxValidatorSnapshotsPerBlock::get(),
+				);
+
+				// Prune the stale reward history of accounts that no longer earn rewards
+				weight += Self::handle_reward_history_pruning(
+					round.current_round_index,
+					T::MaxRewardHistoryPrunesPerBlock::get(),
+				);
 			});
 
+			// Run the next phase of the round transition
+			weight += Self::handle_round_transition(n);
+
 			weight
 		}
 
 		fn on_runtime_upgrade() -> Weight {
-			migrations::v5::MigrateToV5::<T>::on_runtime_upgrade()
+			migrations::v6::MigrateToV6::<T>::on_runtime_upgrade()
+				.saturating_add(migrations::v7::MigrateToV7::<T>::on_runtime_upgrade())
+				.saturating_add(migrations::v8::MigrateToV8::<T>::on_runtime_upgrade())
+				.saturating_add(migrations::v9::MigrateToV9::<T>::on_runtime_upgrade())
+				.saturating_add(Self::refresh_round_inflation())
 		}
 	}
 
@@ -703,6 +916,7 @@ pub mod pallet {
 			// Set maximum validator commission to maximum config
 			<MaxFullValidatorCommission<T>>::put(T::MaxFullValidatorCommission::get());
 			<MaxBasicValidatorCommission<T>>::put(T::MaxBasicValidatorCommission::get());
+			<MaxCommissionIncreasePerRound<T>>::put(T::DefaultMaxCommissionIncreasePerRound::get());
 			let mut candidate_count = 0u32;
 			// Initialize the candidates
 			for &(ref stash, ref controller, ref relayer, balance) in &self.candidates {
@@ -771,6 +985,9 @@ pub mod pallet {
 			let (full_validators, basic_validators) = <Pallet<T>>::compute_top_candidates();
 			let (v_count, _, total_staked) =
 				<Pallet<T>>::update_top_candidates(1u32, full_validators, basic_validators);
+			// Take the snapshots of the initial validators
+			<Pallet<T>>::queue_validator_snapshots(1u32);
+			<Pallet<T>>::handle_pending_validator_snapshots(MAX_AUTHORITIES);
 			// Set majority to initial value
 			let initial_majority: u32 = <Pallet<T>>::compute_majority();
 			<Majority<T>>::put(initial_majority);
@@ -843,18 +1060,29 @@ pub mod pallet {
 
 		#[pallet::call_index(1)]
 		#[pallet::weight(<T as Config>::WeightInfo::set_inflation())]
-		/// Set the annual inflation rate to derive per-round inflation
+		/// Set the annual inflation rate to derive per-round inflation, and the shares of the round
+		/// issuance that are paid to the treasury and burned
 		pub fn set_inflation(
 			origin: OriginFor<T>,
 			schedule: Range<Perbill>,
+			treasury: Perbill,
+			burn: Perbill,
 		) -> DispatchResultWithPostInfo {
 			T::MonetaryGovernanceOrigin::ensure_origin(origin)?;
 			ensure!(schedule.is_valid(), Error::<T>::InvalidSchedule);
 			ensure!(schedule.min >= Perbill::from_percent(1), Error::<T>::InvalidSchedule);
+			ensure!(
+				InflationInfo::<BalanceOf<T>>::is_valid_shares(treasury, burn),
+				Error::<T>::InvalidIssuanceShares
+			);
 			let mut config = <InflationConfig<T>>::get();
-			ensure!(config.annual != schedule, Error::<T>::NoWritingSameValue);
+			ensure!(
+				config.annual != schedule || config.treasury != treasury || config.burn != burn,
+				Error::<T>::NoWritingSameValue
+			);
 			config.annual = schedule;
 			config.set_round_from_annual::<T>(schedule);
+			config.set_shares(treasury, burn);
 			<InflationConfig<T>>::put(&config);
 			Self::deposit_event(Event::InflationSet {
 				annual_min: config.annual.min,
@@ -863,6 +1091,8 @@ pub mod pallet {
 				round_min: config.round.min,
 				round_ideal: config.round.ideal,
 				round_max: config.round.max,
+				treasury: config.treasury,
+				burn: config.burn,
 			});
 			Ok(().into())
 		}
@@ -1012,6 +1242,8 @@ pub mod pallet {
 		#[pallet::weight(<T as Config>::WeightInfo::set_validator_commission())]
 		/// Set the commission rate of the given validator
 		/// - origin should be the controller account
+		/// - increases above `MaxCommissionIncreasePerRound` are applied stepwise, one step per
+		///   round
 		pub fn set_validator_commission(
 			origin: OriginFor<T>,
 			new: Perbill,
@@ -1031,6 +1263,16 @@ pub mod pallet {
 			);
 			ensure!(!state.is_leaving(), Error::<T>::CandidateAlreadyLeaving);
 			Self::add_to_commission_sets(&controller, old, new)?;
+			let steps = Self::commission_increase_steps(old, new);
+			if steps > 1 {
+				let now = Self::delayed_request_round();
+				Self::deposit_event(Event::ValidatorCommissionIncreaseScheduled {
+					candidate: controller.clone(),
+					old,
+					new,
+					final_round: now.saturating_add(steps),
+				});
+			}
 			Self::deposit_event(Event::ValidatorCommissionSet { candidate: controller, old, new });
 			Ok(().into())
 		}
@@ -1049,10 +1291,15 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(8)]
-		#[pallet::weight(<T as Config>::WeightInfo::set_validator_tier())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::set_validator_tier()
+				// clears the relayer endpoint of the identity on a downgrade
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
 		/// Modify validator candidate tier. The actual state reflection will apply at the next
 		/// round
 		/// - origin should be the stash account
+		/// - downgrading to a basic validator clears the relayer endpoint of its identity
 		pub fn set_validator_tier(
 			origin: OriginFor<T>,
 			more: BalanceOf<T>,
@@ -1083,6 +1330,11 @@ pub mod pallet {
 				);
 				state.bond_more::<T>(stash.clone(), controller.clone(), more)?;
 				T::RelayManager::leave_relayers(&controller);
+				<CandidateIdentities<T>>::mutate(&controller, |identity| {
+					if let Some(identity) = identity {
+						identity.relayer_endpoint = None;
+					}
+				});
 			}
 			state.tier = new;
 			state.reset_commission::<T>(&controller);
@@ -1119,7 +1371,7 @@ pub mod pallet {
 			round.round_length = new;
 			// update per-round inflation given new rounds per year
 			let mut inflation_config = <InflationConfig<T>>::get();
-			inflation_config.reset_round(new);
+			inflation_config.reset_round::<T>(new);
 			<Round<T>>::put(round);
 			<InflationConfig<T>>::put(&inflation_config);
 			Self::deposit_event(Event::BlocksPerRoundSet {
@@ -1168,8 +1420,7 @@ pub mod pallet {
 			ensure!(!<CandidateInfo<T>>::contains_key(&controller), Error::<T>::AlreadyPaired);
 
 			// check if the controller account is already scheduled for update
-			let round = Round::<T>::get();
-			let controller_sets = DelayedControllerSets::<T>::get(round.current_round_index);
+			let controller_sets = DelayedControllerSets::<T>::get(Self::delayed_request_round());
 			ensure!(
 				!controller_sets.into_iter().any(|c| c.new == controller),
 				Error::<T>::AlreadyControllerSetRequested
@@ -1243,8 +1494,9 @@ pub mod pallet {
 				candidate_count >= candidates.len() as u32,
 				Error::<T>::TooLowCandidateCountToLeaveCandidates,
 			);
+			// jailed candidates have already been removed from the pool
 			ensure!(
-				Self::remove_from_candidate_pool(&controller),
+				state.is_jailed() || Self::remove_from_candidate_pool(&controller),
 				Error::<T>::CannotLeaveIfOffline,
 			);
 			let (now, when) = state.schedule_leave::<T>()?;
@@ -1260,6 +1512,8 @@ pub mod pallet {
 		#[pallet::call_index(13)]
 		#[pallet::weight(
 			<T as Config>::WeightInfo::execute_leave_candidates(*candidate_nomination_count)
+				// checks for a pending slash and freezes the pending validator snapshot
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2))
 		)]
 		/// Execute leave candidates request
 		/// - origin should be the stash account
@@ -1275,6 +1529,12 @@ pub mod pallet {
 				Error::<T>::TooLowCandidateNominationCountToLeaveCandidates
 			);
 			state.can_leave::<T>()?;
+			// the stake must stay reserved until the deferred slash is applied
+			ensure!(
+				!T::OffenceHandler::has_unapplied_slash(&controller),
+				Error::<T>::CannotLeaveWithUnappliedSlash
+			);
+			Self::freeze_validator_snapshot(&controller);
 			let return_stake =
 				|bond: Bond<T::AccountId, BalanceOf<T>>,
 				 mut nominator: Nominator<T::AccountId, BalanceOf<T>>| {
@@ -1313,10 +1573,14 @@ pub mod pallet {
 			total_backing += bottom_nominations.total;
 			// return stake to stash account
 			T::Currency::unreserve(&stash, state.bond);
+			if let Some(identity) = <CandidateIdentities<T>>::take(&controller) {
+				T::Currency::unreserve(&stash, identity.deposit);
+			}
 			<CandidateInfo<T>>::remove(&controller);
 			<BondedStash<T>>::remove(&stash);
 			<TopNominations<T>>::remove(&controller);
 			<BottomNominations<T>>::remove(&controller);
+			<JailCount<T>>::remove(&controller);
 			let new_total_staked = <Total<T>>::get().saturating_sub(total_backing);
 			<Total<T>>::put(new_total_staked);
 
@@ -1332,7 +1596,11 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(14)]
-		#[pallet::weight(<T as Config>::WeightInfo::cancel_leave_candidates(*candidate_count))]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::cancel_leave_candidates(*candidate_count)
+				// reads the jail count of the candidate to return it to its jail
+				.saturating_add(T::DbWeight::get().reads(1))
+		)]
 		/// Cancel open request to leave candidates
 		/// - only callable by validator account
 		/// - result upon successful call is the candidate is active in the candidate pool
@@ -1344,6 +1612,14 @@ pub mod pallet {
 			let controller = ensure_signed(origin)?;
 			let mut state = <CandidateInfo<T>>::get(&controller).ok_or(Error::<T>::CandidateDNE)?;
 			ensure!(state.is_leaving(), Error::<T>::CandidateNotLeaving);
+			// a candidate that left during its jail returns to the jail until it ends
+			let (_, jailed_until) = <JailCount<T>>::get(&controller);
+			if <Round<T>>::get().current_round_index < jailed_until {
+				state.jail(jailed_until);
+				<CandidateInfo<T>>::insert(&controller, state);
+				Self::deposit_event(Event::CancelledCandidateExit { candidate: controller });
+				return Ok(().into());
+			}
 			state.go_online();
 			let mut candidates = <CandidatePool<T>>::get();
 			ensure!(
@@ -1402,13 +1678,13 @@ pub mod pallet {
 		/// - origin should be the controller account
 		pub fn set_candidate_reward_dst(
 			origin: OriginFor<T>,
-			new_reward_dst: RewardDestination,
+			new_reward_dst: RewardDestination<T::AccountId>,
 		) -> DispatchResultWithPostInfo {
 			let controller = ensure_signed(origin)?;
 			let mut state = <CandidateInfo<T>>::get(&controller).ok_or(Error::<T>::CandidateDNE)?;
-			let old_reward_dst = state.reward_dst;
+			let old_reward_dst = state.reward_dst.clone();
 			ensure!(old_reward_dst != new_reward_dst, Error::<T>::NoWritingSameValue);
-			state.set_reward_dst(new_reward_dst);
+			state.set_reward_dst(new_reward_dst.clone());
 			<CandidateInfo<T>>::insert(&controller, state);
 			Self::deposit_event(Event::ValidatorRewardDstSet {
 				candidate: controller,
@@ -1423,13 +1699,13 @@ pub mod pallet {
 		/// Set the nominator reward destination
 		pub fn set_nominator_reward_dst(
 			origin: OriginFor<T>,
-			new_reward_dst: RewardDestination,
+			new_reward_dst: RewardDestination<T::AccountId>,
 		) -> DispatchResultWithPostInfo {
 			let nominator = ensure_signed(origin)?;
 			let mut state = <NominatorState<T>>::get(&nominator).ok_or(Error::<T>::NominatorDNE)?;
-			let old_reward_dst = state.reward_dst;
+			let old_reward_dst = state.reward_dst.clone();
 			ensure!(old_reward_dst != new_reward_dst, Error::<T>::NoWritingSameValue);
-			state.set_reward_dst(new_reward_dst);
+			state.set_reward_dst(new_reward_dst.clone());
 			<NominatorState<T>>::insert(&nominator, state);
 			Self::deposit_event(Event::NominatorRewardDstSet {
 				nominator,
@@ -1489,6 +1765,7 @@ pub mod pallet {
 		#[pallet::call_index(20)]
 		#[pallet::weight(<T as Config>::WeightInfo::go_online())]
 		/// Rejoin the set of validator candidates if previously been kicked out or went offline
+		/// - jailed candidates must `unjail` instead
 		/// - state changed to `Active`
 		/// - origin should be the controller account
 		pub fn go_online(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
@@ -1498,6 +1775,7 @@ pub mod pallet {
 			// Check for safety
 			ensure!(!state.is_active(), Error::<T>::AlreadyActive);
 			ensure!(!state.is_leaving(), Error::<T>::CannotGoOnlineIfLeaving);
+			ensure!(!state.is_jailed(), Error::<T>::CandidateJailed);
 			state.go_online();
 			let mut candidates = <CandidatePool<T>>::get();
 			candidates
@@ -1510,7 +1788,11 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(21)]
-		#[pallet::weight(<T as Config>::WeightInfo::candidate_bond_more())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::candidate_bond_more()
+				// freezes the pending validator snapshot before the stake changes
+				.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		)]
 		/// Increase validator candidate self bond by `more`
 		/// - origin should be the stash account
 		pub fn candidate_bond_more(
@@ -1550,7 +1832,11 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(23)]
-		#[pallet::weight(<T as Config>::WeightInfo::execute_candidate_bond_less())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::execute_candidate_bond_less()
+				// freezes the pending validator snapshot before the stake changes
+				.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		)]
 		/// Execute pending request to adjust the validator candidate self bond
 		/// - origin should be the stash account
 		pub fn execute_candidate_bond_less(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
@@ -1580,6 +1866,8 @@ pub mod pallet {
 				*candidate_nomination_count,
 				*nomination_count
 			)
+			// freezes the pending validator snapshot before the stake changes
+			.saturating_add(T::DbWeight::get().reads_writes(1, 2))
 		)]
 		/// If caller is not a nominator and not a validator, then join the set of nominators
 		/// If caller is a nominator, then makes nomination to change their nomination state
@@ -1664,7 +1952,13 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(27)]
-		#[pallet::weight(<T as Config>::WeightInfo::execute_leave_nominators(*nomination_count))]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::execute_leave_nominators(*nomination_count)
+				// freezes the pending validator snapshot of each revoked nomination
+				.saturating_add(
+					T::DbWeight::get().reads_writes(1, 2).saturating_mul(*nomination_count as u64)
+				)
+		)]
 		/// Execute the right to exit the set of nominators and revoke all ongoing nominations.
 		pub fn execute_leave_nominators(
 			origin: OriginFor<T>,
@@ -1728,7 +2022,11 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(30)]
-		#[pallet::weight(<T as Config>::WeightInfo::nominator_bond_more())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::nominator_bond_more()
+				// freezes the pending validator snapshot before the stake changes
+				.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		)]
 		/// Bond more for nominators wrt a specific validator candidate.
 		pub fn nominator_bond_more(
 			origin: OriginFor<T>,
@@ -1764,7 +2062,11 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(32)]
-		#[pallet::weight(<T as Config>::WeightInfo::execute_nominator_bond_less())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::execute_nominator_bond_less()
+				// freezes the pending validator snapshot before the stake changes
+				.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		)]
 		/// Execute pending request to change an existing nomination
 		pub fn execute_nomination_request(
 			origin: OriginFor<T>,
@@ -1793,5 +2095,212 @@ pub mod pallet {
 			});
 			Ok(().into())
 		}
+
+		#[pallet::call_index(34)]
+		#[pallet::weight(<T as Config>::WeightInfo::redelegate(
+			Pallet::<T>::max_candidate_nominations(),
+			Pallet::<T>::max_candidate_nominations(),
+		))]
+		/// Move the given amount of an existing nomination from the `from` candidate to the `to`
+		/// candidate without waiting for the revocation delay. The stake remains bonded.
+		/// - a nominator can redelegate once per `RedelegationDelay` rounds
+		/// - neither nomination can have a pending request
+		/// - `from` cannot be jailed or have a deferred slash waiting to be applied
+		/// - the nomination of `from` is removed if the entire amount is moved
+		/// - the weight is charged for full candidates and refunded by their nomination counts
+		pub fn redelegate(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let nominator = ensure_signed(origin)?;
+			ensure!(from != to, Error::<T>::CannotRedelegateToSameCandidate);
+
+			let now = <Round<T>>::get().current_round_index;
+			if let Some(last) = <LastRedelegation<T>>::get(&nominator) {
+				ensure!(
+					now >= last.saturating_add(T::RedelegationDelay::get()),
+					Error::<T>::RedelegationTooFrequent
+				);
+			}
+
+			let mut state = <NominatorState<T>>::get(&nominator).ok_or(Error::<T>::NominatorDNE)?;
+			ensure!(state.is_active(), Error::<T>::CannotNominateIfLeaving);
+			// a pending request of either candidate would be executed on the redelegated stake
+			ensure!(
+				!state.requests.requests.contains_key(&from)
+					&& !state.requests.requests.contains_key(&to),
+				Error::<T>::PendingNominationRequestAlreadyExists
+			);
+			let bond = *state.nominations.get(&from).ok_or(Error::<T>::NominationDNE)?;
+			ensure!(amount <= bond, Error::<T>::RedelegationExceedsNomination);
+			ensure!(amount >= T::MinNomination::get(), Error::<T>::NominationBelowMin);
+			let remaining = bond - amount;
+			ensure!(
+				remaining.is_zero() || remaining >= T::MinNomination::get(),
+				Error::<T>::NominationBelowMin
+			);
+			if !state.nominations.contains_key(&to) && !remaining.is_zero() {
+				ensure!(
+					(state.nominations.len() as u32) < T::MaxNominationsPerNominator::get(),
+					Error::<T>::ExceedMaxNominationsPerNominator
+				);
+			}
+
+			let mut from_state = <CandidateInfo<T>>::get(&from).ok_or(Error::<T>::CandidateDNE)?;
+			// the moved stake would escape the slash of `from` if it was redelegated beforehand
+			ensure!(!from_state.is_jailed(), Error::<T>::CandidateJailed);
+			ensure!(
+				!T::OffenceHandler::has_unapplied_slash(&from),
+				Error::<T>::CannotRedelegateWithUnappliedSlash
+			);
+			let mut to_state = <CandidateInfo<T>>::get(&to).ok_or(Error::<T>::CandidateDNE)?;
+			let actual_weight = <T as Config>::WeightInfo::redelegate(
+				from_state.nomination_count,
+				to_state.nomination_count,
+			);
+			let to_bond = state.redelegate::<T>(&from, to.clone(), amount)?;
+
+			// move the stake out of the previous candidate
+			if remaining.is_zero() {
+				from_state.rm_nomination_if_exists::<T>(&from, nominator.clone(), bond)?;
+			} else {
+				from_state.decrease_nomination::<T>(&from, nominator.clone(), bond, amount)?;
+			}
+			<CandidateInfo<T>>::insert(&from, from_state);
+
+			// move the stake into the new candidate
+			match to_bond {
+				Some(before) => {
+					to_state.increase_nomination::<T>(&to, nominator.clone(), before, amount)?;
+				},
+				None => {
+					let (_, less_total_staked) = to_state
+						.add_nomination::<T>(&to, Bond { owner: nominator.clone(), amount })?;
+					// only is_some if kicked the lowest bottom as a consequence of this nomination
+					if let Some(less) = less_total_staked {
+						<Total<T>>::mutate(|total_locked| {
+							*total_locked = total_locked.saturating_sub(less);
+						});
+					}
+				},
+			}
+			<CandidateInfo<T>>::insert(&to, to_state);
+
+			<NominatorState<T>>::insert(&nominator, state);
+			<LastRedelegation<T>>::insert(&nominator, now);
+			Self::deposit_event(Event::NominationRedelegated { nominator, from, to, amount });
+			Ok(Some(actual_weight).into())
+		}
+
+		#[pallet::call_index(35)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_max_commission_increase_per_round())]
+		/// Set the maximum commission rate increase applied to a validator per round. A zero
+		/// value disables the limit.
+		pub fn set_max_commission_increase_per_round(
+			origin: OriginFor<T>,
+			new: Perbill,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let old = <MaxCommissionIncreasePerRound<T>>::get();
+			ensure!(old != new, Error::<T>::NoWritingSameValue);
+			<MaxCommissionIncreasePerRound<T>>::put(new);
+			Self::deposit_event(Event::MaxCommissionIncreasePerRoundSet { old, new });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(36)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_candidate_identity())]
+		/// Register or update the identity of the candidate. `IdentityDeposit` is reserved from
+		/// the stash account on the first registration.
+		/// - origin should be the controller account
+		/// - a relayer endpoint can only be registered by a full node candidate
+		pub fn set_candidate_identity(
+			origin: OriginFor<T>,
+			name: IdentityField,
+			website: IdentityField,
+			contact: IdentityField,
+			logo_hash: [u8; 32],
+			relayer_endpoint: Option<IdentityField>,
+		) -> DispatchResultWithPostInfo {
+			let controller = ensure_signed(origin)?;
+			let state = <CandidateInfo<T>>::get(&controller).ok_or(Error::<T>::CandidateDNE)?;
+			ensure!(
+				relayer_endpoint.is_none() || state.tier == TierType::Full,
+				Error::<T>::RelayerEndpointOnlyForFullTier
+			);
+
+			let old_deposit = <CandidateIdentities<T>>::get(&controller)
+				.map(|identity| identity.deposit)
+				.unwrap_or_default();
+			let deposit = T::IdentityDeposit::get();
+			if deposit > old_deposit {
+				T::Currency::reserve(&state.stash, deposit - old_deposit)?;
+			} else {
+				T::Currency::unreserve(&state.stash, old_deposit - deposit);
+			}
+
+			<CandidateIdentities<T>>::insert(
+				&controller,
+				CandidateIdentity { name, website, contact, logo_hash, relayer_endpoint, deposit },
+			);
+			Self::deposit_event(Event::CandidateIdentitySet { candidate: controller, deposit });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(37)]
+		#[pallet::weight(<T as Config>::WeightInfo::clear_candidate_identity())]
+		/// Clear the identity of the candidate and return its deposit to the stash account.
+		/// - origin should be the controller account
+		pub fn clear_candidate_identity(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let controller = ensure_signed(origin)?;
+			let state = <CandidateInfo<T>>::get(&controller).ok_or(Error::<T>::CandidateDNE)?;
+			let identity = <CandidateIdentities<T>>::take(&controller)
+				.ok_or(Error::<T>::CandidateIdentityDNE)?;
+			T::Currency::unreserve(&state.stash, identity.deposit);
+			Self::deposit_event(Event::CandidateIdentityCleared {
+				candidate: controller,
+				deposit: identity.deposit,
+			});
+			Ok(().into())
+		}
+
+		#[pallet::call_index(38)]
+		#[pallet::weight(<T as Config>::WeightInfo::unjail())]
+		/// Rejoin the set of validator candidates after the jail has ended
+		/// - state changed to `Active`
+		/// - origin should be the controller account
+		pub fn unjail(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let controller = ensure_signed(origin)?;
+			let mut state = <CandidateInfo<T>>::get(&controller).ok_or(Error::<T>::CandidateDNE)?;
+			state.can_unjail::<T>()?;
+			state.go_online();
+			let mut candidates = <CandidatePool<T>>::get();
+			candidates
+				.try_insert(controller.clone(), state.voting_power)
+				.map_err(|_| Error::<T>::TooManyCandidates)?;
+			<CandidatePool<T>>::put(candidates);
+			<CandidateInfo<T>>::insert(&controller, state);
+			Self::deposit_event(Event::Unjailed { candidate: controller });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(39)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_relayer_score_reward_activation())]
+		/// Set the activation of the relayer score based reward weighting for full validators
+		pub fn set_relayer_score_reward_activation(
+			origin: OriginFor<T>,
+			is_active: bool,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				is_active != <IsRelayerScoreRewardActive<T>>::get(),
+				Error::<T>::NoWritingSameValue
+			);
+			<IsRelayerScoreRewardActive<T>>::put(is_active);
+			Self::deposit_event(Event::RelayerScoreRewardActivationSet { is_active });
+			Ok(().into())
+		}
 	}
 }
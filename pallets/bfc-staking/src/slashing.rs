@@ -0,0 +1,68 @@
+//! Helpers to compute the exposure of nominators to the offences of a validator.
+
+use crate::Bond;
+
+use sp_runtime::traits::Zero;
+use sp_std::vec::Vec;
+
+/// Returns the slashable exposure of each nominator that backed the validator in the given
+/// snapshot. Only the top nominations of the offence round are captured by the snapshot, so the
+/// bottom nominations and the nominations made afterwards are not exposed. Each exposure is
+/// bounded by the current nomination, and nominators that no longer nominate the validator are
+/// not exposed at all.
+pub fn nominator_exposures<AccountId, Balance>(
+	snapshot: &[Bond<AccountId, Balance>],
+	current: &[Bond<AccountId, Balance>],
+) -> Vec<(AccountId, Balance)>
+where
+	AccountId: PartialEq + Clone,
+	Balance: Copy + Ord + Zero,
+{
+	snapshot
+		.iter()
+		.filter_map(|backed| {
+			let amount = current.iter().find(|n| n.owner == backed.owner)?.amount;
+			let exposure = backed.amount.min(amount);
+			(!exposure.is_zero()).then(|| (backed.owner.clone(), exposure))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn bonds(bonds: Vec<(u64, u128)>) -> Vec<Bond<u64, u128>> {
+		bonds.into_iter().map(|(owner, amount)| Bond { owner, amount }).collect()
+	}
+
+	#[test]
+	fn exposure_is_bounded_by_the_snapshot() {
+		let snapshot = bonds(vec![(1, 100), (2, 50)]);
+		let current = bonds(vec![(1, 150), (2, 50)]);
+		assert_eq!(nominator_exposures(&snapshot, &current), vec![(1, 100), (2, 50)]);
+	}
+
+	#[test]
+	fn exposure_is_bounded_by_the_current_nomination() {
+		let snapshot = bonds(vec![(1, 100), (2, 50)]);
+		let current = bonds(vec![(1, 40), (2, 0)]);
+		assert_eq!(nominator_exposures(&snapshot, &current), vec![(1, 40)]);
+	}
+
+	#[test]
+	fn revoked_nominators_are_not_exposed() {
+		let snapshot = bonds(vec![(1, 100), (2, 50)]);
+		let current = bonds(vec![(2, 50)]);
+		assert_eq!(nominator_exposures(&snapshot, &current), vec![(2, 50)]);
+	}
+
+	#[test]
+	fn nominators_missing_from_the_snapshot_are_not_exposed() {
+		// nominator 3 is a bottom nomination (or nominated after the offence round)
+		let snapshot = bonds(vec![(1, 100)]);
+		let current = bonds(vec![(1, 100), (3, 500)]);
+		assert_eq!(nominator_exposures(&snapshot, &current), vec![(1, 100)]);
+		assert!(nominator_exposures(&[], &current).is_empty());
+	}
+}
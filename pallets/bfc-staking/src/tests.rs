@@ -0,0 +1,1003 @@
+use crate::{
+	migrations, mock::*, AtStake, CandidateIdentities, CandidateIdentity, CandidateInfo,
+	DelayedPayouts, ElectedCandidates, Error, Event, IdentityField, InflationConfig,
+	IsRelayerScoreRewardActive, JailCount, MaxCommissionIncreasePerRound,
+	PendingValidatorSnapshots, RewardDestination, RewardEntry, RewardHistory,
+	RewardHistoryPruneCursor, Round, RoundTransition, RoundTransitionPhase, SelectedCandidates,
+	Total, ValidatorIdentification, ValidatorSnapshotOf, WeightInfo, WeightedPoints,
+};
+use bp_core::{AccountId, Balance};
+use bp_staking::{traits::NominationManager, TierType};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchResultWithPostInfo,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	BoundedVec,
+};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{
+	traits::{Convert, One, Zero},
+	FixedU128, Perbill,
+};
+use sp_staking::offence::OffenceDetails;
+
+/// Three full candidates where the first two are nominated
+fn ext() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_candidates(vec![(1, 2_000), (2, 1_500), (3, 1_000)])
+		.with_nominations(vec![(1, 1, 500), (2, 1, 300), (3, 2, 200)])
+		.build()
+}
+
+/// The top nominations of the given candidate, largest first
+fn top_nominations(candidate: u64) -> Vec<(AccountId, Balance)> {
+	let mut nominations: Vec<_> = BfcStaking::candidate_state(&controller(candidate))
+		.unwrap()
+		.top_nominations
+		.into_iter()
+		.map(|bond| (bond.owner, bond.amount))
+		.collect();
+	nominations.sort_by(|a, b| b.1.cmp(&a.1));
+	nominations
+}
+
+/// The bottom nominations of the given candidate, largest first
+fn bottom_nominations(candidate: u64) -> Vec<(AccountId, Balance)> {
+	let mut nominations: Vec<_> = BfcStaking::candidate_state(&controller(candidate))
+		.unwrap()
+		.bottom_nominations
+		.into_iter()
+		.map(|bond| (bond.owner, bond.amount))
+		.collect();
+	nominations.sort_by(|a, b| b.1.cmp(&a.1));
+	nominations
+}
+
+fn nominate(n: u64, c: u64, amount: Balance) {
+	assert_ok!(BfcStaking::nominate(
+		RuntimeOrigin::signed(nominator(n)),
+		controller(c),
+		amount,
+		10,
+		10
+	));
+}
+
+#[test]
+fn candidate_state_returns_the_bond_and_nominations() {
+	ext().execute_with(|| {
+		let state = BfcStaking::candidate_state(&controller(1)).unwrap();
+		assert_eq!(state.controller, controller(1));
+		assert_eq!(state.stash, stash(1));
+		assert_eq!(state.bond, 2_000);
+		assert_eq!(state.voting_power, 2_800);
+		assert_eq!(state.nomination_count, 2);
+		assert_eq!(state.top_nominations.len(), 2);
+		assert!(state.bottom_nominations.is_empty());
+		assert_eq!(state.tier, TierType::Full);
+		assert!(state.is_selected);
+		assert!(state.identity.is_none());
+
+		assert!(BfcStaking::candidate_state(&controller(4)).is_none());
+	});
+}
+
+#[test]
+fn nominator_info_returns_the_nominations() {
+	ext().execute_with(|| {
+		let info = BfcStaking::nominator_info(&nominator(1)).unwrap();
+		assert_eq!(info.id, nominator(1));
+		assert_eq!(info.total, 500);
+		assert_eq!(info.nominations.len(), 1);
+		assert_eq!(info.nominations[0].owner, controller(1));
+		assert_eq!(info.nominations[0].amount, 500);
+		assert!(info.requests.is_empty());
+		assert_eq!(info.reward_dst, RewardDestination::Staked);
+
+		assert!(BfcStaking::nominator_info(&nominator(4)).is_none());
+	});
+}
+
+#[test]
+fn selected_candidates_by_tier_filters_the_tier() {
+	ext().execute_with(|| {
+		let mut full = BfcStaking::selected_candidates_by_tier(TierType::Full);
+		full.sort();
+		assert_eq!(full, vec![controller(1), controller(2), controller(3)]);
+		assert!(BfcStaking::selected_candidates_by_tier(TierType::Basic).is_empty());
+		assert_eq!(BfcStaking::selected_candidates_by_tier(TierType::All).len(), 3);
+	});
+}
+
+#[test]
+fn estimated_apr_is_zero_for_unknown_candidates() {
+	ext().execute_with(|| {
+		assert!(BfcStaking::estimated_apr(None) > FixedU128::zero());
+		assert!(BfcStaking::estimated_apr(Some(controller(1))) > FixedU128::zero());
+		assert_eq!(BfcStaking::estimated_apr(Some(controller(4))), FixedU128::zero());
+	});
+}
+
+#[test]
+fn estimated_apr_is_not_capped() {
+	ext().execute_with(|| {
+		// a short round with a 1% round issuance yields far more than the stake per year
+		assert!(BfcStaking::estimated_apr(None) > FixedU128::one());
+
+		// nothing staked, nothing earned
+		Total::<Test>::kill();
+		assert_eq!(BfcStaking::estimated_apr(None), FixedU128::zero());
+	});
+}
+
+#[test]
+fn pending_rewards_match_the_paid_reward_history() {
+	ext().execute_with(|| {
+		BfcStaking::note_author(&controller(1));
+		// the round is not prepared for payouts yet
+		assert_eq!(BfcStaking::pending_rewards(&controller(1)), 0);
+		assert_eq!(BfcStaking::pending_rewards(&nominator(1)), 0);
+
+		// round 1 is prepared after the boundary and paid out in the next blocks of round 2
+		roll_to_prepared_payouts(2);
+		let validator_pending = BfcStaking::pending_rewards(&controller(1));
+		let nominator_pending = BfcStaking::pending_rewards(&nominator(1));
+		assert!(validator_pending > 0);
+		assert!(nominator_pending > 0);
+		assert_eq!(BfcStaking::pending_rewards(&nominator(3)), 0);
+
+		roll_to(System::block_number() + 2);
+
+		assert_eq!(BfcStaking::pending_rewards(&controller(1)), 0);
+		assert_eq!(BfcStaking::pending_rewards(&nominator(1)), 0);
+
+		let history = BfcStaking::reward_history(&controller(1), 1, 1);
+		assert_eq!(history.len(), 1);
+		assert_eq!(history[0].validator, controller(1));
+		assert_eq!(history[0].amount, validator_pending);
+
+		let history = BfcStaking::reward_history(&nominator(1), 1, 2);
+		assert_eq!(history.len(), 1);
+		assert_eq!(history[0].round, 1);
+		assert_eq!(history[0].amount, nominator_pending);
+		// rewards are auto-compounded by default
+		assert_eq!(history[0].compounded, nominator_pending);
+		assert!(BfcStaking::reward_history(&nominator(1), 2, 2).is_empty());
+	});
+}
+
+#[test]
+fn relayer_score_rewards_are_normalised_by_the_weighted_points() {
+	ext().execute_with(|| {
+		IsRelayerScoreRewardActive::<Test>::put(true);
+		RELAYER_SCORES
+			.with(|scores| scores.borrow_mut().push((controller(1), Perbill::from_percent(40))));
+		BfcStaking::note_author(&controller(1));
+		BfcStaking::note_author(&controller(2));
+
+		roll_to_prepared_payouts(2);
+		// the 5 points of the first validator are weighted down to 2
+		assert_eq!(WeightedPoints::<Test>::get(1), Some(7));
+		let participants = [controller(1), nominator(1), nominator(2), controller(2), nominator(3)];
+		let pending: Balance =
+			participants.iter().map(|who| BfcStaking::pending_rewards(who)).sum();
+		// the entire staking reward is paid out regardless of the weighting
+		let reward = DelayedPayouts::<Test>::get(1).unwrap().total_staking_reward;
+		assert!(pending <= reward && reward - pending <= participants.len() as Balance);
+
+		roll_to(System::block_number() + 3);
+		assert!(WeightedPoints::<Test>::get(1).is_none());
+		let paid: Balance = participants
+			.iter()
+			.flat_map(|who| BfcStaking::reward_history(who, 1, 1))
+			.map(|entry| entry.amount)
+			.sum();
+		assert_eq!(paid, pending);
+	});
+}
+
+#[test]
+fn pending_rewards_use_the_snapshot_commission() {
+	ext().execute_with(|| {
+		BfcStaking::note_author(&controller(1));
+		roll_to_prepared_payouts(2);
+		let validator_pending = BfcStaking::pending_rewards(&controller(1));
+		let nominator_pending = BfcStaking::pending_rewards(&nominator(1));
+
+		// a commission change after the snapshot affects neither the estimate nor the payout
+		CandidateInfo::<Test>::mutate(&controller(1), |state| {
+			state.as_mut().unwrap().set_commission(Perbill::from_percent(50))
+		});
+		assert_eq!(BfcStaking::pending_rewards(&controller(1)), validator_pending);
+		assert_eq!(BfcStaking::pending_rewards(&nominator(1)), nominator_pending);
+
+		roll_to(System::block_number() + 2);
+		assert_eq!(BfcStaking::reward_history(&controller(1), 1, 1)[0].amount, validator_pending);
+		assert_eq!(BfcStaking::reward_history(&nominator(1), 1, 1)[0].amount, nominator_pending);
+	});
+}
+
+#[test]
+fn rewards_are_paid_into_a_new_payee_account() {
+	ext().execute_with(|| {
+		// a payee that does not exist yet, like the reward account of a nomination pool
+		let payee = account(999);
+		assert_eq!(Balances::free_balance(payee), 0);
+		assert_ok!(<BfcStaking as NominationManager<_, _>>::set_reward_payee(&nominator(1), payee));
+
+		BfcStaking::note_author(&controller(1));
+		roll_to_prepared_payouts(2);
+		let pending = BfcStaking::pending_rewards(&nominator(1));
+		roll_to(System::block_number() + 2);
+
+		assert_eq!(Balances::free_balance(payee), pending);
+		assert_eq!(Balances::free_balance(nominator(1)), 9_500);
+		assert_eq!(BfcStaking::nominator_info(&nominator(1)).unwrap().total, 500);
+		let history = BfcStaking::reward_history(&nominator(1), 1, 1);
+		assert_eq!(history[0].amount, pending);
+		assert_eq!(history[0].compounded, 0);
+	});
+}
+
+#[test]
+fn redelegate_moves_the_stake_without_unbonding() {
+	ext().execute_with(|| {
+		let total = Total::<Test>::get();
+		assert_ok!(BfcStaking::redelegate(
+			RuntimeOrigin::signed(nominator(1)),
+			controller(1),
+			controller(2),
+			200
+		));
+		System::assert_last_event(
+			Event::NominationRedelegated {
+				nominator: nominator(1),
+				from: controller(1),
+				to: controller(2),
+				amount: 200,
+			}
+			.into(),
+		);
+
+		let info = BfcStaking::nominator_info(&nominator(1)).unwrap();
+		assert_eq!(info.total, 500);
+		assert_eq!(
+			info.nominations.iter().map(|b| (b.owner, b.amount)).collect::<Vec<_>>(),
+			vec![(controller(1), 300), (controller(2), 200)]
+		);
+		assert_eq!(Balances::reserved_balance(nominator(1)), 500);
+		assert_eq!(BfcStaking::candidate_state(&controller(1)).unwrap().voting_power, 2_600);
+		assert_eq!(BfcStaking::candidate_state(&controller(2)).unwrap().voting_power, 1_900);
+		assert_eq!(Total::<Test>::get(), total);
+
+		// a nominator redelegates once per `RedelegationDelay` rounds
+		assert_noop!(
+			BfcStaking::redelegate(
+				RuntimeOrigin::signed(nominator(1)),
+				controller(2),
+				controller(3),
+				100
+			),
+			Error::<Test>::RedelegationTooFrequent
+		);
+		roll_to_round_begin(2);
+		assert_ok!(BfcStaking::redelegate(
+			RuntimeOrigin::signed(nominator(1)),
+			controller(2),
+			controller(3),
+			100
+		));
+	});
+}
+
+#[test]
+fn redelegate_rejects_pending_requests_of_both_candidates() {
+	ext().execute_with(|| {
+		nominate(1, 2, 100);
+		assert_ok!(BfcStaking::schedule_revoke_nomination(
+			RuntimeOrigin::signed(nominator(1)),
+			controller(2)
+		));
+
+		// the revocation of `to` would remove the redelegated stake with it
+		assert_noop!(
+			BfcStaking::redelegate(
+				RuntimeOrigin::signed(nominator(1)),
+				controller(1),
+				controller(2),
+				200
+			),
+			Error::<Test>::PendingNominationRequestAlreadyExists
+		);
+		assert_noop!(
+			BfcStaking::redelegate(
+				RuntimeOrigin::signed(nominator(1)),
+				controller(2),
+				controller(1),
+				50
+			),
+			Error::<Test>::PendingNominationRequestAlreadyExists
+		);
+
+		assert_ok!(BfcStaking::cancel_nomination_request(
+			RuntimeOrigin::signed(nominator(1)),
+			controller(2)
+		));
+		assert_ok!(BfcStaking::redelegate(
+			RuntimeOrigin::signed(nominator(1)),
+			controller(1),
+			controller(2),
+			200
+		));
+		assert_eq!(BfcStaking::nominator_info(&nominator(1)).unwrap().total, 600);
+	});
+}
+
+#[test]
+fn redelegate_rejects_slashed_or_jailed_candidates() {
+	ext().execute_with(|| {
+		MockOffenceHandler::defer_slash(3, controller(1), 100, vec![(nominator(1), 50)]);
+
+		// the stake stays with `from` until the deferred slash is applied
+		assert_noop!(
+			BfcStaking::redelegate(
+				RuntimeOrigin::signed(nominator(1)),
+				controller(1),
+				controller(2),
+				200
+			),
+			Error::<Test>::CannotRedelegateWithUnappliedSlash
+		);
+
+		roll_to_round_begin(3);
+		// the slash lands on the stake that would have been moved
+		assert_eq!(top_nominations(1), vec![(nominator(1), 450), (nominator(2), 300)]);
+		assert_ok!(BfcStaking::redelegate(
+			RuntimeOrigin::signed(nominator(1)),
+			controller(1),
+			controller(2),
+			200
+		));
+		assert_eq!(top_nominations(1), vec![(nominator(2), 300), (nominator(1), 250)]);
+
+		roll_to_round_begin(4);
+		CandidateInfo::<Test>::mutate(controller(2), |state| state.as_mut().unwrap().jail(10));
+		assert_noop!(
+			BfcStaking::redelegate(
+				RuntimeOrigin::signed(nominator(1)),
+				controller(2),
+				controller(3),
+				100
+			),
+			Error::<Test>::CandidateJailed
+		);
+	});
+}
+
+#[test]
+fn redelegate_rebalances_the_top_and_bottom_nominations() {
+	ext().execute_with(|| {
+		nominate(4, 1, 100);
+		nominate(5, 2, 50);
+		assert_eq!(top_nominations(1), vec![(nominator(1), 500), (nominator(2), 300)]);
+		assert_eq!(bottom_nominations(1), vec![(nominator(4), 100)]);
+		assert_eq!(top_nominations(2), vec![(nominator(3), 200), (nominator(5), 50)]);
+
+		let post_info = BfcStaking::redelegate(
+			RuntimeOrigin::signed(nominator(1)),
+			controller(1),
+			controller(2),
+			500,
+		)
+		.unwrap();
+		// the weight is refunded down to the nomination counts of both candidates
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<Test as crate::Config>::WeightInfo::redelegate(3, 2))
+		);
+
+		// the bottom nomination of `from` is promoted into the free top slot
+		assert_eq!(top_nominations(1), vec![(nominator(2), 300), (nominator(4), 100)]);
+		assert!(bottom_nominations(1).is_empty());
+		assert_eq!(BfcStaking::candidate_state(&controller(1)).unwrap().voting_power, 2_400);
+
+		// the lowest top nomination of `to` is moved to the bottom
+		assert_eq!(top_nominations(2), vec![(nominator(1), 500), (nominator(3), 200)]);
+		assert_eq!(bottom_nominations(2), vec![(nominator(5), 50)]);
+		assert_eq!(BfcStaking::candidate_state(&controller(2)).unwrap().voting_power, 2_200);
+
+		let info = BfcStaking::nominator_info(&nominator(1)).unwrap();
+		assert_eq!(
+			info.nominations.iter().map(|b| (b.owner, b.amount)).collect::<Vec<_>>(),
+			vec![(controller(2), 500)]
+		);
+	});
+}
+
+#[test]
+fn slashed_voting_power_is_refreshed_in_the_candidate_pool() {
+	ext().execute_with(|| {
+		let pool = || {
+			BfcStaking::get_sorted_candidates()
+				.into_iter()
+				.map(|bond| (bond.owner, bond.amount))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			pool(),
+			vec![(controller(1), 2_800), (controller(2), 1_700), (controller(3), 1_000)]
+		);
+
+		MockOffenceHandler::defer_slash(2, controller(1), 1_500, vec![(nominator(1), 100)]);
+		roll_to_round_begin(2);
+
+		assert_eq!(BfcStaking::candidate_state(&controller(1)).unwrap().voting_power, 1_200);
+		assert_eq!(
+			pool(),
+			vec![(controller(2), 1_700), (controller(1), 1_200), (controller(3), 1_000)]
+		);
+	});
+}
+
+#[test]
+fn jailing_notifies_the_top_nominators() {
+	ext().execute_with(|| {
+		nominate(4, 1, 100);
+		assert_eq!(bottom_nominations(1), vec![(nominator(4), 100)]);
+
+		BfcStaking::jail_validator(&controller(1));
+
+		let (_, until_round) = JailCount::<Test>::get(controller(1));
+		let mut notified: Vec<_> = staking_events()
+			.into_iter()
+			.filter_map(|event| match event {
+				Event::NominationJailed { nominator, candidate, until_round: until } => {
+					assert_eq!(candidate, controller(1));
+					assert_eq!(until, until_round);
+					Some(nominator)
+				},
+				_ => None,
+			})
+			.collect();
+		notified.sort();
+		// the bottom nominators are not notified to bound the number of events
+		assert_eq!(notified, vec![nominator(1), nominator(2)]);
+	});
+}
+
+#[test]
+fn candidates_cannot_leave_while_a_slash_is_pending() {
+	ext().execute_with(|| {
+		assert_ok!(BfcStaking::schedule_leave_candidates(RuntimeOrigin::signed(controller(3)), 3));
+		MockOffenceHandler::defer_slash(4, controller(3), 100, vec![]);
+		roll_to_round_begin(3);
+
+		assert_noop!(
+			BfcStaking::execute_leave_candidates(RuntimeOrigin::signed(stash(3)), 0),
+			Error::<Test>::CannotLeaveWithUnappliedSlash
+		);
+
+		roll_to_round_begin(4);
+		assert_eq!(BfcStaking::candidate_state(&controller(3)).unwrap().bond, 900);
+		assert_ok!(BfcStaking::execute_leave_candidates(RuntimeOrigin::signed(stash(3)), 0));
+		assert!(BfcStaking::candidate_state(&controller(3)).is_none());
+	});
+}
+
+#[test]
+fn reward_history_keeps_the_latest_entries() {
+	ext().execute_with(|| {
+		nominate(1, 2, 100);
+		nominate(1, 3, 100);
+		let author_all = || (1..=3).for_each(|c| BfcStaking::note_author(&controller(c)));
+
+		// three rewards of round 2 and three rewards of round 3 for `MaxRewardHistory` = 4
+		author_all();
+		roll_to_round_begin(2);
+		author_all();
+		roll_to_round_begin(3);
+		author_all();
+		roll_to_round_begin(4);
+		roll_to(System::block_number() + 4);
+
+		let history = RewardHistory::<Test>::get(nominator(1));
+		assert_eq!(history.len(), 4);
+		assert_eq!(history[0].round, 2);
+		assert!(history.iter().skip(1).all(|entry| entry.round == 3));
+
+		// the range of the queried rounds is inclusive
+		assert_eq!(BfcStaking::reward_history(&nominator(1), 2, 2).len(), 1);
+		assert_eq!(BfcStaking::reward_history(&nominator(1), 3, 3).len(), 3);
+		assert_eq!(BfcStaking::reward_history(&nominator(1), 1, 4).len(), 4);
+		assert!(BfcStaking::reward_history(&nominator(1), 3, 2).is_empty());
+	});
+}
+
+#[test]
+fn stale_reward_history_is_pruned_at_round_boundaries() {
+	ext().execute_with(|| {
+		let entry =
+			|round| RewardEntry { round, validator: controller(1), amount: 10, compounded: 0 };
+		let insert = |who: u64, round| {
+			RewardHistory::<Test>::insert(
+				account(who),
+				BoundedVec::truncate_from(vec![entry(round)]),
+			)
+		};
+		insert(900, 1);
+		insert(901, 4);
+		roll_to(System::block_number() + 3);
+		// no pass is in progress in the middle of a round
+		assert!(RewardHistoryPruneCursor::<Test>::get().is_none());
+		assert!(RewardHistory::<Test>::contains_key(account(900)));
+
+		// round 1 is older than `RewardHistoryDepth` = 4 rounds in round 5
+		roll_to_round_begin(5);
+		roll_to(System::block_number() + 1);
+		assert!(RewardHistoryPruneCursor::<Test>::get().is_none());
+		assert!(!RewardHistory::<Test>::contains_key(account(900)));
+		assert_eq!(RewardHistory::<Test>::get(account(901)).into_inner(), vec![entry(4)]);
+		assert!(BfcStaking::reward_history(&account(900), 1, 5).is_empty());
+
+		// entries are hidden from the queries once they are stale, even before they are pruned
+		insert(902, 1);
+		roll_to(System::block_number() + 3);
+		assert!(RewardHistory::<Test>::contains_key(account(902)));
+		assert!(BfcStaking::reward_history(&account(902), 1, 5).is_empty());
+
+		roll_to_round_begin(6);
+		roll_to(System::block_number() + 1);
+		assert!(!RewardHistory::<Test>::contains_key(account(902)));
+		assert_eq!(RewardHistory::<Test>::get(account(901)).into_inner(), vec![entry(4)]);
+	});
+}
+
+fn commission(candidate: u64) -> Perbill {
+	CandidateInfo::<Test>::get(controller(candidate)).unwrap().commission
+}
+
+#[test]
+fn commission_increases_are_stepped_across_rounds() {
+	ext().execute_with(|| {
+		assert_eq!(commission(1), Perbill::from_percent(10));
+		assert_ok!(BfcStaking::set_validator_commission(
+			RuntimeOrigin::signed(controller(1)),
+			Perbill::from_percent(22)
+		));
+		// 12% is reached in three steps of `MaxCommissionIncreasePerRound` = 5%
+		assert!(staking_events().contains(&Event::ValidatorCommissionIncreaseScheduled {
+			candidate: controller(1),
+			old: Perbill::from_percent(10),
+			new: Perbill::from_percent(22),
+			final_round: 4,
+		}));
+
+		roll_to_round_begin(2);
+		assert_eq!(commission(1), Perbill::from_percent(15));
+		assert!(staking_events().contains(&Event::ValidatorCommissionStepApplied {
+			candidate: controller(1),
+			commission: Perbill::from_percent(15),
+			target: Perbill::from_percent(22),
+		}));
+		// the remaining steps are still pending
+		assert_noop!(
+			BfcStaking::set_validator_commission(
+				RuntimeOrigin::signed(controller(1)),
+				Perbill::from_percent(30)
+			),
+			Error::<Test>::AlreadyCommissionSetRequested
+		);
+
+		roll_to_round_begin(3);
+		assert_eq!(commission(1), Perbill::from_percent(20));
+		roll_to_round_begin(4);
+		assert_eq!(commission(1), Perbill::from_percent(22));
+		roll_to_round_begin(5);
+		assert_eq!(commission(1), Perbill::from_percent(22));
+	});
+}
+
+#[test]
+fn commission_changes_below_the_cap_are_applied_at_once() {
+	ext().execute_with(|| {
+		assert_ok!(BfcStaking::set_validator_commission(
+			RuntimeOrigin::signed(controller(1)),
+			Perbill::from_percent(13)
+		));
+		assert!(!staking_events()
+			.iter()
+			.any(|event| matches!(event, Event::ValidatorCommissionIncreaseScheduled { .. })));
+
+		roll_to_round_begin(2);
+		assert_eq!(commission(1), Perbill::from_percent(13));
+
+		// decreases are never stepped
+		assert_ok!(BfcStaking::set_validator_commission(
+			RuntimeOrigin::signed(controller(1)),
+			Perbill::from_percent(1)
+		));
+		roll_to_round_begin(3);
+		assert_eq!(commission(1), Perbill::from_percent(1));
+		assert!(!staking_events()
+			.iter()
+			.any(|event| matches!(event, Event::ValidatorCommissionStepApplied { .. })));
+	});
+}
+
+#[test]
+fn migration_to_v8_initializes_the_max_commission_increase() {
+	ext().execute_with(|| {
+		StorageVersion::new(7).put::<BfcStaking>();
+		MaxCommissionIncreasePerRound::<Test>::kill();
+
+		migrations::v8::MigrateToV8::<Test>::on_runtime_upgrade();
+		assert_eq!(MaxCommissionIncreasePerRound::<Test>::get(), Perbill::from_percent(5));
+		assert_eq!(BfcStaking::on_chain_storage_version(), 8);
+
+		// the migration is skipped once the storage version is updated
+		MaxCommissionIncreasePerRound::<Test>::put(Perbill::from_percent(1));
+		migrations::v8::MigrateToV8::<Test>::on_runtime_upgrade();
+		assert_eq!(MaxCommissionIncreasePerRound::<Test>::get(), Perbill::from_percent(1));
+	});
+}
+
+#[test]
+fn migration_to_v9_adds_the_commission_to_the_snapshots() {
+	ext().execute_with(|| {
+		StorageVersion::new(8).put::<BfcStaking>();
+		// a snapshot in the v8 layout of (bond, nominations, total)
+		let old: (Balance, Vec<(AccountId, Balance)>, Balance) =
+			(2_000, vec![(nominator(1), 500)], 2_500);
+		frame_support::storage::unhashed::put(
+			&AtStake::<Test>::hashed_key_for(5, controller(1)),
+			&old,
+		);
+
+		migrations::v9::MigrateToV9::<Test>::on_runtime_upgrade();
+		let snapshot = AtStake::<Test>::get(5, controller(1));
+		assert_eq!(snapshot.bond, 2_000);
+		assert_eq!(snapshot.nominations.len(), 1);
+		assert_eq!(snapshot.total, 2_500);
+		assert_eq!(snapshot.commission, commission(1));
+		assert_eq!(BfcStaking::on_chain_storage_version(), 9);
+	});
+}
+
+#[test]
+fn offence_reports_recorded_before_the_commission_still_decode() {
+	ext().execute_with(|| {
+		// an offence report of `pallet_offences` with the identification tuple of
+		// `pallet_session::historical` in the layout of (bond, nominations, total)
+		let old_identification: (Balance, Vec<(AccountId, Balance)>, Balance) =
+			(2_000, vec![(nominator(1), 500)], 2_500);
+		let encoded = ((controller(1), old_identification), vec![controller(2)]).encode();
+
+		let report = OffenceDetails::<
+			AccountId,
+			(AccountId, ValidatorIdentification<AccountId, Balance>),
+		>::decode(&mut &encoded[..])
+		.unwrap();
+		let (offender, identification) = report.offender;
+		assert_eq!(offender, controller(1));
+		assert_eq!(identification.bond, 2_000);
+		assert_eq!(identification.nominations.len(), 1);
+		assert_eq!(identification.total, 2_500);
+		assert_eq!(report.reporters, vec![controller(2)]);
+
+		// the identifications recorded from now on keep the same layout
+		let identification = ValidatorSnapshotOf::<Test>::convert(controller(1)).unwrap();
+		let round = Round::<Test>::get().current_round_index;
+		let snapshot = BfcStaking::validator_snapshot(round, &controller(1));
+		assert_eq!(
+			identification.encode(),
+			(snapshot.bond, snapshot.nominations, snapshot.total).encode()
+		);
+	});
+}
+
+#[test]
+fn round_issuance_is_split_without_loss() {
+	ext().execute_with(|| {
+		InflationConfig::<Test>::mutate(|config| {
+			config.treasury = Perbill::from_percent(20);
+			config.burn = Perbill::from_percent(15);
+		});
+
+		assert_eq!(BfcStaking::split_round_issuance(1_000), (650, 200, 150));
+		for issuance in [0, 1, 7, 999, 1_001, 123_456_789] {
+			let (rewards, treasury, burn) = BfcStaking::split_round_issuance(issuance);
+			assert_eq!(rewards + treasury + burn, issuance);
+		}
+
+		// the whole issuance is paid to stakers without treasury and burn shares
+		InflationConfig::<Test>::mutate(|config| {
+			config.treasury = Perbill::zero();
+			config.burn = Perbill::zero();
+		});
+		assert_eq!(BfcStaking::split_round_issuance(1_001), (1_001, 0, 0));
+	});
+}
+
+#[test]
+fn migration_to_v7_adds_zero_treasury_and_burn_shares() {
+	ext().execute_with(|| {
+		let config = InflationConfig::<Test>::get();
+		// the inflation config in the v6 layout of (expect, annual, round)
+		frame_support::storage::unhashed::put(
+			&InflationConfig::<Test>::hashed_key(),
+			&(config.expect, config.annual, config.round),
+		);
+		StorageVersion::new(6).put::<BfcStaking>();
+
+		migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+		let migrated = InflationConfig::<Test>::get();
+		assert_eq!(migrated.expect, config.expect);
+		assert_eq!(migrated.annual, config.annual);
+		assert_eq!(migrated.round, config.round);
+		assert_eq!(migrated.treasury, Perbill::zero());
+		assert_eq!(migrated.burn, Perbill::zero());
+		assert_eq!(BfcStaking::on_chain_storage_version(), 7);
+
+		// the migration is skipped once the storage version is updated
+		InflationConfig::<Test>::mutate(|config| config.burn = Perbill::from_percent(10));
+		migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(InflationConfig::<Test>::get().burn, Perbill::from_percent(10));
+	});
+}
+
+#[test]
+fn round_transition_is_spread_over_blocks() {
+	ext().execute_with(|| {
+		let phase = || RoundTransition::<Test>::get();
+		let selected = SelectedCandidates::<Test>::get();
+		BfcStaking::note_author(&controller(1));
+		assert_ok!(BfcStaking::set_validator_commission(
+			RuntimeOrigin::signed(controller(2)),
+			Perbill::from_percent(12)
+		));
+
+		// the transition starts `RoundTransitionBlocks` = 6 blocks before the boundary at block 10
+		roll_to(3);
+		assert_eq!(phase(), None);
+		roll_to(4);
+		assert_eq!(phase(), Some(RoundTransitionPhase::CommissionSets));
+		roll_to(5);
+		assert_eq!(commission(2), Perbill::from_percent(12));
+
+		// requests made once the transition started are applied at the end of the next round
+		assert_ok!(BfcStaking::set_validator_commission(
+			RuntimeOrigin::signed(controller(3)),
+			Perbill::from_percent(11)
+		));
+
+		// the validators are elected ahead of the boundary
+		roll_to(7);
+		assert_eq!(phase(), Some(RoundTransitionPhase::UnappliedSlashes));
+		assert!(ElectedCandidates::<Test>::get().is_some());
+		roll_to(9);
+		assert_eq!(phase(), Some(RoundTransitionPhase::Elected));
+		assert_eq!(Round::<Test>::get().current_round_index, 1);
+		assert_eq!(SelectedCandidates::<Test>::get(), selected);
+
+		// the boundary queues the snapshots, which are taken over the next blocks
+		roll_to(10);
+		assert_eq!(Round::<Test>::get().current_round_index, 2);
+		assert!(ElectedCandidates::<Test>::get().is_none());
+		assert_eq!(phase(), Some(RoundTransitionPhase::StakingPayouts));
+		assert_eq!(AtStake::<Test>::iter_prefix(2).count(), 2);
+		assert!(DelayedPayouts::<Test>::get(1).is_none());
+
+		// the payouts are prepared after the boundary and paid over the next blocks
+		roll_to(11);
+		assert_eq!(phase(), None);
+		assert!(PendingValidatorSnapshots::<Test>::get().is_none());
+		for validator in SelectedCandidates::<Test>::get() {
+			assert!(AtStake::<Test>::contains_key(2, &validator));
+		}
+		assert!(DelayedPayouts::<Test>::get(1).is_some());
+		let pending = BfcStaking::pending_rewards(&controller(1));
+		assert!(pending > 0);
+
+		roll_to(12);
+		assert_eq!(BfcStaking::pending_rewards(&controller(1)), 0);
+		assert_eq!(BfcStaking::reward_history(&controller(1), 1, 1)[0].amount, pending);
+
+		assert_eq!(commission(3), Perbill::from_percent(10));
+		roll_to_round_begin(3);
+		assert_eq!(commission(3), Perbill::from_percent(11));
+	});
+}
+
+fn identity_field(value: &[u8]) -> IdentityField {
+	BoundedVec::truncate_from(value.to_vec())
+}
+
+fn set_identity(candidate: u64, relayer_endpoint: Option<&[u8]>) -> DispatchResultWithPostInfo {
+	BfcStaking::set_candidate_identity(
+		RuntimeOrigin::signed(controller(candidate)),
+		identity_field(b"validator"),
+		identity_field(b"https://example.com"),
+		identity_field(b"validator@example.com"),
+		[1u8; 32],
+		relayer_endpoint.map(identity_field),
+	)
+}
+
+#[test]
+fn candidate_identity_deposit_is_reserved_once() {
+	ext().execute_with(|| {
+		let reserved = Balances::reserved_balance(stash(1));
+
+		assert_ok!(set_identity(1, Some(b"https://relayer.example.com")));
+		let identity = CandidateIdentities::<Test>::get(controller(1)).unwrap();
+		assert_eq!(identity.name, identity_field(b"validator"));
+		assert_eq!(identity.deposit, IdentityDeposit::get());
+		assert_eq!(Balances::reserved_balance(stash(1)), reserved + IdentityDeposit::get());
+		System::assert_last_event(
+			Event::CandidateIdentitySet {
+				candidate: controller(1),
+				deposit: IdentityDeposit::get(),
+			}
+			.into(),
+		);
+
+		// an update keeps the deposit that has already been reserved
+		assert_ok!(set_identity(1, None));
+		assert_eq!(CandidateIdentities::<Test>::get(controller(1)).unwrap().relayer_endpoint, None);
+		assert_eq!(Balances::reserved_balance(stash(1)), reserved + IdentityDeposit::get());
+
+		assert_ok!(BfcStaking::clear_candidate_identity(RuntimeOrigin::signed(controller(1))));
+		assert!(CandidateIdentities::<Test>::get(controller(1)).is_none());
+		assert_eq!(Balances::reserved_balance(stash(1)), reserved);
+		System::assert_last_event(
+			Event::CandidateIdentityCleared {
+				candidate: controller(1),
+				deposit: IdentityDeposit::get(),
+			}
+			.into(),
+		);
+
+		assert_noop!(
+			BfcStaking::clear_candidate_identity(RuntimeOrigin::signed(controller(1))),
+			Error::<Test>::CandidateIdentityDNE
+		);
+		assert_noop!(set_identity(4, None), Error::<Test>::CandidateDNE);
+	});
+}
+
+#[test]
+fn candidate_identity_update_settles_a_changed_deposit() {
+	ext().execute_with(|| {
+		let reserved = Balances::reserved_balance(stash(1));
+		let registered = |deposit: Balance| {
+			assert_ok!(Balances::reserve(&stash(1), deposit));
+			CandidateIdentities::<Test>::insert(
+				controller(1),
+				CandidateIdentity {
+					name: identity_field(b"old"),
+					website: identity_field(b""),
+					contact: identity_field(b""),
+					logo_hash: [0u8; 32],
+					relayer_endpoint: None,
+					deposit,
+				},
+			);
+		};
+
+		// the identity was registered while the deposit was lower
+		registered(IdentityDeposit::get() - 4);
+		assert_ok!(set_identity(1, None));
+		assert_eq!(Balances::reserved_balance(stash(1)), reserved + IdentityDeposit::get());
+		assert_ok!(BfcStaking::clear_candidate_identity(RuntimeOrigin::signed(controller(1))));
+		assert_eq!(Balances::reserved_balance(stash(1)), reserved);
+
+		// the identity was registered while the deposit was higher
+		registered(IdentityDeposit::get() + 5);
+		assert_ok!(set_identity(1, None));
+		assert_eq!(Balances::reserved_balance(stash(1)), reserved + IdentityDeposit::get());
+		assert_eq!(
+			CandidateIdentities::<Test>::get(controller(1)).unwrap().deposit,
+			IdentityDeposit::get()
+		);
+	});
+}
+
+#[test]
+fn relayer_endpoint_is_only_registered_by_full_candidates() {
+	ext().execute_with(|| {
+		assert_ok!(BfcStaking::join_candidates(
+			RuntimeOrigin::signed(stash(4)),
+			controller(4),
+			None,
+			MIN_BASIC_CANDIDATE_STK,
+			3
+		));
+		assert_eq!(BfcStaking::candidate_state(&controller(4)).unwrap().tier, TierType::Basic);
+
+		assert_noop!(
+			set_identity(4, Some(b"https://relayer.example.com")),
+			Error::<Test>::RelayerEndpointOnlyForFullTier
+		);
+		assert_ok!(set_identity(4, None));
+		assert_ok!(set_identity(1, Some(b"https://relayer.example.com")));
+	});
+}
+
+#[test]
+fn jailed_candidates_unjail_once_the_jail_ends() {
+	ext().execute_with(|| {
+		assert_noop!(
+			BfcStaking::unjail(RuntimeOrigin::signed(controller(1))),
+			Error::<Test>::CandidateNotJailed
+		);
+
+		BfcStaking::jail_validator(&controller(1));
+		let (jail_count, until_round) = JailCount::<Test>::get(controller(1));
+		assert_eq!((jail_count, until_round), (1, 2));
+		assert!(BfcStaking::candidate_state(&controller(1)).unwrap().is_jailed());
+		assert!(!BfcStaking::get_sorted_candidates().iter().any(|c| c.owner == controller(1)));
+		assert_noop!(
+			BfcStaking::unjail(RuntimeOrigin::signed(controller(1))),
+			Error::<Test>::CandidateCannotUnjailYet
+		);
+
+		roll_to_round_begin(until_round);
+		assert_ok!(BfcStaking::unjail(RuntimeOrigin::signed(controller(1))));
+		System::assert_last_event(Event::Unjailed { candidate: controller(1) }.into());
+		assert!(!BfcStaking::candidate_state(&controller(1)).unwrap().is_jailed());
+		assert!(BfcStaking::get_sorted_candidates().iter().any(|c| c.owner == controller(1)));
+		assert_noop!(
+			BfcStaking::unjail(RuntimeOrigin::signed(controller(1))),
+			Error::<Test>::CandidateNotJailed
+		);
+	});
+}
+
+#[test]
+fn jail_duration_doubles_up_to_the_max() {
+	ext().execute_with(|| {
+		// `JailDuration` is 1 and `MaxJailDuration` is 8
+		assert_eq!(
+			(1..=5).map(BfcStaking::compute_jail_duration).collect::<Vec<_>>(),
+			vec![1, 2, 4, 8, 8]
+		);
+		assert_eq!(BfcStaking::compute_jail_duration(0), 1);
+		assert_eq!(BfcStaking::compute_jail_duration(u32::MAX), MaxJailDuration::get());
+
+		// a repeated jail right after the previous one doubles the duration
+		BfcStaking::jail_validator(&controller(1));
+		roll_to_round_begin(2);
+		assert_ok!(BfcStaking::unjail(RuntimeOrigin::signed(controller(1))));
+		BfcStaking::jail_validator(&controller(1));
+		assert_eq!(JailCount::<Test>::get(controller(1)), (2, 4));
+	});
+}
+
+#[test]
+fn jail_count_decays_after_the_jail_ends() {
+	ext().execute_with(|| {
+		// `JailCountDecay` is 8
+		assert_eq!(BfcStaking::decayed_jail_count(3, 10, 5), 3);
+		assert_eq!(BfcStaking::decayed_jail_count(3, 10, 10), 3);
+		assert_eq!(BfcStaking::decayed_jail_count(3, 10, 17), 3);
+		assert_eq!(BfcStaking::decayed_jail_count(3, 10, 18), 2);
+		assert_eq!(BfcStaking::decayed_jail_count(3, 10, 34), 0);
+		assert_eq!(BfcStaking::decayed_jail_count(3, 10, u32::MAX), 0);
+	});
+}
+
+#[test]
+fn jailed_candidates_are_not_elected() {
+	ext().execute_with(|| {
+		let (full, _) = BfcStaking::compute_top_candidates();
+		assert!(full.contains(&controller(2)));
+
+		// a jailed candidate that is still in the pool is skipped
+		CandidateInfo::<Test>::mutate(controller(2), |state| state.as_mut().unwrap().jail(10));
+		assert!(BfcStaking::get_sorted_candidates().iter().any(|c| c.owner == controller(2)));
+		let (full, basic) = BfcStaking::compute_top_candidates();
+		assert!(!full.contains(&controller(2)));
+		assert!(!basic.contains(&controller(2)));
+		assert!(full.contains(&controller(1)));
+	});
+}
@@ -48,6 +48,16 @@ pub trait WeightInfo {
 	fn round_transition_on_initialize(x: u32, y: u32) -> Weight;
 	fn base_on_initialize() -> Weight;
 	fn pay_one_validator_reward(y: u32) -> Weight;
+	fn compute_top_candidates_top_n(x: u32, y: u32) -> Weight;
+	fn compute_top_candidates_sequential_phragmen(x: u32, y: u32) -> Weight;
+	fn compute_top_candidates_stake_capped(x: u32, y: u32) -> Weight;
+	fn redelegate(x: u32, y: u32) -> Weight;
+	fn take_validator_snapshots(x: u32) -> Weight;
+	fn set_max_commission_increase_per_round() -> Weight;
+	fn set_candidate_identity() -> Weight;
+	fn clear_candidate_identity() -> Weight;
+	fn unjail() -> Weight;
+	fn set_relayer_score_reward_activation() -> Weight;
 }
 
 /// Weights for pallet_bfc_staking using the Substrate node and recommended hardware.
@@ -262,6 +272,79 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(6 as u64))
 			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(y as u64)))
 	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn compute_top_candidates_top_n(x: u32, _y: u32) -> Weight {
+		Weight::from_parts(10_412_000, 0)
+			.saturating_add(Weight::from_parts(9_876_000, 0).saturating_mul(x as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn compute_top_candidates_sequential_phragmen(x: u32, y: u32) -> Weight {
+		Weight::from_parts(24_630_000, 0)
+			.saturating_add(Weight::from_parts(31_208_000, 0).saturating_mul(x as u64))
+			.saturating_add(Weight::from_parts(6_954_000, 0).saturating_mul(y as u64))
+			.saturating_add(
+				Weight::from_parts(1_187_000, 0)
+					.saturating_mul((x as u64).saturating_mul(y as u64)),
+			)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn compute_top_candidates_stake_capped(x: u32, _y: u32) -> Weight {
+		Weight::from_parts(11_068_000, 0)
+			.saturating_add(Weight::from_parts(10_742_000, 0).saturating_mul(x as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn redelegate(x: u32, y: u32) -> Weight {
+		Weight::from_parts(118_270_000, 0)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(x as u64))
+			.saturating_add(Weight::from_parts(436_000, 0).saturating_mul(y as u64))
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(16 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn take_validator_snapshots(x: u32) -> Weight {
+		Weight::from_parts(6_120_000, 0)
+			.saturating_add(Weight::from_parts(38_450_000, 0).saturating_mul(x as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(x as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_max_commission_increase_per_round() -> Weight {
+		Weight::from_parts(12_104_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_candidate_identity() -> Weight {
+		Weight::from_parts(31_820_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn clear_candidate_identity() -> Weight {
+		Weight::from_parts(29_466_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn unjail() -> Weight {
+		Weight::from_parts(31_178_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_relayer_score_reward_activation() -> Weight {
+		Weight::from_parts(11_872_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -475,4 +558,77 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(y as u64)))
 	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn compute_top_candidates_top_n(x: u32, _y: u32) -> Weight {
+		Weight::from_parts(10_412_000, 0)
+			.saturating_add(Weight::from_parts(9_876_000, 0).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn compute_top_candidates_sequential_phragmen(x: u32, y: u32) -> Weight {
+		Weight::from_parts(24_630_000, 0)
+			.saturating_add(Weight::from_parts(31_208_000, 0).saturating_mul(x as u64))
+			.saturating_add(Weight::from_parts(6_954_000, 0).saturating_mul(y as u64))
+			.saturating_add(
+				Weight::from_parts(1_187_000, 0)
+					.saturating_mul((x as u64).saturating_mul(y as u64)),
+			)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn compute_top_candidates_stake_capped(x: u32, _y: u32) -> Weight {
+		Weight::from_parts(11_068_000, 0)
+			.saturating_add(Weight::from_parts(10_742_000, 0).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn redelegate(x: u32, y: u32) -> Weight {
+		Weight::from_parts(118_270_000, 0)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(x as u64))
+			.saturating_add(Weight::from_parts(436_000, 0).saturating_mul(y as u64))
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(16 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn take_validator_snapshots(x: u32) -> Weight {
+		Weight::from_parts(6_120_000, 0)
+			.saturating_add(Weight::from_parts(38_450_000, 0).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(x as u64)))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_max_commission_increase_per_round() -> Weight {
+		Weight::from_parts(12_104_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_candidate_identity() -> Weight {
+		Weight::from_parts(31_820_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn clear_candidate_identity() -> Weight {
+		Weight::from_parts(29_466_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn unjail() -> Weight {
+		Weight::from_parts(31_178_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_relayer_score_reward_activation() -> Weight {
+		Weight::from_parts(11_872_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
@@ -1,12 +1,58 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use bp_btc_relay::{blaze::UtxoInfo, traits::SocketQueueManager, UnboundedBytes};
+use bp_btc_relay::{
+	blaze::{BatchPolicy, OutboundPriority, UtxoInfo, UtxoInfoWithSize},
+	light_client::{tx_hash, MerkleProof},
+	traits::{LightClientManager, SocketQueueManager},
+	Descriptor, PublicKey, UnboundedBytes, TAPROOT_UNSPENDABLE_KEY,
+};
+use bp_cccp::{traits::SocketVerifier, Instruction, RequestID, SocketMessage, TaskParams};
+use bp_staking::MajorityThreshold;
 use frame_benchmarking::v2::*;
 use frame_system::RawOrigin;
 use parity_scale_codec::Decode;
-use sp_core::H256;
-use sp_std::vec;
+use scale_info::prelude::{format, string::ToString};
+use sp_core::{H160, H256};
+use sp_runtime::Perbill;
+use sp_std::{str::FromStr, vec, vec::Vec};
+
+/// A serialized transaction with a single input that pays `amount` sats to `script_pubkey`.
+fn raw_tx(nonce: u32, amount: u64, script_pubkey: &[u8]) -> UnboundedBytes {
+	let mut tx = vec![];
+	tx.extend(1u32.to_le_bytes()); // version
+	tx.push(1); // input count
+	tx.extend([0u8; 32]); // previous txid
+	tx.extend(nonce.to_le_bytes()); // previous vout
+	tx.push(0); // script_sig length
+	tx.extend(u32::MAX.to_le_bytes()); // sequence
+	tx.push(1); // output count
+	tx.extend(amount.to_le_bytes());
+	tx.push(script_pubkey.len() as u8);
+	tx.extend(script_pubkey);
+	tx.extend(0u32.to_le_bytes()); // lock time
+	tx
+}
+
+/// An outbound Socket message of the given sequence, padded to about `len` bytes.
+fn outbound_message(sequence: u64, len: u32) -> UnboundedBytes {
+	let mut message = SocketMessage {
+		req_id: RequestID { chain: vec![0; 4], round_id: 1.into(), sequence: sequence.into() },
+		status: 1.into(),
+		ins_code: Instruction { chain: vec![0; 4], method: vec![0; 16] },
+		params: TaskParams {
+			token_idx0: vec![0; 32],
+			token_idx1: vec![0; 32],
+			refund: H160::zero(),
+			to: H160::zero(),
+			amount: 100_000u64.into(),
+			variants: vec![],
+		},
+	};
+	let base_len = message.encode().len() as u32;
+	message.params.variants = vec![0; len.saturating_sub(base_len) as usize];
+	message.encode()
+}
 
 #[benchmarks]
 mod benchmarks {
@@ -77,16 +123,28 @@ mod benchmarks {
 	}
 
 	#[benchmark]
-	fn submit_outbound_requests() {
+	fn submit_outbound_requests(
+		n: Linear<1, { MAX_SOCKET_MESSAGES_PER_SUBMISSION as u32 }>,
+		total_msg_bytes: Linear<1, 20_480>,
+	) {
 		let authority: T::AccountId = account("authority", 0, 0);
-		let messages = vec![UnboundedBytes::from(vec![1, 2, 3, 4])];
+		// `total_msg_bytes` is spread evenly over the `n` messages
+		let messages = (0..n)
+			.map(|sequence| outbound_message(sequence as u64, total_msg_bytes / n))
+			.collect::<Vec<_>>();
 
 		let outbound_request_submission =
-			SocketMessagesSubmission { authority_id: authority, messages };
+			SocketMessagesSubmission { authority_id: authority, messages: messages.clone() };
 		let signature = T::Signature::decode(&mut [0u8; 65].as_ref()).expect("Valid signature");
 
 		#[extrinsic_call]
 		_(RawOrigin::None, outbound_request_submission, signature);
+
+		// only the verified messages are pooled
+		let pool = <OutboundPool<T>>::get();
+		assert!(messages.iter().all(|message| {
+			pool.contains(message) == T::SocketQueue::verify_socket_message(message).is_ok()
+		}));
 	}
 
 	#[benchmark]
@@ -123,5 +181,96 @@ mod benchmarks {
 		_(RawOrigin::None, remove_submission, signature);
 	}
 
+	#[benchmark]
+	fn set_vote_threshold() {
+		let threshold = MajorityThreshold::Proportion(Perbill::from_percent(67));
+		<VoteThresholds<T>>::insert(VoteWorkflow::FeeRate, MajorityThreshold::Majority);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, VoteWorkflow::FeeRate, threshold);
+
+		assert_eq!(<VoteThresholds<T>>::get(VoteWorkflow::FeeRate), threshold);
+	}
+
+	#[benchmark]
+	fn set_spv_confirmations() {
+		let confirmations = <SpvConfirmations<T>>::get().saturating_add(1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, confirmations);
+
+		assert_eq!(<SpvConfirmations<T>>::get(), confirmations);
+	}
+
+	#[benchmark]
+	fn submit_utxo_proofs(n: Linear<1, 50>) {
+		let authority: T::AccountId = account("authority", 0, 0);
+		let descriptor =
+			Descriptor::<PublicKey>::from_str(&format!("wsh(pk({}))", TAPROOT_UNSPENDABLE_KEY))
+				.expect("Valid descriptor");
+		let mut proofs = vec![];
+		let mut utxo_hashes = vec![];
+		for i in 0..n {
+			// Setup: Create an unconfirmed UTXO included in a verified block
+			let raw_tx = raw_tx(i, 100000, descriptor.script_pubkey().as_bytes());
+			let txid = tx_hash(&raw_tx);
+			let utxo_hash = H256::from_low_u64_be(u64::MAX - i as u64);
+			let height = T::LightClient::set_benchmark(txid).expect("Valid block");
+			<Utxos<T>>::insert(
+				&utxo_hash,
+				Utxo {
+					inner: UtxoInfoWithSize {
+						hash: utxo_hash,
+						txid,
+						vout: 0,
+						amount: 100000,
+						descriptor: descriptor.to_string(),
+						input_vbytes: 0,
+					},
+					status: UtxoStatus::Unconfirmed,
+					voters: Default::default(),
+				},
+			);
+			utxo_hashes.push(utxo_hash);
+			proofs.push(UtxoProof {
+				utxo_hash,
+				height,
+				merkle_proof: MerkleProof { siblings: vec![], index: 0, tx_count: 1 },
+				raw_tx,
+			});
+		}
+		let utxo_proof_submission = UtxoProofSubmission { authority_id: authority, proofs };
+		let signature = T::Signature::decode(&mut [0u8; 65].as_ref()).expect("Valid signature");
+
+		// Activate the pallet and require SPV proofs first
+		<IsActivated<T>>::put(true);
+		<SpvConfirmations<T>>::put(1);
+
+		#[extrinsic_call]
+		_(RawOrigin::None, utxo_proof_submission, signature);
+
+		// a proven UTXO stays unconfirmed until the vote threshold is reached
+		assert!(utxo_hashes.iter().all(|utxo_hash| {
+			<ProvenUtxos<T>>::contains_key(utxo_hash)
+				|| <Utxos<T>>::get(utxo_hash).is_some_and(|u| u.status != UtxoStatus::Unconfirmed)
+		}));
+	}
+
+	#[benchmark]
+	fn set_batch_policy() {
+		let policy = BatchPolicy {
+			min_batch_size: 10,
+			max_wait_blocks: 100,
+			max_outputs: 50,
+			priority: OutboundPriority::Amount,
+			max_age_blocks: 200,
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, policy);
+
+		assert_eq!(<OutboundBatchPolicy<T>>::get(), policy);
+	}
+
 	impl_benchmark_test_suite!(Pallet, mock::new_test_ext(), mock::Test);
 }
@@ -6,6 +6,8 @@ mod benchmarking;
 pub mod migrations;
 #[cfg(test)]
 mod mock;
+#[cfg(test)]
+mod tests;
 
 mod pallet;
 pub mod weights;
@@ -15,10 +17,11 @@ pub use weights::WeightInfo;
 
 use bp_btc_relay::{
 	blaze::{UtxoInfo, UtxoInfoWithSize},
+	light_client::MerkleProof,
 	UnboundedBytes,
 };
 use bp_staking::MAX_AUTHORITIES;
-use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::{ConstU32, RuntimeDebug, H256};
 use sp_runtime::BoundedVec;
@@ -32,6 +35,10 @@ pub const MAX_SOCKET_MESSAGES_PER_SUBMISSION: usize = 10;
 /// Maximum number of UTXOs allowed per `submit_utxos` submission.
 pub const MAX_UTXOS_PER_SUBMISSION: usize = 50;
 
+/// Maximum byte size of a serialized transaction in a `submit_utxo_proofs` submission.
+/// A standard transaction weighs at most 400_000 WU, so its non-witness serialization is smaller.
+pub const MAX_RAW_TX_SIZE: usize = 100_000;
+
 /// Maximum number of unconfirmed UTXOs a single relayer may have pending at any time.
 pub const MAX_UNCONFIRMED_UTXOS_PER_RELAYER: u32 = 100;
 
@@ -46,6 +53,28 @@ macro_rules! log {
 	};
 }
 
+#[derive(
+	Eq,
+	PartialEq,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+/// The relay workflows that require the relayers to reach a vote threshold.
+pub enum VoteWorkflow {
+	/// The confirmation of submitted UTXOs.
+	Utxo,
+	/// The confirmation of broadcasted PSBTs.
+	Broadcast,
+	/// The finalization of submitted fee rates.
+	FeeRate,
+}
+
 #[derive(Eq, PartialEq, Clone, Encode, Decode, DecodeWithMemTracking, RuntimeDebug, TypeInfo)]
 /// The status of a UTXO.
 pub enum UtxoStatus {
@@ -57,6 +86,8 @@ pub enum UtxoStatus {
 	Locked,
 	/// The UTXO is used.
 	Used,
+	/// The UTXO is close to the maturity of its recovery path and will not be used.
+	Expired,
 }
 
 #[derive(Decode, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
@@ -136,3 +167,25 @@ pub struct SocketMessagesSubmission<AccountId> {
 	/// The Socket messages.
 	pub messages: Vec<UnboundedBytes>,
 }
+
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// An SPV proof of the transaction that created a UTXO.
+pub struct UtxoProof {
+	/// The UTXO hash.
+	pub utxo_hash: H256,
+	/// The height of the block that includes the transaction.
+	pub height: u32,
+	/// The merkle proof of the transaction.
+	pub merkle_proof: MerkleProof,
+	/// The serialized transaction without witness data.
+	pub raw_tx: UnboundedBytes,
+}
+
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// A submission of UTXO SPV proofs.
+pub struct UtxoProofSubmission<AccountId> {
+	/// The authority id.
+	pub authority_id: AccountId,
+	/// The SPV proofs.
+	pub proofs: Vec<UtxoProof>,
+}
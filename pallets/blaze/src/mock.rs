@@ -1,6 +1,7 @@
 use crate as pallet_blaze;
 use bp_btc_relay::{
-	traits::{PoolManager, SocketQueueManager},
+	light_client::MerkleProof,
+	traits::{LightClientManager, PoolManager, SocketQueueManager},
 	BoundedBitcoinAddress, MigrationSequence, UnboundedBytes,
 };
 use bp_cccp::traits::SocketVerifier;
@@ -12,7 +13,7 @@ use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	transaction_validity::TransactionValidityError,
-	DispatchError,
+	BuildStorage, DispatchError,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -30,6 +31,8 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 	pub const FeeRateExpiration: u32 = 100;
 	pub const ToleranceThreshold: u32 = 3;
+	pub const RecoverySweepWindow: u32 = 36;
+	pub const RecoverySafetyMargin: u32 = 12;
 	pub const ExistentialDeposit: u128 = 1;
 }
 
@@ -199,18 +202,36 @@ impl PoolManager<AccountId> for MockPoolManager {
 	}
 }
 
+pub struct MockLightClient;
+impl LightClientManager for MockLightClient {
+	fn best_height() -> u32 {
+		0
+	}
+
+	fn verify_tx_inclusion(_: &[u8], _: &MerkleProof, _: u32, _: u32) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_benchmark(_: H256) -> Result<u32, DispatchError> {
+		Ok(0)
+	}
+}
+
 impl pallet_blaze::Config for Test {
 	type Signature = EthereumSignature;
 	type Signer = EthereumSigner;
 	type Relayers = MockAuthorities;
 	type SocketQueue = MockSocketQueue;
 	type RegistrationPool = MockPoolManager;
+	type LightClient = MockLightClient;
 	type FeeRateExpiration = FeeRateExpiration;
 	type ToleranceThreshold = ToleranceThreshold;
+	type RecoverySweepWindow = RecoverySweepWindow;
+	type RecoverySafetyMargin = RecoverySafetyMargin;
 	type WeightInfo = ();
 }
 
-#[cfg(feature = "runtime-benchmarks")]
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
 }
@@ -1,14 +1,15 @@
 use super::pallet::*;
 use crate::{
-	BTCTransaction, BroadcastSubmission, FeeRateSubmission, SocketMessagesSubmission, UtxoStatus,
-	UtxoSubmission,
+	BTCTransaction, BroadcastSubmission, FeeRateSubmission, SocketMessagesSubmission, Utxo,
+	UtxoProofSubmission, UtxoStatus, UtxoSubmission, VoteWorkflow,
 };
 use bp_btc_relay::{
-	blaze::{ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
-	traits::{BlazeManager, SocketQueueManager},
-	Hash, Psbt, UnboundedBytes,
+	blaze::{BatchPolicy, OutboundPriority, ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
+	traits::{BlazeManager, LightClientManager, OnCheckpointSet, SocketQueueManager},
+	utils::descriptor_recovery_timelock,
+	Descriptor, Hash, Psbt, PublicKey, UnboundedBytes,
 };
-use bp_cccp::traits::SocketVerifier;
+use bp_cccp::{traits::SocketVerifier, SocketMessage};
 use bp_staking::traits::Authorities;
 use frame_support::{
 	ensure,
@@ -22,10 +23,10 @@ use scale_info::prelude::{format, string::String};
 use sp_core::{Get, H256};
 use sp_io::hashing::keccak_256;
 use sp_runtime::{
-	traits::{Block, Header, Verify},
-	BoundedBTreeMap, BoundedVec, DispatchError,
+	traits::{Block, Header, Saturating, Verify},
+	BoundedVec, DispatchError,
 };
-use sp_std::{fmt::Display, vec, vec::Vec};
+use sp_std::{cmp::Ordering, fmt::Display, str::FromStr, vec, vec::Vec};
 
 impl<T: Config> BlazeManager<T> for Pallet<T> {
 	fn replace_authority(old: &T::AccountId, new: &T::AccountId) {
@@ -66,16 +67,45 @@ impl<T: Config> BlazeManager<T> for Pallet<T> {
 	}
 
 	fn get_utxos() -> Vec<UtxoInfoWithSize> {
+		let sweep_window = T::RecoverySweepWindow::get();
 		<Utxos<T>>::iter()
-			.filter_map(
-				|(_, utxo)| {
-					if utxo.status == UtxoStatus::Available {
-						Some(utxo.inner)
-					} else {
-						None
-					}
-				},
-			)
+			.filter_map(|(hash, utxo)| {
+				if utxo.status == UtxoStatus::Available
+					&& !<RecoveryMaturities<T>>::get(hash)
+						.is_some_and(|h| Self::is_near_maturity(h, sweep_window))
+				{
+					Some(utxo.inner)
+				} else {
+					None
+				}
+			})
+			.collect()
+	}
+
+	fn get_sweep_utxos() -> Vec<UtxoInfoWithSize> {
+		let sweep_window = T::RecoverySweepWindow::get();
+		let mut maturities = <RecoveryMaturities<T>>::iter()
+			.filter(|(_, matures_at)| Self::is_near_maturity(*matures_at, sweep_window))
+			.collect::<Vec<_>>();
+		maturities.sort_by_key(|(_, matures_at)| *matures_at);
+
+		maturities
+			.into_iter()
+			.filter_map(|(hash, matures_at)| {
+				let mut utxo = <Utxos<T>>::get(hash)?;
+				if utxo.status != UtxoStatus::Available {
+					return None;
+				}
+				// too late, the user can already spend it through the recovery path
+				if Self::is_near_maturity(matures_at, 0) {
+					utxo.status = UtxoStatus::Expired;
+					<Utxos<T>>::insert(hash, utxo);
+					<RecoveryMaturities<T>>::remove(hash);
+					Self::deposit_event(Event::UtxoExpired { utxo_hash: hash, matures_at });
+					return None;
+				}
+				Some(utxo.inner)
+			})
 			.collect()
 	}
 
@@ -89,6 +119,8 @@ impl<T: Config> BlazeManager<T> for Pallet<T> {
 					}
 				}
 				<Utxos<T>>::remove(hash);
+				<ProvenUtxos<T>>::remove(hash);
+				<RecoveryMaturities<T>>::remove(hash);
 			}
 		}
 	}
@@ -168,7 +200,70 @@ impl<T: Config> BlazeManager<T> for Pallet<T> {
 		<OutboundPool<T>>::get()
 	}
 
+	fn get_outbound_pool_len() -> usize {
+		<OutboundPool<T>>::decode_len().unwrap_or_default()
+	}
+
+	fn get_outbound_batch(n: BlockNumberFor<T>) -> Vec<UnboundedBytes> {
+		let pool = <OutboundPool<T>>::get();
+		if pool.is_empty() {
+			return vec![];
+		}
+		let policy = <OutboundBatchPolicy<T>>::get();
+
+		// (message, amount, sequence, arrival)
+		// messages submitted before the arrivals were tracked have no arrival
+		let mut batch = pool
+			.into_iter()
+			.map(|raw| match SocketMessage::try_from(raw.clone()) {
+				Ok(msg) => {
+					let sequence = msg.req_id.sequence;
+					let arrival = <OutboundArrivals<T>>::get(sequence);
+					(raw, msg.params.amount, sequence, arrival)
+				},
+				Err(_) => (raw, Default::default(), Default::default(), None),
+			})
+			.collect::<Vec<_>>();
+
+		// whether a request has waited for the given number of blocks (zero disables the check).
+		// the requests without an arrival are the oldest and have always waited long enough.
+		let has_waited = |arrival: Option<BlockNumberFor<T>>, blocks: u32| {
+			blocks > 0 && arrival.is_none_or(|arrival| n >= arrival.saturating_add(blocks.into()))
+		};
+
+		// check the batching window
+		let oldest = batch.iter().map(|x| x.3).min().flatten();
+		let is_full = batch.len() as u32 >= policy.min_batch_size;
+		let is_expired =
+			has_waited(oldest, policy.max_wait_blocks) || has_waited(oldest, policy.max_age_blocks);
+		if !is_full && !is_expired {
+			return vec![];
+		}
+
+		match policy.priority {
+			OutboundPriority::Age => batch.sort_by(|a, b| (a.3, a.2).cmp(&(b.3, b.2))),
+			OutboundPriority::Amount => batch.sort_by(|a, b| {
+				// the requests over the age cap come first, regardless of their amount
+				let is_overdue = has_waited(a.3, policy.max_age_blocks);
+				has_waited(b.3, policy.max_age_blocks)
+					.cmp(&is_overdue)
+					.then_with(|| if is_overdue { Ordering::Equal } else { b.1.cmp(&a.1) })
+					.then((a.3, a.2).cmp(&(b.3, b.2)))
+			}),
+		}
+		batch.into_iter().map(|x| x.0).collect()
+	}
+
+	fn get_batch_policy() -> BatchPolicy {
+		<OutboundBatchPolicy<T>>::get()
+	}
+
 	fn clear_outbound_pool(targets: Vec<UnboundedBytes>) {
+		for target in targets.iter() {
+			if let Ok(msg) = SocketMessage::try_from(target.clone()) {
+				<OutboundArrivals<T>>::remove(msg.req_id.sequence);
+			}
+		}
 		<OutboundPool<T>>::mutate(|x| {
 			x.retain(|x| !targets.contains(x));
 		});
@@ -180,14 +275,21 @@ impl<T: Config> BlazeManager<T> for Pallet<T> {
 		submitted_fee_rates.retain(|_, (_, _, expires_at)| n <= *expires_at);
 		<FeeRates<T>>::put(submitted_fee_rates.clone());
 
-		// check majority
-		if submitted_fee_rates.len() as u32 >= T::Relayers::majority() {
+		// the fee rates of accounts that are no longer authorities are not counted
+		let authority_fee_rates = submitted_fee_rates
+			.iter()
+			.filter(|(who, _)| T::Relayers::is_authority(who))
+			.map(|(_, rates)| *rates)
+			.collect::<Vec<_>>();
+
+		// check threshold
+		if authority_fee_rates.len() as u32 >= Self::vote_threshold(VoteWorkflow::FeeRate) {
 			// choose the median fee rate
-			let mut fee_rates = Vec::with_capacity(submitted_fee_rates.len());
-			let mut lt_fee_rates = Vec::with_capacity(submitted_fee_rates.len());
-			for (_, (lt_fee_rate, fee_rate, _)) in &submitted_fee_rates {
-				lt_fee_rates.push(*lt_fee_rate);
-				fee_rates.push(*fee_rate);
+			let mut fee_rates = Vec::with_capacity(authority_fee_rates.len());
+			let mut lt_fee_rates = Vec::with_capacity(authority_fee_rates.len());
+			for (lt_fee_rate, fee_rate, _) in authority_fee_rates {
+				lt_fee_rates.push(lt_fee_rate);
+				fee_rates.push(fee_rate);
 			}
 
 			fee_rates.sort();
@@ -205,7 +307,9 @@ impl<T: Config> BlazeManager<T> for Pallet<T> {
 	}
 
 	fn clear_fee_rates() {
-		<FeeRates<T>>::put(BoundedBTreeMap::new());
+		// the fee rate poll is settled once its fee rate has been used
+		let voters = <FeeRates<T>>::take().keys().cloned().collect::<Vec<_>>();
+		T::Relayers::note_majority_reached(&voters);
 	}
 
 	fn select_coins(
@@ -249,6 +353,43 @@ impl<T: Config> BlazeManager<T> for Pallet<T> {
 		ensure!(Self::is_activated() == is_activated, Error::<T>::InvalidActivationState);
 		Ok(())
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_activation(activate: bool) -> Result<(), DispatchError> {
+		<IsActivated<T>>::put(activate);
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_outbound_pool(pool: Vec<UnboundedBytes>) -> Result<(), DispatchError> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		for message in &pool {
+			let msg = SocketMessage::try_from(message.clone())
+				.map_err(|_| DispatchError::Other("Invalid socket message"))?;
+			<OutboundArrivals<T>>::insert(msg.req_id.sequence, now);
+		}
+		<OutboundPool<T>>::put(pool);
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_utxos(utxos: Vec<UtxoInfoWithSize>) -> Result<(), DispatchError> {
+		for inner in utxos {
+			let hash = inner.hash;
+			let mut utxo =
+				Utxo { inner, status: UtxoStatus::Unconfirmed, voters: BoundedVec::default() };
+			Self::make_available(&hash, &mut utxo);
+			<Utxos<T>>::insert(&hash, utxo);
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config> OnCheckpointSet for Pallet<T> {
+	fn on_checkpoint_set() -> u32 {
+		// the unconfirmed UTXOs have to be proven again against the new chain
+		<ProvenUtxos<T>>::clear(u32::MAX, None).unique
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -563,6 +704,63 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The number of votes required to reach the threshold of the given workflow.
+	pub(crate) fn vote_threshold(workflow: VoteWorkflow) -> u32 {
+		T::Relayers::threshold(<VoteThresholds<T>>::get(workflow))
+	}
+
+	/// Make the given UTXO available, unless it belongs to a user vault whose recovery path is
+	/// about to mature. The recovery path matures `timelock` Bitcoin blocks after the UTXO has been
+	/// included, which is its proven height or else the best known height.
+	pub(crate) fn make_available(utxo_hash: &H256, utxo: &mut Utxo<T::AccountId>) {
+		utxo.status = UtxoStatus::Available;
+
+		let timelock = match Descriptor::<PublicKey>::from_str(&utxo.inner.descriptor) {
+			Ok(descriptor) => match descriptor_recovery_timelock(&descriptor) {
+				Some(timelock) => timelock,
+				None => return,
+			},
+			Err(_) => return,
+		};
+		let included_at =
+			<ProvenUtxos<T>>::get(utxo_hash).unwrap_or_else(T::LightClient::best_height);
+		let matures_at = included_at.saturating_add(timelock);
+
+		if Self::is_near_maturity(matures_at, T::RecoverySafetyMargin::get()) {
+			utxo.status = UtxoStatus::Expired;
+			Self::deposit_event(Event::UtxoExpired { utxo_hash: *utxo_hash, matures_at });
+		} else {
+			<RecoveryMaturities<T>>::insert(utxo_hash, matures_at);
+		}
+	}
+
+	/// Whether a recovery path that matures at the given Bitcoin height matures within `margin`
+	/// blocks from the best known height.
+	fn is_near_maturity(matures_at: u32, margin: u32) -> bool {
+		T::LightClient::best_height().saturating_add(margin) >= matures_at
+	}
+
+	/// Make the given unconfirmed UTXO available if the vote threshold is reached and,
+	/// when SPV proofs are required, its SPV proof has been verified.
+	pub(crate) fn try_confirm_utxo(utxo_hash: &H256, utxo: &mut Utxo<T::AccountId>) {
+		if T::Relayers::count_authorities(utxo.voters.iter())
+			< Self::vote_threshold(VoteWorkflow::Utxo)
+		{
+			return;
+		}
+		if <SpvConfirmations<T>>::get() > 0 && !<ProvenUtxos<T>>::contains_key(utxo_hash) {
+			return;
+		}
+
+		Self::make_available(utxo_hash, utxo);
+		<ProvenUtxos<T>>::remove(utxo_hash);
+		T::Relayers::note_majority_reached(&utxo.voters);
+		// decrement the original submitter's unconfirmed count
+		if let Some(submitter) = utxo.voters.first() {
+			<UnconfirmedUtxoCount<T>>::mutate(submitter, |c| *c = c.saturating_sub(1));
+		}
+	}
+
 	/// Helper function to verify if an authority is a valid relayer
 	fn verify_authority(authority_id: &T::AccountId) -> Result<(), InvalidTransaction> {
 		if !T::Relayers::is_authority(authority_id) {
@@ -632,6 +830,46 @@ impl<T: Config> Pallet<T> {
 			.build()
 	}
 
+	/// Verify a UTXO SPV proof submission.
+	pub fn verify_utxo_proof_submission(
+		utxo_proof_submission: &UtxoProofSubmission<T::AccountId>,
+		signature: &T::Signature,
+	) -> TransactionValidity {
+		let UtxoProofSubmission { authority_id, proofs } = utxo_proof_submission;
+
+		// reject if the number of proofs exceeds the per-submission limit.
+		if proofs.len() > crate::MAX_UTXOS_PER_SUBMISSION
+			|| proofs.iter().any(|x| x.raw_tx.len() > crate::MAX_RAW_TX_SIZE)
+		{
+			return InvalidTransaction::ExhaustsResources.into();
+		}
+
+		// verify if the authority is a selected relayer.
+		Self::verify_authority(authority_id)?;
+
+		// verify if the signature was originated from the authority.
+		let message = [
+			keccak_256("UtxoProofsSubmission".as_bytes()).as_slice(),
+			format!(
+				"{}",
+				proofs
+					.iter()
+					.map(|x| hex::encode(x.utxo_hash))
+					.collect::<Vec<String>>()
+					.concat()
+			)
+			.as_bytes(),
+		]
+		.concat();
+		Self::verify_signature(&message, signature, authority_id)?;
+
+		ValidTransaction::with_tag_prefix("UtxoProofsSubmission")
+			.priority(TransactionPriority::MAX)
+			.and_provides((authority_id, signature))
+			.propagate(true)
+			.build()
+	}
+
 	/// Verify a spend UTXO submission.
 	pub fn verify_broadcast_submission(
 		broadcast_submission: &BroadcastSubmission<T::AccountId>,
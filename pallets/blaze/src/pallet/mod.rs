@@ -2,7 +2,8 @@ mod impls;
 
 use crate::{
 	migrations, weights::WeightInfo, BTCTransaction, BroadcastSubmission, FeeRateSubmission,
-	SocketMessagesSubmission, Utxo, UtxoStatus, UtxoSubmission,
+	SocketMessagesSubmission, Utxo, UtxoProof, UtxoProofSubmission, UtxoStatus, UtxoSubmission,
+	VoteWorkflow,
 };
 
 use frame_support::{
@@ -12,18 +13,19 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 
 use bp_btc_relay::{
-	blaze::{UtxoInfo, UtxoInfoWithSize},
-	traits::{BlazeManager, PoolManager, SocketQueueManager},
-	utils::estimate_finalized_input_size,
-	UnboundedBytes,
+	blaze::{BatchPolicy, UtxoInfo, UtxoInfoWithSize},
+	light_client::verify_tx_output,
+	traits::{BlazeManager, LightClientManager, PoolManager, SocketQueueManager},
+	utils::estimate_descriptor_input_size,
+	Descriptor, PublicKey, UnboundedBytes,
 };
 use bp_cccp::{traits::SocketVerifier, SocketMessage};
-use bp_staking::{traits::Authorities, MAX_AUTHORITIES};
+use bp_staking::{traits::Authorities, MajorityThreshold, MAX_AUTHORITIES};
 use parity_scale_codec::{alloc::string::ToString, Encode};
 use sp_core::{H256, U256};
 use sp_io::hashing::keccak_256;
 use sp_runtime::traits::{Block, Header, IdentifyAccount, Verify};
-use sp_std::{fmt::Display, vec, vec::Vec};
+use sp_std::{fmt::Display, str::FromStr, vec, vec::Vec};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -47,12 +49,22 @@ pub mod pallet {
 		type SocketQueue: SocketVerifier<Self::AccountId> + SocketQueueManager<Self::AccountId>;
 		/// The Bitcoin registration pool pallet.
 		type RegistrationPool: PoolManager<Self::AccountId>;
+		/// The Bitcoin light client.
+		type LightClient: LightClientManager;
 		/// The fee rate expiration in blocks.
 		#[pallet::constant]
 		type FeeRateExpiration: Get<u32>;
 		/// The threshold for fault tolerance.
 		#[pallet::constant]
 		type ToleranceThreshold: Get<u32>;
+		/// The number of Bitcoin blocks before the recovery path of a user vault matures, from
+		/// which its UTXOs are swept into the system vault instead of being used for outbounds.
+		#[pallet::constant]
+		type RecoverySweepWindow: Get<u32>;
+		/// The number of Bitcoin blocks before the recovery path of a user vault matures, from
+		/// which its UTXOs are no longer made available. Must be lower than the sweep window.
+		#[pallet::constant]
+		type RecoverySafetyMargin: Get<u32>;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -79,6 +91,8 @@ pub mod pallet {
 		NoWritingSameValue,
 		/// The activation status is invalid.
 		InvalidActivationState,
+		/// SPV proofs are not required.
+		SpvNotRequired,
 	}
 
 	#[pallet::event]
@@ -98,6 +112,16 @@ pub mod pallet {
 		BroadcastPolled { authority_id: T::AccountId, txid: H256, is_confirmed: bool },
 		/// The socket message has been submitted.
 		SocketMessageSubmitted { authority_id: T::AccountId, message: UnboundedBytes },
+		/// The vote threshold of a relay workflow has been set.
+		VoteThresholdSet { workflow: VoteWorkflow, old: MajorityThreshold, new: MajorityThreshold },
+		/// The required SPV confirmations has been set.
+		SpvConfirmationsSet { old: u32, new: u32 },
+		/// The SPV proof of the UTXO has been verified.
+		UtxoProven { authority_id: T::AccountId, utxo_hash: H256, height: u32 },
+		/// The batching policy of outbound requests has been set.
+		BatchPolicySet { old: BatchPolicy, new: BatchPolicy },
+		/// The UTXO has not been made available since its recovery path is about to mature.
+		UtxoExpired { utxo_hash: H256, matures_at: u32 },
 	}
 
 	#[pallet::storage]
@@ -149,6 +173,14 @@ pub mod pallet {
 	/// Value: SocketMessage's in bytes (The vector will be cleared once SocketQueue builds the PSBT)
 	pub type OutboundPool<T: Config> = StorageValue<_, Vec<UnboundedBytes>, ValueQuery>;
 
+	#[pallet::storage]
+	/// The block number at which each pending outbound Socket message entered the pool.
+	/// The messages pooled before the arrivals were tracked have no entry.
+	///
+	/// Key: The sequence ID of the Socket message
+	/// Value: The block number of the submission
+	pub type OutboundArrivals<T: Config> = StorageMap<_, Twox64Concat, U256, BlockNumberFor<T>>;
+
 	#[pallet::storage]
 	#[pallet::unbounded]
 	/// The fee rates submitted by the relayers.
@@ -161,6 +193,37 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	/// The vote threshold of each relay workflow. Defaults to the simple majority of the relayers.
+	///
+	/// Key: The relay workflow
+	/// Value: The required vote threshold
+	pub type VoteThresholds<T: Config> =
+		StorageMap<_, Twox64Concat, VoteWorkflow, MajorityThreshold, ValueQuery>;
+
+	#[pallet::storage]
+	/// The number of Bitcoin confirmations required for the SPV proof of a UTXO.
+	/// If zero, UTXOs are confirmed by the relayer votes only.
+	pub type SpvConfirmations<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	/// The unconfirmed UTXOs whose SPV proof has been verified.
+	///
+	/// Key: The UTXO hash
+	/// Value: The height of the block that includes the UTXO
+	pub type ProvenUtxos<T: Config> = StorageMap<_, Twox64Concat, H256, u32>;
+
+	#[pallet::storage]
+	/// The available UTXOs of user vaults that have a timelocked recovery path.
+	///
+	/// Key: The UTXO hash
+	/// Value: The Bitcoin height at which the recovery path matures
+	pub type RecoveryMaturities<T: Config> = StorageMap<_, Twox64Concat, H256, u32>;
+
+	#[pallet::storage]
+	/// The batching policy of outbound requests.
+	pub type OutboundBatchPolicy<T: Config> = StorageValue<_, BatchPolicy, ValueQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_runtime_upgrade() -> Weight {
@@ -229,16 +292,8 @@ pub mod pallet {
 					}
 					u.voters.try_push(authority_id.clone()).map_err(|_| Error::<T>::OutOfRange)?;
 
-					// check if the utxo majority is reached
-					if u.voters.len() as u32 >= T::Relayers::majority() {
-						u.status = UtxoStatus::Available;
-						// decrement the original submitter's unconfirmed count
-						if let Some(submitter) = u.voters.first() {
-							<UnconfirmedUtxoCount<T>>::mutate(submitter, |c| {
-								*c = c.saturating_sub(1)
-							});
-						}
-					}
+					// check if the utxo majority is reached (and proven if required)
+					Self::try_confirm_utxo(&utxo_hash, &mut u);
 					<Utxos<T>>::insert(&utxo_hash, u.clone());
 					Self::deposit_event(Event::UtxoSubmitted {
 						authority_id: authority_id.clone(),
@@ -253,13 +308,12 @@ pub mod pallet {
 					}
 
 					let voters = vec![authority_id.clone()];
-					let input_vbytes = if let Some(input_vbytes) =
-						estimate_finalized_input_size(&descriptor.script_code().unwrap(), None)
-					{
-						input_vbytes
-					} else {
-						continue;
-					};
+					let input_vbytes =
+						if let Some(input_vbytes) = estimate_descriptor_input_size(&descriptor) {
+							input_vbytes
+						} else {
+							continue;
+						};
 					<Utxos<T>>::insert(
 						&utxo_hash,
 						Utxo {
@@ -312,7 +366,10 @@ pub mod pallet {
 				.map_err(|_| Error::<T>::OutOfRange)?;
 
 			let mut is_confirmed = false;
-			if pending_txs.voters.len() as u32 >= T::Relayers::majority() {
+			if T::Relayers::count_authorities(pending_txs.voters.iter())
+				>= Self::vote_threshold(VoteWorkflow::Broadcast)
+			{
+				T::Relayers::note_majority_reached(&pending_txs.voters);
 				<ConfirmedTxs<T>>::insert(&txid, pending_txs);
 
 				match <PendingTxs<T>>::take(&txid) {
@@ -322,6 +379,7 @@ pub mod pallet {
 								Some(mut utxo) => {
 									utxo.status = UtxoStatus::Used;
 									<Utxos<T>>::insert(input.hash, utxo);
+									<RecoveryMaturities<T>>::remove(input.hash);
 								},
 								None => return Err(Error::<T>::UtxoDNE.into()),
 							}
@@ -423,6 +481,10 @@ pub mod pallet {
 				}
 				pool_sequences.push(msg.req_id.sequence);
 				pool.push(message.clone());
+				<OutboundArrivals<T>>::insert(
+					msg.req_id.sequence,
+					<frame_system::Pallet<T>>::block_number(),
+				);
 
 				Self::deposit_event(Event::SocketMessageSubmitted {
 					authority_id: authority_id.clone(),
@@ -469,28 +531,26 @@ pub mod pallet {
 					// if duplicate utxo is found, skip
 					continue;
 				}
-				let input_vbytes = if let Some(input_vbytes) =
-					estimate_finalized_input_size(&descriptor.script_code().unwrap(), None)
-				{
-					input_vbytes
-				} else {
-					continue;
-				};
-				<Utxos<T>>::insert(
-					&utxo_hash,
-					Utxo {
-						inner: UtxoInfoWithSize {
-							hash: utxo_hash,
-							txid,
-							vout,
-							amount,
-							descriptor: descriptor.to_string(),
-							input_vbytes,
-						},
-						status: UtxoStatus::Available,
-						voters: BoundedVec::default(),
+				let input_vbytes =
+					if let Some(input_vbytes) = estimate_descriptor_input_size(&descriptor) {
+						input_vbytes
+					} else {
+						continue;
+					};
+				let mut utxo = Utxo {
+					inner: UtxoInfoWithSize {
+						hash: utxo_hash,
+						txid,
+						vout,
+						amount,
+						descriptor: descriptor.to_string(),
+						input_vbytes,
 					},
-				);
+					status: UtxoStatus::Unconfirmed,
+					voters: BoundedVec::default(),
+				};
+				Self::make_available(&utxo_hash, &mut utxo);
+				<Utxos<T>>::insert(&utxo_hash, utxo);
 				Self::deposit_event(Event::UtxoForcePushed { utxo_hash });
 			}
 
@@ -516,6 +576,134 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_vote_threshold())]
+		/// Set the vote threshold of the given relay workflow.
+		pub fn set_vote_threshold(
+			origin: OriginFor<T>,
+			workflow: VoteWorkflow,
+			threshold: MajorityThreshold,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let old = <VoteThresholds<T>>::get(workflow);
+			ensure!(old != threshold, Error::<T>::NoWritingSameValue);
+
+			<VoteThresholds<T>>::insert(workflow, threshold);
+			Self::deposit_event(Event::VoteThresholdSet { workflow, old, new: threshold });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_spv_confirmations())]
+		/// Set the number of Bitcoin confirmations required for the SPV proof of a UTXO.
+		/// Zero disables SPV proofs.
+		pub fn set_spv_confirmations(
+			origin: OriginFor<T>,
+			confirmations: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let old = <SpvConfirmations<T>>::get();
+			ensure!(old != confirmations, Error::<T>::NoWritingSameValue);
+
+			<SpvConfirmations<T>>::put(confirmations);
+			Self::deposit_event(Event::SpvConfirmationsSet { old, new: confirmations });
+			Ok(().into())
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::submit_utxo_proofs(
+			utxo_proof_submission.proofs.len() as u32
+		))]
+		/// Submit SPV proofs of unconfirmed UTXOs. A UTXO will be available once it is proven
+		/// and the majority of the relayers approve it.
+		pub fn submit_utxo_proofs(
+			origin: OriginFor<T>,
+			utxo_proof_submission: UtxoProofSubmission<T::AccountId>,
+			_signature: T::Signature,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			Self::ensure_activation(true)?;
+
+			let confirmations = <SpvConfirmations<T>>::get();
+			ensure!(confirmations > 0, Error::<T>::SpvNotRequired);
+
+			let UtxoProofSubmission { authority_id, proofs } = utxo_proof_submission;
+			ensure!(!proofs.is_empty(), Error::<T>::EmptySubmission);
+
+			for proof in proofs {
+				let UtxoProof { utxo_hash, height, merkle_proof, raw_tx } = proof;
+
+				let mut u = match <Utxos<T>>::get(&utxo_hash) {
+					Some(u) if u.status == UtxoStatus::Unconfirmed => u,
+					_ => continue,
+				};
+				if <ProvenUtxos<T>>::contains_key(&utxo_hash) {
+					continue;
+				}
+				// the proven transaction must create the UTXO as it was submitted
+				let script_pubkey = match Descriptor::<PublicKey>::from_str(&u.inner.descriptor) {
+					Ok(descriptor) => descriptor.script_pubkey(),
+					Err(_) => continue,
+				};
+				if !verify_tx_output(
+					&raw_tx,
+					u.inner.txid,
+					u.inner.vout,
+					u.inner.amount,
+					&script_pubkey,
+				) {
+					crate::log!(warn, "Invalid transaction of UTXO {:?}", utxo_hash);
+					continue;
+				}
+				if let Err(e) = T::LightClient::verify_tx_inclusion(
+					&raw_tx,
+					&merkle_proof,
+					height,
+					confirmations,
+				) {
+					crate::log!(warn, "Invalid SPV proof of UTXO {:?}: {:?}", utxo_hash, e);
+					continue;
+				}
+
+				<ProvenUtxos<T>>::insert(&utxo_hash, height);
+				Self::deposit_event(Event::UtxoProven {
+					authority_id: authority_id.clone(),
+					utxo_hash,
+					height,
+				});
+
+				Self::try_confirm_utxo(&utxo_hash, &mut u);
+				if u.status != UtxoStatus::Unconfirmed {
+					<Utxos<T>>::insert(&utxo_hash, u.clone());
+					Self::deposit_event(Event::UtxoSubmitted {
+						authority_id: authority_id.clone(),
+						utxo_hash,
+						status: u.status,
+					});
+				}
+			}
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_batch_policy())]
+		/// Set the batching policy of outbound requests.
+		pub fn set_batch_policy(
+			origin: OriginFor<T>,
+			policy: BatchPolicy,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(policy.min_batch_size > 0 && policy.max_outputs > 0, Error::<T>::OutOfRange);
+
+			let old = <OutboundBatchPolicy<T>>::get();
+			ensure!(old != policy, Error::<T>::NoWritingSameValue);
+
+			<OutboundBatchPolicy<T>>::put(policy);
+			Self::deposit_event(Event::BatchPolicySet { old, new: policy });
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -542,6 +730,9 @@ pub mod pallet {
 				Call::remove_outbound_messages { remove_submission, signature } => {
 					Self::verify_remove_outbound_messages(remove_submission, signature)
 				},
+				Call::submit_utxo_proofs { utxo_proof_submission, signature } => {
+					Self::verify_utxo_proof_submission(utxo_proof_submission, signature)
+				},
 				_ => InvalidTransaction::Call.into(),
 			}
 		}
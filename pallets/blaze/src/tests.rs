@@ -0,0 +1,164 @@
+use crate::{mock::*, OutboundArrivals, OutboundBatchPolicy, OutboundPool};
+use bp_btc_relay::{
+	blaze::{BatchPolicy, OutboundPriority},
+	traits::BlazeManager,
+	UnboundedBytes,
+};
+use bp_cccp::{Instruction, RequestID, SocketMessage, TaskParams};
+use sp_core::{H160, U256};
+
+fn outbound(sequence: u64, amount: u64) -> UnboundedBytes {
+	SocketMessage {
+		req_id: RequestID { chain: vec![0; 4], round_id: 1.into(), sequence: sequence.into() },
+		status: 1.into(),
+		ins_code: Instruction { chain: vec![0; 4], method: vec![0; 16] },
+		params: TaskParams {
+			token_idx0: vec![0; 32],
+			token_idx1: vec![0; 32],
+			refund: H160::zero(),
+			to: H160::zero(),
+			amount: amount.into(),
+			variants: vec![],
+		},
+	}
+	.encode()
+}
+
+/// Push an outbound request that arrived at the given block into the pool
+fn submit_outbound(sequence: u64, amount: u64, arrival: u64) -> UnboundedBytes {
+	let raw = outbound(sequence, amount);
+	OutboundPool::<Test>::append(raw.clone());
+	OutboundArrivals::<Test>::insert(U256::from(sequence), arrival);
+	raw
+}
+
+#[test]
+fn outbound_batch_waits_for_the_window() {
+	new_test_ext().execute_with(|| {
+		OutboundBatchPolicy::<Test>::put(BatchPolicy {
+			min_batch_size: 3,
+			max_wait_blocks: 10,
+			..Default::default()
+		});
+		assert!(Blaze::get_outbound_batch(100).is_empty());
+
+		let first = submit_outbound(1, 1_000, 5);
+		let second = submit_outbound(2, 1_000, 7);
+		assert_eq!(Blaze::get_outbound_pool_len(), 2);
+
+		// neither full nor expired
+		assert!(Blaze::get_outbound_batch(14).is_empty());
+		// the oldest request waited long enough
+		assert_eq!(Blaze::get_outbound_batch(15), vec![first.clone(), second.clone()]);
+
+		// the pool is full
+		let third = submit_outbound(3, 1_000, 8);
+		assert_eq!(Blaze::get_outbound_batch(8), vec![first, second, third]);
+	});
+}
+
+#[test]
+fn outbound_batch_waits_until_full_without_max_wait_blocks() {
+	new_test_ext().execute_with(|| {
+		OutboundBatchPolicy::<Test>::put(BatchPolicy {
+			min_batch_size: 2,
+			max_wait_blocks: 0,
+			..Default::default()
+		});
+
+		let first = submit_outbound(1, 1_000, 1);
+		assert!(Blaze::get_outbound_batch(u64::MAX).is_empty());
+
+		let second = submit_outbound(2, 1_000, 2);
+		assert_eq!(Blaze::get_outbound_batch(2), vec![first, second]);
+	});
+}
+
+#[test]
+fn outbound_batch_is_ordered_by_priority() {
+	new_test_ext().execute_with(|| {
+		let first = submit_outbound(1, 5_000, 3);
+		let second = submit_outbound(2, 1_000, 1);
+		let third = submit_outbound(3, 5_000, 2);
+		// requests submitted before the arrivals were tracked are the oldest
+		let legacy = outbound(4, 1_000);
+		OutboundPool::<Test>::append(legacy.clone());
+
+		assert_eq!(
+			Blaze::get_outbound_batch(3),
+			vec![legacy.clone(), second.clone(), third.clone(), first.clone()]
+		);
+
+		// requests with the same amount are ordered by age
+		OutboundBatchPolicy::<Test>::put(BatchPolicy {
+			priority: OutboundPriority::Amount,
+			..Default::default()
+		});
+		assert_eq!(Blaze::get_outbound_batch(3), vec![third, first, legacy, second]);
+	});
+}
+
+#[test]
+fn outbound_batch_includes_overdue_requests_regardless_of_amount() {
+	new_test_ext().execute_with(|| {
+		OutboundBatchPolicy::<Test>::put(BatchPolicy {
+			priority: OutboundPriority::Amount,
+			max_age_blocks: 10,
+			..Default::default()
+		});
+		let small = submit_outbound(1, 1_000, 0);
+		let smaller = submit_outbound(2, 500, 2);
+		let large = submit_outbound(3, 9_000, 8);
+		let larger = submit_outbound(4, 10_000, 9);
+
+		// no request has reached the age cap yet
+		assert_eq!(
+			Blaze::get_outbound_batch(9),
+			vec![larger.clone(), large.clone(), small.clone(), smaller.clone()]
+		);
+		// the small request arrived at block 0 and reached the age cap
+		assert_eq!(
+			Blaze::get_outbound_batch(10),
+			vec![small.clone(), larger.clone(), large.clone(), smaller.clone()]
+		);
+		// the overdue requests are ordered by age
+		assert_eq!(Blaze::get_outbound_batch(12), vec![small, smaller, larger, large]);
+	});
+}
+
+#[test]
+fn outbound_batch_tells_untracked_requests_from_requests_of_block_zero() {
+	new_test_ext().execute_with(|| {
+		OutboundBatchPolicy::<Test>::put(BatchPolicy {
+			min_batch_size: 2,
+			max_wait_blocks: 10,
+			..Default::default()
+		});
+
+		// a request that arrived at block 0 waits for the window
+		let first = submit_outbound(1, 1_000, 0);
+		assert!(Blaze::get_outbound_batch(9).is_empty());
+		assert_eq!(Blaze::get_outbound_batch(10), vec![first.clone()]);
+
+		// a request submitted before the arrivals were tracked has already waited long enough
+		OutboundPool::<Test>::kill();
+		let legacy = outbound(2, 1_000);
+		OutboundPool::<Test>::append(legacy.clone());
+		assert!(!OutboundArrivals::<Test>::contains_key(U256::from(2)));
+		assert_eq!(Blaze::get_outbound_batch(1), vec![legacy]);
+	});
+}
+
+#[test]
+fn clear_outbound_pool_removes_the_arrivals() {
+	new_test_ext().execute_with(|| {
+		let first = submit_outbound(1, 1_000, 1);
+		let second = submit_outbound(2, 1_000, 1);
+
+		Blaze::clear_outbound_pool(vec![first]);
+		assert_eq!(Blaze::get_outbound_pool(), vec![second]);
+		assert_eq!(Blaze::get_outbound_pool_len(), 1);
+		assert!(!OutboundArrivals::<Test>::contains_key(U256::from(1)));
+		assert!(OutboundArrivals::<Test>::contains_key(U256::from(2)));
+	});
+}
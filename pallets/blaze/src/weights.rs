@@ -47,6 +47,10 @@ pub trait WeightInfo {
 	fn submit_outbound_requests(n: u32, total_msg_bytes: u32) -> Weight;
 	fn force_push_utxos() -> Weight;
 	fn remove_outbound_messages() -> Weight;
+	fn set_vote_threshold() -> Weight;
+	fn set_spv_confirmations() -> Weight;
+	fn submit_utxo_proofs(n: u32) -> Weight;
+	fn set_batch_policy() -> Weight;
 }
 
 /// Weights for `pallet_blaze` using the Substrate node and recommended hardware.
@@ -118,6 +122,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `BtcSocketQueue::Authority` (`max_values`: Some(1), `max_size`: Some(20), mode: `MaxEncodedLen`)
 	/// Storage: `BtcSocketQueue::Socket` (r:1 w:0)
 	/// Proof: `BtcSocketQueue::Socket` (`max_values`: Some(1), `max_size`: Some(20), mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::OutboundArrivals` (r:0 w:10)
+	/// Proof: `Blaze::OutboundArrivals` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	///
 	/// The `n` parameter represents the number of messages in the submission.
 	/// The `total_msg_bytes` parameter represents the total byte length of all messages.
@@ -126,17 +132,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// from cloning, SCALE decoding/encoding, and keccak256 hashing.
 	/// Sized so that at most 5 worst-case calls (n=10, total_msg_bytes=20480) fit per block
 	/// (Normal class budget: 375_000_000_000; target per call: 75_000_000_000).
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
 	fn submit_outbound_requests(n: u32, total_msg_bytes: u32) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `1485`
-		// Minimum execution time: 18_800_000 picoseconds.
 		Weight::from_parts(60_000_000_000, 0)
 			// base: OutboundPool read + write
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 			// per message: Authority + Socket reads for verify_socket_message
 			.saturating_add(T::DbWeight::get().reads(2_u64.saturating_mul(n as u64)))
+			// per message: OutboundArrivals write
+			.saturating_add(T::DbWeight::get().writes(n as u64))
 			// per byte: clone, SCALE decode/encode, keccak256 hashing, storage encoding
 			.saturating_add(Weight::from_parts(700_000_u64.saturating_mul(total_msg_bytes as u64), 0))
 	}
@@ -172,6 +177,58 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Blaze::VoteThresholds` (r:1 w:1)
+	/// Proof: `Blaze::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_vote_threshold() -> Weight {
+		Weight::from_parts(17_930_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Blaze::SpvConfirmations` (r:1 w:1)
+	/// Proof: `Blaze::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_spv_confirmations() -> Weight {
+		Weight::from_parts(17_520_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Blaze::IsActivated` (r:1 w:0)
+	/// Proof: `Blaze::IsActivated` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::SpvConfirmations` (r:1 w:0)
+	/// Proof: `Blaze::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::Utxos` (r:50 w:50)
+	/// Proof: `Blaze::Utxos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::ProvenUtxos` (r:50 w:50)
+	/// Proof: `Blaze::ProvenUtxos` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::MainChain` (r:50 w:0)
+	/// Proof: `BtcLightClient::MainChain` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::Headers` (r:50 w:0)
+	/// Proof: `BtcLightClient::Headers` (`max_values`: None, `max_size`: Some(164), added: 2639, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::BestHeight` (r:1 w:0)
+	/// Proof: `BtcLightClient::BestHeight` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::VoteThresholds` (r:1 w:0)
+	/// Proof: `Blaze::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::SelectedRelayers` (r:1 w:0)
+	/// Proof: `RelayManager::SelectedRelayers` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 50]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn submit_utxo_proofs(n: u32) -> Weight {
+		Weight::from_parts(34_518_000, 4687)
+			.saturating_add(Weight::from_parts(46_204_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2639).saturating_mul(n.into()))
+	}
+	/// Storage: `Blaze::OutboundBatchPolicy` (r:1 w:1)
+	/// Proof: `Blaze::OutboundBatchPolicy` (`max_values`: Some(1), `max_size`: Some(13), added: 508, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_batch_policy() -> Weight {
+		Weight::from_parts(17_310_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -242,6 +299,8 @@ impl WeightInfo for () {
 	/// Proof: `BtcSocketQueue::Authority` (`max_values`: Some(1), `max_size`: Some(20), mode: `MaxEncodedLen`)
 	/// Storage: `BtcSocketQueue::Socket` (r:1 w:0)
 	/// Proof: `BtcSocketQueue::Socket` (`max_values`: Some(1), `max_size`: Some(20), mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::OutboundArrivals` (r:0 w:10)
+	/// Proof: `Blaze::OutboundArrivals` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	///
 	/// The `n` parameter represents the number of messages in the submission.
 	/// The `total_msg_bytes` parameter represents the total byte length of all messages.
@@ -250,17 +309,16 @@ impl WeightInfo for () {
 	/// from cloning, SCALE decoding/encoding, and keccak256 hashing.
 	/// Sized so that at most 5 worst-case calls (n=10, total_msg_bytes=20480) fit per block
 	/// (Normal class budget: 375_000_000_000; target per call: 75_000_000_000).
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
 	fn submit_outbound_requests(n: u32, total_msg_bytes: u32) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `1485`
-		// Minimum execution time: 18_800_000 picoseconds.
 		Weight::from_parts(60_000_000_000, 0)
 			// base: OutboundPool read + write
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 			// per message: Authority + Socket reads for verify_socket_message
 			.saturating_add(RocksDbWeight::get().reads(2_u64.saturating_mul(n as u64)))
+			// per message: OutboundArrivals write
+			.saturating_add(RocksDbWeight::get().writes(n as u64))
 			// per byte: clone, SCALE decode/encode, keccak256 hashing, storage encoding
 			.saturating_add(Weight::from_parts(700_000_u64.saturating_mul(total_msg_bytes as u64), 0))
 	}
@@ -296,4 +354,56 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-}
\ No newline at end of file
+	/// Storage: `Blaze::VoteThresholds` (r:1 w:1)
+	/// Proof: `Blaze::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_vote_threshold() -> Weight {
+		Weight::from_parts(17_930_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Blaze::SpvConfirmations` (r:1 w:1)
+	/// Proof: `Blaze::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_spv_confirmations() -> Weight {
+		Weight::from_parts(17_520_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Blaze::IsActivated` (r:1 w:0)
+	/// Proof: `Blaze::IsActivated` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::SpvConfirmations` (r:1 w:0)
+	/// Proof: `Blaze::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::Utxos` (r:50 w:50)
+	/// Proof: `Blaze::Utxos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::ProvenUtxos` (r:50 w:50)
+	/// Proof: `Blaze::ProvenUtxos` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::MainChain` (r:50 w:0)
+	/// Proof: `BtcLightClient::MainChain` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::Headers` (r:50 w:0)
+	/// Proof: `BtcLightClient::Headers` (`max_values`: None, `max_size`: Some(164), added: 2639, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::BestHeight` (r:1 w:0)
+	/// Proof: `BtcLightClient::BestHeight` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::VoteThresholds` (r:1 w:0)
+	/// Proof: `Blaze::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::SelectedRelayers` (r:1 w:0)
+	/// Proof: `RelayManager::SelectedRelayers` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 50]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn submit_utxo_proofs(n: u32) -> Weight {
+		Weight::from_parts(34_518_000, 4687)
+			.saturating_add(Weight::from_parts(46_204_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2639).saturating_mul(n.into()))
+	}
+	/// Storage: `Blaze::OutboundBatchPolicy` (r:1 w:1)
+	/// Proof: `Blaze::OutboundBatchPolicy` (`max_values`: Some(1), `max_size`: Some(13), added: 508, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_batch_policy() -> Weight {
+		Weight::from_parts(17_310_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
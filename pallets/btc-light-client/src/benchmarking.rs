@@ -0,0 +1,90 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use bp_btc_relay::light_client::{target_from_compact, TARGET_SPACING};
+use frame_benchmarking::v2::*;
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use parity_scale_codec::Decode;
+use sp_core::H256;
+use sp_std::{vec, vec::Vec};
+
+/// The regtest genesis block header.
+const REGTEST_GENESIS: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4adae5494dffff7f2002000000";
+
+fn genesis_header() -> RawBlockHeader {
+	let mut raw = [0u8; 80];
+	raw.copy_from_slice(&hex::decode(REGTEST_GENESIS).expect("Valid hex"));
+	raw
+}
+
+/// Mine `n` regtest headers on top of the given parent.
+fn mine_headers(parent: &RawBlockHeader, n: u32) -> Vec<RawBlockHeader> {
+	let mut parent = BlockHeader::from_bytes(parent);
+	let mut headers = vec![];
+	for i in 0..n {
+		let mut header = BlockHeader {
+			version: 0x20000000,
+			prev_block_hash: parent.hash(),
+			merkle_root: H256::from_low_u64_be(i as u64 + 1),
+			time: parent.time + TARGET_SPACING,
+			bits: parent.bits,
+			nonce: 0,
+		};
+		let target = target_from_compact(header.bits).expect("Valid target");
+		while !header.check_pow(target) {
+			header.nonce += 1;
+		}
+		headers.push(header.to_bytes());
+		parent = header;
+	}
+	headers
+}
+
+// The headers are mined on the fly, thus the benchmarks require the regtest network.
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn submit_headers(n: Linear<1, 100>) {
+		assert_eq!(T::BitcoinNetwork::get(), bp_btc_relay::Network::Regtest);
+
+		let genesis = genesis_header();
+		Pallet::<T>::set_checkpoint(RawOrigin::Root.into(), genesis, 0, 0)
+			.expect("Valid checkpoint");
+
+		let authority: T::AccountId = account("authority", 0, 0);
+		let header_submission =
+			HeaderSubmission { authority_id: authority, headers: mine_headers(&genesis, n) };
+		let signature = T::Signature::decode(&mut [0u8; 65].as_ref()).expect("Valid signature");
+
+		#[extrinsic_call]
+		_(RawOrigin::None, header_submission, signature);
+
+		assert_eq!(<BestHeight<T>>::get(), n);
+	}
+
+	#[benchmark]
+	fn set_checkpoint(n: Linear<1, 1_000>) {
+		assert_eq!(T::BitcoinNetwork::get(), bp_btc_relay::Network::Regtest);
+
+		// Setup: Initialize the light client and store up to `n` headers
+		let genesis = genesis_header();
+		Pallet::<T>::set_checkpoint(RawOrigin::Root.into(), genesis, 0, 0)
+			.expect("Valid checkpoint");
+		let headers = mine_headers(&genesis, n);
+		for raw in &headers[..n as usize - 1] {
+			Pallet::<T>::import_header(raw).expect("Valid header");
+		}
+		let header_count = <Headers<T>>::count();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, headers[n as usize - 1], n, header_count);
+
+		assert_eq!(<BestHeight<T>>::get(), n);
+		assert_eq!(<Headers<T>>::count(), 1);
+	}
+
+	impl_benchmark_test_suite!(Pallet, mock::new_test_ext(), mock::Test);
+}
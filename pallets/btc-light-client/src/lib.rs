@@ -0,0 +1,69 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+mod pallet;
+pub mod weights;
+
+pub use pallet::pallet::*;
+pub use weights::WeightInfo;
+
+use bp_btc_relay::light_client::{BlockHeader, RawBlockHeader};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, U256};
+use sp_runtime::transaction_validity::TransactionPriority;
+use sp_std::vec::Vec;
+
+pub(crate) const LOG_TARGET: &'static str = "runtime::btc-light-client";
+
+/// Maximum number of block headers allowed per `submit_headers` submission.
+pub const MAX_HEADERS_PER_SUBMISSION: usize = 100;
+
+/// The number of previous blocks used to calculate the median time past.
+pub const MEDIAN_TIME_SPAN: usize = 11;
+
+/// The transaction pool priority of `submit_headers`. Headers are pre-checked when validated, so
+/// they are not prioritized over other transactions.
+pub const HEADERS_SUBMISSION_PRIORITY: TransactionPriority = TransactionPriority::MAX / 2;
+
+// syntactic sugar for logging.
+#[macro_export]
+macro_rules! log {
+	($level:tt, $patter:expr $(, $values:expr)* $(,)?) => {
+		log::$level!(
+			target: crate::LOG_TARGET,
+			concat!("[{:?}] ⛓️ ", $patter), <frame_system::Pallet<T>>::block_number() $(, $values)*
+		)
+	};
+}
+
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+/// A verified Bitcoin block header with its chain metadata.
+pub struct StoredHeader {
+	/// The block header.
+	pub header: BlockHeader,
+	/// The block height.
+	pub height: u32,
+	/// The accumulated work of the chain since the checkpoint (including this block).
+	pub chain_work: U256,
+	/// The timestamp of the first block of the difficulty adjustment interval.
+	pub interval_start_time: u32,
+	/// The compact target of the last block that was not mined under the min-difficulty rule.
+	pub regular_bits: u32,
+}
+
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// A submission of serialized Bitcoin block headers.
+pub struct HeaderSubmission<AccountId> {
+	/// The authority id.
+	pub authority_id: AccountId,
+	/// The serialized block headers. (ordered from the lowest height)
+	pub headers: Vec<RawBlockHeader>,
+}
@@ -0,0 +1,111 @@
+use crate as pallet_btc_light_client;
+use bp_btc_relay::Network;
+use bp_core::{AccountId, Balance, BlockNumber};
+use bp_staking::traits::Authorities;
+use fp_account::{EthereumSignature, EthereumSigner};
+use frame_support::{construct_runtime, parameter_types, traits::Everything};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		BtcLightClient: pallet_btc_light_client,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: BlockNumber = 256;
+	pub const SS58Prefix: u8 = 42;
+	pub const ExistentialDeposit: u128 = 1;
+	pub const BitcoinNetwork: Network = Network::Regtest;
+	pub const MaxReorgDepth: u32 = 6;
+	pub static HeadersToKeep: u32 = 100;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeTask = RuntimeTask;
+	type Nonce = u64;
+	type Block = Block;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type SingleBlockMigrations = ();
+	type MultiBlockMigrator = ();
+	type PreInherents = ();
+	type PostInherents = ();
+	type PostTransactions = ();
+	type ExtensionsWeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type DoneSlashHandler = ();
+}
+
+// Mock implementations for required traits
+pub struct MockAuthorities;
+impl Authorities<AccountId> for MockAuthorities {
+	fn is_authority(_: &AccountId) -> bool {
+		true
+	}
+
+	fn majority() -> u32 {
+		1
+	}
+
+	fn count() -> usize {
+		1
+	}
+}
+
+impl pallet_btc_light_client::Config for Test {
+	type Signature = EthereumSignature;
+	type Signer = EthereumSigner;
+	type Relayers = MockAuthorities;
+	type BitcoinNetwork = BitcoinNetwork;
+	type MaxReorgDepth = MaxReorgDepth;
+	type HeadersToKeep = HeadersToKeep;
+	type OnCheckpointSet = ();
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
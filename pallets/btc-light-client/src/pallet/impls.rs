@@ -0,0 +1,316 @@
+use super::pallet::*;
+use crate::{
+	HeaderSubmission, StoredHeader, HEADERS_SUBMISSION_PRIORITY, MAX_HEADERS_PER_SUBMISSION,
+	MEDIAN_TIME_SPAN,
+};
+use bp_btc_relay::{
+	light_client::{
+		block_work, next_work_required, target_from_compact, BlockHeader, ChainParams, MerkleProof,
+		RawBlockHeader, DIFFICULTY_ADJUSTMENT_INTERVAL, TARGET_SPACING,
+	},
+	traits::LightClientManager,
+};
+use bp_staking::traits::Authorities;
+use frame_support::{
+	ensure,
+	pallet_prelude::{InvalidTransaction, TransactionValidity, ValidTransaction},
+};
+use scale_info::prelude::{format, string::String};
+use sp_core::{Get, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{traits::Verify, DispatchError};
+use sp_std::{vec, vec::Vec};
+
+impl<T: Config> LightClientManager for Pallet<T> {
+	fn best_height() -> u32 {
+		<BestHeight<T>>::get()
+	}
+
+	fn verify_tx_inclusion(
+		raw_tx: &[u8],
+		merkle_proof: &MerkleProof,
+		height: u32,
+		confirmations: u32,
+	) -> Result<(), DispatchError> {
+		let hash = <MainChain<T>>::get(height).ok_or(Error::<T>::BlockNotInMainChain)?;
+		let stored = <Headers<T>>::get(&hash).ok_or(Error::<T>::BlockNotInMainChain)?;
+
+		// the block itself counts as the first confirmation
+		let best_height = <BestHeight<T>>::get();
+		ensure!(
+			best_height.saturating_sub(height).saturating_add(1) >= confirmations,
+			Error::<T>::InsufficientConfirmations
+		);
+
+		let merkle_root =
+			merkle_proof.compute_root(raw_tx).ok_or(Error::<T>::InvalidMerkleProof)?;
+		ensure!(merkle_root == stored.header.merkle_root, Error::<T>::InvalidMerkleProof);
+
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_benchmark(txid: H256) -> Result<u32, DispatchError> {
+		let parent_hash = <BestBlock<T>>::get().unwrap_or_default();
+		let parent = <Headers<T>>::get(&parent_hash);
+		let height = parent.map(|p| p.height.saturating_add(1)).unwrap_or_default();
+		let header = BlockHeader {
+			version: 0x20000000,
+			prev_block_hash: parent_hash,
+			merkle_root: txid,
+			time: parent.map(|p| p.header.time.saturating_add(TARGET_SPACING)).unwrap_or_default(),
+			bits: ChainParams::from_network(T::BitcoinNetwork::get()).pow_limit_bits,
+			nonce: 0,
+		};
+		let hash = header.hash();
+		<Headers<T>>::insert(
+			&hash,
+			StoredHeader {
+				header,
+				height,
+				chain_work: parent.map(|p| p.chain_work).unwrap_or_default(),
+				interval_start_time: header.time,
+				regular_bits: header.bits,
+			},
+		);
+		<HeadersByHeight<T>>::insert(height, hash, ());
+		<MainChain<T>>::insert(height, hash);
+		<BestBlock<T>>::put(hash);
+		<BestHeight<T>>::put(height);
+		Ok(height)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Decode the given compact target. Returns `None` if the target is easier than the limit.
+	pub(crate) fn checked_target(bits: u32, params: &ChainParams) -> Option<U256> {
+		target_from_compact(bits).filter(|target| *target <= params.pow_limit())
+	}
+
+	/// The compact target that the block at the given height (on top of `parent`) must use.
+	pub fn expected_bits(
+		parent: &StoredHeader,
+		header: &BlockHeader,
+		height: u32,
+		params: &ChainParams,
+	) -> u32 {
+		if height % DIFFICULTY_ADJUSTMENT_INTERVAL != 0 {
+			if params.allow_min_difficulty_blocks {
+				// a block may use the easiest target if it took more than 20 minutes to be mined
+				if header.time > parent.header.time.saturating_add(TARGET_SPACING * 2) {
+					return params.pow_limit_bits;
+				}
+				return parent.regular_bits;
+			}
+			return parent.header.bits;
+		}
+		next_work_required(
+			parent.header.bits,
+			parent.interval_start_time,
+			parent.header.time,
+			params,
+		)
+	}
+
+	/// The median timestamp of the given block and its previous blocks.
+	fn median_time_past(parent: &StoredHeader) -> u32 {
+		let mut times = vec![parent.header.time];
+		let mut cursor = parent.header.prev_block_hash;
+		while times.len() < MEDIAN_TIME_SPAN {
+			match <Headers<T>>::get(&cursor) {
+				Some(ancestor) => {
+					times.push(ancestor.header.time);
+					cursor = ancestor.header.prev_block_hash;
+				},
+				// the ancestors before the checkpoint are unknown
+				None => break,
+			}
+		}
+		times.sort();
+		times[times.len() / 2]
+	}
+
+	/// Verify and import the given block header. Already known headers are skipped.
+	pub(crate) fn import_header(raw: &RawBlockHeader) -> Result<(), DispatchError> {
+		let header = BlockHeader::from_bytes(raw);
+		let hash = header.hash();
+		if <Headers<T>>::contains_key(&hash) {
+			return Ok(());
+		}
+
+		let parent = <Headers<T>>::get(&header.prev_block_hash).ok_or(Error::<T>::UnknownParent)?;
+		let height = parent.height.saturating_add(1);
+		ensure!(
+			height.saturating_add(T::MaxReorgDepth::get()) > <BestHeight<T>>::get(),
+			Error::<T>::ForkTooDeep
+		);
+
+		// verify the difficulty and the proof of work
+		let params = ChainParams::from_network(T::BitcoinNetwork::get());
+		ensure!(
+			header.bits == Self::expected_bits(&parent, &header, height, &params),
+			Error::<T>::InvalidDifficulty
+		);
+		let target =
+			Self::checked_target(header.bits, &params).ok_or(Error::<T>::InvalidDifficulty)?;
+		ensure!(header.check_pow(target), Error::<T>::InsufficientProofOfWork);
+		ensure!(header.time > Self::median_time_past(&parent), Error::<T>::InvalidTimestamp);
+
+		let is_interval_start = height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0;
+		let stored = StoredHeader {
+			header,
+			height,
+			chain_work: parent.chain_work.saturating_add(block_work(target)),
+			interval_start_time: if is_interval_start {
+				header.time
+			} else {
+				parent.interval_start_time
+			},
+			regular_bits: if !is_interval_start && header.bits == params.pow_limit_bits {
+				parent.regular_bits
+			} else {
+				header.bits
+			},
+		};
+		<Headers<T>>::insert(&hash, stored);
+		<HeadersByHeight<T>>::insert(height, hash, ());
+		Self::deposit_event(Event::HeaderImported { hash, height });
+
+		let best_hash = <BestBlock<T>>::get().ok_or(Error::<T>::NotInitialized)?;
+		let best = <Headers<T>>::get(&best_hash).ok_or(Error::<T>::NotInitialized)?;
+		if stored.chain_work > best.chain_work {
+			Self::set_best_block(hash, height, best_hash, best.height)?;
+		}
+		Ok(())
+	}
+
+	/// Set the given block as the best block and rewrite the main chain up to the fork point.
+	fn set_best_block(
+		hash: H256,
+		height: u32,
+		old_best: H256,
+		old_best_height: u32,
+	) -> Result<(), DispatchError> {
+		let max_depth = T::MaxReorgDepth::get();
+
+		// walk back from the new tip until the main chain is reached
+		let mut cursor = hash;
+		let mut cursor_height = height;
+		while <MainChain<T>>::get(cursor_height) != Some(cursor) {
+			ensure!(
+				cursor_height.saturating_add(max_depth) >= old_best_height,
+				Error::<T>::ForkTooDeep
+			);
+			<MainChain<T>>::insert(cursor_height, cursor);
+			cursor = <Headers<T>>::get(&cursor)
+				.ok_or(Error::<T>::UnknownParent)?
+				.header
+				.prev_block_hash;
+			cursor_height = cursor_height.saturating_sub(1);
+		}
+		// remove the stale blocks above the new tip
+		for stale_height in height.saturating_add(1)..=old_best_height {
+			<MainChain<T>>::remove(stale_height);
+		}
+
+		<BestBlock<T>>::put(hash);
+		<BestHeight<T>>::put(height);
+		Self::deposit_event(Event::BestBlockUpdated { hash, height });
+
+		// the fork point is below the previous best block
+		if cursor_height < old_best_height {
+			let depth = old_best_height - cursor_height;
+			crate::log!(
+				info,
+				"Bitcoin chain reorganized: {:?} -> {:?} (depth: {})",
+				old_best,
+				hash,
+				depth
+			);
+			Self::deposit_event(Event::ChainReorganized { old_best, new_best: hash, depth });
+		}
+
+		Self::prune_headers(height);
+		Ok(())
+	}
+
+	/// Prune the headers (including forks) that are older than `HeadersToKeep` blocks.
+	fn prune_headers(best_height: u32) {
+		let keep_from = best_height.saturating_sub(T::HeadersToKeep::get());
+		let mut oldest = <OldestHeight<T>>::get();
+		while oldest < keep_from {
+			<MainChain<T>>::remove(oldest);
+			for (hash, _) in <HeadersByHeight<T>>::drain_prefix(oldest) {
+				<Headers<T>>::remove(&hash);
+			}
+			oldest += 1;
+		}
+		<OldestHeight<T>>::put(oldest);
+	}
+
+	/// Verify a header submission. The headers must be linked to each other, satisfy their own
+	/// targets and extend a known block with at least one new header.
+	pub fn verify_header_submission(
+		header_submission: &HeaderSubmission<T::AccountId>,
+		signature: &T::Signature,
+	) -> TransactionValidity {
+		let HeaderSubmission { authority_id, headers } = header_submission;
+
+		// reject if the number of headers exceeds the per-submission limit.
+		if headers.len() > MAX_HEADERS_PER_SUBMISSION {
+			return InvalidTransaction::ExhaustsResources.into();
+		}
+		let first = match headers.first() {
+			Some(raw) => BlockHeader::from_bytes(raw),
+			None => return InvalidTransaction::Call.into(),
+		};
+
+		// verify if the authority is a selected relayer.
+		if !T::Relayers::is_authority(authority_id) {
+			return InvalidTransaction::BadSigner.into();
+		}
+
+		// verify the linkage and the proof of work of the headers.
+		let params = ChainParams::from_network(T::BitcoinNetwork::get());
+		let mut hashes: Vec<H256> = Vec::with_capacity(headers.len());
+		for raw in headers {
+			let header = BlockHeader::from_bytes(raw);
+			let is_linked = hashes.last().is_none_or(|hash| header.prev_block_hash == *hash);
+			let has_pow = Self::checked_target(header.bits, &params)
+				.is_some_and(|target| header.check_pow(target));
+			if !is_linked || !has_pow {
+				return InvalidTransaction::BadProof.into();
+			}
+			hashes.push(header.hash());
+		}
+
+		// verify if the headers extend a known block and the tip is not imported yet.
+		if !<Headers<T>>::contains_key(&first.prev_block_hash) {
+			return InvalidTransaction::Call.into();
+		}
+		let Some(&tip) = hashes.last() else {
+			return InvalidTransaction::Call.into();
+		};
+		if <Headers<T>>::contains_key(&tip) {
+			return InvalidTransaction::Stale.into();
+		}
+
+		// verify if the signature was originated from the authority.
+		let message = [
+			keccak_256("HeadersSubmission".as_bytes()).as_slice(),
+			format!("{}", hashes.iter().map(hex::encode).collect::<Vec<String>>().concat())
+				.as_bytes(),
+		]
+		.concat();
+		if !signature.verify(&*message, authority_id) {
+			return InvalidTransaction::BadProof.into();
+		}
+
+		// submissions of the same tip are deduplicated.
+		ValidTransaction::with_tag_prefix("HeadersSubmission")
+			.priority(HEADERS_SUBMISSION_PRIORITY)
+			.and_provides(tip)
+			.propagate(true)
+			.build()
+	}
+}
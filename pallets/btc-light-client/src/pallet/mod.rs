@@ -0,0 +1,229 @@
+mod impls;
+
+use crate::{weights::WeightInfo, HeaderSubmission, StoredHeader};
+
+use frame_support::{pallet_prelude::*, traits::StorageVersion};
+use frame_system::pallet_prelude::*;
+
+use bp_btc_relay::{
+	light_client::{
+		block_work, BlockHeader, ChainParams, RawBlockHeader, DIFFICULTY_ADJUSTMENT_INTERVAL,
+	},
+	traits::OnCheckpointSet,
+	Network,
+};
+use bp_staking::traits::Authorities;
+use parity_scale_codec::Encode;
+use sp_core::H256;
+use sp_runtime::traits::{IdentifyAccount, Verify};
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The signature signed by the issuer.
+		type Signature: Verify<Signer = Self::Signer> + Encode + Decode + Parameter;
+		/// The signer of the message.
+		type Signer: IdentifyAccount<AccountId = Self::AccountId> + Encode + Decode + MaxEncodedLen;
+		/// The Bifrost relayers.
+		type Relayers: Authorities<Self::AccountId>;
+		/// The target Bitcoin network.
+		type BitcoinNetwork: Get<Network>;
+		/// The maximum number of blocks that can be reorganized at once.
+		#[pallet::constant]
+		type MaxReorgDepth: Get<u32>;
+		/// The number of main chain headers to keep. Older headers are pruned.
+		/// Must be greater than `MaxReorgDepth`.
+		#[pallet::constant]
+		type HeadersToKeep: Get<u32>;
+		/// The handler of checkpoint resets. Drops the SPV proofs of the previous chain.
+		type OnCheckpointSet: OnCheckpointSet;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The light client has not been initialized with a checkpoint.
+		NotInitialized,
+		/// The checkpoint is invalid.
+		InvalidCheckpoint,
+		/// The submission is empty.
+		EmptySubmission,
+		/// The parent of the header is unknown.
+		UnknownParent,
+		/// The header does not use the expected difficulty.
+		InvalidDifficulty,
+		/// The block hash does not satisfy the target.
+		InsufficientProofOfWork,
+		/// The header timestamp is not greater than the median time past.
+		InvalidTimestamp,
+		/// The header forks from a block that is too deep.
+		ForkTooDeep,
+		/// The block is not included in the main chain.
+		BlockNotInMainChain,
+		/// The block does not have enough confirmations.
+		InsufficientConfirmations,
+		/// The merkle proof is invalid.
+		InvalidMerkleProof,
+		/// The given header count is lower than the number of stored headers.
+		InvalidHeaderCount,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new checkpoint has been set.
+		CheckpointSet { hash: H256, height: u32 },
+		/// A block header has been imported.
+		HeaderImported { hash: H256, height: u32 },
+		/// The best block has been updated.
+		BestBlockUpdated { hash: H256, height: u32 },
+		/// The main chain has been reorganized.
+		ChainReorganized { old_best: H256, new_best: H256, depth: u32 },
+	}
+
+	#[pallet::storage]
+	/// The verified block headers.
+	///
+	/// Key: The block hash
+	/// Value: The block header with its chain metadata
+	pub type Headers<T: Config> = CountedStorageMap<_, Twox64Concat, H256, StoredHeader>;
+
+	#[pallet::storage]
+	/// The hashes of the verified block headers (including forks) by height.
+	///
+	/// Key: The block height, The block hash
+	pub type HeadersByHeight<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, H256, ()>;
+
+	#[pallet::storage]
+	/// The block hashes of the main chain.
+	///
+	/// Key: The block height
+	/// Value: The block hash
+	pub type MainChain<T: Config> = StorageMap<_, Twox64Concat, u32, H256>;
+
+	#[pallet::storage]
+	/// The hash of the best block (the tip of the chain with the most work).
+	pub type BestBlock<T: Config> = StorageValue<_, H256, OptionQuery>;
+
+	#[pallet::storage]
+	/// The height of the best block.
+	pub type BestHeight<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	/// The height of the oldest main chain header that has not been pruned.
+	pub type OldestHeight<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::submit_headers(header_submission.headers.len() as u32)
+				// a header may reorganize the main chain down to `MaxReorgDepth` blocks
+				.saturating_add(T::DbWeight::get().reads_writes(
+					2 * T::MaxReorgDepth::get() as u64,
+					2 * T::MaxReorgDepth::get() as u64,
+				))
+		)]
+		/// Submit Bitcoin block headers. Each header must extend a known block.
+		pub fn submit_headers(
+			origin: OriginFor<T>,
+			header_submission: HeaderSubmission<T::AccountId>,
+			_signature: T::Signature,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			ensure!(<BestBlock<T>>::exists(), Error::<T>::NotInitialized);
+
+			let HeaderSubmission { headers, .. } = header_submission;
+			ensure!(!headers.is_empty(), Error::<T>::EmptySubmission);
+
+			for raw in headers {
+				Self::import_header(&raw)?;
+			}
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_checkpoint(*header_count))]
+		/// Reset the light client to start from the given trusted block header.
+		/// The checkpoint must be the first block of a difficulty adjustment interval.
+		/// `header_count` must be at least the number of stored headers, which are all removed.
+		pub fn set_checkpoint(
+			origin: OriginFor<T>,
+			header: RawBlockHeader,
+			height: u32,
+			header_count: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(<Headers<T>>::count() <= header_count, Error::<T>::InvalidHeaderCount);
+
+			let params = ChainParams::from_network(T::BitcoinNetwork::get());
+			ensure!(
+				params.no_retargeting || height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0,
+				Error::<T>::InvalidCheckpoint
+			);
+
+			let header = BlockHeader::from_bytes(&header);
+			let hash = header.hash();
+			let target =
+				Self::checked_target(header.bits, &params).ok_or(Error::<T>::InvalidCheckpoint)?;
+			ensure!(header.check_pow(target), Error::<T>::InsufficientProofOfWork);
+
+			// the main chain is a subset of the stored headers
+			let _ = <Headers<T>>::clear(header_count, None);
+			let _ = <HeadersByHeight<T>>::clear(header_count, None);
+			let _ = <MainChain<T>>::clear(header_count, None);
+
+			// the proofs of the previous chain can not be trusted anymore
+			let removed = T::OnCheckpointSet::on_checkpoint_set();
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+				T::DbWeight::get().reads_writes(removed.into(), removed.into()),
+				DispatchClass::Normal,
+			);
+
+			<Headers<T>>::insert(
+				&hash,
+				StoredHeader {
+					header,
+					height,
+					chain_work: block_work(target),
+					interval_start_time: header.time,
+					regular_bits: header.bits,
+				},
+			);
+			<HeadersByHeight<T>>::insert(height, hash, ());
+			<MainChain<T>>::insert(height, hash);
+			<BestBlock<T>>::put(hash);
+			<BestHeight<T>>::put(height);
+			<OldestHeight<T>>::put(height);
+
+			Self::deposit_event(Event::CheckpointSet { hash, height });
+			Ok(().into())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match call {
+				Call::submit_headers { header_submission, signature } => {
+					Self::verify_header_submission(header_submission, signature)
+				},
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+	}
+}
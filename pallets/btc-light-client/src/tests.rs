@@ -0,0 +1,528 @@
+use crate::{
+	mock::*, BestBlock, BestHeight, Call, Error, Event, HeaderSubmission, Headers, HeadersByHeight,
+	MainChain, OldestHeight, HEADERS_SUBMISSION_PRIORITY,
+};
+use bp_btc_relay::{
+	light_client::{
+		target_from_compact, tx_hash, verify_tx_output, BlockHeader, MerkleProof, RawBlockHeader,
+	},
+	traits::LightClientManager,
+	Script,
+};
+use bp_core::AccountId;
+use fp_account::EthereumSignature;
+use frame_support::{assert_noop, assert_ok, pallet_prelude::InvalidTransaction};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::{ecdsa, Pair, H256};
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use sp_runtime::{
+	traits::ValidateUnsigned,
+	transaction_validity::{TransactionSource, TransactionValidity},
+	DispatchError,
+};
+use std::str::FromStr;
+
+/// The regtest genesis block header.
+const GENESIS: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4adae5494dffff7f2002000000";
+
+/// Regtest block headers of height 1 to 6 built on top of the genesis block.
+const MAIN_CHAIN: [&str; 6] = [
+	"0000002006226e46111a0b59caaf126043eb5bbf28c34f3a5e332a1fc7b2b73cf188910ff4113849d628f7c3bc91cc0ff785a6aee3ee236c1c912b28cc09c44f9f97b74832e8494dffff7f2001000000",
+	"00000020d3edd9c326e63a834842bf08ddea6173909864e0c3dc42e63570383be7ab692cf724b2a71e673849848da6e5f6f4099fed86df22a98d71add536160ba777177f8aea494dffff7f2000000000",
+	"000000206d8ad4973a593915ef6e34d3e9963c61fe7b488779b07fd43a95639e6c704e5698cec015ab7d4e837418b785101db661f383d04939eacff2c2b3eb2dba6b2c4de2ec494dffff7f2001000000",
+	"00000020fc9e3e4dc748ef7aea3d9aea013b8d67d727e801e1da115a693ab1f2302b274c39f9700d6163e90ad2aede650498bf919b30c57600908c4978169d8c1632e82a3aef494dffff7f2003000000",
+	"0000002095c4d05171cefa767e86382d9d2304dc49c79b220aa42c9cc6551ac4ae170f790d4fdf5e00a9bfcc72cce47099454943d6a331fe9cc56609160a4030388b244392f1494dffff7f2007000000",
+	"00000020bea7bfa8fd908d7bd01636865a0b40941295f4b7be942f5c001d04e8d694d228eecae894e72ebc9a56c73c36b828a905b4a6cfeb37f905d25679ba684837f346eaf3494dffff7f2002000000",
+];
+
+/// Regtest block headers of height 3 to 8 forking from the block of height 2.
+const FORK_CHAIN: [&str; 6] = [
+	"000000206d8ad4973a593915ef6e34d3e9963c61fe7b488779b07fd43a95639e6c704e5658b421b3b116d61ad7c2c5391b579e7bd1e632abf93d42f39cb607c1c72dc2c0e3ec494dffff7f2000000000",
+	"00000020513b517e542401b6f1ea878b1b37f56ab9221614dde0f64d60a6c32ae19a270a803f59f62e3642913afe3976bb124bbb84d2eeb4b138ab9799a251b2cd1091d83cef494dffff7f2000000000",
+	"000000209b9d30473c9efa4cf59cf3cb6d953de1ba2088be864980640cf9d085f5908517eea3ca3d84db6a7c5e6912cc47127947f2083ebe58bc8b1f37151950e2dcfb7d95f1494dffff7f2000000000",
+	"00000020928d07244b463de154ab09a6748fabb4b067377910b18daa72a0f5dd1a1e014622618c7b7b80709a126812f7769ed3dd28988bed6bb7f9ca379639907d1e0e62eef3494dffff7f2001000000",
+	"00000020c6a12cecad765bf7d7ac6ead076bdf021a3357fc23b0a9c9e3d7a236166e2b0e2ea5b2409e3dc331af79c407417ea3c952ae8a51564751116e09004d421415e547f6494dffff7f2000000000",
+	"000000209536a803f9dcc196d9487f7d1c805dfc175f06c4fbf89a9ed8f1fe847b46bf031fca1564b66320d6d9062ca0075918b4e4de94878f603e15e5c6a5f558cbb050a0f8494dffff7f2001000000",
+];
+
+/// The coinbase transaction of the mainnet genesis block.
+const COINBASE_TX: &str = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000";
+
+/// The txid of `COINBASE_TX`.
+const COINBASE_TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+/// The script pubkey of the only output of `COINBASE_TX`.
+const COINBASE_SCRIPT_PUBKEY: &str = "4104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac";
+
+fn raw_header(hex_str: &str) -> RawBlockHeader {
+	hex::decode(hex_str).unwrap().try_into().unwrap()
+}
+
+fn hash(hex_str: &str) -> H256 {
+	H256::from_str(hex_str).unwrap()
+}
+
+/// Transactions that only differ in their lock time.
+fn raw_txs(count: u32) -> Vec<Vec<u8>> {
+	let coinbase = hex::decode(COINBASE_TX).unwrap();
+	(0..count)
+		.map(|lock_time| {
+			let mut raw_tx = coinbase.clone();
+			let len = raw_tx.len();
+			raw_tx[len - 4..].copy_from_slice(&lock_time.to_le_bytes());
+			raw_tx
+		})
+		.collect()
+}
+
+/// The parent node of the given nodes of a merkle tree. (display byte order)
+fn merkle_parent(left: H256, right: H256) -> H256 {
+	let mut concat = [left.0, right.0];
+	concat.iter_mut().for_each(|node| node.reverse());
+	tx_hash(&concat.concat())
+}
+
+/// The merkle root of the given transactions and the merkle proof of each transaction.
+fn merkle_tree(raw_txs: &[Vec<u8>]) -> (H256, Vec<MerkleProof>) {
+	let tx_count = raw_txs.len() as u32;
+	let mut proofs: Vec<_> = (0..tx_count)
+		.map(|index| MerkleProof { siblings: vec![], index, tx_count })
+		.collect();
+	let mut level: Vec<_> = raw_txs.iter().map(|raw_tx| tx_hash(raw_tx)).collect();
+	while level.len() > 1 {
+		if level.len() % 2 == 1 {
+			level.push(*level.last().unwrap());
+		}
+		for proof in proofs.iter_mut() {
+			let position = (proof.index >> proof.siblings.len()) as usize;
+			proof.siblings.push(level[position ^ 1]);
+		}
+		level = level.chunks(2).map(|pair| merkle_parent(pair[0], pair[1])).collect();
+	}
+	(level[0], proofs)
+}
+
+/// Mine regtest block headers with the given merkle roots on top of the given header.
+fn mine(parent: &str, merkle_roots: &[H256]) -> Vec<String> {
+	let mut parent = BlockHeader::from_bytes(&raw_header(parent));
+	merkle_roots
+		.iter()
+		.map(|merkle_root| {
+			let mut header = BlockHeader {
+				version: 0x20000000,
+				prev_block_hash: parent.hash(),
+				merkle_root: *merkle_root,
+				time: parent.time + 1,
+				bits: parent.bits,
+				nonce: 0,
+			};
+			while !header.check_pow(target_from_compact(header.bits).unwrap()) {
+				header.nonce += 1;
+			}
+			parent = header;
+			hex::encode(header.to_bytes())
+		})
+		.collect()
+}
+
+fn header_submission(headers: &[&str]) -> HeaderSubmission<AccountId> {
+	HeaderSubmission {
+		authority_id: AccountId::from([0u8; 20]),
+		headers: headers.iter().map(|h| raw_header(h)).collect(),
+	}
+}
+
+fn signature() -> EthereumSignature {
+	EthereumSignature::decode(&mut [0u8; 65].as_ref()).unwrap()
+}
+
+fn submit(headers: &[&str]) -> Result<(), DispatchError> {
+	BtcLightClient::submit_headers(RuntimeOrigin::none(), header_submission(headers), signature())
+		.map(|_| ())
+		.map_err(|e| e.error)
+}
+
+/// A `submit_headers` call signed by the relayer of the given seed.
+fn signed_call(headers: &[&str], seed: u8) -> Call<Test> {
+	let mut header_submission = header_submission(headers);
+	let hashes = header_submission
+		.headers
+		.iter()
+		.map(|raw| hex::encode(BlockHeader::from_bytes(raw).hash()))
+		.collect::<String>();
+	let message =
+		[keccak_256("HeadersSubmission".as_bytes()).as_slice(), hashes.as_bytes()].concat();
+	let message_hash = keccak_256(&message);
+
+	let signature: [u8; 65] = ecdsa::Pair::from_seed(&[seed; 32])
+		.sign_prehashed(&message_hash)
+		.as_ref()
+		.try_into()
+		.unwrap();
+	let public = secp256k1_ecdsa_recover(&signature, &message_hash).unwrap();
+	let address: [u8; 20] = keccak_256(&public)[12..].try_into().unwrap();
+	header_submission.authority_id = AccountId::from(address);
+
+	Call::submit_headers {
+		header_submission,
+		signature: EthereumSignature::decode(&mut signature.as_ref()).unwrap(),
+	}
+}
+
+fn validate(call: &Call<Test>) -> TransactionValidity {
+	BtcLightClient::validate_unsigned(TransactionSource::External, call)
+}
+
+/// Submit a block with the given transactions on top of the genesis block and `confirmations`
+/// blocks on top of it. Returns the merkle proofs of the transactions.
+fn submit_block(raw_txs: &[Vec<u8>], confirmations: u8) -> Vec<MerkleProof> {
+	let (merkle_root, proofs) = merkle_tree(raw_txs);
+	let mut merkle_roots = vec![merkle_root];
+	merkle_roots.extend((1..=confirmations).map(H256::repeat_byte));
+	let chain = mine(GENESIS, &merkle_roots);
+	assert_ok!(submit(&chain.iter().map(String::as_str).collect::<Vec<_>>()));
+	proofs
+}
+
+fn set_genesis_checkpoint() {
+	assert_ok!(BtcLightClient::set_checkpoint(RuntimeOrigin::root(), raw_header(GENESIS), 0, 0));
+}
+
+#[test]
+fn set_checkpoint_works() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+
+		let genesis_hash = BlockHeader::from_bytes(&raw_header(GENESIS)).hash();
+		assert_eq!(
+			genesis_hash,
+			hash("0f9188f13cb7b2c71f2a335e3a4fc328bf5beb436012afca590b1a11466e2206")
+		);
+		assert_eq!(BestBlock::<Test>::get(), Some(genesis_hash));
+		assert_eq!(BestHeight::<Test>::get(), 0);
+		assert_eq!(MainChain::<Test>::get(0), Some(genesis_hash));
+		System::assert_last_event(
+			Event::<Test>::CheckpointSet { hash: genesis_hash, height: 0 }.into(),
+		);
+	});
+}
+
+#[test]
+fn set_checkpoint_rejects_invalid_pow() {
+	new_test_ext().execute_with(|| {
+		let mut header = BlockHeader::from_bytes(&raw_header(GENESIS));
+		// the genesis block does not satisfy the mainnet target
+		header.bits = 0x1d00ffff;
+		assert_noop!(
+			BtcLightClient::set_checkpoint(RuntimeOrigin::root(), header.to_bytes(), 0, 0),
+			Error::<Test>::InsufficientProofOfWork
+		);
+	});
+}
+
+#[test]
+fn submit_headers_requires_checkpoint() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(submit(&MAIN_CHAIN[..1]), Error::<Test>::NotInitialized);
+	});
+}
+
+#[test]
+fn submit_headers_works() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		assert_ok!(submit(&MAIN_CHAIN));
+
+		for (i, raw) in MAIN_CHAIN.iter().enumerate() {
+			let hash = BlockHeader::from_bytes(&raw_header(raw)).hash();
+			assert_eq!(MainChain::<Test>::get(i as u32 + 1), Some(hash));
+			assert_eq!(Headers::<Test>::get(hash).unwrap().height, i as u32 + 1);
+		}
+		assert_eq!(BestHeight::<Test>::get(), 6);
+		assert_eq!(BtcLightClient::best_height(), 6);
+
+		// already imported headers are skipped
+		assert_ok!(submit(&MAIN_CHAIN[..2]));
+		assert_eq!(BestHeight::<Test>::get(), 6);
+	});
+}
+
+#[test]
+fn submit_headers_rejects_unknown_parent() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		assert_noop!(submit(&MAIN_CHAIN[1..]), Error::<Test>::UnknownParent);
+	});
+}
+
+#[test]
+fn submit_headers_rejects_invalid_headers() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+
+		// the nonce no longer satisfies the target
+		let mut header = BlockHeader::from_bytes(&raw_header(MAIN_CHAIN[0]));
+		header.nonce = header.nonce.wrapping_add(1);
+		while header.check_pow(target_from_compact(header.bits).unwrap()) {
+			header.nonce = header.nonce.wrapping_add(1);
+		}
+		assert_noop!(
+			submit(&[hex::encode(header.to_bytes()).as_str()]),
+			Error::<Test>::InsufficientProofOfWork
+		);
+
+		// the header does not use the expected difficulty
+		let mut header = BlockHeader::from_bytes(&raw_header(MAIN_CHAIN[0]));
+		header.bits = 0x1d00ffff;
+		assert_noop!(
+			submit(&[hex::encode(header.to_bytes()).as_str()]),
+			Error::<Test>::InvalidDifficulty
+		);
+	});
+}
+
+#[test]
+fn submit_headers_rejects_old_timestamp() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+
+		let genesis = BlockHeader::from_bytes(&raw_header(GENESIS));
+		let mut header = BlockHeader::from_bytes(&raw_header(MAIN_CHAIN[0]));
+		header.time = genesis.time;
+		while !header.check_pow(target_from_compact(header.bits).unwrap()) {
+			header.nonce = header.nonce.wrapping_add(1);
+		}
+		assert_noop!(
+			submit(&[hex::encode(header.to_bytes()).as_str()]),
+			Error::<Test>::InvalidTimestamp
+		);
+	});
+}
+
+#[test]
+fn chain_reorganization_works() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		assert_ok!(submit(&MAIN_CHAIN));
+		let old_best = BestBlock::<Test>::get().unwrap();
+
+		// the fork does not have more work until it exceeds the main chain
+		assert_ok!(submit(&FORK_CHAIN[..4]));
+		assert_eq!(BestBlock::<Test>::get(), Some(old_best));
+
+		assert_ok!(submit(&FORK_CHAIN[4..]));
+		let new_best = BlockHeader::from_bytes(&raw_header(FORK_CHAIN[5])).hash();
+		assert_eq!(BestBlock::<Test>::get(), Some(new_best));
+		assert_eq!(BestHeight::<Test>::get(), 8);
+		for (i, raw) in FORK_CHAIN.iter().enumerate() {
+			let hash = BlockHeader::from_bytes(&raw_header(raw)).hash();
+			assert_eq!(MainChain::<Test>::get(i as u32 + 3), Some(hash));
+		}
+		// the blocks below the fork point are kept
+		let fork_point = BlockHeader::from_bytes(&raw_header(MAIN_CHAIN[1])).hash();
+		assert_eq!(MainChain::<Test>::get(2), Some(fork_point));
+
+		let reorged_best = BlockHeader::from_bytes(&raw_header(FORK_CHAIN[4])).hash();
+		System::assert_has_event(
+			Event::<Test>::ChainReorganized { old_best, new_best: reorged_best, depth: 4 }.into(),
+		);
+	});
+}
+
+#[test]
+fn stale_forks_are_pruned() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		assert_ok!(submit(&MAIN_CHAIN));
+		assert_ok!(submit(&FORK_CHAIN[..5]));
+		assert_eq!(Headers::<Test>::count(), 12);
+
+		HeadersToKeep::set(2);
+		assert_ok!(submit(&FORK_CHAIN[5..]));
+		assert_eq!(OldestHeight::<Test>::get(), 6);
+
+		// the reorganized blocks below the oldest height are pruned with the main chain
+		for (i, raw) in MAIN_CHAIN.iter().enumerate() {
+			let hash = BlockHeader::from_bytes(&raw_header(raw)).hash();
+			assert_eq!(Headers::<Test>::contains_key(hash), i + 1 >= 6);
+		}
+		for (i, raw) in FORK_CHAIN.iter().enumerate() {
+			let hash = BlockHeader::from_bytes(&raw_header(raw)).hash();
+			assert_eq!(Headers::<Test>::contains_key(hash), i + 3 >= 6);
+			assert_eq!(MainChain::<Test>::contains_key(i as u32 + 3), i + 3 >= 6);
+		}
+		assert_eq!(Headers::<Test>::count(), 4);
+		assert_eq!(HeadersByHeight::<Test>::iter().count(), 4);
+	});
+}
+
+#[test]
+fn set_checkpoint_requires_the_header_count() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		assert_ok!(submit(&MAIN_CHAIN));
+		assert_ok!(submit(&FORK_CHAIN[..2]));
+		assert_eq!(Headers::<Test>::count(), 9);
+
+		let checkpoint = raw_header(FORK_CHAIN[1]);
+		assert_noop!(
+			BtcLightClient::set_checkpoint(RuntimeOrigin::root(), checkpoint, 4, 8),
+			Error::<Test>::InvalidHeaderCount
+		);
+		assert_ok!(BtcLightClient::set_checkpoint(RuntimeOrigin::root(), checkpoint, 4, 9));
+
+		// the headers of the previous chain are removed
+		let hash = BlockHeader::from_bytes(&checkpoint).hash();
+		assert_eq!(Headers::<Test>::count(), 1);
+		assert_eq!(HeadersByHeight::<Test>::iter().collect::<Vec<_>>(), vec![(4, hash, ())]);
+		assert_eq!(MainChain::<Test>::iter().collect::<Vec<_>>(), vec![(4, hash)]);
+		assert_eq!(BestHeight::<Test>::get(), 4);
+		assert_eq!(OldestHeight::<Test>::get(), 4);
+	});
+}
+
+#[test]
+fn verify_tx_inclusion_works() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		let raw_txs = raw_txs(5);
+		let proofs = submit_block(&raw_txs, 5);
+
+		for (raw_tx, proof) in raw_txs.iter().zip(&proofs) {
+			assert_ok!(BtcLightClient::verify_tx_inclusion(raw_tx, proof, 1, 6));
+		}
+
+		// not enough confirmations
+		assert_noop!(
+			BtcLightClient::verify_tx_inclusion(&raw_txs[2], &proofs[2], 1, 7),
+			Error::<Test>::InsufficientConfirmations
+		);
+		// wrong position
+		let proof = MerkleProof { index: 3, ..proofs[2].clone() };
+		assert_noop!(
+			BtcLightClient::verify_tx_inclusion(&raw_txs[2], &proof, 1, 1),
+			Error::<Test>::InvalidMerkleProof
+		);
+		// wrong transaction count
+		let proof = MerkleProof { tx_count: 4, ..proofs[2].clone() };
+		assert_noop!(
+			BtcLightClient::verify_tx_inclusion(&raw_txs[2], &proof, 1, 1),
+			Error::<Test>::InvalidMerkleProof
+		);
+		// the transaction is not included in the given block
+		assert_noop!(
+			BtcLightClient::verify_tx_inclusion(&raw_txs[2], &proofs[2], 2, 1),
+			Error::<Test>::InvalidMerkleProof
+		);
+		// the block does not exist
+		assert_noop!(
+			BtcLightClient::verify_tx_inclusion(&raw_txs[2], &proofs[2], 7, 1),
+			Error::<Test>::BlockNotInMainChain
+		);
+	});
+}
+
+#[test]
+fn verify_tx_inclusion_rejects_inner_nodes() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		let raw_txs = raw_txs(2);
+		submit_block(&raw_txs, 0);
+
+		// the inner node of the two transactions is a 64-byte preimage of the merkle root
+		let mut inner_node = tx_hash(&raw_txs[0]).0;
+		inner_node.reverse();
+		let mut right = tx_hash(&raw_txs[1]).0;
+		right.reverse();
+		let inner_node = [inner_node, right].concat();
+		let proof = MerkleProof { siblings: vec![], index: 0, tx_count: 1 };
+		assert_noop!(
+			BtcLightClient::verify_tx_inclusion(&inner_node, &proof, 1, 1),
+			Error::<Test>::InvalidMerkleProof
+		);
+	});
+}
+
+#[test]
+fn verify_tx_inclusion_fails_after_reorg() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		let raw_txs = raw_txs(3);
+		let proofs = submit_block(&raw_txs, 2);
+		assert_ok!(BtcLightClient::verify_tx_inclusion(&raw_txs[0], &proofs[0], 1, 3));
+
+		// a longer fork replaces the block that includes the transactions
+		let fork = mine(GENESIS, &[H256::repeat_byte(0xff); 4]);
+		assert_ok!(submit(&fork.iter().map(String::as_str).collect::<Vec<_>>()));
+		assert_eq!(BestHeight::<Test>::get(), 4);
+		assert_noop!(
+			BtcLightClient::verify_tx_inclusion(&raw_txs[0], &proofs[0], 1, 1),
+			Error::<Test>::InvalidMerkleProof
+		);
+	});
+}
+
+#[test]
+fn header_submissions_are_pre_checked() {
+	new_test_ext().execute_with(|| {
+		set_genesis_checkpoint();
+		assert_eq!(validate(&signed_call(&[], 1)), InvalidTransaction::Call.into());
+		// the parent is unknown
+		assert_eq!(validate(&signed_call(&MAIN_CHAIN[1..], 1)), InvalidTransaction::Call.into());
+		// the headers are not linked to each other
+		assert_eq!(
+			validate(&signed_call(&[MAIN_CHAIN[0], MAIN_CHAIN[2]], 1)),
+			InvalidTransaction::BadProof.into()
+		);
+
+		// the nonce no longer satisfies the target
+		let mut header = BlockHeader::from_bytes(&raw_header(MAIN_CHAIN[0]));
+		while header.check_pow(target_from_compact(header.bits).unwrap()) {
+			header.nonce = header.nonce.wrapping_add(1);
+		}
+		let header = hex::encode(header.to_bytes());
+		assert_eq!(validate(&signed_call(&[&header], 1)), InvalidTransaction::BadProof.into());
+
+		// the signature does not match the headers
+		let call = Call::submit_headers {
+			header_submission: header_submission(&MAIN_CHAIN),
+			signature: signature(),
+		};
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+		// submissions of the same tip by different relayers provide the same tag
+		let tip = BlockHeader::from_bytes(&raw_header(MAIN_CHAIN[5])).hash();
+		for seed in [1, 2] {
+			let valid = validate(&signed_call(&MAIN_CHAIN, seed)).unwrap();
+			assert_eq!(valid.priority, HEADERS_SUBMISSION_PRIORITY);
+			assert_eq!(valid.provides, vec![("HeadersSubmission", tip).encode()]);
+		}
+
+		// the tip has already been imported
+		assert_ok!(submit(&MAIN_CHAIN[..2]));
+		assert_eq!(validate(&signed_call(&MAIN_CHAIN[..2], 1)), InvalidTransaction::Stale.into());
+		assert!(validate(&signed_call(&MAIN_CHAIN, 1)).is_ok());
+	});
+}
+
+#[test]
+fn verify_tx_output_works() {
+	let raw_tx = hex::decode(COINBASE_TX).unwrap();
+	let script_bytes = hex::decode(COINBASE_SCRIPT_PUBKEY).unwrap();
+	let script_pubkey = Script::from_bytes(&script_bytes);
+	let txid = hash(COINBASE_TXID);
+	assert_eq!(tx_hash(&raw_tx), txid);
+	assert!(verify_tx_output(&raw_tx, txid, 0, 50_0000_0000, script_pubkey));
+
+	// the transaction does not match the txid
+	assert!(!verify_tx_output(&raw_tx, H256::repeat_byte(1), 0, 50_0000_0000, script_pubkey));
+	// the output does not exist
+	assert!(!verify_tx_output(&raw_tx, txid, 1, 50_0000_0000, script_pubkey));
+	// the output pays another amount
+	assert!(!verify_tx_output(&raw_tx, txid, 0, 50_0000_0001, script_pubkey));
+	// the output pays another script
+	assert!(!verify_tx_output(&raw_tx, txid, 0, 50_0000_0000, Script::from_bytes(&[0x51])));
+	// the transaction is malformed
+	let truncated = &raw_tx[..raw_tx.len() - 1];
+	assert!(!verify_tx_output(truncated, tx_hash(truncated), 0, 50_0000_0000, script_pubkey));
+}
@@ -0,0 +1,98 @@
+//! Hand-estimated weights for `pallet_btc_light_client`, not generated by the benchmark CLI
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_btc_light_client`.
+pub trait WeightInfo {
+	fn submit_headers(n: u32) -> Weight;
+	fn set_checkpoint(n: u32) -> Weight;
+}
+
+/// Weights for `pallet_btc_light_client` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `BtcLightClient::BestBlock` (r:101 w:100)
+	/// Storage: `BtcLightClient::Headers` (r:1300 w:200)
+	/// Storage: `BtcLightClient::CounterForHeaders` (r:201 w:200)
+	/// Storage: `BtcLightClient::HeadersByHeight` (r:100 w:200)
+	/// Storage: `BtcLightClient::BestHeight` (r:100 w:100)
+	/// Storage: `BtcLightClient::MainChain` (r:100 w:200)
+	/// Storage: `BtcLightClient::OldestHeight` (r:100 w:100)
+	/// The range of component `n` is `[1, 100]`.
+	/// Every header is charged the worst case of its own import: the median time past of up to
+	/// 10 ancestors, a best block update and the pruning of one height. The walk back of a
+	/// reorganization is charged by the call.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn submit_headers(n: u32) -> Weight {
+		Weight::from_parts(38_262_000, 3629)
+			.saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().reads((20_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((11_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2519).saturating_mul(n.into()))
+	}
+	/// Storage: `BtcLightClient::CounterForHeaders` (r:1 w:1)
+	/// Storage: `BtcLightClient::Headers` (r:0 w:1000)
+	/// Storage: `BtcLightClient::HeadersByHeight` (r:0 w:1000)
+	/// Storage: `BtcLightClient::MainChain` (r:0 w:1000)
+	/// Storage: `BtcLightClient::BestBlock` (r:0 w:1)
+	/// Storage: `BtcLightClient::BestHeight` (r:0 w:1)
+	/// Storage: `BtcLightClient::OldestHeight` (r:0 w:1)
+	/// The range of component `n` is `[1, 1000]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_checkpoint(n: u32) -> Weight {
+		Weight::from_parts(59_381_000, 3629)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `BtcLightClient::BestBlock` (r:101 w:100)
+	/// Storage: `BtcLightClient::Headers` (r:1300 w:200)
+	/// Storage: `BtcLightClient::CounterForHeaders` (r:201 w:200)
+	/// Storage: `BtcLightClient::HeadersByHeight` (r:100 w:200)
+	/// Storage: `BtcLightClient::BestHeight` (r:100 w:100)
+	/// Storage: `BtcLightClient::MainChain` (r:100 w:200)
+	/// Storage: `BtcLightClient::OldestHeight` (r:100 w:100)
+	/// The range of component `n` is `[1, 100]`.
+	/// Every header is charged the worst case of its own import: the median time past of up to
+	/// 10 ancestors, a best block update and the pruning of one height. The walk back of a
+	/// reorganization is charged by the call.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn submit_headers(n: u32) -> Weight {
+		Weight::from_parts(38_262_000, 3629)
+			.saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().reads((20_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((11_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2519).saturating_mul(n.into()))
+	}
+	/// Storage: `BtcLightClient::CounterForHeaders` (r:1 w:1)
+	/// Storage: `BtcLightClient::Headers` (r:0 w:1000)
+	/// Storage: `BtcLightClient::HeadersByHeight` (r:0 w:1000)
+	/// Storage: `BtcLightClient::MainChain` (r:0 w:1000)
+	/// Storage: `BtcLightClient::BestBlock` (r:0 w:1)
+	/// Storage: `BtcLightClient::BestHeight` (r:0 w:1)
+	/// Storage: `BtcLightClient::OldestHeight` (r:0 w:1)
+	/// The range of component `n` is `[1, 1000]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_checkpoint(n: u32) -> Weight {
+		Weight::from_parts(59_381_000, 3629)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
@@ -1,7 +1,7 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use bp_btc_relay::{MigrationSequence, Public};
+use bp_btc_relay::{DescriptorType, MigrationSequence, Public};
 use frame_benchmarking::v2::*;
 use frame_support::traits::SortedMembers;
 use frame_system::RawOrigin;
@@ -259,5 +259,38 @@ mod benchmarks {
 		_(RawOrigin::Root, new_ratio);
 	}
 
+	#[benchmark]
+	fn set_vault_descriptor_type() {
+		#[extrinsic_call]
+		_(RawOrigin::Root, DescriptorType::Tr);
+
+		assert_eq!(<VaultDescriptorType<T>>::get(), DescriptorType::Tr);
+	}
+
+	#[benchmark]
+	fn request_vault_with_recovery() {
+		setup_normal_state::<T>();
+
+		let caller: T::AccountId = account("caller", 0, 0);
+		let refund_address = b"bcrt1qtwjzfmpctpp9g2y7urgjt63jwm9r2xat5pua3g".to_vec();
+
+		// Setup distinct PreSubmittedPubKeys for executives, so the vault address is generated
+		// with the recovery leaf under the heavier Taproot descriptor
+		<VaultDescriptorType<T>>::put(DescriptorType::Tr);
+		let executives = T::Executives::sorted_members();
+		let pub_keys = [DUMMY_PUBKEY_2, DUMMY_PUBKEY_3, DUMMY_PUBKEY_4, DUMMY_PUBKEY_5];
+		for (executive, pub_key) in executives.iter().zip(pub_keys) {
+			<PreSubmittedPubKeys<T>>::mutate(1u32, executive, |keys| {
+				keys.insert(Public(pub_key));
+			});
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), refund_address, Public(DUMMY_PUBKEY));
+
+		assert!(<RecoveryPaths<T>>::contains_key(1u32, &caller));
+		assert!(<RegistrationPool<T>>::contains_key(1u32, &caller));
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -5,6 +5,8 @@
 mod benchmarking;
 #[cfg(test)]
 mod mock;
+#[cfg(test)]
+mod tests;
 
 pub mod migrations;
 mod pallet;
@@ -39,7 +41,7 @@ impl<AccountId, A: InitializeMembers<AccountId>, B: InitializeMembers<AccountId>
 	}
 }
 
-use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
 use sp_std::vec::Vec;
@@ -87,6 +89,15 @@ impl<AccountId: PartialEq + Clone + Ord + sp_std::fmt::Debug> BitcoinRelayTarget
 	}
 }
 
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The timelocked recovery path of a user vault.
+pub struct RecoveryPath {
+	/// The user's public key that can spend the vault after the timelock. (33 bytes)
+	pub pub_key: Public,
+	/// The relative timelock (CSV) in Bitcoin blocks.
+	pub timelock: u32,
+}
+
 #[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 /// The payload used for public key submission.
 pub struct VaultKeySubmission<AccountId> {
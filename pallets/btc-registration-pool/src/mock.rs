@@ -12,7 +12,7 @@ use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup, Lazy, Verify},
 	transaction_validity::TransactionValidityError,
-	Percent,
+	BuildStorage, Percent,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -32,6 +32,7 @@ parameter_types! {
 	pub const DefaultMultiSigRatio: Percent = Percent::from_percent(67);
 	pub const BitcoinChainId: u32 = 10002;
 	pub const BitcoinNetwork: Network = Network::Regtest;
+	pub const RecoveryTimelock: u32 = 144;
 }
 
 impl frame_system::Config for Test {
@@ -142,10 +143,10 @@ impl pallet_btc_registration_pool::Config for Test {
 	type DefaultMultiSigRatio = DefaultMultiSigRatio;
 	type BitcoinChainId = BitcoinChainId;
 	type BitcoinNetwork = BitcoinNetwork;
+	type RecoveryTimelock = RecoveryTimelock;
 	type WeightInfo = ();
 }
 
-#[cfg(feature = "runtime-benchmarks")]
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
 }
@@ -1,9 +1,14 @@
 use bp_btc_relay::{
 	traits::{PoolManager, SocketQueueManager},
-	Address, AddressState, Descriptor, FromSliceError as KeyError, MigrationSequence,
-	MultiSigAccount, Network, PublicKey, UnboundedBytes,
+	utils::descriptor_recovery_script,
+	Address, AddressState, Descriptor, DescriptorType, FromSliceError as KeyError,
+	MigrationSequence, MultiSigAccount, Network, PublicKey, UnboundedBytes,
+	TAPROOT_UNSPENDABLE_KEY,
+};
+use frame_support::{
+	ensure,
+	traits::{ChangeMembers, InitializeMembers, SortedMembers},
 };
-use frame_support::traits::{ChangeMembers, InitializeMembers, SortedMembers};
 use frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::prelude::{
 	format,
@@ -21,7 +26,8 @@ use sp_runtime::{
 use sp_std::{fmt::Display, str, str::FromStr, vec::Vec};
 
 use crate::{
-	BoundedBitcoinAddress, Public, SetRefundsApproval, VaultKeyPreSubmission, VaultKeySubmission,
+	BitcoinRelayTarget, BoundedBitcoinAddress, PoolRound, Public, RecoveryPath, SetRefundsApproval,
+	VaultKeyPreSubmission, VaultKeySubmission,
 };
 
 use super::pallet::*;
@@ -241,6 +247,11 @@ impl<T: Config> PoolManager<T::AccountId> for Pallet<T> {
 		};
 		<SystemVault<T>>::insert(<CurrentRound<T>>::get(), system_vault.clone());
 		<SystemVault<T>>::insert(<CurrentRound<T>>::get() + 1, system_vault);
+		<BondedDescriptor<T>>::insert(
+			<CurrentRound<T>>::get(),
+			BoundedBitcoinAddress::try_from(address.to_string().as_bytes().to_vec()).unwrap(),
+			descriptor.to_string().as_bytes().to_vec(),
+		);
 
 		let pk1 = PublicKey::from_str(
 			"02f1484159b37084e3e9915a737ec59261e95cb3740f6da3afc73d7cceb18ec54e",
@@ -338,24 +349,63 @@ impl<T: Config> Pallet<T> {
 		Ok(pub_keys)
 	}
 
-	/// Create a new wsh sorted multi descriptor.
+	/// Create a new multi-sig descriptor of the given type.
+	/// - `Wsh`: wsh sorted multi descriptor.
+	/// - `Tr`: tr descriptor with an unspendable internal key and a single multi_a leaf of the sorted keys.
+	///
+	/// If a recovery path is given, the descriptor gets an additional branch that can be spent by
+	/// the user's key alone after the relative timelock (CSV) has passed.
 	fn generate_descriptor(
 		m: usize,
 		raw_pub_keys: Vec<Public>,
+		descriptor_type: DescriptorType,
+		recovery: Option<RecoveryPath>,
 	) -> Result<Descriptor<PublicKey>, ()> {
-		let desc =
-			Descriptor::new_wsh_sortedmulti(m, Self::sort_pub_keys(raw_pub_keys).map_err(|_| ())?)
-				.map_err(|_| ())?;
+		let pub_keys = Self::sort_pub_keys(raw_pub_keys).map_err(|_| ())?;
+		let keys = pub_keys.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",");
+		let recovery = match recovery {
+			Some(path) => {
+				let pub_key = PublicKey::from_slice(path.pub_key.as_ref()).map_err(|_| ())?;
+				Some(format!("and_v(v:pk({}),older({}))", pub_key, path.timelock))
+			},
+			None => None,
+		};
+		let desc = match (descriptor_type, recovery) {
+			(DescriptorType::Wsh, None) => {
+				Descriptor::new_wsh_sortedmulti(m, pub_keys).map_err(|_| ())?
+			},
+			(DescriptorType::Wsh, Some(recovery)) => Descriptor::<PublicKey>::from_str(&format!(
+				"wsh(or_d(multi({},{}),{}))",
+				m, keys, recovery
+			))
+			.map_err(|_| ())?,
+			(DescriptorType::Tr, None) => Descriptor::<PublicKey>::from_str(&format!(
+				"tr({},multi_a({},{}))",
+				TAPROOT_UNSPENDABLE_KEY, m, keys
+			))
+			.map_err(|_| ())?,
+			(DescriptorType::Tr, Some(recovery)) => Descriptor::<PublicKey>::from_str(&format!(
+				"tr({},{{multi_a({},{}),{}}})",
+				TAPROOT_UNSPENDABLE_KEY, m, keys, recovery
+			))
+			.map_err(|_| ())?,
+		};
 		desc.sanity_check().map_err(|_| ())?;
 		Ok(desc)
 	}
 
-	/// Generate a multi-sig vault address.
+	/// Generate a multi-sig vault address. The recovery path is only used for user vaults.
 	pub fn generate_vault_address(
 		raw_pub_keys: Vec<Public>,
+		recovery: Option<RecoveryPath>,
 	) -> Result<(BoundedBitcoinAddress, UnboundedBytes), DispatchError> {
-		let desc = Self::generate_descriptor(Self::get_m() as usize, raw_pub_keys)
-			.map_err(|_| Error::<T>::DescriptorGeneration)?;
+		let desc = Self::generate_descriptor(
+			Self::get_m() as usize,
+			raw_pub_keys,
+			<VaultDescriptorType<T>>::get(),
+			recovery,
+		)
+		.map_err(|_| Error::<T>::DescriptorGeneration)?;
 
 		// generate vault address
 		Ok((
@@ -381,7 +431,10 @@ impl<T: Config> Pallet<T> {
 		current_round: u32,
 	) -> Result<(), DispatchError> {
 		// generate vault address
-		let (vault_address, descriptor) = Self::generate_vault_address(vault.pub_keys())?;
+		let (vault_address, descriptor) = Self::generate_vault_address(
+			vault.pub_keys(),
+			<RecoveryPaths<T>>::get(current_round, &who),
+		)?;
 
 		// check if the vault address is the same as the refund address
 		if vault_address == *refund_address {
@@ -407,6 +460,101 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Request a vault address for the given user, optionally with a timelocked recovery path.
+	pub fn do_request_vault(
+		who: T::AccountId,
+		refund_address: UnboundedBytes,
+		recovery_pub_key: Option<Public>,
+	) -> Result<(), DispatchError> {
+		let refund_address: BoundedBitcoinAddress =
+			Self::get_checked_bitcoin_address(&refund_address)?;
+		let current_round = CurrentRound::<T>::get();
+
+		ensure!(
+			!<BondedVault<T>>::contains_key(current_round, &refund_address),
+			Error::<T>::AddressAlreadyRegistered
+		);
+		ensure!(
+			!<RegistrationPool<T>>::contains_key(current_round, &who),
+			Error::<T>::AddressAlreadyRegistered
+		);
+
+		if let Some(pub_key) = recovery_pub_key {
+			PublicKey::from_slice(pub_key.as_ref()).map_err(|_| Error::<T>::InvalidPublicKey)?;
+			let timelock = T::RecoveryTimelock::get();
+			<RecoveryPaths<T>>::insert(current_round, &who, RecoveryPath { pub_key, timelock });
+			Self::deposit_event(Event::RecoveryPathRegistered {
+				who: who.clone(),
+				pub_key,
+				timelock,
+			});
+		}
+
+		let mut relay_target =
+			BitcoinRelayTarget::new::<T>(refund_address.clone(), Self::get_m(), Self::get_n());
+
+		let executives = T::Executives::sorted_members();
+		for executive in executives {
+			if let Some(pub_key) =
+				<PreSubmittedPubKeys<T>>::mutate(current_round, &executive, |keys| keys.pop_first())
+			{
+				if <BondedPubKey<T>>::get(current_round, &pub_key).is_none() {
+					relay_target
+						.vault
+						.pub_keys
+						.try_insert(executive, pub_key)
+						.map_err(|_| <Error<T>>::OutOfRange)?;
+				}
+			}
+		}
+
+		if relay_target.vault.is_key_generation_ready() {
+			match Self::try_bond_vault_address(
+				&mut relay_target.vault,
+				&relay_target.refund_address,
+				who.clone(),
+				current_round,
+			) {
+				Ok(_) => {
+					for pub_key in relay_target.vault.pub_keys() {
+						<BondedPubKey<T>>::insert(current_round, &pub_key, who.clone());
+					}
+				},
+				Err(_) => {
+					relay_target.vault.clear_pub_keys();
+					Self::deposit_event(Event::VaultPending {
+						who: who.clone(),
+						refund_address: refund_address.clone(),
+					});
+				},
+			}
+		}
+		<BondedRefund<T>>::mutate(current_round, &refund_address, |users| {
+			users.push(who.clone());
+		});
+		<RegistrationPool<T>>::insert(current_round, who, relay_target);
+
+		Ok(())
+	}
+
+	/// Get the recovery path of the given user and its script. The script is the witness script
+	/// for `wsh` vaults and the recovery leaf script for `tr` vaults. The script is empty if the
+	/// vault address has not been generated yet.
+	pub fn get_recovery_path(
+		who: &T::AccountId,
+		round: PoolRound,
+	) -> Option<(RecoveryPath, UnboundedBytes)> {
+		let path = <RecoveryPaths<T>>::get(round, who)?;
+		let script = <RegistrationPool<T>>::get(round, who)
+			.and_then(|target| {
+				let desc = str::from_utf8(&target.vault.descriptor).ok()?;
+				descriptor_recovery_script(&Descriptor::<PublicKey>::from_str(desc).ok()?)
+			})
+			.map(|script| script.to_bytes())
+			.unwrap_or_default();
+		Some((path, script))
+	}
+
 	/// Check if the given address is valid on the target Bitcoin network. Then returns the checked address.
 	pub fn get_checked_bitcoin_address(
 		address: &UnboundedBytes,
@@ -2,8 +2,8 @@ mod impls;
 
 use crate::{
 	migrations, BitcoinRelayTarget, BoundedBitcoinAddress, MultiSigAccount, PoolRound,
-	SetRefundState, SetRefundsApproval, VaultKeyPreSubmission, VaultKeySubmission, WeightInfo,
-	ADDRESS_U64,
+	RecoveryPath, SetRefundState, SetRefundsApproval, VaultKeyPreSubmission, VaultKeySubmission,
+	WeightInfo, ADDRESS_U64,
 };
 
 use frame_support::{
@@ -13,7 +13,8 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 
 use bp_btc_relay::{
-	traits::SocketQueueManager, MigrationSequence, Network, Public, PublicKey, UnboundedBytes,
+	traits::SocketQueueManager, DescriptorType, MigrationSequence, Network, Public, PublicKey,
+	UnboundedBytes,
 };
 use sp_core::{H160, H256};
 use sp_runtime::{
@@ -54,6 +55,9 @@ pub mod pallet {
 		type BitcoinChainId: Get<u32>;
 		/// The flag that represents whether the target Bitcoin network is the mainnet.
 		type BitcoinNetwork: Get<Network>;
+		/// The relative timelock (in Bitcoin blocks) of the recovery path of user vaults.
+		#[pallet::constant]
+		type RecoveryTimelock: Get<u32>;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -136,6 +140,10 @@ pub mod pallet {
 		MigrationCompleted,
 		/// A new multi-sig ratio has been set.
 		MultiSigRatioSet { old: Percent, new: Percent },
+		/// A user registered a timelocked recovery path for its vault.
+		RecoveryPathRegistered { who: T::AccountId, pub_key: Public, timelock: u32 },
+		/// A new vault descriptor type has been set.
+		VaultDescriptorTypeSet { old: DescriptorType, new: DescriptorType },
 		/// Vault key has been submitted.
 		VaultKeySubmitted { who: T::AccountId, pub_key: Public },
 		/// Vault key has been pre-submitted.
@@ -221,6 +229,16 @@ pub mod pallet {
 	/// The minimum required ratio of signatures to unlock the vault account's txo.
 	pub type MultiSigRatio<T: Config> = StorageValue<_, Percent, ValueQuery>;
 
+	#[pallet::storage]
+	/// The timelocked recovery paths of user vaults. The key is the user's Bifrost address.
+	pub type RecoveryPaths<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolRound, Twox64Concat, T::AccountId, RecoveryPath>;
+
+	#[pallet::storage]
+	/// The descriptor type used for newly generated vault addresses (including the system vault).
+	/// Vaults that are already generated keep their bonded descriptor.
+	pub type VaultDescriptorType<T: Config> = StorageValue<_, DescriptorType, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::unbounded]
 	/// The public keys that are pre-submitted by the relay executives.
@@ -338,63 +356,7 @@ pub mod pallet {
 			);
 
 			let who = ensure_signed(origin)?;
-			let refund_address: BoundedBitcoinAddress =
-				Self::get_checked_bitcoin_address(&refund_address)?;
-			let current_round = CurrentRound::<T>::get();
-
-			ensure!(
-				!<BondedVault<T>>::contains_key(current_round, &refund_address),
-				Error::<T>::AddressAlreadyRegistered
-			);
-			ensure!(
-				!<RegistrationPool<T>>::contains_key(current_round, &who),
-				Error::<T>::AddressAlreadyRegistered
-			);
-
-			let mut relay_target =
-				BitcoinRelayTarget::new::<T>(refund_address.clone(), Self::get_m(), Self::get_n());
-
-			let executives = T::Executives::sorted_members();
-			for executive in executives {
-				if let Some(pub_key) =
-					<PreSubmittedPubKeys<T>>::mutate(current_round, &executive, |keys| {
-						keys.pop_first()
-					}) {
-					if <BondedPubKey<T>>::get(current_round, &pub_key).is_none() {
-						relay_target
-							.vault
-							.pub_keys
-							.try_insert(executive, pub_key)
-							.map_err(|_| <Error<T>>::OutOfRange)?;
-					}
-				}
-			}
-
-			if relay_target.vault.is_key_generation_ready() {
-				match Self::try_bond_vault_address(
-					&mut relay_target.vault,
-					&relay_target.refund_address,
-					who.clone(),
-					current_round,
-				) {
-					Ok(_) => {
-						for pub_key in relay_target.vault.pub_keys() {
-							<BondedPubKey<T>>::insert(current_round, &pub_key, who.clone());
-						}
-					},
-					Err(_) => {
-						relay_target.vault.clear_pub_keys();
-						Self::deposit_event(Event::VaultPending {
-							who: who.clone(),
-							refund_address: refund_address.clone(),
-						});
-					},
-				}
-			}
-			<BondedRefund<T>>::mutate(current_round, &refund_address, |users| {
-				users.push(who.clone());
-			});
-			<RegistrationPool<T>>::insert(current_round, who.clone(), relay_target);
+			Self::do_request_vault(who, refund_address, None)?;
 
 			Ok(().into())
 		}
@@ -670,6 +632,7 @@ pub mod pallet {
 					<BondedPubKey<T>>::remove(current_round, &pubkey);
 				}
 				<RegistrationPool<T>>::remove(current_round, &who);
+				<RecoveryPaths<T>>::remove(current_round, &who);
 				<BondedRefund<T>>::remove(current_round, &target.refund_address);
 			}
 
@@ -759,6 +722,7 @@ pub mod pallet {
 			<BondedPubKey<T>>::clear_prefix(round, REMOVE_LIMIT, None);
 			<BondedDescriptor<T>>::clear_prefix(round, REMOVE_LIMIT, None);
 			<PreSubmittedPubKeys<T>>::clear_prefix(round, REMOVE_LIMIT, None);
+			<RecoveryPaths<T>>::clear_prefix(round, REMOVE_LIMIT, None);
 
 			Self::deposit_event(Event::RoundDropped(round));
 
@@ -854,6 +818,45 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_vault_descriptor_type())]
+		/// Set the descriptor type of the vault addresses that will be generated.
+		pub fn set_vault_descriptor_type(
+			origin: OriginFor<T>,
+			new: DescriptorType,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let old = VaultDescriptorType::<T>::get();
+			ensure!(new != old, Error::<T>::NoWritingSameValue);
+
+			<VaultDescriptorType<T>>::put(new);
+			Self::deposit_event(Event::VaultDescriptorTypeSet { old, new });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::request_vault_with_recovery())]
+		/// Request a vault address with a timelocked recovery path. The vault can be spent by the
+		/// given public key alone once `RecoveryTimelock` Bitcoin blocks have passed since the
+		/// funds were received.
+		pub fn request_vault_with_recovery(
+			origin: OriginFor<T>,
+			refund_address: UnboundedBytes,
+			recovery_pub_key: Public,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				ServiceState::<T>::get() == MigrationSequence::Normal,
+				Error::<T>::UnderMaintenance
+			);
+
+			let who = ensure_signed(origin)?;
+			Self::do_request_vault(who, refund_address, Some(recovery_pub_key))?;
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
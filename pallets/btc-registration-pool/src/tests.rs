@@ -0,0 +1,118 @@
+use crate::{mock::*, MultiSigRatio, RecoveryPath, VaultDescriptorType};
+use bp_btc_relay::{
+	utils::{
+		descriptor_recovery_script, descriptor_recovery_timelock, estimate_descriptor_input_size,
+	},
+	Descriptor, DescriptorType, Public, PublicKey, Secp256k1, SecretKey, TAPROOT_UNSPENDABLE_KEY,
+};
+use sp_runtime::Percent;
+use sp_std::str::{self, FromStr};
+
+fn public(seed: u8) -> Public {
+	let secret = SecretKey::from_slice(&[seed; 32]).unwrap();
+	Public(secret.public_key(&Secp256k1::new()).serialize())
+}
+
+fn recovery_path() -> RecoveryPath {
+	RecoveryPath { pub_key: public(0xaa), timelock: RecoveryTimelock::get() }
+}
+
+fn vault_descriptor(recovery: Option<RecoveryPath>) -> Descriptor<PublicKey> {
+	let executive_keys = (1..=3).map(public).collect();
+	let (_, descriptor) =
+		BtcRegistrationPool::generate_vault_address(executive_keys, recovery).unwrap();
+	Descriptor::<PublicKey>::from_str(str::from_utf8(&descriptor).unwrap()).unwrap()
+}
+
+/// Checks that the recovery path of the given descriptor is spendable by the recovery key alone
+/// after the configured timelock.
+fn assert_recovery_path(desc: &Descriptor<PublicKey>) {
+	assert_eq!(descriptor_recovery_timelock(desc), Some(RecoveryTimelock::get()));
+	let recovery_script = descriptor_recovery_script(desc).unwrap();
+	assert!(recovery_script.as_bytes().windows(33).any(|key| key == public(0xaa).0));
+	// the vault is still spent through the multisig path
+	assert!(estimate_descriptor_input_size(desc).is_some());
+}
+
+#[test]
+fn wsh_vault_has_a_timelocked_recovery_path() {
+	new_test_ext().execute_with(|| {
+		MultiSigRatio::<Test>::put(Percent::from_percent(67));
+
+		let desc = vault_descriptor(Some(recovery_path()));
+		assert!(matches!(desc, Descriptor::Wsh(_)));
+		assert_recovery_path(&desc);
+		assert_ne!(desc.script_pubkey(), vault_descriptor(None).script_pubkey());
+	});
+}
+
+#[test]
+fn tr_vault_has_a_timelocked_recovery_leaf() {
+	new_test_ext().execute_with(|| {
+		MultiSigRatio::<Test>::put(Percent::from_percent(67));
+		VaultDescriptorType::<Test>::put(DescriptorType::Tr);
+
+		let desc = vault_descriptor(Some(recovery_path()));
+		assert!(matches!(desc, Descriptor::Tr(_)));
+		assert_recovery_path(&desc);
+		assert_ne!(desc.script_pubkey(), vault_descriptor(None).script_pubkey());
+	});
+}
+
+#[test]
+fn vault_without_recovery_has_no_recovery_path() {
+	new_test_ext().execute_with(|| {
+		MultiSigRatio::<Test>::put(Percent::from_percent(67));
+
+		for descriptor_type in [DescriptorType::Wsh, DescriptorType::Tr] {
+			VaultDescriptorType::<Test>::put(descriptor_type);
+			let desc = vault_descriptor(None);
+			assert_eq!(descriptor_recovery_script(&desc), None);
+			assert_eq!(descriptor_recovery_timelock(&desc), None);
+		}
+	});
+}
+
+#[test]
+fn tr_vault_descriptors_and_addresses_match_the_vectors() {
+	new_test_ext().execute_with(|| {
+		// 2-of-3 `multi_a` leaf of the sorted keys under the unspendable internal key
+		MultiSigRatio::<Test>::put(Percent::from_percent(50));
+		VaultDescriptorType::<Test>::put(DescriptorType::Tr);
+		let executive_keys: Vec<Public> = (1..=3).map(public).collect();
+		let multi_a = "multi_a(2,\
+			024d4b6cd1361032ca9bd2aeb9d900aa4d45d9ead80ac9423374c451a7254d0766,\
+			02531fe6068134503d2723133227c867ac8fa6c83c537e9a44c3c5bdbdcb1fe337,\
+			031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f)";
+		let recovery = "and_v(v:pk(\
+			026a04ab98d9e4774ad806e302dddeb63bea16b5cb5f223ee77478e861bb583eb3),older(144))";
+
+		let vectors = [
+			(
+				None,
+				format!("tr({},{})#m2f5jan2", TAPROOT_UNSPENDABLE_KEY, multi_a),
+				"bcrt1pahzuz2jfl5ulh4l270mfsj7yyfpkj07u34r0jsrekj26uxuzwetqqrykeq",
+				// witness: item_count(1) + signatures(64 * 2 + 3) + script(1 + 104) +
+				// control_block(1 + 33)
+				(271 + 41 * 4_u64).div_ceil(4),
+			),
+			(
+				Some(recovery_path()),
+				format!("tr({},{{{},{}}})#e60a06vf", TAPROOT_UNSPENDABLE_KEY, multi_a, recovery),
+				"bcrt1pq9xauzsplsmr2hsvygh0k5u3t3eq6cq0gfnfppmawygmfmnxyctqmufvrw",
+				// the multi_a leaf is one level deeper, so the control block has a merkle node
+				(271 + 32 + 41 * 4_u64).div_ceil(4),
+			),
+		];
+		for (recovery, descriptor, address, input_size) in vectors {
+			let (vault_address, vault_descriptor) =
+				BtcRegistrationPool::generate_vault_address(executive_keys.clone(), recovery)
+					.unwrap();
+			assert_eq!(str::from_utf8(&vault_descriptor).unwrap(), descriptor);
+			assert_eq!(str::from_utf8(&vault_address).unwrap(), address);
+
+			let desc = Descriptor::<PublicKey>::from_str(&descriptor).unwrap();
+			assert_eq!(estimate_descriptor_input_size(&desc), Some(input_size));
+		}
+	});
+}
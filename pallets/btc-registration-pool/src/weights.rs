@@ -52,6 +52,8 @@ pub trait WeightInfo {
 	fn set_max_presubmission() -> Weight;
 	fn set_multi_sig_ratio() -> Weight;
 	fn approve_set_refunds() -> Weight;
+	fn set_vault_descriptor_type() -> Weight;
+	fn request_vault_with_recovery() -> Weight;
 }
 
 /// Weights for `pallet_btc_registration_pool` using the Substrate node and recommended hardware.
@@ -254,6 +256,40 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `BtcRegistrationPool::VaultDescriptorType` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::VaultDescriptorType` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_vault_descriptor_type() -> Weight {
+		Weight::from_parts(16_581_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BtcRegistrationPool::ServiceState` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::ServiceState` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::CurrentRound` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::CurrentRound` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::BondedVault` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::BondedVault` (`max_values`: None, `max_size`: Some(132), added: 2607, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::RegistrationPool` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::RegistrationPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcRegistrationPool::MultiSigRatio` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::MultiSigRatio` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RelayExecutiveMembership::Members` (r:1 w:0)
+	/// Proof: `RelayExecutiveMembership::Members` (`max_values`: Some(1), `max_size`: Some(201), added: 696, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::PreSubmittedPubKeys` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::PreSubmittedPubKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcRegistrationPool::BondedPubKey` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::BondedPubKey` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::RecoveryPaths` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::RecoveryPaths` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::BondedRefund` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::BondedRefund` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn request_vault_with_recovery() -> Weight {
+		Weight::from_parts(233_915_000, 0)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -455,4 +491,38 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 		}
+	/// Storage: `BtcRegistrationPool::VaultDescriptorType` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::VaultDescriptorType` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_vault_descriptor_type() -> Weight {
+		Weight::from_parts(16_581_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BtcRegistrationPool::ServiceState` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::ServiceState` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::CurrentRound` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::CurrentRound` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::BondedVault` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::BondedVault` (`max_values`: None, `max_size`: Some(132), added: 2607, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::RegistrationPool` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::RegistrationPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcRegistrationPool::MultiSigRatio` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::MultiSigRatio` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RelayExecutiveMembership::Members` (r:1 w:0)
+	/// Proof: `RelayExecutiveMembership::Members` (`max_values`: Some(1), `max_size`: Some(201), added: 696, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::PreSubmittedPubKeys` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::PreSubmittedPubKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcRegistrationPool::BondedPubKey` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::BondedPubKey` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::RecoveryPaths` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::RecoveryPaths` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::BondedRefund` (r:1 w:1)
+	/// Proof: `BtcRegistrationPool::BondedRefund` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn request_vault_with_recovery() -> Weight {
+		Weight::from_parts(233_915_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
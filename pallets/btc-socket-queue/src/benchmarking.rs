@@ -1,11 +1,28 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use bp_btc_relay::{traits::PoolManager, Hash, MigrationSequence, Psbt};
+use bp_btc_relay::{
+	blaze::{SelectionStrategy, UtxoInfoWithSize},
+	light_client::MerkleProof,
+	traits::{BlazeManager, LightClientManager, PoolManager},
+	utils::estimate_descriptor_input_size,
+	Descriptor, Hash, MigrationSequence, Psbt, PublicKey,
+};
+use bp_cccp::{Instruction, RequestID, SocketMessage, TaskParams};
+use bp_staking::MajorityThreshold;
 use frame_benchmarking::v2::*;
 use frame_support::pallet_prelude::DispatchError;
 use frame_system::RawOrigin;
 use hex::FromHex;
+use sp_runtime::Perbill;
+use sp_std::str::FromStr;
+
+/// The descriptor of the system vault registered by `PoolManager::set_benchmark`.
+const VAULT_DESCRIPTOR: &str = "wsh(sortedmulti(3,02ece3a9b4c4e42811c4b9d424d76ba4ffeda5e6590d9f6144be1175a0bd54dc0b,03547cb2686e9b53e81bdbe1b2b8a0b5b494cfa05223f5e105fe9364bfbb3aa05f,03b238f9c7bbee00e4e9b3df445ea751a77fe5e4d0eca0f74985676e4a93759c40))";
+
+/// The fee rates (sat/vB) used to composite the benchmarked outbound PSBTs.
+const FEE_RATE: u64 = 10;
+const LONG_TERM_FEE_RATE: u64 = 5;
 
 const DUMMY_UNSIGNED_PSBT_STR: &str = "70736274ff01007d0200000001fe6cf7606b61da1e3a380a0e18891128cb9664dee4cfc4218736174cebe22dd40000000000fdffffff02602e720000000000220020b9f7c13f0cb179daa4ee63ef47c72787a8db1b239ed4d213a38f2c65022b1dc5e0932200000000001600148114cec01b43d48f953a503948f5267165ba76ad00000000000100cf02000000036c0c0f3d8c1591901c8175f9c1d2a9640ef73f17f3859b6282d039cb0397da020100000000fdffffffd548c09b87ba0e57bf3708a42b42b06ede64224ca49a7621db751cc8631967590100000000fdffffffa04edd71b9b8dec9258240f12518b5a2451e962df77c93bad7051aaa34505f630100000000fdffffff02809698000000000022002080b89fa035c251d012e7ba2c6bbe6b0948573fac9c7a243f4a45339abb86caf3bc832301000000001600143ff71794fe168a9514f80e274a7f11046e55eb8bae502c0001012b809698000000000022002080b89fa035c251d012e7ba2c6bbe6b0948573fac9c7a243f4a45339abb86caf301056953210200d16a17d43c25ac12e722a5911666bf0ed143c78d14990241fa5d86158fb9262102010a57a1988a7cea118a0a3e3a81686e29bf93761ea4e9ddd988fff28d6f3aa921021436d8bab43f21c8b74522af3c5a28c4366873efd2c1afe951a314ef2b4cbb1d53ae22060200d16a17d43c25ac12e722a5911666bf0ed143c78d14990241fa5d86158fb926043d937de7220602010a57a1988a7cea118a0a3e3a81686e29bf93761ea4e9ddd988fff28d6f3aa90419e3d0ec2206021436d8bab43f21c8b74522af3c5a28c4366873efd2c1afe951a314ef2b4cbb1d04875a65c400010169532102ece3a9b4c4e42811c4b9d424d76ba4ffeda5e6590d9f6144be1175a0bd54dc0b2103547cb2686e9b53e81bdbe1b2b8a0b5b494cfa05223f5e105fe9364bfbb3aa05f2103b238f9c7bbee00e4e9b3df445ea751a77fe5e4d0eca0f74985676e4a93759c4053ae220202ece3a9b4c4e42811c4b9d424d76ba4ffeda5e6590d9f6144be1175a0bd54dc0b0400378953220203547cb2686e9b53e81bdbe1b2b8a0b5b494cfa05223f5e105fe9364bfbb3aa05f0417edfdb4220203b238f9c7bbee00e4e9b3df445ea751a77fe5e4d0eca0f74985676e4a93759c400401a8c9750000";
 const DUMMY_SIGNED_PSBT_STR: &str = "70736274ff01007d0200000001fe6cf7606b61da1e3a380a0e18891128cb9664dee4cfc4218736174cebe22dd40000000000fdffffff02602e720000000000220020b9f7c13f0cb179daa4ee63ef47c72787a8db1b239ed4d213a38f2c65022b1dc5e0932200000000001600148114cec01b43d48f953a503948f5267165ba76ad00000000000100cf02000000036c0c0f3d8c1591901c8175f9c1d2a9640ef73f17f3859b6282d039cb0397da020100000000fdffffffd548c09b87ba0e57bf3708a42b42b06ede64224ca49a7621db751cc8631967590100000000fdffffffa04edd71b9b8dec9258240f12518b5a2451e962df77c93bad7051aaa34505f630100000000fdffffff02809698000000000022002080b89fa035c251d012e7ba2c6bbe6b0948573fac9c7a243f4a45339abb86caf3bc832301000000001600143ff71794fe168a9514f80e274a7f11046e55eb8bae502c0001012b809698000000000022002080b89fa035c251d012e7ba2c6bbe6b0948573fac9c7a243f4a45339abb86caf322020200d16a17d43c25ac12e722a5911666bf0ed143c78d14990241fa5d86158fb92647304402205940e2bc3adec4e4c6f937052efaf30ddf3492bc6d0ca83bfc796485ac8246d702205dbaeca826d63ea45906ef6e729613e6e3f02a162ae069d9a1913e5e2614446201220202010a57a1988a7cea118a0a3e3a81686e29bf93761ea4e9ddd988fff28d6f3aa94830450221008a5bbc3390eb8895f4b6c15848b3c18fcb16504498fd58ab0cf6fa3ed1cdb1ad022061dc393ed70dc04444122f813756642b077582046837b63069c72b25ce87712e012202021436d8bab43f21c8b74522af3c5a28c4366873efd2c1afe951a314ef2b4cbb1d4830450221009e4c744060de0798bf782e6ad59fedb6984ddadbc80b62faf23250cefcc038f6022030ef5d71850340976ae2d5850152934b6c2a47a0ed87b8c993a6817e368c21860101056953210200d16a17d43c25ac12e722a5911666bf0ed143c78d14990241fa5d86158fb9262102010a57a1988a7cea118a0a3e3a81686e29bf93761ea4e9ddd988fff28d6f3aa921021436d8bab43f21c8b74522af3c5a28c4366873efd2c1afe951a314ef2b4cbb1d53ae22060200d16a17d43c25ac12e722a5911666bf0ed143c78d14990241fa5d86158fb926043d937de7220602010a57a1988a7cea118a0a3e3a81686e29bf93761ea4e9ddd988fff28d6f3aa90419e3d0ec2206021436d8bab43f21c8b74522af3c5a28c4366873efd2c1afe951a314ef2b4cbb1d04875a65c400010169532102ece3a9b4c4e42811c4b9d424d76ba4ffeda5e6590d9f6144be1175a0bd54dc0b2103547cb2686e9b53e81bdbe1b2b8a0b5b494cfa05223f5e105fe9364bfbb3aa05f2103b238f9c7bbee00e4e9b3df445ea751a77fe5e4d0eca0f74985676e4a93759c4053ae220202ece3a9b4c4e42811c4b9d424d76ba4ffeda5e6590d9f6144be1175a0bd54dc0b0400378953220203547cb2686e9b53e81bdbe1b2b8a0b5b494cfa05223f5e105fe9364bfbb3aa05f0417edfdb4220203b238f9c7bbee00e4e9b3df445ea751a77fe5e4d0eca0f74985676e4a93759c400401a8c9750000";
@@ -75,6 +92,39 @@ fn setup_finalized<T: Config>() -> H256 {
 	txid
 }
 
+/// An outbound request of the given sequence that pays 100_000 sats to the given user.
+fn outbound_message(sequence: u64, to: H160) -> SocketMessage {
+	SocketMessage {
+		req_id: RequestID { chain: vec![0; 4], round_id: 1.into(), sequence: sequence.into() },
+		status: 1.into(),
+		ins_code: Instruction { chain: vec![0; 4], method: vec![0; 16] },
+		params: TaskParams {
+			token_idx0: vec![0; 32],
+			token_idx1: vec![0; 32],
+			refund: H160::zero(),
+			to,
+			amount: 100_000u64.into(),
+			variants: vec![],
+		},
+	}
+}
+
+/// `n` UTXOs of the system vault that hold the given amount each.
+fn vault_utxos(n: u32, amount: u64) -> Vec<UtxoInfoWithSize> {
+	let descriptor = Descriptor::<PublicKey>::from_str(VAULT_DESCRIPTOR).expect("Valid descriptor");
+	let input_vbytes = estimate_descriptor_input_size(&descriptor).expect("Valid descriptor");
+	(0..n)
+		.map(|i| UtxoInfoWithSize {
+			hash: H256::from_low_u64_be(i as u64 + 1),
+			txid: H256::from_low_u64_be(i as u64 + 1),
+			vout: 0,
+			amount,
+			descriptor: descriptor.to_string(),
+			input_vbytes,
+		})
+		.collect()
+}
+
 fn setup_executives<T: Config>() -> Result<T::AccountId, DispatchError> {
 	<Authority<T>>::put(account::<T::AccountId>("authority", 0, 0));
 	<BitcoinSocket<T>>::put(account::<T::AccountId>("socket", 0, 0));
@@ -270,5 +320,158 @@ mod benchmarks {
 		_(RawOrigin::Root, psbt_txid);
 	}
 
+	#[benchmark]
+	fn set_vote_threshold() {
+		let threshold = MajorityThreshold::Proportion(Perbill::from_percent(67));
+		<VoteThresholds<T>>::insert(VoteWorkflow::RollbackPoll, MajorityThreshold::Majority);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, VoteWorkflow::RollbackPoll, threshold);
+
+		assert_eq!(<VoteThresholds<T>>::get(VoteWorkflow::RollbackPoll), threshold);
+	}
+
+	#[benchmark]
+	fn set_spv_confirmations() {
+		let confirmations = <SpvConfirmations<T>>::get().saturating_add(1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, confirmations);
+
+		assert_eq!(<SpvConfirmations<T>>::get(), confirmations);
+	}
+
+	#[benchmark]
+	fn submit_broadcast_proof() {
+		let _ = setup_executives::<T>();
+		let txid = setup_finalized::<T>();
+
+		// Setup: Require SPV proofs and include the transaction in a verified block
+		T::Blaze::set_activation(true).expect("Blaze activated");
+		<SpvConfirmations<T>>::put(1);
+		let height = T::LightClient::set_benchmark(txid).expect("Valid block");
+
+		let msg = BroadcastProofMessage {
+			authority_id: account("authority", 0, 0),
+			txid,
+			height,
+			merkle_proof: MerkleProof { siblings: vec![], index: 0, tx_count: 1 },
+		};
+		let signature = T::Signature::decode(&mut [0u8; 65].as_ref()).expect("valid signature");
+
+		#[extrinsic_call]
+		_(RawOrigin::None, msg, signature);
+
+		// the request is executed once the broadcast confirmation votes reach the threshold
+		assert!(
+			<ProvenBroadcasts<T>>::contains_key(&txid)
+				|| <ExecutedRequests<T>>::contains_key(&txid)
+		);
+	}
+
+	#[benchmark]
+	fn set_coin_selection_policy() {
+		let policy = CoinSelectionPolicy {
+			dust_relay_fee_rate: 1,
+			max_selection_weight: 100_000,
+			max_tries: 50_000,
+			change_spend_multiplier: 2,
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, policy);
+
+		assert_eq!(<SelectionPolicy<T>>::get(), policy);
+	}
+
+	#[benchmark]
+	fn outbound_pool(n: Linear<1, 1_000>) {
+		let user: H160 = setup_executives::<T>().expect("Valid executives").into();
+		let messages = (0..n).map(|sequence| outbound_message(sequence as u64, user).encode());
+		T::Blaze::set_outbound_pool(messages.collect()).expect("Valid outbound pool");
+		let outbound_requests;
+
+		#[block]
+		{
+			let outbound_pool = T::Blaze::get_outbound_batch(u32::MAX.into());
+			outbound_requests = Pallet::<T>::filter_unregistered_outbounds(outbound_pool).1;
+		}
+
+		// every pooled request is paid to a registered user
+		assert_eq!(outbound_requests.len(), T::Blaze::get_outbound_pool_len());
+	}
+
+	#[benchmark]
+	fn coin_selection() {
+		let user: H160 = setup_executives::<T>().expect("Valid executives").into();
+		let policy = <SelectionPolicy<T>>::get();
+		let script_pubkey = Descriptor::<PublicKey>::from_str(VAULT_DESCRIPTOR)
+			.expect("Valid descriptor")
+			.script_pubkey();
+		// the largest batch has to be funded by many small UTXOs
+		let outbound_requests = (0..T::Blaze::get_batch_policy().max_outputs)
+			.map(|sequence| (outbound_message(sequence as u64, user), script_pubkey.clone()))
+			.collect::<Vec<_>>();
+		let utxos = vault_utxos(500, 30_000);
+		let composited;
+
+		#[block]
+		{
+			composited = Pallet::<T>::get_change_output(FEE_RATE, LONG_TERM_FEE_RATE, &policy)
+				.and_then(|change| {
+					Pallet::<T>::try_composite_outbound_psbt(
+						&utxos,
+						&outbound_requests,
+						FEE_RATE,
+						LONG_TERM_FEE_RATE,
+						&policy,
+						&change,
+					)
+				});
+		}
+
+		assert!(composited
+			.is_none_or(|(psbt, selected)| psbt.unsigned_tx.input.len() == selected.len()));
+	}
+
+	#[benchmark]
+	fn outbound_batch(n: Linear<1, 100>, i: Linear<1, 500>) {
+		let user: H160 = setup_executives::<T>().expect("Valid executives").into();
+		let script_pubkey = Descriptor::<PublicKey>::from_str(VAULT_DESCRIPTOR)
+			.expect("Valid descriptor")
+			.script_pubkey();
+		let outbound_requests = (0..n)
+			.map(|sequence| (outbound_message(sequence as u64, user), script_pubkey.clone()))
+			.collect::<Vec<_>>();
+		let outbound_pool =
+			outbound_requests.iter().map(|(msg, _)| msg.encode()).collect::<Vec<_>>();
+		T::Blaze::set_outbound_pool(outbound_pool.clone()).expect("Valid outbound pool");
+
+		// the selected UTXOs cover the batch without a change output
+		let target = 100_000 * n as u64;
+		let utxos = vault_utxos(i, target / i as u64 + 1_000);
+		T::Blaze::set_utxos(utxos.clone()).expect("Valid UTXOs");
+		let psbt = Pallet::<T>::composite_psbt(
+			&utxos,
+			&outbound_requests,
+			target,
+			FEE_RATE,
+			SelectionStrategy::Bnb,
+			&<SelectionPolicy<T>>::get(),
+			&ChangeOutput { script_pubkey, cost_of_change: 0, min_value: 0 },
+		)
+		.expect("Valid PSBT");
+		let txid;
+
+		#[block]
+		{
+			txid =
+				Pallet::<T>::submit_outbound_batch(&psbt, outbound_pool, outbound_requests, &utxos);
+		}
+
+		assert!(<PendingRequests<T>>::contains_key(&txid));
+		assert!((0..n).all(|sequence| <SocketMessages<T>>::contains_key(U256::from(sequence))));
+	}
+
 	impl_benchmark_test_suite!(Pallet, mock::new_test_ext(), mock::Test);
 }
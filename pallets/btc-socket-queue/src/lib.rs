@@ -10,17 +10,23 @@ mod benchmarking;
 
 #[cfg(test)]
 mod mock;
+#[cfg(test)]
+mod tests;
 
 pub mod migrations;
 pub mod weights;
 use weights::WeightInfo;
 
-use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
-use bp_btc_relay::{BoundedBitcoinAddress, UnboundedBytes, MULTI_SIG_MAX_ACCOUNTS};
+use bp_btc_relay::{
+	light_client::MerkleProof, BoundedBitcoinAddress, UnboundedBytes, DUST_RELAY_FEE_RATE,
+	MULTI_SIG_MAX_ACCOUNTS,
+};
 use bp_cccp::RequestID;
 use bp_staking::MAX_AUTHORITIES;
+use miniscript::bitcoin::ScriptBuf;
 use sp_core::{ConstU32, RuntimeDebug, H160, H256, U256};
 use sp_runtime::BoundedBTreeMap;
 use sp_std::{vec, vec::Vec};
@@ -31,6 +37,15 @@ const CALL_GAS_LIMIT: u64 = 1_000_000;
 /// The function selector of `BitcoinSocket::txs()`.
 const BITCOIN_SOCKET_TXS_FUNCTION_SELECTOR: &str = "986ba392";
 
+/// The maximum dust relay fee rate (sat/vB) of the coin selection policy.
+pub const MAX_DUST_RELAY_FEE_RATE: u64 = 100;
+
+/// The maximum total weight of the selected UTXOs of the coin selection policy.
+pub const MAX_SELECTION_WEIGHT: u64 = 200_000;
+
+/// The maximum number of branch and bound tries of the coin selection policy.
+pub const MAX_SELECTION_TRIES: u32 = 100_000;
+
 pub(crate) const LOG_TARGET: &'static str = "runtime::socket-queue";
 
 // syntactic sugar for logging.
@@ -44,6 +59,73 @@ macro_rules! log {
 	};
 }
 
+#[derive(
+	Decode,
+	DecodeWithMemTracking,
+	Encode,
+	TypeInfo,
+	MaxEncodedLen,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+)]
+/// The relay workflows that require the relayers to reach a vote threshold.
+pub enum VoteWorkflow {
+	/// The broadcast confirmation of executed requests (BLAZE mode only).
+	BroadcastConfirmation,
+	/// The approval of rollback requests.
+	RollbackPoll,
+}
+
+#[derive(
+	Decode,
+	DecodeWithMemTracking,
+	Encode,
+	TypeInfo,
+	MaxEncodedLen,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+)]
+/// The coin selection policy used to composite outbound PSBTs (BLAZE mode only).
+pub struct CoinSelectionPolicy {
+	/// The fee rate (sat/vB) used to calculate the dust threshold of each output type.
+	pub dust_relay_fee_rate: u64,
+	/// The maximum total weight of the selected UTXOs.
+	pub max_selection_weight: u64,
+	/// The maximum number of tries for the branch and bound selection.
+	pub max_tries: u32,
+	/// A change output is only created if its value is at least this many times
+	/// the fee required to spend it at the current fee rate.
+	pub change_spend_multiplier: u64,
+}
+
+impl Default for CoinSelectionPolicy {
+	fn default() -> Self {
+		Self {
+			dust_relay_fee_rate: DUST_RELAY_FEE_RATE,
+			max_selection_weight: MAX_SELECTION_WEIGHT,
+			max_tries: MAX_SELECTION_TRIES,
+			change_spend_multiplier: 1,
+		}
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+/// The change output of an outbound PSBT. (= system vault)
+pub struct ChangeOutput {
+	/// The script pubkey of the system vault.
+	pub script_pubkey: ScriptBuf,
+	/// The cost of creating the change output now and spending it later.
+	pub cost_of_change: u64,
+	/// The minimum value of the change output. Lower changes are absorbed as fees.
+	pub min_value: u64,
+}
+
 #[derive(Decode, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
 /// The submitted PSBT information for a rollback request.
 pub struct RollbackRequest<AccountId> {
@@ -213,6 +295,19 @@ pub struct ExecutedPsbtMessage<AccountId> {
 	pub txid: H256,
 }
 
+#[derive(Decode, DecodeWithMemTracking, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+/// The message payload for the SPV proof of an executed PSBT.
+pub struct BroadcastProofMessage<AccountId> {
+	/// The authority's account address.
+	pub authority_id: AccountId,
+	/// The executed PSBT's txid.
+	pub txid: H256,
+	/// The height of the block that includes the transaction.
+	pub height: u32,
+	/// The merkle proof of the transaction.
+	pub merkle_proof: MerkleProof,
+}
+
 #[derive(Decode, DecodeWithMemTracking, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
 /// The message payload for rollback PSBT submission.
 pub struct RollbackPsbtMessage<AccountId> {
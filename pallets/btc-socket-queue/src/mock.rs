@@ -1,9 +1,10 @@
 use crate as pallet_btc_socket_queue;
 
 use bp_btc_relay::{
-	blaze::{ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
-	traits::{BlazeManager, PoolManager},
-	BoundedBitcoinAddress, MigrationSequence, UnboundedBytes,
+	blaze::{BatchPolicy, ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
+	light_client::MerkleProof,
+	traits::{BlazeManager, LightClientManager, PoolManager},
+	BoundedBitcoinAddress, Descriptor, MigrationSequence, Network, PublicKey, UnboundedBytes,
 };
 use bp_core::{AccountId, Balance, BlockNumber};
 use bp_staking::traits::Authorities;
@@ -17,8 +18,9 @@ use pallet_evm::FeeCalculator;
 use sp_core::{H256, U256};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	DispatchError,
+	BoundedVec, BuildStorage, DispatchError,
 };
+use std::cell::RefCell;
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -177,12 +179,28 @@ impl Authorities<AccountId> for MockRelayers {
 	}
 }
 
+thread_local! {
+	/// The descriptor of the system vault of the current round
+	pub static SYSTEM_VAULT: RefCell<Option<Descriptor<PublicKey>>> = RefCell::new(None);
+}
+
+/// The address of the system vault of the current round
+pub fn system_vault() -> Option<BoundedBitcoinAddress> {
+	SYSTEM_VAULT.with(|v| {
+		v.borrow().as_ref().map(|desc| {
+			let address = desc.address(Network::Regtest).unwrap().to_string();
+			BoundedVec::try_from(address.into_bytes()).unwrap()
+		})
+	})
+}
+
 pub struct MockPoolManager;
 impl PoolManager<AccountId> for MockPoolManager {
-	fn get_bonded_descriptor(
-		_: &BoundedBitcoinAddress,
-	) -> Option<miniscript::Descriptor<miniscript::bitcoin::PublicKey>> {
-		None
+	fn get_bonded_descriptor(address: &BoundedBitcoinAddress) -> Option<Descriptor<PublicKey>> {
+		if system_vault().as_ref() != Some(address) {
+			return None;
+		}
+		SYSTEM_VAULT.with(|v| v.borrow().clone())
 	}
 
 	fn get_refund_address(_: &AccountId) -> Option<BoundedBitcoinAddress> {
@@ -194,7 +212,7 @@ impl PoolManager<AccountId> for MockPoolManager {
 	}
 
 	fn get_system_vault(_: u32) -> Option<BoundedBitcoinAddress> {
-		None
+		system_vault()
 	}
 
 	fn get_bitcoin_network() -> bp_btc_relay::Network {
@@ -250,6 +268,10 @@ impl BlazeManager<Test> for MockBlazeManager {
 		vec![]
 	}
 
+	fn get_sweep_utxos() -> Vec<UtxoInfoWithSize> {
+		vec![]
+	}
+
 	fn clear_utxos() {}
 
 	fn lock_utxos(_: &H256, _: &Vec<UtxoInfoWithSize>) -> Result<(), DispatchError> {
@@ -270,6 +292,18 @@ impl BlazeManager<Test> for MockBlazeManager {
 		vec![]
 	}
 
+	fn get_outbound_pool_len() -> usize {
+		0
+	}
+
+	fn get_outbound_batch(_: BlockNumberFor<Test>) -> Vec<UnboundedBytes> {
+		vec![]
+	}
+
+	fn get_batch_policy() -> BatchPolicy {
+		Default::default()
+	}
+
 	fn clear_outbound_pool(_: Vec<UnboundedBytes>) {}
 
 	fn try_fee_rate_finalization(_: BlockNumberFor<Test>) -> Option<(u64, u64)> {
@@ -299,6 +333,32 @@ impl BlazeManager<Test> for MockBlazeManager {
 	fn set_activation(_: bool) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_outbound_pool(_: Vec<UnboundedBytes>) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_utxos(_: Vec<UtxoInfoWithSize>) -> Result<(), DispatchError> {
+		Ok(())
+	}
+}
+
+pub struct MockLightClient;
+impl LightClientManager for MockLightClient {
+	fn best_height() -> u32 {
+		0
+	}
+
+	fn verify_tx_inclusion(_: &[u8], _: &MerkleProof, _: u32, _: u32) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_benchmark(_: H256) -> Result<u32, DispatchError> {
+		Ok(0)
+	}
 }
 
 impl pallet_btc_socket_queue::Config for Test {
@@ -308,12 +368,15 @@ impl pallet_btc_socket_queue::Config for Test {
 	type Relayers = MockRelayers;
 	type RegistrationPool = MockPoolManager;
 	type Blaze = MockBlazeManager;
+	type LightClient = MockLightClient;
 	type WeightInfo = ();
 	type DefaultMaxFeeRate = DefaultMaxFeeRate;
 	type DefaultMaxSocketMessageBytes = DefaultMaxSocketMessageBytes;
 }
 
-#[cfg(feature = "runtime-benchmarks")]
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+	let mut ext: sp_io::TestExternalities =
+		frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,18 +1,22 @@
 use super::pallet::*;
 use crate::{
-	HashKeyRequest, RequestType, TxInfo, BITCOIN_SOCKET_TXS_FUNCTION_SELECTOR, CALL_GAS_LIMIT,
+	ChangeOutput, CoinSelectionPolicy, HashKeyRequest, PsbtRequest, RequestType, TxInfo,
+	VoteWorkflow, BITCOIN_SOCKET_TXS_FUNCTION_SELECTOR, CALL_GAS_LIMIT,
 };
 use bp_btc_relay::{
-	blaze::{SelectionStrategy, UtxoInfoWithSize},
-	traits::{BlazeManager, PoolManager, SocketQueueManager},
-	utils::estimate_finalized_input_size,
+	blaze::{ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
+	traits::{BlazeManager, OnCheckpointSet, PoolManager, SocketQueueManager},
+	utils::{
+		dust_threshold, estimate_descriptor_input_size, estimate_finalized_input_size,
+		estimate_finalized_taproot_input_size,
+	},
 	Address, BoundedBitcoinAddress, Hash, Psbt, PsbtExt, Script, Secp256k1, Txid, UnboundedBytes,
 };
 use bp_cccp::{
 	traits::SocketVerifier, RequestInfo, SocketMessage, UserRequest,
 	SOCKET_GET_REQUEST_FUNCTION_SELECTOR,
 };
-use bp_staking::traits::Authorities;
+use bp_staking::{traits::Authorities, MAX_AUTHORITIES};
 use ethabi_decode::ParamKind;
 use frame_support::ensure;
 use miniscript::{
@@ -20,6 +24,7 @@ use miniscript::{
 		absolute::LockTime,
 		bip32::{DerivationPath, Fingerprint},
 		psbt::{Input, Output},
+		taproot::{LeafVersion, TapLeafHash},
 		transaction::Version,
 		Amount, OutPoint, PublicKey, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
 	},
@@ -27,13 +32,20 @@ use miniscript::{
 };
 use pallet_evm::Runner;
 use scale_info::prelude::{format, string::ToString};
-use sp_core::{Get, H160, H256, U256};
+use sp_core::{ConstU32, Get, H160, H256, U256};
 use sp_io::hashing::keccak_256;
 use sp_runtime::{
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
-	BoundedVec, DispatchError,
+	BoundedBTreeMap, BoundedVec, DispatchError,
+};
+use sp_std::{
+	boxed::Box,
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	str,
+	str::FromStr,
+	vec,
+	vec::Vec,
 };
-use sp_std::{boxed::Box, collections::btree_map::BTreeMap, str, str::FromStr, vec, vec::Vec};
 
 impl<T: Config> SocketVerifier<T::AccountId> for Pallet<T>
 where
@@ -179,12 +191,57 @@ impl<T: Config> SocketQueueManager<T::AccountId> for Pallet<T> {
 	}
 }
 
+impl<T: Config> OnCheckpointSet for Pallet<T> {
+	fn on_checkpoint_set() -> u32 {
+		// the broadcasts have to be proven again against the new chain
+		<ProvenBroadcasts<T>>::clear(u32::MAX, None).unique
+	}
+}
+
 impl<T> Pallet<T>
 where
 	T: Config,
 	T::AccountId: Into<H160>,
 	H160: Into<T::AccountId>,
 {
+	/// The number of votes required to reach the threshold of the given workflow.
+	pub fn vote_threshold(workflow: VoteWorkflow) -> u32 {
+		T::Relayers::threshold(<VoteThresholds<T>>::get(workflow))
+	}
+
+	/// Check if the broadcast of the given request is confirmed. The broadcast is confirmed once
+	/// the votes reach the threshold and, when SPV proofs are required, the broadcast is proven.
+	/// The votes are stored until then.
+	pub(crate) fn try_confirm_broadcast(
+		txid: &H256,
+		confirmations: BoundedBTreeMap<T::AccountId, bool, ConstU32<MAX_AUTHORITIES>>,
+	) -> bool {
+		let is_proven =
+			<SpvConfirmations<T>>::get() == 0 || <ProvenBroadcasts<T>>::contains_key(txid);
+		if T::Relayers::count_authorities(confirmations.keys())
+			>= Self::vote_threshold(VoteWorkflow::BroadcastConfirmation)
+			&& is_proven
+		{
+			<BroadcastConfirmations<T>>::remove(txid);
+			<ProvenBroadcasts<T>>::remove(txid);
+			T::Relayers::note_majority_reached(&confirmations.keys().cloned().collect::<Vec<_>>());
+			true
+		} else {
+			<BroadcastConfirmations<T>>::insert(txid, confirmations);
+			false
+		}
+	}
+
+	/// Move the given finalized request to the executed requests.
+	pub(crate) fn execute_request(txid: H256, request: PsbtRequest<T::AccountId>) {
+		if request.request_type == RequestType::Migration {
+			T::RegistrationPool::execute_migration_tx(txid.clone());
+		}
+		<FinalizedRequests<T>>::remove(&txid);
+		<ExecutedRequests<T>>::insert(&txid, request);
+		Self::deposit_event(Event::RequestExecuted { txid });
+	}
+
 	/// Try to finalize the latest combined PSBT.
 	pub fn try_psbt_finalization(combined: Psbt) -> Result<Psbt, DispatchError> {
 		let secp = Secp256k1::new();
@@ -233,10 +290,18 @@ where
 
 		for (i, input) in psbt.inputs.iter().enumerate() {
 			let txin = psbt.unsigned_tx.input.get(i).ok_or(Error::<T>::InvalidPsbt)?;
-			let input_vb = estimate_finalized_input_size(
-				input.witness_script.as_ref().ok_or(Error::<T>::InvalidPsbt)?,
-				Some(txin),
-			)
+			let input_vb = if let Some(witness_script) = input.witness_script.as_ref() {
+				estimate_finalized_input_size(witness_script, Some(txin))
+			} else {
+				// taproot inputs are spent through the multi_a script path
+				input.tap_scripts.iter().find_map(|(control_block, (leaf_script, _))| {
+					estimate_finalized_taproot_input_size(
+						leaf_script,
+						control_block.size(),
+						Some(txin),
+					)
+				})
+			}
 			.ok_or(Error::<T>::InvalidPsbt)?;
 			total_vb += input_vb;
 		}
@@ -382,6 +447,8 @@ where
 		let system_vault = T::RegistrationPool::get_system_vault(current_round)
 			.ok_or(Error::<T>::SystemVaultDNE)?;
 
+		let dust_relay_fee_rate = <SelectionPolicy<T>>::get().dust_relay_fee_rate;
+
 		let mut deserialized_msgs = vec![];
 		let mut serialized_msgs = vec![];
 		let mut msg_sequences = vec![];
@@ -390,6 +457,11 @@ where
 			unchecked_outputs.into_iter().collect();
 
 		for output in psbt_outputs {
+			// dust outputs would not be relayed by the Bitcoin network
+			if output.value.to_sat() < dust_threshold(&output.script_pubkey, dust_relay_fee_rate) {
+				return Err(Error::<T>::InvalidPsbt.into());
+			}
+
 			let to: BoundedBitcoinAddress = BoundedVec::try_from(
 				Self::try_convert_to_address_from_script(output.script_pubkey.as_script())?
 					.to_string()
@@ -484,6 +556,195 @@ where
 		(outbound_pool, outbound_requests)
 	}
 
+	/// Fit the given outbounds (ordered by priority) into a single batch.
+	/// An outbound is skipped if it requires a new output over `max_outputs`
+	/// or if the total amount would reach the available vault balance.
+	pub fn fit_outbound_batch(
+		outbound_pool: Vec<UnboundedBytes>,
+		outbound_requests: Vec<(SocketMessage, ScriptBuf)>,
+		max_outputs: u32,
+		available: u64,
+	) -> (Vec<UnboundedBytes>, Vec<(SocketMessage, ScriptBuf)>) {
+		let mut batch = vec![];
+		let mut batch_requests = vec![];
+		let mut scripts = BTreeSet::new();
+		let mut amount_sum = 0u64;
+
+		for (raw, request) in outbound_pool.into_iter().zip(outbound_requests.into_iter()) {
+			// requests to the same address are merged into a single output
+			if !scripts.contains(&request.1) && scripts.len() as u32 >= max_outputs {
+				continue;
+			}
+			let amount = request.0.params.amount.as_u64();
+			match amount_sum.checked_add(amount) {
+				Some(sum) if sum < available => amount_sum = sum,
+				_ => continue,
+			}
+			scripts.insert(request.1.clone());
+			batch.push(raw);
+			batch_requests.push(request);
+		}
+		(batch, batch_requests)
+	}
+
+	/// Select coins and composite an outbound PSBT for the given requests.
+	/// Returns the PSBT and the selected UTXOs.
+	pub fn try_composite_outbound_psbt(
+		utxos: &[UtxoInfoWithSize],
+		outbound_requests: &[(SocketMessage, ScriptBuf)],
+		fee_rate: u64,
+		long_term_fee_rate: u64,
+		policy: &CoinSelectionPolicy,
+		change: &ChangeOutput,
+	) -> Option<(Psbt, Vec<UtxoInfoWithSize>)> {
+		let outbound_amount_sum =
+			outbound_requests.iter().map(|x| x.0.params.amount.as_u64()).sum::<u64>();
+
+		// estimate output vbytes for fee-adjusted target
+		// TxOut size = 8 (value) + 1 (script_len varint) + script_len
+		let unique_scripts: BTreeSet<_> = outbound_requests.iter().map(|x| x.1.clone()).collect();
+		let output_vbytes: u64 = unique_scripts.iter().map(|s| 9u64 + s.len() as u64).sum();
+		// 11 = version(4) + locktime(4) + input_count(1) + output_count(1) + segwit(1)
+		let base_fee = fee_rate * (11 + output_vbytes);
+		let target = outbound_amount_sum.saturating_add(base_fee);
+
+		let scored_utxos = utxos
+			.iter()
+			.filter_map(|x| {
+				let fee = x.input_vbytes * fee_rate;
+				if x.amount < fee {
+					return None;
+				}
+				Some(ScoredUtxo {
+					utxo: x.clone(),
+					fee,
+					long_term_fee: x.input_vbytes * long_term_fee_rate,
+					effective_value: x.amount - fee,
+				})
+			})
+			.collect::<Vec<_>>();
+
+		let (selected_utxos, strategy) = T::Blaze::select_coins(
+			scored_utxos,
+			target,
+			change.cost_of_change,
+			policy.max_selection_weight,
+			policy.max_tries as usize,
+			change.min_value,
+		)?;
+		let psbt = Self::composite_psbt(
+			&selected_utxos,
+			outbound_requests,
+			outbound_amount_sum,
+			fee_rate,
+			strategy,
+			policy,
+			change,
+		)?;
+		Some((psbt, selected_utxos))
+	}
+
+	/// Submit the composited outbound PSBT as a pending request. The batched messages leave the
+	/// outbound pool and the selected UTXOs are locked until the request is executed.
+	pub(crate) fn submit_outbound_batch(
+		psbt: &Psbt,
+		outbound_pool: Vec<UnboundedBytes>,
+		outbound_requests: Vec<(SocketMessage, ScriptBuf)>,
+		selected_utxos: &Vec<UtxoInfoWithSize>,
+	) -> H256 {
+		let txid = Self::convert_txid(psbt.unsigned_tx.compute_txid());
+		<PendingRequests<T>>::insert(
+			&txid,
+			PsbtRequest::new(psbt.serialize(), outbound_pool.clone(), RequestType::Normal),
+		);
+		Self::deposit_event(Event::UnsignedPsbtSubmitted { txid });
+
+		for (msg, _) in outbound_requests.into_iter() {
+			<SocketMessages<T>>::insert(msg.req_id.sequence, (txid, msg));
+		}
+
+		T::Blaze::clear_fee_rates();
+		T::Blaze::clear_outbound_pool(outbound_pool);
+		T::Blaze::lock_utxos(&txid, selected_utxos).unwrap();
+		T::Blaze::handle_tolerance_counter(false);
+		txid
+	}
+
+	/// Get the change output (= system vault) of an outbound PSBT with the given fee rates.
+	pub fn get_change_output(
+		fee_rate: u64,
+		long_term_fee_rate: u64,
+		policy: &CoinSelectionPolicy,
+	) -> Option<ChangeOutput> {
+		let system_vault =
+			T::RegistrationPool::get_system_vault(T::RegistrationPool::get_current_round())?;
+		let descriptor = T::RegistrationPool::get_bonded_descriptor(&system_vault)?;
+		let script_pubkey = descriptor.script_pubkey();
+
+		// TxOut size = 8 (value) + 1 (script_len varint) + script_len
+		let output_vbytes = 9 + script_pubkey.len() as u64;
+		let spend_vbytes = estimate_descriptor_input_size(&descriptor)?;
+
+		let cost_of_change = fee_rate
+			.saturating_mul(output_vbytes)
+			.saturating_add(long_term_fee_rate.saturating_mul(spend_vbytes));
+		let min_value = dust_threshold(&script_pubkey, policy.dust_relay_fee_rate).max(
+			policy
+				.change_spend_multiplier
+				.saturating_mul(spend_vbytes.saturating_mul(fee_rate)),
+		);
+
+		Some(ChangeOutput { script_pubkey, cost_of_change, min_value })
+	}
+
+	/// Composite a PSBT that sweeps the given user vault UTXOs into the system vault before their
+	/// recovery path matures. The UTXOs are taken in the given order up to the maximum selection
+	/// weight, skipping the ones that can't pay for their own input. Returns the PSBT's txid.
+	pub fn sweep_recovery_utxos(
+		sweep_utxos: Vec<UtxoInfoWithSize>,
+		fee_rate: u64,
+		long_term_fee_rate: u64,
+	) -> Option<H256> {
+		let policy = <SelectionPolicy<T>>::get();
+		let change = Self::get_change_output(fee_rate, long_term_fee_rate, &policy)?;
+
+		let mut selected_utxos = vec![];
+		let mut selection_weight = 0u64;
+		for utxo in sweep_utxos {
+			if utxo.amount <= utxo.input_vbytes.saturating_mul(fee_rate) {
+				continue;
+			}
+			if selection_weight.saturating_add(utxo.input_vbytes) > policy.max_selection_weight {
+				break;
+			}
+			selection_weight += utxo.input_vbytes;
+			selected_utxos.push(utxo);
+		}
+		if selected_utxos.is_empty() {
+			return None;
+		}
+
+		// everything but the fee goes to the change output
+		let psbt = Self::composite_psbt(
+			&selected_utxos,
+			&[],
+			0,
+			fee_rate,
+			SelectionStrategy::Knapsack,
+			&policy,
+			&change,
+		)?;
+		let txid = Self::convert_txid(psbt.unsigned_tx.compute_txid());
+		<PendingRequests<T>>::insert(
+			&txid,
+			PsbtRequest::new(psbt.serialize(), vec![], RequestType::Normal),
+		);
+
+		T::Blaze::clear_fee_rates();
+		T::Blaze::lock_utxos(&txid, &selected_utxos).unwrap();
+		Some(txid)
+	}
+
 	/// Composite PSBT.
 	pub fn composite_psbt(
 		selected_utxos: &[UtxoInfoWithSize],
@@ -491,6 +752,8 @@ where
 		target: u64,
 		fee_rate: u64,
 		selection_strategy: SelectionStrategy,
+		policy: &CoinSelectionPolicy,
+		change: &ChangeOutput,
 	) -> Option<Psbt> {
 		let input = selected_utxos
 			.iter()
@@ -517,35 +780,38 @@ where
 		}
 		let mut output = merged_output
 			.into_iter()
-			.filter(|(_, value)| value.to_sat() > bp_btc_relay::DUST_LIMIT)
+			.filter(|(script_pubkey, value)| {
+				value.to_sat() >= dust_threshold(script_pubkey, policy.dust_relay_fee_rate)
+			})
 			.map(|(script_pubkey, value)| TxOut { value, script_pubkey })
 			.collect::<Vec<_>>();
-		if output.is_empty() {
+		// a PSBT without outbound requests (= sweep) only has the change output
+		if output.is_empty() && !outbound_requests.is_empty() {
 			return None;
 		}
 		if selection_strategy == SelectionStrategy::Knapsack {
 			let input_sum = selected_utxos.iter().map(|x| x.amount).sum::<u64>();
 			let input_size_sum = selected_utxos.iter().map(|x| x.input_vbytes).sum::<u64>();
-			let output_size_sum = output.iter().map(|x| x.size() as u64).sum::<u64>() + 43;
+			let change_size = 9 + change.script_pubkey.len() as u64;
+			let output_size_sum = output.iter().map(|x| x.size() as u64).sum::<u64>() + change_size;
 			let estimated_size = 11 + input_size_sum + output_size_sum;
 			let fee = fee_rate.saturating_mul(estimated_size);
 
 			if let Some(change_amount) =
 				input_sum.checked_sub(target).and_then(|v| v.checked_sub(fee))
 			{
-				if change_amount > bp_btc_relay::DUST_LIMIT {
-					let system_vault = T::RegistrationPool::get_system_vault(
-						T::RegistrationPool::get_current_round(),
-					)?;
-					let system_vault = Self::try_convert_to_address_from_vec(system_vault).ok()?;
+				if change_amount >= change.min_value {
 					output.push(TxOut {
 						value: Amount::from_sat(change_amount),
-						script_pubkey: system_vault.script_pubkey(),
+						script_pubkey: change.script_pubkey.clone(),
 					});
 				}
-				// else: change < dust limit, absorbed as fee
+				// else: change is not worth spending, absorbed as fee
 			}
 		}
+		if output.is_empty() {
+			return None;
+		}
 
 		let tx = Transaction {
 			version: Version::TWO,
@@ -565,17 +831,51 @@ where
 							value: Amount::from_sat(x.amount),
 							script_pubkey: descriptor.script_pubkey(),
 						});
-						psbt_input.witness_script = Some(descriptor.script_code().unwrap());
-
-						let mut derivation = BTreeMap::new();
-						descriptor.for_each_key(|x| {
-							derivation.insert(
-								x.inner,
-								(Fingerprint::default(), DerivationPath::default()),
-							);
-							true
-						});
-						psbt_input.bip32_derivation = derivation;
+
+						if let Descriptor::Tr(tr) = &descriptor {
+							let spend_info = tr.spend_info();
+							psbt_input.tap_internal_key = Some(spend_info.internal_key());
+							psbt_input.tap_merkle_root = spend_info.merkle_root();
+
+							for (_, leaf) in tr.iter_scripts() {
+								let leaf_script = leaf.encode();
+								let leaf_hash =
+									TapLeafHash::from_script(&leaf_script, LeafVersion::TapScript);
+								if let Some(control_block) = spend_info
+									.control_block(&(leaf_script.clone(), LeafVersion::TapScript))
+								{
+									psbt_input.tap_scripts.insert(
+										control_block,
+										(leaf_script, LeafVersion::TapScript),
+									);
+								}
+								for pk in leaf.iter_pk() {
+									psbt_input
+										.tap_key_origins
+										.entry(pk.inner.x_only_public_key().0)
+										.or_insert_with(|| {
+											(
+												vec![],
+												(Fingerprint::default(), DerivationPath::default()),
+											)
+										})
+										.0
+										.push(leaf_hash);
+								}
+							}
+						} else {
+							psbt_input.witness_script = Some(descriptor.script_code().unwrap());
+
+							let mut derivation = BTreeMap::new();
+							descriptor.for_each_key(|x| {
+								derivation.insert(
+									x.inner,
+									(Fingerprint::default(), DerivationPath::default()),
+								);
+								true
+							});
+							psbt_input.bip32_derivation = derivation;
+						}
 						psbt_input
 					})
 					.collect::<Vec<_>>();
@@ -1,8 +1,10 @@
 mod impls;
 
 use crate::{
-	migrations, ExecutedPsbtMessage, PsbtRequest, RequestType, RollbackPollMessage,
-	RollbackPsbtMessage, RollbackRequest, SignedPsbtMessage, UnsignedPsbtMessage, WeightInfo,
+	migrations, BroadcastProofMessage, CoinSelectionPolicy, ExecutedPsbtMessage, PsbtRequest,
+	RequestType, RollbackPollMessage, RollbackPsbtMessage, RollbackRequest, SignedPsbtMessage,
+	UnsignedPsbtMessage, VoteWorkflow, WeightInfo, MAX_DUST_RELAY_FEE_RATE, MAX_SELECTION_TRIES,
+	MAX_SELECTION_WEIGHT,
 };
 
 use frame_support::{
@@ -12,13 +14,12 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 
 use bp_btc_relay::{
-	blaze::ScoredUtxo,
-	traits::{BlazeManager, PoolManager, SocketQueueManager},
+	traits::{BlazeManager, LightClientManager, PoolManager, SocketQueueManager},
 	Amount, BoundedBitcoinAddress, MigrationSequence, UnboundedBytes,
 };
 use bp_cccp::SocketMessage;
-use bp_staking::{traits::Authorities, MAX_AUTHORITIES};
-use miniscript::bitcoin::FeeRate;
+use bp_staking::{traits::Authorities, MajorityThreshold, MAX_AUTHORITIES};
+use miniscript::bitcoin::{consensus::serialize, FeeRate};
 use scale_info::prelude::string::ToString;
 use sp_core::ConstU32;
 use sp_core::{H160, H256, U256};
@@ -51,6 +52,8 @@ pub mod pallet {
 		type RegistrationPool: PoolManager<Self::AccountId>;
 		/// The Blaze pallet.
 		type Blaze: BlazeManager<Self>;
+		/// The Bitcoin light client.
+		type LightClient: LightClientManager;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 		/// The maximum fee rate that can be set for PSBT.
@@ -111,6 +114,10 @@ pub mod pallet {
 		UnderMaintenance,
 		/// The PSBT fee rate was not set properly.
 		InvalidFeeRate,
+		/// SPV proofs are not required.
+		SpvNotRequired,
+		/// The broadcast of this request has already been proven.
+		BroadcastAlreadyProven,
 	}
 
 	#[pallet::event]
@@ -144,6 +151,14 @@ pub mod pallet {
 		MaxSocketMessageBytesSet { new: u32 },
 		/// A relayer has confirmed the broadcast of an executed request.
 		BroadcastConfirmed { txid: H256, authority_id: T::AccountId },
+		/// The vote threshold of a relay workflow has been set.
+		VoteThresholdSet { workflow: VoteWorkflow, old: MajorityThreshold, new: MajorityThreshold },
+		/// The required SPV confirmations has been set.
+		SpvConfirmationsSet { old: u32, new: u32 },
+		/// The SPV proof of an executed request has been verified.
+		BroadcastProven { txid: H256, authority_id: T::AccountId, height: u32 },
+		/// The coin selection policy has been set.
+		CoinSelectionPolicySet { old: CoinSelectionPolicy, new: CoinSelectionPolicy },
 	}
 
 	#[pallet::storage]
@@ -234,6 +249,28 @@ pub mod pallet {
 		BoundedBTreeMap<T::AccountId, bool, ConstU32<MAX_AUTHORITIES>>,
 	>;
 
+	#[pallet::storage]
+	/// The vote threshold of each relay workflow. Defaults to the simple majority of the relayers.
+	/// key: The relay workflow.
+	/// value: The required vote threshold.
+	pub type VoteThresholds<T: Config> =
+		StorageMap<_, Twox64Concat, VoteWorkflow, MajorityThreshold, ValueQuery>;
+
+	#[pallet::storage]
+	/// The number of Bitcoin confirmations required for the SPV proof of a broadcast (Blaze mode only).
+	/// If zero, requests are executed by the broadcast confirmation votes only.
+	pub type SpvConfirmations<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	/// Finalized requests whose broadcast has been proven by an SPV proof.
+	/// key: The PSBT's txid.
+	/// value: The height of the block that includes the transaction.
+	pub type ProvenBroadcasts<T: Config> = StorageMap<_, Twox64Concat, H256, u32>;
+
+	#[pallet::storage]
+	/// The coin selection policy used to composite outbound PSBTs (BLAZE mode only).
+	pub type SelectionPolicy<T: Config> = StorageValue<_, CoinSelectionPolicy, ValueQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
 	where
@@ -254,7 +291,26 @@ pub mod pallet {
 
 				if let Some((long_term_fee_rate, fee_rate)) = T::Blaze::try_fee_rate_finalization(n)
 				{
-					let outbound_pool = T::Blaze::get_outbound_pool();
+					// user vault UTXOs are swept before their recovery path matures
+					let sweep_utxos = T::Blaze::get_sweep_utxos();
+					if !sweep_utxos.is_empty() {
+						if let Some(txid) =
+							Self::sweep_recovery_utxos(sweep_utxos, fee_rate, long_term_fee_rate)
+						{
+							Self::deposit_event(Event::UnsignedPsbtSubmitted { txid });
+							return weight
+								.saturating_add(Weight::from_parts(100_000_000, 0))
+								.saturating_add(T::DbWeight::get().reads(5 as u64))
+								.saturating_add(T::DbWeight::get().writes(3 as u64));
+						}
+					}
+
+					// every pooled message is decoded and sorted, even when none of them is batched
+					weight = weight.saturating_add(<T as Config>::WeightInfo::outbound_pool(
+						T::Blaze::get_outbound_pool_len() as u32,
+					));
+
+					let outbound_pool = T::Blaze::get_outbound_batch(n);
 					if !outbound_pool.is_empty() {
 						let (filtered_outbound_pool, outbound_requests) =
 							Self::filter_unregistered_outbounds(outbound_pool);
@@ -264,96 +320,72 @@ pub mod pallet {
 						}
 
 						let utxos = T::Blaze::get_utxos();
-
-						let outbound_amount_sum = outbound_requests
-							.iter()
-							.map(|x| x.0.params.amount.as_u64())
-							.sum::<u64>();
 						let blaze_vault_sum = utxos.iter().map(|x| x.amount).sum::<u64>();
 
-						if outbound_amount_sum >= blaze_vault_sum {
+						// the outbounds that do not fit roll into the next batch
+						let (mut filtered_outbound_pool, mut outbound_requests) =
+							Self::fit_outbound_batch(
+								filtered_outbound_pool,
+								outbound_requests,
+								T::Blaze::get_batch_policy().max_outputs,
+								blaze_vault_sum,
+							);
+
+						if filtered_outbound_pool.is_empty() {
 							T::Blaze::handle_tolerance_counter(true);
 							return weight;
 						}
 
-						// estimate output vbytes for fee-adjusted target
-						// TxOut size = 8 (value) + 1 (script_len varint) + script_len
-						let unique_scripts: sp_std::collections::btree_set::BTreeSet<_> =
-							outbound_requests.iter().map(|x| x.1.clone()).collect();
-						let output_vbytes: u64 =
-							unique_scripts.iter().map(|s| 9u64 + s.len() as u64).sum();
-						// 11 = version(4) + locktime(4) + input_count(1) + output_count(1) + segwit(1)
-						let base_fee = fee_rate * (11 + output_vbytes);
-						let target = outbound_amount_sum.saturating_add(base_fee);
-
-						let scored_utxos = utxos
-							.iter()
-							.filter_map(|x| {
-								let fee = x.input_vbytes * fee_rate;
-								if x.amount < fee {
-									return None;
-								}
-								Some(ScoredUtxo {
-									utxo: x.clone(),
-									fee,
-									long_term_fee: x.input_vbytes * long_term_fee_rate,
-									effective_value: x.amount - fee,
-								})
-							})
-							.collect::<Vec<_>>();
-
-						let (selected_utxos, strategy) = match T::Blaze::select_coins(
-							scored_utxos,
-							target,
-							43 * fee_rate,
-							200_000,
-							100_000,
-							bp_btc_relay::DUST_LIMIT,
-						) {
-							Some(utxos) => utxos,
+						let policy = <SelectionPolicy<T>>::get();
+						let change =
+							match Self::get_change_output(fee_rate, long_term_fee_rate, &policy) {
+								Some(change) => change,
+								None => {
+									T::Blaze::handle_tolerance_counter(true);
+									return weight;
+								},
+							};
+
+						// on failure, retry with a smaller prefix of the batch.
+						// the remaining outbounds roll into the next batch.
+						let mut batch_len = outbound_requests.len();
+						let mut composited = None;
+						while batch_len > 0 {
+							weight = weight
+								.saturating_add(<T as Config>::WeightInfo::coin_selection());
+							composited = Self::try_composite_outbound_psbt(
+								&utxos,
+								&outbound_requests[..batch_len],
+								fee_rate,
+								long_term_fee_rate,
+								&policy,
+								&change,
+							);
+							if composited.is_some() {
+								break;
+							}
+							batch_len /= 2;
+						}
+						let (psbt, selected_utxos) = match composited {
+							Some(composited) => composited,
 							None => {
 								T::Blaze::handle_tolerance_counter(true);
 								return weight;
 							},
 						};
-						match Self::composite_psbt(
+						filtered_outbound_pool.truncate(batch_len);
+						outbound_requests.truncate(batch_len);
+
+						Self::submit_outbound_batch(
+							&psbt,
+							filtered_outbound_pool,
+							outbound_requests,
 							&selected_utxos,
-							&outbound_requests,
-							outbound_amount_sum,
-							fee_rate,
-							strategy,
-						) {
-							Some(psbt) => {
-								let txid = Self::convert_txid(psbt.unsigned_tx.compute_txid());
-								<PendingRequests<T>>::insert(
-									&txid,
-									PsbtRequest::new(
-										psbt.serialize(),
-										filtered_outbound_pool.clone(),
-										RequestType::Normal,
-									),
-								);
-								Self::deposit_event(Event::UnsignedPsbtSubmitted { txid });
-
-								for msg in filtered_outbound_pool.iter() {
-									let msg = SocketMessage::try_from(msg.clone()).unwrap();
-									<SocketMessages<T>>::insert(msg.req_id.sequence, (txid, msg));
-								}
-
-								T::Blaze::clear_fee_rates();
-								T::Blaze::clear_outbound_pool(filtered_outbound_pool);
-								T::Blaze::lock_utxos(&txid, &selected_utxos).unwrap();
-								T::Blaze::handle_tolerance_counter(false);
-
-								weight += Weight::from_parts(100_000_000, 0)
-									.saturating_add(T::DbWeight::get().reads(5 as u64))
-									.saturating_add(T::DbWeight::get().writes(3 as u64));
-							},
-							_ => {
-								T::Blaze::handle_tolerance_counter(true);
-								return weight;
-							},
-						};
+						);
+						weight = weight.saturating_add(<T as Config>::WeightInfo::outbound_batch(
+							batch_len as u32,
+							selected_utxos.len() as u32,
+						));
 					}
 				}
 			}
@@ -553,7 +585,8 @@ pub mod pallet {
 		/// Submit an executed PSBT request.
 		/// When Blaze is disabled, the single authority immediately marks the request as executed.
 		/// When Blaze is enabled, each relayer votes to confirm broadcast; the request moves to
-		/// `ExecutedRequests` only once a majority of relayers have confirmed.
+		/// `ExecutedRequests` only once a majority of relayers have confirmed (and the broadcast
+		/// has been proven, if SPV proofs are required).
 		pub fn submit_executed_request(
 			origin: OriginFor<T>,
 			msg: ExecutedPsbtMessage<T::AccountId>,
@@ -578,26 +611,16 @@ pub mod pallet {
 
 				Self::deposit_event(Event::BroadcastConfirmed { txid, authority_id });
 
-				if confirmations.len() as u32 >= T::Relayers::majority() {
-					<BroadcastConfirmations<T>>::remove(&txid);
-					true
-				} else {
-					<BroadcastConfirmations<T>>::insert(&txid, confirmations);
-					false
-				}
+				Self::try_confirm_broadcast(&txid, confirmations)
 			} else {
 				// Clean up any stale broadcast confirmations accumulated before Blaze fallback.
 				<BroadcastConfirmations<T>>::remove(&txid);
+				<ProvenBroadcasts<T>>::remove(&txid);
 				true
 			};
 
 			if should_execute {
-				if request.request_type == RequestType::Migration {
-					T::RegistrationPool::execute_migration_tx(txid.clone());
-				}
-				<FinalizedRequests<T>>::remove(&txid);
-				<ExecutedRequests<T>>::insert(&txid, request);
-				Self::deposit_event(Event::RequestExecuted { txid });
+				Self::execute_request(txid, request);
 			}
 
 			Ok(().into())
@@ -733,11 +756,15 @@ pub mod pallet {
 
 			Self::deposit_event(Event::RollbackPollSubmitted { txid, authority_id, is_approved });
 
-			if rollback_request.votes.iter().filter(|v| *v.1).count() as u32
-				>= T::Relayers::majority()
+			if T::Relayers::count_authorities(
+				rollback_request.votes.iter().filter(|v| *v.1).map(|v| v.0),
+			) >= Self::vote_threshold(VoteWorkflow::RollbackPoll)
 			{
 				// approve request and move the `PendingRequests`
 				rollback_request.is_approved = true;
+				T::Relayers::note_majority_reached(
+					&rollback_request.votes.keys().cloned().collect::<Vec<_>>(),
+				);
 				<PendingRequests<T>>::insert(
 					&txid,
 					PsbtRequest::new(
@@ -993,6 +1020,109 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_vote_threshold())]
+		/// Set the vote threshold of the given relay workflow.
+		pub fn set_vote_threshold(
+			origin: OriginFor<T>,
+			workflow: VoteWorkflow,
+			threshold: MajorityThreshold,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let old = <VoteThresholds<T>>::get(workflow);
+			ensure!(old != threshold, Error::<T>::NoWritingSameValue);
+
+			<VoteThresholds<T>>::insert(workflow, threshold);
+			Self::deposit_event(Event::VoteThresholdSet { workflow, old, new: threshold });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_spv_confirmations())]
+		/// Set the number of Bitcoin confirmations required for the SPV proof of a broadcast.
+		/// Zero disables SPV proofs.
+		pub fn set_spv_confirmations(
+			origin: OriginFor<T>,
+			confirmations: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let old = <SpvConfirmations<T>>::get();
+			ensure!(old != confirmations, Error::<T>::NoWritingSameValue);
+
+			<SpvConfirmations<T>>::put(confirmations);
+			Self::deposit_event(Event::SpvConfirmationsSet { old, new: confirmations });
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config>::WeightInfo::submit_broadcast_proof())]
+		/// Submit the SPV proof of an executed PSBT request (Blaze mode only).
+		/// The request will be executed if the broadcast confirmations already reached the threshold.
+		pub fn submit_broadcast_proof(
+			origin: OriginFor<T>,
+			msg: BroadcastProofMessage<T::AccountId>,
+			_signature: T::Signature,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let confirmations = <SpvConfirmations<T>>::get();
+			ensure!(T::Blaze::is_activated() && confirmations > 0, Error::<T>::SpvNotRequired);
+
+			let BroadcastProofMessage { authority_id, txid, height, merkle_proof } = msg;
+
+			let request = <FinalizedRequests<T>>::get(&txid).ok_or(Error::<T>::RequestDNE)?;
+			ensure!(
+				!<ProvenBroadcasts<T>>::contains_key(&txid),
+				Error::<T>::BroadcastAlreadyProven
+			);
+			// the vault inputs are segwit, so the broadcast transaction without its witness data
+			// serializes as the unsigned transaction
+			let psbt = Self::try_get_checked_psbt(&request.unsigned_psbt)?;
+			let raw_tx = serialize(&psbt.unsigned_tx);
+			T::LightClient::verify_tx_inclusion(&raw_tx, &merkle_proof, height, confirmations)?;
+
+			<ProvenBroadcasts<T>>::insert(&txid, height);
+			Self::deposit_event(Event::BroadcastProven { txid, authority_id, height });
+
+			let votes = <BroadcastConfirmations<T>>::get(&txid).unwrap_or_default();
+			if Self::try_confirm_broadcast(&txid, votes) {
+				Self::execute_request(txid, request);
+			}
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_coin_selection_policy())]
+		/// Set the coin selection policy used to composite outbound PSBTs.
+		/// The dust relay fee rate, the selection weight and the number of tries are capped to
+		/// bound the cost of the coin selection in `on_initialize`.
+		pub fn set_coin_selection_policy(
+			origin: OriginFor<T>,
+			policy: CoinSelectionPolicy,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(
+				(1..=MAX_DUST_RELAY_FEE_RATE).contains(&policy.dust_relay_fee_rate)
+					&& (1..=MAX_SELECTION_WEIGHT).contains(&policy.max_selection_weight)
+					&& (1..=MAX_SELECTION_TRIES).contains(&policy.max_tries),
+				Error::<T>::OutOfRange
+			);
+
+			let old = <SelectionPolicy<T>>::get();
+			ensure!(old != policy, Error::<T>::NoWritingSameValue);
+
+			<SelectionPolicy<T>>::put(policy);
+			Self::deposit_event(Event::CoinSelectionPolicySet { old, new: policy });
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -1069,6 +1199,24 @@ pub mod pallet {
 						.propagate(true)
 						.build()
 				},
+				Call::submit_broadcast_proof { msg, signature } => {
+					let BroadcastProofMessage { authority_id, txid, .. } = msg;
+					Self::verify_authority(authority_id)?;
+
+					// verify if the signature was originated from the authority_id.
+					let message =
+						[keccak_256("BroadcastProof".as_bytes()).as_slice(), txid.as_ref()]
+							.concat();
+					if !signature.verify(&*message, authority_id) {
+						return InvalidTransaction::BadProof.into();
+					}
+
+					ValidTransaction::with_tag_prefix("BroadcastProofSubmission")
+						.priority(TransactionPriority::MAX)
+						.and_provides((authority_id, txid))
+						.propagate(true)
+						.build()
+				},
 				Call::submit_rollback_poll { msg, signature } => {
 					let RollbackPollMessage { authority_id, txid, is_approved } = msg;
 
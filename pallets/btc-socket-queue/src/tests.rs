@@ -0,0 +1,247 @@
+use crate::{
+	mock::*, ChangeOutput, CoinSelectionPolicy, Error, SelectionPolicy, MAX_DUST_RELAY_FEE_RATE,
+	MAX_SELECTION_TRIES, MAX_SELECTION_WEIGHT,
+};
+use bp_btc_relay::{
+	utils::{dust_threshold, estimate_descriptor_input_size},
+	Address, Amount, BoundedBitcoinAddress, Descriptor, Network, Psbt, PublicKey, Secp256k1,
+	SecretKey, UnboundedBytes, DUST_RELAY_FEE_RATE,
+};
+use bp_cccp::{Instruction, RequestID, SocketMessage, TaskParams};
+use frame_support::{assert_noop, assert_ok};
+use miniscript::bitcoin::{
+	absolute::LockTime, transaction::Version, ScriptBuf, Transaction, TxOut,
+};
+use sp_core::H160;
+use sp_runtime::{BoundedVec, DispatchError};
+use std::str::FromStr;
+
+fn pub_key(seed: u8) -> PublicKey {
+	let secret = SecretKey::from_slice(&[seed; 32]).unwrap();
+	PublicKey::new(secret.public_key(&Secp256k1::new()))
+}
+
+fn descriptor(desc: &str) -> Descriptor<PublicKey> {
+	Descriptor::<PublicKey>::from_str(desc).unwrap()
+}
+
+/// Set a 2-of-3 system vault for the current round
+fn set_system_vault() -> Descriptor<PublicKey> {
+	let desc =
+		descriptor(&format!("wsh(sortedmulti(2,{},{},{}))", pub_key(1), pub_key(2), pub_key(3)));
+	SYSTEM_VAULT.with(|v| *v.borrow_mut() = Some(desc.clone()));
+	desc
+}
+
+fn address(script_pubkey: &ScriptBuf) -> BoundedBitcoinAddress {
+	let address = Address::from_script(script_pubkey, Network::Regtest).unwrap();
+	BoundedVec::try_from(address.to_string().into_bytes()).unwrap()
+}
+
+/// Verify an unsigned PSBT with the given outputs and return the error, if any
+fn verify_outputs(
+	outputs: &[(ScriptBuf, u64)],
+	unchecked_outputs: &[(BoundedBitcoinAddress, Vec<Vec<u8>>)],
+) -> Option<DispatchError> {
+	let psbt = Psbt::from_unsigned_tx(Transaction {
+		version: Version::TWO,
+		lock_time: LockTime::ZERO,
+		input: vec![],
+		output: outputs
+			.iter()
+			.map(|(script_pubkey, value)| TxOut {
+				value: Amount::from_sat(*value),
+				script_pubkey: script_pubkey.clone(),
+			})
+			.collect(),
+	})
+	.unwrap();
+	BtcSocketQueue::try_psbt_output_verification(&psbt, unchecked_outputs.to_vec()).err()
+}
+
+/// An outbound request of the given amount to the given script
+fn outbound(
+	sequence: u64,
+	amount: u64,
+	script_pubkey: &ScriptBuf,
+) -> (UnboundedBytes, (SocketMessage, ScriptBuf)) {
+	let msg = SocketMessage {
+		req_id: RequestID { chain: vec![0; 4], round_id: 1.into(), sequence: sequence.into() },
+		status: 1.into(),
+		ins_code: Instruction { chain: vec![0; 4], method: vec![0; 16] },
+		params: TaskParams {
+			token_idx0: vec![0; 32],
+			token_idx1: vec![0; 32],
+			refund: H160::zero(),
+			to: H160::zero(),
+			amount: amount.into(),
+			variants: vec![],
+		},
+	};
+	(msg.encode(), (msg, script_pubkey.clone()))
+}
+
+fn fit_outbound_batch(
+	outbounds: &[(UnboundedBytes, (SocketMessage, ScriptBuf))],
+	max_outputs: u32,
+	available: u64,
+) -> Vec<UnboundedBytes> {
+	let (pool, requests): (Vec<_>, Vec<_>) = outbounds.iter().cloned().unzip();
+	let (batch, batch_requests) =
+		BtcSocketQueue::fit_outbound_batch(pool, requests, max_outputs, available);
+	assert_eq!(batch.len(), batch_requests.len());
+	batch
+}
+
+#[test]
+fn outbound_batch_is_capped_by_max_outputs() {
+	new_test_ext().execute_with(|| {
+		let user = |seed| descriptor(&format!("wpkh({})", pub_key(seed))).script_pubkey();
+		let outbounds = [
+			outbound(1, 1_000, &user(4)),
+			outbound(2, 1_000, &user(5)),
+			// merged into the output of the first request
+			outbound(3, 1_000, &user(4)),
+			outbound(4, 1_000, &user(6)),
+			outbound(5, 1_000, &user(5)),
+		];
+		let raw = |i: usize| outbounds[i].0.clone();
+
+		assert_eq!(
+			fit_outbound_batch(&outbounds, 2, 100_000),
+			vec![raw(0), raw(1), raw(2), raw(4)]
+		);
+		assert_eq!(fit_outbound_batch(&outbounds, 1, 100_000), vec![raw(0), raw(2)]);
+		assert_eq!(fit_outbound_batch(&outbounds, 3, 100_000).len(), outbounds.len());
+	});
+}
+
+#[test]
+fn outbound_batch_is_capped_by_the_available_amount() {
+	new_test_ext().execute_with(|| {
+		let user = descriptor(&format!("wpkh({})", pub_key(4))).script_pubkey();
+		let outbounds = [
+			outbound(1, 6_000, &user),
+			outbound(2, 5_000, &user),
+			outbound(3, 3_000, &user),
+			outbound(4, u64::MAX, &user),
+		];
+
+		// the requests that do not fit are skipped, the smaller ones after them still fit
+		assert_eq!(
+			fit_outbound_batch(&outbounds, 100, 10_000),
+			vec![outbounds[0].0.clone(), outbounds[2].0.clone()]
+		);
+		// a request of the whole available amount leaves nothing for the fee
+		assert_eq!(fit_outbound_batch(&outbounds, 100, 3_000), Vec::<UnboundedBytes>::new());
+		assert_eq!(fit_outbound_batch(&outbounds, 100, 0), Vec::<UnboundedBytes>::new());
+	});
+}
+
+#[test]
+fn change_output_requires_a_system_vault() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BtcSocketQueue::get_change_output(10, 5, &Default::default()), None);
+	});
+}
+
+#[test]
+fn change_output_is_worth_spending() {
+	new_test_ext().execute_with(|| {
+		let desc = set_system_vault();
+		let script_pubkey = desc.script_pubkey();
+		let spend_vbytes = estimate_descriptor_input_size(&desc).unwrap();
+
+		// value(8) + script_pubkey_len(1) + P2WSH script_pubkey(34)
+		let cost_of_change = 10 * 43 + 5 * spend_vbytes;
+		assert_eq!(
+			BtcSocketQueue::get_change_output(10, 5, &Default::default()),
+			Some(ChangeOutput {
+				script_pubkey: script_pubkey.clone(),
+				cost_of_change,
+				min_value: 10 * spend_vbytes,
+			})
+		);
+
+		let policy = CoinSelectionPolicy { change_spend_multiplier: 3, ..Default::default() };
+		let change = BtcSocketQueue::get_change_output(10, 5, &policy).unwrap();
+		assert_eq!(change.cost_of_change, cost_of_change);
+		assert_eq!(change.min_value, 3 * 10 * spend_vbytes);
+
+		// a cheap change output must still not be dust
+		let policy = CoinSelectionPolicy { change_spend_multiplier: 0, ..Default::default() };
+		let change = BtcSocketQueue::get_change_output(1, 1, &policy).unwrap();
+		assert_eq!(change.min_value, dust_threshold(&script_pubkey, DUST_RELAY_FEE_RATE));
+		assert_eq!(change.min_value, 330);
+	});
+}
+
+#[test]
+fn dust_outputs_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let vault = set_system_vault().script_pubkey();
+		let user = descriptor(&format!("wpkh({})", pub_key(4))).script_pubkey();
+		let other = descriptor(&format!("wpkh({})", pub_key(5))).script_pubkey();
+		let unchecked_outputs = [(address(&vault), vec![]), (address(&other), vec![])];
+
+		let threshold = dust_threshold(&user, DUST_RELAY_FEE_RATE);
+		assert_eq!(threshold, 294);
+		assert_eq!(
+			verify_outputs(
+				&[(vault.clone(), 10_000), (user.clone(), threshold - 1)],
+				&unchecked_outputs
+			),
+			Some(Error::<Test>::InvalidPsbt.into())
+		);
+		// the output is not dust, but it is not an unchecked output either
+		assert_eq!(
+			verify_outputs(
+				&[(vault.clone(), 10_000), (user.clone(), threshold)],
+				&unchecked_outputs
+			),
+			Some(Error::<Test>::InvalidUncheckedOutput.into())
+		);
+		// the change output is checked as well
+		assert_eq!(
+			verify_outputs(&[(vault.clone(), 329), (user.clone(), threshold)], &unchecked_outputs),
+			Some(Error::<Test>::InvalidPsbt.into())
+		);
+
+		// the threshold follows the dust relay fee rate of the policy
+		SelectionPolicy::<Test>::put(CoinSelectionPolicy {
+			dust_relay_fee_rate: 10,
+			..Default::default()
+		});
+		assert_eq!(
+			verify_outputs(&[(vault, 10_000), (user.clone(), threshold)], &unchecked_outputs),
+			Some(Error::<Test>::InvalidPsbt.into())
+		);
+	});
+}
+
+#[test]
+fn coin_selection_policy_is_capped() {
+	new_test_ext().execute_with(|| {
+		let default = CoinSelectionPolicy::default();
+		for policy in [
+			CoinSelectionPolicy { dust_relay_fee_rate: 0, ..default },
+			CoinSelectionPolicy { dust_relay_fee_rate: MAX_DUST_RELAY_FEE_RATE + 1, ..default },
+			CoinSelectionPolicy { max_selection_weight: MAX_SELECTION_WEIGHT + 1, ..default },
+			CoinSelectionPolicy { max_tries: MAX_SELECTION_TRIES + 1, ..default },
+		] {
+			assert_noop!(
+				BtcSocketQueue::set_coin_selection_policy(RuntimeOrigin::root(), policy),
+				Error::<Test>::OutOfRange
+			);
+		}
+
+		let policy = CoinSelectionPolicy {
+			dust_relay_fee_rate: MAX_DUST_RELAY_FEE_RATE,
+			max_selection_weight: MAX_SELECTION_WEIGHT,
+			max_tries: MAX_SELECTION_TRIES,
+			change_spend_multiplier: 2,
+		};
+		assert_ok!(BtcSocketQueue::set_coin_selection_policy(RuntimeOrigin::root(), policy));
+		assert_eq!(SelectionPolicy::<Test>::get(), policy);
+	});
+}
@@ -52,6 +52,13 @@ pub trait WeightInfo {
 	fn submit_bump_fee_request() -> Weight;
 	fn drop_pending_rollback_request() -> Weight;
 	fn set_max_socket_message_bytes() -> Weight;
+	fn set_vote_threshold() -> Weight;
+	fn set_spv_confirmations() -> Weight;
+	fn submit_broadcast_proof() -> Weight;
+	fn set_coin_selection_policy() -> Weight;
+	fn outbound_pool(n: u32) -> Weight;
+	fn coin_selection() -> Weight;
+	fn outbound_batch(n: u32, i: u32) -> Weight;
 }
 
 /// Weights for `pallet_btc_socket_queue` using the Substrate node and recommended hardware.
@@ -303,6 +310,118 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `BtcSocketQueue::VoteThresholds` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_vote_threshold() -> Weight {
+		Weight::from_parts(17_930_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BtcSocketQueue::SpvConfirmations` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_spv_confirmations() -> Weight {
+		Weight::from_parts(17_610_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BtcSocketQueue::SpvConfirmations` (r:1 w:0)
+	/// Proof: `BtcSocketQueue::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::IsActivated` (r:1 w:0)
+	/// Proof: `Blaze::IsActivated` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::FinalizedRequests` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::FinalizedRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcSocketQueue::ProvenBroadcasts` (r:2 w:1)
+	/// Proof: `BtcSocketQueue::ProvenBroadcasts` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::MainChain` (r:1 w:0)
+	/// Proof: `BtcLightClient::MainChain` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::Headers` (r:1 w:0)
+	/// Proof: `BtcLightClient::Headers` (`max_values`: None, `max_size`: Some(164), added: 2639, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::BestHeight` (r:1 w:0)
+	/// Proof: `BtcLightClient::BestHeight` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::BroadcastConfirmations` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::BroadcastConfirmations` (`max_values`: None, `max_size`: Some(33356), added: 35831, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::VoteThresholds` (r:1 w:0)
+	/// Proof: `BtcSocketQueue::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::SelectedRelayers` (r:1 w:0)
+	/// Proof: `RelayManager::SelectedRelayers` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::ExecutedRequests` (r:0 w:1)
+	/// Proof: `BtcSocketQueue::ExecutedRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn submit_broadcast_proof() -> Weight {
+		Weight::from_parts(90_652_000, 36821)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `BtcSocketQueue::SelectionPolicy` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::SelectionPolicy` (`max_values`: Some(1), `max_size`: Some(28), added: 523, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_coin_selection_policy() -> Weight {
+		Weight::from_parts(17_790_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Blaze::OutboundPool` (r:1 w:0)
+	/// Proof: `Blaze::OutboundPool` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::OutboundBatchPolicy` (r:1 w:0)
+	/// Proof: `Blaze::OutboundBatchPolicy` (`max_values`: Some(1), `max_size`: Some(13), added: 508, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::OutboundArrivals` (r:1000 w:0)
+	/// Proof: `Blaze::OutboundArrivals` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::CurrentRound` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::CurrentRound` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::RegistrationPool` (r:1000 w:0)
+	/// Proof: `BtcRegistrationPool::RegistrationPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 1000]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn outbound_pool(n: u32) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(35_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `BtcSocketQueue::SelectionPolicy` (r:1 w:0)
+	/// Proof: `BtcSocketQueue::SelectionPolicy` (`max_values`: Some(1), `max_size`: Some(28), added: 523, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::CurrentRound` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::CurrentRound` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::SystemVault` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::SystemVault` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcRegistrationPool::ServiceState` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::ServiceState` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::BondedDescriptor` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::BondedDescriptor` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn coin_selection() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+	}
+	/// Storage: `BtcSocketQueue::PendingRequests` (r:0 w:1)
+	/// Proof: `BtcSocketQueue::PendingRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcSocketQueue::SocketMessages` (r:0 w:100)
+	/// Proof: `BtcSocketQueue::SocketMessages` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::FeeRates` (r:1 w:1)
+	/// Proof: `Blaze::FeeRates` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::OutboundPool` (r:1 w:1)
+	/// Proof: `Blaze::OutboundPool` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::OutboundArrivals` (r:0 w:100)
+	/// Proof: `Blaze::OutboundArrivals` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::Utxos` (r:500 w:500)
+	/// Proof: `Blaze::Utxos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::PendingTxs` (r:0 w:1)
+	/// Proof: `Blaze::PendingTxs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::ToleranceCounter` (r:1 w:0)
+	/// Proof: `Blaze::ToleranceCounter` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 100]`.
+	/// The range of component `i` is `[1, 500]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn outbound_batch(n: u32, i: u32) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(i.into())))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -553,4 +672,116 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-}
\ No newline at end of file
+	/// Storage: `BtcSocketQueue::VoteThresholds` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_vote_threshold() -> Weight {
+		Weight::from_parts(17_930_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BtcSocketQueue::SpvConfirmations` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_spv_confirmations() -> Weight {
+		Weight::from_parts(17_610_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BtcSocketQueue::SpvConfirmations` (r:1 w:0)
+	/// Proof: `BtcSocketQueue::SpvConfirmations` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::IsActivated` (r:1 w:0)
+	/// Proof: `Blaze::IsActivated` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::FinalizedRequests` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::FinalizedRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcSocketQueue::ProvenBroadcasts` (r:2 w:1)
+	/// Proof: `BtcSocketQueue::ProvenBroadcasts` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::MainChain` (r:1 w:0)
+	/// Proof: `BtcLightClient::MainChain` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::Headers` (r:1 w:0)
+	/// Proof: `BtcLightClient::Headers` (`max_values`: None, `max_size`: Some(164), added: 2639, mode: `MaxEncodedLen`)
+	/// Storage: `BtcLightClient::BestHeight` (r:1 w:0)
+	/// Proof: `BtcLightClient::BestHeight` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::BroadcastConfirmations` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::BroadcastConfirmations` (`max_values`: None, `max_size`: Some(33356), added: 35831, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::VoteThresholds` (r:1 w:0)
+	/// Proof: `BtcSocketQueue::VoteThresholds` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::SelectedRelayers` (r:1 w:0)
+	/// Proof: `RelayManager::SelectedRelayers` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// Storage: `BtcSocketQueue::ExecutedRequests` (r:0 w:1)
+	/// Proof: `BtcSocketQueue::ExecutedRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn submit_broadcast_proof() -> Weight {
+		Weight::from_parts(90_652_000, 36821)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `BtcSocketQueue::SelectionPolicy` (r:1 w:1)
+	/// Proof: `BtcSocketQueue::SelectionPolicy` (`max_values`: Some(1), `max_size`: Some(28), added: 523, mode: `MaxEncodedLen`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_coin_selection_policy() -> Weight {
+		Weight::from_parts(17_790_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Blaze::OutboundPool` (r:1 w:0)
+	/// Proof: `Blaze::OutboundPool` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::OutboundBatchPolicy` (r:1 w:0)
+	/// Proof: `Blaze::OutboundBatchPolicy` (`max_values`: Some(1), `max_size`: Some(13), added: 508, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::OutboundArrivals` (r:1000 w:0)
+	/// Proof: `Blaze::OutboundArrivals` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::CurrentRound` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::CurrentRound` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::RegistrationPool` (r:1000 w:0)
+	/// Proof: `BtcRegistrationPool::RegistrationPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 1000]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn outbound_pool(n: u32) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(35_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `BtcSocketQueue::SelectionPolicy` (r:1 w:0)
+	/// Proof: `BtcSocketQueue::SelectionPolicy` (`max_values`: Some(1), `max_size`: Some(28), added: 523, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::CurrentRound` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::CurrentRound` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::SystemVault` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::SystemVault` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcRegistrationPool::ServiceState` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::ServiceState` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BtcRegistrationPool::BondedDescriptor` (r:1 w:0)
+	/// Proof: `BtcRegistrationPool::BondedDescriptor` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn coin_selection() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+	}
+	/// Storage: `BtcSocketQueue::PendingRequests` (r:0 w:1)
+	/// Proof: `BtcSocketQueue::PendingRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BtcSocketQueue::SocketMessages` (r:0 w:100)
+	/// Proof: `BtcSocketQueue::SocketMessages` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::FeeRates` (r:1 w:1)
+	/// Proof: `Blaze::FeeRates` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::OutboundPool` (r:1 w:1)
+	/// Proof: `Blaze::OutboundPool` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::OutboundArrivals` (r:0 w:100)
+	/// Proof: `Blaze::OutboundArrivals` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Blaze::Utxos` (r:500 w:500)
+	/// Proof: `Blaze::Utxos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::PendingTxs` (r:0 w:1)
+	/// Proof: `Blaze::PendingTxs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Blaze::ToleranceCounter` (r:1 w:0)
+	/// Proof: `Blaze::ToleranceCounter` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 100]`.
+	/// The range of component `i` is `[1, 500]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn outbound_batch(n: u32, i: u32) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(i.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(i.into())))
+	}
+}
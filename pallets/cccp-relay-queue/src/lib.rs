@@ -10,7 +10,7 @@ pub use weights::WeightInfo;
 use bp_cccp::UnboundedBytes;
 use bp_staking::MAX_AUTHORITIES;
 use frame_support::traits::Currency;
-use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::{ConstU32, RuntimeDebug, H160, H256, U256};
 use sp_runtime::BoundedVec;
@@ -41,6 +41,37 @@ pub enum TransferOption {
 	Standard,
 }
 
+#[derive(
+	Decode,
+	Encode,
+	TypeInfo,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+)]
+/// The relay workflows that require the relayers to reach a vote threshold.
+pub enum VoteWorkflow {
+	/// The on-flight approval of Fast transfers.
+	FastTransfer,
+	/// The on-flight approval of Standard transfers.
+	StandardTransfer,
+	/// The finalization of on-flight transfers.
+	Finalization,
+}
+
+impl From<TransferOption> for VoteWorkflow {
+	fn from(option: TransferOption) -> Self {
+		match option {
+			TransferOption::Fast => VoteWorkflow::FastTransfer,
+			TransferOption::Standard => VoteWorkflow::StandardTransfer,
+		}
+	}
+}
+
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
@@ -16,7 +16,7 @@ use sp_runtime::{BoundedVec, DispatchError};
 
 use crate::{
 	AssetCapInfo, AssetId, AssetIndexHash, BalanceOf, TransferInfo, TransferInfoWithTxId,
-	TransferOption,
+	TransferOption, VoteWorkflow,
 };
 
 use super::pallet::*;
@@ -235,6 +235,11 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The number of votes required to reach the threshold of the given workflow.
+	pub fn vote_threshold(workflow: VoteWorkflow) -> u32 {
+		T::Relayers::threshold(<VoteThresholds<T>>::get(workflow))
+	}
+
 	/// Update asset cap for fast transfers.
 	///
 	/// # Arguments
@@ -3,7 +3,7 @@ mod impls;
 use crate::{
 	weights::WeightInfo, AssetCapInfo, AssetId, AssetIndexHash, AssetIndexInfo, BalanceOf, ChainId,
 	FinalizePollSubmission, OnFlightPollSubmission, SocketMessageHash, SourceTransactionId,
-	TransferInfo, TransferInfoWithTxId, TransferOption,
+	TransferInfo, TransferInfoWithTxId, TransferOption, VoteWorkflow,
 };
 
 use frame_support::{
@@ -13,7 +13,7 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 
 use bp_cccp::traits::SocketVerifier;
-use bp_staking::traits::Authorities;
+use bp_staking::{traits::Authorities, MajorityThreshold};
 use sp_core::{H160, U256};
 use sp_io::hashing::keccak_256;
 use sp_runtime::traits::{Block, Header, IdentifyAccount, Verify};
@@ -146,6 +146,8 @@ pub mod pallet {
 		},
 		/// The socket has been set.
 		SocketSet { new: T::AccountId },
+		/// The vote threshold of a relay workflow has been set.
+		VoteThresholdSet { workflow: VoteWorkflow, old: MajorityThreshold, new: MajorityThreshold },
 	}
 
 	#[pallet::storage]
@@ -337,6 +339,15 @@ pub mod pallet {
 		TransferInfoWithTxId<BalanceOf<T>, T::AccountId>,
 	>;
 
+	#[pallet::storage]
+	/// The vote threshold of each relay workflow.
+	///
+	/// - **Key**: `VoteWorkflow` - Fast transfer approval, Standard transfer approval or finalization
+	/// - **Value**: `MajorityThreshold` - The number of relayer votes required for consensus
+	/// - **Query**: Returns `MajorityThreshold::Majority` (simple majority) if not configured
+	pub type VoteThresholds<T: Config> =
+		StorageMap<_, Twox64Concat, VoteWorkflow, MajorityThreshold, ValueQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T>
 	where
@@ -520,9 +531,11 @@ pub mod pallet {
 				},
 			};
 
-			let is_approved = if transfer_info.on_flight_voters.len() as u32
-				>= T::Relayers::majority()
+			let is_approved = if T::Relayers::count_authorities(
+				transfer_info.on_flight_voters.iter(),
+			) >= Self::vote_threshold(option.into())
 			{
+				T::Relayers::note_majority_reached(&transfer_info.on_flight_voters);
 				if let Some((asset_id, asset_cap)) = asset_info {
 					if option == TransferOption::Fast {
 						Self::update_fast_transfer_cap(asset_id, asset_cap, amount.into(), true)?;
@@ -741,12 +754,15 @@ pub mod pallet {
 				Error::<T>::MessageStatusMismatch
 			);
 
-			// Check if majority is reached
-			if on_flight_transfer.finalization_voters.len() as u32 >= T::Relayers::majority() {
+			// Check if the finalization threshold is reached
+			if T::Relayers::count_authorities(on_flight_transfer.finalization_voters.iter())
+				>= Self::vote_threshold(VoteWorkflow::Finalization)
+			{
 				// Extract transfer option before moving
 				let is_fast_transfer = on_flight_transfer.option == TransferOption::Fast;
 
 				// Finalize with majority consensus
+				T::Relayers::note_majority_reached(&on_flight_transfer.finalization_voters);
 				OnFlightTransfers::<T>::remove(msg_hash);
 				FinalizedTransfers::<T>::insert(msg_hash, on_flight_transfer.clone());
 
@@ -1207,6 +1223,47 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Set the vote threshold of a relay workflow.
+		///
+		/// By default, every workflow requires the simple majority of the selected relayers.
+		/// This allows each workflow to require a stricter (or looser) consensus, e.g. Fast
+		/// transfers may require two-thirds of the relayers since they are executed before the
+		/// source chain transaction is finalized.
+		///
+		/// # Parameters
+		/// * `origin` - Must be `Root` (sudo access required)
+		/// * `workflow` - The relay workflow to configure
+		/// * `threshold` - The new vote threshold
+		///   - `Majority`: The simple majority of the selected relayers
+		///   - `Proportion`: The given proportion of the selected relayers, rounded up
+		///   - `Absolute`: The given number of votes, capped at the number of selected relayers
+		///
+		/// # Errors
+		/// * `NoWritingSameValue` - If the new threshold is identical to the current threshold
+		///
+		/// # Events
+		/// * `VoteThresholdSet { workflow, old, new }` - Emitted when the threshold is updated
+		///
+		/// # Important
+		/// - The threshold is evaluated on every vote, so pending polls are affected immediately
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::default())]
+		pub fn set_vote_threshold(
+			origin: OriginFor<T>,
+			workflow: VoteWorkflow,
+			threshold: MajorityThreshold,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let old = <VoteThresholds<T>>::get(workflow);
+			ensure!(old != threshold, Error::<T>::NoWritingSameValue);
+
+			<VoteThresholds<T>>::insert(workflow, threshold);
+			Self::deposit_event(Event::VoteThresholdSet { workflow, old, new: threshold });
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
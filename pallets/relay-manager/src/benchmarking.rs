@@ -6,6 +6,7 @@ use frame_benchmarking::v2::*;
 use frame_support::pallet_prelude::ConstU32;
 use frame_support::BoundedBTreeSet;
 use frame_system::RawOrigin;
+use sp_std::{collections::btree_set::BTreeSet, vec};
 
 fn set_controller<T: Config>() -> (T::AccountId, T::AccountId) {
 	<Round<T>>::put(1);
@@ -24,6 +25,16 @@ fn set_controller<T: Config>() -> (T::AccountId, T::AccountId) {
 	(controller, relayer)
 }
 
+/// A set of `count` relayer accounts
+fn selected_relayers<T: Config>(
+	count: u32,
+) -> BoundedBTreeSet<T::AccountId, ConstU32<MAX_AUTHORITIES>> {
+	let relayers = (0..count)
+		.map(|i| account::<T::AccountId>("selected", i, 0))
+		.collect::<BTreeSet<_>>();
+	BoundedBTreeSet::try_from(relayers).unwrap()
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -80,13 +91,46 @@ mod benchmarks {
 	#[benchmark]
 	fn heartbeat_v2() {
 		let (_, relayer) = set_controller::<T>();
-		let mut selected_relayers: BoundedBTreeSet<T::AccountId, ConstU32<MAX_AUTHORITIES>> =
-			Default::default();
+		// a full set of selected relayers, where the caller upgrades from an outdated version
+		// while the version requirement is enforced
+		let mut selected_relayers = selected_relayers::<T>(MAX_AUTHORITIES - 1);
 		selected_relayers.try_insert(relayer.clone()).unwrap();
-		<SelectedRelayers<T>>::put(selected_relayers);
+		<SelectedRelayers<T>>::put(selected_relayers.clone());
+		<OutdatedRelayers<T>>::put(selected_relayers);
+		<MinRelayerImplVersion<T>>::put(1);
+		<AcceptedSpecVersions<T>>::put(
+			BoundedBTreeSet::try_from([T::Hash::default()].into_iter().collect::<BTreeSet<_>>())
+				.unwrap(),
+		);
+		<RelayerVersionEnforcedAt<T>>::put(1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(relayer.clone()), 1, T::Hash::default());
+
+		assert!(!<OutdatedRelayers<T>>::get().contains(&relayer));
+	}
+
+	#[benchmark]
+	fn set_relayer_version_requirement(x: Linear<1, MAX_AUTHORITIES>) {
+		// every relayer is selected and flagged as outdated by the new requirement
+		let relayers = selected_relayers::<T>(x);
+		for (i, relayer) in relayers.iter().enumerate() {
+			let controller = account::<T::AccountId>("controller", i as u32, 0);
+			<RelayerState<T>>::insert(relayer, RelayerMetadata::new(controller.clone()));
+			<RelayerPool<T>>::mutate(|pool| {
+				pool.try_push(Relayer { relayer: relayer.clone(), controller }).unwrap()
+			});
+		}
+		<SelectedRelayers<T>>::put(relayers);
+		// the previous requirement is enforced until the new one has been set
+		<Round<T>>::put(1);
+		<MinRelayerImplVersion<T>>::put(1);
+		<RelayerVersionEnforcedAt<T>>::put(1);
 
 		#[extrinsic_call]
-		_(RawOrigin::Signed(relayer), 1, T::Hash::default());
+		_(RawOrigin::Root, 2, vec![T::Hash::default()]);
+
+		assert_eq!(<OutdatedRelayers<T>>::get().len() as u32, x);
 	}
 
 	impl_benchmark_test_suite!(Pallet, mock::new_test_ext(), mock::Test);
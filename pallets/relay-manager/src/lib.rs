@@ -25,6 +25,8 @@ mod benchmarking;
 
 #[cfg(test)]
 mod mock;
+#[cfg(test)]
+mod tests;
 
 /// A type for representing the validator id in a session.
 pub type ValidatorId<T> = <<T as Config>::ValidatorSet as ValidatorSet<
@@ -40,6 +42,9 @@ pub type IdentificationTuple<T> = (
 	>>::Identification,
 );
 
+/// The maximum number of relayer spec versions that can be accepted at once.
+pub const MAX_ACCEPTED_SPEC_VERSIONS: u32 = 16;
+
 pub(crate) const LOG_TARGET: &'static str = "runtime::relay-manager";
 
 // syntactic sugar for logging.
@@ -144,6 +149,49 @@ impl<AccountId: PartialEq + Clone, Hash> RelayerMetadata<AccountId, Hash> {
 	}
 }
 
+#[derive(
+	Default, Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+/// The performance score of a specific relayer for a single round
+pub struct RelayerScore {
+	/// The number of sessions in which this relayer sent a heartbeat
+	pub heartbeats: u32,
+	/// The number of sessions in which this relayer didn't send a heartbeat
+	pub missed_heartbeats: u32,
+	/// The number of polls that this relayer voted before the majority has been reached
+	pub on_time_votes: u32,
+	/// The number of polls that have reached the majority without this relayer's vote
+	pub missed_votes: u32,
+}
+
+impl RelayerScore {
+	pub fn note_heartbeat(&mut self, is_pulsed: bool) {
+		if is_pulsed {
+			self.heartbeats = self.heartbeats.saturating_add(1);
+		} else {
+			self.missed_heartbeats = self.missed_heartbeats.saturating_add(1);
+		}
+	}
+
+	pub fn note_votes(&mut self, on_time: u32, missed: u32) {
+		self.on_time_votes = self.on_time_votes.saturating_add(on_time);
+		self.missed_votes = self.missed_votes.saturating_add(missed);
+	}
+
+	/// The ratio of the fulfilled duties (heartbeats and on-time votes) over the entire duties
+	/// of this round. A relayer without any duties is considered as fully performed.
+	pub fn performance(&self) -> Perbill {
+		let fulfilled = self.heartbeats.saturating_add(self.on_time_votes);
+		let total = fulfilled
+			.saturating_add(self.missed_heartbeats)
+			.saturating_add(self.missed_votes);
+		if total == 0 {
+			return Perbill::one();
+		}
+		Perbill::from_rational(fulfilled, total)
+	}
+}
+
 #[derive(RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Clone, PartialEq, Eq))]
 /// An offence that is filed if a validator didn't send a heartbeat message.
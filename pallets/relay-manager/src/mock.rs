@@ -2,7 +2,7 @@ use crate as pallet_relay_manager;
 use crate::{IdentificationTuple, UnresponsivenessOffence};
 
 use bp_btc_relay::{
-	blaze::{ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
+	blaze::{BatchPolicy, ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
 	traits::{BlazeManager, PoolManager, SocketQueueManager},
 	BoundedBitcoinAddress, Descriptor, MigrationSequence, Psbt, PublicKey, UnboundedBytes,
 };
@@ -10,9 +10,10 @@ use bp_cccp::traits::{RelayQueueManager, SocketVerifier};
 use bp_core::{AccountId, Balance, BlockNumber};
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{Everything, ValidatorSet, ValidatorSetWithIdentification},
+	traits::{ConstU32, Everything, ValidatorSet, ValidatorSetWithIdentification},
 };
-use frame_system::pallet_prelude::BlockNumberFor;
+use frame_system::{pallet_prelude::BlockNumberFor, EnsureRoot};
+use pallet_membership::Instance3;
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, Convert, IdentityLookup},
@@ -29,6 +30,7 @@ construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		RelayManager: pallet_relay_manager,
+		RelayExecutiveMembership: pallet_membership::<Instance3>,
 	}
 );
 
@@ -41,6 +43,7 @@ parameter_types! {
 	pub const StorageCacheLifetimeInRounds: u32 = 100;
 	pub const IsHeartbeatOffenceActive: bool = true;
 	pub const DefaultHeartbeatSlashFraction: Perbill = Perbill::from_percent(10);
+	pub const RelayerVersionGracePeriod: u32 = 1;
 }
 
 impl frame_system::Config for Test {
@@ -93,6 +96,19 @@ impl pallet_balances::Config for Test {
 	type DoneSlashHandler = ();
 }
 
+impl pallet_membership::Config<Instance3> for Test {
+	type AddOrigin = EnsureRoot<AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type MaxMembers = ConstU32<16>;
+	type MembershipChanged = ();
+	type MembershipInitialized = ();
+	type PrimeOrigin = EnsureRoot<AccountId>;
+	type RemoveOrigin = EnsureRoot<AccountId>;
+	type ResetOrigin = EnsureRoot<AccountId>;
+	type SwapOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
 pub struct MockBlaze;
 impl BlazeManager<Test> for MockBlaze {
 	fn replace_authority(_: &AccountId, _: &AccountId) {}
@@ -105,6 +121,10 @@ impl BlazeManager<Test> for MockBlaze {
 		vec![]
 	}
 
+	fn get_sweep_utxos() -> Vec<UtxoInfoWithSize> {
+		vec![]
+	}
+
 	fn clear_utxos() {}
 
 	fn lock_utxos(_: &H256, _: &Vec<UtxoInfoWithSize>) -> Result<(), DispatchError> {
@@ -123,6 +143,18 @@ impl BlazeManager<Test> for MockBlaze {
 		vec![]
 	}
 
+	fn get_outbound_pool_len() -> usize {
+		0
+	}
+
+	fn get_outbound_batch(_: BlockNumberFor<Test>) -> Vec<UnboundedBytes> {
+		vec![]
+	}
+
+	fn get_batch_policy() -> BatchPolicy {
+		Default::default()
+	}
+
 	fn clear_outbound_pool(_: Vec<UnboundedBytes>) {}
 
 	fn try_fee_rate_finalization(_: BlockNumberFor<Test>) -> Option<(u64, u64)> {
@@ -152,6 +184,16 @@ impl BlazeManager<Test> for MockBlaze {
 	fn set_activation(_: bool) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_outbound_pool(_: Vec<UnboundedBytes>) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_utxos(_: Vec<UtxoInfoWithSize>) -> Result<(), DispatchError> {
+		Ok(())
+	}
 }
 
 // Mock implementations for required traits
@@ -314,10 +356,10 @@ impl pallet_relay_manager::Config for Test {
 	type StorageCacheLifetimeInRounds = StorageCacheLifetimeInRounds;
 	type IsHeartbeatOffenceActive = IsHeartbeatOffenceActive;
 	type DefaultHeartbeatSlashFraction = DefaultHeartbeatSlashFraction;
+	type RelayerVersionGracePeriod = RelayerVersionGracePeriod;
 	type WeightInfo = ();
 }
 
-#[cfg(feature = "runtime-benchmarks")]
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
 }
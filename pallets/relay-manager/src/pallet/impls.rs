@@ -4,7 +4,7 @@ use crate::{pallet::*, IdentificationTuple, Relayer, RelayerMetadata, Unresponsi
 
 use bp_staking::{
 	traits::{Authorities, RelayManager},
-	RoundIndex,
+	MajorityThreshold, RoundIndex,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -12,22 +12,66 @@ use frame_support::{
 	BoundedBTreeSet,
 };
 use pallet_membership::{Instance3, Members, Prime};
-use sp_runtime::traits::Convert;
+use sp_runtime::{traits::Convert, Perbill};
 use sp_staking::offence::ReportOffence;
 use sp_std::{vec, vec::Vec};
 
 impl<T: Config> Authorities<T::AccountId> for Pallet<T> {
 	fn is_authority(who: &T::AccountId) -> bool {
-		SelectedRelayers::<T>::get().contains(who)
+		SelectedRelayers::<T>::get().contains(who) && !Self::is_excluded_relayer(who)
 	}
 
 	fn count() -> usize {
-		SelectedRelayers::<T>::get().len()
+		SelectedRelayers::<T>::get()
+			.into_iter()
+			.filter(|relayer| !Self::is_excluded_relayer(relayer))
+			.count()
 	}
 
 	fn majority() -> u32 {
 		<Majority<T>>::get()
 	}
+
+	fn cached_count(round: RoundIndex) -> Option<usize> {
+		CachedSelectedRelayers::<T>::get().get(&round).map(|relayers| {
+			relayers.iter().filter(|relayer| !Self::is_excluded_relayer(relayer)).count()
+		})
+	}
+
+	fn count_authorities<'a>(who: impl IntoIterator<Item = &'a T::AccountId>) -> u32
+	where
+		T::AccountId: 'a,
+	{
+		// the sets are read once instead of once per account
+		let selected_relayers = SelectedRelayers::<T>::get();
+		let outdated_relayers = if Self::is_version_enforced() {
+			OutdatedRelayers::<T>::get()
+		} else {
+			Default::default()
+		};
+		who.into_iter()
+			.filter(|who| selected_relayers.contains(who) && !outdated_relayers.contains(who))
+			.count() as u32
+	}
+
+	fn threshold(threshold: MajorityThreshold) -> u32 {
+		// the threshold of the current round is computed against its cached relayer set
+		Self::threshold_at(threshold, Round::<T>::get())
+	}
+
+	fn note_majority_reached(voters: &[T::AccountId]) {
+		// the votes are aggregated per round to keep the cost of a poll constant
+		<RoundPolls<T>>::mutate(|polls| *polls = polls.saturating_add(1));
+		<RoundOnTimeVotes<T>>::mutate(|votes| {
+			voters.iter().for_each(|voter| {
+				if let Some(count) = votes.get_mut(voter) {
+					*count = count.saturating_add(1);
+				} else {
+					let _ = votes.try_insert(voter.clone(), 1);
+				}
+			});
+		});
+	}
 }
 
 impl<T: Config> RelayManager<T::AccountId> for Pallet<T>
@@ -49,7 +93,28 @@ where
 	}
 
 	fn refresh_round(now: RoundIndex) {
+		Self::settle_round_votes(Round::<T>::get());
 		<Round<T>>::put(now);
+		// remove the relayer scores that are older than the storage cache lifetime
+		let lifetime = StorageCacheLifetime::<T>::get();
+		if now > lifetime {
+			let _ = <RelayerScores<T>>::clear_prefix(now - lifetime, u32::MAX, None);
+		}
+		// warn the outdated relayers until the version requirement is enforced
+		if let Some(enforced_at) = RelayerVersionEnforcedAt::<T>::get() {
+			if now < enforced_at {
+				OutdatedRelayers::<T>::get().into_iter().for_each(|relayer| {
+					if let Some(relayer_state) = RelayerState::<T>::get(&relayer) {
+						Self::deposit_event(Event::RelayerOutdated {
+							relayer,
+							impl_version: relayer_state.impl_version,
+							spec_version: relayer_state.spec_version,
+							enforced_at,
+						});
+					}
+				});
+			}
+		}
 	}
 
 	fn refresh_relayer_pool() {
@@ -136,6 +201,7 @@ where
 		if let Some(relayer) = <BondedController<T>>::take(controller) {
 			Self::remove_from_relayer_pool(&relayer, true);
 			<RelayerState<T>>::remove(&relayer);
+			<OutdatedRelayers<T>>::mutate(|outdated| outdated.remove(&relayer));
 		}
 	}
 
@@ -160,6 +226,7 @@ where
 	fn collect_heartbeats() {
 		let current_validators = T::ValidatorSet::validators();
 		let session_index = T::ValidatorSet::session_index();
+		let round = Round::<T>::get();
 		let offenders = current_validators
 			.clone()
 			.into_iter()
@@ -167,7 +234,11 @@ where
 			.filter(|(_, id)| {
 				let controller: T::AccountId = id.clone().into();
 				if let Some(relayer) = BondedController::<T>::get(&controller) {
-					!Self::is_heartbeat_pulsed(&relayer)
+					let is_pulsed = Self::is_heartbeat_pulsed(&relayer);
+					<RelayerScores<T>>::mutate(round, &relayer, |score| {
+						score.note_heartbeat(is_pulsed)
+					});
+					!is_pulsed
 				} else {
 					false
 				}
@@ -210,6 +281,11 @@ where
 		}
 	}
 
+	fn relayer_score(controller: &T::AccountId, round: RoundIndex) -> Option<Perbill> {
+		let relayer = BondedController::<T>::get(controller)?;
+		<RelayerScores<T>>::try_get(round, &relayer).ok().map(|score| score.performance())
+	}
+
 	fn handle_delayed_relayer_sets(now: RoundIndex) {
 		let delayed_round = now - 1;
 		let relayer_sets = <DelayedRelayerSets<T>>::take(delayed_round);
@@ -267,9 +343,74 @@ impl<T: Config> Pallet<T> {
 		relayer_sets.into_iter().any(|r| r.old == relayer)
 	}
 
-	/// Compute majority based on the current selected relayers
+	/// Compute majority based on the current selected relayers. Relayers excluded due to an
+	/// outdated version are not counted.
 	fn compute_majority() -> u32 {
-		((SelectedRelayers::<T>::get().len() as u32) / 2) + 1
+		((Self::count() as u32) / 2) + 1
+	}
+
+	/// Refresh the `Majority` and the latest rounds cached majority to the current state
+	fn refresh_current_majority() {
+		<Majority<T>>::put(Self::compute_majority());
+		Self::refresh_latest_cached_majority();
+	}
+
+	/// Verifies whether the given versions don't meet the relayer version requirement
+	pub fn is_outdated_version(impl_version: Option<u32>, spec_version: Option<T::Hash>) -> bool {
+		let min_impl_version = MinRelayerImplVersion::<T>::get();
+		let accepted_spec_versions = AcceptedSpecVersions::<T>::get();
+
+		let is_impl_outdated =
+			min_impl_version > 0 && impl_version.is_none_or(|v| v < min_impl_version);
+		let is_spec_outdated = !accepted_spec_versions.is_empty()
+			&& spec_version.is_none_or(|v| !accepted_spec_versions.contains(&v));
+		is_impl_outdated || is_spec_outdated
+	}
+
+	/// Verifies whether the relayer version requirement is enforced in the current round
+	pub fn is_version_enforced() -> bool {
+		RelayerVersionEnforcedAt::<T>::get().is_some_and(|r| Round::<T>::get() >= r)
+	}
+
+	/// Verifies whether the given relayer is excluded from the authorities due to an outdated
+	/// version
+	pub fn is_excluded_relayer(relayer: &T::AccountId) -> bool {
+		Self::is_version_enforced() && OutdatedRelayers::<T>::get().contains(relayer)
+	}
+
+	/// Flag or unflag the given relayer as outdated based on its latest reported versions. The
+	/// majority will be refreshed if the exclusion of a selected relayer has changed.
+	pub fn refresh_relayer_version(
+		relayer: &T::AccountId,
+		relayer_state: &RelayerMetadata<T::AccountId, T::Hash>,
+	) {
+		let is_outdated =
+			Self::is_outdated_version(relayer_state.impl_version, relayer_state.spec_version);
+		let was_outdated = <OutdatedRelayers<T>>::mutate(|outdated| {
+			if is_outdated {
+				!outdated.try_insert(relayer.clone()).unwrap_or(false)
+			} else {
+				outdated.remove(relayer)
+			}
+		});
+
+		if is_outdated {
+			Self::deposit_event(Event::RelayerOutdated {
+				relayer: relayer.clone(),
+				impl_version: relayer_state.impl_version,
+				spec_version: relayer_state.spec_version,
+				enforced_at: RelayerVersionEnforcedAt::<T>::get().unwrap_or_default(),
+			});
+		} else if was_outdated {
+			Self::deposit_event(Event::RelayerUpgraded { relayer: relayer.clone() });
+		}
+
+		if is_outdated != was_outdated
+			&& Self::is_version_enforced()
+			&& Self::is_selected_relayer(relayer, false)
+		{
+			Self::refresh_current_majority();
+		}
 	}
 
 	/// Verifies the existence of the given relayer and controller account. If it is both not bonded
@@ -420,6 +561,34 @@ impl<T: Config> Pallet<T> {
 		<ReceivedHeartbeats<T>>::insert(session_index, new, is_pulsed);
 	}
 
+	/// Settle the aggregated votes of the ending `round` into the `RelayerScores` of the selected
+	/// relayers. A relayer misses every poll of the round that it didn't vote on time.
+	fn settle_round_votes(round: RoundIndex) {
+		let polls = <RoundPolls<T>>::take();
+		let on_time_votes = <RoundOnTimeVotes<T>>::take();
+		if polls == 0 {
+			return;
+		}
+		SelectedRelayers::<T>::get().into_iter().for_each(|relayer| {
+			let on_time = on_time_votes.get(&relayer).copied().unwrap_or_default().min(polls);
+			<RelayerScores<T>>::mutate(round, &relayer, |score| {
+				score.note_votes(on_time, polls - on_time)
+			});
+		});
+	}
+
+	/// Replace the `RelayerScores` mapped key from `old` to `new` for every cached round
+	fn replace_relayer_scores(old: &T::AccountId, new: &T::AccountId) {
+		let round = Round::<T>::get();
+		let lifetime = StorageCacheLifetime::<T>::get();
+		(round.saturating_sub(lifetime)..=round).for_each(|r| {
+			if let Ok(score) = <RelayerScores<T>>::try_get(r, old) {
+				<RelayerScores<T>>::remove(r, old);
+				<RelayerScores<T>>::insert(r, new, score);
+			}
+		});
+	}
+
 	/// Try to replace the bonded `old` relayer account to the given `new` relayer account. Returns
 	/// `true` if the bonded relayer has been replaced.
 	pub fn replace_bonded_relayer(
@@ -436,6 +605,14 @@ impl<T: Config> Pallet<T> {
 			Self::replace_relayer_pool(&old, &new, controller.clone())?;
 			// replace selected relayers
 			Self::replace_selected_relayers(&old, &new)?;
+			// replace relayer scores
+			Self::replace_relayer_scores(&old, &new);
+			// replace outdated relayers
+			<OutdatedRelayers<T>>::mutate(|outdated| {
+				if outdated.remove(old) {
+					let _ = outdated.try_insert(new.clone());
+				}
+			});
 			return Ok(true);
 		}
 		return Ok(false);
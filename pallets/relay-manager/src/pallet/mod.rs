@@ -1,14 +1,14 @@
 mod impls;
 
 use crate::{
-	migrations, DelayedRelayerSet, IdentificationTuple, Relayer, RelayerMetadata,
-	UnresponsivenessOffence, WeightInfo,
+	migrations, DelayedRelayerSet, IdentificationTuple, Relayer, RelayerMetadata, RelayerScore,
+	UnresponsivenessOffence, WeightInfo, MAX_ACCEPTED_SPEC_VERSIONS,
 };
 
 use frame_support::{
 	pallet_prelude::*,
 	traits::{OnRuntimeUpgrade, StorageVersion, ValidatorSetWithIdentification},
-	BoundedBTreeSet, Twox64Concat,
+	BoundedBTreeMap, BoundedBTreeSet, Twox64Concat,
 };
 use frame_system::pallet_prelude::*;
 
@@ -17,7 +17,10 @@ use bp_cccp::traits::RelayQueueManager;
 use bp_staking::{RoundIndex, MAX_AUTHORITIES};
 use sp_runtime::Perbill;
 use sp_staking::{offence::ReportOffence, SessionIndex};
-use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
+};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -59,6 +62,9 @@ pub mod pallet {
 		/// The default slash fraction for heartbeat offences
 		#[pallet::constant]
 		type DefaultHeartbeatSlashFraction: Get<Perbill>;
+		/// The number of rounds before a newly set relayer version requirement is enforced
+		#[pallet::constant]
+		type RelayerVersionGracePeriod: Get<RoundIndex>;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -89,6 +95,8 @@ pub mod pallet {
 		AlreadyRelayerSetRequested,
 		/// DelayedRelayerSets out of bound.
 		TooManyDelayedRelayers,
+		/// AcceptedSpecVersions out of bound.
+		TooManyAcceptedSpecVersions,
 	}
 
 	#[pallet::event]
@@ -114,6 +122,22 @@ pub mod pallet {
 		HeartbeatSlashFractionSet { old: Perbill, new: Perbill },
 		/// Cancel the relayer set.
 		RelayerSetCancelled { relayer: T::AccountId },
+		/// Set the relayer version requirement. It will be enforced from the `enforced_at` round.
+		RelayerVersionRequirementSet {
+			min_impl_version: u32,
+			accepted_spec_versions: Vec<T::Hash>,
+			enforced_at: RoundIndex,
+		},
+		/// The relayer runs an outdated version. It will be excluded from the majority from the
+		/// `enforced_at` round until it has been upgraded.
+		RelayerOutdated {
+			relayer: T::AccountId,
+			impl_version: Option<u32>,
+			spec_version: Option<T::Hash>,
+			enforced_at: RoundIndex,
+		},
+		/// The outdated relayer has been upgraded to a required version.
+		RelayerUpgraded { relayer: T::AccountId },
 	}
 
 	#[pallet::storage]
@@ -207,6 +231,29 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	/// The performance score of a specific relayer for each round. The scores are kept for
+	/// `StorageCacheLifetime` rounds.
+	pub type RelayerScores<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		RoundIndex,
+		Twox64Concat,
+		T::AccountId,
+		RelayerScore,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// The number of polls that have reached the majority in the current round
+	pub type RoundPolls<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	/// The number of on-time votes of each relayer in the current round. The votes are settled
+	/// into `RelayerScores` once the round ends.
+	pub type RoundOnTimeVotes<T: Config> =
+		StorageValue<_, BoundedBTreeMap<T::AccountId, u32, ConstU32<MAX_AUTHORITIES>>, ValueQuery>;
+
 	#[pallet::storage]
 	/// The activation of relayer heartbeat offence management
 	pub type IsHeartbeatOffenceActive<T: Config> = StorageValue<_, bool, ValueQuery>;
@@ -215,6 +262,25 @@ pub mod pallet {
 	/// The slash fraction for heartbeat offences
 	pub type HeartbeatSlashFraction<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
+	#[pallet::storage]
+	/// The minimum relayer implementation version. A zero value disables the requirement.
+	pub type MinRelayerImplVersion<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	/// The accepted relayer spec versions. An empty set accepts any spec version.
+	pub type AcceptedSpecVersions<T: Config> =
+		StorageValue<_, BoundedBTreeSet<T::Hash, ConstU32<MAX_ACCEPTED_SPEC_VERSIONS>>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The round index from which the relayer version requirement is enforced
+	pub type RelayerVersionEnforcedAt<T: Config> = StorageValue<_, RoundIndex, OptionQuery>;
+
+	#[pallet::storage]
+	/// The relayers running a version that doesn't meet the requirement. Once the requirement is
+	/// enforced, these relayers are excluded from the authorities and the majority.
+	pub type OutdatedRelayers<T: Config> =
+		StorageValue<_, BoundedBTreeSet<T::AccountId, ConstU32<MAX_AUTHORITIES>>, ValueQuery>;
+
 	#[pallet::storage]
 	/// Delayed relayer address update requests
 	pub type DelayedRelayerSets<T: Config> = StorageMap<
@@ -367,7 +433,11 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(6)]
-		#[pallet::weight(<T as Config>::WeightInfo::heartbeat_v2())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::heartbeat_v2()
+				// refreshes the version of the relayer and the majority that it counts towards
+				.saturating_add(T::DbWeight::get().reads_writes(6, 3))
+		)]
 		/// Sends a new heartbeat to manage relayer liveness for the current session. The origin
 		/// must be the registered relayer account, and only the selected relayers can request.
 		pub fn heartbeat_v2(
@@ -385,8 +455,55 @@ pub mod pallet {
 				relayer_state.go_online();
 				relayer_state.set_impl_version(Some(impl_version));
 				relayer_state.set_spec_version(Some(spec_version));
-				<RelayerState<T>>::insert(&relayer, relayer_state);
-				Self::deposit_event(Event::<T>::HeartbeatReceived { relayer });
+				<RelayerState<T>>::insert(&relayer, relayer_state.clone());
+				Self::deposit_event(Event::<T>::HeartbeatReceived { relayer: relayer.clone() });
+				Self::refresh_relayer_version(&relayer, &relayer_state);
+			}
+			Ok(().into())
+		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_relayer_version_requirement(MAX_AUTHORITIES))]
+		/// Set the minimum relayer implementation version and the accepted spec versions. The
+		/// requirement is enforced after `RelayerVersionGracePeriod` rounds, and the relayers
+		/// that are outdated by then are excluded from the majority until they have been upgraded.
+		/// A zero `min_impl_version` with no `accepted_spec_versions` removes the requirement.
+		pub fn set_relayer_version_requirement(
+			origin: OriginFor<T>,
+			min_impl_version: u32,
+			accepted_spec_versions: Vec<T::Hash>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let accepted =
+				BoundedBTreeSet::<T::Hash, ConstU32<MAX_ACCEPTED_SPEC_VERSIONS>>::try_from(
+					accepted_spec_versions.into_iter().collect::<BTreeSet<T::Hash>>(),
+				)
+				.map_err(|_| Error::<T>::TooManyAcceptedSpecVersions)?;
+
+			let was_enforced = Self::is_version_enforced();
+			let enforced_at = Round::<T>::get().saturating_add(T::RelayerVersionGracePeriod::get());
+			<MinRelayerImplVersion<T>>::put(min_impl_version);
+			<AcceptedSpecVersions<T>>::put(accepted.clone());
+			Self::deposit_event(Event::RelayerVersionRequirementSet {
+				min_impl_version,
+				accepted_spec_versions: accepted.clone().into_iter().collect(),
+				enforced_at,
+			});
+
+			if min_impl_version == 0 && accepted.is_empty() {
+				<RelayerVersionEnforcedAt<T>>::kill();
+				<OutdatedRelayers<T>>::kill();
+			} else {
+				<RelayerVersionEnforcedAt<T>>::put(enforced_at);
+				// re-evaluate every relayer with the new requirement
+				RelayerPool::<T>::get().into_iter().for_each(|r| {
+					if let Some(relayer_state) = RelayerState::<T>::get(&r.relayer) {
+						Self::refresh_relayer_version(&r.relayer, &relayer_state);
+					}
+				});
+			}
+			if was_enforced != Self::is_version_enforced() {
+				Self::refresh_current_majority();
 			}
 			Ok(().into())
 		}
@@ -0,0 +1,131 @@
+use crate::{
+	mock::*, BondedController, RelayerMetadata, RelayerScore, RelayerScores, RelayerState, Round,
+	RoundOnTimeVotes, RoundPolls, SelectedRelayers, StorageCacheLifetime,
+};
+use bp_core::AccountId;
+use bp_staking::traits::{Authorities, RelayManager as RelayManagerT};
+use frame_support::BoundedBTreeSet;
+use sp_runtime::Perbill;
+
+fn controller(index: u8) -> AccountId {
+	AccountId::from([index; 20])
+}
+
+fn relayer(index: u8) -> AccountId {
+	AccountId::from([index + 100; 20])
+}
+
+/// Bond and select the relayers of the given controller indexes
+fn select_relayers(indexes: &[u8]) {
+	let mut selected = BoundedBTreeSet::new();
+	for &index in indexes {
+		BondedController::<Test>::insert(controller(index), relayer(index));
+		RelayerState::<Test>::insert(relayer(index), RelayerMetadata::new(controller(index)));
+		selected.try_insert(relayer(index)).unwrap();
+	}
+	SelectedRelayers::<Test>::put(selected);
+}
+
+#[test]
+fn relayer_score_performance_counts_every_duty() {
+	let mut score = RelayerScore::default();
+	// a relayer without any duties is considered as fully performed
+	assert_eq!(score.performance(), Perbill::one());
+
+	score.note_heartbeat(true);
+	score.note_heartbeat(false);
+	assert_eq!(score.performance(), Perbill::from_percent(50));
+
+	score.note_votes(3, 1);
+	assert_eq!((score.on_time_votes, score.missed_votes), (3, 1));
+	assert_eq!(score.performance(), Perbill::from_rational(4u32, 6u32));
+}
+
+#[test]
+fn votes_are_settled_into_the_relayer_scores_once_the_round_ends() {
+	new_test_ext().execute_with(|| {
+		StorageCacheLifetime::<Test>::put(10);
+		Round::<Test>::put(1);
+		select_relayers(&[1, 2]);
+
+		RelayManager::note_majority_reached(&[relayer(1)]);
+		RelayManager::note_majority_reached(&[relayer(1)]);
+		RelayManager::note_majority_reached(&[relayer(1), relayer(2)]);
+		assert_eq!(RoundPolls::<Test>::get(), 3);
+		assert_eq!(RoundOnTimeVotes::<Test>::get().get(&relayer(2)), Some(&1));
+		// nothing is scored until the round ends
+		assert_eq!(RelayManager::relayer_score(&controller(1), 1), None);
+
+		RelayManager::refresh_round(2);
+		let score = RelayerScores::<Test>::get(1, relayer(2));
+		assert_eq!((score.on_time_votes, score.missed_votes), (1, 2));
+		assert_eq!(RelayManager::relayer_score(&controller(1), 1), Some(Perbill::one()));
+		assert_eq!(
+			RelayManager::relayer_score(&controller(2), 1),
+			Some(Perbill::from_rational(1u32, 3u32))
+		);
+		assert_eq!(RoundPolls::<Test>::get(), 0);
+		assert!(RoundOnTimeVotes::<Test>::get().is_empty());
+
+		// a round without any polls leaves the scores untouched
+		RelayManager::refresh_round(3);
+		assert_eq!(RelayManager::relayer_score(&controller(1), 2), None);
+	});
+}
+
+#[test]
+fn heartbeats_are_scored_in_the_current_round() {
+	new_test_ext().execute_with(|| {
+		Round::<Test>::put(1);
+		select_relayers(&[1, 2]);
+
+		assert!(RelayManager::pulse_heartbeat(&relayer(1)));
+		RelayManager::collect_heartbeats();
+
+		let score = RelayerScores::<Test>::get(1, relayer(1));
+		assert_eq!((score.heartbeats, score.missed_heartbeats), (1, 0));
+		let score = RelayerScores::<Test>::get(1, relayer(2));
+		assert_eq!((score.heartbeats, score.missed_heartbeats), (0, 1));
+		assert_eq!(RelayManager::relayer_score(&controller(2), 1), Some(Perbill::zero()));
+		// validators without a bonded relayer are not scored
+		assert!(!RelayerScores::<Test>::contains_key(1, relayer(3)));
+	});
+}
+
+#[test]
+fn relayer_scores_are_removed_after_the_storage_cache_lifetime() {
+	new_test_ext().execute_with(|| {
+		StorageCacheLifetime::<Test>::put(2);
+		select_relayers(&[1]);
+		for round in 1..=2 {
+			RelayerScores::<Test>::mutate(round, relayer(1), |score| score.note_heartbeat(true));
+		}
+
+		RelayManager::refresh_round(3);
+		assert_eq!(RelayManager::relayer_score(&controller(1), 1), None);
+		assert_eq!(RelayManager::relayer_score(&controller(1), 2), Some(Perbill::one()));
+
+		RelayManager::refresh_round(4);
+		assert_eq!(RelayManager::relayer_score(&controller(1), 2), None);
+	});
+}
+
+#[test]
+fn votes_of_excluded_relayers_are_not_counted() {
+	new_test_ext().execute_with(|| {
+		Round::<Test>::put(1);
+		select_relayers(&[1, 2]);
+		let voters = [relayer(1), relayer(2), relayer(3)];
+		// only the selected relayers are counted
+		assert_eq!(RelayManager::count_authorities(voters.iter()), 2);
+
+		OutdatedRelayers::<Test>::mutate(|outdated| outdated.try_insert(relayer(2)).unwrap());
+		RelayerVersionEnforcedAt::<Test>::put(2);
+		// an outdated relayer is counted until the requirement is enforced
+		assert_eq!(RelayManager::count_authorities(voters.iter()), 2);
+
+		Round::<Test>::put(2);
+		assert_eq!(RelayManager::count_authorities(voters.iter()), 1);
+		assert!(!RelayManager::is_authority(&relayer(2)));
+	});
+}
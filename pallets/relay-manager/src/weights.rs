@@ -47,6 +47,7 @@ pub trait WeightInfo {
 	fn cancel_relayer_set() -> Weight;
 	fn heartbeat() -> Weight;
 	fn heartbeat_v2() -> Weight;
+	fn set_relayer_version_requirement(x: u32) -> Weight;
 }
 
 /// Weights for `pallet_relay_manager` using the Substrate node and recommended hardware.
@@ -151,6 +152,36 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `RelayManager::Round` (r:1 w:0)
+	/// Proof: `RelayManager::Round` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::RelayerVersionEnforcedAt` (r:1 w:1)
+	/// Proof: `RelayManager::RelayerVersionEnforcedAt` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::RelayerPool` (r:1 w:0)
+	/// Proof: `RelayManager::RelayerPool` (`max_values`: Some(1), `max_size`: Some(40002), added: 40497, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::RelayerState` (r:1000 w:0)
+	/// Proof: `RelayManager::RelayerState` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::OutdatedRelayers` (r:1 w:1)
+	/// Proof: `RelayManager::OutdatedRelayers` (`max_values`: Some(1), `max_size`: Some(20002), added: 20497, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::MinRelayerImplVersion` (r:1 w:1)
+	/// Proof: `RelayManager::MinRelayerImplVersion` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::AcceptedSpecVersions` (r:1 w:1)
+	/// Proof: `RelayManager::AcceptedSpecVersions` (`max_values`: Some(1), `max_size`: Some(513), added: 1008, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::SelectedRelayers` (r:1 w:0)
+	/// Proof: `RelayManager::SelectedRelayers` (`max_values`: Some(1), `max_size`: Some(20002), added: 20497, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::Majority` (r:0 w:1)
+	/// Proof: `RelayManager::Majority` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::CachedMajority` (r:1 w:1)
+	/// Proof: `RelayManager::CachedMajority` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[1, 1000]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_relayer_version_requirement(x: u32) -> Weight {
+		Weight::from_parts(39_874_000, 0)
+			.saturating_add(Weight::from_parts(12_406_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(Weight::from_parts(0, 2562).saturating_mul(x.into()))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -254,4 +285,34 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: `RelayManager::Round` (r:1 w:0)
+	/// Proof: `RelayManager::Round` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::RelayerVersionEnforcedAt` (r:1 w:1)
+	/// Proof: `RelayManager::RelayerVersionEnforcedAt` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::RelayerPool` (r:1 w:0)
+	/// Proof: `RelayManager::RelayerPool` (`max_values`: Some(1), `max_size`: Some(40002), added: 40497, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::RelayerState` (r:1000 w:0)
+	/// Proof: `RelayManager::RelayerState` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::OutdatedRelayers` (r:1 w:1)
+	/// Proof: `RelayManager::OutdatedRelayers` (`max_values`: Some(1), `max_size`: Some(20002), added: 20497, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::MinRelayerImplVersion` (r:1 w:1)
+	/// Proof: `RelayManager::MinRelayerImplVersion` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::AcceptedSpecVersions` (r:1 w:1)
+	/// Proof: `RelayManager::AcceptedSpecVersions` (`max_values`: Some(1), `max_size`: Some(513), added: 1008, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::SelectedRelayers` (r:1 w:0)
+	/// Proof: `RelayManager::SelectedRelayers` (`max_values`: Some(1), `max_size`: Some(20002), added: 20497, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::Majority` (r:0 w:1)
+	/// Proof: `RelayManager::Majority` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RelayManager::CachedMajority` (r:1 w:1)
+	/// Proof: `RelayManager::CachedMajority` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[1, 1000]`.
+	/// Estimated by hand, not generated by the benchmark CLI. Regenerate with `benchmark pallet`.
+	fn set_relayer_version_requirement(x: u32) -> Weight {
+		Weight::from_parts(39_874_000, 0)
+			.saturating_add(Weight::from_parts(12_406_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(Weight::from_parts(0, 2562).saturating_mul(x.into()))
+	}
 }
\ No newline at end of file
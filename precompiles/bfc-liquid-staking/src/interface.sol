@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+/**
+ * @title The interface through which solidity contracts will interact with pallet_bfc_liquid_staking
+ * The pool shares are exposed as an ERC-20 token. We follow this same interface including
+ * four-byte function selectors, in the precompile that wraps the pallet
+ * Address :    0x0000000000000000000000000000000000000401
+ */
+
+interface BfcLiquidStaking {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(
+        address indexed owner,
+        address indexed spender,
+        uint256 value
+    );
+
+    /// @dev Get the name of the share token
+    /// Selector: 06fdde03
+    function name() external view returns (string memory);
+
+    /// @dev Get the symbol of the share token
+    /// Selector: 95d89b41
+    function symbol() external view returns (string memory);
+
+    /// @dev Get the decimals of the share token
+    /// Selector: 313ce567
+    function decimals() external view returns (uint8);
+
+    /// @dev Get the total amount of issued shares
+    /// Selector: 18160ddd
+    function totalSupply() external view returns (uint256);
+
+    /// @dev Get the shares of the given owner
+    /// Selector: 70a08231
+    function balanceOf(address owner) external view returns (uint256);
+
+    /// @dev Get the amount of shares that the spender is allowed to transfer on behalf of the owner
+    /// Selector: dd62ed3e
+    function allowance(address owner, address spender)
+        external
+        view
+        returns (uint256);
+
+    /// @dev Transfer shares to the given account
+    /// Selector: a9059cbb
+    function transfer(address to, uint256 value) external returns (bool);
+
+    /// @dev Allow the spender to transfer the given amount of the caller's shares
+    /// Selector: 095ea7b3
+    function approve(address spender, uint256 value) external returns (bool);
+
+    /// @dev Transfer shares of the given owner within the caller's allowance
+    /// Selector: 23b872dd
+    function transferFrom(
+        address from,
+        address to,
+        uint256 value
+    ) external returns (bool);
+
+    /// @dev Get the amount of BFC that a single share unit (1e18) is worth
+    /// Selector: b5955e9d
+    function share_price() external view returns (uint256);
+
+    /// @dev Get the total amount of BFC that backs the issued shares
+    /// Selector: 212b5f50
+    function total_pooled() external view returns (uint256);
+
+    /// @dev Get the pending withdrawals of the given owner
+    /// Selector: af6066e6
+    /// @return The amount and the claimable round of each pending withdrawal. The round is zero
+    /// if the withdrawal is still queued.
+    function unbonding_of(address owner)
+        external
+        view
+        returns (uint256[] memory, uint256[] memory);
+
+    /// @dev Deposit BFC to the pool and receive shares at the current share price
+    /// Selector: b6b55f25
+    /// @param amount the amount of BFC to deposit
+    function deposit(uint256 amount) external;
+
+    /// @dev Burn the given shares and queue the withdrawal of the backing BFC
+    /// Selector: 2e1a7d4d
+    /// @param shares the amount of shares to burn
+    function withdraw(uint256 shares) external;
+
+    /// @dev Claim every matured withdrawal of the caller
+    /// Selector: 4e71d92d
+    function claim() external;
+
+    /// @dev Execute the pool's due nomination requests and schedule the queued withdrawals
+    /// Selector: 373e2ed6
+    function process_withdrawals() external;
+}
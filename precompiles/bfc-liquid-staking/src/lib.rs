@@ -0,0 +1,275 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+
+use pallet_bfc_liquid_staking::{
+	Allowances, BalanceOf, Call as LiquidStakingCall, Shares, TotalShares,
+};
+use pallet_evm::AddressMapping;
+
+use precompile_utils::prelude::*;
+
+use sp_core::{H160, U256};
+use sp_runtime::traits::{Dispatchable, Saturating};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+
+/// Solidity selector of the Approval log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+/// The name of the share token
+const NAME: &str = "Liquid Staked BFC";
+
+/// The symbol of the share token
+const SYMBOL: &str = "LBFC";
+
+/// The decimals of the share token
+const DECIMALS: u8 = 18;
+
+pub type EvmUnbondingOf = (Vec<U256>, Vec<u32>);
+
+/// A precompile to wrap the functionality from pallet_bfc_liquid_staking. The pool shares are
+/// exposed as an ERC-20 token.
+pub struct BfcLiquidStakingPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> BfcLiquidStakingPrecompile<Runtime>
+where
+	Runtime: pallet_bfc_liquid_staking::Config + pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Runtime::RuntimeCall: From<LiquidStakingCall<Runtime>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	<Runtime as pallet_evm::Config>::AddressMapping: AddressMapping<Runtime::AccountId>,
+{
+	// ERC-20 storage getters
+
+	#[precompile::public("name()")]
+	#[precompile::view]
+	fn name(_handle: &mut impl PrecompileHandle) -> EvmResult<UnboundedString> {
+		Ok(NAME.into())
+	}
+
+	#[precompile::public("symbol()")]
+	#[precompile::view]
+	fn symbol(_handle: &mut impl PrecompileHandle) -> EvmResult<UnboundedString> {
+		Ok(SYMBOL.into())
+	}
+
+	#[precompile::public("decimals()")]
+	#[precompile::view]
+	fn decimals(_handle: &mut impl PrecompileHandle) -> EvmResult<u8> {
+		Ok(DECIMALS)
+	}
+
+	#[precompile::public("totalSupply()")]
+	#[precompile::public("total_supply()")]
+	#[precompile::view]
+	fn total_supply(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(TotalShares::<Runtime>::get().into())
+	}
+
+	#[precompile::public("balanceOf(address)")]
+	#[precompile::public("balance_of(address)")]
+	#[precompile::view]
+	fn balance_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner = Runtime::AddressMapping::into_account_id(owner.0);
+		Ok(Shares::<Runtime>::get(&owner).into())
+	}
+
+	#[precompile::public("allowance(address,address)")]
+	#[precompile::view]
+	fn allowance(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+	) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner = Runtime::AddressMapping::into_account_id(owner.0);
+		let spender = Runtime::AddressMapping::into_account_id(spender.0);
+		Ok(Allowances::<Runtime>::get(&owner, &spender).into())
+	}
+
+	// Pool storage getters
+
+	#[precompile::public("sharePrice()")]
+	#[precompile::public("share_price()")]
+	#[precompile::view]
+	fn share_price(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 5)?;
+		Ok(pallet_bfc_liquid_staking::Pallet::<Runtime>::share_price().into())
+	}
+
+	#[precompile::public("totalPooled()")]
+	#[precompile::public("total_pooled()")]
+	#[precompile::view]
+	fn total_pooled(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 4)?;
+		Ok(pallet_bfc_liquid_staking::Pallet::<Runtime>::total_pooled().into())
+	}
+
+	#[precompile::public("unbondingOf(address)")]
+	#[precompile::public("unbonding_of(address)")]
+	#[precompile::view]
+	fn unbonding_of(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+	) -> EvmResult<EvmUnbondingOf> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 6)?;
+		let owner = Runtime::AddressMapping::into_account_id(owner.0);
+
+		let (amounts, unlock_rounds) =
+			pallet_bfc_liquid_staking::Pallet::<Runtime>::unbonding_of(&owner)
+				.into_iter()
+				.map(|(amount, unlock_round)| (amount.into(), unlock_round))
+				.unzip();
+		Ok((amounts, unlock_rounds))
+	}
+
+	// ERC-20 dispatchable methods
+
+	#[precompile::public("transfer(address,uint256)")]
+	fn transfer(handle: &mut impl PrecompileHandle, to: Address, value: U256) -> EvmResult<bool> {
+		let shares = Self::u256_to_amount(value).in_field("value")?;
+		let caller = handle.context().caller;
+
+		let origin = Runtime::AddressMapping::into_account_id(caller);
+		let call = LiquidStakingCall::<Runtime>::transfer {
+			to: Runtime::AddressMapping::into_account_id(to.0),
+			shares,
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Self::log_transfer(handle, caller, to.0, value)?;
+		Ok(true)
+	}
+
+	#[precompile::public("approve(address,uint256)")]
+	fn approve(
+		handle: &mut impl PrecompileHandle,
+		spender: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		let shares = Self::u256_to_amount(value).in_field("value")?;
+		let caller = handle.context().caller;
+
+		let origin = Runtime::AddressMapping::into_account_id(caller);
+		let call = LiquidStakingCall::<Runtime>::approve {
+			spender: Runtime::AddressMapping::into_account_id(spender.0),
+			shares,
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		let event = log3(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL,
+			caller,
+			spender.0,
+			solidity::encode_event_data(value),
+		);
+		handle.record_log_costs(&[&event])?;
+		event.record(handle)?;
+		Ok(true)
+	}
+
+	#[precompile::public("transferFrom(address,address,uint256)")]
+	#[precompile::public("transfer_from(address,address,uint256)")]
+	fn transfer_from(
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		let shares = Self::u256_to_amount(value).in_field("value")?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = LiquidStakingCall::<Runtime>::transfer_from {
+			from: Runtime::AddressMapping::into_account_id(from.0),
+			to: Runtime::AddressMapping::into_account_id(to.0),
+			shares,
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Self::log_transfer(handle, from.0, to.0, value)?;
+		Ok(true)
+	}
+
+	// Pool dispatchable methods
+
+	#[precompile::public("deposit(uint256)")]
+	fn deposit(handle: &mut impl PrecompileHandle, amount: U256) -> EvmResult {
+		let amount = Self::u256_to_amount(amount).in_field("amount")?;
+		let caller = handle.context().caller;
+		let who = Runtime::AddressMapping::into_account_id(caller);
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let before = Shares::<Runtime>::get(&who);
+		let call = LiquidStakingCall::<Runtime>::deposit { amount };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(who.clone()).into(), call, 0)?;
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let minted = Shares::<Runtime>::get(&who).saturating_sub(before);
+		Self::log_transfer(handle, H160::zero(), caller, minted.into())?;
+		Ok(())
+	}
+
+	#[precompile::public("withdraw(uint256)")]
+	fn withdraw(handle: &mut impl PrecompileHandle, shares: U256) -> EvmResult {
+		let amount = Self::u256_to_amount(shares).in_field("shares")?;
+		let caller = handle.context().caller;
+
+		let origin = Runtime::AddressMapping::into_account_id(caller);
+		let call = LiquidStakingCall::<Runtime>::withdraw { shares: amount };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Self::log_transfer(handle, caller, H160::zero(), shares)?;
+		Ok(())
+	}
+
+	#[precompile::public("claim()")]
+	fn claim(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = LiquidStakingCall::<Runtime>::claim {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("process_withdrawals()")]
+	fn process_withdrawals(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = LiquidStakingCall::<Runtime>::process_withdrawals {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	// Util methods
+
+	fn log_transfer(
+		handle: &mut impl PrecompileHandle,
+		from: H160,
+		to: H160,
+		value: U256,
+	) -> EvmResult {
+		let event = log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			from,
+			to,
+			solidity::encode_event_data(value),
+		);
+		handle.record_log_costs(&[&event])?;
+		event.record(handle)?;
+		Ok(())
+	}
+
+	fn u256_to_amount(value: U256) -> MayRevert<BalanceOf<Runtime>> {
+		value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").into())
+	}
+}
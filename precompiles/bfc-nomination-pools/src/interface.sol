@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+/**
+ * @title The interface through which solidity contracts will interact with pallet_bfc_nomination_pools
+ * We follow this same interface including four-byte function selectors, in the precompile that
+ * wraps the pallet
+ * Address :    0x0000000000000000000000000000000000000402
+ */
+
+interface BfcNominationPools {
+    /// @dev Get the identifier of the next created pool
+    /// Selector: e7c3f21d
+    function next_pool_id() external view returns (uint256);
+
+    /// @dev Get the state of the given pool
+    /// Selector: 3a6179bd
+    /// @param pool_id the identifier of the pool
+    /// @return The operator, the nominated candidate, the commission, the total points,
+    /// the number of members, the queued unbonding amount and the pending commission
+    function pool_info(uint256 pool_id)
+        external
+        view
+        returns (
+            address,
+            address,
+            uint256,
+            uint256,
+            uint256,
+            uint256,
+            uint256
+        );
+
+    /// @dev Get the pool membership of the given account
+    /// Selector: 7dd50155
+    /// @return Whether the account is a member, its pool identifier and its bonded points
+    function member_info(address member)
+        external
+        view
+        returns (
+            bool,
+            uint256,
+            uint256
+        );
+
+    /// @dev Get the rewards that the given member can claim
+    /// Selector: 2975e99a
+    function pending_rewards(address member) external view returns (uint256);
+
+    /// @dev Get the pending withdrawals of the given member
+    /// Selector: af6066e6
+    /// @return The amount and the withdrawable round of each pending withdrawal
+    function unbonding_of(address member)
+        external
+        view
+        returns (uint256[] memory, uint256[] memory);
+
+    /// @dev Create a new pool that nominates the given candidate
+    /// Selector: 5165da30
+    /// @param candidate the candidate that the pool nominates
+    /// @param amount the initial bond of the operator
+    /// @param commission the commission of the pool in parts per billion
+    function create(
+        address candidate,
+        uint256 amount,
+        uint256 commission
+    ) external;
+
+    /// @dev Bond the given amount to a pool
+    /// Selector: 79e66b46
+    /// @param pool_id the identifier of the pool
+    /// @param amount the amount of BFC to bond
+    function join(uint256 pool_id, uint256 amount) external;
+
+    /// @dev Unbond the given amount from the caller's pool
+    /// Selector: 27de9e32
+    /// @param amount the amount of BFC to unbond
+    function unbond(uint256 amount) external;
+
+    /// @dev Withdraw every matured unbonding of the caller
+    /// Selector: 77a0fe02
+    function withdraw_unbonded() external;
+
+    /// @dev Claim the pending rewards of the caller
+    /// Selector: e6f1daf2
+    function claim_rewards() external;
+
+    /// @dev Set the commission of the given pool. Only callable by the pool operator
+    /// Selector: 4f9b4581
+    /// @param pool_id the identifier of the pool
+    /// @param commission the new commission in parts per billion
+    function set_commission(uint256 pool_id, uint256 commission) external;
+
+    /// @dev Claim the pending commission of the given pool. Only callable by the pool operator
+    /// Selector: c6ba7d71
+    /// @param pool_id the identifier of the pool
+    function claim_commission(uint256 pool_id) external;
+
+    /// @dev Execute the due nomination request of the given pool and schedule its queued unbondings
+    /// Selector: 32145f90
+    /// @param pool_id the identifier of the pool
+    function poke(uint256 pool_id) external;
+}
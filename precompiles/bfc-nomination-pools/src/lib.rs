@@ -0,0 +1,209 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+
+use pallet_bfc_nomination_pools::{
+	BalanceOf, Call as NominationPoolsCall, Members, NextPoolId, PoolId, Pools, UnbondingBatches,
+};
+use pallet_evm::AddressMapping;
+
+use precompile_utils::prelude::*;
+
+use sp_core::{H160, U256};
+use sp_runtime::{traits::Dispatchable, Perbill};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// The pool operator, the nominated candidate, the commission, the total points, the number of
+/// members, the unbonding amount of the current batch and the pending commission of a pool
+pub type EvmPoolInfoOf = (Address, Address, u32, U256, u32, U256, U256);
+
+/// Whether the account is a pool member, its pool identifier and its bonded points
+pub type EvmMemberInfoOf = (bool, u32, U256);
+
+/// The amount and the execution round of each pending withdrawal. The round is zero while the
+/// unbonding batch waits to be scheduled.
+pub type EvmUnbondingOf = (Vec<U256>, Vec<u32>);
+
+/// A precompile to wrap the functionality from pallet_bfc_nomination_pools.
+pub struct BfcNominationPoolsPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> BfcNominationPoolsPrecompile<Runtime>
+where
+	Runtime: pallet_bfc_nomination_pools::Config + pallet_evm::Config,
+	Runtime::AccountId: Into<H160>,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Runtime::RuntimeCall: From<NominationPoolsCall<Runtime>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	<Runtime as pallet_evm::Config>::AddressMapping: AddressMapping<Runtime::AccountId>,
+{
+	// Storage getters
+
+	#[precompile::public("nextPoolId()")]
+	#[precompile::public("next_pool_id()")]
+	#[precompile::view]
+	fn next_pool_id(handle: &mut impl PrecompileHandle) -> EvmResult<u32> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(NextPoolId::<Runtime>::get())
+	}
+
+	#[precompile::public("poolInfo(uint256)")]
+	#[precompile::public("pool_info(uint256)")]
+	#[precompile::view]
+	fn pool_info(handle: &mut impl PrecompileHandle, pool_id: u32) -> EvmResult<EvmPoolInfoOf> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 2)?;
+		let pool = Pools::<Runtime>::get(pool_id)
+			.ok_or(RevertReason::custom("Pool does not exist").in_field("pool_id"))?;
+		let unbonding = UnbondingBatches::<Runtime>::get(pool_id, pool.current_batch)
+			.map(|batch| batch.balance)
+			.unwrap_or_default();
+
+		Ok((
+			Address(pool.operator.into()),
+			Address(pool.candidate.into()),
+			pool.commission.deconstruct(),
+			pool.points.into(),
+			pool.member_count,
+			unbonding.into(),
+			pool.pending_commission.into(),
+		))
+	}
+
+	#[precompile::public("memberInfo(address)")]
+	#[precompile::public("member_info(address)")]
+	#[precompile::view]
+	fn member_info(
+		handle: &mut impl PrecompileHandle,
+		member: Address,
+	) -> EvmResult<EvmMemberInfoOf> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let member = Runtime::AddressMapping::into_account_id(member.0);
+
+		Ok(match Members::<Runtime>::get(&member) {
+			Some(member) => (true, member.pool_id, member.points.into()),
+			None => (false, 0, U256::zero()),
+		})
+	}
+
+	#[precompile::public("pendingRewards(address)")]
+	#[precompile::public("pending_rewards(address)")]
+	#[precompile::view]
+	fn pending_rewards(handle: &mut impl PrecompileHandle, member: Address) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 3)?;
+		let member = Runtime::AddressMapping::into_account_id(member.0);
+		Ok(pallet_bfc_nomination_pools::Pallet::<Runtime>::pending_rewards(&member).into())
+	}
+
+	#[precompile::public("unbondingOf(address)")]
+	#[precompile::public("unbonding_of(address)")]
+	#[precompile::view]
+	fn unbonding_of(
+		handle: &mut impl PrecompileHandle,
+		member: Address,
+	) -> EvmResult<EvmUnbondingOf> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 6)?;
+		let member = Runtime::AddressMapping::into_account_id(member.0);
+
+		let (amounts, execute_rounds) =
+			pallet_bfc_nomination_pools::Pallet::<Runtime>::unbonding_of(&member)
+				.into_iter()
+				.map(|(amount, execute_round)| (amount.into(), execute_round.unwrap_or_default()))
+				.unzip();
+		Ok((amounts, execute_rounds))
+	}
+
+	// Dispatchable methods
+
+	#[precompile::public("create(address,uint256,uint256)")]
+	fn create(
+		handle: &mut impl PrecompileHandle,
+		candidate: Address,
+		amount: U256,
+		commission: u32,
+	) -> EvmResult {
+		let amount = Self::u256_to_amount(amount).in_field("amount")?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::create {
+			candidate: Runtime::AddressMapping::into_account_id(candidate.0),
+			amount,
+			commission: Perbill::from_parts(commission),
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("join(uint256,uint256)")]
+	fn join(handle: &mut impl PrecompileHandle, pool_id: PoolId, amount: U256) -> EvmResult {
+		let amount = Self::u256_to_amount(amount).in_field("amount")?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::join { pool_id, amount };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("unbond(uint256)")]
+	fn unbond(handle: &mut impl PrecompileHandle, amount: U256) -> EvmResult {
+		let amount = Self::u256_to_amount(amount).in_field("amount")?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::unbond { amount };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("withdrawUnbonded()")]
+	#[precompile::public("withdraw_unbonded()")]
+	fn withdraw_unbonded(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::withdraw_unbonded {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("claimRewards()")]
+	#[precompile::public("claim_rewards()")]
+	fn claim_rewards(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::claim_rewards {};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("setCommission(uint256,uint256)")]
+	#[precompile::public("set_commission(uint256,uint256)")]
+	fn set_commission(handle: &mut impl PrecompileHandle, pool_id: PoolId, new: u32) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::set_commission {
+			pool_id,
+			new: Perbill::from_parts(new),
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("claimCommission(uint256)")]
+	#[precompile::public("claim_commission(uint256)")]
+	fn claim_commission(handle: &mut impl PrecompileHandle, pool_id: PoolId) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::claim_commission { pool_id };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	#[precompile::public("poke(uint256)")]
+	fn poke(handle: &mut impl PrecompileHandle, pool_id: PoolId) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = NominationPoolsCall::<Runtime>::poke { pool_id };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+		Ok(())
+	}
+
+	// Util methods
+
+	fn u256_to_amount(value: U256) -> MayRevert<BalanceOf<Runtime>> {
+		value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").into())
+	}
+}
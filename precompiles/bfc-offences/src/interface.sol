@@ -14,9 +14,15 @@ interface BfcOffences {
         uint256 latest_offence_round_index;
         uint256 latest_offence_session_index;
         uint256 offence_count;
+        uint256 latest_offence_kind;
+        uint256 unresponsive_count;
+        uint256 grandpa_equivocation_count;
+        uint256 relayer_heartbeat_count;
+        uint256 aura_equivocation_count;
     }
 
-    /// @dev Get the maximum offence count
+    /// @dev Get the maximum offence count of unresponsiveness offences. The other offence kinds
+    /// are only exposed through offence_kind_config
     /// Selector: 42caa150
     /// @param tier the type of the validator tier (0: All, 1: Basic, 2: Full)
     /// @return The maximum offence count
@@ -25,6 +31,21 @@ interface BfcOffences {
         view
         returns (uint256[] memory);
 
+    /// @dev Get the offence management policy of the given offence kind
+    /// Selector: 6d1eb59c
+    /// @param kind the offence kind (0: Unresponsive, 1: GrandpaEquivocation, 2: RelayerHeartbeat, 3: AuraEquivocation)
+    /// @return The maximum offence count of full and basic nodes, the maximum slash fraction of a
+    /// single offence in parts per billion and the expiration in sessions
+    function offence_kind_config(uint256 kind)
+        external
+        view
+        returns (
+            uint256,
+            uint256,
+            uint256,
+            uint256
+        );
+
     /// @dev Get the current offence state of the given validator
     /// Selector: c63c3f8a
     /// @return The current offence state of the given validator
@@ -43,6 +64,11 @@ interface BfcOffences {
             address[] memory,
             uint256[] memory,
             uint256[] memory,
+            uint256[] memory,
+            uint256[] memory,
+            uint256[] memory,
+            uint256[] memory,
+            uint256[] memory,
             uint256[] memory
         );
 }
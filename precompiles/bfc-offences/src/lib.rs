@@ -5,12 +5,15 @@ use pallet_evm::AddressMapping;
 
 use precompile_utils::prelude::*;
 
-use bp_staking::TierType;
+use bp_staking::{OffenceKind, TierType};
 use sp_core::{H160, H256};
 use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, vec, vec::Vec};
 
 mod types;
-use types::{EvmValidatorOffenceOf, EvmValidatorOffencesOf, ValidatorOffence, ValidatorOffences};
+use types::{
+	EvmOffenceKindConfigOf, EvmValidatorOffenceOf, EvmValidatorOffencesOf, ValidatorOffence,
+	ValidatorOffences,
+};
 
 /// A precompile to wrap the functionality from pallet_bfc_offences
 pub struct BfcOffencesPrecompile<Runtime>(PhantomData<Runtime>);
@@ -25,6 +28,8 @@ where
 {
 	// Storage getters
 
+	/// Returns the maximum offence counts of unresponsiveness offences only. The policies of the
+	/// other offence kinds are returned by `offenceKindConfig`.
 	#[precompile::public("maximumOffenceCount(uint256)")]
 	#[precompile::public("maximum_offence_count(uint256)")]
 	#[precompile::view]
@@ -37,27 +42,38 @@ where
 			_ => return Err(RevertReason::read_out_of_bounds("tier").into()),
 		};
 
-		let mut maximum_offence_count = vec![];
-		match tier {
-			TierType::Full => {
-				maximum_offence_count
-					.push(pallet_bfc_offences::FullMaximumOffenceCount::<Runtime>::get());
-			},
-			TierType::Basic => {
-				maximum_offence_count
-					.push(pallet_bfc_offences::BasicMaximumOffenceCount::<Runtime>::get());
-			},
+		// the maximum offence count of unresponsiveness offences
+		let config =
+			pallet_bfc_offences::OffenceKindConfigs::<Runtime>::get(OffenceKind::Unresponsive);
+		let maximum_offence_count = match tier {
 			TierType::All => {
-				maximum_offence_count
-					.push(pallet_bfc_offences::FullMaximumOffenceCount::<Runtime>::get());
-				maximum_offence_count
-					.push(pallet_bfc_offences::BasicMaximumOffenceCount::<Runtime>::get());
+				vec![config.full_maximum_offence_count, config.basic_maximum_offence_count]
 			},
-		}
+			tier => vec![config.maximum_offence_count(tier)],
+		};
 
 		Ok(maximum_offence_count)
 	}
 
+	#[precompile::public("offenceKindConfig(uint256)")]
+	#[precompile::public("offence_kind_config(uint256)")]
+	#[precompile::view]
+	fn offence_kind_config(
+		handle: &mut impl PrecompileHandle,
+		kind: u32,
+	) -> EvmResult<EvmOffenceKindConfigOf> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let kind = OffenceKind::from_index(kind).ok_or(RevertReason::read_out_of_bounds("kind"))?;
+
+		let config = pallet_bfc_offences::OffenceKindConfigs::<Runtime>::get(kind);
+		Ok((
+			config.full_maximum_offence_count,
+			config.basic_maximum_offence_count,
+			config.max_slash_fraction.deconstruct(),
+			config.expiration_in_sessions,
+		))
+	}
+
 	#[precompile::public("validatorOffence(address)")]
 	#[precompile::public("validator_offence(address)")]
 	#[precompile::view]
@@ -3,7 +3,7 @@ use frame_support::traits::Currency;
 use pallet_bfc_offences::{OffenceCount, ValidatorOffenceInfo};
 use precompile_utils::prelude::Address;
 
-use bp_staking::RoundIndex;
+use bp_staking::{OffenceKind, RoundIndex};
 use sp_core::H160;
 use sp_staking::SessionIndex;
 use sp_std::{marker::PhantomData, vec, vec::Vec};
@@ -12,10 +12,33 @@ pub type BalanceOf<Runtime> = <<Runtime as pallet_bfc_offences::Config>::Currenc
 	<Runtime as frame_system::Config>::AccountId,
 >>::Balance;
 
-pub type EvmValidatorOffenceOf = (Address, RoundIndex, SessionIndex, OffenceCount);
+pub type EvmValidatorOffenceOf = (
+	Address,
+	RoundIndex,
+	SessionIndex,
+	OffenceCount,
+	u32,
+	OffenceCount,
+	OffenceCount,
+	OffenceCount,
+	OffenceCount,
+);
 
-pub type EvmValidatorOffencesOf =
-	(Vec<Address>, Vec<RoundIndex>, Vec<SessionIndex>, Vec<OffenceCount>);
+pub type EvmValidatorOffencesOf = (
+	Vec<Address>,
+	Vec<RoundIndex>,
+	Vec<SessionIndex>,
+	Vec<OffenceCount>,
+	Vec<u32>,
+	Vec<OffenceCount>,
+	Vec<OffenceCount>,
+	Vec<OffenceCount>,
+	Vec<OffenceCount>,
+);
+
+/// The maximum offence count of full and basic nodes, the maximum slash fraction and the
+/// expiration in sessions of an offence kind
+pub type EvmOffenceKindConfigOf = (OffenceCount, OffenceCount, u32, SessionIndex);
 
 /// EVM struct for validator offence
 pub struct ValidatorOffence<Runtime: pallet_bfc_offences::Config> {
@@ -27,6 +50,16 @@ pub struct ValidatorOffence<Runtime: pallet_bfc_offences::Config> {
 	pub latest_offence_session_index: RoundIndex,
 	/// The total offences this validator earned
 	pub offence_count: OffenceCount,
+	/// The kind of the latest offence this validator earned
+	pub latest_offence_kind: u32,
+	/// The unresponsiveness offences this validator earned
+	pub unresponsive_count: OffenceCount,
+	/// The GRANDPA equivocation offences this validator earned
+	pub grandpa_equivocation_count: OffenceCount,
+	/// The relayer heartbeat offences this validator earned
+	pub relayer_heartbeat_count: OffenceCount,
+	/// The Aura equivocation offences this validator earned
+	pub aura_equivocation_count: OffenceCount,
 	/// A zero-sized type used to mark things that "act like" they own a T.
 	phantom: PhantomData<Runtime>,
 }
@@ -42,6 +75,11 @@ where
 			latest_offence_round_index: 0u32,
 			latest_offence_session_index: 0u32,
 			offence_count: 0u32,
+			latest_offence_kind: 0u32,
+			unresponsive_count: 0u32,
+			grandpa_equivocation_count: 0u32,
+			relayer_heartbeat_count: 0u32,
+			aura_equivocation_count: 0u32,
 			phantom: PhantomData,
 		}
 	}
@@ -61,6 +99,11 @@ where
 			latest_offence_round_index: offence.latest_offence_round_index,
 			latest_offence_session_index: offence.latest_offence_session_index,
 			offence_count: offence.offence_count,
+			latest_offence_kind: offence.latest_offence_kind().unwrap_or_default().index(),
+			unresponsive_count: offence.offence_count_of(OffenceKind::Unresponsive),
+			grandpa_equivocation_count: offence.offence_count_of(OffenceKind::GrandpaEquivocation),
+			relayer_heartbeat_count: offence.offence_count_of(OffenceKind::RelayerHeartbeat),
+			aura_equivocation_count: offence.offence_count_of(OffenceKind::AuraEquivocation),
 			phantom: PhantomData,
 		}
 	}
@@ -76,6 +119,11 @@ where
 			offence.latest_offence_round_index,
 			offence.latest_offence_session_index,
 			offence.offence_count,
+			offence.latest_offence_kind,
+			offence.unresponsive_count,
+			offence.grandpa_equivocation_count,
+			offence.relayer_heartbeat_count,
+			offence.aura_equivocation_count,
 		)
 	}
 }
@@ -90,6 +138,16 @@ pub struct ValidatorOffences<Runtime: pallet_bfc_offences::Config> {
 	pub latest_offence_session_index: Vec<RoundIndex>,
 	/// The total offences this validator earned
 	pub offence_count: Vec<OffenceCount>,
+	/// The kind of the latest offence this validator earned
+	pub latest_offence_kind: Vec<u32>,
+	/// The unresponsiveness offences this validator earned
+	pub unresponsive_count: Vec<OffenceCount>,
+	/// The GRANDPA equivocation offences this validator earned
+	pub grandpa_equivocation_count: Vec<OffenceCount>,
+	/// The relayer heartbeat offences this validator earned
+	pub relayer_heartbeat_count: Vec<OffenceCount>,
+	/// The Aura equivocation offences this validator earned
+	pub aura_equivocation_count: Vec<OffenceCount>,
 	/// A zero-sized type used to mark things that "act like" they own a T.
 	phantom: PhantomData<Runtime>,
 }
@@ -105,6 +163,11 @@ where
 			latest_offence_round_index: vec![],
 			latest_offence_session_index: vec![],
 			offence_count: vec![],
+			latest_offence_kind: vec![],
+			unresponsive_count: vec![],
+			grandpa_equivocation_count: vec![],
+			relayer_heartbeat_count: vec![],
+			aura_equivocation_count: vec![],
 			phantom: PhantomData,
 		}
 	}
@@ -114,6 +177,11 @@ where
 		self.latest_offence_round_index.push(0u32);
 		self.latest_offence_session_index.push(0u32);
 		self.offence_count.push(0u32);
+		self.latest_offence_kind.push(0u32);
+		self.unresponsive_count.push(0u32);
+		self.grandpa_equivocation_count.push(0u32);
+		self.relayer_heartbeat_count.push(0u32);
+		self.aura_equivocation_count.push(0u32);
 	}
 
 	pub fn insert_offence(&mut self, offence: ValidatorOffence<Runtime>) {
@@ -121,6 +189,11 @@ where
 		self.latest_offence_round_index.push(offence.latest_offence_round_index);
 		self.latest_offence_session_index.push(offence.latest_offence_session_index);
 		self.offence_count.push(offence.offence_count);
+		self.latest_offence_kind.push(offence.latest_offence_kind);
+		self.unresponsive_count.push(offence.unresponsive_count);
+		self.grandpa_equivocation_count.push(offence.grandpa_equivocation_count);
+		self.relayer_heartbeat_count.push(offence.relayer_heartbeat_count);
+		self.aura_equivocation_count.push(offence.aura_equivocation_count);
 	}
 }
 
@@ -134,6 +207,11 @@ where
 			offence.latest_offence_round_index,
 			offence.latest_offence_session_index,
 			offence.offence_count,
+			offence.latest_offence_kind,
+			offence.unresponsive_count,
+			offence.grandpa_equivocation_count,
+			offence.relayer_heartbeat_count,
+			offence.aura_equivocation_count,
 		)
 	}
 }
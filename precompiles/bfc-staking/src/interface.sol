@@ -32,6 +32,14 @@ interface BfcStaking {
         uint256 tier;
     }
 
+    struct candidate_identity {
+        bytes name;
+        bytes website;
+        bytes contact;
+        bytes32 logo_hash;
+        bytes relayer_endpoint;
+    }
+
     struct candidate_request_data {
         address candidate;
         uint256 amount;
@@ -300,10 +308,13 @@ interface BfcStaking {
     /// @dev Get the current state of the given candidate
     /// Selector: 36f3b497
     /// @param candidate the address for which we are querying the state
-    /// @return The current state of the queried candidate
+    /// @return The current state and the registered identity of the queried candidate
     function candidate_state(
         address candidate
-    ) external view returns (candidate_meta_data memory);
+    )
+        external
+        view
+        returns (candidate_meta_data memory, candidate_identity memory);
 
     /// @dev Get every candidate states
     /// Selector: 3b368c8c
@@ -459,6 +470,26 @@ interface BfcStaking {
         address nominator
     ) external view returns (uint256);
 
+    /// @dev Get the rewards paid to the given account that were earned between the given rounds
+    /// Selector: 511189c0
+    /// @param account The validator (controller) or nominator address for which we are querying
+    /// @param from_round The first round (inclusive) of the queried range
+    /// @param to_round The last round (inclusive) of the queried range
+    /// @return The rounds, validators, total amounts and compounded amounts of the rewards
+    function reward_history(
+        address account,
+        uint256 from_round,
+        uint256 to_round
+    )
+        external
+        view
+        returns (
+            uint256[] memory,
+            address[] memory,
+            uint256[] memory,
+            uint256[] memory
+        );
+
     /// @dev Temporarily leave the set of validator candidates without unbonding
     /// Selector: 767e0450
     function go_offline() external;
@@ -467,6 +498,10 @@ interface BfcStaking {
     /// Selector: d2f73ceb
     function go_online() external;
 
+    /// @dev Rejoin the set of validator candidates after the jail for an offence has ended
+    /// Selector: f679d305
+    function unjail() external;
+
     /// @dev Join the set of validator candidates
     /// Selector: f98e1021
     /// @param controller The paired controller to the stash account
@@ -523,6 +558,25 @@ interface BfcStaking {
     /// Selector: c1f6309a
     function cancel_validator_commission_set() external;
 
+    /// @dev Register or update the identity of the given candidate
+    /// Selector: 0d21a813
+    /// @param name the display name of the validator
+    /// @param website the website of the validator operator
+    /// @param contact the contact of the validator operator
+    /// @param logo_hash the hash of the validator logo image
+    /// @param relayer_endpoint the relayer endpoint of a full validator (empty if none)
+    function set_candidate_identity(
+        bytes calldata name,
+        bytes calldata website,
+        bytes calldata contact,
+        bytes32 logo_hash,
+        bytes calldata relayer_endpoint
+    ) external;
+
+    /// @dev Clear the identity of the given candidate and return its deposit
+    /// Selector: 586b5091
+    function clear_candidate_identity() external;
+
     /// @dev Set the tier of the given candidate
     /// Selector: ed048bda
     /// @param more The additional amount self-bonded (set to 0 if additional bonding is not desired)
@@ -548,6 +602,17 @@ interface BfcStaking {
     /// @param new_reward_dst The new reward destination to be set (Staked = 0, Account = 1)
     function set_candidate_reward_dst(uint256 new_reward_dst) external;
 
+    /// @dev Set the validator candidate reward destination with a payee account
+    /// Selector: f7eb3dcd
+    /// @param new_reward_dst The new reward destination to be set (Staked = 0, Account = 1, Payee = 2, Split = 3)
+    /// @param payee The account that receives the rewards (Payee, Split)
+    /// @param staked The portion of the rewards to be auto-compounded in parts per billion (Split)
+    function set_candidate_reward_dst(
+        uint256 new_reward_dst,
+        address payee,
+        uint256 staked
+    ) external;
+
     /// @dev Make a nomination in support of a validator candidate
     /// Selector: 49df6eb3
     /// @param candidate The address of the supported validator candidate
@@ -567,6 +632,13 @@ interface BfcStaking {
     /// @param more The amount by which the nomination is increased
     function nominator_bond_more(address candidate, uint256 more) external;
 
+    /// @dev Move a nomination from one validator candidate to another without the revocation delay
+    /// Selector: 6bd8f804
+    /// @param from The address of the currently nominated validator candidate
+    /// @param to The address of the validator candidate to be nominated
+    /// @param amount The amount of the nomination to be moved
+    function redelegate(address from, address to, uint256 amount) external;
+
     /// @dev Request to leave the set of nominators
     /// Selector: 13153b19
     function schedule_leave_nominators() external;
@@ -610,4 +682,15 @@ interface BfcStaking {
     /// Selector: 5706390d
     /// @param new_reward_dst The new reward destination to be set (Staked = 0, Account = 1)
     function set_nominator_reward_dst(uint256 new_reward_dst) external;
+
+    /// @dev Set the nominator reward destination with a payee account
+    /// Selector: 90e2e151
+    /// @param new_reward_dst The new reward destination to be set (Staked = 0, Account = 1, Payee = 2, Split = 3)
+    /// @param payee The account that receives the rewards (Payee, Split)
+    /// @param staked The portion of the rewards to be auto-compounded in parts per billion (Split)
+    function set_nominator_reward_dst(
+        uint256 new_reward_dst,
+        address payee,
+        uint256 staked
+    ) external;
 }
@@ -9,14 +9,15 @@ use frame_support::{
 };
 
 use pallet_bfc_staking::{
-	BlockNumberFor, Call as StakingCall, NominationChange, RewardDestination,
+	BlockNumberFor, Call as StakingCall, IdentityField, NominationChange, RewardDestination,
+	MAX_IDENTITY_FIELD_LENGTH,
 };
 use pallet_evm::AddressMapping;
 
 use precompile_utils::prelude::*;
 
 use bp_staking::{RoundIndex, TierType, MAX_AUTHORITIES};
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::{traits::Dispatchable, Perbill, Saturating};
 use sp_std::{
 	collections::btree_set::BTreeSet, convert::TryInto, marker::PhantomData, vec, vec::Vec,
@@ -24,9 +25,10 @@ use sp_std::{
 
 mod types;
 use types::{
-	BalanceOf, CandidateState, CandidateStates, EvmCandidatePoolOf, EvmCandidateStateOf,
-	EvmCandidateStatesOf, EvmNominatorRequestsOf, EvmNominatorStateOf, EvmRoundInfoOf, EvmTotalOf,
-	NominatorState, StakingOf, TotalStake,
+	evm_reward_history, BalanceOf, CandidateState, CandidateStates, EvmCandidateIdentityOf,
+	EvmCandidatePoolOf, EvmCandidateStateOf, EvmCandidateStatesOf, EvmNominatorRequestsOf,
+	EvmNominatorStateOf, EvmRewardHistoryOf, EvmRoundInfoOf, EvmTotalOf, NominatorState, StakingOf,
+	TotalStake,
 };
 
 /// A precompile to wrap the functionality from pallet_bfc_staking.
@@ -720,18 +722,19 @@ where
 
 	/// Returns the state of the given `candidate`
 	/// @param: `candidate` the address for which to verify
-	/// @return: the state of the given `candidate`
+	/// @return: the state and the registered identity of the given `candidate`
 	#[precompile::public("candidateState(address)")]
 	#[precompile::public("candidate_state(address)")]
 	#[precompile::view]
 	fn candidate_state(
 		handle: &mut impl PrecompileHandle,
 		candidate: Address,
-	) -> EvmResult<EvmCandidateStateOf> {
+	) -> EvmResult<(EvmCandidateStateOf, EvmCandidateIdentityOf)> {
 		let candidate = Runtime::AddressMapping::into_account_id(candidate.0);
 
-		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 2)?;
 		let mut candidate_state = CandidateStates::<Runtime>::default();
+		let identity = pallet_bfc_staking::CandidateIdentities::<Runtime>::get(&candidate);
 
 		let mut is_existed: bool = false;
 		if let Some(state) = pallet_bfc_staking::CandidateInfo::<Runtime>::get(&candidate) {
@@ -744,7 +747,24 @@ where
 			candidate_state.insert_empty();
 		}
 
-		Ok(candidate_state.into())
+		let identity = match identity {
+			Some(identity) => (
+				identity.name.into_inner().into(),
+				identity.website.into_inner().into(),
+				identity.contact.into_inner().into(),
+				H256::from(identity.logo_hash),
+				identity.relayer_endpoint.map(|e| e.into_inner()).unwrap_or_default().into(),
+			),
+			None => (
+				Vec::new().into(),
+				Vec::new().into(),
+				Vec::new().into(),
+				H256::zero(),
+				Vec::new().into(),
+			),
+		};
+
+		Ok((candidate_state.into(), identity))
 	}
 
 	/// Returns the state of the entire validator candidates
@@ -1039,6 +1059,28 @@ where
 		Ok(result)
 	}
 
+	// Reward history getters
+
+	/// Returns the rewards paid to the given `account` that were earned between the given rounds
+	/// @param: `account` the validator (controller) or nominator address for which to verify
+	/// @param: `from_round` the first round (inclusive) of the queried range
+	/// @param: `to_round` the last round (inclusive) of the queried range
+	/// @return: the rounds, validators, total amounts and compounded amounts of the rewards
+	#[precompile::public("rewardHistory(address,uint256,uint256)")]
+	#[precompile::public("reward_history(address,uint256,uint256)")]
+	#[precompile::view]
+	fn reward_history(
+		handle: &mut impl PrecompileHandle,
+		account: Address,
+		from_round: RoundIndex,
+		to_round: RoundIndex,
+	) -> EvmResult<EvmRewardHistoryOf> {
+		let account = Runtime::AddressMapping::into_account_id(account.0);
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		Ok(evm_reward_history(StakingOf::<Runtime>::reward_history(&account, from_round, to_round)))
+	}
+
 	// Common dispatchable methods
 
 	#[precompile::public("goOffline()")]
@@ -1065,6 +1107,17 @@ where
 		Ok(())
 	}
 
+	#[precompile::public("unjail()")]
+	fn unjail(handle: &mut impl PrecompileHandle) -> EvmResult {
+		Self::ensure_caller_is_eoa(handle)?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = StakingCall::<Runtime>::unjail {};
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
 	// Validator dispatchable methods
 
 	#[precompile::public("joinCandidates(address,address,uint256,uint256)")]
@@ -1227,6 +1280,45 @@ where
 		Ok(())
 	}
 
+	#[precompile::public("setCandidateIdentity(bytes,bytes,bytes,bytes32,bytes)")]
+	#[precompile::public("set_candidate_identity(bytes,bytes,bytes,bytes32,bytes)")]
+	fn set_candidate_identity(
+		handle: &mut impl PrecompileHandle,
+		name: BoundedBytes<ConstU32<MAX_IDENTITY_FIELD_LENGTH>>,
+		website: BoundedBytes<ConstU32<MAX_IDENTITY_FIELD_LENGTH>>,
+		contact: BoundedBytes<ConstU32<MAX_IDENTITY_FIELD_LENGTH>>,
+		logo_hash: H256,
+		relayer_endpoint: BoundedBytes<ConstU32<MAX_IDENTITY_FIELD_LENGTH>>,
+	) -> EvmResult {
+		Self::ensure_caller_is_eoa(handle)?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let relayer_endpoint: Vec<u8> = relayer_endpoint.into();
+		let call = StakingCall::<Runtime>::set_candidate_identity {
+			name: IdentityField::truncate_from(name.into()),
+			website: IdentityField::truncate_from(website.into()),
+			contact: IdentityField::truncate_from(contact.into()),
+			logo_hash: logo_hash.0,
+			relayer_endpoint: (!relayer_endpoint.is_empty())
+				.then(|| IdentityField::truncate_from(relayer_endpoint)),
+		};
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("clearCandidateIdentity()")]
+	#[precompile::public("clear_candidate_identity()")]
+	fn clear_candidate_identity(handle: &mut impl PrecompileHandle) -> EvmResult {
+		Self::ensure_caller_is_eoa(handle)?;
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = StakingCall::<Runtime>::clear_candidate_identity {};
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
 	#[precompile::public("setValidatorTier(uint256,uint256,address)")]
 	#[precompile::public("set_validator_tier(uint256,uint256,address)")]
 	fn set_validator_tier(
@@ -1286,11 +1378,26 @@ where
 	#[precompile::public("set_candidate_reward_dst(uint256)")]
 	fn set_candidate_reward_dst(handle: &mut impl PrecompileHandle, reward_dst: u8) -> EvmResult {
 		Self::ensure_caller_is_eoa(handle)?;
-		let new_reward_dst = match reward_dst {
-			0 => RewardDestination::Staked,
-			1 => RewardDestination::Account,
-			_ => return Err(RevertReason::read_out_of_bounds("reward_dst").into()),
-		};
+		let new_reward_dst = Self::to_reward_dst(reward_dst, None, 0)?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = StakingCall::<Runtime>::set_candidate_reward_dst { new_reward_dst };
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("setCandidateRewardDst(uint256,address,uint256)")]
+	#[precompile::public("set_candidate_reward_dst(uint256,address,uint256)")]
+	fn set_candidate_reward_dst_with_payee(
+		handle: &mut impl PrecompileHandle,
+		reward_dst: u8,
+		payee: Address,
+		staked: u32,
+	) -> EvmResult {
+		Self::ensure_caller_is_eoa(handle)?;
+		let new_reward_dst = Self::to_reward_dst(reward_dst, Some(payee), staked)?;
 
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let call = StakingCall::<Runtime>::set_candidate_reward_dst { new_reward_dst };
@@ -1344,6 +1451,25 @@ where
 		Ok(())
 	}
 
+	#[precompile::public("redelegate(address,address,uint256)")]
+	fn redelegate(
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		amount: U256,
+	) -> EvmResult {
+		let amount = Self::u256_to_amount(amount).in_field("amount")?;
+		let from = Runtime::AddressMapping::into_account_id(from.0);
+		let to = Runtime::AddressMapping::into_account_id(to.0);
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = StakingCall::<Runtime>::redelegate { from, to, amount };
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
 	#[precompile::public("scheduleLeaveNominators()")]
 	#[precompile::public("schedule_leave_nominators()")]
 	fn schedule_leave_nominators(handle: &mut impl PrecompileHandle) -> EvmResult {
@@ -1449,11 +1575,25 @@ where
 	#[precompile::public("setNominatorRewardDst(uint256)")]
 	#[precompile::public("set_nominator_reward_dst(uint256)")]
 	fn set_nominator_reward_dst(handle: &mut impl PrecompileHandle, reward_dst: u8) -> EvmResult {
-		let new_reward_dst = match reward_dst {
-			0 => RewardDestination::Staked,
-			1 => RewardDestination::Account,
-			_ => return Err(RevertReason::read_out_of_bounds("reward_dst").into()),
-		};
+		let new_reward_dst = Self::to_reward_dst(reward_dst, None, 0)?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = StakingCall::<Runtime>::set_nominator_reward_dst { new_reward_dst };
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("setNominatorRewardDst(uint256,address,uint256)")]
+	#[precompile::public("set_nominator_reward_dst(uint256,address,uint256)")]
+	fn set_nominator_reward_dst_with_payee(
+		handle: &mut impl PrecompileHandle,
+		reward_dst: u8,
+		payee: Address,
+		staked: u32,
+	) -> EvmResult {
+		let new_reward_dst = Self::to_reward_dst(reward_dst, Some(payee), staked)?;
 
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
 		let call = StakingCall::<Runtime>::set_nominator_reward_dst { new_reward_dst };
@@ -1465,6 +1605,28 @@ where
 
 	// Util methods
 
+	/// Converts the given reward destination parameters (Staked = 0, Account = 1, Payee = 2,
+	/// Split = 3) to a `RewardDestination`. `Payee` and `Split` require a `payee` account and
+	/// `Split` pays the `staked` portion (in parts per billion) to the bonded account.
+	fn to_reward_dst(
+		reward_dst: u8,
+		payee: Option<Address>,
+		staked: u32,
+	) -> EvmResult<RewardDestination<Runtime::AccountId>> {
+		let payee = || {
+			payee
+				.map(|payee| Runtime::AddressMapping::into_account_id(payee.0))
+				.ok_or(RevertReason::custom("Payee account required"))
+		};
+		Ok(match reward_dst {
+			0 => RewardDestination::Staked,
+			1 => RewardDestination::Account,
+			2 => RewardDestination::Payee(payee()?),
+			3 => RewardDestination::Split { staked: Perbill::from_parts(staked), payee: payee()? },
+			_ => return Err(RevertReason::read_out_of_bounds("reward_dst").into()),
+		})
+	}
+
 	fn get_unique_candidates(candidates: &Vec<Address>) -> EvmResult<BTreeSet<Runtime::AccountId>> {
 		let unique_candidates: BTreeSet<Runtime::AccountId> = candidates
 			.iter()
@@ -2,14 +2,14 @@ use frame_support::traits::Currency;
 use frame_system::pallet_prelude::BlockNumberFor;
 
 use pallet_bfc_staking::{
-	CandidateMetadata, CapacityStatus, Nominator, NominatorStatus, RewardDestination,
+	CandidateMetadata, CapacityStatus, Nominator, NominatorStatus, RewardDestination, RewardEntry,
 	TotalSnapshot, ValidatorStatus,
 };
 
 use precompile_utils::prelude::*;
 
 use bp_staking::TierType;
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_std::{vec, vec::Vec};
 
 pub type BalanceOf<Runtime> = <<Runtime as pallet_bfc_staking::Config>::Currency as Currency<
@@ -94,6 +94,32 @@ pub type EvmNominatorStateOf =
 
 pub type EvmNominatorRequestsOf = (Address, u32, U256, Vec<Address>, Vec<U256>, Vec<u32>, Vec<u32>);
 
+pub type EvmRewardHistoryOf = (Vec<u32>, Vec<Address>, Vec<U256>, Vec<U256>);
+
+/// Splits the given reward entries into the rounds, validators, total amounts and compounded
+/// amounts of the rewards
+pub fn evm_reward_history<AccountId: Into<H160>, Balance: Into<U256>>(
+	entries: Vec<RewardEntry<AccountId, Balance>>,
+) -> EvmRewardHistoryOf {
+	let mut rounds: Vec<u32> = vec![];
+	let mut validators: Vec<Address> = vec![];
+	let mut amounts: Vec<U256> = vec![];
+	let mut compounded: Vec<U256> = vec![];
+
+	for entry in entries {
+		rounds.push(entry.round);
+		validators.push(Address(entry.validator.into()));
+		amounts.push(entry.amount.into());
+		compounded.push(entry.compounded.into());
+	}
+
+	(rounds, validators, amounts, compounded)
+}
+
+/// The name, website, contact, logo hash and relayer endpoint of a candidate identity
+pub type EvmCandidateIdentityOf =
+	(UnboundedBytes, UnboundedBytes, UnboundedBytes, H256, UnboundedBytes);
+
 /// EVM struct for candidate states
 pub struct CandidateStates<Runtime: pallet_bfc_staking::Config> {
 	/// This candidate's controller account
@@ -409,7 +435,7 @@ where
 		};
 
 		self.status = match state.status {
-			ValidatorStatus::KickedOut => 2u32.into(),
+			ValidatorStatus::KickedOut | ValidatorStatus::Jailed { .. } => 2u32.into(),
 			ValidatorStatus::Active => 1u32.into(),
 			ValidatorStatus::Idle => 0u32.into(),
 			ValidatorStatus::Leaving(when) => when.into(),
@@ -424,6 +450,8 @@ where
 		self.reward_dst = match state.reward_dst {
 			RewardDestination::Staked => 0u32.into(),
 			RewardDestination::Account => 1u32.into(),
+			RewardDestination::Payee(_) => 2u32.into(),
+			RewardDestination::Split { .. } => 3u32.into(),
 		};
 		self.awarded_tokens = state.awarded_tokens.into();
 
@@ -502,6 +530,8 @@ where
 		self.reward_dst = match state.reward_dst {
 			RewardDestination::Staked => 0u32.into(),
 			RewardDestination::Account => 1u32.into(),
+			RewardDestination::Payee(_) => 2u32.into(),
+			RewardDestination::Split { .. } => 3u32.into(),
 		};
 
 		state.awarded_tokens_per_candidate.iter().for_each(|(_, amount)| {
@@ -653,3 +683,29 @@ where
 		self.active_voting_power = stake.active_voting_power;
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reward_history_is_split_in_entry_order() {
+		let entry = |round, validator: u64, amount: u128, compounded: u128| RewardEntry {
+			round,
+			validator: H160::from_low_u64_be(validator),
+			amount,
+			compounded,
+		};
+		let (rounds, validators, amounts, compounded) =
+			evm_reward_history(vec![entry(3, 1, 100, 0), entry(4, 2, 250, 250)]);
+
+		assert_eq!(rounds, vec![3, 4]);
+		assert_eq!(
+			validators,
+			vec![Address(H160::from_low_u64_be(1)), Address(H160::from_low_u64_be(2))]
+		);
+		assert_eq!(amounts, vec![U256::from(100), U256::from(250)]);
+		assert_eq!(compounded, vec![U256::zero(), U256::from(250)]);
+		assert_eq!(evm_reward_history::<H160, u128>(vec![]), (vec![], vec![], vec![], vec![]));
+	}
+}
@@ -142,11 +142,31 @@ interface BtcRegistrationPool {
         uint32 pool_round
     ) external view returns (string memory);
 
+    /// @dev Returns the timelocked recovery path of the given user's vault
+    /// @custom:selector ce345d06
+    /// @param user_bfc_address The user's Bifrost address
+    /// @return The recovery public key, the relative timelock (in Bitcoin blocks) and the script
+    /// that contains the recovery branch. The script is empty until the vault address is generated.
+    function recovery_path(
+        address user_bfc_address,
+        uint32 pool_round
+    ) external view returns (bytes memory, uint32, bytes memory);
+
     /// @dev Join the registration pool and request a Bitcoin vault address.
     /// @custom:selector f65d6a74
     /// @param refund_address The Bitcoin refund address
     function request_vault(string memory refund_address) external;
 
+    /// @dev Join the registration pool and request a Bitcoin vault address with a timelocked
+    /// recovery path. The vault can be spent by the recovery key alone after the timelock.
+    /// @custom:selector d5f8c8f0
+    /// @param refund_address The Bitcoin refund address
+    /// @param recovery_pub_key The compressed public key (33 bytes) of the recovery path
+    function request_vault_with_recovery(
+        string memory refund_address,
+        bytes memory recovery_pub_key
+    ) external;
+
     /// @dev (Re-)set the user's refund address.
     /// @custom:selector 0e9212a7
     /// @param refund_address The Bitcoin refund address
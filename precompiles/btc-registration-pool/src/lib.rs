@@ -8,7 +8,7 @@ use pallet_evm::AddressMapping;
 
 use precompile_utils::prelude::*;
 
-use bp_btc_relay::{AddressState, BoundedBitcoinAddress, MigrationSequence};
+use bp_btc_relay::{AddressState, BoundedBitcoinAddress, MigrationSequence, Public};
 use fp_account::EthereumSignature;
 use sp_core::H160;
 use sp_runtime::{traits::Dispatchable, BoundedVec};
@@ -16,8 +16,8 @@ use sp_std::{marker::PhantomData, vec, vec::Vec};
 
 mod types;
 use types::{
-	BitcoinAddressString, EvmPendingRegistrationsOf, EvmRegistrationInfoOf, EvmRegistrationPoolOf,
-	RegistrationInfo,
+	BitcoinAddressString, EvmPendingRegistrationsOf, EvmRecoveryPathOf, EvmRegistrationInfoOf,
+	EvmRegistrationPoolOf, PublicKeyBytes, RegistrationInfo,
 };
 
 /// Solidity selector of the VaultPending log, which is the Keccak of the Log signature.
@@ -397,6 +397,30 @@ where
 		)
 	}
 
+	#[precompile::public("recoveryPath(address,uint32)")]
+	#[precompile::public("recovery_path(address,uint32)")]
+	#[precompile::view]
+	fn recovery_path(
+		handle: &mut impl PrecompileHandle,
+		user_bfc_address: Address,
+		pool_round: PoolRound,
+	) -> EvmResult<EvmRecoveryPathOf> {
+		// RecoveryPaths + RegistrationPool
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 2)?;
+
+		let user_bfc_address = Runtime::AddressMapping::into_account_id(user_bfc_address.0);
+
+		Ok(
+			match pallet_btc_registration_pool::Pallet::<Runtime>::get_recovery_path(
+				&user_bfc_address,
+				Self::target_round(pool_round),
+			) {
+				Some((path, script)) => (path.pub_key.0.into(), path.timelock, script.into()),
+				None => (PublicKeyBytes::from(vec![]), 0, UnboundedBytes::from(vec![])),
+			},
+		)
+	}
+
 	#[precompile::public("request_vault(string)")]
 	#[precompile::public("requestVault(string)")]
 	fn request_vault(
@@ -431,6 +455,44 @@ where
 		Ok(())
 	}
 
+	#[precompile::public("request_vault_with_recovery(string,bytes)")]
+	#[precompile::public("requestVaultWithRecovery(string,bytes)")]
+	fn request_vault_with_recovery(
+		handle: &mut impl PrecompileHandle,
+		refund_address: BitcoinAddressString,
+		recovery_pub_key: PublicKeyBytes,
+	) -> EvmResult {
+		if pallet_btc_registration_pool::ServiceState::<Runtime>::get() != MigrationSequence::Normal
+		{
+			return Err(RevertReason::custom("Service is under maintenance").into());
+		}
+
+		let caller = handle.context().caller;
+
+		let raw_refund_address = refund_address.clone();
+		let refund_address = Self::convert_string_to_bitcoin_address(raw_refund_address.clone())
+			.in_field("refund_address")?;
+		let recovery_pub_key = <[u8; 33]>::try_from(recovery_pub_key.as_bytes())
+			.map_err(|_| RevertReason::custom("invalid public key").in_field("recovery_pub_key"))?;
+
+		let call = BtcRegistrationPoolCall::<Runtime>::request_vault_with_recovery {
+			refund_address: refund_address.to_vec(),
+			recovery_pub_key: Public(recovery_pub_key),
+		};
+		let origin = Runtime::AddressMapping::into_account_id(caller);
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		let event = log1(
+			handle.context().address,
+			SELECTOR_LOG_VAULT_PENDING,
+			solidity::encode_event_data((Address(caller), raw_refund_address)),
+		);
+		handle.record_log_costs(&[&event])?;
+		event.record(handle)?;
+
+		Ok(())
+	}
+
 	#[precompile::public("request_set_refund(string)")]
 	#[precompile::public("requestSetRefund(string)")]
 	fn request_set_refund(
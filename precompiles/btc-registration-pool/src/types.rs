@@ -1,6 +1,6 @@
 use bp_btc_relay::{ADDRESS_MAX_LENGTH, PUBLIC_KEY_LENGTH};
 
-use precompile_utils::prelude::{Address, BoundedBytes, BoundedString};
+use precompile_utils::prelude::{Address, BoundedBytes, BoundedString, UnboundedBytes};
 use sp_core::ConstU32;
 use sp_std::{vec, vec::Vec};
 
@@ -17,6 +17,9 @@ pub type EvmRegistrationPoolOf =
 /// The solidity type for pending registrations.
 pub type EvmPendingRegistrationsOf = (Vec<Address>, Vec<BitcoinAddressString>);
 
+/// The solidity type for the recovery path of a user vault. (public key, timelock, script)
+pub type EvmRecoveryPathOf = (PublicKeyBytes, u32, UnboundedBytes);
+
 pub type EvmRegistrationInfoOf =
 	(Address, BitcoinAddressString, BitcoinAddressString, Vec<Address>, Vec<PublicKeyBytes>);
 
@@ -157,6 +157,34 @@ interface RelayManager {
             uint256[] memory
         );
 
+    /// @dev Get the performance score of the given relayer for the given round
+    /// Selector: a0afaab5
+    /// @param relayer the address that we want to check
+    /// @param round_index the round index that we want to check
+    /// @return The relayer score. (heartbeats, missed heartbeats, on-time votes, missed votes, performance in parts per billion)
+    function relayer_score(address relayer, uint256 round_index)
+        external
+        view
+        returns (
+            uint256,
+            uint256,
+            uint256,
+            uint256,
+            uint256
+        );
+
+    /// @dev Get the relayer version requirement
+    /// Selector: 1727a770
+    /// @return The minimum implementation version, the accepted spec versions and the round index from which the requirement is enforced
+    function relayer_version_requirement()
+        external
+        view
+        returns (
+            uint256,
+            bytes32[] memory,
+            uint256
+        );
+
     /// @dev Sends a heartbeat that sets relayer liveness
     /// Selector: 3defb962
     function heartbeat() external;
@@ -18,7 +18,10 @@ use sp_runtime::traits::Dispatchable;
 use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, vec, vec::Vec};
 
 mod types;
-use types::{EvmRelayerStateOf, EvmRelayerStatesOf, RelayManagerOf, RelayerState, RelayerStates};
+use types::{
+	EvmRelayerScoreOf, EvmRelayerStateOf, EvmRelayerStatesOf, RelayManagerOf, RelayerState,
+	RelayerStates,
+};
 
 /// A precompile to wrap the functionality from pallet_relay_manager
 pub struct RelayManagerPrecompile<Runtime>(PhantomData<Runtime>);
@@ -352,6 +355,46 @@ where
 		Ok(relayer_states.into())
 	}
 
+	#[precompile::public("relayerScore(address,uint256)")]
+	#[precompile::public("relayer_score(address,uint256)")]
+	#[precompile::view]
+	fn relayer_score(
+		handle: &mut impl PrecompileHandle,
+		relayer: Address,
+		round_index: RoundIndex,
+	) -> EvmResult<EvmRelayerScoreOf> {
+		let relayer = Runtime::AddressMapping::into_account_id(relayer.0);
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let score = pallet_relay_manager::RelayerScores::<Runtime>::get(round_index, &relayer);
+
+		Ok((
+			score.heartbeats,
+			score.missed_heartbeats,
+			score.on_time_votes,
+			score.missed_votes,
+			score.performance().deconstruct(),
+		))
+	}
+
+	#[precompile::public("relayerVersionRequirement()")]
+	#[precompile::public("relayer_version_requirement()")]
+	#[precompile::view]
+	fn relayer_version_requirement(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<(u32, Vec<H256>, RoundIndex)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 3)?;
+		let min_impl_version = pallet_relay_manager::MinRelayerImplVersion::<Runtime>::get();
+		let accepted_spec_versions = pallet_relay_manager::AcceptedSpecVersions::<Runtime>::get()
+			.into_iter()
+			.map(|spec_version| spec_version.into())
+			.collect::<Vec<H256>>();
+		let enforced_at =
+			pallet_relay_manager::RelayerVersionEnforcedAt::<Runtime>::get().unwrap_or_default();
+
+		Ok((min_impl_version, accepted_spec_versions, enforced_at))
+	}
+
 	// Dispatchable methods
 
 	#[precompile::public("heartbeat()")]
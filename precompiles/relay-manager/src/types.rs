@@ -11,6 +11,8 @@ pub type EvmRelayerStateOf = (Address, Address, u32);
 
 pub type EvmRelayerStatesOf = (Vec<Address>, Vec<Address>, Vec<u32>);
 
+pub type EvmRelayerScoreOf = (u32, u32, u32, u32, u32);
+
 /// EVM struct for relayer state
 pub struct RelayerState<Runtime: pallet_relay_manager::Config> {
 	/// This relayer's account
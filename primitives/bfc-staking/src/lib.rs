@@ -4,10 +4,11 @@ pub mod traits;
 
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode};
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 
 use frame_support::pallet_prelude::MaxEncodedLen;
-use sp_runtime::{traits::Zero, Perbill, RuntimeDebug};
-use sp_staking::SessionIndex;
+use sp_runtime::{traits::Zero, PerThing, Perbill, RuntimeDebug};
+use sp_staking::{offence::Kind, SessionIndex};
 
 /// The type that indicates the index of a round
 pub type RoundIndex = u32;
@@ -28,6 +29,8 @@ pub const MAX_AUTHORITIES: u32 = 1_000;
 	RuntimeDebug,
 	TypeInfo,
 	MaxEncodedLen,
+	Serialize,
+	Deserialize,
 )]
 /// The tier type of a validator node.
 pub enum TierType {
@@ -45,9 +48,160 @@ impl Default for TierType {
 	}
 }
 
+#[derive(
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+/// The kind of misbehavior that an offence has been reported for.
+pub enum OffenceKind {
+	/// The validator didn't send an `im-online` heartbeat during the session
+	Unresponsive,
+	/// The validator signed two conflicting GRANDPA votes in the same round
+	GrandpaEquivocation,
+	/// The relayer of a full node didn't send a heartbeat during the session
+	RelayerHeartbeat,
+	/// The validator authored two different blocks in the same Aura slot
+	AuraEquivocation,
+}
+
+impl Default for OffenceKind {
+	fn default() -> Self {
+		OffenceKind::Unresponsive
+	}
+}
+
+impl OffenceKind {
+	/// Every offence kind, in the order of their encoded index
+	pub const ALL: [OffenceKind; 4] = [
+		OffenceKind::Unresponsive,
+		OffenceKind::GrandpaEquivocation,
+		OffenceKind::RelayerHeartbeat,
+		OffenceKind::AuraEquivocation,
+	];
+
+	/// Returns the offence kind of the given `sp_staking` offence identifier, if it is known.
+	pub fn from_id(id: &Kind) -> Option<Self> {
+		match id {
+			b"im-online:offlin" => Some(OffenceKind::Unresponsive),
+			b"grandpa:equivoca" => Some(OffenceKind::GrandpaEquivocation),
+			b"relay-mgr:offlin" => Some(OffenceKind::RelayerHeartbeat),
+			b"aura:equivocatio" => Some(OffenceKind::AuraEquivocation),
+			_ => None,
+		}
+	}
+
+	/// Returns the offence kind of the given encoded index.
+	pub fn from_index(index: u32) -> Option<Self> {
+		Self::ALL.get(index as usize).copied()
+	}
+
+	/// Returns the encoded index of this offence kind.
+	pub fn index(&self) -> u32 {
+		*self as u32
+	}
+
+	/// Whether this offence kind is a consensus equivocation.
+	pub fn is_equivocation(&self) -> bool {
+		matches!(self, OffenceKind::GrandpaEquivocation | OffenceKind::AuraEquivocation)
+	}
+}
+
+#[derive(
+	Eq,
+	PartialEq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	Default,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+/// The offence management policy that applies to a specific offence kind
+pub struct OffenceKindConfig {
+	/// The maximum offence count of this kind for full nodes before slashing. Offences are
+	/// slashed as soon as they are reported if it's set to zero.
+	pub full_maximum_offence_count: u32,
+	/// The maximum offence count of this kind for basic nodes before slashing. Offences are
+	/// slashed as soon as they are reported if it's set to zero.
+	pub basic_maximum_offence_count: u32,
+	/// The maximum slash fraction that a single offence of this kind can hold
+	pub max_slash_fraction: Perbill,
+	/// The number of sessions without another offence of this kind before they expire
+	pub expiration_in_sessions: SessionIndex,
+}
+
+impl OffenceKindConfig {
+	/// Returns the maximum offence count of the given tier.
+	pub fn maximum_offence_count(&self, tier: TierType) -> u32 {
+		match tier {
+			TierType::Full => self.full_maximum_offence_count,
+			_ => self.basic_maximum_offence_count,
+		}
+	}
+}
+
+#[derive(
+	Eq,
+	PartialEq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	Default,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+/// The number of votes required for a relay workflow to reach the consensus
+pub enum MajorityThreshold {
+	/// The simple majority of the authorities
+	#[default]
+	Majority,
+	/// The given proportion of the authorities, rounded up
+	Proportion(Perbill),
+	/// An absolute number of votes, capped at the number of authorities
+	Absolute(u32),
+}
+
+impl MajorityThreshold {
+	/// Computes the number of required votes for the given number of authorities. The result is
+	/// never below the simple majority, so a poll can't be finalized by a minority.
+	pub fn compute(&self, count: u32) -> u32 {
+		let majority = (count / 2) + 1;
+		let threshold = match self {
+			MajorityThreshold::Majority => majority,
+			MajorityThreshold::Proportion(proportion) => proportion.mul_ceil(count),
+			MajorityThreshold::Absolute(votes) => (*votes).min(count),
+		};
+		threshold.max(majority)
+	}
+}
+
 #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 /// The detailed information of offences that a specific validator earned
 pub struct Offence<Balance> {
+	/// The kind of misbehavior this offence has been reported for
+	pub kind: OffenceKind,
 	/// The round index this offence happened
 	pub round_index: RoundIndex,
 	/// The session index this offence happened
@@ -75,6 +229,7 @@ impl<
 	> Offence<Balance>
 {
 	pub fn new(
+		kind: OffenceKind,
 		round_index: RoundIndex,
 		session_index: SessionIndex,
 		self_bond: Balance,
@@ -84,6 +239,7 @@ impl<
 		slash_fraction: Perbill,
 	) -> Self {
 		Offence {
+			kind,
 			round_index,
 			session_index,
 			self_bond,
@@ -94,3 +250,38 @@ impl<
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn majority_threshold_is_the_simple_majority() {
+		assert_eq!(MajorityThreshold::Majority.compute(0), 1);
+		assert_eq!(MajorityThreshold::Majority.compute(1), 1);
+		assert_eq!(MajorityThreshold::Majority.compute(4), 3);
+		assert_eq!(MajorityThreshold::Majority.compute(5), 3);
+	}
+
+	#[test]
+	fn proportion_threshold_is_rounded_up() {
+		let two_thirds = MajorityThreshold::Proportion(Perbill::from_rational(2u32, 3u32));
+		assert_eq!(two_thirds.compute(3), 2);
+		assert_eq!(two_thirds.compute(10), 7);
+		assert_eq!(MajorityThreshold::Proportion(Perbill::one()).compute(7), 7);
+	}
+
+	#[test]
+	fn absolute_threshold_is_capped_at_the_count() {
+		assert_eq!(MajorityThreshold::Absolute(8).compute(10), 8);
+		assert_eq!(MajorityThreshold::Absolute(12).compute(10), 10);
+	}
+
+	#[test]
+	fn threshold_is_never_below_the_simple_majority() {
+		assert_eq!(MajorityThreshold::Proportion(Perbill::zero()).compute(10), 6);
+		assert_eq!(MajorityThreshold::Proportion(Perbill::from_percent(30)).compute(10), 6);
+		assert_eq!(MajorityThreshold::Absolute(0).compute(0), 1);
+		assert_eq!(MajorityThreshold::Absolute(2).compute(10), 6);
+	}
+}
@@ -1,7 +1,8 @@
-use crate::{Offence, RoundIndex, TierType, MAX_AUTHORITIES};
+use crate::{MajorityThreshold, Offence, OffenceKind, RoundIndex, TierType, MAX_AUTHORITIES};
 use frame_support::{pallet_prelude::ConstU32, BoundedBTreeSet};
 
-use sp_runtime::{DispatchError, Perbill};
+use sp_runtime::{DispatchError, DispatchResult, Perbill};
+use sp_staking::{offence::OffenceDetails, SessionIndex};
 use sp_std::vec::Vec;
 
 /// The trait used for authority management. (ex: Relayers)
@@ -14,6 +15,37 @@ pub trait Authorities<AccountId> {
 
 	/// Get the majority number of the set.
 	fn majority() -> u32;
+
+	/// Get the number of authorities among the given accounts. The votes of accounts that are
+	/// no longer authorities, like excluded relayers, are not counted towards a threshold.
+	fn count_authorities<'a>(who: impl IntoIterator<Item = &'a AccountId>) -> u32
+	where
+		AccountId: 'a,
+	{
+		who.into_iter().filter(|who| Self::is_authority(who)).count() as u32
+	}
+
+	/// Get the number of items in the cached set of the given round. Returns `None` if the set of
+	/// the round is not cached.
+	fn cached_count(_round: RoundIndex) -> Option<usize> {
+		None
+	}
+
+	/// Get the number of votes required to reach the given `threshold` with the current set. It is
+	/// never below the simple majority of the set.
+	fn threshold(threshold: MajorityThreshold) -> u32 {
+		threshold.compute(Self::count() as u32)
+	}
+
+	/// Get the number of votes required to reach the given `threshold` with the cached set of the
+	/// given round. Falls back to the current set if the round is not cached.
+	fn threshold_at(threshold: MajorityThreshold, round: RoundIndex) -> u32 {
+		threshold.compute(Self::cached_count(round).unwrap_or_else(Self::count) as u32)
+	}
+
+	/// Notes that a poll has reached the majority with the given `voters`. The voters are
+	/// recorded as voted on time, and the rest of the set as missed the majority.
+	fn note_majority_reached(_voters: &[AccountId]) {}
 }
 
 /// The trait used for `pallet_relay_manager`
@@ -55,32 +87,41 @@ pub trait RelayManager<AccountId> {
 
 	/// Apply the delayed relayer set requests. Replaces the entire bonded storage values from the old to new.
 	fn handle_delayed_relayer_sets(now: RoundIndex);
+
+	/// Get the performance score of the relayer bonded to the given `controller` for the given
+	/// `round`. Returns `None` if no score has been recorded.
+	fn relayer_score(controller: &AccountId, round: RoundIndex) -> Option<Perbill>;
 }
 
 /// The trait used for `pallet_bfc_offences`
 pub trait OffenceHandler<AccountId, Balance> {
 	/// Try to handle the given offence of a specific validator.
 	/// This method checks whether the validator exceeds the maximum offence count. If it exceeds
-	/// the offence will be handled.
+	/// the offence will be handled. Returns whether the validator has been slashed, the slashed
+	/// self-bond amount and the slashed amount of each nominator.
 	fn try_handle_offence(
 		who: &AccountId,
 		stash: &AccountId,
 		tier: TierType,
 		offence: Offence<Balance>,
-	) -> (bool, Balance);
+		nominators: Vec<(AccountId, Balance)>,
+	) -> (bool, Balance, Vec<(AccountId, Balance)>);
 
-	/// Handles the given offence of a specific validator. The validator's reserved self-bond will
-	/// be slashed by the given slash fraction. If not, the validator's offence count will be
+	/// Handles the given offence of a specific validator. The validator's reserved self-bond and
+	/// the given nominator exposures will be slashed by the given slash fraction. The slash will be
+	/// deferred if a slash defer duration is set. If not, the validator's offence count will be
 	/// incremented.
 	fn handle_offence(
 		who: &AccountId,
 		stash: &AccountId,
 		tier: TierType,
 		offence: Offence<Balance>,
-	) -> (bool, Balance);
+		nominators: Vec<(AccountId, Balance)>,
+	) -> (bool, Balance, Vec<(AccountId, Balance)>);
 
 	/// This method first check whether the slash mechanism is activated. If it is activated the
-	/// target validator's self-bond will be slashed.
+	/// target validator's self-bond will be slashed. Returns the amount actually slashed, which is
+	/// lower than the slash fraction of the bond if the reserved balance falls short.
 	fn try_slash(
 		who: &AccountId,
 		stash: &AccountId,
@@ -88,10 +129,107 @@ pub trait OffenceHandler<AccountId, Balance> {
 		bond: Balance,
 	) -> Balance;
 
+	/// This method first check whether the nominator slash mechanism is activated. If it is
+	/// activated each nominator's reserved balance will be slashed pro rata to its exposure.
+	/// Returns the slashed amount of each nominator.
+	fn try_slash_nominators(
+		slash_fraction: Perbill,
+		nominators: Vec<(AccountId, Balance)>,
+	) -> Vec<(AccountId, Balance)>;
+
+	/// Applies the deferred slashes scheduled for the given round. This method will be requested
+	/// at every new round. The self-bond exposure is bounded by `current_bond`, the self-bond of
+	/// the offender at the moment, and the slashes of offenders that already left the candidate
+	/// pool (`None`) are skipped. Each nominator exposure is bounded by `current_nomination`, the
+	/// nomination (of the nominator to the offender) that still exists at the moment, so that
+	/// revoked or decreased nominations never slash unrelated reserved balances. Returns the
+	/// slashed self-bond amount and the slashed amount of each nominator for each offender.
+	fn apply_unapplied_slashes<B, F>(
+		now: RoundIndex,
+		current_bond: B,
+		current_nomination: F,
+	) -> Vec<(AccountId, Balance, Vec<(AccountId, Balance)>)>
+	where
+		B: Fn(&AccountId) -> Option<Balance>,
+		F: Fn(&AccountId, &AccountId) -> Balance;
+
+	/// Returns whether a deferred slash of the given validator is waiting to be applied.
+	fn has_unapplied_slash(who: &AccountId) -> bool;
+
 	/// This method will be requested at every new session. It will check every offences stored in
-	/// the system, and will remove the offence if the latest session exceeds the expiration.
+	/// the system, and will remove the offences of each kind if the latest session of that kind
+	/// exceeds its expiration.
 	fn refresh_offences(round_index: RoundIndex);
 
-	/// Verifies whether the given count has exceeded the maximum offence count.
-	fn is_offence_count_exceeds(count: u32, tier: TierType) -> bool;
+	/// Verifies whether the given count has exceeded the maximum offence count of the given kind.
+	fn is_offence_count_exceeds(kind: OffenceKind, count: u32, tier: TierType) -> bool;
+}
+
+/// The trait used to handle reported offences together with their kind. (ex: Validator offences)
+pub trait OnOffenceKindHandler<Reporter, Offender, Res> {
+	/// Handles the offence of the given kind committed by the given offenders in the given
+	/// session. Each offender is slashed by the fraction at the same index.
+	fn on_offence(
+		kind: OffenceKind,
+		offenders: &[OffenceDetails<Reporter, Offender>],
+		slash_fraction: &[Perbill],
+		slash_session: SessionIndex,
+	) -> Res;
+}
+
+/// The trait used to manage the nominations of `pallet_bfc_staking` on behalf of other pallets
+/// (ex: Liquid staking pools)
+pub trait NominationManager<AccountId, Balance> {
+	/// Returns the current round index.
+	fn current_round() -> RoundIndex;
+
+	/// Returns the minimum amount of a single nomination.
+	fn min_nomination() -> Balance;
+
+	/// Returns the minimum total stake of a nominator.
+	fn min_nominator_stake() -> Balance;
+
+	/// Returns the maximum number of nominations per nominator.
+	fn max_nominations_per_nominator() -> u32;
+
+	/// Returns the number of rounds that a nomination decrease is delayed by.
+	fn nomination_bond_less_delay() -> RoundIndex;
+
+	/// Returns the selected candidates of the current round and their voting power.
+	fn selected_candidates() -> Vec<(AccountId, Balance)>;
+
+	/// Returns every nomination of the given nominator and its bonded amount.
+	fn nominations(nominator: &AccountId) -> Vec<(AccountId, Balance)>;
+
+	/// Returns the total bonded amount of the given nominator. This includes the amounts that
+	/// are scheduled to be unbonded.
+	fn total_nominated(nominator: &AccountId) -> Balance;
+
+	/// Returns the pending decrease/revoke requests of the given nominator. Each request contains
+	/// the candidate, the requested amount and the round that it becomes executable.
+	fn pending_requests(nominator: &AccountId) -> Vec<(AccountId, Balance, RoundIndex)>;
+
+	/// Nominates the given amount to the candidate. Bonds more if the nominator has already
+	/// nominated the candidate.
+	fn nominate(nominator: &AccountId, candidate: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Schedules a request to decrease the nomination of the given candidate. The nomination will
+	/// be revoked if the given amount is equal to the bonded amount. Returns the round that the
+	/// request becomes executable.
+	fn schedule_nomination_decrease(
+		nominator: &AccountId,
+		candidate: &AccountId,
+		amount: Balance,
+	) -> Result<RoundIndex, DispatchError>;
+
+	/// Executes the pending request of the given nomination.
+	fn execute_nomination_request(nominator: &AccountId, candidate: &AccountId) -> DispatchResult;
+
+	/// Pays the future rewards of the given nominator into the payee account instead of
+	/// compounding them.
+	fn set_reward_payee(nominator: &AccountId, payee: AccountId) -> DispatchResult;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	/// Joins a new candidate and selects it for the current round. Returns the candidate account.
+	fn set_benchmark(index: u32) -> Result<AccountId, DispatchError>;
 }
@@ -1,6 +1,6 @@
 use crate::BoundedBitcoinAddress;
 
-use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::{prelude::string::String, TypeInfo};
 use sp_core::{RuntimeDebug, H256};
 
@@ -47,3 +47,67 @@ pub enum SelectionStrategy {
 	Bnb,
 	Knapsack,
 }
+
+#[derive(
+	Decode,
+	Encode,
+	DecodeWithMemTracking,
+	TypeInfo,
+	MaxEncodedLen,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+)]
+/// The order in which outbound requests are included in a batch.
+pub enum OutboundPriority {
+	/// The oldest requests first.
+	Age,
+	/// The largest requests first. Requests with the same amount are ordered by age.
+	Amount,
+}
+
+#[derive(
+	Decode,
+	Encode,
+	DecodeWithMemTracking,
+	TypeInfo,
+	MaxEncodedLen,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+)]
+/// The batching policy of outbound requests.
+/// A batch is built once the pool reaches `min_batch_size` requests
+/// or the oldest request has waited for `max_wait_blocks` or `max_age_blocks` blocks.
+pub struct BatchPolicy {
+	/// The minimum number of pending requests to build a batch.
+	pub min_batch_size: u32,
+	/// The maximum number of blocks a request waits before a batch is built anyway.
+	/// If zero, requests wait until the pool reaches `min_batch_size`.
+	pub max_wait_blocks: u32,
+	/// The maximum number of outputs in a PSBT. (excluding the change output)
+	/// Requests that do not fit roll into the next batch.
+	pub max_outputs: u32,
+	/// The order in which requests are included in a batch.
+	pub priority: OutboundPriority,
+	/// The number of blocks after which a request is included ahead of the others regardless
+	/// of the priority, so that small requests are not starved under `Amount` priority.
+	/// If zero, requests are only ordered by the priority.
+	pub max_age_blocks: u32,
+}
+
+impl Default for BatchPolicy {
+	fn default() -> Self {
+		Self {
+			min_batch_size: 1,
+			max_wait_blocks: 0,
+			max_outputs: 100,
+			priority: OutboundPriority::Age,
+			max_age_blocks: 0,
+		}
+	}
+}
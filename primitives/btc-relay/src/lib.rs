@@ -1,6 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod blaze;
+pub mod light_client;
 pub mod traits;
 pub mod utils;
 
@@ -35,6 +36,15 @@ pub const PUBLIC_KEY_LENGTH: u32 = 33;
 /// non-standard and will be rejected by Bitcoin nodes.
 pub const DUST_LIMIT: u64 = 546;
 
+/// The default fee rate (sat/vB) used to calculate the dust threshold of an output.
+/// Equals to the default `-dustrelayfee` of Bitcoin Core (3000 sat/kvB).
+pub const DUST_RELAY_FEE_RATE: u64 = 3;
+
+/// The unspendable internal key for Taproot vault descriptors. This is the NUMS point `H` suggested by
+/// BIP341 (with an even y coordinate), so that the vault can only be spent through the script path.
+pub const TAPROOT_UNSPENDABLE_KEY: &str =
+	"0250929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0";
+
 /// The Bitcoin address type (length bounded).
 pub type BoundedBitcoinAddress = BoundedVec<u8, ConstU32<ADDRESS_MAX_LENGTH>>;
 
@@ -147,6 +157,28 @@ pub enum AddressState {
 	Generated(BoundedBitcoinAddress),
 }
 
+#[derive(
+	Eq,
+	PartialEq,
+	Copy,
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Default,
+)]
+/// The output type of the multi-sig vault descriptors.
+pub enum DescriptorType {
+	/// P2WSH with a `sortedmulti` witness script.
+	#[default]
+	Wsh,
+	/// P2TR with an unspendable internal key and a single `multi_a` script leaf.
+	Tr,
+}
+
 #[derive(
 	Eq,
 	PartialEq,
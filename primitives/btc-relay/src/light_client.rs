@@ -0,0 +1,495 @@
+use miniscript::bitcoin::{
+	consensus::deserialize,
+	hashes::{sha256d, Hash},
+	Network, Script, Transaction,
+};
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, H256, U256};
+use sp_std::vec::Vec;
+
+/// The byte size of a serialized Bitcoin block header.
+pub const BLOCK_HEADER_SIZE: usize = 80;
+
+/// The number of blocks between difficulty adjustments.
+pub const DIFFICULTY_ADJUSTMENT_INTERVAL: u32 = 2016;
+
+/// The expected time between blocks in seconds.
+pub const TARGET_SPACING: u32 = 10 * 60;
+
+/// The expected time of a difficulty adjustment interval in seconds.
+pub const TARGET_TIMESPAN: u32 = DIFFICULTY_ADJUSTMENT_INTERVAL * TARGET_SPACING;
+
+/// A serialized Bitcoin block header.
+pub type RawBlockHeader = [u8; BLOCK_HEADER_SIZE];
+
+/// Double SHA256 the given bytes. The result is in the internal byte order.
+fn hash256(bytes: &[u8]) -> [u8; 32] {
+	sha256d::Hash::hash(bytes).to_byte_array()
+}
+
+/// Reverse the byte order of the given hash. (internal <-> display order)
+fn reversed(hash: &[u8; 32]) -> [u8; 32] {
+	let mut hash = *hash;
+	hash.reverse();
+	hash
+}
+
+#[derive(
+	Eq,
+	PartialEq,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+/// A Bitcoin block header. Hashes are kept in the display (RPC) byte order.
+pub struct BlockHeader {
+	/// The block version.
+	pub version: i32,
+	/// The hash of the previous block.
+	pub prev_block_hash: H256,
+	/// The merkle root of the transactions.
+	pub merkle_root: H256,
+	/// The block timestamp.
+	pub time: u32,
+	/// The target in compact form.
+	pub bits: u32,
+	/// The nonce.
+	pub nonce: u32,
+}
+
+impl BlockHeader {
+	/// Decode a serialized block header.
+	pub fn from_bytes(raw: &RawBlockHeader) -> Self {
+		let read_u32 =
+			|at: usize| u32::from_le_bytes([raw[at], raw[at + 1], raw[at + 2], raw[at + 3]]);
+		let read_hash = |at: usize| {
+			let mut hash = [0u8; 32];
+			hash.copy_from_slice(&raw[at..at + 32]);
+			H256::from(reversed(&hash))
+		};
+		Self {
+			version: read_u32(0) as i32,
+			prev_block_hash: read_hash(4),
+			merkle_root: read_hash(36),
+			time: read_u32(68),
+			bits: read_u32(72),
+			nonce: read_u32(76),
+		}
+	}
+
+	/// Serialize the block header.
+	pub fn to_bytes(&self) -> RawBlockHeader {
+		let mut raw = [0u8; BLOCK_HEADER_SIZE];
+		raw[0..4].copy_from_slice(&self.version.to_le_bytes());
+		raw[4..36].copy_from_slice(&reversed(&self.prev_block_hash.0));
+		raw[36..68].copy_from_slice(&reversed(&self.merkle_root.0));
+		raw[68..72].copy_from_slice(&self.time.to_le_bytes());
+		raw[72..76].copy_from_slice(&self.bits.to_le_bytes());
+		raw[76..80].copy_from_slice(&self.nonce.to_le_bytes());
+		raw
+	}
+
+	/// The block hash.
+	pub fn hash(&self) -> H256 {
+		H256::from(reversed(&hash256(&self.to_bytes())))
+	}
+
+	/// Check if the block hash satisfies the given target.
+	pub fn check_pow(&self, target: U256) -> bool {
+		U256::from_big_endian(self.hash().as_bytes()) <= target
+	}
+}
+
+#[derive(Eq, PartialEq, Clone, Encode, Decode, DecodeWithMemTracking, RuntimeDebug, TypeInfo)]
+/// A merkle proof of a transaction included in a block.
+pub struct MerkleProof {
+	/// The sibling hashes from the leaf to the root. (display byte order)
+	pub siblings: Vec<H256>,
+	/// The position of the transaction in the block.
+	pub index: u32,
+	/// The number of transactions in the block.
+	pub tx_count: u32,
+}
+
+impl MerkleProof {
+	/// Compute the merkle root from the given serialized transaction (without witness data).
+	/// (display byte order)
+	/// Returns `None` if the proof is malformed or if the transaction is 64 bytes long, since it
+	/// can't be told apart from an inner node of the tree. (CVE-2017-12842)
+	pub fn compute_root(&self, raw_tx: &[u8]) -> Option<H256> {
+		if raw_tx.len() == 64 {
+			return None;
+		}
+		self.compute_root_from_txid(tx_hash(raw_tx))
+	}
+
+	/// Compute the merkle root from the given txid. (display byte order)
+	/// Returns `None` if the proof does not match the shape of a tree of `tx_count` transactions.
+	fn compute_root_from_txid(&self, txid: H256) -> Option<H256> {
+		// a tree of `tx_count` transactions has a depth of ceil(log2(tx_count))
+		let depth = self.tx_count.checked_next_power_of_two()?.trailing_zeros() as usize;
+		if self.tx_count == 0 || self.index >= self.tx_count || self.siblings.len() != depth {
+			return None;
+		}
+
+		let mut node = reversed(&txid.0);
+		// the number of nodes in the current level of the tree
+		let mut width = self.tx_count;
+		for (depth, sibling) in self.siblings.iter().enumerate() {
+			let position = self.index >> depth;
+			let sibling = reversed(&sibling.0);
+			let mut concat = [0u8; 64];
+			if position & 1 == 1 {
+				// a right node equal to its sibling is a duplicated node, not a transaction
+				if sibling == node {
+					return None;
+				}
+				concat[..32].copy_from_slice(&sibling);
+				concat[32..].copy_from_slice(&node);
+			} else {
+				// only the last node of an odd level is paired with itself
+				if (position == width - 1) != (sibling == node) {
+					return None;
+				}
+				concat[..32].copy_from_slice(&node);
+				concat[32..].copy_from_slice(&sibling);
+			}
+			node = hash256(&concat);
+			width = width.div_ceil(2);
+		}
+		Some(H256::from(reversed(&node)))
+	}
+}
+
+/// The txid of the given serialized transaction (without witness data). (display byte order)
+pub fn tx_hash(raw_tx: &[u8]) -> H256 {
+	H256::from(reversed(&hash256(raw_tx)))
+}
+
+/// Check that the given serialized transaction (without witness data) has the given txid and pays
+/// `amount` sats to `script_pubkey` at output `vout`. (txid in display byte order)
+pub fn verify_tx_output(
+	raw_tx: &[u8],
+	txid: H256,
+	vout: u32,
+	amount: u64,
+	script_pubkey: &Script,
+) -> bool {
+	if tx_hash(raw_tx) != txid {
+		return false;
+	}
+	let tx = match deserialize::<Transaction>(raw_tx) {
+		Ok(tx) => tx,
+		Err(_) => return false,
+	};
+	tx.output.get(vout as usize).is_some_and(|output| {
+		output.value.to_sat() == amount && output.script_pubkey.as_script() == script_pubkey
+	})
+}
+
+/// The consensus parameters of a Bitcoin network for header verification.
+#[derive(Clone, Copy, RuntimeDebug)]
+pub struct ChainParams {
+	/// The easiest allowed target in compact form.
+	pub pow_limit_bits: u32,
+	/// Whether blocks may use the easiest target after 20 minutes without a block.
+	pub allow_min_difficulty_blocks: bool,
+	/// Whether the difficulty is never adjusted.
+	pub no_retargeting: bool,
+}
+
+impl ChainParams {
+	/// Get the consensus parameters of the given network.
+	pub fn from_network(network: Network) -> Self {
+		match network {
+			Network::Testnet => Self {
+				pow_limit_bits: 0x1d00ffff,
+				allow_min_difficulty_blocks: true,
+				no_retargeting: false,
+			},
+			Network::Signet => Self {
+				pow_limit_bits: 0x1e0377ae,
+				allow_min_difficulty_blocks: false,
+				no_retargeting: false,
+			},
+			Network::Regtest => Self {
+				pow_limit_bits: 0x207fffff,
+				allow_min_difficulty_blocks: true,
+				no_retargeting: true,
+			},
+			_ => Self {
+				pow_limit_bits: 0x1d00ffff,
+				allow_min_difficulty_blocks: false,
+				no_retargeting: false,
+			},
+		}
+	}
+
+	/// The easiest allowed target.
+	pub fn pow_limit(&self) -> U256 {
+		target_from_compact(self.pow_limit_bits).unwrap_or_default()
+	}
+}
+
+/// Decode a compact target. Returns `None` if the target is negative, zero or overflows.
+pub fn target_from_compact(bits: u32) -> Option<U256> {
+	let exponent = bits >> 24;
+	let mantissa = bits & 0x007fffff;
+	// negative targets are invalid
+	if mantissa != 0 && bits & 0x00800000 != 0 {
+		return None;
+	}
+	let target = if exponent <= 3 {
+		U256::from(mantissa >> (8 * (3 - exponent)))
+	} else {
+		// overflow check
+		if mantissa != 0
+			&& (exponent > 34
+				|| (mantissa > 0xff && exponent > 33)
+				|| (mantissa > 0xffff && exponent > 32))
+		{
+			return None;
+		}
+		U256::from(mantissa) << (8 * (exponent - 3))
+	};
+	if target.is_zero() {
+		None
+	} else {
+		Some(target)
+	}
+}
+
+/// Encode the given target to the compact form.
+pub fn compact_from_target(target: U256) -> u32 {
+	let mut size = (target.bits() as u32 + 7) / 8;
+	let mut compact = if size <= 3 {
+		(target.low_u64() << (8 * (3 - size))) as u32
+	} else {
+		(target >> (8 * (size - 3))).low_u32()
+	};
+	// the sign bit must not be set
+	if compact & 0x00800000 != 0 {
+		compact >>= 8;
+		size += 1;
+	}
+	compact | (size << 24)
+}
+
+/// The expected number of hashes required to produce a block of the given target.
+pub fn block_work(target: U256) -> U256 {
+	// 2**256 / (target + 1) == ~target / (target + 1) + 1
+	(!target / target.saturating_add(U256::one())).saturating_add(U256::one())
+}
+
+/// Calculate the compact target of the next difficulty adjustment interval.
+/// - `last_bits`: the compact target of the last block of the interval
+/// - `first_time`: the timestamp of the first block of the interval
+/// - `last_time`: the timestamp of the last block of the interval
+pub fn next_work_required(
+	last_bits: u32,
+	first_time: u32,
+	last_time: u32,
+	params: &ChainParams,
+) -> u32 {
+	if params.no_retargeting {
+		return last_bits;
+	}
+	let timespan = (last_time as i64 - first_time as i64)
+		.clamp((TARGET_TIMESPAN / 4) as i64, (TARGET_TIMESPAN * 4) as i64) as u64;
+
+	let pow_limit = params.pow_limit();
+	let target = target_from_compact(last_bits).unwrap_or(pow_limit);
+	let target = target
+		.checked_mul(U256::from(timespan))
+		.map(|x| x / U256::from(TARGET_TIMESPAN))
+		.unwrap_or(pow_limit);
+	compact_from_target(target.min(pow_limit))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use core::str::FromStr;
+
+	/// The transactions of a regtest block.
+	const TXIDS: [&str; 5] = [
+		"9a538906e6466ebd2617d321f71bc94e56056ce213d366773699e28158e00614",
+		"705f425bfcb81942ec8db27abc2485c1322177233dac87d78445c704dccf129c",
+		"babb95b7a797b2e17dbc71c7b49dce0c15687d7704c03a4394fdeb40eaadc31c",
+		"45faf3a124b1edcf3e4f3599d2084217fb0a0288e8772602182c7c126ca042c9",
+		"a7891ef9e90ee411ae78dfcc8d2c8d6caa07678f777644d3670e4941bf634e21",
+	];
+
+	/// The merkle proof of the third transaction.
+	const PROOF_2: [&str; 3] = [
+		"45faf3a124b1edcf3e4f3599d2084217fb0a0288e8772602182c7c126ca042c9",
+		"55766b905b9b12c5b1ea831fa5ffb90e1cdf3941230d52c7bce2eb38bc83be4b",
+		"7fd4028dabb39d1c4b55f0414ab9ad1cc74c4d63bb23f3bf79f969212a334a1d",
+	];
+
+	/// The merkle proof of the fifth transaction, which is paired with itself.
+	const PROOF_4: [&str; 3] = [
+		"a7891ef9e90ee411ae78dfcc8d2c8d6caa07678f777644d3670e4941bf634e21",
+		"fbb31c45f9af62bca012637384e54b532a0239026e69f8839ec03eb43659867a",
+		"8ef7ba67071e963505cbcf95ee192c594c61f16a522fc74d2b5a11a001572fe3",
+	];
+
+	/// The merkle root of the block.
+	const ROOT: &str = "48b7979f4fc409cc282b911c6c23eee3aea685f70fcc91bcc3f728d6493811f4";
+
+	fn hash(hex_str: &str) -> H256 {
+		H256::from_str(hex_str).unwrap()
+	}
+
+	fn merkle_proof(siblings: &[&str], index: u32, tx_count: u32) -> MerkleProof {
+		MerkleProof { siblings: siblings.iter().map(|s| hash(s)).collect(), index, tx_count }
+	}
+
+	#[test]
+	fn merkle_proof_computes_the_root() {
+		let root = Some(hash(ROOT));
+		assert_eq!(merkle_proof(&PROOF_2, 2, 5).compute_root_from_txid(hash(TXIDS[2])), root);
+		assert_eq!(merkle_proof(&PROOF_4, 4, 5).compute_root_from_txid(hash(TXIDS[4])), root);
+
+		// a block of a single transaction has its txid as the merkle root
+		let proof = merkle_proof(&[], 0, 1);
+		assert_eq!(proof.compute_root_from_txid(hash(TXIDS[0])), Some(hash(TXIDS[0])));
+	}
+
+	#[test]
+	fn merkle_proof_is_bound_to_the_tx_count() {
+		let txid = hash(TXIDS[4]);
+		// the depth must be ceil(log2(tx_count))
+		for tx_count in [0, 1, 2, 4, 9, 16] {
+			assert_eq!(merkle_proof(&PROOF_4, 4, tx_count).compute_root_from_txid(txid), None);
+		}
+		// only the last node of an odd level is paired with itself
+		for tx_count in [6, 7, 8] {
+			assert_eq!(merkle_proof(&PROOF_4, 4, tx_count).compute_root_from_txid(txid), None);
+		}
+		// the index must be in the block
+		assert_eq!(merkle_proof(&PROOF_4, 8, 5).compute_root_from_txid(txid), None);
+		assert_eq!(merkle_proof(&PROOF_4, u32::MAX, 5).compute_root_from_txid(txid), None);
+	}
+
+	#[test]
+	fn merkle_proof_rejects_duplicated_right_node() {
+		// the fifth transaction is paired with itself, which must not be provable at index 5
+		let mut siblings = vec![TXIDS[4]];
+		siblings.extend(&PROOF_4[1..]);
+		let proof = merkle_proof(&siblings, 5, 6);
+		assert_eq!(proof.compute_root_from_txid(hash(TXIDS[4])), None);
+		let proof = merkle_proof(&siblings, 5, 5);
+		assert_eq!(proof.compute_root_from_txid(hash(TXIDS[4])), None);
+	}
+
+	#[test]
+	fn merkle_proof_rejects_64_byte_transactions() {
+		// the inner node of a block of two transactions
+		let mut inner_node = reversed(&hash(TXIDS[0]).0).to_vec();
+		inner_node.extend(reversed(&hash(TXIDS[1]).0));
+		let root = merkle_proof(&TXIDS[1..2], 0, 2).compute_root_from_txid(hash(TXIDS[0]));
+
+		// the inner node would be proven as the only transaction of the block
+		let proof = merkle_proof(&[], 0, 1);
+		assert_eq!(proof.compute_root_from_txid(tx_hash(&inner_node)), root);
+		assert_eq!(proof.compute_root(&inner_node), None);
+
+		inner_node.push(0);
+		assert_eq!(proof.compute_root(&inner_node), Some(tx_hash(&inner_node)));
+	}
+
+	#[test]
+	fn compact_target_round_trips() {
+		// canonical targets, including the mainnet genesis and retarget targets
+		for bits in [0x1d00ffffu32, 0x1b0404cb, 0x1c05a3f4, 0x1d00d86a, 0x17053894, 0x207fffff] {
+			assert_eq!(compact_from_target(target_from_compact(bits).unwrap()), bits);
+		}
+
+		// Bitcoin Core `arith_uint256` vectors
+		let vectors: [(u32, U256, u32); 7] = [
+			(0x01123456, U256::from(0x12u64), 0x01120000),
+			(0x02123456, U256::from(0x1234u64), 0x02123400),
+			(0x03123456, U256::from(0x123456u64), 0x03123456),
+			(0x04123456, U256::from(0x12345600u64), 0x04123456),
+			(0x05009234, U256::from(0x92340000u64), 0x05009234),
+			(0x20123456, U256::from(0x123456u64) << 232u32, 0x20123456),
+			// a target that would set the sign bit takes a larger exponent
+			(0x2100ffff, U256::from(0xffffu64) << 240u32, 0x2100ffff),
+		];
+		for (bits, target, compact) in vectors {
+			assert_eq!(target_from_compact(bits), Some(target));
+			assert_eq!(compact_from_target(target), compact);
+		}
+		assert_eq!(compact_from_target(U256::from(0x80u64)), 0x02008000);
+
+		// a non-canonical encoding decodes to the same target
+		assert_eq!(target_from_compact(0x22000001), target_from_compact(0x20010000));
+		assert_eq!(compact_from_target(target_from_compact(0x22000001).unwrap()), 0x20010000);
+	}
+
+	#[test]
+	fn invalid_compact_targets_are_rejected() {
+		// negative targets
+		for bits in [0x04923456, 0x01fedcba, 0x01803456] {
+			assert_eq!(target_from_compact(bits), None);
+		}
+		// zero targets
+		for bits in [0x00000000, 0x00123456, 0x01003456, 0x02000056, 0x04000000] {
+			assert_eq!(target_from_compact(bits), None);
+		}
+		// overflowing targets
+		for bits in [0xff123456, 0x23000001, 0x22000100, 0x21010000] {
+			assert_eq!(target_from_compact(bits), None);
+		}
+	}
+
+	#[test]
+	fn next_work_required_follows_mainnet_retargets() {
+		let params = ChainParams::from_network(Network::Bitcoin);
+		// Bitcoin Core `get_next_work`: the retarget at block 32,256
+		assert_eq!(next_work_required(0x1d00ffff, 1261130161, 1262152739, &params), 0x1d00d86a);
+		// Bitcoin Core `get_next_work_pow_limit`: the retarget at block 2,016
+		assert_eq!(next_work_required(0x1d00ffff, 1231006505, 1233061996, &params), 0x1d00ffff);
+		// Bitcoin Core `get_next_work_lower_limit_actual`: the retarget at block 68,544
+		assert_eq!(next_work_required(0x1c05a3f4, 1279008237, 1279297671, &params), 0x1c0168fd);
+		// Bitcoin Core `get_next_work_upper_limit_actual`: the retarget at block 46,368
+		assert_eq!(next_work_required(0x1c387f6f, 1263163443, 1269211443, &params), 0x1d00e1fd);
+
+		// an on-time interval keeps the target
+		assert_eq!(next_work_required(0x1b0404cb, 0, TARGET_TIMESPAN, &params), 0x1b0404cb);
+		assert_eq!(next_work_required(0x1b0404cb, 0, TARGET_TIMESPAN * 2, &params), 0x1b080996);
+		assert_eq!(next_work_required(0x1b0404cb, 0, TARGET_TIMESPAN / 2, &params), 0x1b020265);
+	}
+
+	#[test]
+	fn next_work_required_is_clamped() {
+		let params = ChainParams::from_network(Network::Bitcoin);
+		// the timespan is clamped to a quarter of the target timespan
+		let fastest = next_work_required(0x1c05a3f4, 0, TARGET_TIMESPAN / 4, &params);
+		assert_eq!(fastest, 0x1c0168fd);
+		assert_eq!(next_work_required(0x1c05a3f4, 0, 1, &params), fastest);
+		// timestamps may go backwards
+		assert_eq!(next_work_required(0x1c05a3f4, 1_000, 0, &params), fastest);
+
+		// the timespan is clamped to four times the target timespan
+		let slowest = next_work_required(0x1c387f6f, 0, TARGET_TIMESPAN * 4, &params);
+		assert_eq!(slowest, 0x1d00e1fd);
+		assert_eq!(next_work_required(0x1c387f6f, 0, u32::MAX, &params), slowest);
+
+		// the target never exceeds the pow limit
+		assert_eq!(next_work_required(0x1d00ffff, 0, TARGET_TIMESPAN * 4, &params), 0x1d00ffff);
+		// an invalid target restarts from the pow limit
+		assert_eq!(next_work_required(0x04923456, 0, TARGET_TIMESPAN, &params), 0x1d00ffff);
+
+		// regtest never retargets
+		let params = ChainParams::from_network(Network::Regtest);
+		assert_eq!(next_work_required(0x207fffff, 0, 1, &params), 0x207fffff);
+	}
+}
@@ -8,7 +8,8 @@ use sp_runtime::{transaction_validity::TransactionValidityError, DispatchError};
 use sp_std::vec::Vec;
 
 use crate::{
-	blaze::{ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
+	blaze::{BatchPolicy, ScoredUtxo, SelectionStrategy, UtxoInfoWithSize},
+	light_client::MerkleProof,
 	BoundedBitcoinAddress, MigrationSequence, Psbt, UnboundedBytes,
 };
 
@@ -86,9 +87,13 @@ pub trait BlazeManager<T: frame_system::Config> {
 	/// Check if BLAZE is activated.
 	fn is_activated() -> bool;
 
-	/// Get all available utxos.
+	/// Get all available utxos. The utxos that are reserved for a recovery sweep are excluded.
 	fn get_utxos() -> Vec<UtxoInfoWithSize>;
 
+	/// Get the available utxos of user vaults whose recovery path matures soon, ordered by the
+	/// maturity. These must be swept into the system vault before the user can spend them alone.
+	fn get_sweep_utxos() -> Vec<UtxoInfoWithSize>;
+
 	/// Clear all utxos. Except the ones that are used.
 	fn clear_utxos();
 
@@ -104,6 +109,16 @@ pub trait BlazeManager<T: frame_system::Config> {
 	/// Read the outbound pool.
 	fn get_outbound_pool() -> Vec<UnboundedBytes>;
 
+	/// Read the number of messages in the outbound pool without decoding them.
+	fn get_outbound_pool_len() -> usize;
+
+	/// Get the outbound requests to batch at the given block, ordered by priority.
+	/// Returns an empty vector if the batching window is not reached yet.
+	fn get_outbound_batch(n: BlockNumberFor<T>) -> Vec<UnboundedBytes>;
+
+	/// Get the batching policy of outbound requests.
+	fn get_batch_policy() -> BatchPolicy;
+
 	/// Clear the outbound pool.
 	fn clear_outbound_pool(targets: Vec<UnboundedBytes>);
 
@@ -134,4 +149,64 @@ pub trait BlazeManager<T: frame_system::Config> {
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn set_activation(activate: bool) -> Result<(), DispatchError>;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_outbound_pool(pool: Vec<UnboundedBytes>) -> Result<(), DispatchError>;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_utxos(utxos: Vec<UtxoInfoWithSize>) -> Result<(), DispatchError>;
+}
+
+pub trait LightClientManager {
+	/// Get the height of the best known Bitcoin block.
+	fn best_height() -> u32;
+
+	/// Verify that the given serialized transaction (without witness data) is included in the main
+	/// chain block at the given height and that the block has at least the given number of
+	/// confirmations.
+	fn verify_tx_inclusion(
+		raw_tx: &[u8],
+		merkle_proof: &MerkleProof,
+		height: u32,
+		confirmations: u32,
+	) -> Result<(), DispatchError>;
+
+	/// Import a block that only includes the given transaction on top of the best block.
+	/// Returns the height of the imported block.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_benchmark(txid: H256) -> Result<u32, DispatchError>;
+}
+
+impl LightClientManager for () {
+	fn best_height() -> u32 {
+		0
+	}
+
+	fn verify_tx_inclusion(_: &[u8], _: &MerkleProof, _: u32, _: u32) -> Result<(), DispatchError> {
+		Err(DispatchError::Other("Bitcoin light client is not available"))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_benchmark(_: H256) -> Result<u32, DispatchError> {
+		Ok(0)
+	}
+}
+
+/// Handles the reset of the Bitcoin light client to a new checkpoint.
+pub trait OnCheckpointSet {
+	/// Drop the SPV proofs that were verified against the previous chain.
+	/// Returns the number of removed proofs.
+	fn on_checkpoint_set() -> u32;
+}
+
+impl OnCheckpointSet for () {
+	fn on_checkpoint_set() -> u32 {
+		0
+	}
+}
+
+impl<A: OnCheckpointSet, B: OnCheckpointSet> OnCheckpointSet for (A, B) {
+	fn on_checkpoint_set() -> u32 {
+		A::on_checkpoint_set().saturating_add(B::on_checkpoint_set())
+	}
 }
@@ -1,12 +1,47 @@
-use crate::Script;
+use crate::{Descriptor, PublicKey, Script};
 
 use miniscript::bitcoin::{opcodes, script::Instruction, ScriptBuf, TxIn, Weight};
 use sp_std::vec::Vec;
 
+/// The byte size of a Schnorr signature with the default sighash type.
+const SCHNORR_SIGNATURE_SIZE: usize = 64;
+
+/// The byte size of a Taproot control block without any merkle branch.
+const TAPROOT_CONTROL_BASE_SIZE: usize = 33;
+
+/// The byte size of a single node of a Taproot merkle branch.
+const TAPROOT_CONTROL_NODE_SIZE: usize = 32;
+
+/// The assumed vsize to spend a witness program output. (P2WPKH, P2WSH, P2TR)
+/// outpoint(36) + script_sig_len(1) + sequence(4) + witness(107 / 4)
+const WITNESS_OUTPUT_SPEND_SIZE: u64 = 36 + 1 + 4 + 107 / 4;
+
+/// The assumed vsize to spend a legacy output. (P2PKH, P2SH)
+/// outpoint(36) + script_sig_len(1) + sequence(4) + script_sig(107)
+const LEGACY_OUTPUT_SPEND_SIZE: u64 = 36 + 1 + 4 + 107;
+
+/// Get the byte size of the compact size prefix for the given length.
+fn compact_size_len(len: usize) -> usize {
+	match len {
+		0..=0xfc => 1,
+		0xfd..=0xffff => 3,
+		_ => 5,
+	}
+}
+
 /// Parse the witness script for extract m and n for m-of-n multisig.
+/// The multisig may be followed by a timelocked recovery branch (`or_d(multi(..),..)`).
 /// return None if the script is not a valid multisig script.
 fn parse_multisig_script(script: &Script) -> Option<(usize, usize)> {
-	let instructions = script.instructions().collect::<Vec<_>>();
+	let mut instructions = script.instructions().collect::<Vec<_>>();
+
+	// Drop the recovery branch that follows the OP_CHECKMULTISIG opcode
+	if let Some(pos) = instructions
+		.iter()
+		.position(|x| matches!(x, Ok(Instruction::Op(opcodes::all::OP_CHECKMULTISIG))))
+	{
+		instructions.truncate(pos + 1);
+	}
 
 	if instructions.len() < 3 {
 		return None; // Not enough instructions for multisig
@@ -36,6 +71,39 @@ fn parse_multisig_script(script: &Script) -> Option<(usize, usize)> {
 	}
 }
 
+/// Parse the Taproot leaf script for extract m and n for m-of-n `multi_a`.
+/// return None if the script is not a valid `multi_a` script.
+fn parse_multi_a_script(script: &Script) -> Option<(usize, usize)> {
+	let instructions = script.instructions().collect::<Result<Vec<_>, _>>().ok()?;
+
+	// <pk_1> OP_CHECKSIG <pk_2> OP_CHECKSIGADD ... <pk_n> OP_CHECKSIGADD <m> OP_NUMEQUAL
+	if instructions.len() < 4 || instructions.len() % 2 != 0 {
+		return None;
+	}
+	let (keys, threshold) = instructions.split_at(instructions.len() - 2);
+
+	let n = keys.len() / 2;
+	for (i, pair) in keys.chunks(2).enumerate() {
+		let expected_op =
+			if i == 0 { opcodes::all::OP_CHECKSIG } else { opcodes::all::OP_CHECKSIGADD };
+		match pair {
+			[Instruction::PushBytes(pk), Instruction::Op(op)]
+				if pk.len() == 32 && *op == expected_op => {},
+			_ => return None, // Not a valid multi_a script
+		}
+	}
+
+	if threshold[1] != Instruction::Op(opcodes::all::OP_NUMEQUAL) {
+		return None;
+	}
+	let m = usize::try_from(threshold[0].script_num()?).ok()?;
+	if m == 0 || m > n {
+		return None;
+	}
+
+	Some((m, n))
+}
+
 /// Estimate the finalized input vsize from unsigned.
 pub fn estimate_finalized_input_size(
 	witness_script: &ScriptBuf,
@@ -64,3 +132,331 @@ pub fn estimate_finalized_input_size(
 
 	Some(estimated_final_vsize)
 }
+
+/// Estimate the finalized Taproot script path input vsize from unsigned.
+pub fn estimate_finalized_taproot_input_size(
+	leaf_script: &ScriptBuf,
+	control_block_size: usize,
+	txin: Option<&TxIn>,
+) -> Option<u64> {
+	let (m, n) = parse_multi_a_script(leaf_script)?;
+
+	// signatures(64 * m) + length prefixes of every signature slot(1 * n), left empty if unsigned
+	let signatures_size = SCHNORR_SIGNATURE_SIZE * m + n;
+	let script_len = compact_size_len(leaf_script.len()) + leaf_script.len();
+	let control_block_len = compact_size_len(control_block_size) + control_block_size;
+
+	// item_count + signatures + script_len + control_block_len
+	let estimated_witness_size =
+		compact_size_len(n + 2) + signatures_size + script_len + control_block_len;
+
+	let non_witness_data_size = if let Some(txin) = txin {
+		Weight::from_non_witness_data_size(txin.base_size() as u64)
+	} else {
+		Weight::from_non_witness_data_size(41)
+	};
+
+	let estimated_final_vsize = (Weight::from_witness_data_size(estimated_witness_size as u64)
+		+ non_witness_data_size)
+		.to_vbytes_ceil();
+
+	Some(estimated_final_vsize)
+}
+
+/// Estimate the finalized input vsize of the given vault descriptor.
+pub fn estimate_descriptor_input_size(descriptor: &Descriptor<PublicKey>) -> Option<u64> {
+	match descriptor {
+		Descriptor::Wsh(_) => estimate_finalized_input_size(&descriptor.script_code().ok()?, None),
+		Descriptor::Tr(tr) => {
+			// vaults are spent through the multi_a leaf (the other leaf is the recovery path)
+			tr.iter_scripts().find_map(|(depth, leaf)| {
+				let control_block_size =
+					TAPROOT_CONTROL_BASE_SIZE + TAPROOT_CONTROL_NODE_SIZE * depth as usize;
+				estimate_finalized_taproot_input_size(&leaf.encode(), control_block_size, None)
+			})
+		},
+		_ => None,
+	}
+}
+
+/// Get the dust threshold (in satoshis) of an output with the given script pubkey.
+/// An output is dust if its value is lower than the cost to create and spend it at the given dust
+/// relay fee rate (sat/vB). At the default rate, the threshold is 294 sats for P2WPKH, 330 sats for
+/// P2WSH and P2TR, and 546 sats for P2PKH.
+pub fn dust_threshold(script_pubkey: &Script, dust_relay_fee_rate: u64) -> u64 {
+	// unspendable outputs are never dust
+	if script_pubkey.is_op_return() {
+		return 0;
+	}
+	// value(8) + script_pubkey_len + script_pubkey
+	let output_size = (8 + compact_size_len(script_pubkey.len()) + script_pubkey.len()) as u64;
+	let spend_size = if script_pubkey.is_witness_program() {
+		WITNESS_OUTPUT_SPEND_SIZE
+	} else {
+		LEGACY_OUTPUT_SPEND_SIZE
+	};
+	(output_size + spend_size).saturating_mul(dust_relay_fee_rate)
+}
+
+/// Get the timelocked recovery script of the given vault descriptor, if it has one.
+/// For `wsh` descriptors this is the witness script, and for `tr` descriptors the leaf script
+/// that contains `OP_CSV`.
+pub fn descriptor_recovery_script(descriptor: &Descriptor<PublicKey>) -> Option<ScriptBuf> {
+	let has_csv = |script: &Script| {
+		script
+			.instructions()
+			.any(|ins| matches!(ins, Ok(Instruction::Op(opcodes::all::OP_CSV))))
+	};
+	match descriptor {
+		Descriptor::Wsh(wsh) => Some(wsh.inner_script()).filter(|script| has_csv(script)),
+		Descriptor::Tr(tr) => {
+			tr.iter_scripts().map(|(_, leaf)| leaf.encode()).find(|script| has_csv(script))
+		},
+		_ => None,
+	}
+}
+
+/// Get the relative timelock (in blocks) of the recovery path of the given vault descriptor, if it
+/// has one. This is the number pushed right before `OP_CSV` in the recovery script.
+pub fn descriptor_recovery_timelock(descriptor: &Descriptor<PublicKey>) -> Option<u32> {
+	let script = descriptor_recovery_script(descriptor)?;
+	let instructions = script.instructions().collect::<Result<Vec<_>, _>>().ok()?;
+	let pos = instructions
+		.iter()
+		.position(|ins| *ins == Instruction::Op(opcodes::all::OP_CSV))?;
+	let timelock = instructions.get(pos.checked_sub(1)?)?.script_num()?;
+	u32::try_from(timelock).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Secp256k1, SecretKey, DUST_RELAY_FEE_RATE, TAPROOT_UNSPENDABLE_KEY};
+	use core::str::FromStr;
+
+	/// The m-of-n sets of the vault descriptors under test
+	const MULTISIG_SETS: [(usize, usize); 5] = [(1, 1), (2, 3), (3, 5), (7, 10), (11, 15)];
+
+	fn pub_key(seed: u8) -> PublicKey {
+		let secret = SecretKey::from_slice(&[seed; 32]).unwrap();
+		PublicKey::new(secret.public_key(&Secp256k1::new()))
+	}
+
+	fn keys(n: usize) -> String {
+		(1..=n as u8)
+			.map(|seed| pub_key(seed).to_string())
+			.collect::<Vec<_>>()
+			.join(",")
+	}
+
+	fn recovery() -> String {
+		format!("and_v(v:pk({}),older(4320))", pub_key(0xaa))
+	}
+
+	fn descriptor(desc: &str) -> Descriptor<PublicKey> {
+		Descriptor::<PublicKey>::from_str(desc).unwrap()
+	}
+
+	fn first_leaf(desc: &Descriptor<PublicKey>) -> ScriptBuf {
+		match desc {
+			Descriptor::Tr(tr) => tr.iter_scripts().next().unwrap().1.encode(),
+			_ => panic!("not a tr descriptor"),
+		}
+	}
+
+	/// The upper bound of the satisfied input vsize computed by miniscript
+	fn max_satisfied_input_size(desc: &Descriptor<PublicKey>) -> u64 {
+		(Weight::from_non_witness_data_size(41) + desc.max_weight_to_satisfy().unwrap())
+			.to_vbytes_ceil()
+	}
+
+	/// Miniscript assumes a sighash byte on every Schnorr signature and the largest spending
+	/// path, so the estimate may only be lower by a few vbytes.
+	fn assert_close_to_miniscript(desc: &Descriptor<PublicKey>, m: usize) {
+		let estimated = estimate_descriptor_input_size(desc).unwrap();
+		let max = max_satisfied_input_size(desc);
+		assert!(estimated <= max, "{desc}: estimated {estimated} > max {max}");
+		assert!(
+			max - estimated <= (m as u64).div_ceil(4) + 1,
+			"{desc}: estimated {estimated} is far below max {max}"
+		);
+	}
+
+	#[test]
+	fn multi_a_leaf_is_parsed() {
+		for (m, n) in MULTISIG_SETS {
+			let desc =
+				descriptor(&format!("tr({},multi_a({},{}))", TAPROOT_UNSPENDABLE_KEY, m, keys(n)));
+			assert_eq!(parse_multi_a_script(&first_leaf(&desc)), Some((m, n)));
+		}
+	}
+
+	#[test]
+	fn non_multi_a_scripts_are_not_parsed() {
+		let desc = descriptor(&format!("tr({},{})", TAPROOT_UNSPENDABLE_KEY, recovery()));
+		assert_eq!(parse_multi_a_script(&first_leaf(&desc)), None);
+
+		let desc = descriptor(&format!("wsh(multi(2,{}))", keys(3)));
+		assert_eq!(parse_multi_a_script(&desc.script_code().unwrap()), None);
+
+		assert_eq!(parse_multi_a_script(&ScriptBuf::new()), None);
+	}
+
+	#[test]
+	fn taproot_input_size_is_estimated() {
+		let desc = descriptor(&format!("tr({},multi_a(2,{}))", TAPROOT_UNSPENDABLE_KEY, keys(3)));
+		let leaf = first_leaf(&desc);
+
+		// witness: item_count(1) + signatures(64 * 2 + 3) + script(1 + 104) + control_block(1 + 33)
+		// non-witness: outpoint(36) + script_sig_len(1) + sequence(4)
+		assert_eq!(leaf.len(), 104);
+		assert_eq!(
+			estimate_finalized_taproot_input_size(&leaf, TAPROOT_CONTROL_BASE_SIZE, None),
+			Some((271 + 41 * 4_u64).div_ceil(4))
+		);
+
+		// a deeper leaf has a longer control block
+		assert_eq!(
+			estimate_finalized_taproot_input_size(
+				&leaf,
+				TAPROOT_CONTROL_BASE_SIZE + TAPROOT_CONTROL_NODE_SIZE,
+				None
+			),
+			Some((271 + 32 + 41 * 4_u64).div_ceil(4))
+		);
+
+		let desc = descriptor(&format!("tr({},{})", TAPROOT_UNSPENDABLE_KEY, recovery()));
+		assert_eq!(
+			estimate_finalized_taproot_input_size(
+				&first_leaf(&desc),
+				TAPROOT_CONTROL_BASE_SIZE,
+				None
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn taproot_signatures_are_counted_without_a_sighash_byte() {
+		// the leaves only differ in the threshold, so every extra signature adds 64 witness bytes
+		let estimate = |m: usize| {
+			let desc =
+				descriptor(&format!("tr({},multi_a({},{}))", TAPROOT_UNSPENDABLE_KEY, m, keys(4)));
+			let leaf = first_leaf(&desc);
+			(
+				leaf.len(),
+				estimate_finalized_taproot_input_size(&leaf, TAPROOT_CONTROL_BASE_SIZE, None),
+			)
+		};
+		let (len, vsize) = estimate(1);
+		assert_eq!(len, 4 * 34 + 2);
+		for m in 2..=4 {
+			let extra = ((m - 1) * SCHNORR_SIGNATURE_SIZE / 4) as u64;
+			assert_eq!(estimate(m), (len, vsize.map(|vsize| vsize + extra)));
+		}
+	}
+
+	#[test]
+	fn taproot_descriptor_input_size_matches_miniscript() {
+		for (m, n) in MULTISIG_SETS {
+			let multi_a = format!("multi_a({},{})", m, keys(n));
+			assert_close_to_miniscript(
+				&descriptor(&format!("tr({},{})", TAPROOT_UNSPENDABLE_KEY, multi_a)),
+				m,
+			);
+			assert_close_to_miniscript(
+				&descriptor(&format!(
+					"tr({},{{{},{}}})",
+					TAPROOT_UNSPENDABLE_KEY,
+					multi_a,
+					recovery()
+				)),
+				m,
+			);
+		}
+	}
+
+	#[test]
+	fn wsh_descriptor_input_size_matches_miniscript() {
+		for (m, n) in MULTISIG_SETS {
+			let multi = format!("multi({},{})", m, keys(n));
+			assert_close_to_miniscript(&descriptor(&format!("wsh({})", multi)), m);
+			assert_close_to_miniscript(
+				&descriptor(&format!("wsh(or_d({},{}))", multi, recovery())),
+				m,
+			);
+		}
+	}
+
+	#[test]
+	fn recovery_script_is_found() {
+		let multi = format!("multi(2,{})", keys(3));
+		let multi_a = format!("multi_a(2,{})", keys(3));
+		for desc in [
+			format!("wsh(or_d({},{}))", multi, recovery()),
+			format!("tr({},{{{},{}}})", TAPROOT_UNSPENDABLE_KEY, multi_a, recovery()),
+		] {
+			let desc = descriptor(&desc);
+			let script = descriptor_recovery_script(&desc).unwrap();
+			assert!(script
+				.instructions()
+				.any(|ins| matches!(ins, Ok(Instruction::Op(opcodes::all::OP_CSV)))));
+			assert_eq!(descriptor_recovery_timelock(&desc), Some(4320));
+		}
+
+		// the tr recovery script is the recovery leaf, not the multi_a leaf
+		let desc =
+			descriptor(&format!("tr({},{{{},{}}})", TAPROOT_UNSPENDABLE_KEY, multi_a, recovery()));
+		let recovery_leaf = descriptor(&format!("tr({},{})", TAPROOT_UNSPENDABLE_KEY, recovery()));
+		assert_eq!(descriptor_recovery_script(&desc), Some(first_leaf(&recovery_leaf)));
+	}
+
+	#[test]
+	fn vaults_without_recovery_have_no_recovery_script() {
+		for desc in [
+			format!("wsh(multi(2,{}))", keys(3)),
+			format!("wsh(sortedmulti(2,{}))", keys(3)),
+			format!("tr({},multi_a(2,{}))", TAPROOT_UNSPENDABLE_KEY, keys(3)),
+			format!("wpkh({})", pub_key(1)),
+		] {
+			let desc = descriptor(&desc);
+			assert_eq!(descriptor_recovery_script(&desc), None);
+			assert_eq!(descriptor_recovery_timelock(&desc), None);
+		}
+	}
+
+	#[test]
+	fn dust_threshold_matches_bitcoin_core() {
+		let key = pub_key(1);
+		let p2wpkh = descriptor(&format!("wpkh({key})")).script_pubkey();
+		let p2wsh = descriptor(&format!("wsh(multi(2,{}))", keys(3))).script_pubkey();
+		let p2tr = descriptor(&format!("tr({key})")).script_pubkey();
+		let p2pkh = descriptor(&format!("pkh({key})")).script_pubkey();
+		let p2sh = descriptor(&format!("sh(wpkh({key}))")).script_pubkey();
+
+		// the default dust relay fee rate
+		assert_eq!(dust_threshold(&p2wpkh, DUST_RELAY_FEE_RATE), 294);
+		assert_eq!(dust_threshold(&p2wsh, DUST_RELAY_FEE_RATE), 330);
+		assert_eq!(dust_threshold(&p2tr, DUST_RELAY_FEE_RATE), 330);
+		assert_eq!(dust_threshold(&p2pkh, DUST_RELAY_FEE_RATE), 546);
+		assert_eq!(dust_threshold(&p2sh, DUST_RELAY_FEE_RATE), 540);
+
+		// the threshold scales with the dust relay fee rate
+		assert_eq!(dust_threshold(&p2wpkh, 1), 98);
+		assert_eq!(dust_threshold(&p2wpkh, 10), 980);
+		assert_eq!(dust_threshold(&p2pkh, 0), 0);
+	}
+
+	#[test]
+	fn unspendable_outputs_are_never_dust() {
+		let op_return = ScriptBuf::from_bytes(vec![0x6a, 0x01, 0xff]);
+		assert!(op_return.is_op_return());
+		assert_eq!(dust_threshold(&op_return, DUST_RELAY_FEE_RATE), 0);
+	}
+
+	#[test]
+	fn unsupported_descriptors_are_not_estimated() {
+		let desc = descriptor(&format!("wpkh({})", pub_key(1)));
+		assert_eq!(estimate_descriptor_input_size(&desc), None);
+	}
+}
@@ -578,19 +578,52 @@ macro_rules! impl_common_runtime_apis {
 					Grandpa::current_set_id()
 				}
 				fn submit_report_equivocation_unsigned_extrinsic(
-					_equivocation_proof: fg_primitives::EquivocationProof<
+					equivocation_proof: fg_primitives::EquivocationProof<
 						<Block as BlockT>::Hash,
 						NumberFor<Block>,
 					>,
-					_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+					key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 				) -> Option<()> {
-					None
+					let key_owner_proof = key_owner_proof.decode()?;
+
+					Grandpa::submit_unsigned_equivocation_report(
+						equivocation_proof,
+						key_owner_proof,
+					)
 				}
 				fn generate_key_ownership_proof(
 					_set_id: fg_primitives::SetId,
-					_authority_id: GrandpaId,
+					authority_id: GrandpaId,
 				) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-					None
+					Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+						.map(|p| p.encode())
+						.map(fg_primitives::OpaqueKeyOwnershipProof::new)
+				}
+			}
+			impl pallet_bfc_offences_runtime_api::AuraEquivocationApi<Block, AuraId> for Runtime {
+				fn generate_key_ownership_proof(
+					_slot: pallet_bfc_offences_runtime_api::Slot,
+					authority_id: AuraId,
+				) -> Option<pallet_bfc_offences_runtime_api::OpaqueKeyOwnershipProof> {
+					// the proof is only valid for the current session, which the pallet enforces
+					// when the report is submitted
+					Historical::prove((sp_core::crypto::key_types::AURA, authority_id))
+						.map(|p| p.encode())
+						.map(pallet_bfc_offences_runtime_api::OpaqueKeyOwnershipProof::new)
+				}
+				fn submit_report_equivocation_unsigned_extrinsic(
+					equivocation_proof: pallet_bfc_offences_runtime_api::EquivocationProof<
+						<Block as BlockT>::Header,
+						AuraId,
+					>,
+					key_owner_proof: pallet_bfc_offences_runtime_api::OpaqueKeyOwnershipProof,
+				) -> Option<()> {
+					let key_owner_proof = key_owner_proof.decode()?;
+
+					BfcOffences::submit_unsigned_equivocation_report(
+						equivocation_proof,
+						key_owner_proof,
+					)
 				}
 			}
 			impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce> for Runtime {
@@ -618,6 +651,34 @@ macro_rules! impl_common_runtime_apis {
 					TransactionPayment::length_to_fee(length)
 				}
 			}
+			impl pallet_bfc_staking_rpc_runtime_api::BfcStakingApi<Block, AccountId, Balance> for Runtime {
+				fn candidate_state(
+					candidate: AccountId,
+				) -> Option<pallet_bfc_staking::CandidateState<AccountId, Balance>> {
+					BfcStaking::candidate_state(&candidate)
+				}
+				fn nominator_state(
+					nominator: AccountId,
+				) -> Option<pallet_bfc_staking::NominatorInfo<AccountId, Balance>> {
+					BfcStaking::nominator_info(&nominator)
+				}
+				fn selected_candidates(tier: pallet_bfc_staking_rpc_runtime_api::TierType) -> Vec<AccountId> {
+					BfcStaking::selected_candidates_by_tier(tier)
+				}
+				fn estimated_apr(candidate: Option<AccountId>) -> sp_runtime::FixedU128 {
+					BfcStaking::estimated_apr(candidate)
+				}
+				fn pending_rewards(account: AccountId) -> Balance {
+					BfcStaking::pending_rewards(&account)
+				}
+				fn reward_history(
+					account: AccountId,
+					from: pallet_bfc_staking_rpc_runtime_api::RoundIndex,
+					to: pallet_bfc_staking_rpc_runtime_api::RoundIndex,
+				) -> Vec<pallet_bfc_staking::RewardEntry<AccountId, Balance>> {
+					BfcStaking::reward_history(&account, from, to)
+				}
+			}
 			#[cfg(feature = "runtime-benchmarks")]
 			impl frame_benchmarking::Benchmark<Block> for Runtime {
 				fn benchmark_metadata(extra: bool) -> (
@@ -22,7 +22,7 @@ use fp_rpc::TransactionStatus;
 use fp_rpc_txpool::TxPoolResponse;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, ConstBool, ConstU64, OpaqueMetadata, H160, H256, U256};
+use sp_core::{crypto::KeyTypeId, ConstBool, OpaqueMetadata, H160, H256, U256};
 use sp_genesis_builder::PresetId;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -47,6 +47,7 @@ use sp_version::RuntimeVersion;
 use parity_scale_codec::{Decode, Encode};
 
 pub use pallet_balances::{Call as BalancesCall, NegativeImbalance};
+use pallet_bfc_offences::{OffenceKind, OffenceKindConfig, OffenceKindReporter};
 pub use pallet_bfc_staking::{InflationInfo, Range};
 use pallet_bifrost_evm_tx_payment::BifrostFeeAdapter;
 use pallet_ethereum::{
@@ -301,15 +302,28 @@ impl pallet_aura::Config for Runtime {
 	type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
 }
 
+parameter_types! {
+	/// The number of blocks that an equivocation report remains valid in the transaction pool.
+	/// Key ownership proofs can only be verified within the session they were generated in.
+	pub const ReportLongevity: u64 = SessionPeriod::get() as u64;
+	/// The number of historical GRANDPA set id to session mappings to keep.
+	pub const MaxSetIdSessionEntries: u64 = 32;
+}
+
 /// Provides the GRANDPA block finality gadget.
 impl pallet_grandpa::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type KeyOwnerProof = sp_core::Void;
-	type EquivocationReportSystem = ();
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
+	type EquivocationReportSystem = pallet_grandpa::EquivocationReportSystem<
+		Self,
+		OffenceKindReporter<Offences, BfcStaking, BfcOffences>,
+		Historical,
+		ReportLongevity,
+	>;
 	type WeightInfo = ();
 	type MaxAuthorities = MaxAuthorities;
 	type MaxNominators = ConstU32<150>;
-	type MaxSetIdSessionEntries = ConstU64<0>;
+	type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
 }
 
 parameter_types! {
@@ -440,7 +454,7 @@ impl pallet_session::Config for Runtime {
 
 impl pallet_session::historical::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type FullIdentification = pallet_bfc_staking::ValidatorSnapshot<AccountId, Balance>;
+	type FullIdentification = pallet_bfc_staking::ValidatorIdentification<AccountId, Balance>;
 	type FullIdentificationOf = pallet_bfc_staking::ValidatorSnapshotOf<Self>;
 }
 
@@ -791,7 +805,7 @@ impl pallet_im_online::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type NextSessionRotation = BfcStaking;
 	type ValidatorSet = Historical;
-	type ReportUnresponsiveness = Offences;
+	type ReportUnresponsiveness = OffenceKindReporter<Offences, BfcStaking, BfcOffences>;
 	type UnsignedPriority = ImOnlineUnsignedPriority;
 	type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
 	type MaxKeys = MaxKeys;
@@ -803,33 +817,137 @@ impl pallet_im_online::Config for Runtime {
 impl pallet_offences::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
-	type OnOffenceHandler = BfcStaking;
+	// offences are handled with their kind by `OffenceKindReporter`
+	type OnOffenceHandler = ();
 }
 
 parameter_types! {
-	pub const DefaultOffenceExpirationInSessions: u32 = 5u32;
-	pub const DefaultFullMaximumOffenceCount: u32 = 5u32;
-	pub const DefaultBasicMaximumOffenceCount: u32 = 3u32;
+	pub DefaultOffenceKindConfigs: Vec<(OffenceKind, OffenceKindConfig)> = sp_std::vec![
+		(
+			OffenceKind::Unresponsive,
+			OffenceKindConfig {
+				full_maximum_offence_count: 5u32,
+				basic_maximum_offence_count: 3u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 5u32,
+			},
+		),
+		(
+			OffenceKind::GrandpaEquivocation,
+			OffenceKindConfig {
+				full_maximum_offence_count: 0u32,
+				basic_maximum_offence_count: 0u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 20u32,
+			},
+		),
+		(
+			OffenceKind::AuraEquivocation,
+			OffenceKindConfig {
+				full_maximum_offence_count: 0u32,
+				basic_maximum_offence_count: 0u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 20u32,
+			},
+		),
+		(
+			OffenceKind::RelayerHeartbeat,
+			OffenceKindConfig {
+				full_maximum_offence_count: 5u32,
+				basic_maximum_offence_count: 3u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 5u32,
+			},
+		),
+	];
 	pub const IsOffenceActive: bool = true;
 	pub const IsSlashActive: bool = true;
+	pub const IsNominatorSlashActive: bool = false;
+	pub const DefaultSlashDeferDuration: u32 = 0u32;
+	pub MinUnbondingDelay: u32 = LeaveCandidatesDelay::get()
+		.min(CandidateBondLessDelay::get())
+		.min(LeaveNominatorsDelay::get())
+		.min(RevokeNominationDelay::get())
+		.min(NominationBondLessDelay::get());
+	pub const DefaultEquivocationSlashFraction: Perbill = Perbill::from_percent(10);
 }
 
 /// A module that wraps `pallet_offences` to act as a central offence handler
 impl pallet_bfc_offences::Config for Runtime {
 	type Currency = Balances;
 	type Slash = Treasury;
-	type DefaultOffenceExpirationInSessions = DefaultOffenceExpirationInSessions;
-	type DefaultFullMaximumOffenceCount = DefaultFullMaximumOffenceCount;
-	type DefaultBasicMaximumOffenceCount = DefaultBasicMaximumOffenceCount;
+	type DefaultOffenceKindConfigs = DefaultOffenceKindConfigs;
 	type IsOffenceActive = IsOffenceActive;
 	type IsSlashActive = IsSlashActive;
+	type IsNominatorSlashActive = IsNominatorSlashActive;
+	type DefaultSlashDeferDuration = DefaultSlashDeferDuration;
+	type MinUnbondingDelay = MinUnbondingDelay;
+	type DefaultEquivocationSlashFraction = DefaultEquivocationSlashFraction;
+	type AuraId = AuraId;
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof;
+	type EquivocationReportSystem = pallet_bfc_offences::equivocation::EquivocationReportSystem<
+		Self,
+		OffenceKindReporter<Offences, BfcStaking, BfcOffences>,
+		Historical,
+		ReportLongevity,
+	>;
+	type CancelOrigin = MoreThanHalfCouncil;
 	type WeightInfo = pallet_bfc_offences::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	/// The pallet id that the liquid staking pool account is derived from.
+	pub const LiquidStakingPalletId: PalletId = PalletId(*b"bfc/lqst");
+	/// Minimum amount of a single liquid staking deposit.
+	pub const MinLiquidStakingDeposit: u128 = 1 * BFC;
+	/// Minimum amount of a single liquid staking withdrawal, unless it withdraws every share.
+	pub const MinLiquidStakingWithdrawal: u128 = 1 * BFC;
+	/// Maximum number of pending liquid staking withdrawals per account.
+	pub const MaxLiquidStakingUnbondingChunks: u32 = 32;
+	/// Maximum number of liquid staking withdrawals waiting for a nomination to be unbonded from.
+	pub const MaxLiquidStakingQueuedWithdrawals: u32 = 64;
+}
+
+/// A module that pools nominations and issues transferable shares of the pooled funds
+impl pallet_bfc_liquid_staking::Config for Runtime {
+	type Currency = Balances;
+	type NominationManager = BfcStaking;
+	type Strategy = pallet_bfc_liquid_staking::strategy::EvenSpread<AccountId, Balance>;
+	type PalletId = LiquidStakingPalletId;
+	type MinDeposit = MinLiquidStakingDeposit;
+	type MinWithdrawal = MinLiquidStakingWithdrawal;
+	type MaxUnbondingChunks = MaxLiquidStakingUnbondingChunks;
+	type MaxQueuedWithdrawals = MaxLiquidStakingQueuedWithdrawals;
+	type WeightInfo = pallet_bfc_liquid_staking::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// The pallet id that the nomination pool accounts are derived from.
+	pub const NominationPoolsPalletId: PalletId = PalletId(*b"bfc/npls");
+	/// Minimum amount that a member has to bond to join a nomination pool.
+	pub const MinPoolJoinBond: u128 = 1 * BFC;
+	/// Maximum commission that a nomination pool operator can set.
+	pub const MaxPoolCommission: Perbill = Perbill::from_percent(20);
+	/// Maximum number of pending nomination pool withdrawals per member.
+	pub const MaxPoolUnbondingChunks: u32 = 32;
+}
+
+/// A module that lets small holders nominate through shared pools
+impl pallet_bfc_nomination_pools::Config for Runtime {
+	type Currency = Balances;
+	type NominationManager = BfcStaking;
+	type PalletId = NominationPoolsPalletId;
+	type MinJoinBond = MinPoolJoinBond;
+	type MaxPoolCommission = MaxPoolCommission;
+	type MaxUnbondingChunks = MaxPoolUnbondingChunks;
+	type WeightInfo = pallet_bfc_nomination_pools::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const StorageCacheLifetimeInRounds: u32 = 64u32;
 	pub const IsHeartbeatOffenceActive: bool = false;
 	pub const DefaultHeartbeatSlashFraction: Perbill = Perbill::from_percent(1);
+	pub const RelayerVersionGracePeriod: u32 = 1;
 }
 
 /// A module that manages registered relayers for cross chain interoperability
@@ -839,10 +957,11 @@ impl pallet_relay_manager::Config for Runtime {
 	type RegistrationPool = BtcRegistrationPool;
 	type RelayQueue = CCCPRelayQueue;
 	type ValidatorSet = Historical;
-	type ReportUnresponsiveness = Offences;
+	type ReportUnresponsiveness = OffenceKindReporter<Offences, BfcStaking, BfcOffences>;
 	type StorageCacheLifetimeInRounds = StorageCacheLifetimeInRounds;
 	type IsHeartbeatOffenceActive = IsHeartbeatOffenceActive;
 	type DefaultHeartbeatSlashFraction = DefaultHeartbeatSlashFraction;
+	type RelayerVersionGracePeriod = RelayerVersionGracePeriod;
 	type WeightInfo = pallet_relay_manager::weights::SubstrateWeight<Runtime>;
 }
 
@@ -866,14 +985,32 @@ parameter_types! {
 	pub const LeaveCandidatesDelay: u32 = 1;
 	/// Rounds before the candidate bond increase/decrease can be executed.
 	pub const CandidateBondLessDelay: u32 = 1;
+	/// Rounds that a validator is jailed for its first offence (doubled for repeated offences).
+	pub const JailDuration: u32 = 1;
+	/// Maximum rounds that a validator can be jailed for.
+	pub const MaxJailDuration: u32 = 8;
+	/// Rounds after a jail ends without another jail that decrease the jail count by one.
+	pub const JailCountDecay: u32 = 8;
 	/// Rounds before the nominator exit can be executed.
 	pub const LeaveNominatorsDelay: u32 = 1;
 	/// Rounds before the nominator revocation can be executed.
 	pub const RevokeNominationDelay: u32 = 1;
+	/// Rounds between two redelegations of a nominator.
+	pub const RedelegationDelay: u32 = 1;
 	/// Rounds before the nominator bond increase/decrease can be executed.
 	pub const NominationBondLessDelay: u32 = 1;
 	/// Rounds before the reward is paid.
 	pub const RewardPaymentDelay: u32 = 1;
+	/// Maximum reward entries kept in the history of each account.
+	pub const MaxRewardHistory: u32 = 100;
+	/// Rounds that reward entries are kept in the history of each account.
+	pub const RewardHistoryDepth: u32 = 30;
+	/// Maximum accounts whose stale reward history is pruned per block.
+	pub const MaxRewardHistoryPrunesPerBlock: u32 = 20;
+	/// Maximum validator snapshots taken per block after a round transition.
+	pub const MaxValidatorSnapshotsPerBlock: u32 = 10;
+	/// Blocks before the round boundary at which the round transition starts.
+	pub const RoundTransitionBlocks: u32 = 10;
 	/// Default maximum full validators selected per round, default at genesis.
 	pub const DefaultMaxSelectedFullCandidates: u32 = 10;
 	/// Default maximum basicvalidators selected per round, default at genesis.
@@ -892,6 +1029,8 @@ parameter_types! {
 	pub const MaxFullValidatorCommission: Perbill = Perbill::from_percent(100);
 	/// Maximum commission rate available for basic validators.
 	pub const MaxBasicValidatorCommission: Perbill = Perbill::from_percent(20);
+	/// Default maximum commission rate increase applied to a validator per round.
+	pub const DefaultMaxCommissionIncreasePerRound: Perbill = Perbill::from_percent(20);
 	/// Minimum stake required to become a full validator.
 	pub const MinFullValidatorStk: u128 = 1_000 * SUPPLY_FACTOR * BFC;
 	/// Minimum stake required to become a basic validator.
@@ -902,6 +1041,8 @@ parameter_types! {
 	pub const MinBasicCandidateStk: u128 = 100 * SUPPLY_FACTOR * BFC;
 	/// Minimum stake required to be reserved to be a nominator.
 	pub const MinNominatorStk: u128 = 1 * SUPPLY_FACTOR * BFC;
+	/// Amount reserved from the stash account to register a candidate identity.
+	pub const CandidateIdentityDeposit: u128 = 1 * BFC;
 }
 
 /// Minimal staking pallet that implements validator selection by total backed stake.
@@ -910,6 +1051,9 @@ impl pallet_bfc_staking::Config for Runtime {
 	type MonetaryGovernanceOrigin = EnsureRoot<AccountId>;
 	type RelayManager = RelayManager;
 	type OffenceHandler = BfcOffences;
+	type Treasury = Treasury;
+	type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
+	type ElectionProvider = pallet_bfc_staking::election::TopN;
 	type MinBlocksPerRound = MinBlocksPerRound;
 	type MaxBlocksPerRound = MaxBlocksPerRound;
 	type DefaultBlocksPerSession = SessionPeriod;
@@ -917,10 +1061,19 @@ impl pallet_bfc_staking::Config for Runtime {
 	type StorageCacheLifetimeInRounds = StorageCacheLifetimeInRounds;
 	type LeaveCandidatesDelay = LeaveCandidatesDelay;
 	type CandidateBondLessDelay = CandidateBondLessDelay;
+	type JailDuration = JailDuration;
+	type MaxJailDuration = MaxJailDuration;
+	type JailCountDecay = JailCountDecay;
 	type LeaveNominatorsDelay = LeaveNominatorsDelay;
 	type RevokeNominationDelay = RevokeNominationDelay;
+	type RedelegationDelay = RedelegationDelay;
 	type NominationBondLessDelay = NominationBondLessDelay;
 	type RewardPaymentDelay = RewardPaymentDelay;
+	type MaxRewardHistory = MaxRewardHistory;
+	type RewardHistoryDepth = RewardHistoryDepth;
+	type MaxRewardHistoryPrunesPerBlock = MaxRewardHistoryPrunesPerBlock;
+	type MaxValidatorSnapshotsPerBlock = MaxValidatorSnapshotsPerBlock;
+	type RoundTransitionBlocks = RoundTransitionBlocks;
 	type DefaultMaxSelectedFullCandidates = DefaultMaxSelectedFullCandidates;
 	type DefaultMaxSelectedBasicCandidates = DefaultMaxSelectedBasicCandidates;
 	type MaxTopNominationsPerCandidate = MaxTopNominationsPerCandidate;
@@ -930,12 +1083,14 @@ impl pallet_bfc_staking::Config for Runtime {
 	type DefaultBasicValidatorCommission = DefaultBasicValidatorCommission;
 	type MaxFullValidatorCommission = MaxFullValidatorCommission;
 	type MaxBasicValidatorCommission = MaxBasicValidatorCommission;
+	type DefaultMaxCommissionIncreasePerRound = DefaultMaxCommissionIncreasePerRound;
 	type MinFullValidatorStk = MinFullValidatorStk;
 	type MinBasicValidatorStk = MinBasicValidatorStk;
 	type MinFullCandidateStk = MinFullCandidateStk;
 	type MinBasicCandidateStk = MinBasicCandidateStk;
 	type MinNomination = MinNominatorStk;
 	type MinNominatorStk = MinNominatorStk;
+	type IdentityDeposit = CandidateIdentityDeposit;
 	type WeightInfo = pallet_bfc_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1081,6 +1236,7 @@ impl pallet_btc_socket_queue::Config for Runtime {
 	type Relayers = RelayManager;
 	type RegistrationPool = BtcRegistrationPool;
 	type Blaze = Blaze;
+	type LightClient = BtcLightClient;
 	type WeightInfo = pallet_btc_socket_queue::weights::SubstrateWeight<Runtime>;
 	type DefaultMaxFeeRate = DefaultMaxFeeRate;
 	type DefaultMaxSocketMessageBytes = DefaultMaxSocketMessageBytes;
@@ -1089,6 +1245,8 @@ impl pallet_btc_socket_queue::Config for Runtime {
 parameter_types! {
 	pub const BitcoinChainId: u32 = 10002;
 	pub const BitcoinNetwork: Network = Network::Regtest;
+	/// The relative timelock of the user vault recovery path. 1 day of Bitcoin blocks.
+	pub const RecoveryTimelock: u32 = 144;
 	pub const DefaultMultiSigRatio: Percent = Percent::from_percent(100);
 	pub const DefaultMaxFeeRate: u64 = 15;
 	pub const DefaultMaxSocketMessageBytes: u32 = 2 * 1024;
@@ -1102,12 +1260,16 @@ impl pallet_btc_registration_pool::Config for Runtime {
 	type DefaultMultiSigRatio = DefaultMultiSigRatio;
 	type BitcoinChainId = BitcoinChainId;
 	type BitcoinNetwork = BitcoinNetwork;
+	type RecoveryTimelock = RecoveryTimelock;
 	type WeightInfo = pallet_btc_registration_pool::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
 	pub const FeeRateExpiration: u32 = 1 * MINUTES;
 	pub const ToleranceThreshold: u32 = 3;
+	/// Recovery vault UTXOs are swept 36 Bitcoin blocks before their recovery path matures.
+	pub const RecoverySweepWindow: u32 = 36;
+	pub const RecoverySafetyMargin: u32 = 12;
 }
 
 impl pallet_blaze::Config for Runtime {
@@ -1116,11 +1278,30 @@ impl pallet_blaze::Config for Runtime {
 	type Relayers = RelayManager;
 	type SocketQueue = BtcSocketQueue;
 	type RegistrationPool = BtcRegistrationPool;
+	type LightClient = BtcLightClient;
 	type FeeRateExpiration = FeeRateExpiration;
 	type ToleranceThreshold = ToleranceThreshold;
+	type RecoverySweepWindow = RecoverySweepWindow;
+	type RecoverySafetyMargin = RecoverySafetyMargin;
 	type WeightInfo = pallet_blaze::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxReorgDepth: u32 = 6;
+	pub const HeadersToKeep: u32 = 144;
+}
+
+impl pallet_btc_light_client::Config for Runtime {
+	type Signature = EthereumSignature;
+	type Signer = EthereumSigner;
+	type Relayers = RelayManager;
+	type BitcoinNetwork = BitcoinNetwork;
+	type MaxReorgDepth = MaxReorgDepth;
+	type HeadersToKeep = HeadersToKeep;
+	type OnCheckpointSet = (Blaze, BtcSocketQueue);
+	type WeightInfo = pallet_btc_light_client::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	/// Pallet ID for ERC20 gas fee collection.
 	/// Used to derive a deterministic EOA address outside the precompile range.
@@ -1226,6 +1407,12 @@ mod runtime {
 	#[runtime::pallet_index(23)]
 	pub type BfcOffences = pallet_bfc_offences;
 
+	#[runtime::pallet_index(24)]
+	pub type BfcLiquidStaking = pallet_bfc_liquid_staking;
+
+	#[runtime::pallet_index(25)]
+	pub type BfcNominationPools = pallet_bfc_nomination_pools;
+
 	#[runtime::pallet_index(30)]
 	pub type Utility = pallet_utility;
 
@@ -1295,6 +1482,9 @@ mod runtime {
 	#[runtime::pallet_index(65)]
 	pub type OracleRegistry = pallet_oracle_registry;
 
+	#[runtime::pallet_index(66)]
+	pub type BtcLightClient = pallet_btc_light_client;
+
 	#[runtime::pallet_index(99)]
 	pub type Sudo = pallet_sudo;
 
@@ -1306,8 +1496,12 @@ mod runtime {
 mod benches {
 	frame_benchmarking::define_benchmarks!(
 		[frame_system, SystemBench::<Runtime>]
+		[pallet_bfc_staking, BfcStaking]
+		[pallet_bfc_liquid_staking, BfcLiquidStaking]
+		[pallet_bfc_nomination_pools, BfcNominationPools]
 		[pallet_relay_manager, RelayManager]
 		[pallet_blaze, Blaze]
+		[pallet_btc_light_client, BtcLightClient]
 		[pallet_btc_registration_pool, BtcRegistrationPool]
 		[pallet_btc_socket_queue, BtcSocketQueue]
 	);
@@ -22,7 +22,7 @@ use fp_rpc::TransactionStatus;
 use fp_rpc_txpool::TxPoolResponse;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, ConstBool, ConstU64, OpaqueMetadata, H160, H256, U256};
+use sp_core::{crypto::KeyTypeId, ConstBool, OpaqueMetadata, H160, H256, U256};
 use sp_genesis_builder::PresetId;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -44,6 +44,7 @@ use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
 pub use pallet_balances::{Call as BalancesCall, NegativeImbalance};
+use pallet_bfc_offences::{OffenceKind, OffenceKindConfig, OffenceKindReporter};
 pub use pallet_bfc_staking::{InflationInfo, Range};
 use pallet_ethereum::{
 	Call::transact, EthereumBlockHashMapping, PostLogContent, Transaction as EthereumTransaction,
@@ -299,15 +300,28 @@ impl pallet_aura::Config for Runtime {
 	type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
 }
 
+parameter_types! {
+	/// The number of blocks that an equivocation report remains valid in the transaction pool.
+	/// Key ownership proofs can only be verified within the session they were generated in.
+	pub const ReportLongevity: u64 = SessionPeriod::get() as u64;
+	/// The number of historical GRANDPA set id to session mappings to keep.
+	pub const MaxSetIdSessionEntries: u64 = 32;
+}
+
 /// Provides the GRANDPA block finality gadget.
 impl pallet_grandpa::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type KeyOwnerProof = sp_core::Void;
-	type EquivocationReportSystem = ();
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
+	type EquivocationReportSystem = pallet_grandpa::EquivocationReportSystem<
+		Self,
+		OffenceKindReporter<Offences, BfcStaking, BfcOffences>,
+		Historical,
+		ReportLongevity,
+	>;
 	type WeightInfo = ();
 	type MaxAuthorities = MaxAuthorities;
 	type MaxNominators = ConstU32<150>;
-	type MaxSetIdSessionEntries = ConstU64<0>;
+	type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
 }
 
 parameter_types! {
@@ -438,7 +452,7 @@ impl pallet_session::Config for Runtime {
 
 impl pallet_session::historical::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type FullIdentification = pallet_bfc_staking::ValidatorSnapshot<AccountId, Balance>;
+	type FullIdentification = pallet_bfc_staking::ValidatorIdentification<AccountId, Balance>;
 	type FullIdentificationOf = pallet_bfc_staking::ValidatorSnapshotOf<Self>;
 }
 
@@ -472,7 +486,7 @@ impl pallet_im_online::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type NextSessionRotation = BfcStaking;
 	type ValidatorSet = Historical;
-	type ReportUnresponsiveness = Offences;
+	type ReportUnresponsiveness = OffenceKindReporter<Offences, BfcStaking, BfcOffences>;
 	type UnsignedPriority = ImOnlineUnsignedPriority;
 	type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
 	type MaxKeys = MaxKeys;
@@ -484,7 +498,8 @@ impl pallet_im_online::Config for Runtime {
 impl pallet_offences::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
-	type OnOffenceHandler = BfcStaking;
+	// offences are handled with their kind by `OffenceKindReporter`
+	type OnOffenceHandler = ();
 }
 
 /// The Authorship module tracks the current author of the block.
@@ -803,22 +818,76 @@ impl pallet_identity::Config for Runtime {
 }
 
 parameter_types! {
-	pub const DefaultOffenceExpirationInSessions: u32 = 4u32; // 1 HOUR
-	pub const DefaultFullMaximumOffenceCount: u32 = 10u32;
-	pub const DefaultBasicMaximumOffenceCount: u32 = 5u32;
+	pub DefaultOffenceKindConfigs: Vec<(OffenceKind, OffenceKindConfig)> = sp_std::vec![
+		(
+			OffenceKind::Unresponsive,
+			OffenceKindConfig {
+				full_maximum_offence_count: 10u32,
+				basic_maximum_offence_count: 5u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 4u32, // 1 HOUR
+			},
+		),
+		(
+			OffenceKind::GrandpaEquivocation,
+			OffenceKindConfig {
+				full_maximum_offence_count: 0u32,
+				basic_maximum_offence_count: 0u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 16u32,
+			},
+		),
+		(
+			OffenceKind::AuraEquivocation,
+			OffenceKindConfig {
+				full_maximum_offence_count: 0u32,
+				basic_maximum_offence_count: 0u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 16u32,
+			},
+		),
+		(
+			OffenceKind::RelayerHeartbeat,
+			OffenceKindConfig {
+				full_maximum_offence_count: 10u32,
+				basic_maximum_offence_count: 5u32,
+				max_slash_fraction: Perbill::one(),
+				expiration_in_sessions: 4u32, // 1 HOUR
+			},
+		),
+	];
 	pub const IsOffenceActive: bool = true;
 	pub const IsSlashActive: bool = true;
+	pub const IsNominatorSlashActive: bool = false;
+	pub const DefaultSlashDeferDuration: u32 = 0u32;
+	pub MinUnbondingDelay: u32 = LeaveCandidatesDelay::get()
+		.min(CandidateBondLessDelay::get())
+		.min(LeaveNominatorsDelay::get())
+		.min(RevokeNominationDelay::get())
+		.min(NominationBondLessDelay::get());
+	pub const DefaultEquivocationSlashFraction: Perbill = Perbill::from_percent(10);
 }
 
 /// A module that wraps `pallet_offences` to act as a central offence handler
 impl pallet_bfc_offences::Config for Runtime {
 	type Currency = Balances;
 	type Slash = Treasury;
-	type DefaultOffenceExpirationInSessions = DefaultOffenceExpirationInSessions;
-	type DefaultFullMaximumOffenceCount = DefaultFullMaximumOffenceCount;
-	type DefaultBasicMaximumOffenceCount = DefaultBasicMaximumOffenceCount;
+	type DefaultOffenceKindConfigs = DefaultOffenceKindConfigs;
 	type IsOffenceActive = IsOffenceActive;
 	type IsSlashActive = IsSlashActive;
+	type IsNominatorSlashActive = IsNominatorSlashActive;
+	type DefaultSlashDeferDuration = DefaultSlashDeferDuration;
+	type MinUnbondingDelay = MinUnbondingDelay;
+	type DefaultEquivocationSlashFraction = DefaultEquivocationSlashFraction;
+	type AuraId = AuraId;
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof;
+	type EquivocationReportSystem = pallet_bfc_offences::equivocation::EquivocationReportSystem<
+		Self,
+		OffenceKindReporter<Offences, BfcStaking, BfcOffences>,
+		Historical,
+		ReportLongevity,
+	>;
+	type CancelOrigin = MoreThanHalfCouncil;
 	type WeightInfo = pallet_bfc_offences::weights::SubstrateWeight<Runtime>;
 }
 
@@ -826,6 +895,7 @@ parameter_types! {
 	pub const StorageCacheLifetimeInRounds: u32 = 64u32;
 	pub const IsHeartbeatOffenceActive: bool = false;
 	pub const DefaultHeartbeatSlashFraction: Perbill = Perbill::from_parts(2000000);
+	pub const RelayerVersionGracePeriod: u32 = 14;
 }
 
 /// A module that manages registered relayers for cross chain interoperability
@@ -835,10 +905,11 @@ impl pallet_relay_manager::Config for Runtime {
 	type RegistrationPool = BtcRegistrationPool;
 	type RelayQueue = ();
 	type ValidatorSet = Historical;
-	type ReportUnresponsiveness = Offences;
+	type ReportUnresponsiveness = OffenceKindReporter<Offences, BfcStaking, BfcOffences>;
 	type StorageCacheLifetimeInRounds = StorageCacheLifetimeInRounds;
 	type IsHeartbeatOffenceActive = IsHeartbeatOffenceActive;
 	type DefaultHeartbeatSlashFraction = DefaultHeartbeatSlashFraction;
+	type RelayerVersionGracePeriod = RelayerVersionGracePeriod;
 	type WeightInfo = pallet_relay_manager::weights::SubstrateWeight<Runtime>;
 }
 
@@ -853,14 +924,32 @@ parameter_types! {
 	pub const LeaveCandidatesDelay: u32 = 14;
 	/// Rounds before the candidate bond increase/decrease can be executed.
 	pub const CandidateBondLessDelay: u32 = 14;
+	/// Rounds that a validator is jailed for its first offence (doubled for repeated offences).
+	pub const JailDuration: u32 = 2;
+	/// Maximum rounds that a validator can be jailed for.
+	pub const MaxJailDuration: u32 = 28;
+	/// Rounds after a jail ends without another jail that decrease the jail count by one.
+	pub const JailCountDecay: u32 = 56;
 	/// Rounds before the nominator exit can be executed.
 	pub const LeaveNominatorsDelay: u32 = 14;
 	/// Rounds before the nominator revocation can be executed.
 	pub const RevokeNominationDelay: u32 = 14;
+	/// Rounds between two redelegations of a nominator.
+	pub const RedelegationDelay: u32 = 14;
 	/// Rounds before the nominator bond increase/decrease can be executed.
 	pub const NominationBondLessDelay: u32 = 14;
 	/// Rounds before the reward is paid.
 	pub const RewardPaymentDelay: u32 = 1;
+	/// Maximum reward entries kept in the history of each account.
+	pub const MaxRewardHistory: u32 = 200;
+	/// Rounds that reward entries are kept in the history of each account.
+	pub const RewardHistoryDepth: u32 = 60;
+	/// Maximum accounts whose stale reward history is pruned per block.
+	pub const MaxRewardHistoryPrunesPerBlock: u32 = 50;
+	/// Maximum validator snapshots taken per block after a round transition.
+	pub const MaxValidatorSnapshotsPerBlock: u32 = 20;
+	/// Blocks before the round boundary at which the round transition starts.
+	pub const RoundTransitionBlocks: u32 = 20;
 	/// Default maximum full validators selected per round, default at genesis.
 	pub const DefaultMaxSelectedFullCandidates: u32 = 20;
 	/// Default maximum basic validators selected per round, default at genesis.
@@ -879,6 +968,8 @@ parameter_types! {
 	pub const MaxFullValidatorCommission: Perbill = Perbill::from_percent(100);
 	/// Maximum commission rate available for basic validators.
 	pub const MaxBasicValidatorCommission: Perbill = Perbill::from_percent(20);
+	/// Default maximum commission rate increase applied to a validator per round.
+	pub const DefaultMaxCommissionIncreasePerRound: Perbill = Perbill::from_percent(1);
 	/// Minimum stake required to become a full validator.
 	pub const MinFullValidatorStk: u128 = 4_000_000 * SUPPLY_FACTOR * BFC;
 	/// Minimum stake required to become a basic validator.
@@ -889,6 +980,8 @@ parameter_types! {
 	pub const MinBasicCandidateStk: u128 = 400_000 * SUPPLY_FACTOR * BFC;
 	/// Minimum stake required to be reserved to be a nominator.
 	pub const MinNominatorStk: u128 = 100 * SUPPLY_FACTOR * BFC;
+	/// Amount reserved from the stash account to register a candidate identity.
+	pub const CandidateIdentityDeposit: u128 = 100 * BFC;
 }
 
 /// Minimal staking pallet that implements validator selection by total backed stake.
@@ -897,6 +990,9 @@ impl pallet_bfc_staking::Config for Runtime {
 	type MonetaryGovernanceOrigin = EnsureRoot<AccountId>;
 	type RelayManager = RelayManager;
 	type OffenceHandler = BfcOffences;
+	type Treasury = Treasury;
+	type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
+	type ElectionProvider = pallet_bfc_staking::election::TopN;
 	type MinBlocksPerRound = MinBlocksPerRound;
 	type MaxBlocksPerRound = MaxBlocksPerRound;
 	type DefaultBlocksPerSession = SessionPeriod;
@@ -904,10 +1000,19 @@ impl pallet_bfc_staking::Config for Runtime {
 	type StorageCacheLifetimeInRounds = StorageCacheLifetimeInRounds;
 	type LeaveCandidatesDelay = LeaveCandidatesDelay;
 	type CandidateBondLessDelay = CandidateBondLessDelay;
+	type JailDuration = JailDuration;
+	type MaxJailDuration = MaxJailDuration;
+	type JailCountDecay = JailCountDecay;
 	type LeaveNominatorsDelay = LeaveNominatorsDelay;
 	type RevokeNominationDelay = RevokeNominationDelay;
+	type RedelegationDelay = RedelegationDelay;
 	type NominationBondLessDelay = NominationBondLessDelay;
 	type RewardPaymentDelay = RewardPaymentDelay;
+	type MaxRewardHistory = MaxRewardHistory;
+	type RewardHistoryDepth = RewardHistoryDepth;
+	type MaxRewardHistoryPrunesPerBlock = MaxRewardHistoryPrunesPerBlock;
+	type MaxValidatorSnapshotsPerBlock = MaxValidatorSnapshotsPerBlock;
+	type RoundTransitionBlocks = RoundTransitionBlocks;
 	type DefaultMaxSelectedFullCandidates = DefaultMaxSelectedFullCandidates;
 	type DefaultMaxSelectedBasicCandidates = DefaultMaxSelectedBasicCandidates;
 	type MaxTopNominationsPerCandidate = MaxTopNominationsPerCandidate;
@@ -917,12 +1022,14 @@ impl pallet_bfc_staking::Config for Runtime {
 	type DefaultBasicValidatorCommission = DefaultBasicValidatorCommission;
 	type MaxFullValidatorCommission = MaxFullValidatorCommission;
 	type MaxBasicValidatorCommission = MaxBasicValidatorCommission;
+	type DefaultMaxCommissionIncreasePerRound = DefaultMaxCommissionIncreasePerRound;
 	type MinFullValidatorStk = MinFullValidatorStk;
 	type MinBasicValidatorStk = MinBasicValidatorStk;
 	type MinFullCandidateStk = MinFullCandidateStk;
 	type MinBasicCandidateStk = MinBasicCandidateStk;
 	type MinNomination = MinNominatorStk;
 	type MinNominatorStk = MinNominatorStk;
+	type IdentityDeposit = CandidateIdentityDeposit;
 	type WeightInfo = pallet_bfc_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1068,6 +1175,7 @@ impl pallet_btc_socket_queue::Config for Runtime {
 	type Relayers = RelayManager;
 	type RegistrationPool = BtcRegistrationPool;
 	type Blaze = Blaze;
+	type LightClient = BtcLightClient;
 	type WeightInfo = pallet_btc_socket_queue::weights::SubstrateWeight<Runtime>;
 	type DefaultMaxFeeRate = DefaultMaxFeeRate;
 	type DefaultMaxSocketMessageBytes = DefaultMaxSocketMessageBytes;
@@ -1076,6 +1184,8 @@ impl pallet_btc_socket_queue::Config for Runtime {
 parameter_types! {
 	pub const BitcoinChainId: u32 = 10000;
 	pub const BitcoinNetwork: Network = Network::Bitcoin;
+	/// The relative timelock of the user vault recovery path. 1 year of Bitcoin blocks.
+	pub const RecoveryTimelock: u32 = 52_560;
 	pub const DefaultMultiSigRatio: Percent = Percent::from_percent(60);
 	pub const DefaultMaxFeeRate: u64 = 100;
 	pub const DefaultMaxSocketMessageBytes: u32 = 2 * 1024;
@@ -1089,12 +1199,16 @@ impl pallet_btc_registration_pool::Config for Runtime {
 	type DefaultMultiSigRatio = DefaultMultiSigRatio;
 	type BitcoinChainId = BitcoinChainId;
 	type BitcoinNetwork = BitcoinNetwork;
+	type RecoveryTimelock = RecoveryTimelock;
 	type WeightInfo = pallet_btc_registration_pool::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
 	pub const FeeRateExpiration: u32 = 5 * MINUTES;
 	pub const ToleranceThreshold: u32 = 5;
+	/// Recovery vault UTXOs are swept 1_008 Bitcoin blocks before their recovery path matures.
+	pub const RecoverySweepWindow: u32 = 1_008;
+	pub const RecoverySafetyMargin: u32 = 144;
 }
 
 impl pallet_blaze::Config for Runtime {
@@ -1103,11 +1217,30 @@ impl pallet_blaze::Config for Runtime {
 	type Relayers = RelayManager;
 	type SocketQueue = BtcSocketQueue;
 	type RegistrationPool = BtcRegistrationPool;
+	type LightClient = BtcLightClient;
 	type FeeRateExpiration = FeeRateExpiration;
 	type ToleranceThreshold = ToleranceThreshold;
+	type RecoverySweepWindow = RecoverySweepWindow;
+	type RecoverySafetyMargin = RecoverySafetyMargin;
 	type WeightInfo = pallet_blaze::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxReorgDepth: u32 = 6;
+	pub const HeadersToKeep: u32 = 2_016;
+}
+
+impl pallet_btc_light_client::Config for Runtime {
+	type Signature = EthereumSignature;
+	type Signer = EthereumSigner;
+	type Relayers = RelayManager;
+	type BitcoinNetwork = BitcoinNetwork;
+	type MaxReorgDepth = MaxReorgDepth;
+	type HeadersToKeep = HeadersToKeep;
+	type OnCheckpointSet = (Blaze, BtcSocketQueue);
+	type WeightInfo = pallet_btc_light_client::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub MbmServiceWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
 }
@@ -1245,6 +1378,9 @@ mod runtime {
 	#[runtime::pallet_index(62)]
 	pub type Blaze = pallet_blaze;
 
+	#[runtime::pallet_index(66)]
+	pub type BtcLightClient = pallet_btc_light_client;
+
 	#[runtime::pallet_index(99)]
 	pub type Sudo = pallet_sudo;
 
@@ -6,6 +6,8 @@ use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
 
 use precompile_balance::BalancePrecompile;
+use precompile_bfc_liquid_staking::BfcLiquidStakingPrecompile;
+use precompile_bfc_nomination_pools::BfcNominationPoolsPrecompile;
 use precompile_bfc_offences::BfcOffencesPrecompile;
 use precompile_bfc_staking::BfcStakingPrecompile;
 use precompile_blaze::BlazePrecompile;
@@ -39,6 +41,8 @@ pub type BifrostPrecompilesAt<R> = (
 	PrecompileAt<AddressU64<257>, BtcSocketQueuePrecompile<R>, BifrostPrecompilesChecks>,
 	PrecompileAt<AddressU64<258>, BlazePrecompile<R>, BifrostPrecompilesChecks>,
 	PrecompileAt<AddressU64<1024>, BfcStakingPrecompile<R>, BifrostPrecompilesChecks>,
+	PrecompileAt<AddressU64<1025>, BfcLiquidStakingPrecompile<R>, BifrostPrecompilesChecks>,
+	PrecompileAt<AddressU64<1026>, BfcNominationPoolsPrecompile<R>, BifrostPrecompilesChecks>,
 	PrecompileAt<AddressU64<1280>, BfcOffencesPrecompile<R>, BifrostPrecompilesChecks>,
 	PrecompileAt<AddressU64<2048>, GovernancePrecompile<R>, BifrostPrecompilesChecks>,
 	PrecompileAt<
@@ -320,6 +320,38 @@ export const BFC_STAKING_ABI = [
         "internalType": "struct BfcStaking.candidate_meta_data",
         "name": "",
         "type": "tuple"
+      },
+      {
+        "components": [
+          {
+            "internalType": "bytes",
+            "name": "name",
+            "type": "bytes"
+          },
+          {
+            "internalType": "bytes",
+            "name": "website",
+            "type": "bytes"
+          },
+          {
+            "internalType": "bytes",
+            "name": "contact",
+            "type": "bytes"
+          },
+          {
+            "internalType": "bytes32",
+            "name": "logo_hash",
+            "type": "bytes32"
+          },
+          {
+            "internalType": "bytes",
+            "name": "relayer_endpoint",
+            "type": "bytes"
+          }
+        ],
+        "internalType": "struct BfcStaking.candidate_identity",
+        "name": "",
+        "type": "tuple"
       }
     ],
     "stateMutability": "view",
@@ -613,6 +645,13 @@ export const BFC_STAKING_ABI = [
     "stateMutability": "view",
     "type": "function"
   },
+  {
+    "inputs": [],
+    "name": "clear_candidate_identity",
+    "outputs": [],
+    "stateMutability": "nonpayable",
+    "type": "function"
+  },
   {
     "inputs": [
       {
@@ -1422,6 +1461,39 @@ export const BFC_STAKING_ABI = [
     "stateMutability": "view",
     "type": "function"
   },
+  {
+    "inputs": [
+      {
+        "internalType": "bytes",
+        "name": "name",
+        "type": "bytes"
+      },
+      {
+        "internalType": "bytes",
+        "name": "website",
+        "type": "bytes"
+      },
+      {
+        "internalType": "bytes",
+        "name": "contact",
+        "type": "bytes"
+      },
+      {
+        "internalType": "bytes32",
+        "name": "logo_hash",
+        "type": "bytes32"
+      },
+      {
+        "internalType": "bytes",
+        "name": "relayer_endpoint",
+        "type": "bytes"
+      }
+    ],
+    "name": "set_candidate_identity",
+    "outputs": [],
+    "stateMutability": "nonpayable",
+    "type": "function"
+  },
   {
     "inputs": [
       {
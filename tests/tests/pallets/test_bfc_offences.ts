@@ -202,6 +202,7 @@ describeDevNode('pallet_bfc_offences - simple validator offences', (context) =>
     expect(validatorOffences.offenceCount).equal(1);
     expect(validatorOffences.aggregatedSlashFraction.toString()).equal(new BigNumber(0.5).multipliedBy(10 ** 7).toFixed());
     expect(validatorOffences.offences.length).equal(1);
+    expect(validatorOffences.offences[0].kind).equal('Unresponsive');
     expect(validatorOffences.offences[0].roundIndex).equal(rawCurrentRound.currentRoundIndex.toNumber());
     expect(validatorOffences.offences[0].sessionIndex).equal(rawCurrentRound.currentSessionIndex.toNumber());
     expect(context.web3.utils.hexToNumberString(validatorOffences.offences[0].totalSlash)).equal(new BigNumber(5).multipliedBy(10 ** 18).toFixed());
@@ -238,7 +239,7 @@ describeDevNode('pallet_bfc_offences - simple validator offences', (context) =>
             { method: 'Offence', section: 'offences' },
             { method: 'Slashed', section: 'bfcOffences' },
             { method: 'Slashed', section: 'balances' },
-            { method: 'KickedOut', section: 'bfcStaking' },
+            { method: 'Jailed', section: 'bfcStaking' },
           ],
         );
         expect(success).equal(true);
@@ -281,7 +282,7 @@ describeDevNode('pallet_bfc_offences - simple validator offences', (context) =>
         // check candidate info
         const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
         const candidateState = rawCandidateState.unwrap().toJSON();
-        expect(candidateState.status).has.key('kickedOut');
+        expect(candidateState.status).has.key('jailed');
         expect(new BigNumber(candidateState.bond).eq(new BigNumber(selfBondAfterSlash))).equal(true);
         expect(new BigNumber(candidateState.votingPower).eq(new BigNumber(selfBondAfterSlash))).equal(true);
 
@@ -370,12 +371,35 @@ describeDevNode('pallet_bfc_offences - simple validator offences', (context) =>
     expect(validatorOffences).to.be.null;
   });
 
-  it('should successfully return back to online state', async function () {
+  it('should fail to go online while jailed', async function () {
     await context.polkadotApi.tx.bfcStaking
       .goOnline()
       .signAndSend(baltathar);
     await context.createBlock();
 
+    // check candidate info
+    const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
+    const candidateState = rawCandidateState.unwrap().toJSON();
+    expect(candidateState.status).has.key('jailed');
+
+    const rawJailCount: any = await context.polkadotApi.query.bfcStaking.jailCount(baltathar.address);
+    expect(rawJailCount.toNumber()).equal(1);
+  });
+
+  it('should successfully unjail after the jail ends', async function () {
+    const rawCandidateStateBefore: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
+    const untilRound = rawCandidateStateBefore.unwrap().toJSON().status.jailed.untilRound;
+
+    const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
+    if (rawCurrentRound.currentRoundIndex.toNumber() < untilRound) {
+      await jumpToRound(context, untilRound);
+    }
+
+    await context.polkadotApi.tx.bfcStaking
+      .unjail()
+      .signAndSend(baltathar);
+    await context.createBlock();
+
     // check candidate info
     const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
     const candidateState = rawCandidateState.unwrap().toJSON();
@@ -556,7 +580,7 @@ describeDevNode('pallet_bfc_offences - update bond less pending request #1', (co
             { method: 'Offence', section: 'offences' },
             { method: 'Slashed', section: 'bfcOffences' },
             { method: 'Slashed', section: 'balances' },
-            { method: 'KickedOut', section: 'bfcStaking' },
+            { method: 'Jailed', section: 'bfcStaking' },
           ],
         );
         expect(success).equal(true);
@@ -598,7 +622,7 @@ describeDevNode('pallet_bfc_offences - update bond less pending request #1', (co
         // check candidate info
         const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
         const candidateState = rawCandidateState.unwrap().toJSON();
-        expect(candidateState.status).has.key('kickedOut');
+        expect(candidateState.status).has.key('jailed');
         expect(new BigNumber(candidateState.bond).eq(new BigNumber(selfBondAfterSlash))).equal(true);
         expect(new BigNumber(candidateState.votingPower).eq(new BigNumber(selfBondAfterSlash))).equal(true);
         expect(candidateState.request).is.null;
@@ -801,7 +825,7 @@ describeDevNode('pallet_bfc_offences - update bond less pending request #2', (co
             { method: 'Offence', section: 'offences' },
             { method: 'Slashed', section: 'bfcOffences' },
             { method: 'Slashed', section: 'balances' },
-            { method: 'KickedOut', section: 'bfcStaking' },
+            { method: 'Jailed', section: 'bfcStaking' },
           ],
         );
         expect(success).equal(true);
@@ -813,7 +837,7 @@ describeDevNode('pallet_bfc_offences - update bond less pending request #2', (co
         // check candidate info
         const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
         const candidateState = rawCandidateState.unwrap().toJSON();
-        expect(candidateState.status).has.key('kickedOut');
+        expect(candidateState.status).has.key('jailed');
         expect(new BigNumber(candidateState.bond).eq(new BigNumber(selfBondAfterSlash))).equal(true);
         expect(new BigNumber(candidateState.votingPower).eq(new BigNumber(selfBondAfterSlash))).equal(true);
         expect(candidateState.request).is.not.null;
@@ -967,7 +991,7 @@ describeDevNode('pallet_bfc_offences - minimum bond requirement', (context) => {
           { method: 'Offence', section: 'offences' },
           { method: 'Slashed', section: 'bfcOffences' },
           { method: 'Slashed', section: 'balances' },
-          { method: 'KickedOut', section: 'bfcStaking' },
+          { method: 'Jailed', section: 'bfcStaking' },
         ],
       );
       if (success) {
@@ -978,7 +1002,7 @@ describeDevNode('pallet_bfc_offences - minimum bond requirement', (context) => {
         // check candidate info
         const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
         const candidateState = rawCandidateState.unwrap().toJSON();
-        expect(candidateState.status).has.key('kickedOut');
+        expect(candidateState.status).has.key('jailed');
         expect(candidateState.isSelected).equal(false);
       }
     }
@@ -260,7 +260,7 @@ describeDevNode('pallet_bfc_staking - staking inflations', (context) => {
       min: new BigNumber(10).multipliedBy(10 ** 7).toFixed(),
       ideal: new BigNumber(15).multipliedBy(10 ** 7).toFixed(),
       max: new BigNumber(20).multipliedBy(10 ** 7).toFixed(),
-    }).signAndSend(alithStash);
+    }, 0, 0).signAndSend(alithStash);
     await context.createBlock();
 
     const extrinsicResult = await getExtrinsicResult(context, 'bfcStaking', 'setInflation');
@@ -273,7 +273,7 @@ describeDevNode('pallet_bfc_staking - staking inflations', (context) => {
         min: new BigNumber(10).multipliedBy(10 ** 7).toFixed(),
         ideal: new BigNumber(15).multipliedBy(10 ** 7).toFixed(),
         max: new BigNumber(20).multipliedBy(10 ** 7).toFixed(),
-      }),
+      }, 0, 0),
     ).signAndSend(alith);
     await context.createBlock();
 
@@ -284,6 +284,40 @@ describeDevNode('pallet_bfc_staking - staking inflations', (context) => {
     expect(inflation.annual.ideal.toString()).equal(new BigNumber(15).multipliedBy(10 ** 7).toFixed());
     expect(inflation.annual.max.toString()).equal(new BigNumber(20).multipliedBy(10 ** 7).toFixed());
   });
+
+  it('should fail to set issuance shares exceeding the round issuance', async function () {
+    await context.polkadotApi.tx.sudo.sudo(
+      context.polkadotApi.tx.bfcStaking.setInflation({
+        min: new BigNumber(10).multipliedBy(10 ** 7).toFixed(),
+        ideal: new BigNumber(15).multipliedBy(10 ** 7).toFixed(),
+        max: new BigNumber(20).multipliedBy(10 ** 7).toFixed(),
+      }, 600_000_000, 500_000_000),
+    ).signAndSend(alith);
+    await context.createBlock();
+
+    const rawInflation: any = await context.polkadotApi.query.bfcStaking.inflationConfig();
+    const inflation = rawInflation.toJSON();
+
+    expect(inflation.treasury).equal(0);
+    expect(inflation.burn).equal(0);
+  });
+
+  it('should successfully set treasury and burn shares of the round issuance', async function () {
+    await context.polkadotApi.tx.sudo.sudo(
+      context.polkadotApi.tx.bfcStaking.setInflation({
+        min: new BigNumber(10).multipliedBy(10 ** 7).toFixed(),
+        ideal: new BigNumber(15).multipliedBy(10 ** 7).toFixed(),
+        max: new BigNumber(20).multipliedBy(10 ** 7).toFixed(),
+      }, 100_000_000, 50_000_000),
+    ).signAndSend(alith);
+    await context.createBlock();
+
+    const rawInflation: any = await context.polkadotApi.query.bfcStaking.inflationConfig();
+    const inflation = rawInflation.toJSON();
+
+    expect(inflation.treasury).equal(100_000_000);
+    expect(inflation.burn).equal(50_000_000);
+  });
 });
 
 describeDevNode('pallet_bfc_staking - round configuration', (context) => {
@@ -719,6 +753,74 @@ describeDevNode('pallet_bfc_staking - validator commission', (context) => {
     const currentCommission = rawCurrentCommission.toNumber();
     expect(currentCommission.toString()).equal(commission.toFixed());
   });
+
+  it('should successfully update max commission increase per round', async function () {
+    const maxIncrease = new BigNumber(5).multipliedBy(10 ** 7);
+
+    await context.polkadotApi.tx.sudo.sudo(
+      context.polkadotApi.tx.bfcStaking.setMaxCommissionIncreasePerRound(maxIncrease.toFixed())
+    ).signAndSend(alith);
+    await context.createBlock();
+
+    const rawMaxIncrease: any = await context.polkadotApi.query.bfcStaking.maxCommissionIncreasePerRound();
+    expect(rawMaxIncrease.toNumber().toString()).equal(maxIncrease.toFixed());
+  });
+
+  it('should successfully step a commission increase above the per round limit', async function () {
+    const commission = new BigNumber(80).multipliedBy(10 ** 7);
+    const step = new BigNumber(75).multipliedBy(10 ** 7);
+
+    const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
+    const currentRound = rawCurrentRound.currentRoundIndex.toNumber();
+
+    await context.polkadotApi.tx.bfcStaking
+      .setValidatorCommission(commission.toFixed())
+      .signAndSend(alith);
+    await context.createBlock();
+
+    // the first step is applied in the next round and the remainder is requeued
+    await jumpToRound(context, currentRound + 1);
+    const rawCandidateStateV1: any = await context.polkadotApi.query.bfcStaking.candidateInfo(alith.address);
+    expect(rawCandidateStateV1.unwrap().toJSON().commission.toString()).equal(step.toFixed());
+
+    const rawCommissionSets: any = await context.polkadotApi.query.bfcStaking.delayedCommissionSets(currentRound + 1);
+    const commissionSets = rawCommissionSets.toJSON();
+    expect(commissionSets.some((set: any) => set.who === alith.address)).equal(true);
+
+    await jumpToRound(context, currentRound + 2);
+    const rawCandidateStateV2: any = await context.polkadotApi.query.bfcStaking.candidateInfo(alith.address);
+    expect(rawCandidateStateV2.unwrap().toJSON().commission.toString()).equal(commission.toFixed());
+  });
+
+  it('should fail to register a relayer endpoint for a basic node identity', async function () {
+    await context.polkadotApi.tx.bfcStaking
+      .setCandidateIdentity('baltathar', 'https://baltathar.io', 'ops@baltathar.io', '0x' + '11'.repeat(32), 'wss://relayer.baltathar.io')
+      .signAndSend(baltathar);
+    await context.createBlock();
+
+    const extrinsicResult = await getExtrinsicResult(context, 'bfcStaking', 'setCandidateIdentity');
+    expect(extrinsicResult).equal('RelayerEndpointOnlyForFullTier');
+  });
+
+  it('should successfully register and clear a candidate identity', async function () {
+    await context.polkadotApi.tx.bfcStaking
+      .setCandidateIdentity('alith', 'https://alith.io', 'ops@alith.io', '0x' + '11'.repeat(32), 'wss://relayer.alith.io')
+      .signAndSend(alith);
+    await context.createBlock();
+
+    const rawIdentity: any = await context.polkadotApi.query.bfcStaking.candidateIdentities(alith.address);
+    const identity = rawIdentity.unwrap();
+    expect(identity.name.toUtf8()).equal('alith');
+    expect(identity.relayerEndpoint.unwrap().toUtf8()).equal('wss://relayer.alith.io');
+
+    await context.polkadotApi.tx.bfcStaking
+      .clearCandidateIdentity()
+      .signAndSend(alith);
+    await context.createBlock();
+
+    const rawClearedIdentity: any = await context.polkadotApi.query.bfcStaking.candidateIdentities(alith.address);
+    expect(rawClearedIdentity.isNone).equal(true);
+  });
 });
 
 describeDevNode('pallet_bfc_staking - validator selection', (context) => {
@@ -800,6 +902,26 @@ describeDevNode('pallet_bfc_staking - validator selection', (context) => {
     const rawSelectedBasicCandidates: any = await context.polkadotApi.query.bfcStaking.selectedBasicCandidates();
     expect(rawSelectedBasicCandidates.toJSON().length).equal(0);
   });
+
+  it('should successfully take the validator snapshots after the round transition', async function () {
+    const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
+    const currentRound = rawCurrentRound.currentRoundIndex.toNumber();
+
+    await jumpToRound(context, currentRound + 1);
+
+    const rawPendingSnapshots: any = await context.polkadotApi.query.bfcStaking.pendingValidatorSnapshots();
+    const pendingSnapshots = rawPendingSnapshots.unwrap().toJSON();
+    expect(pendingSnapshots[0]).equal(currentRound + 1);
+    expect(pendingSnapshots[1]).includes(alith.address);
+
+    await context.createBlock();
+
+    const rawPendingSnapshotsAfter: any = await context.polkadotApi.query.bfcStaking.pendingValidatorSnapshots();
+    expect(rawPendingSnapshotsAfter.isNone).equal(true);
+
+    const rawAtStake: any = await context.polkadotApi.query.bfcStaking.atStake(currentRound + 1, alith.address);
+    expect(new BigNumber(rawAtStake.total.toString()).gt(0)).equal(true);
+  });
 });
 
 describeDevNode('pallet_bfc_staking - join candidates', (context) => {
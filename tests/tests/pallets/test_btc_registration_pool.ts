@@ -319,6 +319,115 @@ describeDevNode('pallet_btc_registration_pool - submit_key (1-of-1)', (context)
   });
 });
 
+describeDevNode('pallet_btc_registration_pool - taproot vault (1-of-1)', (context) => {
+  const keyring = new Keyring({ type: 'ethereum' });
+  const alith = keyring.addFromUri(TEST_CONTROLLERS[0].private);
+  const baltathar = keyring.addFromUri(TEST_CONTROLLERS[1].private);
+  const alithRelayer = keyring.addFromUri(TEST_RELAYERS[0].private);
+  let alithNonce: number;
+
+  beforeEach(async function () {
+    const alithAccount = await context.polkadotApi.query.system.account(alith.address);
+    alithNonce = alithAccount.nonce.toNumber();
+  });
+
+  it('should successfully set vault descriptor type', async function () {
+    await context.polkadotApi.tx.sudo.sudo(
+        context.polkadotApi.tx.btcRegistrationPool.setVaultDescriptorType('Tr')
+    ).signAndSend(alith, { nonce: alithNonce++ });
+    await context.createBlock();
+
+    const rawDescriptorType: any = await context.polkadotApi.query.btcRegistrationPool.vaultDescriptorType();
+    expect(rawDescriptorType.toJSON()).eq('Tr');
+  });
+
+  it('should successfully generate a taproot vault address', async function () {
+    const baltatharAccount = await context.polkadotApi.query.system.account(baltathar.address);
+    const refund = 'bcrt1qurj4xpaw95jlr28lqhankfdqce7tatgkeqrk9q';
+
+    await context.polkadotApi.tx.btcRegistrationPool.requestVault(refund).signAndSend(baltathar, { nonce: baltatharAccount.nonce.toNumber() });
+    await context.createBlock();
+
+    const pubKey = '0x02c56c0cf38df8708f2e5725102f87a1d91f9356b0b7ebc4f6cafb396684e143b4';
+    const keySubmission = {
+      authorityId: alithRelayer.address,
+      who: baltathar.address,
+      pubKey,
+      poolRound: await getCurrentRound(context)
+    };
+    const signature = '0xd19701003fb3b0ad88cad82c85da2bf01b1e6855c0636384fd23ba061ec0fbc077c386a05f013f3f0f53faa5fe59f977cc557a7176ba00acfc7655a6767a121d1b';
+
+    await context.polkadotApi.tx.btcRegistrationPool.submitVaultKey(keySubmission, signature).send();
+    await context.createBlock();
+
+    const rawRegisteredBitcoinPair: any = await context.polkadotApi.query.btcRegistrationPool.registrationPool(await getCurrentRound(context), baltathar.address);
+    const registeredBitcoinPair = rawRegisteredBitcoinPair.toHuman();
+
+    const vault = registeredBitcoinPair.vault.address['Generated'];
+    expect(vault.startsWith('bcrt1p')).is.true;
+
+    const rawBondedDescriptor: any = await context.polkadotApi.query.btcRegistrationPool.bondedDescriptor(await getCurrentRound(context), vault);
+    const bondedDescriptor = rawBondedDescriptor.toHuman();
+    expect(bondedDescriptor.startsWith('tr(')).is.true;
+  });
+});
+
+describeDevNode('pallet_btc_registration_pool - recovery path (1-of-1)', (context) => {
+  const keyring = new Keyring({ type: 'ethereum' });
+  const baltathar = keyring.addFromUri(TEST_CONTROLLERS[1].private);
+  const alithRelayer = keyring.addFromUri(TEST_RELAYERS[0].private);
+
+  const recoveryPubKey = '0x0200c708c3eef9658fd000b3262a5ddc4821f2adcd3f777eb3b2d002dcc04efb87';
+
+  it('should fail to request a vault due to invalid recovery public key', async function () {
+    const refund = 'bcrt1qurj4xpaw95jlr28lqhankfdqce7tatgkeqrk9q';
+    const invalidPubKey = '0x04' + '00'.repeat(32);
+
+    await context.polkadotApi.tx.btcRegistrationPool.requestVaultWithRecovery(refund, invalidPubKey).signAndSend(baltathar);
+    await context.createBlock();
+
+    const rawRecoveryPath: any = await context.polkadotApi.query.btcRegistrationPool.recoveryPaths(await getCurrentRound(context), baltathar.address);
+    expect(rawRecoveryPath.isNone).is.true;
+  });
+
+  it('should successfully request a vault with a recovery path', async function () {
+    const refund = 'bcrt1qurj4xpaw95jlr28lqhankfdqce7tatgkeqrk9q';
+
+    await context.polkadotApi.tx.btcRegistrationPool.requestVaultWithRecovery(refund, recoveryPubKey).signAndSend(baltathar);
+    await context.createBlock();
+
+    const rawRecoveryPath: any = await context.polkadotApi.query.btcRegistrationPool.recoveryPaths(await getCurrentRound(context), baltathar.address);
+    const recoveryPath = rawRecoveryPath.unwrap().toJSON();
+    expect(recoveryPath.pubKey).eq(recoveryPubKey);
+    expect(recoveryPath.timelock).eq(144);
+  });
+
+  it('should successfully generate a vault address with a recovery branch', async function () {
+    const pubKey = '0x02c56c0cf38df8708f2e5725102f87a1d91f9356b0b7ebc4f6cafb396684e143b4';
+    const keySubmission = {
+      authorityId: alithRelayer.address,
+      who: baltathar.address,
+      pubKey,
+      poolRound: await getCurrentRound(context)
+    };
+    const signature = '0xd19701003fb3b0ad88cad82c85da2bf01b1e6855c0636384fd23ba061ec0fbc077c386a05f013f3f0f53faa5fe59f977cc557a7176ba00acfc7655a6767a121d1b';
+
+    await context.polkadotApi.tx.btcRegistrationPool.submitVaultKey(keySubmission, signature).send();
+    await context.createBlock();
+
+    const rawRegisteredBitcoinPair: any = await context.polkadotApi.query.btcRegistrationPool.registrationPool(await getCurrentRound(context), baltathar.address);
+    const registeredBitcoinPair = rawRegisteredBitcoinPair.toHuman();
+
+    const vault = registeredBitcoinPair.vault.address['Generated'];
+    expect(vault.startsWith('bcrt1q')).is.true;
+
+    const rawBondedDescriptor: any = await context.polkadotApi.query.btcRegistrationPool.bondedDescriptor(await getCurrentRound(context), vault);
+    const bondedDescriptor = rawBondedDescriptor.toHuman();
+    expect(bondedDescriptor.startsWith('wsh(or_d(multi(1,')).is.true;
+    expect(bondedDescriptor).contains('older(144)');
+  });
+});
+
 describeDevNode('pallet_btc_registration_pool - submit_key (2-of-2)', (context) => {
   const keyring = new Keyring({ type: 'ethereum' });
   const alith = keyring.addFromUri(TEST_CONTROLLERS[0].private);
@@ -241,6 +241,10 @@ describeDevNode('pallet_relay_manager - relayer heartbeat', (context) => {
     const relayerState = rawRelayerState.unwrap().toJSON();
     expect(relayerState.status).equal('Idle');
 
+    const rawRelayerScore: any = await context.polkadotApi.query.relayManager.relayerScores(rawCurrentRound.currentRoundIndex.toNumber(), alithRelayer.address);
+    const relayerScore = rawRelayerScore.toJSON();
+    expect(relayerScore.missedHeartbeats).to.be.greaterThan(0);
+
     const rawValidatorOffences: any = await context.polkadotApi.query.bfcOffences.validatorOffences(alith.address);
     const validatorOffences = rawValidatorOffences.unwrap().toJSON();
     expect(validatorOffences.latestOffenceRoundIndex).equal(rawCurrentRound.currentRoundIndex.toNumber());
@@ -248,6 +252,7 @@ describeDevNode('pallet_relay_manager - relayer heartbeat', (context) => {
     expect(validatorOffences.offenceCount).equal(1);
     expect(validatorOffences.aggregatedSlashFraction.toString()).equal(new BigNumber(1).multipliedBy(10 ** 7).toFixed());
     expect(validatorOffences.offences.length).equal(1);
+    expect(validatorOffences.offences[0].kind).equal('RelayerHeartbeat');
     expect(validatorOffences.offences[0].roundIndex).equal(rawCurrentRound.currentRoundIndex.toNumber());
     expect(validatorOffences.offences[0].sessionIndex).equal(rawCurrentRound.currentSessionIndex.toNumber());
     expect(context.web3.utils.hexToNumberString(validatorOffences.offences[0].totalSlash)).equal(new BigNumber(10).multipliedBy(10 ** 18).toFixed());
@@ -274,8 +279,9 @@ describeDevNode('pallet_relay_manager - relayer heartbeat', (context) => {
   });
 
   it('should refresh offences due to active heartbeats', async function () {
-    const rawOffenceExpiration: any = await context.polkadotApi.query.bfcOffences.offenceExpirationInSessions();
-    for (let idx = 0; idx < rawOffenceExpiration.toNumber(); idx++) {
+    const rawOffenceKindConfig: any = await context.polkadotApi.query.bfcOffences.offenceKindConfigs('RelayerHeartbeat');
+    const offenceExpiration = rawOffenceKindConfig.expirationInSessions.toNumber();
+    for (let idx = 0; idx < offenceExpiration; idx++) {
       const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
       const currentSession = rawCurrentRound.currentSessionIndex.toNumber();
 
@@ -422,8 +428,9 @@ describeDevNode('pallet_relay_manager - relayer kick out', (context) => {
   });
 
   it('should be kicked out due to inactive heartbeats - baltathar', async function () {
-    const rawMaximumOffenceCount: any = await context.polkadotApi.query.bfcOffences.fullMaximumOffenceCount();
-    for (let idx = 0; idx < rawMaximumOffenceCount.toNumber(); idx++) {
+    const rawOffenceKindConfig: any = await context.polkadotApi.query.bfcOffences.offenceKindConfigs('RelayerHeartbeat');
+    const maximumOffenceCount = rawOffenceKindConfig.fullMaximumOffenceCount.toNumber();
+    for (let idx = 0; idx < maximumOffenceCount; idx++) {
       const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
       const currentSession = rawCurrentRound.currentSessionIndex.toNumber();
 
@@ -445,7 +452,7 @@ describeDevNode('pallet_relay_manager - relayer kick out', (context) => {
           { method: 'Offence', section: 'offences' },
           { method: 'Slashed', section: 'bfcOffences' },
           { method: 'Slashed', section: 'balances' },
-          { method: 'KickedOut', section: 'bfcStaking' },
+          { method: 'Jailed', section: 'bfcStaking' },
         ],
       );
       if (success) {
@@ -459,7 +466,7 @@ describeDevNode('pallet_relay_manager - relayer kick out', (context) => {
 
         const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
         const candidateState = rawCandidateState.unwrap().toJSON();
-        expect(candidateState.status).has.key('kickedOut');
+        expect(candidateState.status).has.key('jailed');
         break;
       } else {
         const success = await isEventTriggered(
@@ -603,8 +610,9 @@ describeDevNode('pallet_relay_manager - leave request cancelled', (context) => {
   });
 
   it('should be kicked out and prior leave request cancelled - baltathar', async function () {
-    const rawMaximumOffenceCount: any = await context.polkadotApi.query.bfcOffences.fullMaximumOffenceCount();
-    for (let idx = 0; idx < rawMaximumOffenceCount.toNumber(); idx++) {
+    const rawOffenceKindConfig: any = await context.polkadotApi.query.bfcOffences.offenceKindConfigs('RelayerHeartbeat');
+    const maximumOffenceCount = rawOffenceKindConfig.fullMaximumOffenceCount.toNumber();
+    for (let idx = 0; idx < maximumOffenceCount; idx++) {
       const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
       const currentSession = rawCurrentRound.currentSessionIndex.toNumber();
 
@@ -626,7 +634,7 @@ describeDevNode('pallet_relay_manager - leave request cancelled', (context) => {
           { method: 'Offence', section: 'offences' },
           { method: 'Slashed', section: 'bfcOffences' },
           { method: 'Slashed', section: 'balances' },
-          { method: 'KickedOut', section: 'bfcStaking' },
+          { method: 'Jailed', section: 'bfcStaking' },
         ],
       );
       if (success) {
@@ -640,7 +648,7 @@ describeDevNode('pallet_relay_manager - leave request cancelled', (context) => {
 
         const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
         const candidateState = rawCandidateState.unwrap().toJSON();
-        expect(candidateState.status).has.key('kickedOut');
+        expect(candidateState.status).has.key('jailed');
       }
     }
   });
@@ -765,8 +773,9 @@ describeDevNode('pallet_relay_manager - set kicked out relayer address', (contex
   });
 
   it('should be kicked out and apply relayer address update on next round', async function () {
-    const rawMaximumOffenceCount: any = await context.polkadotApi.query.bfcOffences.fullMaximumOffenceCount();
-    for (let idx = 0; idx < rawMaximumOffenceCount.toNumber(); idx++) {
+    const rawOffenceKindConfig: any = await context.polkadotApi.query.bfcOffences.offenceKindConfigs('RelayerHeartbeat');
+    const maximumOffenceCount = rawOffenceKindConfig.fullMaximumOffenceCount.toNumber();
+    for (let idx = 0; idx < maximumOffenceCount; idx++) {
       const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
       const currentSession = rawCurrentRound.currentSessionIndex.toNumber();
 
@@ -788,7 +797,7 @@ describeDevNode('pallet_relay_manager - set kicked out relayer address', (contex
           { method: 'Offence', section: 'offences' },
           { method: 'Slashed', section: 'bfcOffences' },
           { method: 'Slashed', section: 'balances' },
-          { method: 'KickedOut', section: 'bfcStaking' },
+          { method: 'Jailed', section: 'bfcStaking' },
         ],
       );
       if (success) {
@@ -814,7 +823,7 @@ describeDevNode('pallet_relay_manager - set kicked out relayer address', (contex
 
         const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(baltathar.address);
         const candidateState = rawCandidateState.unwrap().toJSON();
-        expect(candidateState.status).has.key('kickedOut');
+        expect(candidateState.status).has.key('jailed');
       }
     }
 
@@ -831,7 +840,7 @@ describeDevNode('pallet_relay_manager - set kicked out relayer address', (contex
 
     const rawCandidateState: any = await context.polkadotApi.query.bfcStaking.candidateInfo(relayerStateB.controller);
     const candidateState = rawCandidateState.unwrap().toJSON();
-    expect(candidateState.status).has.key('kickedOut');
+    expect(candidateState.status).has.key('jailed');
   });
 }, true);
 
@@ -893,3 +902,69 @@ describeDevNode('pallet_relay_manager - controller and relayer address update',
     expect(relayerState.controller).equal(newController.address);
   });
 });
+
+describeDevNode('pallet_relay_manager - relayer version requirement', (context) => {
+  const keyring = new Keyring({ type: 'ethereum' });
+  const alith = keyring.addFromUri(TEST_CONTROLLERS[0].private);
+  const alithRelayer = keyring.addFromUri(TEST_RELAYERS[0].private);
+
+  const specVersion = '0x0000000000000000000000000000000000000000000000000000000000000001';
+
+  it('should successfully set relayer version requirement', async function () {
+    await context.polkadotApi.tx.sudo.sudo(
+      context.polkadotApi.tx.relayManager.setRelayerVersionRequirement(2, [specVersion]),
+    ).signAndSend(alith);
+    await context.createBlock();
+
+    const rawCurrentRound: any = await context.polkadotApi.query.bfcStaking.round();
+    const currentRound = rawCurrentRound.currentRoundIndex.toNumber();
+
+    const minImplVersion: any = await context.polkadotApi.query.relayManager.minRelayerImplVersion();
+    expect(minImplVersion.toNumber()).equal(2);
+    const acceptedSpecVersions: any = await context.polkadotApi.query.relayManager.acceptedSpecVersions();
+    expect(acceptedSpecVersions.toJSON()).to.include(specVersion);
+    const enforcedAt: any = await context.polkadotApi.query.relayManager.relayerVersionEnforcedAt();
+    expect(enforcedAt.toJSON()).equal(currentRound + 1);
+  });
+
+  it('should flag outdated relayer on heartbeat', async function () {
+    await context.polkadotApi.tx.relayManager.heartbeatV2(1, specVersion)
+      .signAndSend(alithRelayer);
+    const block = await context.createBlock();
+
+    const success = await isEventTriggered(
+      context,
+      block.block.hash,
+      [
+        { method: 'RelayerOutdated', section: 'relayManager' },
+      ],
+    );
+    expect(success).equal(true);
+
+    const outdatedRelayers: any = await context.polkadotApi.query.relayManager.outdatedRelayers();
+    expect(outdatedRelayers.toJSON()).to.include(alithRelayer.address);
+  });
+
+  it('should unflag upgraded relayer after enforcement', async function () {
+    const enforcedAt: any = await context.polkadotApi.query.relayManager.relayerVersionEnforcedAt();
+    await jumpToRound(context, enforcedAt.toJSON());
+
+    await context.polkadotApi.tx.relayManager.heartbeatV2(2, specVersion)
+      .signAndSend(alithRelayer);
+    const block = await context.createBlock();
+
+    const success = await isEventTriggered(
+      context,
+      block.block.hash,
+      [
+        { method: 'RelayerUpgraded', section: 'relayManager' },
+      ],
+    );
+    expect(success).equal(true);
+
+    const outdatedRelayers: any = await context.polkadotApi.query.relayManager.outdatedRelayers();
+    expect(outdatedRelayers.toJSON()).to.not.include(alithRelayer.address);
+    const majority: any = await context.polkadotApi.query.relayManager.majority();
+    expect(majority.toNumber()).equal(1);
+  });
+});
@@ -6,6 +6,7 @@ import { callPrecompile } from '../transactions';
 
 const SELECTORS = {
   maximum_offence_count: '42caa150',
+  offence_kind_config: '6d1eb59c',
   validator_offence: 'c63c3f8a',
   validator_offences: '2962bb0b',
 };
@@ -31,6 +32,40 @@ describeDevNode('precompile_bfc_offences - precompile view functions', (context)
     expect(Number(decoded_maximum_offence_count[0])).equal(5);
     expect(Number(decoded_maximum_offence_count[1])).equal(3);
 
+    const offence_kind_config = await callPrecompile(
+      context,
+      alith.public,
+      PRECOMPILE_ADDRESS,
+      SELECTORS,
+      'offence_kind_config',
+      ['0x2'],
+    );
+    const decoded_offence_kind_config: any = context.web3.eth.abi.decodeParameters(
+      ['uint256', 'uint256', 'uint256', 'uint256'],
+      offence_kind_config,
+    );
+    expect(Number(decoded_offence_kind_config[0])).equal(5);
+    expect(Number(decoded_offence_kind_config[1])).equal(3);
+    expect(Number(decoded_offence_kind_config[2])).equal(10 ** 9);
+    expect(Number(decoded_offence_kind_config[3])).equal(5);
+
+    // equivocations are slashed as soon as they are reported
+    const aura_equivocation_config = await callPrecompile(
+      context,
+      alith.public,
+      PRECOMPILE_ADDRESS,
+      SELECTORS,
+      'offence_kind_config',
+      ['0x3'],
+    );
+    const decoded_aura_equivocation_config: any = context.web3.eth.abi.decodeParameters(
+      ['uint256', 'uint256', 'uint256', 'uint256'],
+      aura_equivocation_config,
+    );
+    expect(Number(decoded_aura_equivocation_config[0])).equal(0);
+    expect(Number(decoded_aura_equivocation_config[1])).equal(0);
+    expect(Number(decoded_aura_equivocation_config[3])).equal(20);
+
     const validator_offence = await callPrecompile(
       context,
       alith.public,
@@ -40,10 +75,13 @@ describeDevNode('precompile_bfc_offences - precompile view functions', (context)
       [alith.public],
     );
     const decoded_validator_offence: any = context.web3.eth.abi.decodeParameters(
-      ['tuple(address,uint256,uint256,uint256)'],
+      ['tuple(address,uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256)'],
       validator_offence,
     )[0];
     expect(decoded_validator_offence[0]).equal(alith.public);
+    expect(Number(decoded_validator_offence[3])).equal(0);
+    expect(Number(decoded_validator_offence[7])).equal(0);
+    expect(Number(decoded_validator_offence[8])).equal(0);
 
     const validator_offences = await callPrecompile(
       context,
@@ -56,7 +94,7 @@ describeDevNode('precompile_bfc_offences - precompile view functions', (context)
       ],
     );
     const decoded_validator_offences: any = context.web3.eth.abi.decodeParameters(
-      ['address[]', 'uint256[]', 'uint256[]', 'uint256[]'],
+      ['address[]', 'uint256[]', 'uint256[]', 'uint256[]', 'uint256[]', 'uint256[]', 'uint256[]', 'uint256[]', 'uint256[]'],
       validator_offences,
     );
     expect(decoded_validator_offences[0][0]).equal(alith.public);
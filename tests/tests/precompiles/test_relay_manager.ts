@@ -22,6 +22,8 @@ const SELECTORS = {
   previous_majority: 'ea6ce574',
   relayer_state: '3f4e4fae',
   relayer_states: 'a77293f0',
+  relayer_score: 'a0afaab5',
+  relayer_version_requirement: '1727a770',
   // Common storage getters
   latest_round: '6f31dd98',
   // Relayer dispatchable methods
@@ -232,5 +234,34 @@ describeDevNode('precompile_relay_manager - precompile view functions', (context
       relayer_states,
     );
     expect(decoded_relayer_states[0][0]).equal(alithRelayer.public);
+
+    const relayer_score = await callPrecompile(
+      context,
+      alith.public,
+      PRECOMPILE_ADDRESS,
+      SELECTORS,
+      'relayer_score',
+      [alithRelayer.public, '0x1'],
+    );
+    const decoded_relayer_score: any = context.web3.eth.abi.decodeParameters(
+      ['uint256', 'uint256', 'uint256', 'uint256', 'uint256'],
+      relayer_score,
+    );
+    expect(Number(decoded_relayer_score[4])).lessThanOrEqual(10 ** 9);
+
+    const relayer_version_requirement = await callPrecompile(
+      context,
+      alith.public,
+      PRECOMPILE_ADDRESS,
+      SELECTORS,
+      'relayer_version_requirement',
+      [],
+    );
+    const decoded_relayer_version_requirement: any = context.web3.eth.abi.decodeParameters(
+      ['uint256', 'bytes32[]', 'uint256'],
+      relayer_version_requirement,
+    );
+    expect(Number(decoded_relayer_version_requirement[0])).equal(0);
+    expect(decoded_relayer_version_requirement[1].length).equal(0);
   });
 });